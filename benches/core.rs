@@ -0,0 +1,75 @@
+use chi_tui::bench;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+fn flatten(c: &mut Criterion) {
+    let mut g = c.benchmark_group("flatten_nodes");
+    let mut menu = bench::menu(50, 200);
+    g.bench_function("50x200 rebuild", |b| b.iter(|| black_box(menu.flatten())));
+    g.bench_function("50x200 cached", |b| {
+        b.iter(|| black_box(menu.flatten_cached()))
+    });
+    g.finish();
+}
+
+fn viewer(c: &mut Criterion) {
+    let mut g = c.benchmark_group("result_viewer");
+    let value = bench::payload(5_000);
+    g.bench_function("pretty 5k rows", |b| {
+        b.iter(|| black_box(bench::pretty(&value)))
+    });
+    g.bench_function("first frame 5k rows", |b| {
+        b.iter_batched(
+            || bench::viewer(value.clone(), 120, 40),
+            |mut v| v.draw(),
+            BatchSize::LargeInput,
+        )
+    });
+    let mut v = bench::viewer(value.clone(), 120, 40);
+    v.draw();
+    g.bench_function("page down 5k rows", |b| b.iter(|| v.page_down()));
+    g.finish();
+}
+
+fn update(c: &mut Criterion) {
+    let mut g = c.benchmark_group("update");
+    let rows = bench::payload(1_000)
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let mut app = bench::app(50);
+    g.bench_function("loaded panel 1k rows", |b| {
+        b.iter_batched(
+            || rows.clone(),
+            |rows| black_box(app.loaded_panel(rows)),
+            BatchSize::LargeInput,
+        )
+    });
+    g.bench_function("loaded menu 1k rows", |b| {
+        b.iter_batched(
+            || rows.clone(),
+            |rows| black_box(app.loaded_menu(rows)),
+            BatchSize::LargeInput,
+        )
+    });
+    g.finish();
+}
+
+fn watchdog(c: &mut Criterion) {
+    let mut g = c.benchmark_group("watchdog");
+    let lines: Vec<String> = (0..20_000)
+        .map(|i| format!("2024-06-01T12:00:00Z INFO request {i} served in 12ms"))
+        .collect();
+    g.throughput(criterion::Throughput::Elements(lines.len() as u64));
+    g.bench_function("ring buffer 20k lines", |b| {
+        b.iter(|| black_box(bench::watchdog_lines(&lines).len()))
+    });
+    let full = bench::watchdog_lines(&lines);
+    g.throughput(criterion::Throughput::Elements(40));
+    g.bench_function("visible tail of a full buffer", |b| {
+        b.iter(|| black_box(bench::watchdog_tail(&full, 40)))
+    });
+    g.finish();
+}
+
+criterion_group!(benches, flatten, viewer, update, watchdog);
+criterion_main!(benches);
@@ -1,3 +1,5 @@
+use crate::chi_core::envelope::Envelope;
+use crate::chi_core::slots::SlotTarget;
 use crate::model::MenuItem;
 use crate::ui::{AppState, LoadOutcome};
 use serde_json::Value as JsonValue;
@@ -10,6 +12,11 @@ pub enum AppMsg {
         val: JsonValue,
     },
     RefreshMenu(MenuItem),
+    // `[` / `]`: load the previous / next page of the paginated list `key`
+    TurnPage {
+        key: String,
+        forward: bool,
+    },
     RefreshChild {
         key: String,
         val: JsonValue,
@@ -35,7 +42,7 @@ pub enum AppMsg {
         outcome: Result<LoadOutcome, String>,
     },
     LoadedFormOptions {
-        key: String,
+        field: String,
         outcome: Result<LoadOutcome, String>,
     },
     StreamProgress {
@@ -46,6 +53,43 @@ pub enum AppMsg {
         result: Option<JsonValue>,
         err: Option<String>,
     },
+    // The running stream was cancelled (Esc / Ctrl+X) and its process killed
+    StreamCancelled,
+    // A scheduled job finished
+    ScheduledDone {
+        id: u64,
+        outcome: Result<LoadOutcome, String>,
+    },
+    // Startup check `index` finished
+    PreflightDone {
+        index: usize,
+        outcome: Result<LoadOutcome, String>,
+    },
+    // `auth:` session check at startup, and the end of a login run
+    AuthChecked {
+        outcome: Result<LoadOutcome, String>,
+    },
+    AuthLoginDone {
+        outcome: Result<LoadOutcome, String>,
+    },
+    // The `auth_retry:` refresh command finished
+    AuthRefreshed {
+        outcome: Result<LoadOutcome, String>,
+    },
+    // One row of a batch form submission finished
+    BatchRowDone {
+        index: usize,
+        outcome: Result<LoadOutcome, String>,
+    },
+    // The `update_cmd` of an inline cell edit in Pane B finished
+    RowUpdated {
+        outcome: Result<LoadOutcome, String>,
+    },
+    // The left-menu selection published a new value into a named slot (`publish:`)
+    Publish {
+        slot: String,
+        value: String,
+    },
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -73,6 +117,8 @@ pub enum Effect {
     SubmitForm {
         pane: super::ui::PanelPane,
         cmdline: String,
+        // `mutex:` of the item that opened the form
+        mutex: Option<String>,
     },
     CancelForm {
         pane: super::ui::PanelPane,
@@ -88,13 +134,491 @@ pub enum Effect {
         level: crate::ui::ToastLevel,
         seconds: u64,
     },
+    // Run the `auth:` session check / login command
+    CheckAuth,
+    Login,
+    // Run the `auth_retry:` refresh command
+    RefreshAuth,
+    // Submit a form once per (row index, command), one after another
+    SubmitBatch {
+        rows: Vec<(usize, String)>,
+        mutex: Option<String>,
+    },
+    // Run the `update_cmd` of an inline cell edit
+    UpdateRow {
+        cmdline: String,
+    },
+    // Load a command into a subpane of the nested panel in Pane B
+    LoadNestedCmd {
+        subpane: super::ui::PanelPane,
+        cmdline: String,
+    },
+    // Re-run a startup check (state.preflight index)
+    RunPreflight {
+        index: usize,
+    },
+    // Fire-and-forget `on_enter_cmd`/`on_leave_cmd`; the outcome is logged
+    RunHook {
+        label: String,
+        cmdline: String,
+    },
+    // Command effect (RunStream/LoadPanelCmd) serialized by a named mutex
+    Guarded {
+        mutex: String,
+        effect: Box<Effect>,
+    },
+    // Enter a menu item or child again (reload of a crashed widget)
+    Enter(crate::ui::RetryTarget),
+    // Select and open the menu item with this id (call-to-action of an empty state)
+    OpenItem(String),
+    // Run the `when_cmd` / `enabled_cmd` of the current config's items again
+    CheckConditions,
+}
+
+// New Pane B content replaces the watched one; templated commands behind it are watched
+fn watch_slots(
+    state: &mut AppState,
+    effects: &[Effect],
+    command: Option<String>,
+    pane_a: Option<String>,
+    pane_b: Option<String>,
+) {
+    use crate::chi_core::slots::uses_slots;
+    let loads_b: Vec<&String> = effects
+        .iter()
+        .filter_map(|e| match e {
+            Effect::LoadPanelCmd {
+                pane: super::ui::PanelPane::B,
+                cmdline,
+            } => Some(cmdline),
+            _ => None,
+        })
+        .collect();
+    let nested = matches!(
+        state.panel.as_ref().map(|ps| &ps.b_content),
+        Some(super::ui::PaneContent::Panel(_))
+    ) && (pane_a.is_some() || pane_b.is_some());
+    if loads_b.is_empty() && !nested {
+        return;
+    }
+    state.slots.clear_watches();
+    for template in [command, pane_b.clone()].into_iter().flatten() {
+        if uses_slots(&template) && loads_b.contains(&&state.slots.fill(&template)) {
+            state.slots.watch(SlotTarget::PaneB, &template);
+        }
+    }
+    if nested {
+        for (sub, template) in [
+            (super::ui::PanelPane::A, pane_a),
+            (super::ui::PanelPane::B, pane_b),
+        ] {
+            if let Some(t) = template.filter(|t| uses_slots(t)) {
+                state.slots.watch(SlotTarget::Nested(sub), &t);
+            }
+        }
+    }
+}
+
+// `${selected.<path>}` in the commands of an item (and its Pane A widget spec)
+fn fill_selected(mi: &mut MenuItem, sel: &JsonValue) {
+    use crate::services::interpolate::{selected, selected_in_value};
+    for cmd in [
+        &mut mi.command,
+        &mut mi.pane_a_cmd,
+        &mut mi.pane_b_cmd,
+        &mut mi.external_check_cmd,
+        &mut mi.external_kill_cmd,
+        &mut mi.on_panic_exit_cmd,
+        &mut mi.on_enter_cmd,
+        &mut mi.on_leave_cmd,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        *cmd = selected(cmd, sel);
+    }
+    for cmd in mi.commands.iter_mut().flatten() {
+        *cmd = selected(cmd, sel);
+    }
+    if let Some(spec) = &mut mi.pane_a_widget {
+        selected_in_value(spec, sel);
+    }
+}
+
+// JSON output of a finished command, checked for a pushed banner (`_chi: { banner }`)
+fn command_output(msg: &AppMsg) -> Option<&JsonValue> {
+    match msg {
+        AppMsg::LoadedMenu { outcome, .. }
+        | AppMsg::LoadedChild { outcome, .. }
+        | AppMsg::LoadedPanel { outcome, .. }
+        | AppMsg::LoadedNested { outcome, .. }
+        | AppMsg::LoadedSubmitForm { outcome, .. }
+        | AppMsg::ScheduledDone { outcome, .. }
+        | AppMsg::RowUpdated { outcome } => match outcome {
+            Ok(LoadOutcome::Fallback(v)) => Some(v),
+            _ => None,
+        },
+        AppMsg::StreamDone { result, .. } => result.as_ref(),
+        _ => None,
+    }
 }
 
 pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
+    if let Some(pushed) = command_output(&msg).and_then(crate::widgets::notice::from_envelope) {
+        let now = crate::services::clock::unix_secs(std::time::SystemTime::now());
+        match pushed.and_then(|spec| crate::widgets::notice::Notice::from_spec(&spec, "pushed")) {
+            Ok(n) => crate::widgets::notice::push(&mut state.notices, n, now),
+            Err(e) => state.dbg(e),
+        }
+    }
+    // Items hidden or disabled by their `when_cmd` / `enabled_cmd` stay closed
+    if let AppMsg::EnterMenu(mi) = &msg {
+        if let Some(text) = state.conditions.refusal(mi) {
+            return vec![Effect::ShowToast {
+                text,
+                level: crate::ui::ToastLevel::Error,
+                seconds: 3,
+            }];
+        }
+    }
+    // Items blocked by a failing startup check stay closed; the check is retried
+    if let AppMsg::EnterMenu(mi) = &msg {
+        if let Some(index) = state.preflight.iter().position(|p| p.blocks(&mi.id)) {
+            let check = &state.preflight[index].check;
+            let hint = check
+                .remediation
+                .as_deref()
+                .map(|r| format!(" — {r}"))
+                .unwrap_or_default();
+            return vec![
+                Effect::ShowToast {
+                    text: format!("{} is blocked: '{}' failed{hint}", mi.title, check.title),
+                    level: crate::ui::ToastLevel::Error,
+                    seconds: 4,
+                },
+                Effect::RunPreflight { index },
+            ];
+        }
+    }
+    // Opening another item over unsaved Pane B input asks first (items that only expand
+    // in the menu leave Pane B alone)
+    let target = match &msg {
+        AppMsg::EnterMenu(mi) if !crate::ui::is_autoload(mi) => {
+            Some(crate::ui::RetryTarget::Menu(mi.clone()))
+        }
+        AppMsg::EnterChild { key, val } if !crate::ui::is_autoload_value(val) => {
+            Some(crate::ui::RetryTarget::Child {
+                key: key.clone(),
+                val: val.clone(),
+            })
+        }
+        _ => None,
+    };
+    if let Some(target) = target.clone() {
+        if crate::ui::guard_nav(state, crate::ui::NavTarget::Enter(target)) {
+            return Vec::new();
+        }
+    }
+    // A save started from the unsaved-changes dialog finished: (succeeded, the result
+    // replaced the form)
+    let saved = match &msg {
+        AppMsg::LoadedSubmitForm { outcome, .. } => Some((
+            matches!(
+                outcome,
+                Ok(LoadOutcome::Fallback(v)) if crate::widgets::form_batch::envelope_error(v).is_none()
+            ),
+            true,
+        )),
+        AppMsg::RowUpdated { outcome } => Some((outcome.is_ok(), false)),
+        _ => None,
+    };
+    // Items whose program is not installed stay closed; Enter checks again in case it was
+    if let AppMsg::EnterMenu(mi) = &msg {
+        let key = crate::nav::keys::menu_key(mi);
+        if state.missing_commands.remove(&key).is_some() {
+            if let Some(program) = crate::ui::item_missing_program(mi) {
+                state.missing_commands.insert(key, program);
+                let label = crate::ui::missing_label(state, mi).unwrap_or_default();
+                return vec![Effect::ShowToast {
+                    text: format!("{} is unavailable — {label}", mi.title),
+                    level: crate::ui::ToastLevel::Error,
+                    seconds: 4,
+                }];
+            }
+        }
+    }
+    // Items with `cooldown_secs:` refuse to re-run until the cooldown expires
+    let mut cooldown: Option<(String, u64)> = None;
+    if let AppMsg::EnterMenu(mi) = &msg {
+        if let Some(left) = super::ui::cooldown_left(state, mi) {
+            return vec![Effect::ShowToast {
+                text: format!("{} is cooling down ({left}s left)", mi.title),
+                level: crate::ui::ToastLevel::Info,
+                seconds: 2,
+            }];
+        }
+        if let Some(secs) = mi.cooldown_secs.filter(|s| *s > 0) {
+            cooldown = Some((crate::nav::keys::menu_key(mi), secs));
+        }
+    }
+    // Remember what opened Pane B or the stream so its failure can be retried
+    if target.is_some() {
+        state.pane_origin = target;
+    }
+    // A command failing with the `auth:` unauthenticated exit code offers the login flow
+    if let (Some(auth), Some(err)) = (&state.config.auth, msg_error(&msg)) {
+        if crate::services::auth::is_unauthenticated(auth, err) {
+            let reason = err.lines().next().unwrap_or_default().to_string();
+            state.login_retry = failed_target(state, &msg);
+            crate::ui::offer_login(state, reason);
+        }
+    }
+    // A command whose program turns out not to be installed greys out the item that ran it
+    if let Some(program) = msg_error(&msg).and_then(crate::services::cli_runner::not_found_program)
+    {
+        if let Some(crate::ui::RetryTarget::Menu(mi)) = &state.pane_origin {
+            let key = crate::nav::keys::menu_key(mi);
+            state.missing_commands.insert(key, program.to_string());
+        }
+    }
+    // `auth_retry:` an expired token is refreshed in the background, then the item runs again
+    let mut reauth = Vec::new();
+    if matches!(msg, AppMsg::EnterMenu(_) | AppMsg::EnterChild { .. }) {
+        state.refresh_retried = false;
+    }
+    let expirable = matches!(
+        msg,
+        AppMsg::LoadedMenu { .. }
+            | AppMsg::LoadedChild { .. }
+            | AppMsg::LoadedPanel { .. }
+            | AppMsg::LoadedNested { .. }
+            | AppMsg::StreamDone { .. }
+    );
+    if let Some(policy) = state.config.auth_retry.as_ref().filter(|_| expirable) {
+        if crate::services::auth::token_expired(policy, command_output(&msg), msg_error(&msg)) {
+            if std::mem::take(&mut state.refresh_retried) {
+                reauth.push(Effect::ShowToast {
+                    text: "Still not authorized after re-authenticating".into(),
+                    level: crate::ui::ToastLevel::Error,
+                    seconds: 4,
+                });
+            } else if state.refresh_retry.is_none() {
+                if let Some(target) = failed_target(state, &msg) {
+                    state.refresh_retry = Some(target);
+                    reauth.push(Effect::ShowToast {
+                        text: "Session expired — re-authenticating…".into(),
+                        level: crate::ui::ToastLevel::Info,
+                        seconds: 3,
+                    });
+                    reauth.push(Effect::RefreshAuth);
+                }
+            }
+        }
+    }
+    // Items with `mutex:` run their commands under that mutex
+    let mutex = match &msg {
+        AppMsg::EnterMenu(mi) => mi.mutex.clone(),
+        AppMsg::EnterChild { val, .. } => val
+            .get("mutex")
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_string()),
+        _ => None,
+    };
+    // Opt-in telemetry: an item run starts on Enter and ends with its stream or panel load
+    let telemetry_start = match &msg {
+        AppMsg::EnterMenu(mi) => Some((mi.id.clone(), mi.title.clone())),
+        _ => None,
+    };
+    let telemetry_end = match &msg {
+        AppMsg::StreamDone { .. } | AppMsg::LoadedPanel { .. } => {
+            Some(msg_error(&msg).map(|e| e.to_string()))
+        }
+        AppMsg::StreamCancelled => Some(Some("cancelled".to_string())),
+        _ => None,
+    };
+    let filters = match &msg {
+        AppMsg::EnterMenu(mi) => mi.filters.clone(),
+        _ => None,
+    };
+    let row_edit = match &msg {
+        AppMsg::EnterMenu(mi) => mi.editable_fields.clone().zip(mi.update_cmd.clone()),
+        _ => None,
+    };
+    let highlights = match &msg {
+        AppMsg::EnterMenu(mi) => Some(mi.clone()),
+        _ => None,
+    };
+    // `${selected.<path>}` in an item's commands: the selected child, else the item itself
+    let mut msg = msg;
+    match &mut msg {
+        AppMsg::EnterMenu(mi) => {
+            let sel = crate::ui::selected_child_value(state)
+                .unwrap_or_else(|| serde_json::json!({"id": mi.id, "title": mi.title}));
+            fill_selected(mi, &sel);
+            state.selection_context = Some(sel);
+        }
+        AppMsg::EnterChild { val, .. } => {
+            let sel = val.clone();
+            crate::services::interpolate::selected_in_value(val, &sel);
+            state.selection_context = Some(sel);
+        }
+        _ => {}
+    }
+    // `{slot.<name>}` in an item's commands: run with the current slot values, then watched
+    let slot_templates = match &mut msg {
+        AppMsg::EnterMenu(mi) => {
+            let templates = [
+                mi.command.clone(),
+                mi.pane_a_cmd.clone(),
+                mi.pane_b_cmd.clone(),
+            ];
+            for cmd in [&mut mi.command, &mut mi.pane_a_cmd, &mut mi.pane_b_cmd]
+                .into_iter()
+                .flatten()
+            {
+                *cmd = state.slots.fill(cmd);
+            }
+            Some(templates)
+        }
+        _ => None,
+    };
+    // `on_enter_cmd` / `on_leave_cmd` of an entered item
+    let hooks = match &msg {
+        AppMsg::EnterMenu(mi) => Some((
+            mi.title.clone(),
+            mi.on_enter_cmd.clone(),
+            mi.on_leave_cmd.clone(),
+        )),
+        _ => None,
+    };
+    let mut effects = update_msg(state, msg);
+    effects.extend(reauth);
+    // The item entered before is left first
+    if let Some((title, enter, leave)) = hooks {
+        let mut run: Vec<Effect> = state
+            .item_leave_hook
+            .take()
+            .map(crate::ui::LeaveHook::effect)
+            .into_iter()
+            .collect();
+        run.extend(enter.map(|cmdline| Effect::RunHook {
+            label: format!("enter {title}"),
+            cmdline,
+        }));
+        state.item_leave_hook = leave.map(|cmdline| crate::ui::LeaveHook {
+            label: format!("leave {title}"),
+            cmdline,
+            opened: false,
+        });
+        effects.splice(0..0, run);
+    }
+    if let Some([command, pane_a, pane_b]) = slot_templates {
+        watch_slots(state, &effects, command, pane_a, pane_b);
+    }
+    // `filters:` the Pane B command runs with the default filter values substituted
+    if let Some(specs) = filters {
+        for e in effects.iter_mut() {
+            if let Effect::LoadPanelCmd {
+                pane: super::ui::PanelPane::B,
+                cmdline,
+            } = e
+            {
+                let bar =
+                    crate::widgets::filter_bar::FilterBar::new(specs.clone(), cmdline.clone());
+                *cmdline = bar.command();
+                state.pane_b_filters = Some(bar);
+            }
+        }
+    }
+    // `editable_fields:` the editor goes on the viewer of the Pane B result
+    if let Some((fields, update_cmd)) = row_edit {
+        for e in effects.iter() {
+            if let Effect::LoadPanelCmd {
+                pane: super::ui::PanelPane::B,
+                cmdline,
+            } = e
+            {
+                state.pane_b_row_editor = Some(crate::widgets::row_edit::RowEditor::new(
+                    fields.clone(),
+                    update_cmd.clone(),
+                    cmdline.clone(),
+                ));
+            }
+        }
+    }
+    // `highlights:` a streamed item's rules apply to the JSON view; invalid rules are reported
+    if let Some(mi) = highlights {
+        if effects
+            .iter()
+            .any(|e| matches!(e, Effect::RunStream { .. }))
+        {
+            state.stream_highlights = super::ui::item_highlights(&mi);
+        }
+        if let Some(err) = mi
+            .highlights
+            .as_deref()
+            .and_then(crate::widgets::highlight::Highlights::check)
+        {
+            effects.push(Effect::ShowToast {
+                text: err,
+                level: crate::ui::ToastLevel::Error,
+                seconds: 4,
+            });
+        }
+    }
+    if let Some((ok, replaced)) = saved {
+        match state.nav_after_save.take() {
+            // The submit result took the form's place, which already went back from it
+            Some(crate::ui::NavTarget::Back) if replaced => {}
+            Some(target) if ok => effects.extend(crate::ui::continue_nav(state, target)),
+            _ => {}
+        }
+    }
+    if state.config.telemetry.is_some() {
+        if let Some(err) = telemetry_end {
+            state.telemetry.finish(err.as_deref());
+        }
+        let ran = effects
+            .iter()
+            .any(|e| matches!(e, Effect::RunStream { .. } | Effect::LoadPanelCmd { .. }));
+        if let (Some((id, title)), true) = (telemetry_start, ran) {
+            state.telemetry.start(&id, &title);
+        }
+    }
+    if let Some((key, secs)) = cooldown {
+        let ran = effects
+            .iter()
+            .any(|e| matches!(e, Effect::RunStream { .. } | Effect::LoadPanelCmd { .. }));
+        if ran {
+            let until = Instant::now() + std::time::Duration::from_secs(secs);
+            state.cooldowns.insert(key, until);
+        }
+    }
+    match mutex {
+        Some(mutex) => effects
+            .into_iter()
+            .map(|e| match e {
+                Effect::RunStream { .. } | Effect::LoadPanelCmd { .. } => Effect::Guarded {
+                    mutex: mutex.clone(),
+                    effect: Box::new(e),
+                },
+                other => other,
+            })
+            .collect(),
+        None => effects,
+    }
+}
+
+fn update_msg(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
     use AppMsg::*;
     let mut effects: Vec<Effect> = Vec::new();
     match msg {
         EnterMenu(mi) => {
+            // The Results screen is a builtin page of its own, not markdown
+            if mi.builtin.as_deref() == Some("results") {
+                super::ui::open_results(state);
+                return effects;
+            }
             // Support static hierarchical children: toggle expand/collapse and seed children map.
             let has_static_children = mi.children.as_ref().map(|v| !v.is_empty()).unwrap_or(false);
             if has_static_children && !super::ui::is_lazy(&mi) && !super::ui::is_autoload(&mi) {
@@ -110,11 +634,40 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     state.expanded.insert(key);
                 }
             }
+            // Children from a directory glob: re-read the files on every expand
+            if let Some(pattern) = &mi.children_from_glob {
+                let key = crate::nav::keys::menu_key(&mi);
+                if state.expanded.remove(&key) {
+                    return effects;
+                }
+                match crate::chi_core::glob_children::glob_children(pattern) {
+                    Ok(children) => {
+                        if children.is_empty() {
+                            effects.push(Effect::ShowToast {
+                                text: format!("No files match {pattern}"),
+                                level: crate::ui::ToastLevel::Info,
+                                seconds: 3,
+                            });
+                        }
+                        state.children.insert(key.clone(), children);
+                        state.expanded.insert(key);
+                    }
+                    Err(e) => {
+                        state.dbg(format!("children_from_glob: {e}"));
+                        effects.push(Effect::ShowToast {
+                            text: e,
+                            level: crate::ui::ToastLevel::Error,
+                            seconds: 5,
+                        });
+                    }
+                }
+                return effects;
+            }
             // Pane B (panel mode) intercept: handle menu items locally in Pane B
             if matches!(state.view, super::ui::View::Panel)
                 && matches!(state.panel_focus, super::ui::PanelPane::B)
             {
-                // If explicitly marked as streaming, bypass Pane B and run as global stream
+                // If explicitly marked as streaming, run it as a stream (progress shown in Pane B)
                 if mi.stream.unwrap_or(false) {
                     if let Some(cmdline) = mi.command.clone() {
                         effects.push(Effect::RunStream {
@@ -126,6 +679,9 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 }
                 // Update Pane B title override for upcoming content
                 state.pane_b_title = mi.pane_b_title.clone();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
                 if super::ui::is_panel(&mi) {
                     // Build nested panel from MenuItem fields (synchronous fill)
                     let mut nested = super::ui::PanelState {
@@ -137,9 +693,8 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     if let Some(cmd) = mi.pane_a_cmd.clone() {
                         if let Ok(j) = crate::services::cli_runner::run_cmdline_to_json(&cmd) {
                             nested.a.last_error = None;
-                            nested.a.last_json_pretty = Some(
-                                serde_json::to_string_pretty(&j).unwrap_or_else(|_| j.to_string()),
-                            );
+                            nested.a.last_json_pretty =
+                                Some(crate::widgets::large_json::stored_pretty(&j));
                         }
                     } else if let Some(path) = mi.pane_a_yaml.clone() {
                         let full_path = {
@@ -155,12 +710,12 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                             }
                         };
                         if let Ok(s) = std::fs::read_to_string(&full_path) {
-                            if let Ok(j) = serde_yaml::from_str::<serde_json::Value>(&s) {
+                            if let Ok(j) =
+                                crate::chi_core::vars::parse::<serde_json::Value>(&s, &full_path)
+                            {
                                 nested.a.last_error = None;
-                                nested.a.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&j)
-                                        .unwrap_or_else(|_| j.to_string()),
-                                );
+                                nested.a.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&j));
                             }
                         }
                     }
@@ -168,9 +723,8 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     if let Some(cmd) = mi.pane_b_cmd.clone() {
                         if let Ok(j) = crate::services::cli_runner::run_cmdline_to_json(&cmd) {
                             nested.b.last_error = None;
-                            nested.b.last_json_pretty = Some(
-                                serde_json::to_string_pretty(&j).unwrap_or_else(|_| j.to_string()),
-                            );
+                            nested.b.last_json_pretty =
+                                Some(crate::widgets::large_json::stored_pretty(&j));
                         }
                     } else if let Some(path) = mi.pane_b_yaml.clone() {
                         let full_path = {
@@ -186,12 +740,12 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                             }
                         };
                         if let Ok(s) = std::fs::read_to_string(&full_path) {
-                            if let Ok(j) = serde_yaml::from_str::<serde_json::Value>(&s) {
+                            if let Ok(j) =
+                                crate::chi_core::vars::parse::<serde_json::Value>(&s, &full_path)
+                            {
                                 nested.b.last_error = None;
-                                nested.b.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&j)
-                                        .unwrap_or_else(|_| j.to_string()),
-                                );
+                                nested.b.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&j));
                             }
                         }
                     }
@@ -210,7 +764,9 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                             .pane_b_title
                             .clone()
                             .unwrap_or_else(|| "Pane B — Markdown".to_string());
-                        if let Some(text) = mi.content.clone() {
+                        if let Some(w) = builtin_markdown_widget(state, &mi, &title) {
+                            super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                        } else if let Some(text) = mi.content.clone() {
                             super::ui::pane_b_replace_with_widget(
                                 state,
                                 Box::new(crate::widgets::markdown::MarkdownWidget::from_text(
@@ -248,6 +804,65 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     }
                     return effects;
                 }
+                if super::ui::is_board(&mi) {
+                    if let (true, Some(cmdline)) = (state.panel.is_some(), mi.command.clone()) {
+                        let w = board_widget(&mi, &cmdline);
+                        super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                        effects.push(Effect::LoadPanelCmd {
+                            pane: super::ui::PanelPane::B,
+                            cmdline,
+                        });
+                    }
+                    return effects;
+                }
+                if super::ui::is_tree(&mi) {
+                    if let (true, Some(w)) = (state.panel.is_some(), tree_widget(&mi)) {
+                        super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                    }
+                    return effects;
+                }
+                if super::ui::is_runbook(&mi) {
+                    if state.panel.is_some() {
+                        let w = runbook_widget(&mi);
+                        super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                    }
+                    return effects;
+                }
+                if super::ui::is_processes(&mi) {
+                    if state.panel.is_some() {
+                        let w = processes_widget(&mi);
+                        super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                    }
+                    return effects;
+                }
+                if super::ui::is_ports(&mi) {
+                    if state.panel.is_some() {
+                        let w = ports_widget(&mi);
+                        super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                    }
+                    return effects;
+                }
+                if super::ui::is_http_checks(&mi) {
+                    if state.panel.is_some() {
+                        let w = http_checks_widget(&mi);
+                        super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                    }
+                    return effects;
+                }
+                if super::ui::is_log_viewer(&mi) {
+                    if state.panel.is_some() {
+                        let w = log_viewer_widget(&mi);
+                        super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                    }
+                    return effects;
+                }
+                if super::ui::is_promql(&mi) {
+                    if state.panel.is_some() {
+                        let w = promql_widget(&mi, state);
+                        super::ui::pane_b_replace_with_widget(state, Box::new(w), true);
+                    }
+                    return effects;
+                }
                 if super::ui::is_watchdog(&mi) {
                     if let Some(_ps) = &mut state.panel {
                         let title = mi
@@ -269,6 +884,10 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                             stats: vec![],
                             external_check_cmd: mi.external_check_cmd.clone(),
                             external_kill_cmd: mi.external_kill_cmd.clone(),
+                            targets: crate::widgets::watchdog::targets::from_fields(
+                                mi.unit.as_ref(),
+                                mi.container.as_ref(),
+                            ),
                         };
                         // Reuse or create a persistent watchdog session by menu key
                         let key = crate::nav::keys::menu_key(&mi);
@@ -303,43 +922,39 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 }
                 if super::ui::is_lazy(&mi) || super::ui::is_autoload(&mi) {
                     match crate::services::loader::load_lazy_children_cmd(&mi) {
-                        Ok(crate::services::loader::Loaded::Items(arr)) => {
+                        Ok(crate::services::loader::Loaded::Items(items)) => {
                             if let Some(ps) = &mut state.panel {
                                 ps.b.last_error = None;
-                                let v = serde_json::Value::Array(arr);
                                 let title = state
                                     .pane_b_title
                                     .clone()
                                     .unwrap_or_else(|| "Pane B".to_string());
                                 super::ui::pane_b_replace_with_widget(
                                     state,
-                                    Box::new(
-                                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                                            title, v,
-                                        ),
-                                    ),
+                                    crate::widgets::renderers::for_rows(title, items, None),
                                     true,
                                 );
                             }
                             return effects;
                         }
                         Ok(crate::services::loader::Loaded::ItemsWithPagination {
-                            items, ..
+                            items,
+                            content_type,
+                            ..
                         }) => {
-                            // For now, just show items in panel B
+                            // Pages are turned in the menu; Pane B shows the rows
                             if let Some(ps) = &mut state.panel {
                                 ps.b.last_error = None;
-                                let v = serde_json::Value::Array(items);
                                 let title = state
                                     .pane_b_title
                                     .clone()
                                     .unwrap_or_else(|| "Pane B".to_string());
                                 super::ui::pane_b_replace_with_widget(
                                     state,
-                                    Box::new(
-                                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                                            title, v,
-                                        ),
+                                    crate::widgets::renderers::for_rows(
+                                        title,
+                                        items,
+                                        content_type.as_deref(),
                                     ),
                                     true,
                                 );
@@ -355,11 +970,7 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                     .unwrap_or_else(|| "Pane B".to_string());
                                 super::ui::pane_b_replace_with_widget(
                                     state,
-                                    Box::new(
-                                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                                            title, v,
-                                        ),
-                                    ),
+                                    crate::widgets::renderers::for_result(title, v),
                                     true,
                                 );
                             }
@@ -429,6 +1040,78 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 } else {
                     state.expanded.remove(&key);
                 }
+            } else if let (true, Some(w)) = (super::ui::is_tree(&mi), tree_widget(&mi)) {
+                state.panel = Some(super::ui::PanelState {
+                    layout: super::ui::PanelLayout::Horizontal,
+                    ratio: super::ui::PanelRatio::Half,
+                    a: super::ui::PaneData::default(),
+                    b: super::ui::PaneData::default(),
+                    b_content: super::ui::PaneContent::Widget(Box::new(w)),
+                    b_history: Vec::new(),
+                    a_content: None,
+                });
+                state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
+                state.view = super::ui::View::Panel;
+                state.panel_focus = super::ui::PanelPane::B;
+                state.panel_nested_focus = super::ui::PanelPane::A;
+                return effects;
+            } else if let (true, Some(cmdline)) = (super::ui::is_board(&mi), mi.command.clone()) {
+                state.panel = Some(super::ui::PanelState {
+                    layout: super::ui::PanelLayout::Horizontal,
+                    ratio: super::ui::PanelRatio::Half,
+                    a: super::ui::PaneData::default(),
+                    b: super::ui::PaneData::default(),
+                    b_content: super::ui::PaneContent::Widget(Box::new(board_widget(
+                        &mi, &cmdline,
+                    ))),
+                    b_history: Vec::new(),
+                    a_content: None,
+                });
+                state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
+                state.view = super::ui::View::Panel;
+                state.panel_focus = super::ui::PanelPane::B;
+                state.panel_nested_focus = super::ui::PanelPane::A;
+                effects.push(Effect::LoadPanelCmd {
+                    pane: super::ui::PanelPane::B,
+                    cmdline,
+                });
+                return effects;
+            } else if super::ui::is_log_viewer(&mi) {
+                state.panel = Some(super::ui::PanelState {
+                    layout: super::ui::PanelLayout::Horizontal,
+                    ratio: super::ui::PanelRatio::Half,
+                    a: super::ui::PaneData::default(),
+                    b: super::ui::PaneData::default(),
+                    b_content: super::ui::PaneContent::Widget(Box::new(log_viewer_widget(&mi))),
+                    b_history: Vec::new(),
+                    a_content: None,
+                });
+                state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
+                state.view = super::ui::View::Panel;
+                state.panel_focus = super::ui::PanelPane::B;
+                state.panel_nested_focus = super::ui::PanelPane::A;
+                return effects;
             } else if let Some(cmdline) = mi.command.clone() {
                 if mi.stream.unwrap_or(false) {
                     let run_title = mi.title.clone();
@@ -466,11 +1149,20 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                         crate::widgets::json_viewer::JsonViewerWidget::from_text("Pane B", ""),
                     )),
                     b_history: Vec::new(),
+                    a_content: None,
                 });
                 // Reset Pane B back history when opening a new panel
                 state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
                 // Apply custom Pane B title if provided
                 state.pane_b_title = mi.pane_b_title.clone();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                // Per-screen responsive template (falls back to config.responsive)
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
                 state.view = super::ui::View::Panel;
                 // UX: new panel -> focus on B automatically
                 state.panel_focus = super::ui::PanelPane::B;
@@ -499,6 +1191,14 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                         path,
                     });
                 }
+                if let Some(spec) = mi.pane_a_widget.as_ref() {
+                    // Specs that load their content first go through the usual pane loads
+                    if let Some(eff) = pane_yaml_effect(super::ui::PanelPane::A, spec) {
+                        effects.push(eff);
+                    } else if !apply_pane_loaded_yaml(super::ui::PanelPane::A, spec, state) {
+                        state.dbg(format!("pane_a_widget of {}: unknown widget spec", mi.id));
+                    }
+                }
             } else if super::ui::is_markdown(&mi) {
                 // Build single-panel view with Markdown in Pane B
                 state.panel = Some(super::ui::PanelState {
@@ -507,20 +1207,35 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     a: super::ui::PaneData::default(),
                     b: super::ui::PaneData::default(),
                     b_content: super::ui::PaneContent::Widget(Box::new(
-                        crate::widgets::markdown::MarkdownWidget::from_text(
-                            mi.pane_b_title
-                                .clone()
-                                .unwrap_or_else(|| "Pane B — Markdown".to_string()),
-                            &mi.content.clone().unwrap_or_default(),
-                        ),
+                        builtin_markdown_widget(
+                            state,
+                            &mi,
+                            mi.pane_b_title.as_deref().unwrap_or("Pane B — Markdown"),
+                        )
+                        .unwrap_or_else(|| {
+                            crate::widgets::markdown::MarkdownWidget::from_text(
+                                mi.pane_b_title
+                                    .clone()
+                                    .unwrap_or_else(|| "Pane B — Markdown".to_string()),
+                                &mi.content.clone().unwrap_or_default(),
+                            )
+                        }),
                     )),
                     b_history: Vec::new(),
+                    a_content: None,
                 });
                 state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
                 state.view = super::ui::View::Panel;
                 state.panel_focus = super::ui::PanelPane::B;
                 state.panel_nested_focus = super::ui::PanelPane::A;
-                if let Some(path) = mi.path.clone() {
+                if let (None, Some(path)) = (&mi.builtin, mi.path.clone()) {
                     let pb = std::path::PathBuf::from(&path);
                     let full = if pb.is_absolute() {
                         pb
@@ -545,46 +1260,169 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     }
                 }
                 return effects;
-            } else if super::ui::is_watchdog(&mi) {
+            } else if super::ui::is_runbook(&mi) {
                 state.panel = Some(super::ui::PanelState {
-                    layout: super::ui::PanelLayout::Vertical,
+                    layout: super::ui::PanelLayout::Horizontal,
                     ratio: super::ui::PanelRatio::Half,
                     a: super::ui::PaneData::default(),
                     b: super::ui::PaneData::default(),
-                    b_content: super::ui::PaneContent::Widget(Box::new(
-                        crate::widgets::json_viewer::JsonViewerWidget::from_text("Pane B", ""),
-                    )),
+                    b_content: super::ui::PaneContent::Widget(Box::new(runbook_widget(&mi))),
                     b_history: Vec::new(),
+                    a_content: None,
                 });
                 state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
                 state.view = super::ui::View::Panel;
                 state.panel_focus = super::ui::PanelPane::B;
                 state.panel_nested_focus = super::ui::PanelPane::A;
-                let cmds = mi.commands.clone().unwrap_or_default();
-                let cfg = crate::widgets::watchdog::WatchdogConfig {
-                    sequential: mi.sequential.unwrap_or(false),
-                    auto_restart: mi.auto_restart.unwrap_or(false),
-                    max_retries: mi.max_retries.unwrap_or(0) as usize,
-                    restart_delay_ms: mi.restart_delay_ms.unwrap_or(1000) as u64,
-                    allowed_exit_codes: mi.allowed_exit_codes.clone().unwrap_or_else(|| vec![0]),
-                    stop_on_failure: mi.stop_on_failure.unwrap_or(false),
-                    on_panic_exit_cmd: mi.on_panic_exit_cmd.clone(),
-                    stats: vec![],
-                    external_check_cmd: mi.external_check_cmd.clone(),
-                    external_kill_cmd: mi.external_kill_cmd.clone(),
-                };
-                if state.panel.is_some() {
-                    let key = crate::nav::keys::menu_key(&mi);
-                    let (session, reused) =
-                        if let Some(s) = state.watchdog_sessions.get(&key).cloned() {
-                            (s, true)
-                        } else {
-                            let s = crate::widgets::watchdog::WatchdogSession::create(
-                                cmds.clone(),
-                                crate::widgets::watchdog::WatchdogConfig {
-                                    stats: cfg.stats.clone(),
-                                    ..cfg
-                                },
+                return effects;
+            } else if super::ui::is_processes(&mi) {
+                state.panel = Some(super::ui::PanelState {
+                    layout: super::ui::PanelLayout::Horizontal,
+                    ratio: super::ui::PanelRatio::Half,
+                    a: super::ui::PaneData::default(),
+                    b: super::ui::PaneData::default(),
+                    b_content: super::ui::PaneContent::Widget(Box::new(processes_widget(&mi))),
+                    b_history: Vec::new(),
+                    a_content: None,
+                });
+                state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
+                state.view = super::ui::View::Panel;
+                state.panel_focus = super::ui::PanelPane::B;
+                state.panel_nested_focus = super::ui::PanelPane::A;
+                return effects;
+            } else if super::ui::is_ports(&mi) {
+                state.panel = Some(super::ui::PanelState {
+                    layout: super::ui::PanelLayout::Horizontal,
+                    ratio: super::ui::PanelRatio::Half,
+                    a: super::ui::PaneData::default(),
+                    b: super::ui::PaneData::default(),
+                    b_content: super::ui::PaneContent::Widget(Box::new(ports_widget(&mi))),
+                    b_history: Vec::new(),
+                    a_content: None,
+                });
+                state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
+                state.view = super::ui::View::Panel;
+                state.panel_focus = super::ui::PanelPane::B;
+                state.panel_nested_focus = super::ui::PanelPane::A;
+                return effects;
+            } else if super::ui::is_http_checks(&mi) {
+                state.panel = Some(super::ui::PanelState {
+                    layout: super::ui::PanelLayout::Horizontal,
+                    ratio: super::ui::PanelRatio::Half,
+                    a: super::ui::PaneData::default(),
+                    b: super::ui::PaneData::default(),
+                    b_content: super::ui::PaneContent::Widget(Box::new(http_checks_widget(&mi))),
+                    b_history: Vec::new(),
+                    a_content: None,
+                });
+                state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
+                state.view = super::ui::View::Panel;
+                state.panel_focus = super::ui::PanelPane::B;
+                state.panel_nested_focus = super::ui::PanelPane::A;
+                return effects;
+            } else if super::ui::is_promql(&mi) {
+                let w = promql_widget(&mi, state);
+                state.panel = Some(super::ui::PanelState {
+                    layout: super::ui::PanelLayout::Horizontal,
+                    ratio: super::ui::PanelRatio::Half,
+                    a: super::ui::PaneData::default(),
+                    b: super::ui::PaneData::default(),
+                    b_content: super::ui::PaneContent::Widget(Box::new(w)),
+                    b_history: Vec::new(),
+                    a_content: None,
+                });
+                state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
+                state.view = super::ui::View::Panel;
+                state.panel_focus = super::ui::PanelPane::B;
+                state.panel_nested_focus = super::ui::PanelPane::A;
+                return effects;
+            } else if super::ui::is_watchdog(&mi) {
+                state.panel = Some(super::ui::PanelState {
+                    layout: super::ui::PanelLayout::Vertical,
+                    ratio: super::ui::PanelRatio::Half,
+                    a: super::ui::PaneData::default(),
+                    b: super::ui::PaneData::default(),
+                    b_content: super::ui::PaneContent::Widget(Box::new(
+                        crate::widgets::json_viewer::JsonViewerWidget::from_text("Pane B", ""),
+                    )),
+                    b_history: Vec::new(),
+                    a_content: None,
+                });
+                state.pane_b_title_stack.clear();
+                state.pane_b_highlights_stack.clear();
+                state.pane_b_highlights = super::ui::item_highlights(&mi);
+                state.pane_b_empty =
+                    crate::widgets::empty_state::EmptyState::from_item(&mi, &state.config.menu);
+                state.panel_narrow_layout = mi.narrow_layout.clone();
+                state.panel_focus_on_load = mi.focus_on_load.clone();
+                state.panel_max_depth = mi.max_panel_depth;
+                state.view = super::ui::View::Panel;
+                state.panel_focus = super::ui::PanelPane::B;
+                state.panel_nested_focus = super::ui::PanelPane::A;
+                let cmds = mi.commands.clone().unwrap_or_default();
+                let cfg = crate::widgets::watchdog::WatchdogConfig {
+                    sequential: mi.sequential.unwrap_or(false),
+                    auto_restart: mi.auto_restart.unwrap_or(false),
+                    max_retries: mi.max_retries.unwrap_or(0) as usize,
+                    restart_delay_ms: mi.restart_delay_ms.unwrap_or(1000) as u64,
+                    allowed_exit_codes: mi.allowed_exit_codes.clone().unwrap_or_else(|| vec![0]),
+                    stop_on_failure: mi.stop_on_failure.unwrap_or(false),
+                    on_panic_exit_cmd: mi.on_panic_exit_cmd.clone(),
+                    stats: vec![],
+                    external_check_cmd: mi.external_check_cmd.clone(),
+                    external_kill_cmd: mi.external_kill_cmd.clone(),
+                    targets: crate::widgets::watchdog::targets::from_fields(
+                        mi.unit.as_ref(),
+                        mi.container.as_ref(),
+                    ),
+                };
+                if state.panel.is_some() {
+                    let key = crate::nav::keys::menu_key(&mi);
+                    let (session, reused) =
+                        if let Some(s) = state.watchdog_sessions.get(&key).cloned() {
+                            (s, true)
+                        } else {
+                            let s = crate::widgets::watchdog::WatchdogSession::create(
+                                cmds.clone(),
+                                crate::widgets::watchdog::WatchdogConfig {
+                                    stats: cfg.stats.clone(),
+                                    ..cfg
+                                },
                             );
                             state.watchdog_sessions.insert(key.clone(), s.clone());
                             (s, false)
@@ -621,56 +1459,12 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 }
                 return effects;
             }
-            // Check if this is a pagination control
-            if val
-                .get("__is_pagination")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false)
-            {
-                // This is a pagination item - reload the parent list with new page
-                if let Some(cmd) = val.get("command").and_then(|c| c.as_str()) {
-                    // Determine parent menu key. Child keys are formatted as
-                    //   "menu:<parent_id>/<child_id_or_index>"
-                    // so take the segment before the first '/'.
-                    let parent_key = key
-                        .split('/')
-                        .next()
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| key.clone());
-
-                    // Find the parent menu item to reload it with new command
-                    if let Some(parent_mi) = state
-                        .config
-                        .menu
-                        .iter()
-                        .find(|mi| crate::nav::keys::menu_key(mi) == parent_key)
-                        .cloned()
-                    {
-                        // Create a modified menu item with the pagination command
-                        let mut paginated_mi = parent_mi;
-                        paginated_mi.command = Some(cmd.to_string());
-
-                        // Clear existing children and reload
-                        state.children.remove(&parent_key);
-                        state.loading.insert(parent_key.clone());
-
-                        // Load the new page using Effect
-                        effects.push(Effect::LoadMenu {
-                            mi: paginated_mi,
-                            key: parent_key,
-                        });
-                    }
-                }
-                return effects;
-            }
-
-            // Check if this is an info item (page indicator)
+            // Info rows (a `db:` table header) are not interactive
             if val
                 .get("__is_info")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false)
             {
-                // Info items are not interactive - do nothing
                 return effects;
             }
 
@@ -703,6 +1497,7 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                     ),
                                 )),
                                 b_history: Vec::new(),
+                                a_content: None,
                             });
                             state.view = super::ui::View::Panel;
                             state.panel_focus = super::ui::PanelPane::B;
@@ -755,31 +1550,39 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     }
                     "watchdog" => {
                         // Handle watchdog widget for list items
-                        if let Some(commands) = val.get("commands").and_then(|c| c.as_array()) {
-                            let cmds: Vec<String> = commands
-                                .iter()
-                                .filter_map(|v| v.as_str().map(String::from))
-                                .collect();
-
-                            if !cmds.is_empty() {
-                                if state.view != super::ui::View::Panel {
-                                    state.panel = Some(super::ui::PanelState {
-                                        layout: super::ui::PanelLayout::Vertical,
-                                        ratio: super::ui::PanelRatio::Half,
-                                        a: super::ui::PaneData::default(),
-                                        b: super::ui::PaneData::default(),
-                                        b_content: super::ui::PaneContent::Widget(Box::new(
-                                            crate::widgets::json_viewer::JsonViewerWidget::from_text("Watchdog", ""),
-                                        )),
-                                        b_history: Vec::new(),
-                                    });
-                                    state.view = super::ui::View::Panel;
-                                    state.panel_focus = super::ui::PanelPane::B;
-                                }
+                        let cmds: Vec<String> = val
+                            .get("commands")
+                            .and_then(|c| c.as_array())
+                            .map(|arr| {
+                                arr.iter()
+                                    .filter_map(|v| v.as_str().map(String::from))
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        if !cmds.is_empty()
+                            || !crate::widgets::watchdog::targets::from_value(&val).is_empty()
+                        {
+                            if state.view != super::ui::View::Panel {
+                                state.panel = Some(super::ui::PanelState {
+                                    layout: super::ui::PanelLayout::Vertical,
+                                    ratio: super::ui::PanelRatio::Half,
+                                    a: super::ui::PaneData::default(),
+                                    b: super::ui::PaneData::default(),
+                                    b_content: super::ui::PaneContent::Widget(Box::new(
+                                        crate::widgets::json_viewer::JsonViewerWidget::from_text(
+                                            "Watchdog", "",
+                                        ),
+                                    )),
+                                    b_history: Vec::new(),
+                                    a_content: None,
+                                });
+                                state.view = super::ui::View::Panel;
+                                state.panel_focus = super::ui::PanelPane::B;
+                            }
 
-                                if let Some(_ps) = &mut state.panel {
-                                    let title = super::ui::title_from_value(&val);
-                                    let cfg = crate::widgets::watchdog::WatchdogConfig {
+                            if let Some(_ps) = &mut state.panel {
+                                let title = super::ui::title_from_value(&val);
+                                let cfg = crate::widgets::watchdog::WatchdogConfig {
                                         sequential: val
                                             .get("sequential")
                                             .and_then(|v| v.as_bool())
@@ -837,43 +1640,39 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                             .get("external_kill_cmd")
                                             .and_then(|v| v.as_str())
                                             .map(String::from),
+                                        targets: crate::widgets::watchdog::targets::from_value(&val),
                                     };
-                                    // Use the child key to uniquely identify the session
-                                    let sess_key = key.clone();
-                                    let (session, reused) = if let Some(s) =
-                                        state.watchdog_sessions.get(&sess_key).cloned()
-                                    {
-                                        (s, true)
-                                    } else {
-                                        let s = crate::widgets::watchdog::WatchdogSession::create(
-                                            cmds.clone(),
-                                            crate::widgets::watchdog::WatchdogConfig {
-                                                stats: cfg.stats.clone(),
-                                                ..cfg
-                                            },
-                                        );
-                                        state.watchdog_sessions.insert(sess_key.clone(), s.clone());
-                                        (s, false)
-                                    };
-                                    if reused {
-                                        state.dbg(format!(
-                                            "watchdog: reusing session for {sess_key}"
-                                        ));
-                                    } else {
-                                        state.dbg(format!(
-                                            "watchdog: creating session for {sess_key}"
-                                        ));
-                                    }
-                                    super::ui::pane_b_replace_with_widget(
-                                        state,
-                                        Box::new(
-                                            crate::widgets::watchdog::WatchdogWidget::from_session(
-                                                title, &session,
-                                            ),
-                                        ),
-                                        true,
+                                // Use the child key to uniquely identify the session
+                                let sess_key = key.clone();
+                                let (session, reused) = if let Some(s) =
+                                    state.watchdog_sessions.get(&sess_key).cloned()
+                                {
+                                    (s, true)
+                                } else {
+                                    let s = crate::widgets::watchdog::WatchdogSession::create(
+                                        cmds.clone(),
+                                        crate::widgets::watchdog::WatchdogConfig {
+                                            stats: cfg.stats.clone(),
+                                            ..cfg
+                                        },
                                     );
+                                    state.watchdog_sessions.insert(sess_key.clone(), s.clone());
+                                    (s, false)
+                                };
+                                if reused {
+                                    state.dbg(format!("watchdog: reusing session for {sess_key}"));
+                                } else {
+                                    state.dbg(format!("watchdog: creating session for {sess_key}"));
                                 }
+                                super::ui::pane_b_replace_with_widget(
+                                    state,
+                                    Box::new(
+                                        crate::widgets::watchdog::WatchdogWidget::from_session(
+                                            title, &session,
+                                        ),
+                                    ),
+                                    true,
+                                );
                             }
                         }
                     }
@@ -897,10 +1696,8 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                         } else {
                             // Show as JSON
                             state.last_error = None;
-                            state.last_json_pretty = Some(
-                                serde_json::to_string_pretty(&val)
-                                    .unwrap_or_else(|_| val.to_string()),
-                            );
+                            state.last_json_pretty =
+                                Some(crate::widgets::large_json::stored_pretty(&val));
                             state.json_scroll_y = 0;
                             state.view = super::ui::View::Json;
                         }
@@ -929,19 +1726,19 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 // Non-command leaf selected in panel mode: show in Pane B
                 if let Some(ps) = &mut state.panel {
                     ps.b.last_error = None;
-                    ps.b.last_json_pretty = Some(
-                        serde_json::to_string_pretty(&val).unwrap_or_else(|_| val.to_string()),
-                    );
+                    ps.b.last_json_pretty = Some(crate::widgets::large_json::stored_pretty(&val));
                 }
             } else {
                 state.last_error = None;
-                state.last_json_pretty =
-                    Some(serde_json::to_string_pretty(&val).unwrap_or_else(|_| val.to_string()));
+                state.last_json_pretty = Some(crate::widgets::large_json::stored_pretty(&val));
+                crate::ui::show_json_result(state, "JSON Output", val);
                 state.json_scroll_y = 0;
                 state.view = super::ui::View::Json;
             }
         }
         RefreshMenu(mi) => {
+            // 'r' in the menu also asks the items' conditions again
+            effects.push(Effect::CheckConditions);
             if super::ui::is_lazy(&mi) || super::ui::is_autoload(&mi) {
                 let key = crate::nav::keys::menu_key(&mi);
                 state.loading.insert(key.clone());
@@ -989,6 +1786,34 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 }
             }
         }
+        TurnPage { key, forward } => {
+            let Some(cmd) = state
+                .pages
+                .get(&key)
+                .and_then(|p| p.command(forward))
+                .map(String::from)
+            else {
+                return effects;
+            };
+            // The list belongs to a menu item or to a loaded child: reload it with the page's
+            // command (the parent's configured command stays as it is)
+            let owner = state
+                .config
+                .menu
+                .iter()
+                .find(|mi| crate::nav::keys::menu_key(mi) == key)
+                .cloned();
+            if let Some(mut mi) = owner {
+                mi.command = Some(cmd);
+                state.loading.insert(key.clone());
+                effects.push(Effect::LoadMenu { mi, key });
+            } else if let Some(mut val) = crate::nav::pagination::child_value(&state.children, &key)
+            {
+                val["command"] = JsonValue::String(cmd);
+                state.loading.insert(key.clone());
+                effects.push(Effect::LoadChild { val, key });
+            }
+        }
         RefreshChild { key, val } => {
             if super::ui::is_lazy_value(&val) || super::ui::is_autoload_value(&val) {
                 state.loading.insert(key.clone());
@@ -1011,6 +1836,7 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
         LoadedMenu { key, outcome } => match outcome {
             Ok(LoadOutcome::Items(arr)) => {
                 state.dbg(format!("loaded menu {} items", arr.len()));
+                state.pages.remove(&key);
                 state.children.insert(key.clone(), arr);
                 state.last_error = None;
                 state.last_json_pretty = None;
@@ -1047,60 +1873,19 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     tot,
                     items.len()
                 ));
-                // Build paginated list with navigation items
-                let mut paginated_items = Vec::new();
-
-                // Add "Previous Page" if available
-                if let Some(prev_cmd) = pagination.get("prev_page_cmd").and_then(|v| v.as_str()) {
-                    let prev_item = serde_json::json!({
-                        "id": "__prev_page__",
-                        "title": format!("Previous Page ({})",
-                            pagination.get("current_page").and_then(|v| v.as_i64()).map(|p| p - 1).unwrap_or(0)),
-                        "command": prev_cmd,
-                        "__is_pagination": true
-                    });
-                    paginated_items.push(prev_item);
-                }
-
-                // Add actual items
-                paginated_items.extend(items);
-
-                // Add "Next Page" if available
-                if let Some(next_cmd) = pagination.get("next_page_cmd").and_then(|v| v.as_str()) {
-                    let next_item = serde_json::json!({
-                        "id": "__next_page__",
-                        "title": format!("Next Page ({})",
-                            pagination.get("current_page").and_then(|v| v.as_i64()).map(|p| p + 1).unwrap_or(2)),
-                        "command": next_cmd,
-                        "__is_pagination": true
-                    });
-                    paginated_items.push(next_item);
-                }
-
-                // Add page info at the bottom
-                if let (Some(current), Some(total)) = (
-                    pagination.get("current_page").and_then(|v| v.as_i64()),
-                    pagination.get("total_pages").and_then(|v| v.as_i64()),
-                ) {
-                    let page_info = serde_json::json!({
-                        "id": "__page_info__",
-                        "title": format!("─────  Page {}/{} ({} items)  ─────",
-                            current, total,
-                            pagination.get("total_items").and_then(|v| v.as_i64()).unwrap_or(0)),
-                        "__is_info": true
-                    });
-                    paginated_items.push(page_info);
-                }
-
-                state.children.insert(key.clone(), paginated_items);
+                state.pages.insert(
+                    key.clone(),
+                    crate::nav::pagination::Pagination::from_json(&pagination),
+                );
+                state.children.insert(key.clone(), items);
                 state.last_error = None;
                 state.last_json_pretty = None;
                 state.expanded.insert(key.clone());
             }
             Ok(LoadOutcome::Fallback(v)) => {
                 state.last_error = None;
-                state.last_json_pretty =
-                    Some(serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string()));
+                state.last_json_pretty = Some(crate::widgets::large_json::stored_pretty(&v));
+                crate::ui::show_json_result(state, "JSON Output", v);
             }
             Err(e) => {
                 state.dbg(format!("load menu error: {e}"));
@@ -1111,6 +1896,7 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
         LoadedChild { key, outcome } => match outcome {
             Ok(LoadOutcome::Items(arr)) => {
                 state.dbg(format!("loaded child {} items", arr.len()));
+                state.pages.remove(&key);
                 state.children.insert(key.clone(), arr);
                 state.last_error = None;
                 state.last_json_pretty = None;
@@ -1131,52 +1917,11 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     tot,
                     items.len()
                 ));
-                // Build paginated list with navigation items
-                let mut paginated_items = Vec::new();
-
-                // Add "Previous Page" if available
-                if let Some(prev_cmd) = pagination.get("prev_page_cmd").and_then(|v| v.as_str()) {
-                    let prev_item = serde_json::json!({
-                        "id": "__prev_page__",
-                        "title": format!("Previous Page ({})",
-                            pagination.get("current_page").and_then(|v| v.as_i64()).map(|p| p - 1).unwrap_or(0)),
-                        "command": prev_cmd,
-                        "__is_pagination": true
-                    });
-                    paginated_items.push(prev_item);
-                }
-
-                // Add actual items
-                paginated_items.extend(items);
-
-                // Add "Next Page" if available
-                if let Some(next_cmd) = pagination.get("next_page_cmd").and_then(|v| v.as_str()) {
-                    let next_item = serde_json::json!({
-                        "id": "__next_page__",
-                        "title": format!("Next Page ({})",
-                            pagination.get("current_page").and_then(|v| v.as_i64()).map(|p| p + 1).unwrap_or(2)),
-                        "command": next_cmd,
-                        "__is_pagination": true
-                    });
-                    paginated_items.push(next_item);
-                }
-
-                // Add page info at the bottom
-                if let (Some(current), Some(total)) = (
-                    pagination.get("current_page").and_then(|v| v.as_i64()),
-                    pagination.get("total_pages").and_then(|v| v.as_i64()),
-                ) {
-                    let page_info = serde_json::json!({
-                        "id": "__page_info__",
-                        "title": format!("─────  Page {}/{} ({} items)  ─────",
-                            current, total,
-                            pagination.get("total_items").and_then(|v| v.as_i64()).unwrap_or(0)),
-                        "__is_info": true
-                    });
-                    paginated_items.push(page_info);
-                }
-
-                state.children.insert(key.clone(), paginated_items);
+                state.pages.insert(
+                    key.clone(),
+                    crate::nav::pagination::Pagination::from_json(&pagination),
+                );
+                state.children.insert(key.clone(), items);
                 state.last_error = None;
                 state.last_json_pretty = None;
                 state.expanded.insert(key);
@@ -1184,8 +1929,8 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
             Ok(LoadOutcome::Fallback(v)) => {
                 state.dbg("loaded fallback JSON".to_string());
                 state.last_error = None;
-                state.last_json_pretty =
-                    Some(serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string()));
+                state.last_json_pretty = Some(crate::widgets::large_json::stored_pretty(&v));
+                crate::ui::show_json_result(state, "JSON Output", v);
             }
             Err(e) => {
                 state.dbg(format!("load child error: {e}"));
@@ -1213,28 +1958,20 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                         match pane {
                             super::ui::PanelPane::A => {
                                 ps.a.last_error = None;
-                                ps.a.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                );
+                                ps.a.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v));
                             }
                             super::ui::PanelPane::B => {
                                 ps.b.last_error = None;
-                                ps.b.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                );
+                                ps.b.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v));
                                 let title = state
                                     .pane_b_title
                                     .clone()
                                     .unwrap_or_else(|| "Pane B".to_string());
                                 super::ui::pane_b_replace_with_widget(
                                     state,
-                                    Box::new(
-                                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                                            title, v,
-                                        ),
-                                    ),
+                                    crate::widgets::renderers::for_result(title, v),
                                     true,
                                 );
                             }
@@ -1248,66 +1985,78 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                         match pane {
                             super::ui::PanelPane::A => {
                                 ps.a.last_error = None;
-                                ps.a.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                );
+                                ps.a.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v));
                             }
                             super::ui::PanelPane::B => {
                                 ps.b.last_error = None;
-                                ps.b.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                );
+                                ps.b.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v));
                                 let title = state
                                     .pane_b_title
                                     .clone()
                                     .unwrap_or_else(|| "Pane B".to_string());
                                 super::ui::pane_b_replace_with_widget(
                                     state,
-                                    Box::new(
-                                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                                            title, v,
-                                        ),
-                                    ),
+                                    crate::widgets::renderers::for_result(title, v),
                                     true,
                                 );
                             }
                         }
                     }
                 }
-                Ok(LoadOutcome::Fallback(v)) => {
+                Ok(LoadOutcome::Fallback(mut v)) => {
+                    // A widget spec (form `submit_cmd`, watchdog `commands`, ...) fills
+                    // `${selected.<path>}` from the selection its item was entered with
+                    if let Some(sel) = &state.selection_context {
+                        if v.get("type").or_else(|| v.get("widget")).is_some() {
+                            crate::services::interpolate::selected_in_value(&mut v, sel);
+                        }
+                    }
                     // Try to interpret YAML-as-widget spec; if recognized, schedule appropriate loads
                     if let Some(eff) = pane_yaml_effect(pane, &v) {
-                        effects.push(eff);
+                        // A pane spec's own empty state wins over the item's
+                        if let (super::ui::PanelPane::B, Some(empty)) = (
+                            pane,
+                            crate::widgets::empty_state::EmptyState::from_spec(
+                                &v,
+                                &state.config.menu,
+                            ),
+                        ) {
+                            state.pane_b_empty = Some(empty);
+                        }
+                        let limit = state
+                            .panel_max_depth
+                            .or(state.config.max_panel_depth)
+                            .unwrap_or(crate::chi_core::nesting::DEFAULT_MAX_DEPTH);
+                        match state.panel_nesting.follow(pane, &eff, limit) {
+                            Ok(()) => effects.push(eff),
+                            Err(msg) => {
+                                state.dbg(msg.clone());
+                                show_pane_error(state, pane, msg);
+                            }
+                        }
                     } else if apply_pane_loaded_yaml(pane, &v, state) {
-                        // handled
+                        // handled; a freshly opened form prefetches its dynamic options
+                        effects.extend(form_prefetch_effects(state));
                     } else if let Some(ps) = &mut state.panel {
                         match pane {
                             super::ui::PanelPane::A => {
                                 ps.a.last_error = None;
-                                ps.a.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                );
+                                ps.a.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v));
                             }
                             super::ui::PanelPane::B => {
                                 ps.b.last_error = None;
-                                ps.b.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                );
+                                ps.b.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v));
                                 let title = state
                                     .pane_b_title
                                     .clone()
                                     .unwrap_or_else(|| "Pane B".to_string());
                                 super::ui::pane_b_replace_with_widget(
                                     state,
-                                    Box::new(
-                                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                                            title, v,
-                                        ),
-                                    ),
+                                    crate::widgets::renderers::for_result(title, v),
                                     true,
                                 );
                             }
@@ -1329,6 +2078,9 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     }
                 }
             }
+            if state.panel.is_some() {
+                focus_after_load(state, pane, None);
+            }
         }
         LoadedNested { subpane, outcome } => {
             // Derive parent menu key (top-level menu item) to key nested watchdog sessions
@@ -1345,23 +2097,50 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     None
                 }
             };
+            let mut delivered = false;
+            // Nested panel built from an item's pane_a_cmd/pane_b_cmd (re-run by a slot change)
+            if let Some(super::ui::PaneContent::Panel(nested)) =
+                state.panel.as_mut().map(|ps| &mut ps.b_content)
+            {
+                let data = match subpane {
+                    super::ui::PanelPane::A => &mut nested.a,
+                    super::ui::PanelPane::B => &mut nested.b,
+                };
+                match outcome {
+                    Ok(o) => {
+                        let v = match o {
+                            LoadOutcome::Items(vs) => JsonValue::Array(vs),
+                            LoadOutcome::ItemsWithPagination { items, .. } => {
+                                JsonValue::Array(items)
+                            }
+                            LoadOutcome::Fallback(v) => v,
+                        };
+                        data.last_error = None;
+                        data.last_json_pretty = Some(crate::widgets::large_json::stored_pretty(&v));
+                    }
+                    Err(e) => {
+                        data.last_error = Some(e);
+                        data.last_json_pretty = None;
+                    }
+                }
+                return effects;
+            }
             if let Some(ps) = &mut state.panel {
                 if let super::ui::PaneContent::Widget(ref mut w) = ps.b_content {
                     if let Some(pw) = w
                         .as_any_mut()
                         .downcast_mut::<crate::widgets::panel::PanelWidget>()
                     {
+                        delivered = true;
                         match outcome {
                             Ok(LoadOutcome::Items(vs)) => {
                                 let v = JsonValue::Array(vs);
-                                let txt = serde_json::to_string_pretty(&v)
-                                    .unwrap_or_else(|_| v.to_string());
+                                let txt = crate::widgets::large_json::stored_pretty(&v);
                                 pw.set_subpane_text(subpane, txt);
                             }
                             Ok(LoadOutcome::ItemsWithPagination { items, .. }) => {
                                 let v = JsonValue::Array(items);
-                                let txt = serde_json::to_string_pretty(&v)
-                                    .unwrap_or_else(|_| v.to_string());
+                                let txt = crate::widgets::large_json::stored_pretty(&v);
                                 pw.set_subpane_text(subpane, txt);
                             }
                             Ok(LoadOutcome::Fallback(v)) => {
@@ -1473,6 +2252,7 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                             .get("external_kill_cmd")
                                             .and_then(|s| s.as_str())
                                             .map(|s| s.to_string()),
+                                        targets: crate::widgets::watchdog::targets::from_value(&v),
                                     };
                                     if let Some(parent_key) = parent_key_opt {
                                         let sess_key = format!("{parent_key}/nested:{subpane:?}");
@@ -1497,8 +2277,6 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                                 title, &session,
                                             );
                                         pw.set_subpane_widget(subpane, Box::new(ww));
-                                        // handled watchdog; do not fall back to generic path
-                                        return effects;
                                     }
                                 } else {
                                     // 2) Other widget types via registry; if not recognized, fall back to text
@@ -1509,8 +2287,7 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                     {
                                         pw.set_subpane_widget(subpane, w);
                                     } else {
-                                        let txt = serde_json::to_string_pretty(&v)
-                                            .unwrap_or_else(|_| v.to_string());
+                                        let txt = crate::widgets::large_json::stored_pretty(&v);
                                         pw.set_subpane_text(subpane, txt);
                                     }
                                 }
@@ -1522,6 +2299,9 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     }
                 }
             }
+            if delivered {
+                focus_after_load(state, super::ui::PanelPane::B, Some(subpane));
+            }
         }
         LoadedSubmitForm { pane, outcome } => {
             // Clear submitting status
@@ -1530,22 +2310,37 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
             // If we are in Form view, update inline errors or show result JSON
             match outcome {
                 Ok(LoadOutcome::Fallback(v)) => {
-                    let is_error = v
-                        .get("ok")
-                        .and_then(|b| b.as_bool())
-                        .map(|b| !b)
-                        .unwrap_or_else(|| v.get("type").and_then(|s| s.as_str()) == Some("error"));
-                    if is_error {
+                    let env = match Envelope::parse(&v) {
+                        Ok(env) => env,
+                        Err(e) => {
+                            // Malformed reply: the form stays editable and says why
+                            if let Some(super::ui::PaneContent::Widget(w)) =
+                                state.panel.as_mut().map(|ps| &mut ps.b_content)
+                            {
+                                if let Some(fw) =
+                                    crate::widgets::wizard::current_form_mut(w.as_any_mut())
+                                {
+                                    fw.form.disabled = false;
+                                    fw.form.message = Some(e.clone());
+                                }
+                            }
+                            return vec![Effect::ShowToast {
+                                text: e,
+                                level: crate::ui::ToastLevel::Error,
+                                seconds: 5,
+                            }];
+                        }
+                    };
+                    if env.failed() {
                         if let Some(ps) = &mut state.panel {
                             if let super::ui::PaneContent::Widget(ref mut w) = ps.b_content {
                                 if let Some(fw) =
-                                    w.as_any_mut()
-                                        .downcast_mut::<crate::widgets::form_widget::FormWidget>()
+                                    crate::widgets::wizard::current_form_mut(w.as_any_mut())
                                 {
                                     let form = &mut fw.form;
                                     let mut any = false;
-                                    let details = v.get("data").and_then(|d| d.get("details"));
-                                    if let Some(errs) = details
+                                    if let Some(errs) = env
+                                        .details()
                                         .and_then(|d| d.get("errors"))
                                         .and_then(|x| x.as_array())
                                     {
@@ -1572,51 +2367,51 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                     form.message = Some(if any {
                                         "Please fix the highlighted errors".into()
                                     } else {
-                                        v.get("data")
-                                            .and_then(|d| d.get("message"))
-                                            .and_then(|s| s.as_str())
-                                            .unwrap_or("Submit failed")
-                                            .to_string()
+                                        env.message().unwrap_or("Submit failed").to_string()
                                     });
                                 }
                             }
                         }
                         // Toast error message
-                        let msg = v
-                            .get("data")
-                            .and_then(|d| d.get("message"))
-                            .and_then(|s| s.as_str())
-                            .unwrap_or("Submit failed")
-                            .to_string();
+                        let msg = env.message().unwrap_or("Submit failed").to_string();
                         return vec![Effect::ShowToast {
                             text: msg,
                             level: crate::ui::ToastLevel::Error,
                             seconds: 3,
                         }];
                     } else {
+                        // Saved: the form kept in Pane B history is no longer dirty
+                        if let Some(super::ui::PaneContent::Widget(w)) =
+                            state.panel.as_mut().map(|ps| &mut ps.b_content)
+                        {
+                            if let Some(wz) = w
+                                .as_any_mut()
+                                .downcast_mut::<crate::widgets::wizard::WizardWidget>()
+                            {
+                                wz.saved();
+                            } else if let Some(fw) =
+                                w.as_any_mut()
+                                    .downcast_mut::<crate::widgets::form_widget::FormWidget>()
+                            {
+                                crate::widgets::form::capture_initial(&mut fw.form);
+                                fw.form.dirty = false;
+                            }
+                        }
                         // Show result in Pane B using pretty ResultViewer
                         if let Some(ps) = &mut state.panel {
                             match pane {
                                 super::ui::PanelPane::A => {
                                     ps.a.last_error = None;
-                                    ps.a.last_json_pretty = Some(
-                                        serde_json::to_string_pretty(&v)
-                                            .unwrap_or_else(|_| v.to_string()),
-                                    );
+                                    ps.a.last_json_pretty =
+                                        Some(crate::widgets::large_json::stored_pretty(&v));
                                 }
                                 super::ui::PanelPane::B => {
                                     ps.b.last_error = None;
-                                    ps.b.last_json_pretty = Some(
-                                        serde_json::to_string_pretty(&v)
-                                            .unwrap_or_else(|_| v.to_string()),
-                                    );
+                                    ps.b.last_json_pretty =
+                                        Some(crate::widgets::large_json::stored_pretty(&v));
                                     super::ui::pane_b_replace_with_widget(
                                         state,
-                                        Box::new(
-                                            crate::widgets::result_viewer::ResultViewerWidget::new(
-                                                "Pane B", v,
-                                            ),
-                                        ),
+                                        crate::widgets::renderers::for_result("Pane B", v),
                                         true,
                                     );
                                 }
@@ -1638,23 +2433,15 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     if let Some(ps) = &mut state.panel {
                         match pane {
                             super::ui::PanelPane::A => {
-                                ps.a.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                )
+                                ps.a.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v))
                             }
                             super::ui::PanelPane::B => {
-                                ps.b.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                );
+                                ps.b.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v));
                                 super::ui::pane_b_replace_with_widget(
                                     state,
-                                    Box::new(
-                                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                                            "Pane B", v,
-                                        ),
-                                    ),
+                                    crate::widgets::renderers::for_result("Pane B", v),
                                     true,
                                 );
                             }
@@ -1667,23 +2454,15 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     if let Some(ps) = &mut state.panel {
                         match pane {
                             super::ui::PanelPane::A => {
-                                ps.a.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                )
+                                ps.a.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v))
                             }
                             super::ui::PanelPane::B => {
-                                ps.b.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&v)
-                                        .unwrap_or_else(|_| v.to_string()),
-                                );
+                                ps.b.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&v));
                                 super::ui::pane_b_replace_with_widget(
                                     state,
-                                    Box::new(
-                                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                                            "Pane B", v,
-                                        ),
-                                    ),
+                                    crate::widgets::renderers::for_result("Pane B", v),
                                     true,
                                 );
                             }
@@ -1693,9 +2472,8 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 Err(e) => {
                     if let Some(ps) = &mut state.panel {
                         if let super::ui::PaneContent::Widget(ref mut w) = ps.b_content {
-                            if let Some(fw) = w
-                                .as_any_mut()
-                                .downcast_mut::<crate::widgets::form_widget::FormWidget>()
+                            if let Some(fw) =
+                                crate::widgets::wizard::current_form_mut(w.as_any_mut())
                             {
                                 fw.form.disabled = false;
                                 fw.form.message = Some(e);
@@ -1710,12 +2488,11 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 }
             }
         }
-        LoadedFormOptions { key, outcome } => {
+        LoadedFormOptions { field, outcome } => {
             // Clear any transient status like "Refreshing options"
             state.status_text = None;
             state.status_percent = None;
-            // key format: "form:opt:<field_name>"
-            let field_name = key.strip_prefix("form:opt:").unwrap_or(&key).to_string();
+            let field_name = field;
             match outcome {
                 Ok(LoadOutcome::Fallback(v)) => {
                     let opts = v
@@ -1749,9 +2526,8 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                     }
                     if let Some(ps) = &mut state.panel {
                         if let super::ui::PaneContent::Widget(ref mut w) = ps.b_content {
-                            if let Some(fw) = w
-                                .as_any_mut()
-                                .downcast_mut::<crate::widgets::form_widget::FormWidget>()
+                            if let Some(fw) =
+                                crate::widgets::wizard::current_form_mut(w.as_any_mut())
                             {
                                 if let Some(fld) =
                                     fw.form.fields.iter_mut().find(|f| f.name == field_name)
@@ -1764,11 +2540,18 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                             selected,
                                             offset,
                                         } => {
+                                            // Stale-while-revalidate: keep the current choice if it still exists
+                                            let prev = vals.get(*selected).cloned();
+                                            let keep = prev
+                                                .and_then(|p| values.iter().position(|x| *x == p))
+                                                .unwrap_or(0);
                                             *options = labels;
                                             *vals = values;
-                                            *cursor = 0;
-                                            *selected = 0;
-                                            *offset = 0;
+                                            *cursor = keep;
+                                            *selected = keep;
+                                            *offset = keep.saturating_sub(
+                                                crate::widgets::form::OPTIONS_VISIBLE - 1,
+                                            );
                                             fld.error = None;
                                             fld.dyn_loaded = true;
                                             fld.dyn_loaded_at = Some(Instant::now());
@@ -1780,11 +2563,19 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                             selected,
                                             offset,
                                         } => {
+                                            // Stale-while-revalidate: carry over checked values still offered
+                                            let prev: Vec<String> = vals
+                                                .iter()
+                                                .zip(selected.iter())
+                                                .filter(|(_, on)| **on)
+                                                .map(|(v, _)| v.clone())
+                                                .collect();
+                                            *selected =
+                                                values.iter().map(|v| prev.contains(v)).collect();
                                             *options = labels.clone();
                                             *vals = values;
                                             *cursor = 0;
                                             *offset = 0;
-                                            *selected = vec![false; options.len()];
                                             fld.error = None;
                                             fld.dyn_loaded = true;
                                             fld.dyn_loaded_at = Some(Instant::now());
@@ -1792,6 +2583,7 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                                         _ => {}
                                     }
                                 }
+                                effects.extend(fw.options_finished(&field_name));
                             }
                         }
                     }
@@ -1802,15 +2594,16 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
                 Err(e) => {
                     if let Some(ps) = &mut state.panel {
                         if let super::ui::PaneContent::Widget(ref mut w) = ps.b_content {
-                            if let Some(fw) = w
-                                .as_any_mut()
-                                .downcast_mut::<crate::widgets::form_widget::FormWidget>()
+                            if let Some(fw) =
+                                crate::widgets::wizard::current_form_mut(w.as_any_mut())
                             {
                                 if let Some(fld) =
                                     fw.form.fields.iter_mut().find(|f| f.name == field_name)
                                 {
-                                    fld.error = Some(e);
+                                    // Keep previously loaded options; surface the failure on the field
+                                    fld.error = Some(format!("Options failed: {e}"));
                                 }
+                                effects.extend(fw.options_finished(&field_name));
                             }
                         }
                     }
@@ -1818,40 +2611,598 @@ pub fn update(state: &mut AppState, msg: AppMsg) -> Vec<Effect> {
             }
         }
         StreamProgress { text, percent } => {
+            if let Some(sv) = pane_b_stream(state) {
+                sv.progress(text.clone(), percent);
+            }
             state.status_text = text;
             state.status_percent = percent;
             // Restart animation when progress starts
-            if state.animations_enabled {
-                state.animation_start_tick = state.tick;
+            if state.motion() {
+                state.animation_start_frame = state.frame();
             }
         }
         StreamDone { result, err } => {
             state.status_text = None;
             state.status_percent = None;
-            if let Some(e) = err {
+            state.stream_handle = None;
+            if std::mem::take(&mut state.stream_in_panel) {
+                effects.extend(stream_done_in_panel(state, result, err));
+            } else if let Some(key) = crate::ui::parked_stream_tab(state) {
+                // Started in the panel of another tab: its viewer ends there
+                let done = crate::ui::in_parked_tab(state, &key, |st| {
+                    st.stream_in_panel = false;
+                    stream_done_in_panel(st, result, err)
+                });
+                let done = done.unwrap_or_default();
+                if done.is_empty() {
+                    let title = state.stream_title.clone().unwrap_or_default();
+                    effects.push(Effect::ShowToast {
+                        text: format!("{title} ended — its tab shows the result"),
+                        level: crate::ui::ToastLevel::Info,
+                        seconds: 4,
+                    });
+                }
+                effects.extend(done);
+            } else if let Some(e) = err {
                 state.dbg(format!("stream error: {e}"));
                 state.last_error = Some(e);
                 state.last_json_pretty = None;
                 state.json_scroll_y = 0;
                 state.view = super::ui::View::Json;
                 state.json_viewer = None;
+                state.json_rendered = None;
             } else if let Some(v) = result {
                 state.dbg("stream done".to_string());
                 state.last_error = None;
-                state.last_json_pretty =
-                    Some(serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string()));
+                state.last_json_pretty = Some(crate::widgets::large_json::stored_pretty(&v));
+                if let Some(source) = state.stream_source.clone() {
+                    state.observe_watches(&source, &v);
+                }
+                let title = state
+                    .stream_title
+                    .clone()
+                    .unwrap_or_else(|| "JSON Output".to_string());
+                state
+                    .results
+                    .push(title, state.stream_source.clone(), v.clone());
                 // Seed pretty JSON viewer for global results
-                state.json_viewer = Some(crate::widgets::result_viewer::ResultViewerWidget::new(
-                    "JSON Output",
-                    v,
-                ));
+                crate::ui::show_json_result(state, "JSON Output", v);
                 state.json_scroll_y = 0;
                 state.view = super::ui::View::Json;
             }
         }
-    }
-    effects
-}
+        StreamCancelled => {
+            state.status_text = None;
+            state.status_percent = None;
+            state.stream_handle = None;
+            let title = state
+                .stream_title
+                .clone()
+                .unwrap_or_else(|| "stream".to_string());
+            state.dbg(format!("stream cancelled: {title}"));
+            let cancel_viewer = |st: &mut AppState| {
+                st.stream_in_panel = false;
+                if let Some(sv) = pane_b_stream(st) {
+                    sv.cancel();
+                }
+            };
+            if state.stream_in_panel {
+                cancel_viewer(state);
+            } else if let Some(key) = crate::ui::parked_stream_tab(state) {
+                crate::ui::in_parked_tab(state, &key, cancel_viewer);
+            }
+            effects.push(Effect::ShowToast {
+                text: format!("Cancelled: {title}"),
+                level: crate::ui::ToastLevel::Info,
+                seconds: 3,
+            });
+        }
+        PreflightDone { index, outcome } => preflight_done(state, index, outcome),
+        AuthChecked { outcome } => match outcome {
+            Ok(_) => state.dbg("auth check ok"),
+            Err(e) => {
+                let reason = format!("Not logged in: {}", e.lines().next().unwrap_or_default());
+                crate::ui::offer_login(state, reason);
+            }
+        },
+        AuthLoginDone { outcome } => effects = auth_login_done(state, outcome),
+        AuthRefreshed { outcome } => effects = auth_refreshed(state, outcome),
+        BatchRowDone { index, outcome } => {
+            let result = match outcome {
+                Ok(LoadOutcome::Fallback(v)) => {
+                    crate::widgets::form_batch::envelope_error(&v).map_or(Ok(()), Err)
+                }
+                Ok(_) => Ok(()),
+                Err(e) => Err(e.lines().next().unwrap_or("failed").to_string()),
+            };
+            state.dbg(format!("batch row {} done: {result:?}", index + 1));
+            let form = match state.panel.as_mut().map(|ps| &mut ps.b_content) {
+                Some(super::ui::PaneContent::Widget(w)) => {
+                    crate::widgets::wizard::current_form_mut(w.as_any_mut())
+                }
+                _ => None,
+            };
+            let Some(fw) = form else {
+                return effects;
+            };
+            let Some((done, total)) = fw.batch_row_done(index, result) else {
+                return effects;
+            };
+            if done < total {
+                state.status_text = Some(format!("Batch: {done}/{total}"));
+                state.status_percent = Some(done as f64 * 100.0 / total as f64);
+            } else {
+                let (ok, failed) = fw.batch_counts();
+                state.status_text = None;
+                state.status_percent = None;
+                effects.push(Effect::ShowToast {
+                    text: format!("Batch finished: {ok} ok, {failed} failed or skipped"),
+                    level: if failed == 0 {
+                        crate::ui::ToastLevel::Success
+                    } else {
+                        crate::ui::ToastLevel::Error
+                    },
+                    seconds: 4,
+                });
+            }
+        }
+        RowUpdated { outcome } => {
+            let reply = outcome.map(|o| match o {
+                LoadOutcome::Fallback(v) => v,
+                _ => JsonValue::Null,
+            });
+            // A board card moved with `move_cmd`
+            if let Some(board) = match state.panel.as_mut().map(|ps| &mut ps.b_content) {
+                Some(super::ui::PaneContent::Widget(w)) => {
+                    w.as_any_mut()
+                        .downcast_mut::<crate::widgets::board::BoardWidget>()
+                }
+                _ => None,
+            } {
+                effects.push(match board.move_finished(reply) {
+                    Ok(moved) => Effect::ShowToast {
+                        text: format!("Moved {moved}"),
+                        level: crate::ui::ToastLevel::Success,
+                        seconds: 2,
+                    },
+                    Err(e) => Effect::ShowToast {
+                        text: format!("Move failed: {}", e.lines().next().unwrap_or_default()),
+                        level: crate::ui::ToastLevel::Error,
+                        seconds: 4,
+                    },
+                });
+                return effects;
+            }
+            let viewer = match state.panel.as_mut().map(|ps| &mut ps.b_content) {
+                Some(super::ui::PaneContent::Widget(w)) => {
+                    w.as_any_mut()
+                        .downcast_mut::<crate::widgets::result_viewer::ResultViewerWidget>()
+                }
+                _ => None,
+            };
+            let result = match viewer {
+                Some(rv) => rv.row_updated(reply),
+                None => reply.map(|_| String::new()),
+            };
+            effects.push(match result {
+                Ok(field) => {
+                    state.dbg(format!("row update ok: {field}"));
+                    Effect::ShowToast {
+                        text: if field.is_empty() {
+                            "Updated".into()
+                        } else {
+                            format!("Updated {field}")
+                        },
+                        level: crate::ui::ToastLevel::Success,
+                        seconds: 2,
+                    }
+                }
+                Err(e) => {
+                    state.dbg(format!("row update failed: {e}"));
+                    Effect::ShowToast {
+                        text: format!("Update failed: {}", e.lines().next().unwrap_or_default()),
+                        level: crate::ui::ToastLevel::Error,
+                        seconds: 4,
+                    }
+                }
+            });
+        }
+        Publish { slot, value } => {
+            if !state.slots.set(&slot, value.clone()) {
+                return effects;
+            }
+            state.dbg(format!("slot {slot} changed"));
+            if let Some(super::ui::PaneContent::Widget(w)) =
+                state.panel.as_mut().map(|ps| &mut ps.b_content)
+            {
+                if let Some(pq) = w
+                    .as_any_mut()
+                    .downcast_mut::<crate::widgets::promql::PromqlWidget>()
+                {
+                    pq.set_slot(&slot, &value);
+                }
+            }
+            for (target, prev, cmdline) in state.slots.rerun(&slot) {
+                let Some(ps) = &mut state.panel else {
+                    continue;
+                };
+                match (target, &mut ps.b_content) {
+                    (SlotTarget::PaneB, super::ui::PaneContent::Widget(w)) => {
+                        // The reply matches the new source, so it updates the viewer in place
+                        if let Some(rv) = w
+                            .as_any_mut()
+                            .downcast_mut::<crate::widgets::result_viewer::ResultViewerWidget>()
+                            .filter(|rv| rv.source() == Some(prev.as_str()))
+                        {
+                            rv.set_source(Some(cmdline.clone()));
+                            effects.push(Effect::LoadPanelCmd {
+                                pane: super::ui::PanelPane::B,
+                                cmdline,
+                            });
+                        }
+                    }
+                    (SlotTarget::Nested(subpane), super::ui::PaneContent::Panel(_)) => {
+                        effects.push(Effect::LoadNestedCmd { subpane, cmdline });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        ScheduledDone { id, outcome } => {
+            // Results are not shown in a pane: the user may be anywhere by now
+            let (ok, summary) = match outcome {
+                Ok(LoadOutcome::Fallback(v)) => scheduled_summary(&v),
+                Ok(_) => (true, "ok".to_string()),
+                Err(e) => (false, e.lines().next().unwrap_or("failed").to_string()),
+            };
+            let done = state
+                .scheduler
+                .finish(id, ok, summary, std::time::SystemTime::now());
+            state.dbg(format!("scheduled #{id} done ok={ok}: {}", done.summary));
+            effects.push(Effect::ShowToast {
+                text: format!(
+                    "Scheduled '{}' {}: {}",
+                    done.title,
+                    if ok { "finished" } else { "failed" },
+                    done.summary
+                ),
+                level: if ok {
+                    crate::ui::ToastLevel::Success
+                } else {
+                    crate::ui::ToastLevel::Error
+                },
+                seconds: 4,
+            });
+        }
+    }
+    effects
+}
+
+// Error text carried by a load/stream result, if it failed
+fn msg_error(msg: &AppMsg) -> Option<&str> {
+    use AppMsg::*;
+    match msg {
+        LoadedMenu { outcome, .. }
+        | LoadedChild { outcome, .. }
+        | LoadedPanel { outcome, .. }
+        | LoadedNested { outcome, .. }
+        | LoadedSubmitForm { outcome, .. }
+        | ScheduledDone { outcome, .. } => outcome.as_ref().err().map(|e| e.as_str()),
+        StreamDone { err, .. } => err.as_deref(),
+        _ => None,
+    }
+}
+
+// What to run again once logged in or re-authenticated: the list whose load failed, or the item that opened
+// Pane B or the stream (scheduled jobs and form submits are not re-run)
+fn failed_target(state: &AppState, msg: &AppMsg) -> Option<crate::ui::RetryTarget> {
+    use crate::ui::RetryTarget;
+    match msg {
+        AppMsg::LoadedMenu { key, .. } => state
+            .config
+            .menu
+            .iter()
+            .find(|mi| crate::nav::keys::menu_key(mi) == *key)
+            .cloned()
+            .map(RetryTarget::MenuList),
+        AppMsg::LoadedChild { key, .. } => {
+            crate::nav::pagination::child_value(&state.children, key).map(|val| {
+                RetryTarget::ChildList {
+                    key: key.clone(),
+                    val,
+                }
+            })
+        }
+        AppMsg::LoadedPanel { .. } | AppMsg::LoadedNested { .. } | AppMsg::StreamDone { .. } => {
+            state.pane_origin.clone()
+        }
+        _ => None,
+    }
+}
+
+// Open or reload a retry target again
+pub(crate) fn retry(state: &mut AppState, target: crate::ui::RetryTarget) -> Vec<Effect> {
+    use crate::ui::RetryTarget;
+    match target {
+        RetryTarget::Menu(mi) => update(state, AppMsg::EnterMenu(mi)),
+        RetryTarget::Child { key, val } => update(state, AppMsg::EnterChild { key, val }),
+        RetryTarget::MenuList(mi) => update(state, AppMsg::RefreshMenu(mi)),
+        RetryTarget::ChildList { key, val } => update(state, AppMsg::RefreshChild { key, val }),
+    }
+}
+
+fn auth_login_done(state: &mut AppState, outcome: Result<LoadOutcome, String>) -> Vec<Effect> {
+    match outcome {
+        Ok(_) => {
+            state.auth_prompt = None;
+            // Items shown only when logged in may appear now
+            let mut effects = vec![
+                Effect::ShowToast {
+                    text: "Logged in".into(),
+                    level: crate::ui::ToastLevel::Success,
+                    seconds: 3,
+                },
+                Effect::CheckConditions,
+            ];
+            if let Some(target) = state.login_retry.take() {
+                effects.extend(retry(state, target));
+            }
+            effects
+        }
+        Err(e) => {
+            if let Some(prompt) = state.auth_prompt.as_mut() {
+                prompt.running = false;
+                prompt.error = Some(e);
+            }
+            Vec::new()
+        }
+    }
+}
+
+// `auth_retry:` the refresh finished: the expired item runs again, once
+fn auth_refreshed(state: &mut AppState, outcome: Result<LoadOutcome, String>) -> Vec<Effect> {
+    let target = state.refresh_retry.take();
+    if let Err(e) = outcome {
+        return vec![Effect::ShowToast {
+            text: format!("Re-authentication failed: {e}"),
+            level: crate::ui::ToastLevel::Error,
+            seconds: 4,
+        }];
+    }
+    let mut effects = vec![Effect::ShowToast {
+        text: "Re-authenticated — retrying".into(),
+        level: crate::ui::ToastLevel::Success,
+        seconds: 3,
+    }];
+    if let Some(target) = target {
+        effects.extend(retry(state, target));
+    }
+    // Entering reset it; the retry's own expiry is reported instead of refreshing again
+    state.refresh_retried = true;
+    effects
+}
+
+// Record a startup check result; the banner and item blocking read state.preflight
+fn preflight_done(state: &mut AppState, index: usize, outcome: Result<LoadOutcome, String>) {
+    let Some(status) = state.preflight.get_mut(index) else {
+        return;
+    };
+    status.pending = false;
+    status.failure = outcome.err();
+    let msg = match &status.failure {
+        Some(e) => format!("preflight '{}' failed: {e}", status.check.title),
+        None => format!("preflight '{}' ok", status.check.title),
+    };
+    state.dbg(msg);
+}
+
+// (ok, one-line summary) of a scheduled run's `{ ok, data | error }` envelope
+fn scheduled_summary(v: &JsonValue) -> (bool, String) {
+    let env = match Envelope::parse(v) {
+        Ok(env) => env,
+        Err(e) => return (false, e),
+    };
+    if env.failed() {
+        return (false, env.message().unwrap_or("failed").to_string());
+    }
+    let summary = match &env.data {
+        JsonValue::String(s) => Some(s.clone()),
+        d => d.get("message").and_then(|m| m.as_str()).map(String::from),
+    };
+    (true, summary.unwrap_or_else(|| "ok".to_string()))
+}
+
+// Start background loading of dynamic select options for a form shown in Pane B
+fn form_prefetch_effects(state: &mut AppState) -> Vec<Effect> {
+    if let Some(ps) = &mut state.panel {
+        if let super::ui::PaneContent::Widget(ref mut w) = ps.b_content {
+            if let Some(fw) = crate::widgets::wizard::current_form_mut(w.as_any_mut()) {
+                return fw.prefetch_options();
+            }
+        }
+    }
+    Vec::new()
+}
+
+// Apply the configured focus_on_load policy and flash the pane that just received content
+// Board of a `widget: board` item; its cards come from `cmdline`
+fn board_widget(mi: &MenuItem, cmdline: &str) -> crate::widgets::board::BoardWidget {
+    crate::widgets::board::BoardWidget::new(
+        mi.pane_b_title.clone().unwrap_or_else(|| mi.title.clone()),
+        cmdline.to_string(),
+        mi.status_field.clone(),
+        mi.board_columns.clone().unwrap_or_default(),
+        mi.move_cmd.clone(),
+    )
+}
+
+// Tree of a `widget: tree` item; its roots come from the item's command
+fn tree_widget(mi: &MenuItem) -> Option<crate::widgets::tree::TreeWidget> {
+    let cmdline = mi.command.clone()?;
+    Some(
+        crate::widgets::tree::TreeWidget::new(
+            mi.pane_b_title.clone().unwrap_or_else(|| mi.title.clone()),
+            mi.children_key.clone(),
+            mi.children_cmd.clone(),
+            mi.publish.clone(),
+        )
+        .with_command(cmdline),
+    )
+}
+
+// Process list of a `widget: processes` item
+fn processes_widget(mi: &MenuItem) -> crate::widgets::processes::ProcessesWidget {
+    crate::widgets::processes::ProcessesWidget::new(
+        mi.pane_b_title.clone().unwrap_or_else(|| mi.title.clone()),
+        mi.pattern.as_deref(),
+        mi.refresh_secs,
+    )
+}
+
+// Reachability dashboard of a `widget: ports` item
+fn ports_widget(mi: &MenuItem) -> crate::widgets::ports::PortsWidget {
+    crate::widgets::ports::PortsWidget::new(
+        mi.pane_b_title.clone().unwrap_or_else(|| mi.title.clone()),
+        mi.targets.as_deref().unwrap_or_default(),
+        mi.refresh_secs,
+        mi.timeout_ms,
+    )
+}
+
+// Health dashboard of a `widget: http_checks` item
+fn http_checks_widget(mi: &MenuItem) -> crate::widgets::http_checks::HttpChecksWidget {
+    crate::widgets::http_checks::HttpChecksWidget::new(
+        mi.pane_b_title.clone().unwrap_or_else(|| mi.title.clone()),
+        mi.checks.as_deref().unwrap_or_default(),
+        mi.refresh_secs,
+        mi.timeout_ms,
+    )
+}
+
+// Tail of a `widget: log_viewer` item: its `command:`, else its `path:` (relative to the
+// config dir)
+fn log_viewer_widget(mi: &MenuItem) -> crate::widgets::log_viewer::LogViewerWidget {
+    use crate::widgets::log_viewer::{LogSource, LogViewerWidget};
+    let title = mi.pane_b_title.clone().unwrap_or_else(|| mi.title.clone());
+    if let Some(cmd) = mi.command.as_deref().filter(|c| !c.is_empty()) {
+        return LogViewerWidget::new(title, LogSource::Command(cmd.to_string()));
+    }
+    let path = mi.path.clone().unwrap_or_default();
+    let pb = std::path::PathBuf::from(&path);
+    let full = if pb.is_absolute() {
+        pb
+    } else if let Ok(dir) = std::env::var("CHI_TUI_CONFIG_DIR") {
+        std::path::PathBuf::from(dir).join(&path)
+    } else {
+        std::env::current_dir()
+            .unwrap_or_else(|_| std::path::PathBuf::from("."))
+            .join(&path)
+    };
+    LogViewerWidget::new(title, LogSource::File(full))
+}
+
+// Metrics dashboard of a `widget: promql` item, starting from the current slot values
+fn promql_widget(mi: &MenuItem, state: &AppState) -> crate::widgets::promql::PromqlWidget {
+    crate::widgets::promql::PromqlWidget::new(
+        mi.pane_b_title.clone().unwrap_or_else(|| mi.title.clone()),
+        mi.prometheus.as_deref(),
+        mi.queries.as_deref().unwrap_or_default(),
+        state.slots.values().clone(),
+        mi.refresh_secs,
+        mi.timeout_ms,
+    )
+}
+
+// Runbook page of a `widget: runbook` item, from `content:` or `path:`
+fn runbook_widget(mi: &MenuItem) -> crate::widgets::runbook::RunbookWidget {
+    use crate::widgets::runbook::RunbookWidget;
+    let title = mi
+        .pane_b_title
+        .clone()
+        .unwrap_or_else(|| format!("Runbook — {}", mi.title));
+    if let Some(text) = &mi.content {
+        return RunbookWidget::from_text(title, text);
+    }
+    let Some(path) = &mi.path else {
+        return RunbookWidget::from_text(title, "");
+    };
+    let pb = std::path::PathBuf::from(path);
+    let full = if pb.is_absolute() {
+        pb
+    } else if let Ok(dir) = std::env::var("CHI_TUI_CONFIG_DIR") {
+        std::path::PathBuf::from(dir).join(path)
+    } else {
+        std::env::current_dir()
+            .unwrap_or_else(|_| std::path::PathBuf::from("."))
+            .join(path)
+    };
+    RunbookWidget::from_path(title, &full)
+}
+
+// Generated markdown page for items with `builtin:` (None for regular markdown items)
+fn builtin_markdown_widget(
+    state: &AppState,
+    mi: &MenuItem,
+    title: &str,
+) -> Option<crate::widgets::markdown::MarkdownWidget> {
+    let name = mi.builtin.as_deref()?;
+    let text = crate::chi_core::builtins::builtin_markdown(name, &state.config, &state.tab_order)
+        .unwrap_or_else(|| format!("# Error\nUnknown builtin page: {name}"));
+    Some(
+        crate::widgets::markdown::MarkdownWidget::from_text(title, &text)
+            .with_export_name(crate::chi_core::builtins::export_file_name(name)),
+    )
+}
+
+fn focus_after_load(
+    state: &mut AppState,
+    pane: super::ui::PanelPane,
+    nested: Option<super::ui::PanelPane>,
+) {
+    let policy = crate::chi_core::focus::FocusOnLoad::parse(
+        state
+            .panel_focus_on_load
+            .as_deref()
+            .or(state.config.focus_on_load.as_deref()),
+    );
+    let mut focus =
+        crate::chi_core::focus::FocusState::new(state.panel_focus, state.panel_nested_focus);
+    focus.apply_load(policy, pane, nested);
+    state.panel_focus = focus.panel_focus;
+    state.panel_nested_focus = focus.nested_focus;
+    state.pane_flash = Some(super::ui::PaneFlash {
+        pane,
+        until_tick: state.tick + super::ui::PANE_FLASH_TICKS,
+    });
+}
+
+// Error shown in place of a pane's content (Pane B replaces its widget so it is visible)
+fn show_pane_error(state: &mut AppState, pane: super::ui::PanelPane, msg: String) {
+    let Some(ps) = &mut state.panel else {
+        return;
+    };
+    match pane {
+        super::ui::PanelPane::A => {
+            ps.a.last_error = Some(msg);
+            ps.a.last_json_pretty = None;
+        }
+        super::ui::PanelPane::B => {
+            ps.b.last_error = Some(msg.clone());
+            ps.b.last_json_pretty = None;
+            let title = state
+                .pane_b_title
+                .clone()
+                .unwrap_or_else(|| "Pane B".to_string());
+            super::ui::pane_b_replace_with_widget(
+                state,
+                Box::new(crate::widgets::json_viewer::JsonViewerWidget::from_text(
+                    title, msg,
+                )),
+                true,
+            );
+        }
+    }
+}
 
 fn pane_yaml_effect(pane: super::ui::PanelPane, v: &JsonValue) -> Option<Effect> {
     // Route through the widget registry for known specs
@@ -1862,165 +3213,255 @@ fn pane_yaml_effect(pane: super::ui::PanelPane, v: &JsonValue) -> Option<Effect>
     None
 }
 
+// Stream viewer in Pane B still waiting for its stream
+fn pane_b_stream(
+    state: &mut AppState,
+) -> Option<&mut crate::widgets::stream_viewer::StreamViewerWidget> {
+    match state.panel.as_mut().map(|ps| &mut ps.b_content) {
+        Some(super::ui::PaneContent::Widget(w)) => w
+            .as_any_mut()
+            .downcast_mut::<crate::widgets::stream_viewer::StreamViewerWidget>()
+            .filter(|sv| sv.running()),
+        _ => None,
+    }
+}
+
+// A stream started in panel mode ends in Pane B: its result replaces the stream viewer, a
+// failure stays on it under the progress log
+fn stream_done_in_panel(
+    state: &mut AppState,
+    result: Option<JsonValue>,
+    err: Option<String>,
+) -> Vec<Effect> {
+    let title = state
+        .stream_title
+        .clone()
+        .unwrap_or_else(|| "Stream".to_string());
+    if let Some(e) = err {
+        state.dbg(format!("stream error: {e}"));
+        return match pane_b_stream(state) {
+            Some(sv) => {
+                sv.fail(e);
+                Vec::new()
+            }
+            None => vec![Effect::ShowToast {
+                text: format!("{title} failed: {e}"),
+                level: crate::ui::ToastLevel::Error,
+                seconds: 5,
+            }],
+        };
+    }
+    let Some(v) = result else {
+        return Vec::new();
+    };
+    state.dbg("stream done (Pane B)".to_string());
+    state
+        .results
+        .push(title.clone(), state.stream_source.clone(), v.clone());
+    if pane_b_stream(state).is_none() {
+        // Pane B moved on meanwhile: the result waits on the Results screen
+        if let Some(source) = state.stream_source.clone() {
+            state.observe_watches(&source, &v);
+        }
+        return vec![Effect::ShowToast {
+            text: format!("{title} finished — H shows the result"),
+            level: crate::ui::ToastLevel::Success,
+            seconds: 4,
+        }];
+    }
+    if let Some(ps) = &mut state.panel {
+        ps.b.last_error = None;
+        ps.b.last_json_pretty = Some(crate::widgets::large_json::stored_pretty(&v));
+    }
+    state.pane_b_source = state.stream_source.clone();
+    super::ui::pane_b_replace_with_widget(
+        state,
+        crate::widgets::renderers::for_result(title, v),
+        false,
+    );
+    Vec::new()
+}
+
+// Watchdog session key of a panel pane: Pane B shares the menu item's key
+pub(crate) fn pane_session_key(menu_key: String, pane: super::ui::PanelPane) -> String {
+    match pane {
+        super::ui::PanelPane::A => format!("{menu_key}/pane:A"),
+        super::ui::PanelPane::B => menu_key,
+    }
+}
+
+// Pane B content goes through its history; a widget for Pane A takes the place of the menu
+fn place_pane_widget(
+    state: &mut AppState,
+    pane: super::ui::PanelPane,
+    w: Box<dyn crate::widgets::Widget>,
+) {
+    match pane {
+        super::ui::PanelPane::A => {
+            if let Some(ps) = &mut state.panel {
+                ps.a_content = Some(w);
+            }
+        }
+        super::ui::PanelPane::B => super::ui::pane_b_replace_with_widget(state, w, true),
+    }
+}
+
 fn apply_pane_loaded_yaml(pane: super::ui::PanelPane, v: &JsonValue, state: &mut AppState) -> bool {
     // Special-case: Watchdog spec inside Panel YAML should reuse an existing session
     // based on the current top-level menu key so that closing and re-entering the
-    // panel re-attaches instead of restarting the processes.
-    if matches!(pane, super::ui::PanelPane::B) {
-        let is_watchdog_spec = v
-            .get("type")
-            .and_then(|s| s.as_str())
-            .map(|t| t.eq_ignore_ascii_case("watchdog"))
-            .unwrap_or_else(|| {
-                v.get("widget")
-                    .and_then(|s| s.as_str())
-                    .map(|t| t.eq_ignore_ascii_case("watchdog"))
-                    .unwrap_or(false)
-            });
-        if is_watchdog_spec {
-            if let Some(_ps) = &mut state.panel {
-                // Build title + config (align with registry logic)
-                let default_title = "Pane B — Watchdog".to_string();
-                let title = v
-                    .get("title")
+    // panel re-attaches instead of restarting the processes. Pane A keeps its own session
+    // (`<key>/pane:A`) so a dev server watchdog there survives Pane B browsing.
+    let is_watchdog_spec = v
+        .get("type")
+        .and_then(|s| s.as_str())
+        .map(|t| t.eq_ignore_ascii_case("watchdog"))
+        .unwrap_or_else(|| {
+            v.get("widget")
+                .and_then(|s| s.as_str())
+                .map(|t| t.eq_ignore_ascii_case("watchdog"))
+                .unwrap_or(false)
+        });
+    if is_watchdog_spec {
+        if let Some(_ps) = &mut state.panel {
+            // Build title + config (align with registry logic)
+            let default_title = match pane {
+                super::ui::PanelPane::A => "Pane A — Watchdog".to_string(),
+                super::ui::PanelPane::B => "Pane B — Watchdog".to_string(),
+            };
+            let title = v
+                .get("title")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or(default_title);
+            let cmds: Vec<String> = v
+                .get("commands")
+                .and_then(|a| a.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|x| x.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let sequential = v
+                .get("sequential")
+                .and_then(|b| b.as_bool())
+                .unwrap_or(false);
+            let auto_restart = v
+                .get("auto_restart")
+                .and_then(|b| b.as_bool())
+                .unwrap_or(false);
+            let max_retries = v.get("max_retries").and_then(|n| n.as_u64()).unwrap_or(0) as usize;
+            let restart_delay_ms = v
+                .get("restart_delay_ms")
+                .and_then(|n| n.as_u64())
+                .unwrap_or(1000);
+            let stop_on_failure = v
+                .get("stop_on_failure")
+                .and_then(|b| b.as_bool())
+                .unwrap_or(false);
+            let allowed_exit_codes: Vec<i32> = v
+                .get("allowed_exit_codes")
+                .and_then(|a| a.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|x| x.as_i64().map(|i| i as i32))
+                        .collect()
+                })
+                .unwrap_or_else(|| vec![0]);
+            let on_panic_exit_cmd = v
+                .get("on_panic_exit_cmd")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string());
+            let stats = v
+                .get("stats")
+                .and_then(|a| a.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|x| {
+                            let label = x
+                                .get("label")
+                                .and_then(|s| s.as_str())
+                                .unwrap_or("")
+                                .to_string();
+                            let regexp = x
+                                .get("regexp")
+                                .and_then(|s| s.as_str())
+                                .unwrap_or("")
+                                .to_string();
+                            if !label.is_empty() && !regexp.is_empty() {
+                                Some(crate::widgets::watchdog::WatchdogStatSpec { label, regexp })
+                            } else {
+                                None
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let cfg = crate::widgets::watchdog::WatchdogConfig {
+                sequential,
+                auto_restart,
+                max_retries,
+                restart_delay_ms,
+                allowed_exit_codes,
+                stop_on_failure,
+                on_panic_exit_cmd,
+                stats,
+                external_check_cmd: v
+                    .get("external_check_cmd")
                     .and_then(|s| s.as_str())
-                    .map(|s| s.to_string())
-                    .unwrap_or(default_title);
-                let cmds: Vec<String> = v
-                    .get("commands")
-                    .and_then(|a| a.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .filter_map(|x| x.as_str().map(|s| s.to_string()))
-                            .collect()
-                    })
-                    .unwrap_or_default();
-                let sequential = v
-                    .get("sequential")
-                    .and_then(|b| b.as_bool())
-                    .unwrap_or(false);
-                let auto_restart = v
-                    .get("auto_restart")
-                    .and_then(|b| b.as_bool())
-                    .unwrap_or(false);
-                let max_retries =
-                    v.get("max_retries").and_then(|n| n.as_u64()).unwrap_or(0) as usize;
-                let restart_delay_ms = v
-                    .get("restart_delay_ms")
-                    .and_then(|n| n.as_u64())
-                    .unwrap_or(1000);
-                let stop_on_failure = v
-                    .get("stop_on_failure")
-                    .and_then(|b| b.as_bool())
-                    .unwrap_or(false);
-                let allowed_exit_codes: Vec<i32> = v
-                    .get("allowed_exit_codes")
-                    .and_then(|a| a.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .filter_map(|x| x.as_i64().map(|i| i as i32))
-                            .collect()
-                    })
-                    .unwrap_or_else(|| vec![0]);
-                let on_panic_exit_cmd = v
-                    .get("on_panic_exit_cmd")
+                    .map(|s| s.to_string()),
+                external_kill_cmd: v
+                    .get("external_kill_cmd")
                     .and_then(|s| s.as_str())
-                    .map(|s| s.to_string());
-                let stats = v
-                    .get("stats")
-                    .and_then(|a| a.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .filter_map(|x| {
-                                let label = x
-                                    .get("label")
-                                    .and_then(|s| s.as_str())
-                                    .unwrap_or("")
-                                    .to_string();
-                                let regexp = x
-                                    .get("regexp")
-                                    .and_then(|s| s.as_str())
-                                    .unwrap_or("")
-                                    .to_string();
-                                if !label.is_empty() && !regexp.is_empty() {
-                                    Some(crate::widgets::watchdog::WatchdogStatSpec {
-                                        label,
-                                        regexp,
-                                    })
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
-                let cfg = crate::widgets::watchdog::WatchdogConfig {
-                    sequential,
-                    auto_restart,
-                    max_retries,
-                    restart_delay_ms,
-                    allowed_exit_codes,
-                    stop_on_failure,
-                    on_panic_exit_cmd,
-                    stats,
-                    external_check_cmd: v
-                        .get("external_check_cmd")
-                        .and_then(|s| s.as_str())
-                        .map(|s| s.to_string()),
-                    external_kill_cmd: v
-                        .get("external_kill_cmd")
-                        .and_then(|s| s.as_str())
-                        .map(|s| s.to_string()),
-                };
+                    .map(|s| s.to_string()),
+                targets: crate::widgets::watchdog::targets::from_value(v),
+            };
 
-                // Determine parent menu key for session reuse
-                let parent_key_opt = {
-                    let nodes = crate::nav::flatten::flatten_nodes(state);
-                    if let Some(crate::ui::FlatNode::Menu { idx, depth }) =
-                        nodes.get(state.selected)
-                    {
-                        if *depth == 0 {
-                            let mi = &state.config.menu[*idx];
-                            Some(crate::nav::keys::menu_key(mi))
-                        } else {
-                            None
-                        }
+            // Determine parent menu key for session reuse
+            let parent_key_opt = {
+                let nodes = crate::nav::flatten::flatten_nodes(state);
+                if let Some(crate::ui::FlatNode::Menu { idx, depth }) = nodes.get(state.selected) {
+                    if *depth == 0 {
+                        let mi = &state.config.menu[*idx];
+                        Some(crate::nav::keys::menu_key(mi))
                     } else {
                         None
                     }
-                };
-                if let Some(parent_key) = parent_key_opt {
-                    // Reuse if present; else create and register
-                    let (session, reused) =
-                        if let Some(s) = state.watchdog_sessions.get(&parent_key).cloned() {
-                            (s, true)
-                        } else {
-                            let s = crate::widgets::watchdog::WatchdogSession::create(
-                                cmds.clone(),
-                                cfg.clone(),
-                            );
-                            state
-                                .watchdog_sessions
-                                .insert(parent_key.clone(), s.clone());
-                            (s, false)
-                        };
-                    if reused {
-                        state.dbg(format!("watchdog(panel): reusing session for {parent_key}"));
+                } else {
+                    None
+                }
+            };
+            if let Some(parent_key) = parent_key_opt.map(|k| pane_session_key(k, pane)) {
+                // Reuse if present; else create and register
+                let (session, reused) =
+                    if let Some(s) = state.watchdog_sessions.get(&parent_key).cloned() {
+                        (s, true)
                     } else {
-                        state.dbg(format!(
-                            "watchdog(panel): creating session for {parent_key}"
-                        ));
-                    }
-                    // Attach widget to the session so processes are not restarted
-                    super::ui::pane_b_replace_with_widget(
-                        state,
-                        Box::new(crate::widgets::watchdog::WatchdogWidget::from_session(
-                            title, &session,
-                        )),
-                        true,
-                    );
-                    return true;
+                        let s = crate::widgets::watchdog::WatchdogSession::create(
+                            cmds.clone(),
+                            cfg.clone(),
+                        );
+                        state
+                            .watchdog_sessions
+                            .insert(parent_key.clone(), s.clone());
+                        (s, false)
+                    };
+                if reused {
+                    state.dbg(format!("watchdog(panel): reusing session for {parent_key}"));
+                } else {
+                    state.dbg(format!(
+                        "watchdog(panel): creating session for {parent_key}"
+                    ));
                 }
+                // Attach widget to the session so processes are not restarted
+                place_pane_widget(
+                    state,
+                    pane,
+                    Box::new(crate::widgets::watchdog::WatchdogWidget::from_session(
+                        title, &session,
+                    )),
+                );
+                return true;
             }
         }
     }
@@ -2045,7 +3486,7 @@ fn apply_pane_loaded_yaml(pane: super::ui::PanelPane, v: &JsonValue, state: &mut
                         None
                     }
                 };
-                if let Some(parent_key) = parent_key_opt {
+                if let Some(parent_key) = parent_key_opt.map(|k| pane_session_key(k, pane)) {
                     let sess = ww.session_ref();
                     // Keep existing session if any; otherwise register
                     state
@@ -2054,9 +3495,7 @@ fn apply_pane_loaded_yaml(pane: super::ui::PanelPane, v: &JsonValue, state: &mut
                         .or_insert_with(|| sess);
                 }
             }
-            if let super::ui::PanelPane::B = pane {
-                super::ui::pane_b_replace_with_widget(state, w, true);
-            }
+            place_pane_widget(state, pane, w);
             return true;
         }
     }
@@ -2095,8 +3534,7 @@ fn apply_pane_loaded_yaml(pane: super::ui::PanelPane, v: &JsonValue, state: &mut
             if let Some(cmd) = sub.get("cmd").and_then(|s| s.as_str()) {
                 match crate::services::cli_runner::run_cmdline_to_json(cmd) {
                     Ok(j) => {
-                        txt_lines =
-                            serde_json::to_string_pretty(&j).unwrap_or_else(|_| j.to_string());
+                        txt_lines = crate::widgets::large_json::stored_pretty(&j);
                     }
                     Err(e) => {
                         target.last_error = Some(format!("{e}"));
@@ -2112,15 +3550,16 @@ fn apply_pane_loaded_yaml(pane: super::ui::PanelPane, v: &JsonValue, state: &mut
                     base
                 };
                 match std::fs::read_to_string(&full_path) {
-                    Ok(s) => match serde_yaml::from_str::<serde_json::Value>(&s) {
-                        Ok(j) => {
-                            txt_lines =
-                                serde_json::to_string_pretty(&j).unwrap_or_else(|_| j.to_string());
-                        }
-                        Err(e) => {
-                            target.last_error = Some(format!("{e}"));
+                    Ok(s) => {
+                        match crate::chi_core::vars::parse::<serde_json::Value>(&s, &full_path) {
+                            Ok(j) => {
+                                txt_lines = crate::widgets::large_json::stored_pretty(&j);
+                            }
+                            Err(e) => {
+                                target.last_error = Some(format!("{e}"));
+                            }
                         }
-                    },
+                    }
                     Err(e) => {
                         target.last_error = Some(format!("{e}"));
                     }
@@ -2267,6 +3706,9 @@ fn apply_pane_loaded_yaml(pane: super::ui::PanelPane, v: &JsonValue, state: &mut
                                                 .get("external_kill_cmd")
                                                 .and_then(|s| s.as_str())
                                                 .map(|s| s.to_string()),
+                                            targets: crate::widgets::watchdog::targets::from_value(
+                                                spec,
+                                            ),
                                         };
                                         let (session, _reused) = if let Some(s) =
                                             state.watchdog_sessions.get(&sess_key).cloned()
@@ -2336,551 +3778,598 @@ fn apply_pane_loaded_yaml(pane: super::ui::PanelPane, v: &JsonValue, state: &mut
             }
             return true;
         }
-        let title = v
-            .get("title")
-            .and_then(|s| s.as_str())
-            .unwrap_or("Form")
-            .to_string();
-        // Detect command for submit
-        // Priority: submit_cmd | submit.command | command
+        let form = form_state_from_spec(v);
+        if state.panel.is_some() && matches!(pane, super::ui::PanelPane::B) {
+            let mut fw = crate::widgets::form_widget::FormWidget::new(form);
+            fw.preview_mode = crate::widgets::form_widget::PreviewMode::from_spec(v.get("preview"));
+            fw.batch_columns = crate::widgets::form_batch::columns_from_spec(v.get("batch"));
+            fw.mutex = origin_mutex(state);
+            super::ui::pane_b_replace_with_widget(state, Box::new(fw), true);
+        }
+        return true;
+    }
+    // 4) Multi-step form: each step is a form spec, the wizard's submit command gets them all
+    if v.get("type")
+        .and_then(|s| s.as_str())
+        .is_some_and(|s| s.eq_ignore_ascii_case("wizard"))
+    {
+        if let Err(e) = validate_wizard_yaml(v) {
+            if let (Some(ps), super::ui::PanelPane::B) = (&mut state.panel, pane) {
+                ps.b.last_error = Some(e);
+                ps.b.last_json_pretty = None;
+            }
+            return true;
+        }
+        let title = v.get("title").and_then(|s| s.as_str()).unwrap_or("Wizard");
+        // Same priority as a form: submit_cmd | submit.command | command
         let submit_cmd = v
             .get("submit_cmd")
+            .or_else(|| v.get("submit").and_then(|x| x.get("command")))
+            .or_else(|| v.get("command"))
             .and_then(|s| s.as_str())
-            .map(|s| s.to_string())
-            .or_else(|| {
-                v.get("submit")
-                    .and_then(|x| x.get("command"))
-                    .and_then(|s| s.as_str())
-                    .map(|s| s.to_string())
+            .map(|s| s.to_string());
+        let steps = v
+            .get("steps")
+            .and_then(|x| x.as_array())
+            .map(|a| {
+                a.iter()
+                    .enumerate()
+                    .map(|(i, step)| {
+                        let mut form = form_state_from_spec(step);
+                        if step.get("title").is_none() {
+                            form.title = format!("Step {}", i + 1);
+                        }
+                        form
+                    })
+                    .collect()
             })
-            .or_else(|| {
-                v.get("command")
+            .unwrap_or_default();
+        if state.panel.is_some() && matches!(pane, super::ui::PanelPane::B) {
+            let mut wz = crate::widgets::wizard::WizardWidget::new(title, steps, submit_cmd);
+            wz.mutex = origin_mutex(state);
+            super::ui::pane_b_replace_with_widget(state, Box::new(wz), true);
+        }
+        return true;
+    }
+    false
+}
+
+// `mutex:` of the item that opened Pane B; its form submits run under the same mutex
+fn origin_mutex(state: &AppState) -> Option<String> {
+    match state.pane_origin.as_ref()? {
+        crate::ui::RetryTarget::Menu(mi) => mi.mutex.clone(),
+        crate::ui::RetryTarget::Child { val, .. } => val
+            .get("mutex")
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+// FormState of a form spec (also one step of a wizard): fields, groups or the CLI schema,
+// then `overrides:`
+fn form_state_from_spec(v: &JsonValue) -> crate::widgets::form::FormState {
+    let title = v
+        .get("title")
+        .and_then(|s| s.as_str())
+        .unwrap_or("Form")
+        .to_string();
+    // Detect command for submit
+    // Priority: submit_cmd | submit.command | command
+    let submit_cmd = v
+        .get("submit_cmd")
+        .and_then(|s| s.as_str())
+        .map(|s| s.to_string())
+        .or_else(|| {
+            v.get("submit")
+                .and_then(|x| x.get("command"))
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string())
+        })
+        .or_else(|| {
+            v.get("command")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string())
+        });
+    let mut form = crate::widgets::form::FormState {
+        title,
+        submit_cmd,
+        ..Default::default()
+    };
+    if let Some(fields) = v.get("fields").and_then(|x| x.as_array()) {
+        for f in fields {
+            if let Some(name) = f.get("name").and_then(|s| s.as_str()) {
+                let label = f
+                    .get("label")
                     .and_then(|s| s.as_str())
-                    .map(|s| s.to_string())
-            });
-        let mut form = crate::widgets::form::FormState {
-            title,
-            submit_cmd,
-            ..Default::default()
-        };
-        if let Some(fields) = v.get("fields").and_then(|x| x.as_array()) {
-            for f in fields {
-                if let Some(name) = f.get("name").and_then(|s| s.as_str()) {
-                    let label = f
-                        .get("label")
-                        .and_then(|s| s.as_str())
-                        .unwrap_or(name)
-                        .to_string();
-                    let required = f.get("required").and_then(|b| b.as_bool()).unwrap_or(false);
-                    let t = f
-                        .get("type")
-                        .and_then(|s| s.as_str())
-                        .unwrap_or("text")
-                        .to_ascii_lowercase();
-                    let kind = match t.as_str() {
-                        "checkbox" | "bool" | "boolean" => {
-                            crate::widgets::form::FieldKind::Checkbox
-                        }
-                        "select" => {
-                            if let Some(opts_arr) = f.get("options").and_then(|x| x.as_array()) {
-                                let opts: Vec<String> = opts_arr
-                                    .iter()
-                                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                    .collect();
-                                crate::widgets::form::FieldKind::Select {
-                                    options: opts.clone(),
-                                    values: opts,
-                                    cursor: 0,
-                                    selected: 0,
-                                    offset: 0,
-                                }
-                            } else {
-                                // dynamic options via options_cmd
-                                crate::widgets::form::FieldKind::Select {
-                                    options: vec![],
-                                    values: vec![],
-                                    cursor: 0,
-                                    selected: 0,
-                                    offset: 0,
-                                }
+                    .unwrap_or(name)
+                    .to_string();
+                let required = f.get("required").and_then(|b| b.as_bool()).unwrap_or(false);
+                let t = f
+                    .get("type")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or("text")
+                    .to_ascii_lowercase();
+                let kind = match t.as_str() {
+                    "checkbox" | "bool" | "boolean" => crate::widgets::form::FieldKind::Checkbox,
+                    "select" => {
+                        if let Some(opts_arr) = f.get("options").and_then(|x| x.as_array()) {
+                            let opts: Vec<String> = opts_arr
+                                .iter()
+                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                .collect();
+                            crate::widgets::form::FieldKind::Select {
+                                options: opts.clone(),
+                                values: opts,
+                                cursor: 0,
+                                selected: 0,
+                                offset: 0,
                             }
-                        }
-                        "multiselect" | "multi-select" => {
-                            if let Some(opts_arr) = f.get("options").and_then(|x| x.as_array()) {
-                                let opts: Vec<String> = opts_arr
-                                    .iter()
-                                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                    .collect();
-                                let selected = vec![false; opts.len()];
-                                crate::widgets::form::FieldKind::MultiSelect {
-                                    options: opts.clone(),
-                                    values: opts,
-                                    cursor: 0,
-                                    selected,
-                                    offset: 0,
-                                }
-                            } else {
-                                crate::widgets::form::FieldKind::MultiSelect {
-                                    options: vec![],
-                                    values: vec![],
-                                    cursor: 0,
-                                    selected: vec![],
-                                    offset: 0,
-                                }
+                        } else {
+                            // dynamic options via options_cmd
+                            crate::widgets::form::FieldKind::Select {
+                                options: vec![],
+                                values: vec![],
+                                cursor: 0,
+                                selected: 0,
+                                offset: 0,
                             }
                         }
-                        "password" => crate::widgets::form::FieldKind::Password,
-                        "textarea" => crate::widgets::form::FieldKind::TextArea {
-                            edit_lines: 6,
-                            offset: 0,
-                        },
-                        _ => crate::widgets::form::FieldKind::Text,
-                    };
-                    let value = match kind {
-                        crate::widgets::form::FieldKind::Checkbox => {
-                            let b = f.get("default").and_then(|x| x.as_bool()).unwrap_or(false);
-                            crate::widgets::form::FieldValue::Bool(b)
+                    }
+                    "multiselect" | "multi-select" => {
+                        if let Some(opts_arr) = f.get("options").and_then(|x| x.as_array()) {
+                            let opts: Vec<String> = opts_arr
+                                .iter()
+                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                .collect();
+                            let selected = vec![false; opts.len()];
+                            crate::widgets::form::FieldKind::MultiSelect {
+                                options: opts.clone(),
+                                values: opts,
+                                cursor: 0,
+                                selected,
+                                offset: 0,
+                            }
+                        } else {
+                            crate::widgets::form::FieldKind::MultiSelect {
+                                options: vec![],
+                                values: vec![],
+                                cursor: 0,
+                                selected: vec![],
+                                offset: 0,
+                            }
                         }
-                        crate::widgets::form::FieldKind::Number { .. } => {
-                            let s = if let Some(v) = f.get("default").and_then(|x| x.as_i64()) {
-                                v.to_string()
-                            } else if let Some(v) = f.get("default").and_then(|x| x.as_f64()) {
-                                if v.fract().abs() < 1e-12 {
-                                    format!("{v:.0}")
-                                } else {
-                                    v.to_string()
-                                }
+                    }
+                    "password" => crate::widgets::form::FieldKind::Password,
+                    "textarea" => crate::widgets::form::FieldKind::TextArea {
+                        edit_lines: 6,
+                        offset: 0,
+                    },
+                    _ => crate::widgets::form::FieldKind::Text,
+                };
+                let value = match kind {
+                    crate::widgets::form::FieldKind::Checkbox => {
+                        let b = f.get("default").and_then(|x| x.as_bool()).unwrap_or(false);
+                        crate::widgets::form::FieldValue::Bool(b)
+                    }
+                    crate::widgets::form::FieldKind::Number { .. } => {
+                        let s = if let Some(v) = f.get("default").and_then(|x| x.as_i64()) {
+                            v.to_string()
+                        } else if let Some(v) = f.get("default").and_then(|x| x.as_f64()) {
+                            if v.fract().abs() < 1e-12 {
+                                format!("{v:.0}")
                             } else {
-                                f.get("default")
-                                    .and_then(|x| x.as_str())
-                                    .unwrap_or("")
-                                    .to_string()
-                            };
-                            crate::widgets::form::FieldValue::Text(s)
-                        }
-                        crate::widgets::form::FieldKind::Array { .. } => {
-                            let s = if let Some(arr) = f.get("default").and_then(|x| x.as_array()) {
-                                let mut parts = Vec::new();
-                                for v in arr {
-                                    if let Some(t) = v.as_str() {
-                                        parts.push(t.to_string());
-                                    } else if let Some(i) = v.as_i64() {
-                                        parts.push(i.to_string());
-                                    } else if let Some(fl) = v.as_f64() {
-                                        parts.push(if fl.fract().abs() < 1e-12 {
-                                            format!("{fl:.0}")
-                                        } else {
-                                            fl.to_string()
-                                        });
-                                    }
+                                v.to_string()
+                            }
+                        } else {
+                            f.get("default")
+                                .and_then(|x| x.as_str())
+                                .unwrap_or("")
+                                .to_string()
+                        };
+                        crate::widgets::form::FieldValue::Text(s)
+                    }
+                    crate::widgets::form::FieldKind::Array { .. } => {
+                        let s = if let Some(arr) = f.get("default").and_then(|x| x.as_array()) {
+                            let mut parts = Vec::new();
+                            for v in arr {
+                                if let Some(t) = v.as_str() {
+                                    parts.push(t.to_string());
+                                } else if let Some(i) = v.as_i64() {
+                                    parts.push(i.to_string());
+                                } else if let Some(fl) = v.as_f64() {
+                                    parts.push(if fl.fract().abs() < 1e-12 {
+                                        format!("{fl:.0}")
+                                    } else {
+                                        fl.to_string()
+                                    });
                                 }
-                                parts.join(", ")
-                            } else {
-                                f.get("default")
-                                    .and_then(|x| x.as_str())
-                                    .unwrap_or("")
-                                    .to_string()
-                            };
-                            crate::widgets::form::FieldValue::Text(s)
-                        }
-                        crate::widgets::form::FieldKind::Text
-                        | crate::widgets::form::FieldKind::Password
-                        | crate::widgets::form::FieldKind::TextArea { .. }
-                        | crate::widgets::form::FieldKind::Select { .. }
-                        | crate::widgets::form::FieldKind::MultiSelect { .. } => {
-                            let s = f
-                                .get("default")
+                            }
+                            parts.join(", ")
+                        } else {
+                            f.get("default")
                                 .and_then(|x| x.as_str())
                                 .unwrap_or("")
-                                .to_string();
-                            crate::widgets::form::FieldValue::Text(s)
-                        }
-                    };
-                    let mut ff = crate::widgets::form::FormField {
-                        name: name.to_string(),
-                        label,
-                        required,
-                        kind,
-                        value,
-                        error: None,
-                        text_min_len: None,
-                        text_max_len: None,
-                        text_pattern: None,
-                        textarea_max_lines: None,
-                        dyn_options_cmd: None,
-                        dyn_unwrap: None,
-                        dyn_loaded: false,
-                        dyn_loaded_at: None,
-                        group: None,
-                        order: None,
-                    };
-                    if let Some(cmd) = f.get("options_cmd").and_then(|s| s.as_str()) {
-                        ff.dyn_options_cmd = Some(cmd.to_string());
-                        ff.dyn_unwrap = f
-                            .get("unwrap")
-                            .and_then(|s| s.as_str())
-                            .map(|s| s.to_string());
+                                .to_string()
+                        };
+                        crate::widgets::form::FieldValue::Text(s)
                     }
-                    if let Some(maxl) = f.get("max_lines").and_then(|x| x.as_u64()) {
-                        ff.textarea_max_lines = Some(maxl as usize);
+                    crate::widgets::form::FieldKind::Text
+                    | crate::widgets::form::FieldKind::Password
+                    | crate::widgets::form::FieldKind::TextArea { .. }
+                    | crate::widgets::form::FieldKind::Select { .. }
+                    | crate::widgets::form::FieldKind::MultiSelect { .. } => {
+                        let s = f
+                            .get("default")
+                            .and_then(|x| x.as_str())
+                            .unwrap_or("")
+                            .to_string();
+                        crate::widgets::form::FieldValue::Text(s)
                     }
-                    form.fields.push(ff);
+                };
+                let mut ff = crate::widgets::form::FormField {
+                    name: name.to_string(),
+                    label,
+                    required,
+                    kind,
+                    value,
+                    error: None,
+                    text_min_len: None,
+                    text_max_len: None,
+                    text_pattern: None,
+                    textarea_max_lines: None,
+                    dyn_options_cmd: None,
+                    dyn_unwrap: None,
+                    dyn_loaded: false,
+                    dyn_loaded_at: None,
+                    group: None,
+                    order: None,
+                };
+                if let Some(cmd) = f.get("options_cmd").and_then(|s| s.as_str()) {
+                    ff.dyn_options_cmd = Some(cmd.to_string());
+                    ff.dyn_unwrap = f
+                        .get("unwrap")
+                        .and_then(|s| s.as_str())
+                        .map(|s| s.to_string());
                 }
+                if let Some(maxl) = f.get("max_lines").and_then(|x| x.as_u64()) {
+                    ff.textarea_max_lines = Some(maxl as usize);
+                }
+                form.fields.push(ff);
             }
-        } else if let Some(groups) = v.get("groups").and_then(|x| x.as_array()) {
-            // Support grouped fields
-            for g in groups {
-                let group_title = g
-                    .get("title")
-                    .and_then(|s| s.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                if let Some(fields) = g.get("fields").and_then(|x| x.as_array()) {
-                    for f in fields {
-                        if let Some(name) = f.get("name").and_then(|s| s.as_str()) {
-                            let label = f
-                                .get("label")
-                                .and_then(|s| s.as_str())
-                                .unwrap_or(name)
-                                .to_string();
-                            let required =
-                                f.get("required").and_then(|b| b.as_bool()).unwrap_or(false);
-                            let t = f
-                                .get("type")
-                                .and_then(|s| s.as_str())
-                                .unwrap_or("text")
-                                .to_ascii_lowercase();
-                            let kind = match t.as_str() {
-                                "checkbox" | "bool" | "boolean" => {
-                                    crate::widgets::form::FieldKind::Checkbox
-                                }
-                                "select" => {
-                                    if let Some(opts_arr) =
-                                        f.get("options").and_then(|x| x.as_array())
-                                    {
-                                        let opts: Vec<String> = opts_arr
-                                            .iter()
-                                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                            .collect();
-                                        crate::widgets::form::FieldKind::Select {
-                                            options: opts.clone(),
-                                            values: opts,
-                                            cursor: 0,
-                                            selected: 0,
-                                            offset: 0,
-                                        }
-                                    } else {
-                                        crate::widgets::form::FieldKind::Select {
-                                            options: vec![],
-                                            values: vec![],
-                                            cursor: 0,
-                                            selected: 0,
-                                            offset: 0,
-                                        }
+        }
+    } else if let Some(groups) = v.get("groups").and_then(|x| x.as_array()) {
+        // Support grouped fields
+        for g in groups {
+            let group_title = g
+                .get("title")
+                .and_then(|s| s.as_str())
+                .unwrap_or("")
+                .to_string();
+            if let Some(fields) = g.get("fields").and_then(|x| x.as_array()) {
+                for f in fields {
+                    if let Some(name) = f.get("name").and_then(|s| s.as_str()) {
+                        let label = f
+                            .get("label")
+                            .and_then(|s| s.as_str())
+                            .unwrap_or(name)
+                            .to_string();
+                        let required = f.get("required").and_then(|b| b.as_bool()).unwrap_or(false);
+                        let t = f
+                            .get("type")
+                            .and_then(|s| s.as_str())
+                            .unwrap_or("text")
+                            .to_ascii_lowercase();
+                        let kind = match t.as_str() {
+                            "checkbox" | "bool" | "boolean" => {
+                                crate::widgets::form::FieldKind::Checkbox
+                            }
+                            "select" => {
+                                if let Some(opts_arr) = f.get("options").and_then(|x| x.as_array())
+                                {
+                                    let opts: Vec<String> = opts_arr
+                                        .iter()
+                                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                        .collect();
+                                    crate::widgets::form::FieldKind::Select {
+                                        options: opts.clone(),
+                                        values: opts,
+                                        cursor: 0,
+                                        selected: 0,
+                                        offset: 0,
                                     }
-                                }
-                                "multiselect" | "multi-select" => {
-                                    if let Some(opts_arr) =
-                                        f.get("options").and_then(|x| x.as_array())
-                                    {
-                                        let opts: Vec<String> = opts_arr
-                                            .iter()
-                                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                            .collect();
-                                        let selected = vec![false; opts.len()];
-                                        crate::widgets::form::FieldKind::MultiSelect {
-                                            options: opts.clone(),
-                                            values: opts,
-                                            cursor: 0,
-                                            selected,
-                                            offset: 0,
-                                        }
-                                    } else {
-                                        crate::widgets::form::FieldKind::MultiSelect {
-                                            options: vec![],
-                                            values: vec![],
-                                            cursor: 0,
-                                            selected: vec![],
-                                            offset: 0,
-                                        }
+                                } else {
+                                    crate::widgets::form::FieldKind::Select {
+                                        options: vec![],
+                                        values: vec![],
+                                        cursor: 0,
+                                        selected: 0,
+                                        offset: 0,
                                     }
                                 }
-                                "password" => crate::widgets::form::FieldKind::Password,
-                                "textarea" => crate::widgets::form::FieldKind::TextArea {
-                                    edit_lines: 6,
-                                    offset: 0,
-                                },
-                                _ => crate::widgets::form::FieldKind::Text,
-                            };
-                            let value = match kind {
-                                crate::widgets::form::FieldKind::Checkbox => {
-                                    let b =
-                                        f.get("default").and_then(|x| x.as_bool()).unwrap_or(false);
-                                    crate::widgets::form::FieldValue::Bool(b)
-                                }
-                                crate::widgets::form::FieldKind::Number { .. } => {
-                                    let s = if let Some(v) =
-                                        f.get("default").and_then(|x| x.as_i64())
-                                    {
-                                        v.to_string()
-                                    } else if let Some(v) =
-                                        f.get("default").and_then(|x| x.as_f64())
-                                    {
-                                        if v.fract().abs() < 1e-12 {
-                                            format!("{v:.0}")
-                                        } else {
-                                            v.to_string()
-                                        }
-                                    } else {
-                                        f.get("default")
-                                            .and_then(|x| x.as_str())
-                                            .unwrap_or("")
-                                            .to_string()
-                                    };
-                                    crate::widgets::form::FieldValue::Text(s)
+                            }
+                            "multiselect" | "multi-select" => {
+                                if let Some(opts_arr) = f.get("options").and_then(|x| x.as_array())
+                                {
+                                    let opts: Vec<String> = opts_arr
+                                        .iter()
+                                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                        .collect();
+                                    let selected = vec![false; opts.len()];
+                                    crate::widgets::form::FieldKind::MultiSelect {
+                                        options: opts.clone(),
+                                        values: opts,
+                                        cursor: 0,
+                                        selected,
+                                        offset: 0,
+                                    }
+                                } else {
+                                    crate::widgets::form::FieldKind::MultiSelect {
+                                        options: vec![],
+                                        values: vec![],
+                                        cursor: 0,
+                                        selected: vec![],
+                                        offset: 0,
+                                    }
                                 }
-                                crate::widgets::form::FieldKind::Array { .. } => {
-                                    let s = if let Some(arr) =
-                                        f.get("default").and_then(|x| x.as_array())
-                                    {
-                                        let mut parts = Vec::new();
-                                        for v in arr {
-                                            if let Some(t) = v.as_str() {
-                                                parts.push(t.to_string());
-                                            } else if let Some(i) = v.as_i64() {
-                                                parts.push(i.to_string());
-                                            } else if let Some(fl) = v.as_f64() {
-                                                parts.push(if fl.fract().abs() < 1e-12 {
-                                                    format!("{fl:.0}")
-                                                } else {
-                                                    fl.to_string()
-                                                });
-                                            }
-                                        }
-                                        parts.join(", ")
+                            }
+                            "password" => crate::widgets::form::FieldKind::Password,
+                            "textarea" => crate::widgets::form::FieldKind::TextArea {
+                                edit_lines: 6,
+                                offset: 0,
+                            },
+                            _ => crate::widgets::form::FieldKind::Text,
+                        };
+                        let value = match kind {
+                            crate::widgets::form::FieldKind::Checkbox => {
+                                let b = f.get("default").and_then(|x| x.as_bool()).unwrap_or(false);
+                                crate::widgets::form::FieldValue::Bool(b)
+                            }
+                            crate::widgets::form::FieldKind::Number { .. } => {
+                                let s = if let Some(v) = f.get("default").and_then(|x| x.as_i64()) {
+                                    v.to_string()
+                                } else if let Some(v) = f.get("default").and_then(|x| x.as_f64()) {
+                                    if v.fract().abs() < 1e-12 {
+                                        format!("{v:.0}")
                                     } else {
-                                        f.get("default")
-                                            .and_then(|x| x.as_str())
-                                            .unwrap_or("")
-                                            .to_string()
-                                    };
-                                    crate::widgets::form::FieldValue::Text(s)
-                                }
-                                _ => {
-                                    let s = f
-                                        .get("default")
+                                        v.to_string()
+                                    }
+                                } else {
+                                    f.get("default")
                                         .and_then(|x| x.as_str())
                                         .unwrap_or("")
-                                        .to_string();
-                                    crate::widgets::form::FieldValue::Text(s)
-                                }
-                            };
-                            let mut ff = crate::widgets::form::FormField {
-                                name: name.to_string(),
-                                label,
-                                required,
-                                kind,
-                                value,
-                                error: None,
-                                text_min_len: None,
-                                text_max_len: None,
-                                text_pattern: None,
-                                textarea_max_lines: None,
-                                dyn_options_cmd: None,
-                                dyn_unwrap: None,
-                                dyn_loaded: false,
-                                dyn_loaded_at: None,
-                                group: if group_title.is_empty() {
-                                    None
+                                        .to_string()
+                                };
+                                crate::widgets::form::FieldValue::Text(s)
+                            }
+                            crate::widgets::form::FieldKind::Array { .. } => {
+                                let s = if let Some(arr) =
+                                    f.get("default").and_then(|x| x.as_array())
+                                {
+                                    let mut parts = Vec::new();
+                                    for v in arr {
+                                        if let Some(t) = v.as_str() {
+                                            parts.push(t.to_string());
+                                        } else if let Some(i) = v.as_i64() {
+                                            parts.push(i.to_string());
+                                        } else if let Some(fl) = v.as_f64() {
+                                            parts.push(if fl.fract().abs() < 1e-12 {
+                                                format!("{fl:.0}")
+                                            } else {
+                                                fl.to_string()
+                                            });
+                                        }
+                                    }
+                                    parts.join(", ")
                                 } else {
-                                    Some(group_title.clone())
-                                },
-                                order: None,
-                            };
-                            if let Some(cmd) = f.get("options_cmd").and_then(|s| s.as_str()) {
-                                ff.dyn_options_cmd = Some(cmd.to_string());
-                                ff.dyn_unwrap = f
-                                    .get("unwrap")
-                                    .and_then(|s| s.as_str())
-                                    .map(|s| s.to_string());
+                                    f.get("default")
+                                        .and_then(|x| x.as_str())
+                                        .unwrap_or("")
+                                        .to_string()
+                                };
+                                crate::widgets::form::FieldValue::Text(s)
                             }
-                            if let Some(maxl) = f.get("max_lines").and_then(|x| x.as_u64()) {
-                                ff.textarea_max_lines = Some(maxl as usize);
+                            _ => {
+                                let s = f
+                                    .get("default")
+                                    .and_then(|x| x.as_str())
+                                    .unwrap_or("")
+                                    .to_string();
+                                crate::widgets::form::FieldValue::Text(s)
                             }
-                            form.fields.push(ff);
+                        };
+                        let mut ff = crate::widgets::form::FormField {
+                            name: name.to_string(),
+                            label,
+                            required,
+                            kind,
+                            value,
+                            error: None,
+                            text_min_len: None,
+                            text_max_len: None,
+                            text_pattern: None,
+                            textarea_max_lines: None,
+                            dyn_options_cmd: None,
+                            dyn_unwrap: None,
+                            dyn_loaded: false,
+                            dyn_loaded_at: None,
+                            group: if group_title.is_empty() {
+                                None
+                            } else {
+                                Some(group_title.clone())
+                            },
+                            order: None,
+                        };
+                        if let Some(cmd) = f.get("options_cmd").and_then(|s| s.as_str()) {
+                            ff.dyn_options_cmd = Some(cmd.to_string());
+                            ff.dyn_unwrap = f
+                                .get("unwrap")
+                                .and_then(|s| s.as_str())
+                                .map(|s| s.to_string());
+                        }
+                        if let Some(maxl) = f.get("max_lines").and_then(|x| x.as_u64()) {
+                            ff.textarea_max_lines = Some(maxl as usize);
                         }
+                        form.fields.push(ff);
                     }
                 }
             }
         }
-        // If no fields were defined explicitly, attempt schema-driven mapping from CLI
-        if form.fields.is_empty() {
-            // Prefer explicit schema_cmd if provided, otherwise attempt to derive from submit_cmd
-            if let Some(schema_cmd) = v
-                .get("schema_cmd")
-                .and_then(|s| s.as_str())
-                .map(|s| s.to_string())
-            {
-                if let Ok(schema_env) =
-                    crate::services::cli_runner::run_cmdline_to_json(&schema_cmd)
-                {
-                    // Try common shapes
-                    let data = schema_env.get("data").cloned().unwrap_or(JsonValue::Null);
-                    // 1) Direct input_schema
-                    if let Some(inp) = data.get("input_schema") {
-                        form.fields = crate::widgets::form::fields_from_json_schema(inp);
-                    } else if let Some(commands) = data.get("commands").and_then(|x| x.as_array()) {
-                        if let Some(first) = commands.first() {
-                            if let Some(inp) = first.get("input_schema") {
-                                form.fields = crate::widgets::form::fields_from_json_schema(inp);
-                            }
+    }
+    // If no fields were defined explicitly, attempt schema-driven mapping from CLI
+    if form.fields.is_empty() {
+        // Prefer explicit schema_cmd if provided, otherwise attempt to derive from submit_cmd
+        if let Some(schema_cmd) = v
+            .get("schema_cmd")
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_string())
+        {
+            if let Ok(schema_env) = crate::services::cli_runner::run_cmdline_to_json(&schema_cmd) {
+                // Try common shapes
+                let data = Envelope::parse(&schema_env).unwrap_or_default().data;
+                // 1) Direct input_schema
+                if let Some(inp) = data.get("input_schema") {
+                    form.fields = crate::widgets::form::fields_from_json_schema(inp);
+                } else if let Some(commands) = data.get("commands").and_then(|x| x.as_array()) {
+                    if let Some(first) = commands.first() {
+                        if let Some(inp) = first.get("input_schema") {
+                            form.fields = crate::widgets::form::fields_from_json_schema(inp);
                         }
                     }
                 }
-            } else if let Some(cmdline) = &form.submit_cmd {
-                // Parse program and (heuristically) command token
-                let parts = shlex::split(cmdline).unwrap_or_default();
-                if parts.len() >= 2 {
-                    let prog = &parts[0];
-                    let cmd_name = &parts[1];
-                    let schema_cmd = format!("{prog} schema");
-                    if let Ok(schema_env) =
-                        crate::services::cli_runner::run_cmdline_to_json(&schema_cmd)
+            }
+        } else if let Some(cmdline) = &form.submit_cmd {
+            // Parse program and (heuristically) command token
+            let parts = shlex::split(cmdline).unwrap_or_default();
+            if parts.len() >= 2 {
+                let prog = &parts[0];
+                let cmd_name = &parts[1];
+                let schema_cmd = format!("{prog} schema");
+                if let Ok(schema_env) =
+                    crate::services::cli_runner::run_cmdline_to_json(&schema_cmd)
+                {
+                    // Expect envelope: { ok, data: { commands: [...] } }
+                    let data = Envelope::parse(&schema_env).unwrap_or_default().data;
+                    let commands = data
+                        .get("commands")
+                        .and_then(|x| x.as_array())
+                        .cloned()
+                        .unwrap_or_default();
+                    if let Some(spec) = commands
+                        .iter()
+                        .find(|c| c.get("name").and_then(|s| s.as_str()) == Some(cmd_name))
                     {
-                        // Expect envelope: { ok, data: { commands: [...] } }
-                        let data = schema_env
-                            .get("data")
-                            .cloned()
-                            .unwrap_or(serde_json::Value::Null);
-                        let commands = data
-                            .get("commands")
-                            .and_then(|x| x.as_array())
-                            .cloned()
-                            .unwrap_or_default();
-                        if let Some(spec) = commands
-                            .iter()
-                            .find(|c| c.get("name").and_then(|s| s.as_str()) == Some(cmd_name))
-                        {
-                            if let Some(inp) = spec.get("input_schema") {
-                                // Build fields from the input schema via widget helper
-                                form.fields = crate::widgets::form::fields_from_json_schema(inp);
-                            }
+                        if let Some(inp) = spec.get("input_schema") {
+                            // Build fields from the input schema via widget helper
+                            form.fields = crate::widgets::form::fields_from_json_schema(inp);
                         }
                     }
                 }
             }
         }
-        // Apply overrides if present
-        if let Some(ov) = v.get("overrides").and_then(|x| x.as_object()) {
-            for (fname, o) in ov.iter() {
-                if let Some(ff) = form.fields.iter_mut().find(|f| &f.name == fname) {
-                    if let Some(lbl) = o.get("label").and_then(|s| s.as_str()) {
-                        ff.label = lbl.to_string();
-                    }
-                    if let Some(req) = o.get("required").and_then(|b| b.as_bool()) {
-                        ff.required = req;
-                    }
-                    if let Some(g) = o.get("group").and_then(|s| s.as_str()) {
-                        ff.group = Some(g.to_string());
-                    }
-                    if let Some(ord) = o.get("order").and_then(|x| x.as_i64()) {
-                        ff.order = Some(ord as i32);
-                    }
-                    if let Some(w) = o.get("widget").and_then(|s| s.as_str()) {
-                        match w.to_ascii_lowercase().as_str() {
-                            "checkbox" => ff.kind = crate::widgets::form::FieldKind::Checkbox,
-                            "select" => {
-                                let opts: Vec<String> = o
-                                    .get("options")
-                                    .and_then(|x| x.as_array())
-                                    .map(|a| {
-                                        a.iter()
-                                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                            .collect()
-                                    })
-                                    .unwrap_or_default();
-                                ff.kind = crate::widgets::form::FieldKind::Select {
-                                    options: opts.clone(),
-                                    values: opts,
-                                    cursor: 0,
-                                    selected: 0,
-                                    offset: 0,
-                                };
-                            }
-                            "password" => ff.kind = crate::widgets::form::FieldKind::Password,
-                            "textarea" => {
-                                let edit_lines = o
-                                    .get("edit_lines")
-                                    .and_then(|x| x.as_u64())
-                                    .map(|x| x as usize)
-                                    .unwrap_or(6);
-                                ff.kind = crate::widgets::form::FieldKind::TextArea {
-                                    edit_lines,
-                                    offset: 0,
-                                };
-                            }
-                            _ => ff.kind = crate::widgets::form::FieldKind::Text,
+    }
+    // Apply overrides if present
+    if let Some(ov) = v.get("overrides").and_then(|x| x.as_object()) {
+        for (fname, o) in ov.iter() {
+            if let Some(ff) = form.fields.iter_mut().find(|f| &f.name == fname) {
+                if let Some(lbl) = o.get("label").and_then(|s| s.as_str()) {
+                    ff.label = lbl.to_string();
+                }
+                if let Some(req) = o.get("required").and_then(|b| b.as_bool()) {
+                    ff.required = req;
+                }
+                if let Some(g) = o.get("group").and_then(|s| s.as_str()) {
+                    ff.group = Some(g.to_string());
+                }
+                if let Some(ord) = o.get("order").and_then(|x| x.as_i64()) {
+                    ff.order = Some(ord as i32);
+                }
+                if let Some(w) = o.get("widget").and_then(|s| s.as_str()) {
+                    match w.to_ascii_lowercase().as_str() {
+                        "checkbox" => ff.kind = crate::widgets::form::FieldKind::Checkbox,
+                        "select" => {
+                            let opts: Vec<String> = o
+                                .get("options")
+                                .and_then(|x| x.as_array())
+                                .map(|a| {
+                                    a.iter()
+                                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                        .collect()
+                                })
+                                .unwrap_or_default();
+                            ff.kind = crate::widgets::form::FieldKind::Select {
+                                options: opts.clone(),
+                                values: opts,
+                                cursor: 0,
+                                selected: 0,
+                                offset: 0,
+                            };
                         }
-                    }
-                    match ff.kind {
-                        crate::widgets::form::FieldKind::Checkbox => {
-                            if let Some(b) = o.get("default").and_then(|x| x.as_bool()) {
-                                ff.value = crate::widgets::form::FieldValue::Bool(b);
-                            }
+                        "password" => ff.kind = crate::widgets::form::FieldKind::Password,
+                        "textarea" => {
+                            let edit_lines = o
+                                .get("edit_lines")
+                                .and_then(|x| x.as_u64())
+                                .map(|x| x as usize)
+                                .unwrap_or(6);
+                            ff.kind = crate::widgets::form::FieldKind::TextArea {
+                                edit_lines,
+                                offset: 0,
+                            };
                         }
-                        crate::widgets::form::FieldKind::Select {
-                            ref options,
-                            ref mut selected,
-                            ..
-                        } => {
-                            if let Some(s) = o.get("default").and_then(|x| x.as_str()) {
-                                if let Some(idx) = options.iter().position(|v| v == s) {
-                                    *selected = idx;
-                                }
-                                ff.value = crate::widgets::form::FieldValue::Text(s.to_string());
-                            }
+                        _ => ff.kind = crate::widgets::form::FieldKind::Text,
+                    }
+                }
+                match ff.kind {
+                    crate::widgets::form::FieldKind::Checkbox => {
+                        if let Some(b) = o.get("default").and_then(|x| x.as_bool()) {
+                            ff.value = crate::widgets::form::FieldValue::Bool(b);
                         }
-                        _ => {
-                            if let Some(s) = o.get("default").and_then(|x| x.as_str()) {
-                                ff.value = crate::widgets::form::FieldValue::Text(s.to_string());
+                    }
+                    crate::widgets::form::FieldKind::Select {
+                        ref options,
+                        ref mut selected,
+                        ..
+                    } => {
+                        if let Some(s) = o.get("default").and_then(|x| x.as_str()) {
+                            if let Some(idx) = options.iter().position(|v| v == s) {
+                                *selected = idx;
                             }
+                            ff.value = crate::widgets::form::FieldValue::Text(s.to_string());
                         }
                     }
-                    // dynamic options overrides
-                    if let Some(cmd) = o.get("options_cmd").and_then(|s| s.as_str()) {
-                        ff.dyn_options_cmd = Some(cmd.to_string());
-                        ff.dyn_unwrap = o
-                            .get("unwrap")
-                            .and_then(|s| s.as_str())
-                            .map(|s| s.to_string());
-                    }
-                    if let Some(maxl) = o.get("max_lines").and_then(|x| x.as_u64()) {
-                        ff.textarea_max_lines = Some(maxl as usize);
+                    _ => {
+                        if let Some(s) = o.get("default").and_then(|x| x.as_str()) {
+                            ff.value = crate::widgets::form::FieldValue::Text(s.to_string());
+                        }
                     }
                 }
+                // dynamic options overrides
+                if let Some(cmd) = o.get("options_cmd").and_then(|s| s.as_str()) {
+                    ff.dyn_options_cmd = Some(cmd.to_string());
+                    ff.dyn_unwrap = o
+                        .get("unwrap")
+                        .and_then(|s| s.as_str())
+                        .map(|s| s.to_string());
+                }
+                if let Some(maxl) = o.get("max_lines").and_then(|x| x.as_u64()) {
+                    ff.textarea_max_lines = Some(maxl as usize);
+                }
             }
         }
-        // Sort fields by group and order for deterministic layout
-        form.fields.sort_by(|a, b| {
-            let ga = a.group.as_deref().unwrap_or("");
-            let gb = b.group.as_deref().unwrap_or("");
-            if ga != gb {
-                return ga.cmp(gb);
-            }
-            let oa = a.order.unwrap_or(i32::MAX);
-            let ob = b.order.unwrap_or(i32::MAX);
-            oa.cmp(&ob)
-        });
-        if state.panel.is_some() && matches!(pane, super::ui::PanelPane::B) {
-            super::ui::pane_b_replace_with_widget(
-                state,
-                Box::new(crate::widgets::form_widget::FormWidget::new(form)),
-                true,
-            );
-        }
-        return true;
     }
-    false
+    // Sort fields by group and order for deterministic layout
+    form.fields.sort_by(|a, b| {
+        let ga = a.group.as_deref().unwrap_or("");
+        let gb = b.group.as_deref().unwrap_or("");
+        if ga != gb {
+            return ga.cmp(gb);
+        }
+        let oa = a.order.unwrap_or(i32::MAX);
+        let ob = b.order.unwrap_or(i32::MAX);
+        oa.cmp(&ob)
+    });
+    form
 }
 
 fn validate_form_yaml(v: &JsonValue) -> Result<(), String> {
@@ -2892,6 +4381,26 @@ fn validate_form_yaml(v: &JsonValue) -> Result<(), String> {
     {
         return Err("type must be 'form'".into());
     }
+    validate_form_fields(v)
+}
+
+// Wizard spec: `steps:` is a non-empty list of form specs
+fn validate_wizard_yaml(v: &JsonValue) -> Result<(), String> {
+    let steps = v
+        .get("steps")
+        .and_then(|x| x.as_array())
+        .filter(|a| !a.is_empty())
+        .ok_or_else(|| "wizard needs a non-empty 'steps' list".to_string())?;
+    for (i, step) in steps.iter().enumerate() {
+        if !step.is_object() {
+            return Err(format!("steps[{i}] must be a form spec"));
+        }
+        validate_form_fields(step).map_err(|e| format!("steps[{i}]: {e}"))?;
+    }
+    Ok(())
+}
+
+fn validate_form_fields(v: &JsonValue) -> Result<(), String> {
     if let Some(fields) = v.get("fields") {
         let arr = fields
             .as_array()
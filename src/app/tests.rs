@@ -118,9 +118,14 @@ fn loaded_submit_form_maps_nested_error_locations() {
                 dirty: false,
                 initial: vec![],
                 confirm: None,
+                options_loading: vec![],
+                tick: 0,
+                caret: None,
+                step: None,
             },
         ))),
         b_history: Vec::new(),
+        a_content: None,
     });
     // Simulate error envelope with nested loc ["payload","username"]
     let env = json!({
@@ -166,3 +171,1471 @@ fn validate_form_yaml_reports_field_index_and_name() {
     assert!(err.contains("fields[0]"));
     assert!(err.contains("choice"));
 }
+
+#[test]
+fn wizard_spec_opens_its_steps_in_pane_b() {
+    use crate::ui::{PanelPane, PanelState as UiPanelState};
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let spec = json!({
+        "type": "wizard",
+        "title": "New project",
+        "submit": {"command": "app projects create"},
+        "steps": [
+            {"title": "Basics", "fields": [{"name": "name", "required": true}]},
+            {"fields": [{"name": "region", "type": "select", "options_cmd": "app regions"}]}
+        ]
+    });
+    let _ = update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(spec)),
+        },
+    );
+    let crate::ui::PaneContent::Widget(w) = &st.panel.as_ref().unwrap().b_content else {
+        panic!("expected widget");
+    };
+    let wz = w
+        .as_any()
+        .downcast_ref::<crate::widgets::wizard::WizardWidget>()
+        .expect("wizard widget");
+    assert_eq!(wz.step().form.title, "New project — Basics");
+    assert_eq!(
+        wz.merged().submit_cmd.as_deref(),
+        Some("app projects create")
+    );
+    // Ctrl+C, textarea saves and submit handling see the current step as the form
+    let fw = crate::widgets::wizard::current_form(w.as_any()).unwrap();
+    assert_eq!(fw.form.step, Some((0, 2)));
+
+    let err = super::validate_wizard_yaml(&json!({
+        "type": "wizard",
+        "steps": [{"fields": [{"name": "a"}]}, {"fields": [{"name": "choice", "type": "select"}]}]
+    }))
+    .unwrap_err();
+    assert!(err.starts_with("steps[1]: fields[0]"), "{err}");
+    assert!(super::validate_wizard_yaml(&json!({"type": "wizard", "steps": []})).is_err());
+}
+
+#[test]
+fn loaded_panel_applies_focus_on_load_policy() {
+    use crate::ui::{PanelPane, PanelState as UiPanelState};
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    st.panel_focus = PanelPane::B;
+    st.config.focus_on_load = Some("pane_a".into());
+    let _ = update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Items(vec![json!({"id": 1})])),
+        },
+    );
+    assert!(matches!(st.panel_focus, PanelPane::A));
+    let flash = st.pane_flash.expect("flash set");
+    assert!(matches!(flash.pane, PanelPane::B));
+
+    // Per-screen override wins over the global policy
+    st.panel_focus_on_load = Some("keep".into());
+    let _ = update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Items(vec![])),
+        },
+    );
+    assert!(matches!(st.panel_focus, PanelPane::A));
+}
+
+#[test]
+fn results_reach_the_renderer_their_content_type_picks() {
+    use crate::ui::{PaneContent, PanelPane, PanelState as UiPanelState, View};
+    use crate::widgets::markdown::MarkdownWidget;
+    let is_text = |w: &dyn crate::widgets::Widget| w.as_any().is::<MarkdownWidget>();
+    let mut st = AppState::default();
+
+    // A streamed result in the global JSON view
+    let _ = update(
+        &mut st,
+        AppMsg::StreamDone {
+            result: Some(json!({"ok": true, "content_type": "markdown", "data": "# Done"})),
+            err: None,
+        },
+    );
+    assert_eq!(st.view, View::Json);
+    assert!(st.json_rendered.as_deref().is_some_and(is_text));
+    let _ = update(
+        &mut st,
+        AppMsg::StreamDone {
+            result: Some(json!({"ok": true, "data": "plain"})),
+            err: None,
+        },
+    );
+    assert!(st.json_rendered.is_none() && st.json_viewer.is_some());
+
+    // A lazy item's rows in Pane B keep the envelope's content type
+    st.panel = Some(UiPanelState::default());
+    st.view = View::Panel;
+    st.panel_focus = PanelPane::B;
+    let mi = crate::model::MenuItem {
+        id: "pods".into(),
+        title: "Pods".into(),
+        widget: Some("lazy_items".into()),
+        command: Some(
+            r#"echo '{"ok": true, "content_type": "table", "data": {"items": [{"name": "api"}]}}'"#
+                .into(),
+        ),
+        ..Default::default()
+    };
+    let _ = update(&mut st, AppMsg::EnterMenu(mi));
+    assert!(matches!(
+        &st.panel.as_ref().unwrap().b_content,
+        PaneContent::Widget(w) if is_text(w.as_ref())
+    ));
+}
+
+#[test]
+fn form_open_prefetches_dynamic_options_with_bounded_concurrency() {
+    use crate::ui::{PanelPane, PanelState as UiPanelState};
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let spec = json!({
+        "type": "form",
+        "title": "Deploy",
+        "submit": "app deploy",
+        "fields": [
+            {"name": "env", "type": "select", "options_cmd": "app envs"},
+            {"name": "region", "type": "select", "options_cmd": "app regions"},
+            {"name": "tags", "type": "multiselect", "options_cmd": "app tags"}
+        ]
+    });
+    let effs = update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(spec)),
+        },
+    );
+    let fetched: Vec<String> = effs
+        .iter()
+        .filter_map(|e| match e {
+            Effect::LoadFormOptions { field, force, .. } => {
+                assert!(!force);
+                Some(field.clone())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(fetched, vec!["env".to_string(), "region".to_string()]);
+
+    // A failed fetch shows a per-field error and frees a slot for the queued field
+    let effs = update(
+        &mut st,
+        AppMsg::LoadedFormOptions {
+            field: "env".into(),
+            outcome: Err("boom".into()),
+        },
+    );
+    assert!(matches!(
+        effs.as_slice(),
+        [Effect::LoadFormOptions { field, .. }] if field == "tags"
+    ));
+    let ps = st.panel.as_ref().unwrap();
+    let crate::ui::PaneContent::Widget(w) = &ps.b_content else {
+        panic!("expected widget");
+    };
+    let fw = w
+        .as_any()
+        .downcast_ref::<crate::widgets::form_widget::FormWidget>()
+        .expect("form widget");
+    let env = fw.form.fields.iter().find(|f| f.name == "env").unwrap();
+    assert!(env.error.as_deref().unwrap().contains("boom"));
+    assert_eq!(fw.form.options_loading, vec!["region", "tags"]);
+}
+
+#[test]
+fn form_preview_required_gates_submit_and_masks_secrets() {
+    use crate::ui::{PanelPane, PanelState as UiPanelState};
+    use crate::widgets::form::FieldValue;
+    use crate::widgets::form_widget::{FormWidget, PreviewMode};
+    use crate::widgets::Widget;
+    use crossterm::event::KeyCode;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let spec = json!({
+        "type": "form",
+        "title": "Login",
+        "submit_cmd": "app login",
+        "preview": "required",
+        "fields": [
+            {"name": "user", "type": "text"},
+            {"name": "pass", "type": "password"}
+        ]
+    });
+    update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(spec)),
+        },
+    );
+    let ps = st.panel.as_mut().unwrap();
+    let crate::ui::PaneContent::Widget(w) = &mut ps.b_content else {
+        panic!("expected widget");
+    };
+    let fw = w
+        .as_any_mut()
+        .downcast_mut::<FormWidget>()
+        .expect("form widget");
+    assert_eq!(fw.preview_mode, PreviewMode::Required);
+    for fld in &mut fw.form.fields {
+        fld.value = FieldValue::Text(if fld.name == "pass" { "hunter2" } else { "ada" }.into());
+    }
+    fw.form.selected = fw.form.fields.len();
+    // First Save opens the preview instead of submitting
+    assert!(fw.on_key(KeyCode::Enter).is_empty());
+    assert!(fw.preview_open());
+    // Confirming from the preview submits the real command
+    let effs = fw.on_key(KeyCode::Enter);
+    assert!(matches!(
+        effs.as_slice(),
+        [Effect::SubmitForm { cmdline, .. }] if cmdline.contains("--pass hunter2")
+    ));
+    assert!(!fw.preview_open());
+}
+
+#[test]
+fn mutex_items_wrap_command_effects() {
+    let mut st = AppState::default();
+    let mi = crate::model::MenuItem {
+        id: "deploy".into(),
+        title: "Deploy".into(),
+        command: Some("app deploy --prod".into()),
+        mutex: Some("deploy-prod".into()),
+        ..Default::default()
+    };
+    let effs = update(&mut st, AppMsg::EnterMenu(mi));
+    match effs.as_slice() {
+        [Effect::Guarded { mutex, effect }] => {
+            assert_eq!(mutex, "deploy-prod");
+            assert!(matches!(**effect, Effect::RunStream { .. }));
+        }
+        _ => panic!("expected a guarded RunStream"),
+    }
+    // Children opt in with the same key
+    let effs = update(
+        &mut st,
+        AppMsg::EnterChild {
+            key: "menu:x/0".into(),
+            val: json!({"title": "Plain", "command": "app status"}),
+        },
+    );
+    assert!(!effs.iter().any(|e| matches!(e, Effect::Guarded { .. })));
+}
+
+#[test]
+fn forms_opened_by_a_mutex_item_submit_under_it() {
+    use crate::ui::{PanelPane, PanelState as UiPanelState};
+    use crate::widgets::form_widget::FormWidget;
+    use crate::widgets::Widget;
+    use crossterm::event::KeyCode;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    st.pane_origin = Some(crate::ui::RetryTarget::Menu(crate::model::MenuItem {
+        id: "release".into(),
+        title: "Release".into(),
+        mutex: Some("deploy-prod".into()),
+        ..Default::default()
+    }));
+    let spec = json!({"type": "form", "submit_cmd": "app release", "fields": []});
+    update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(spec)),
+        },
+    );
+    let fw = match st.panel.as_mut().map(|ps| &mut ps.b_content) {
+        Some(crate::ui::PaneContent::Widget(w)) => w.as_any_mut().downcast_mut::<FormWidget>(),
+        _ => None,
+    }
+    .expect("form widget");
+    assert!(matches!(
+        fw.on_key(KeyCode::Enter).as_slice(),
+        [Effect::SubmitForm { mutex: Some(m), .. }] if m == "deploy-prod"
+    ));
+}
+
+#[test]
+fn cooldown_blocks_rerun_until_expired() {
+    let mut st = AppState::default();
+    let mi = crate::model::MenuItem {
+        id: "rebuild".into(),
+        title: "Rebuild index".into(),
+        command: Some("app rebuild".into()),
+        cooldown_secs: Some(60),
+        ..Default::default()
+    };
+    let effs = update(&mut st, AppMsg::EnterMenu(mi.clone()));
+    assert!(matches!(effs.as_slice(), [Effect::RunStream { .. }]));
+    let left = crate::ui::cooldown_left(&st, &mi).expect("cooling down");
+    assert!(left > 55 && left <= 60);
+
+    let effs = update(&mut st, AppMsg::EnterMenu(mi.clone()));
+    match effs.as_slice() {
+        [Effect::ShowToast { text, .. }] => assert!(text.contains("cooling down")),
+        _ => panic!("expected the re-run to be blocked"),
+    }
+
+    // Expired cooldowns no longer block
+    st.cooldowns
+        .insert(crate::nav::keys::menu_key(&mi), Instant::now());
+    assert!(crate::ui::cooldown_left(&st, &mi).is_none());
+    let effs = update(&mut st, AppMsg::EnterMenu(mi));
+    assert!(matches!(effs.as_slice(), [Effect::RunStream { .. }]));
+}
+
+#[test]
+fn items_with_a_missing_program_are_greyed_out_until_installed() {
+    let mut st = AppState::default();
+    let mut mi = crate::model::MenuItem {
+        id: "pods".into(),
+        title: "Pods".into(),
+        command: Some("mycli pods".into()),
+        install_hint: Some("brew install mycli".into()),
+        ..Default::default()
+    };
+    // The first run fails because the program is not there
+    let effs = update(&mut st, AppMsg::EnterMenu(mi.clone()));
+    assert!(matches!(effs.as_slice(), [Effect::RunStream { .. }]));
+    let err = crate::services::cli_runner::not_found_error("mycli");
+    let _ = update(
+        &mut st,
+        AppMsg::StreamDone {
+            result: None,
+            err: Some(err),
+        },
+    );
+    assert_eq!(
+        crate::ui::missing_label(&st, &mi).as_deref(),
+        Some("missing: mycli (brew install mycli)")
+    );
+
+    mi.command = Some("chi-tui-no-such-program pods".into());
+    let effs = update(&mut st, AppMsg::EnterMenu(mi.clone()));
+    match effs.as_slice() {
+        [Effect::ShowToast { text, .. }] => assert_eq!(
+            text,
+            "Pods is unavailable — missing: chi-tui-no-such-program (brew install mycli)"
+        ),
+        _ => panic!("expected the item to stay closed"),
+    }
+
+    // Installed in the meantime: Enter runs it again
+    mi.command = Some("sh -c true".into());
+    let effs = update(&mut st, AppMsg::EnterMenu(mi.clone()));
+    assert!(matches!(effs.as_slice(), [Effect::RunStream { .. }]));
+    assert!(crate::ui::missing_label(&st, &mi).is_none());
+}
+
+#[test]
+fn selected_placeholders_fill_from_the_triggering_selection() {
+    let mut st = AppState::default();
+    let mi = crate::model::MenuItem {
+        id: "pods".into(),
+        title: "Pods".into(),
+        command: Some("app describe ${selected.id}".into()),
+        ..Default::default()
+    };
+    let effs = update(&mut st, AppMsg::EnterMenu(mi));
+    assert!(matches!(
+        effs.as_slice(),
+        [Effect::RunStream { cmdline, .. }] if cmdline == "app describe pods"
+    ));
+
+    let val = serde_json::json!({
+        "id": "web 1",
+        "title": "web",
+        "command": "app logs ${selected.id} -n ${selected.meta.ns}",
+        "meta": {"ns": "prod"},
+    });
+    let effs = update(
+        &mut st,
+        AppMsg::EnterChild {
+            key: "menu:pods/0".into(),
+            val,
+        },
+    );
+    assert!(
+        effs.iter().any(|e| matches!(
+            e,
+            Effect::RunStream { cmdline, .. } if cmdline == "app logs 'web 1' -n prod"
+        )),
+        "{:?}",
+        effs.len()
+    );
+    assert_eq!(st.selection_context.as_ref().unwrap()["id"], "web 1");
+}
+
+#[test]
+fn entering_items_runs_their_enter_and_leave_hooks() {
+    let mut st = AppState::default();
+    let item = |id: &str| crate::model::MenuItem {
+        id: id.into(),
+        title: id.to_uppercase(),
+        command: Some(format!("app {id}")),
+        on_enter_cmd: Some("kubectl config use-context ${selected.id}".into()),
+        on_leave_cmd: Some(format!("app track leave {id}")),
+        ..Default::default()
+    };
+    let hook = |e: &Effect| match e {
+        Effect::RunHook { label, cmdline } => Some(format!("{label}: {cmdline}")),
+        _ => None,
+    };
+    let effs = update(&mut st, AppMsg::EnterMenu(item("prod")));
+    let hooks: Vec<String> = effs.iter().filter_map(hook).collect();
+    assert_eq!(hooks, ["enter PROD: kubectl config use-context prod"]);
+    assert!(matches!(effs.last(), Some(Effect::RunStream { .. })));
+
+    // The next item entered leaves the previous one first
+    let effs = update(&mut st, AppMsg::EnterMenu(item("dev")));
+    let hooks: Vec<String> = effs.iter().filter_map(hook).collect();
+    assert_eq!(
+        hooks,
+        [
+            "leave PROD: app track leave prod",
+            "enter DEV: kubectl config use-context dev"
+        ]
+    );
+    assert_eq!(st.item_leave_hook.as_ref().unwrap().label, "leave DEV");
+}
+
+#[test]
+fn expired_tokens_refresh_and_retry_the_item_once() {
+    let mut st = AppState::default();
+    st.config.auth_retry = Some(crate::model::AuthRetryConfig {
+        detect: ".error.code == \"token_expired\"".into(),
+        refresh_cmd: "mycli login --refresh".into(),
+    });
+    let mi = crate::model::MenuItem {
+        id: "pods".into(),
+        title: "Pods".into(),
+        command: Some("mycli pods".into()),
+        ..Default::default()
+    };
+    let _ = update(&mut st, AppMsg::EnterMenu(mi.clone()));
+    let expired = |st: &mut AppState| {
+        update(
+            st,
+            AppMsg::StreamDone {
+                result: None,
+                err: Some(
+                    "exit code 1\n{\"ok\": false, \"error\": {\"code\": \"token_expired\"}}".into(),
+                ),
+            },
+        )
+    };
+    let effs = expired(&mut st);
+    assert!(effs.iter().any(|e| matches!(e, Effect::RefreshAuth)));
+    assert!(st.refresh_retry.is_some());
+
+    let effs = update(
+        &mut st,
+        AppMsg::AuthRefreshed {
+            outcome: Ok(crate::ui::LoadOutcome::Fallback(serde_json::json!("ok"))),
+        },
+    );
+    assert!(effs.iter().any(
+        |e| matches!(e, Effect::ShowToast { text, .. } if text == "Re-authenticated — retrying")
+    ));
+    assert!(effs
+        .iter()
+        .any(|e| matches!(e, Effect::RunStream { cmdline, .. } if cmdline == "mycli pods")));
+
+    // The retry expiring again is reported, not refreshed again
+    let effs = expired(&mut st);
+    assert!(!effs.iter().any(|e| matches!(e, Effect::RefreshAuth)));
+    assert!(st.refresh_retry.is_none());
+
+    // Other errors leave the policy alone
+    let effs = update(
+        &mut st,
+        AppMsg::StreamDone {
+            result: None,
+            err: Some("exit code 2".into()),
+        },
+    );
+    assert!(!effs.iter().any(|e| matches!(e, Effect::RefreshAuth)));
+
+    // A refresh that fails (or times out) clears the pending retry, so the next expiry
+    // refreshes again
+    let _ = update(&mut st, AppMsg::EnterMenu(mi));
+    assert!(expired(&mut st)
+        .iter()
+        .any(|e| matches!(e, Effect::RefreshAuth)));
+    let _ = update(
+        &mut st,
+        AppMsg::AuthRefreshed {
+            outcome: Err("timed out after 30s".into()),
+        },
+    );
+    assert!(st.refresh_retry.is_none());
+    assert!(expired(&mut st)
+        .iter()
+        .any(|e| matches!(e, Effect::RefreshAuth)));
+}
+
+#[test]
+fn scheduled_results_land_in_history_with_a_toast() {
+    let mut st = AppState::default();
+    let now = std::time::SystemTime::now();
+    let id = st
+        .scheduler
+        .add("Backup", "app backup", None, "1m", now)
+        .unwrap()
+        .id;
+    let due = st
+        .scheduler
+        .take_due(now + std::time::Duration::from_secs(120));
+    assert_eq!(due.len(), 1);
+    let outcome = Ok(crate::ui::LoadOutcome::Fallback(
+        serde_json::json!({"ok": false, "error": {"message": "disk full"}}),
+    ));
+    let effs = update(&mut st, AppMsg::ScheduledDone { id, outcome });
+    match effs.as_slice() {
+        [Effect::ShowToast { text, .. }] => {
+            assert_eq!(text, "Scheduled 'Backup' failed: disk full")
+        }
+        _ => panic!("expected a toast"),
+    }
+    assert!(!st.scheduler.history[0].ok);
+    assert!(st.scheduler.jobs.is_empty());
+}
+
+#[test]
+fn rerunning_the_viewer_source_refreshes_it_in_place() {
+    use crate::ui::{PaneContent, PanelPane, PanelState as UiPanelState};
+    use crate::widgets::result_viewer::ResultViewerWidget;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let loaded = |st: &mut AppState, items: Vec<JsonValue>| {
+        st.pane_b_source = Some("example-app list-items".into());
+        let _ = update(
+            st,
+            AppMsg::LoadedPanel {
+                pane: PanelPane::B,
+                outcome: Ok(LoadOutcome::Items(items)),
+            },
+        );
+    };
+    loaded(&mut st, vec![json!({"id": 1})]);
+    let ps = st.panel.as_mut().unwrap();
+    assert_eq!(ps.b_history.len(), 1);
+    let PaneContent::Widget(w) = &mut ps.b_content else {
+        panic!("expected a viewer in Pane B");
+    };
+    match w.refresh().first() {
+        Some(Effect::LoadPanelCmd { pane, cmdline }) => {
+            assert!(matches!(pane, PanelPane::B));
+            assert_eq!(cmdline, "example-app list-items");
+        }
+        _ => panic!("expected the viewer to re-run its source"),
+    }
+
+    // The refreshed result replaces the value without stacking history
+    loaded(&mut st, vec![json!({"id": 1}), json!({"id": 2})]);
+    let ps = st.panel.as_mut().unwrap();
+    assert_eq!(ps.b_history.len(), 1);
+    let PaneContent::Widget(w) = &ps.b_content else {
+        panic!("expected a viewer in Pane B");
+    };
+    let rv = w.as_any().downcast_ref::<ResultViewerWidget>().unwrap();
+    assert_eq!(rv.value().as_array().map(|a| a.len()), Some(2));
+}
+
+#[test]
+fn failing_preflight_blocks_items_until_it_passes() {
+    let mut st = AppState::default();
+    st.preflight = vec![crate::ui::PreflightStatus {
+        check: crate::model::PreflightCheck {
+            title: "Logged in".into(),
+            command: "app auth status".into(),
+            remediation: Some("run `app login`".into()),
+            blocks: vec!["deploy".into()],
+        },
+        pending: true,
+        failure: None,
+    }];
+    let _ = update(
+        &mut st,
+        AppMsg::PreflightDone {
+            index: 0,
+            outcome: Err("not authenticated".into()),
+        },
+    );
+    assert_eq!(
+        st.preflight[0].failure.as_deref(),
+        Some("not authenticated")
+    );
+    let mi = crate::model::MenuItem {
+        id: "deploy".into(),
+        title: "Deploy".into(),
+        command: Some("app deploy".into()),
+        ..Default::default()
+    };
+    match update(&mut st, AppMsg::EnterMenu(mi.clone())).as_slice() {
+        [Effect::ShowToast { text, .. }, Effect::RunPreflight { index: 0 }] => {
+            assert!(text.contains("'Logged in' failed — run `app login`"))
+        }
+        _ => panic!("expected the item to be blocked and the check retried"),
+    }
+
+    let _ = update(
+        &mut st,
+        AppMsg::PreflightDone {
+            index: 0,
+            outcome: Ok(LoadOutcome::Fallback(json!("alice"))),
+        },
+    );
+    let effs = update(&mut st, AppMsg::EnterMenu(mi));
+    assert!(matches!(effs.as_slice(), [Effect::RunStream { .. }]));
+}
+
+#[test]
+fn unauthenticated_exit_offers_login_and_retries() {
+    let mut st = AppState::default();
+    st.config.auth = Some(crate::model::AuthConfig {
+        check: "app auth status".into(),
+        login: "app login".into(),
+        mode: None,
+        unauthenticated_exit_code: Some(77),
+    });
+    let mi = crate::model::MenuItem {
+        id: "deploy".into(),
+        title: "Deploy".into(),
+        command: Some("app deploy".into()),
+        ..Default::default()
+    };
+    let _ = update(&mut st, AppMsg::EnterMenu(mi));
+    // Other failures leave the login dialog closed
+    let _ = update(
+        &mut st,
+        AppMsg::StreamDone {
+            result: None,
+            err: Some("Command failed (exit 1): app deploy".into()),
+        },
+    );
+    assert!(st.auth_prompt.is_none());
+    let _ = update(
+        &mut st,
+        AppMsg::StreamDone {
+            result: None,
+            err: Some("Command failed (exit 77): app deploy\nsession expired".into()),
+        },
+    );
+    assert_eq!(
+        st.auth_prompt.as_ref().map(|p| p.reason.as_str()),
+        Some("Command failed (exit 77): app deploy")
+    );
+
+    let effs = update(
+        &mut st,
+        AppMsg::AuthLoginDone {
+            outcome: Ok(LoadOutcome::Fallback(json!(null))),
+        },
+    );
+    assert!(st.auth_prompt.is_none());
+    assert!(effs.iter().any(|e| matches!(e, Effect::RunStream { .. })));
+}
+
+#[test]
+fn login_retries_the_list_that_failed_not_the_last_item_opened() {
+    let mut st = AppState::default();
+    st.config.auth = Some(crate::model::AuthConfig {
+        check: "app auth status".into(),
+        login: "app login".into(),
+        mode: None,
+        unauthenticated_exit_code: Some(77),
+    });
+    let pods = crate::model::MenuItem {
+        id: "pods".into(),
+        title: "Pods".into(),
+        command: Some("app pods".into()),
+        widget: Some("autoload_items".into()),
+        ..Default::default()
+    };
+    st.config.menu = vec![pods.clone()];
+    let deploy = crate::model::MenuItem {
+        id: "deploy".into(),
+        title: "Deploy".into(),
+        command: Some("app deploy".into()),
+        ..Default::default()
+    };
+    let _ = update(&mut st, AppMsg::EnterMenu(deploy));
+    // The background list load fails after the user opened another item
+    let _ = update(
+        &mut st,
+        AppMsg::LoadedMenu {
+            key: crate::nav::keys::menu_key(&pods),
+            outcome: Err("Command failed (exit 77): app pods".into()),
+        },
+    );
+    assert!(st.auth_prompt.is_some());
+    let effs = update(
+        &mut st,
+        AppMsg::AuthLoginDone {
+            outcome: Ok(LoadOutcome::Fallback(json!(null))),
+        },
+    );
+    assert!(effs
+        .iter()
+        .any(|e| matches!(e, Effect::LoadMenu { mi, .. } if mi.id == "pods")));
+    assert!(!effs.iter().any(|e| matches!(e, Effect::RunStream { .. })));
+
+    // Scheduled jobs are not re-run
+    let _ = update(
+        &mut st,
+        AppMsg::ScheduledDone {
+            id: 1,
+            outcome: Err("Command failed (exit 77): app sync".into()),
+        },
+    );
+    assert!(st.login_retry.is_none());
+}
+
+#[test]
+fn telemetry_records_item_runs_only_when_configured() {
+    let mi = crate::model::MenuItem {
+        id: "deploy".into(),
+        title: "Deploy".into(),
+        command: Some("app deploy".into()),
+        ..Default::default()
+    };
+    let done = || AppMsg::StreamDone {
+        result: Some(json!({"ok": true})),
+        err: None,
+    };
+    let mut st = AppState::default();
+    let _ = update(&mut st, AppMsg::EnterMenu(mi.clone()));
+    let _ = update(&mut st, done());
+    assert!(st.telemetry.events.is_empty());
+
+    st.config.telemetry = Some(crate::model::TelemetryConfig {
+        command: "usage-hook".into(),
+        ..Default::default()
+    });
+    let _ = update(&mut st, AppMsg::EnterMenu(mi));
+    let _ = update(&mut st, done());
+    assert_eq!(st.telemetry.events.len(), 1);
+    assert_eq!(st.telemetry.events[0]["item"], "deploy");
+    assert_eq!(st.telemetry.events[0]["success"], true);
+}
+
+#[test]
+fn filter_bar_rides_on_the_items_pane_b_result() {
+    use crate::ui::{PaneContent, PanelPane, PanelState as UiPanelState, View};
+    use crate::widgets::result_viewer::ResultViewerWidget;
+    use crossterm::event::KeyCode;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    st.view = View::Panel;
+    let mi = crate::model::MenuItem {
+        id: "jobs".into(),
+        title: "Jobs".into(),
+        command: Some("app jobs --env {env}".into()),
+        filters: Some(vec![
+            crate::model::FilterSpec {
+                name: "env".into(),
+                options: vec!["prod".into(), "dev".into()],
+                field: None,
+            },
+            crate::model::FilterSpec {
+                name: "status".into(),
+                options: vec!["all".into(), "failed".into()],
+                field: None,
+            },
+        ]),
+        ..Default::default()
+    };
+    let effs = update(&mut st, AppMsg::EnterMenu(mi));
+    let cmdline = match effs.as_slice() {
+        [Effect::LoadPanelCmd { cmdline, .. }] => cmdline.clone(),
+        _ => panic!("expected the Pane B command"),
+    };
+    assert_eq!(cmdline, "app jobs --env prod");
+
+    st.pane_b_source = Some(cmdline);
+    let _ = update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Items(vec![
+                json!({"status": "failed"}),
+                json!({"status": "running"}),
+            ])),
+        },
+    );
+    let Some(PaneContent::Widget(w)) = st.panel.as_mut().map(|ps| &mut ps.b_content) else {
+        panic!("expected a viewer in Pane B");
+    };
+    match w.on_key(KeyCode::Char('v')).as_slice() {
+        [Effect::LoadPanelCmd { cmdline, .. }] => assert_eq!(cmdline, "app jobs --env dev"),
+        _ => panic!("expected the command re-run with env=dev"),
+    }
+    // status is not in the command: filtered in place
+    w.on_key(KeyCode::Char('>'));
+    assert!(w.on_key(KeyCode::Char('v')).is_empty());
+    let rv = w.as_any().downcast_ref::<ResultViewerWidget>().unwrap();
+    assert_eq!(rv.value(), &json!([{"status": "failed"}]));
+}
+
+#[test]
+fn editable_fields_update_a_row_in_place() {
+    use crate::ui::{PaneContent, PanelPane, PanelState as UiPanelState, View};
+    use crate::widgets::result_viewer::ResultViewerWidget;
+    use crossterm::event::KeyCode;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    st.view = View::Panel;
+    let mi = crate::model::MenuItem {
+        id: "users".into(),
+        title: "Users".into(),
+        command: Some("app users".into()),
+        editable_fields: Some(vec!["email".into()]),
+        update_cmd: Some("app user set {id} {field} {value}".into()),
+        ..Default::default()
+    };
+    let effs = update(&mut st, AppMsg::EnterMenu(mi));
+    assert!(matches!(effs.as_slice(), [Effect::LoadPanelCmd { .. }]));
+    st.pane_b_source = Some("app users".into());
+    let _ = update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Items(vec![
+                json!({"id": 1, "email": "a@x"}),
+                json!({"id": 2, "email": "b@x"}),
+            ])),
+        },
+    );
+    let Some(PaneContent::Widget(w)) = st.panel.as_mut().map(|ps| &mut ps.b_content) else {
+        panic!("expected a viewer in Pane B");
+    };
+    w.on_key(KeyCode::Down);
+    w.on_key(KeyCode::Char('e'));
+    for _ in 0.."b@x".len() {
+        w.on_key(KeyCode::Backspace);
+    }
+    for c in "c@x".chars() {
+        w.on_key(KeyCode::Char(c));
+    }
+    match w.on_key(KeyCode::Enter).as_slice() {
+        [Effect::UpdateRow { cmdline }] => assert_eq!(cmdline, "app user set 2 email c@x"),
+        _ => panic!("expected the update command"),
+    }
+    let effs = update(
+        &mut st,
+        AppMsg::RowUpdated {
+            outcome: Ok(LoadOutcome::Fallback(JsonValue::Null)),
+        },
+    );
+    assert!(matches!(
+        effs.as_slice(),
+        [Effect::ShowToast {
+            level: crate::ui::ToastLevel::Success,
+            ..
+        }]
+    ));
+    let Some(PaneContent::Widget(w)) = st.panel.as_ref().map(|ps| &ps.b_content) else {
+        panic!("expected a viewer in Pane B");
+    };
+    let rv = w.as_any().downcast_ref::<ResultViewerWidget>().unwrap();
+    assert_eq!(rv.value()[1], json!({"id": 2, "email": "c@x"}));
+}
+
+#[test]
+fn form_batch_submits_file_rows_with_progress() {
+    use crate::ui::{PanelPane, PanelState as UiPanelState};
+    use crate::widgets::form_widget::FormWidget;
+    use crate::widgets::Widget;
+    use crossterm::event::KeyCode;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let spec = json!({
+        "type": "form",
+        "title": "Invite",
+        "submit_cmd": "app invite",
+        "batch": {"columns": {"Mail": "email"}},
+        "fields": [{"name": "email", "type": "text", "required": true}]
+    });
+    update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(spec)),
+        },
+    );
+    let path = std::env::temp_dir().join(format!("chi-batch-app-{}.json", std::process::id()));
+    std::fs::write(&path, r#"[{"Mail": "a@x"}, {"Mail": "b@x"}, {"Mail": ""}]"#).unwrap();
+    let fw = match st.panel.as_mut().map(|ps| &mut ps.b_content) {
+        Some(crate::ui::PaneContent::Widget(w)) => w.as_any_mut().downcast_mut::<FormWidget>(),
+        _ => None,
+    }
+    .expect("form widget");
+    fw.on_key(KeyCode::Char('b'));
+    assert!(fw.batch_open());
+    for c in path.to_str().unwrap().chars() {
+        fw.on_key(KeyCode::Char(c));
+    }
+    assert!(fw.on_key(KeyCode::Enter).is_empty());
+    // The preview lists the rows; Enter runs the valid ones
+    match fw.on_key(KeyCode::Enter).as_slice() {
+        [Effect::SubmitBatch { rows, .. }] => assert_eq!(
+            rows,
+            &vec![
+                (0, "app invite --email a@x".to_string()),
+                (1, "app invite --email b@x".to_string())
+            ]
+        ),
+        _ => panic!("expected the batch submission"),
+    }
+    let _ = std::fs::remove_file(&path);
+
+    let _ = update(
+        &mut st,
+        AppMsg::BatchRowDone {
+            index: 0,
+            outcome: Ok(LoadOutcome::Fallback(json!({"ok": true}))),
+        },
+    );
+    assert_eq!(st.status_text.as_deref(), Some("Batch: 1/2"));
+    assert_eq!(st.status_percent, Some(50.0));
+    let effs = update(
+        &mut st,
+        AppMsg::BatchRowDone {
+            index: 1,
+            outcome: Ok(LoadOutcome::Fallback(
+                json!({"ok": false, "data": {"message": "already invited"}}),
+            )),
+        },
+    );
+    assert!(st.status_text.is_none());
+    match effs.as_slice() {
+        [Effect::ShowToast { text, .. }] => {
+            assert_eq!(text, "Batch finished: 1 ok, 2 failed or skipped")
+        }
+        _ => panic!("expected the summary toast"),
+    }
+}
+
+#[test]
+fn unsaved_form_guards_entering_another_item() {
+    use crate::ui::{NavTarget, PanelPane, PanelState as UiPanelState};
+    use crate::widgets::form::FieldValue;
+    use crate::widgets::form_widget::FormWidget;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let spec = json!({
+        "type": "form",
+        "title": "Note",
+        "submit_cmd": "app note",
+        "fields": [{"name": "text", "type": "text"}]
+    });
+    update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(spec)),
+        },
+    );
+    let fw = match st.panel.as_mut().map(|ps| &mut ps.b_content) {
+        Some(crate::ui::PaneContent::Widget(w)) => w.as_any_mut().downcast_mut::<FormWidget>(),
+        _ => None,
+    }
+    .expect("form widget");
+    fw.form.fields[0].value = FieldValue::Text("draft".into());
+    crate::widgets::form::compute_dirty(&mut fw.form);
+    assert!(crate::ui::pane_b_unsaved(&st));
+
+    let mi = crate::model::MenuItem {
+        id: "other".into(),
+        title: "Other".into(),
+        command: Some("app other".into()),
+        ..Default::default()
+    };
+    assert!(update(&mut st, AppMsg::EnterMenu(mi)).is_empty());
+    assert!(matches!(st.nav_guard, Some(NavTarget::Enter(_))));
+
+    // "save": the navigation continues once the submit succeeds
+    st.nav_after_save = st.nav_guard.take();
+    let effs = update(
+        &mut st,
+        AppMsg::LoadedSubmitForm {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(json!({"ok": true}))),
+        },
+    );
+    assert!(effs.iter().any(|e| matches!(e, Effect::RunStream { .. })));
+    assert!(!crate::ui::pane_b_unsaved(&st));
+}
+
+#[test]
+fn slot_changes_rerun_the_pane_b_command_in_place() {
+    use crate::ui::{PaneContent, PanelPane, PanelState as UiPanelState, View};
+    use crate::widgets::result_viewer::ResultViewerWidget;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    st.view = View::Panel;
+    let mi = crate::model::MenuItem {
+        id: "detail".into(),
+        title: "Pod detail".into(),
+        command: Some("app pod get {slot.pod}".into()),
+        ..Default::default()
+    };
+    match update(&mut st, AppMsg::EnterMenu(mi)).as_slice() {
+        [Effect::LoadPanelCmd { cmdline, .. }] => assert_eq!(cmdline, "app pod get ''"),
+        _ => panic!("expected the Pane B load"),
+    }
+    st.pane_b_source = Some("app pod get ''".into());
+    update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(json!({"name": null}))),
+        },
+    );
+    let effs = update(
+        &mut st,
+        AppMsg::Publish {
+            slot: "pod".into(),
+            value: "web-1".into(),
+        },
+    );
+    match effs.as_slice() {
+        [Effect::LoadPanelCmd { cmdline, .. }] => assert_eq!(cmdline, "app pod get web-1"),
+        _ => panic!("expected a re-run"),
+    }
+    let Some(PaneContent::Widget(w)) = st.panel.as_ref().map(|ps| &ps.b_content) else {
+        panic!("expected a viewer in Pane B");
+    };
+    let rv = w.as_any().downcast_ref::<ResultViewerWidget>().unwrap();
+    assert_eq!(rv.source(), Some("app pod get web-1"));
+    // The same value again does nothing
+    assert!(update(
+        &mut st,
+        AppMsg::Publish {
+            slot: "pod".into(),
+            value: "web-1".into(),
+        },
+    )
+    .is_empty());
+}
+
+#[test]
+fn board_item_loads_cards_in_place_and_reports_moves() {
+    use crate::ui::{PaneContent, PanelPane, View};
+    use crate::widgets::board::BoardWidget;
+    use crate::widgets::Widget;
+    use crossterm::event::KeyCode;
+    let mut st = AppState::default();
+    let mi = crate::model::MenuItem {
+        id: "deploys".into(),
+        title: "Deploys".into(),
+        widget: Some("board".into()),
+        command: Some("app deploys".into()),
+        board_columns: Some(vec!["staging".into(), "prod".into()]),
+        status_field: Some("stage".into()),
+        move_cmd: Some("app promote {id} {to}".into()),
+        ..Default::default()
+    };
+    match update(&mut st, AppMsg::EnterMenu(mi)).as_slice() {
+        [Effect::LoadPanelCmd { cmdline, .. }] => assert_eq!(cmdline, "app deploys"),
+        _ => panic!("expected the board load"),
+    }
+    assert_eq!(st.view, View::Panel);
+    st.pane_b_source = Some("app deploys".into());
+    update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Items(vec![
+                json!({"id": "api", "stage": "staging"}),
+            ])),
+        },
+    );
+    let Some(PaneContent::Widget(w)) = st.panel.as_mut().map(|ps| &mut ps.b_content) else {
+        panic!("expected the board in Pane B");
+    };
+    let board = w.as_any_mut().downcast_mut::<BoardWidget>().unwrap();
+    match board.on_key(KeyCode::Char('>')).as_slice() {
+        [Effect::UpdateRow { cmdline }] => assert_eq!(cmdline, "app promote api prod"),
+        _ => panic!("expected the move command"),
+    }
+    let effs = update(
+        &mut st,
+        AppMsg::RowUpdated {
+            outcome: Ok(LoadOutcome::Fallback(json!({"ok": true}))),
+        },
+    );
+    assert!(matches!(
+        effs.as_slice(),
+        [Effect::ShowToast { text, .. }] if text == "Moved api → prod"
+    ));
+}
+
+#[test]
+fn commands_push_banners_through_the_control_envelope() {
+    let mut st = AppState::default();
+    let until = "2999-01-01T00:00Z";
+    update(
+        &mut st,
+        AppMsg::ScheduledDone {
+            id: 0,
+            outcome: Ok(LoadOutcome::Fallback(json!({
+                "ok": true,
+                "_chi": {"banner": {"text": "Release freeze", "until": until}},
+            }))),
+        },
+    );
+    let text = |st: &AppState| -> Vec<String> {
+        crate::widgets::notice::banner_lines(st)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    };
+    let lines = text(&st);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].contains("Release freeze — ends in"));
+
+    // An empty text clears the pushed banner
+    update(
+        &mut st,
+        AppMsg::RowUpdated {
+            outcome: Ok(LoadOutcome::Fallback(
+                json!({"_chi": {"banner": {"text": ""}}}),
+            )),
+        },
+    );
+    assert!(text(&st).is_empty());
+}
+
+#[test]
+fn form_text_edits_at_a_grapheme_caret_and_takes_pastes() {
+    use crate::ui::{PanelPane, PanelState as UiPanelState};
+    use crate::widgets::form::FieldValue;
+    use crate::widgets::form_widget::FormWidget;
+    use crate::widgets::Widget;
+    use crossterm::event::KeyCode;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let spec = json!({
+        "type": "form",
+        "title": "Profile",
+        "submit_cmd": "app profile",
+        "fields": [
+            {"name": "name", "type": "text"},
+            {"name": "age"}
+        ]
+    });
+    update(
+        &mut st,
+        AppMsg::LoadedPanel {
+            pane: PanelPane::B,
+            outcome: Ok(LoadOutcome::Fallback(spec)),
+        },
+    );
+    let ps = st.panel.as_mut().unwrap();
+    let crate::ui::PaneContent::Widget(w) = &mut ps.b_content else {
+        panic!("expected widget");
+    };
+    let fw = w
+        .as_any_mut()
+        .downcast_mut::<FormWidget>()
+        .expect("form widget");
+    let text = |fw: &FormWidget, i: usize| match &fw.form.fields[i].value {
+        FieldValue::Text(s) => s.clone(),
+        other => panic!("unexpected value {other:?}"),
+    };
+    fw.on_key(KeyCode::Enter);
+    assert!(fw.form.editing);
+    // A letter composed by a dead key arrives as base + combining accent
+    for c in ['J', 'o', 's', 'e', '\u{301}'] {
+        fw.on_key(KeyCode::Char(c));
+    }
+    // Left steps over "é" as one character; Backspace then removes the "s"
+    fw.on_key(KeyCode::Left);
+    fw.on_key(KeyCode::Backspace);
+    assert_eq!(text(fw, 0), "Joe\u{301}");
+    // An IME commit or a bracketed paste goes in at the caret, on one line
+    fw.paste("s\né");
+    assert_eq!(text(fw, 0), "Jos ée\u{301}");
+    fw.on_key(KeyCode::End);
+    fw.on_key(KeyCode::Backspace);
+    fw.on_key(KeyCode::Home);
+    fw.on_key(KeyCode::Delete);
+    assert_eq!(text(fw, 0), "os é");
+    assert!(fw.form.dirty);
+
+    // Number fields (from a JSON Schema) keep their rules for pasted text
+    fw.on_key(KeyCode::Enter);
+    fw.form.fields[1].kind = crate::widgets::form::FieldKind::Number {
+        is_integer: true,
+        minimum: None,
+        maximum: None,
+        exclusive_minimum: false,
+        exclusive_maximum: false,
+        multiple_of: None,
+    };
+    fw.form.selected = 1;
+    fw.on_key(KeyCode::Enter);
+    fw.paste("4x2");
+    assert_eq!(text(fw, 1), "42");
+    fw.on_key(KeyCode::Esc);
+    // Outside editing a paste changes nothing
+    fw.paste("7");
+    assert_eq!(text(fw, 1), "42");
+}
+
+#[test]
+fn self_referencing_pane_yaml_stops_with_an_error_in_the_pane() {
+    use crate::ui::{PanelPane, PanelState as UiPanelState};
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let start = Effect::LoadPanelYaml {
+        pane: PanelPane::B,
+        path: "/tmp/loop.yaml".into(),
+    };
+    st.panel_nesting.started(PanelPane::B, &start);
+    let looped = |st: &mut AppState, path: &str| {
+        update(
+            st,
+            AppMsg::LoadedPanel {
+                pane: PanelPane::B,
+                outcome: Ok(LoadOutcome::Fallback(
+                    json!({"type": "json_viewer", "yaml": path}),
+                )),
+            },
+        )
+    };
+    let effects = looped(&mut st, "/tmp/loop.yaml");
+    assert!(effects.is_empty());
+    let err = st.panel.as_ref().unwrap().b.last_error.clone().unwrap();
+    assert_eq!(err, "Nested panel cycle: /tmp/loop.yaml → /tmp/loop.yaml");
+
+    // The screen's limit applies to chains of distinct specs
+    st.panel_max_depth = Some(2);
+    st.panel_nesting.started(PanelPane::B, &start);
+    let effects = looped(&mut st, "/tmp/next.yaml");
+    assert!(matches!(effects.as_slice(), [Effect::LoadPanelYaml { .. }]));
+    st.panel_nesting.started(PanelPane::B, &effects[0]);
+    assert!(looped(&mut st, "/tmp/deeper.yaml").is_empty());
+    let err = st.panel.as_ref().unwrap().b.last_error.clone().unwrap();
+    assert!(err.starts_with("Nested panel limit reached (2 levels)"));
+}
+
+#[test]
+fn empty_results_show_the_items_empty_state_in_place_of_the_viewer() {
+    use crate::ui::{PaneContent, PanelPane, PanelState as UiPanelState};
+    use crate::widgets::empty_state::{EmptyState, EmptyStateWidget};
+    use crate::widgets::result_viewer::ResultViewerWidget;
+    let mut st = AppState::default();
+    st.panel = Some(UiPanelState::default());
+    let mi = crate::model::MenuItem {
+        id: "deploys".into(),
+        title: "Deployments".into(),
+        empty_text: Some("No deployments yet".into()),
+        empty_action: Some(crate::model::EmptyActionSpec {
+            item: "deploys".into(),
+            key: Some("n".into()),
+            label: None,
+        }),
+        ..Default::default()
+    };
+    st.pane_b_empty = EmptyState::from_item(&mi, std::slice::from_ref(&mi));
+    let loaded = |st: &mut AppState, items: Vec<JsonValue>| {
+        st.pane_b_source = Some("app deploys".into());
+        let _ = update(
+            st,
+            AppMsg::LoadedPanel {
+                pane: PanelPane::B,
+                outcome: Ok(LoadOutcome::Items(items)),
+            },
+        );
+    };
+    let content = |st: &mut AppState| {
+        let ps = st.panel.as_mut().unwrap();
+        let PaneContent::Widget(w) = &mut ps.b_content else {
+            panic!("expected a widget in Pane B");
+        };
+        (
+            ps.b_history.len(),
+            w.as_any().is::<EmptyStateWidget>(),
+            w.as_any().is::<ResultViewerWidget>(),
+            w.on_key(crossterm::event::KeyCode::Char('n')),
+        )
+    };
+    loaded(&mut st, vec![]);
+    let (history, empty, _, effs) = content(&mut st);
+    assert_eq!(history, 1);
+    assert!(empty);
+    assert!(matches!(effs.as_slice(), [Effect::OpenItem(id)] if id == "deploys"));
+
+    // Results of the same command take the empty state's place, and vice versa
+    loaded(&mut st, vec![json!({"id": 1})]);
+    let (history, _, viewer, _) = content(&mut st);
+    assert_eq!(history, 1);
+    assert!(viewer);
+    loaded(&mut st, vec![]);
+    let (history, empty, _, _) = content(&mut st);
+    assert_eq!(history, 1);
+    assert!(empty);
+}
+
+#[test]
+fn pane_a_widget_hosts_a_watchdog_that_survives_re_entering_the_panel() {
+    use crate::ui::PanelPane;
+    use crate::widgets::watchdog::WatchdogWidget;
+    let mut st = AppState::default();
+    let mi = crate::model::MenuItem {
+        id: "dev".into(),
+        title: "Dev servers".into(),
+        widget: Some("panel".into()),
+        pane_b_cmd: Some("app routes".into()),
+        pane_a_widget: Some(json!({"type": "watchdog", "commands": ["sleep 30"]})),
+        ..Default::default()
+    };
+    st.config.menu = vec![mi.clone()];
+    let effs = update(&mut st, AppMsg::EnterMenu(mi.clone()));
+    // Pane B still loads its browsing content
+    assert!(matches!(
+        effs.as_slice(),
+        [Effect::LoadPanelCmd {
+            pane: PanelPane::B,
+            ..
+        }]
+    ));
+    let hosted = |st: &AppState| {
+        st.panel
+            .as_ref()
+            .and_then(|ps| ps.a_content.as_ref())
+            .and_then(|w| w.as_any().downcast_ref::<WatchdogWidget>())
+            .map(|w| w.session_ref())
+            .expect("watchdog hosted in Pane A")
+    };
+    let first = hosted(&st);
+    let key = "menu:dev/pane:A";
+    assert!(std::sync::Arc::ptr_eq(&first, &st.watchdog_sessions[key]));
+    assert!(!st.watchdog_sessions.contains_key("menu:dev"));
+
+    // Re-entering re-attaches instead of starting the commands again
+    let _ = update(&mut st, AppMsg::EnterMenu(mi));
+    assert!(std::sync::Arc::ptr_eq(&first, &hosted(&st)));
+    first.lock().unwrap().stop_all();
+}
+
+#[test]
+fn paginated_lists_keep_only_items_and_turn_pages_in_place() {
+    let mut st = AppState::default();
+    st.config.menu = vec![MenuItem {
+        id: "jobs".into(),
+        title: "Jobs".into(),
+        widget: Some("lazy_items".into()),
+        command: Some("app jobs list".into()),
+        ..Default::default()
+    }];
+    let page = |n: i64, items: Vec<JsonValue>| AppMsg::LoadedMenu {
+        key: "menu:jobs".into(),
+        outcome: Ok(LoadOutcome::ItemsWithPagination {
+            items,
+            pagination: json!({
+                "current_page": n,
+                "total_pages": 3,
+                "prev_page_cmd": format!("app jobs list --page {}", n - 1),
+                "next_page_cmd": format!("app jobs list --page {}", n + 1),
+            }),
+        }),
+    };
+    let _ = update(
+        &mut st,
+        page(2, vec![json!({"id": "j1"}), json!({"id": "j2"})]),
+    );
+    assert_eq!(
+        st.children["menu:jobs"],
+        [json!({"id": "j1"}), json!({"id": "j2"})]
+    );
+    assert_eq!(st.pages["menu:jobs"].current, Some(2));
+
+    let effects = update(
+        &mut st,
+        AppMsg::TurnPage {
+            key: "menu:jobs".into(),
+            forward: true,
+        },
+    );
+    assert!(matches!(
+        effects.as_slice(),
+        [Effect::LoadMenu { mi, key }]
+            if key == "menu:jobs" && mi.command.as_deref() == Some("app jobs list --page 3")
+    ));
+    // The configured command is untouched
+    assert_eq!(st.config.menu[0].command.as_deref(), Some("app jobs list"));
+
+    // A reload without pages drops the page state
+    let _ = update(
+        &mut st,
+        AppMsg::LoadedMenu {
+            key: "menu:jobs".into(),
+            outcome: Ok(LoadOutcome::Items(vec![json!({"id": "j1"})])),
+        },
+    );
+    assert!(!st.pages.contains_key("menu:jobs"));
+}
+
+#[test]
+fn condition_commands_hide_and_disable_menu_items() {
+    let mut st = AppState::default();
+    let deploy = crate::model::MenuItem {
+        id: "deploy".into(),
+        title: "Deploy".into(),
+        command: Some("app deploy".into()),
+        when_cmd: Some("app whoami".into()),
+        enabled_cmd: Some("app can-deploy".into()),
+        ..Default::default()
+    };
+    st.config.menu = vec![deploy.clone()];
+    let jobs = st.conditions.start(&st.config.menu);
+    assert_eq!(jobs.len(), 2);
+    // Hidden until the `when_cmd` passes
+    assert!(crate::nav::flatten::flatten_nodes(&st).is_empty());
+    let effs = update(&mut st, AppMsg::EnterMenu(deploy.clone()));
+    assert!(matches!(effs.as_slice(), [Effect::ShowToast { .. }]));
+
+    let run = st.conditions.run();
+    let text = |s: &str| Ok(crate::ui::LoadOutcome::Fallback(json!(s)));
+    use crate::nav::conditions::Kind;
+    st.conditions
+        .done(run, "menu:deploy".into(), Kind::When, text("alice"));
+    st.conditions.done(
+        run,
+        "menu:deploy".into(),
+        Kind::Enabled,
+        Err("Command failed (exit 1): app can-deploy\nfrozen until Monday".into()),
+    );
+    assert_eq!(crate::nav::flatten::flatten_nodes(&st).len(), 1);
+    match update(&mut st, AppMsg::EnterMenu(deploy.clone())).as_slice() {
+        [Effect::ShowToast { text, .. }] => {
+            assert_eq!(text, "Deploy is disabled — frozen until Monday")
+        }
+        _ => panic!("expected the item to stay closed"),
+    }
+
+    st.conditions
+        .done(run, "menu:deploy".into(), Kind::Enabled, text("yes"));
+    let effs = update(&mut st, AppMsg::EnterMenu(deploy.clone()));
+    assert!(matches!(effs.as_slice(), [Effect::RunStream { .. }]));
+
+    // Refresh asks again
+    let effs = update(&mut st, AppMsg::RefreshMenu(deploy));
+    assert!(effs.iter().any(|e| matches!(e, Effect::CheckConditions)));
+}
@@ -0,0 +1,198 @@
+use crate::app::{update, AppMsg};
+use crate::model::{AppConfig, MenuItem};
+use crate::nav::flatten::flatten_nodes;
+use crate::nav::keys::{child_key, menu_key};
+use crate::ui::{AppState, LoadOutcome, PanelPane, PanelState};
+use crate::widgets::result_viewer::ResultViewerWidget;
+use crate::widgets::watchdog::output::OutputBuffer;
+use crate::widgets::Widget;
+use crossterm::event::KeyCode;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use serde_json::{json, Value as JsonValue};
+
+// Fixtures for benches/core.rs: the hot paths (menu flattening, result rendering, update()
+// and the watchdog output buffer) driven with synthetic data, without a terminal or backend.
+
+fn lazy_item(i: usize) -> MenuItem {
+    MenuItem {
+        id: format!("m{i}"),
+        title: format!("Item {i}"),
+        widget: Some("lazy_items".into()),
+        command: Some(format!("example-app list-items --group {i}")),
+        ..Default::default()
+    }
+}
+
+// Rows shaped like a list command's output: ids, nested objects, tags and a long text field
+pub fn payload(rows: usize) -> JsonValue {
+    JsonValue::Array(
+        (0..rows)
+            .map(|i| {
+                let status = ["ok", "degraded", "failed"][i % 3];
+                json!({
+                    "id": format!("row-{i}"),
+                    "title": format!("Row {i}"),
+                    "status": status,
+                    "count": i * 7,
+                    "owner": {"name": format!("team-{}", i % 13), "email": format!("t{i}@example.com")},
+                    "tags": ["alpha", "beta", "gamma"],
+                    "description": "lorem ipsum dolor sit amet ".repeat(4),
+                })
+            })
+            .collect(),
+    )
+}
+
+// Main menu of `items` lazy items, each expanded with `children` loaded rows
+pub struct Menu {
+    state: AppState,
+}
+
+pub fn menu(items: usize, children: usize) -> Menu {
+    let mut state = AppState::default();
+    let menu: Vec<MenuItem> = (0..items).map(lazy_item).collect();
+    for mi in &menu {
+        let key = menu_key(mi);
+        let rows = payload(children).as_array().cloned().unwrap_or_default();
+        state.expanded.insert(key.clone());
+        if let Some(first) = rows.first() {
+            state.expanded.insert(child_key(&key, first, 0));
+        }
+        state.children.insert(key, rows);
+    }
+    *state.config = AppConfig {
+        menu,
+        ..Default::default()
+    };
+    Menu { state }
+}
+
+impl Menu {
+    // Rows rebuilt after a change to the expanded set
+    pub fn flatten(&mut self) -> usize {
+        let _ = &mut *self.state.expanded;
+        flatten_nodes(&self.state).len()
+    }
+
+    // Rows served from the cache (no change since the last call)
+    pub fn flatten_cached(&self) -> usize {
+        flatten_nodes(&self.state).len()
+    }
+}
+
+pub fn pretty(value: &JsonValue) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+// Result viewer over a value, drawn into an off-screen terminal
+pub struct Viewer {
+    widget: ResultViewerWidget,
+    terminal: Terminal<TestBackend>,
+}
+
+pub fn viewer(value: JsonValue, width: u16, height: u16) -> Viewer {
+    Viewer {
+        widget: ResultViewerWidget::new("Result", value),
+        terminal: Terminal::new(TestBackend::new(width, height)).expect("test backend"),
+    }
+}
+
+impl Viewer {
+    pub fn draw(&mut self) {
+        let w = &mut self.widget;
+        let _ = self.terminal.draw(|f| w.render(f, f.area(), true, 0));
+    }
+
+    // A page down followed by a frame, as when paging through the result
+    pub fn page_down(&mut self) {
+        let _ = self.widget.on_key(KeyCode::PageDown);
+        self.draw();
+    }
+}
+
+// App state with the panel open, for feeding messages through update()
+pub struct App {
+    state: AppState,
+}
+
+pub fn app(items: usize) -> App {
+    let mut state = AppState::default();
+    *state.config = AppConfig {
+        menu: (0..items).map(lazy_item).collect(),
+        ..Default::default()
+    };
+    state.panel = Some(PanelState::default());
+    App { state }
+}
+
+impl App {
+    // A list command's result landing in Pane B (viewer replaced in place)
+    pub fn loaded_panel(&mut self, items: Vec<JsonValue>) -> usize {
+        self.state.pane_b_source = Some("example-app list-items".into());
+        update(
+            &mut self.state,
+            AppMsg::LoadedPanel {
+                pane: PanelPane::B,
+                outcome: Ok(LoadOutcome::Items(items)),
+            },
+        )
+        .len()
+    }
+
+    // Lazy children of the first menu item arriving
+    pub fn loaded_menu(&mut self, items: Vec<JsonValue>) -> usize {
+        let key = menu_key(&self.state.config.menu[0]);
+        update(
+            &mut self.state,
+            AppMsg::LoadedMenu {
+                key,
+                outcome: Ok(LoadOutcome::Items(items)),
+            },
+        )
+        .len()
+    }
+}
+
+// Lines pushed through a watchdog command's output buffer (capped like a running session)
+pub fn watchdog_lines(lines: &[String]) -> OutputBuffer {
+    let buf = OutputBuffer::new();
+    for line in lines {
+        buf.push(line.clone());
+    }
+    buf
+}
+
+// What a watchdog pane copies per frame: the last `rows` lines of a full buffer
+pub fn watchdog_tail(buf: &OutputBuffer, rows: usize) -> usize {
+    buf.read(|l| {
+        l.range(l.len().saturating_sub(rows), rows)
+            .map(str::len)
+            .sum()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixtures_exercise_the_paths() {
+        // 3 items, each with 4 rows; the first row is expanded but has no children
+        let mut m = menu(3, 4);
+        assert_eq!(m.flatten(), 15);
+        assert_eq!(m.flatten_cached(), 15);
+        let mut a = app(2);
+        let rows = payload(10).as_array().cloned().unwrap();
+        a.loaded_panel(rows.clone());
+        a.loaded_menu(rows);
+        assert_eq!(a.state.children.values().next().map(Vec::len), Some(10));
+        let lines: Vec<String> = (0..6_000).map(|i| i.to_string()).collect();
+        let buf = watchdog_lines(&lines);
+        assert_eq!(buf.len(), 5_000);
+        assert!(watchdog_tail(&buf, 40) > 0);
+        let mut v = viewer(payload(50), 80, 20);
+        v.page_down();
+        assert!(pretty(&payload(1)).contains("\"row-0\""));
+    }
+}
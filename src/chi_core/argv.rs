@@ -0,0 +1,148 @@
+use serde_yaml::Value as YamlValue;
+
+// Exec form: `command: ["mycli", "list", "--name", "{name}"]` (any `command`, `cmd` or
+// `*_cmd` key) is the argument vector itself. It is stored as a command line whose literal
+// characters are escaped, so splitting it gives back exactly these arguments: spaces and
+// quotes in an element (or in a `{var.x}` it contains) never start a new argument. Later
+// substitutions (`{field}`, `${selected.x}`, `{slot.x}`) insert shell-quoted values that stay
+// inside their element. Either form runs the program directly; no shell is involved.
+
+// One element as a word of a command line; `{...}` / `${...}` placeholders are kept as written
+fn escape(arg: &str) -> String {
+    if arg.is_empty() {
+        return "''".to_string();
+    }
+    let mut out = String::with_capacity(arg.len());
+    for c in arg.chars() {
+        match c {
+            // A backslash before a newline would join the lines
+            '\n' => out.push_str("'\n'"),
+            ' ' | '\t' | '\r' | '\'' | '"' | '\\' | '#' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// A value as one shell-quoted word of a command line. NUL bytes (which no argument can
+// carry) are dropped, so the value is always quoted, never passed through as written.
+pub fn quote(value: &str) -> String {
+    let value = value.replace('\0', "");
+    shlex::try_quote(&value).map_or_else(|_| "''".to_string(), |q| q.into_owned())
+}
+
+// The command line of an argument vector
+pub fn join<S: AsRef<str>>(args: &[S]) -> String {
+    args.iter()
+        .map(|a| escape(a.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_command_key(key: &str) -> bool {
+    key == "command" || key == "cmd" || key.ends_with("_cmd")
+}
+
+// Rewrite every exec-form command of a parsed file into its command line
+pub fn normalize(doc: &mut YamlValue) -> Result<(), String> {
+    walk(doc, "")
+}
+
+fn walk(v: &mut YamlValue, path: &str) -> Result<(), String> {
+    match v {
+        YamlValue::Mapping(m) => {
+            for (k, x) in m.iter_mut() {
+                let key = k.as_str().unwrap_or("?");
+                let child = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                if let (true, YamlValue::Sequence(seq)) = (is_command_key(key), &*x) {
+                    let args = seq
+                        .iter()
+                        .map(|a| match a {
+                            YamlValue::String(s) => Some(s.clone()),
+                            YamlValue::Number(n) => Some(n.to_string()),
+                            YamlValue::Bool(b) => Some(b.to_string()),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| format!("{child}: every argument must be a string"))?;
+                    if args.is_empty() {
+                        return Err(format!("{child}: empty argument list"));
+                    }
+                    *x = YamlValue::String(join(&args));
+                } else {
+                    walk(x, &child)?;
+                }
+            }
+        }
+        YamlValue::Sequence(seq) => {
+            for (i, x) in seq.iter_mut().enumerate() {
+                walk(x, &format!("{path}[{i}]"))?;
+            }
+        }
+        YamlValue::Tagged(t) => walk(&mut t.value, path)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn quoted_values_split_back_into_one_word() {
+        for value in ["plain", "a b", "it's", "", "$(rm -rf ~)", "x\0y"] {
+            let word = quote(value);
+            assert_eq!(
+                shlex::split(&format!("echo {word}")).unwrap()[1..],
+                [value.replace('\0', "")]
+            );
+        }
+    }
+
+    #[test]
+    fn exec_form_splits_back_into_its_arguments() {
+        let args = [
+            "mycli",
+            "list",
+            "--label",
+            "a b",
+            "it's",
+            "",
+            "# not a comment",
+            "x\ny",
+        ];
+        assert_eq!(shlex::split(&join(&args)).unwrap(), args);
+
+        // A value filled into a placeholder stays inside its argument
+        let line = join(&["mycli", "greet", "hello {name}!"]);
+        let filled = crate::widgets::row_edit::fill_placeholders(&line, &json!({"name": "x y'z"}));
+        assert_eq!(
+            shlex::split(&filled).unwrap(),
+            ["mycli", "greet", "hello x y'z!"]
+        );
+
+        let mut doc: YamlValue = serde_yaml::from_str(
+            "menu:\n  - id: a\n    command: [mycli, get, 'my pod', 3]\n    commands: [x, y]\n    pane_b_cmd: mycli show\n",
+        )
+        .unwrap();
+        normalize(&mut doc).unwrap();
+        assert_eq!(doc["menu"][0]["command"], "mycli get my\\ pod 3");
+        assert_eq!(doc["menu"][0]["commands"][1], "y");
+        assert_eq!(doc["menu"][0]["pane_b_cmd"], "mycli show");
+
+        let mut bad: YamlValue = serde_yaml::from_str("cmd: [mycli, {a: 1}]\n").unwrap();
+        assert_eq!(
+            normalize(&mut bad).unwrap_err(),
+            "cmd: every argument must be a string"
+        );
+    }
+}
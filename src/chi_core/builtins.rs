@@ -0,0 +1,221 @@
+use crate::model::AppConfig;
+
+/// Markdown pages generated from the loaded config (`builtin:` on markdown menu items).
+/// `tab_order` is the session's tab order (tabs moved with Ctrl+Shift+←/→).
+pub fn builtin_markdown(name: &str, cfg: &AppConfig, tab_order: &[String]) -> Option<String> {
+    match name {
+        "menu_tree" => {
+            let tree = crate::nav::tree::menu_tree(cfg);
+            Some(format!(
+                "# Menu tree\n\nPress `e` to export this page.\n\n```text\n{}```\n",
+                crate::nav::tree::render_text(&tree)
+            ))
+        }
+        "cheatsheet" => Some(cheatsheet(cfg, tab_order)),
+        "doctor" => Some(doctor(cfg)),
+        _ => None,
+    }
+}
+
+/// File name used when exporting a generated page.
+pub fn export_file_name(name: &str) -> String {
+    format!("{name}.md")
+}
+
+// Digit hotkey of a `[[n]]` marker (only single digits are reachable from the keyboard)
+fn digit_hotkey(title: &str) -> Option<char> {
+    let rest = &title[title.find("[[")? + 2..];
+    let mut chars = rest.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(d), Some(']'), Some(']')) if d.is_ascii_digit() => Some(d),
+        _ => None,
+    }
+}
+
+fn cheatsheet(cfg: &AppConfig, tab_order: &[String]) -> String {
+    let mut md = String::new();
+    let name = cfg.header.as_deref().unwrap_or("CHI TUI");
+    md.push_str(&format!(
+        "# {name} — cheat-sheet\n\nPress `e` to export this page.\n"
+    ));
+    if !cfg.horizontal_menu.is_empty() {
+        md.push_str("\n## Tabs\n\n| Key | Tab |\n|---|---|\n");
+        let shown = crate::widgets::horizontal_menu::ordered_tabs(&cfg.horizontal_menu, tab_order);
+        for (i, tab) in shown.into_iter().take(12).enumerate() {
+            let title = &cfg.horizontal_menu[tab].title;
+            md.push_str(&format!("| F{} | {title} |\n", i + 1));
+        }
+    }
+    md.push_str("\n## Items\n\n| Key | Item | Id | Kind | Verbs |\n|---|---|---|---|---|\n");
+    for (mi, node) in cfg.menu.iter().zip(crate::nav::tree::menu_tree(cfg)) {
+        if node.kind == "header" {
+            continue;
+        }
+        // `hotkey:` first, else the `[[n]]` digit
+        let key = crate::nav::hotkeys::key_of(mi)
+            .or_else(|| digit_hotkey(&node.title))
+            .map(|c| c.to_string())
+            .unwrap_or_default();
+        let verbs = mi
+            .verbs
+            .iter()
+            .flatten()
+            .map(|v| format!("`{}` {}", v.key, v.title))
+            .collect::<Vec<_>>()
+            .join(", ");
+        md.push_str(&format!(
+            "| {key} | {} | `{}` | {} | {verbs} |\n",
+            node.title, node.id, node.kind
+        ));
+    }
+    md.push_str("\n## Keys\n\n| Key | Action |\n|---|---|\n");
+    for k in crate::nav::hotkeys::GLOBAL_KEYS {
+        md.push_str(&format!("| {} | {} |\n", k.keys, k.action));
+    }
+    let esc = if cfg.can_close {
+        "back / close panel"
+    } else {
+        "back (panel stays open on this screen)"
+    };
+    md.push_str(&format!("| Esc | {esc} |\n"));
+    md
+}
+
+fn doctor(cfg: &AppConfig) -> String {
+    let mut md =
+        String::from("# Doctor\n\nPress `e` to export this page.\n\n## Broken references\n\n");
+    let refs = crate::chi_core::file_refs::broken(&cfg.menu);
+    if refs.is_empty() {
+        md.push_str("Every file referenced by this screen's items exists.\n");
+        return md;
+    }
+    md.push_str("| Item | Id | Field | Path |\n|---|---|---|---|\n");
+    for b in refs {
+        md.push_str(&format!(
+            "| {} | `{}` | `{}` | `{}` |\n",
+            b.title, b.id, b.field, b.path
+        ));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{HorizontalMenuItem, MenuItem, VerbSpec};
+
+    #[test]
+    fn cheatsheet_lists_tabs_items_and_hotkeys() {
+        let cfg = AppConfig {
+            header: Some("Demo".into()),
+            horizontal_menu: vec![
+                HorizontalMenuItem {
+                    id: "main".into(),
+                    title: "Main".into(),
+                    config: None,
+                    hidden: false,
+                },
+                HorizontalMenuItem {
+                    id: "ops".into(),
+                    title: "Ops".into(),
+                    config: None,
+                    hidden: false,
+                },
+            ],
+            menu: vec![
+                MenuItem {
+                    id: "hello".into(),
+                    title: "[[1]] Hello".into(),
+                    command: Some("app hello".into()),
+                    ..Default::default()
+                },
+                MenuItem {
+                    id: "docs".into(),
+                    title: "Docs [[12]]".into(),
+                    widget: Some("markdown".into()),
+                    ..Default::default()
+                },
+                MenuItem {
+                    id: "deploys".into(),
+                    title: "Deploys".into(),
+                    command: Some("app deploys".into()),
+                    hotkey: Some("d".into()),
+                    verbs: Some(vec![
+                        VerbSpec {
+                            key: 'l',
+                            title: "logs".into(),
+                            command: "app logs {id}".into(),
+                            confirm: false,
+                        },
+                        VerbSpec {
+                            key: 'x',
+                            title: "rollback".into(),
+                            command: "app rollback {id}".into(),
+                            confirm: true,
+                        },
+                    ]),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let md = builtin_markdown("cheatsheet", &cfg, &[]).unwrap();
+        assert!(md.starts_with("# Demo — cheat-sheet"));
+        assert!(md.contains("| F1 | Main |\n| F2 | Ops |"));
+        // Tabs moved this session keep their F-key
+        let moved = builtin_markdown("cheatsheet", &cfg, &["ops".into()]).unwrap();
+        assert!(moved.contains("| F1 | Ops |\n| F2 | Main |"));
+        assert!(md.contains("| 1 | [[1]] Hello | `hello` | command |  |"));
+        // Two-digit markers cannot be typed as a single key
+        assert!(md.contains("|  | Docs [[12]] | `docs` | markdown |  |"));
+        assert!(md.contains("| d | Deploys | `deploys` | command | `l` logs, `x` rollback |"));
+        assert!(md.contains("| Esc | back / close panel |"));
+    }
+
+    #[test]
+    fn cheatsheet_lists_every_reserved_key() {
+        let md = builtin_markdown("cheatsheet", &AppConfig::default(), &[]).unwrap();
+        let keys: Vec<&str> = md
+            .split("## Keys")
+            .nth(1)
+            .unwrap()
+            .lines()
+            .filter_map(|l| l.strip_prefix("| ")?.split(" | ").next())
+            .collect();
+        for c in (' '..='~').filter(|c| crate::nav::hotkeys::reserved(*c)) {
+            let shown = match c {
+                ' ' => "space".to_string(),
+                '0'..='9' => "0-9".to_string(),
+                'c' => "Ctrl+C".to_string(),
+                _ => c.to_string(),
+            };
+            assert!(
+                keys.iter().any(|k| k.split([' ', ',']).any(|w| w == shown)),
+                "'{c}' is missing from the cheat-sheet"
+            );
+        }
+        for shown in ["Ctrl+K", "Ctrl+X", "Ctrl+Shift+←/→", "["] {
+            assert!(keys.iter().any(|k| k.contains(shown)), "{shown}");
+        }
+    }
+
+    #[test]
+    fn doctor_lists_broken_references() {
+        let cfg = AppConfig {
+            menu: vec![MenuItem {
+                id: "split".into(),
+                title: "Split".into(),
+                widget: Some("panel".into()),
+                pane_b_yaml: Some("/chi-tui-no-such-dir/pane_b.yaml".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let md = builtin_markdown("doctor", &cfg, &[]).unwrap();
+        assert!(
+            md.contains("| Split | `split` | `pane_b_yaml` | `/chi-tui-no-such-dir/pane_b.yaml` |")
+        );
+        let md = builtin_markdown("doctor", &AppConfig::default(), &[]).unwrap();
+        assert!(md.contains("Every file referenced"));
+    }
+}
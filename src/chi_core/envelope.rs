@@ -0,0 +1,237 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+// The JSON a backing CLI prints when run with CHI_TUI_JSON=1 (contract version `VERSION`):
+//
+//   {"ok": true, "data": {"items": [...], "pagination": {...}}}
+//   {"ok": false, "error": "text" | {"message": "...", "details": {...}}}
+//   {"type": "progress", "data": {"message": "...", "stage": "...", "percent": 40}}
+//   {"ok": true, "content_type": "diff", "data": "--- a\n+++ b\n..."}
+//
+// A streaming command prints `type: progress` lines before its result line. `pagination`
+// may also sit next to `data`, and `content_type` picks the pane that shows the result
+// (widgets::renderers). An object is an envelope when `ok` is a bool or `type` is
+// "progress" or "error"; a key of the wrong shape in it is reported with its name instead of
+// being read as absent. Any other output is raw JSON, shown as is (its keys are read where
+// they fit, so `{"data": {"items": [...]}}` still lists rows).
+
+pub const VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Envelope {
+    pub ok: Option<bool>,
+    // `type`: "progress" for stream events, "error" for failures without `ok`
+    pub kind: Option<String>,
+    pub data: JsonValue,
+    pub error: Option<ErrorBody>,
+    pub message: Option<String>,
+    pub pagination: Option<Pagination>,
+    // Renderer hint for the result: "markdown", "table", "diff" or "ansi"
+    pub content_type: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ErrorBody {
+    Text(String),
+    Detail {
+        #[serde(default)]
+        message: Option<String>,
+        #[serde(default)]
+        details: Option<JsonValue>,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Pagination {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_page: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_page_cmd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_cmd: Option<String>,
+}
+
+// `data` of a `type: progress` line
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct Progress {
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub stage: Option<String>,
+    #[serde(default)]
+    pub percent: Option<f64>,
+}
+
+// Error for output that is not JSON at all, quoting the start of it
+pub fn not_json(cmdline: &str, text: &str, err: &serde_json::Error) -> String {
+    let head: String = text.trim().chars().take(80).collect();
+    let more = if text.trim().chars().count() > 80 {
+        "…"
+    } else {
+        ""
+    };
+    format!("{cmdline}: output is not JSON ({err}): {head:?}{more}")
+}
+
+// A key of `o` as `T`; null counts as absent, and so does a wrong shape unless `strict`
+fn field<T: DeserializeOwned>(
+    o: &Map<String, JsonValue>,
+    key: &str,
+    strict: bool,
+) -> Result<Option<T>, String> {
+    match o.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(v) => match T::deserialize(v) {
+            Ok(t) => Ok(Some(t)),
+            Err(e) if strict => Err(format!("malformed envelope: `{key}`: {e}")),
+            Err(_) => Ok(None),
+        },
+    }
+}
+
+impl Envelope {
+    // Anything that is not an object is raw data
+    pub fn parse(v: &JsonValue) -> Result<Self, String> {
+        let Some(o) = v.as_object() else {
+            return Ok(Self {
+                data: v.clone(),
+                ..Default::default()
+            });
+        };
+        let strict = o.get("ok").is_some_and(JsonValue::is_boolean)
+            || matches!(
+                o.get("type").and_then(JsonValue::as_str),
+                Some("progress" | "error")
+            );
+        let data = o.get("data").cloned().unwrap_or(JsonValue::Null);
+        let pagination = match field(o, "pagination", strict)? {
+            Some(p) => Some(p),
+            None => match data.as_object() {
+                Some(d) => {
+                    field(d, "pagination", strict).map_err(|e| e.replacen('`', "`data.", 1))?
+                }
+                None => None,
+            },
+        };
+        Ok(Self {
+            ok: field(o, "ok", strict)?,
+            kind: field(o, "type", strict)?,
+            error: field(o, "error", strict)?,
+            message: field(o, "message", strict)?,
+            pagination,
+            content_type: field(o, "content_type", strict)?,
+            data,
+        })
+    }
+
+    // `ok: false`, or `type: error` without `ok`
+    pub fn failed(&self) -> bool {
+        self.ok
+            .map(|ok| !ok)
+            .unwrap_or(self.kind.as_deref() == Some("error"))
+    }
+
+    // `data.message`, `error.message`, `error` or `message`
+    pub fn message(&self) -> Option<&str> {
+        let error = match &self.error {
+            Some(ErrorBody::Text(s)) => Some(s.as_str()),
+            Some(ErrorBody::Detail { message, .. }) => message.as_deref(),
+            None => None,
+        };
+        self.data
+            .get("message")
+            .and_then(JsonValue::as_str)
+            .or(error)
+            .or(self.message.as_deref())
+    }
+
+    // Validation details of a failure (`data.details` or `error.details`)
+    pub fn details(&self) -> Option<&JsonValue> {
+        self.data.get("details").or(match &self.error {
+            Some(ErrorBody::Detail { details, .. }) => details.as_ref(),
+            _ => None,
+        })
+    }
+
+    // `data.items`, when it is a list
+    pub fn items(&self) -> Option<&Vec<JsonValue>> {
+        self.data.get("items")?.as_array()
+    }
+
+    // The stream event of a `type: progress` line
+    pub fn progress(&self) -> Option<Progress> {
+        if self.kind.as_deref() != Some("progress") {
+            return None;
+        }
+        Some(Progress::deserialize(&self.data).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn envelopes_are_read_and_malformed_keys_named() {
+        let env = Envelope::parse(&json!({
+            "ok": true,
+            "data": {"items": [1, 2], "pagination": {"current_page": 2, "total_pages": 5}}
+        }))
+        .unwrap();
+        assert!(!env.failed());
+        assert_eq!(env.items().map(Vec::len), Some(2));
+        assert_eq!(env.pagination.and_then(|p| p.total_pages), Some(5));
+
+        let env = Envelope::parse(&json!({"ok": false, "error": {"message": "denied"}})).unwrap();
+        assert!(env.failed());
+        assert_eq!(env.message(), Some("denied"));
+        let env = Envelope::parse(&json!({"type": "error", "error": "boom"})).unwrap();
+        assert_eq!((env.failed(), env.message()), (true, Some("boom")));
+
+        let env = Envelope::parse(&json!({"type": "progress", "data": {"percent": 40}})).unwrap();
+        assert_eq!(env.progress().and_then(|p| p.percent), Some(40.0));
+
+        // Raw JSON stays data
+        let env = Envelope::parse(&json!([1, 2])).unwrap();
+        assert_eq!((&env.data, env.failed()), (&json!([1, 2]), false));
+
+        let err = Envelope::parse(&json!({"ok": true, "error": 3})).unwrap_err();
+        assert!(
+            err.starts_with("malformed envelope: `error`: data did not match"),
+            "{err}"
+        );
+        let err =
+            Envelope::parse(&json!({"ok": true, "data": {"pagination": {"current_page": "2"}}}))
+                .unwrap_err();
+        assert!(
+            err.starts_with("malformed envelope: `data.pagination`"),
+            "{err}"
+        );
+        let err = Envelope::parse(&json!({"type": "progress", "message": 1})).unwrap_err();
+        assert!(err.starts_with("malformed envelope: `message`"), "{err}");
+
+        // Objects that merely use the key names are raw JSON, not failures
+        for raw in [
+            json!({"ok": 1}),
+            json!({"error": false}),
+            json!({"type": ["string", "null"]}),
+            json!({"ok": "yes", "data": {"pagination": {"current_page": "2"}}}),
+        ] {
+            let env = Envelope::parse(&raw).unwrap();
+            assert!(!env.failed(), "{raw}");
+        }
+        let env = Envelope::parse(&json!({"data": {"items": [1]}})).unwrap();
+        assert_eq!(env.items().map(Vec::len), Some(1));
+
+        let e = serde_json::from_str::<JsonValue>("Traceback (most recent").unwrap_err();
+        assert!(not_json("app list", "Traceback (most recent", &e)
+            .starts_with("app list: output is not JSON (expected value"));
+    }
+}
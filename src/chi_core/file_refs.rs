@@ -0,0 +1,118 @@
+use crate::model::MenuItem;
+use std::path::PathBuf;
+
+// Files a screen's menu items point at (`path:` of markdown / runbook pages, `pane_a_yaml:`,
+// `pane_b_yaml:`), checked when the config loads so a typo shows up as a badge on the item
+// and on the `builtin: doctor` page instead of as an error once the item is entered. A
+// log_viewer `path:` is left out: the log file may not have been written yet.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrokenRef {
+    pub id: String,
+    pub title: String,
+    pub field: &'static str,
+    pub path: String,
+}
+
+// Absolute, or relative to CHI_TUI_CONFIG_DIR (the working directory without it)
+pub fn resolve(path: &str) -> PathBuf {
+    let pb = PathBuf::from(path);
+    if pb.is_absolute() {
+        pb
+    } else if let Ok(dir) = std::env::var("CHI_TUI_CONFIG_DIR") {
+        PathBuf::from(dir).join(path)
+    } else {
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(path)
+    }
+}
+
+// (field, path) of every file the item reads when entered
+fn references(mi: &MenuItem) -> Vec<(&'static str, &str)> {
+    let mut refs = Vec::new();
+    let page = matches!(mi.widget.as_deref(), Some("markdown" | "runbook"));
+    let inline = mi.builtin.is_some() || mi.content.as_deref().is_some_and(|c| !c.is_empty());
+    if page && !inline {
+        refs.extend(mi.path.as_deref().map(|p| ("path", p)));
+    }
+    refs.extend(mi.pane_a_yaml.as_deref().map(|p| ("pane_a_yaml", p)));
+    refs.extend(mi.pane_b_yaml.as_deref().map(|p| ("pane_b_yaml", p)));
+    refs.retain(|(_, p)| !p.is_empty());
+    refs
+}
+
+// References of `menu` whose file does not exist, in menu order
+pub fn broken(menu: &[MenuItem]) -> Vec<BrokenRef> {
+    menu.iter()
+        .flat_map(|mi| {
+            references(mi)
+                .into_iter()
+                .filter(|(_, p)| !resolve(p).is_file())
+                .map(|(field, p)| BrokenRef {
+                    id: mi.id.clone(),
+                    title: mi.title.clone(),
+                    field,
+                    path: p.to_string(),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_pages_and_pane_specs_are_reported() {
+        let dir = std::env::temp_dir().join(format!("chi-tui-refs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("intro.md"), "# Intro").unwrap();
+        let abs = |name: &str| dir.join(name).display().to_string();
+        let menu = vec![
+            MenuItem {
+                id: "intro".into(),
+                title: "Intro".into(),
+                widget: Some("markdown".into()),
+                path: Some(abs("intro.md")),
+                ..Default::default()
+            },
+            MenuItem {
+                id: "guide".into(),
+                title: "Guide".into(),
+                widget: Some("runbook".into()),
+                path: Some(abs("guide.md")),
+                pane_b_yaml: Some(abs("intro.md")),
+                ..Default::default()
+            },
+            MenuItem {
+                id: "split".into(),
+                title: "Split".into(),
+                widget: Some("panel".into()),
+                pane_a_yaml: Some(abs("pane_a.yaml")),
+                ..Default::default()
+            },
+            // Inline content wins over `path`; a log file may appear later
+            MenuItem {
+                id: "notes".into(),
+                title: "Notes".into(),
+                widget: Some("markdown".into()),
+                path: Some(abs("typo.md")),
+                content: Some("# Notes".into()),
+                ..Default::default()
+            },
+            MenuItem {
+                id: "logs".into(),
+                title: "Logs".into(),
+                widget: Some("log_viewer".into()),
+                path: Some(abs("app.log")),
+                ..Default::default()
+            },
+        ];
+        let refs = broken(&menu);
+        let found: Vec<(&str, &str)> = refs.iter().map(|b| (b.id.as_str(), b.field)).collect();
+        assert_eq!(found, vec![("guide", "path"), ("split", "pane_a_yaml")]);
+        assert_eq!(refs[0].path, abs("guide.md"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
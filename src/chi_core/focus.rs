@@ -28,4 +28,54 @@ impl FocusState {
             PanelPane::B => PanelPane::A,
         };
     }
+    // Apply the focus policy after `pane` (and optionally nested subpane) received content
+    pub fn apply_load(&mut self, policy: FocusOnLoad, pane: PanelPane, nested: Option<PanelPane>) {
+        match policy {
+            FocusOnLoad::PaneB => {
+                self.panel_focus = PanelPane::B;
+                if let (PanelPane::B, Some(sub)) = (pane, nested) {
+                    self.nested_focus = sub;
+                }
+            }
+            FocusOnLoad::PaneA => self.panel_focus = PanelPane::A,
+            FocusOnLoad::Keep => {}
+        }
+    }
+}
+
+// Focus policy applied uniformly when async pane loads complete (config: focus_on_load)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusOnLoad {
+    PaneB,
+    Keep,
+    PaneA,
+}
+
+impl FocusOnLoad {
+    pub fn parse(s: Option<&str>) -> Self {
+        match s.unwrap_or("keep").to_ascii_lowercase().as_str() {
+            "pane_b" | "b" => FocusOnLoad::PaneB,
+            "pane_a" | "a" => FocusOnLoad::PaneA,
+            _ => FocusOnLoad::Keep,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_load_follows_policy() {
+        let mut f = FocusState::new(PanelPane::A, PanelPane::A);
+        f.apply_load(FocusOnLoad::Keep, PanelPane::B, None);
+        assert!(matches!(f.panel_focus, PanelPane::A));
+        f.apply_load(FocusOnLoad::PaneB, PanelPane::B, Some(PanelPane::B));
+        assert!(matches!(f.panel_focus, PanelPane::B));
+        assert!(matches!(f.nested_focus, PanelPane::B));
+        f.apply_load(FocusOnLoad::PaneA, PanelPane::B, None);
+        assert!(matches!(f.panel_focus, PanelPane::A));
+        assert_eq!(FocusOnLoad::parse(Some("PANE_B")), FocusOnLoad::PaneB);
+        assert_eq!(FocusOnLoad::parse(None), FocusOnLoad::Keep);
+    }
 }
@@ -0,0 +1,107 @@
+use regex::Regex;
+use serde_json::{json, Value as JsonValue};
+use std::path::{Path, PathBuf};
+
+// `children_from_glob:` on a menu item: one markdown child per file matching the pattern,
+// titled by the file's first heading. Wildcards (`*`, `?`) are allowed in the file name
+// part only; relative patterns resolve against CHI_TUI_CONFIG_DIR like markdown paths.
+pub fn glob_children(pattern: &str) -> Result<Vec<JsonValue>, String> {
+    let (dir, name) = match pattern.rsplit_once('/') {
+        Some((dir, name)) => (dir, name),
+        None => (".", pattern),
+    };
+    if dir.contains(['*', '?']) {
+        return Err(format!(
+            "children_from_glob `{pattern}`: wildcards are only supported in the file name"
+        ));
+    }
+    let re = name_regex(name);
+    let dir = resolve_dir(dir);
+    let entries = std::fs::read_dir(&dir).map_err(|e| format!("reading {dir:?}: {e}"))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| re.is_match(n))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths.iter().map(|p| child(p)).collect())
+}
+
+fn name_regex(name: &str) -> Regex {
+    let mut re = String::from("^");
+    for c in name.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).expect("escaped glob is a valid regex")
+}
+
+fn resolve_dir(dir: &str) -> PathBuf {
+    let pb = PathBuf::from(dir);
+    if pb.is_absolute() {
+        pb
+    } else if let Ok(base) = std::env::var("CHI_TUI_CONFIG_DIR") {
+        // Config dir already points to .tui, so strip .tui/ from the pattern if present
+        let clean = dir.strip_prefix(".tui/").unwrap_or(dir);
+        PathBuf::from(base).join(clean)
+    } else {
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(dir)
+    }
+}
+
+fn child(path: &Path) -> JsonValue {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+        .to_string();
+    let heading = std::fs::read_to_string(path).ok().and_then(|text| {
+        text.lines()
+            .find(|l| l.starts_with('#'))
+            .map(|l| l.trim_start_matches('#').trim().to_string())
+            .filter(|t| !t.is_empty())
+    });
+    json!({
+        "id": stem,
+        "title": heading.unwrap_or_else(|| stem.clone()),
+        "widget": "markdown",
+        "path": path.to_string_lossy(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_children_from_matching_files() {
+        let dir = std::env::temp_dir().join(format!("chi-glob-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("b-restart.md"), "intro\n## Restart the API\n").unwrap();
+        std::fs::write(dir.join("a-deploy.md"), "# Deploy\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "# Not a runbook\n").unwrap();
+        std::fs::write(dir.join("empty.md"), "").unwrap();
+
+        let kids = glob_children(&format!("{}/*.md", dir.display())).unwrap();
+        let titles: Vec<&str> = kids.iter().map(|k| k["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, ["Deploy", "Restart the API", "empty"]);
+        assert_eq!(kids[0]["id"], "a-deploy");
+        assert_eq!(kids[0]["widget"], "markdown");
+        assert!(kids[0]["path"].as_str().unwrap().ends_with("a-deploy.md"));
+
+        let one = glob_children(&format!("{}/?-deploy.md", dir.display())).unwrap();
+        assert_eq!(one.len(), 1);
+        assert!(glob_children("runbooks/*/x.md").is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,100 @@
+use serde::de::Error as _;
+use serde_yaml::Value as YamlValue;
+use std::path::Path;
+
+// Per-user overrides: an optional `chi-local.yaml` next to the entry config (kept out of git)
+// is merged over it before anything else reads it. Mappings merge key by key, lists of items
+// with an `id` merge by id (new ids are appended), and any other value replaces the shared
+// one, so a developer can add menu items, change a command or set their own theme and
+// keybindings without touching the shared files.
+pub const LOCAL_FILE: &str = "chi-local.yaml";
+
+// Merge the local file beside `entry` (if any) into the parsed entry config
+pub fn apply(doc: &mut YamlValue, entry: &Path) -> Result<(), serde_yaml::Error> {
+    let path = entry
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(LOCAL_FILE);
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    let mut local: YamlValue = serde_yaml::from_str(&text)
+        .map_err(|e| serde_yaml::Error::custom(format!("{LOCAL_FILE}: {e}")))?;
+    crate::chi_core::migrate::report(&path, crate::chi_core::migrate::upgrade(&mut local));
+    // An empty file is no overrides
+    if !local.is_null() {
+        merge(doc, local);
+    }
+    Ok(())
+}
+
+fn id_of(v: &YamlValue) -> Option<&YamlValue> {
+    v.as_mapping().and_then(|m| m.get("id"))
+}
+
+// Deep-merge `over` into `base`
+pub fn merge(base: &mut YamlValue, over: YamlValue) {
+    match (base, over) {
+        (YamlValue::Mapping(b), YamlValue::Mapping(o)) => {
+            for (k, v) in o {
+                match b.get_mut(&k) {
+                    Some(x) => merge(x, v),
+                    None => {
+                        b.insert(k, v);
+                    }
+                }
+            }
+        }
+        // Items with ids: same id merges, a new one is appended
+        (YamlValue::Sequence(b), YamlValue::Sequence(o))
+            if !o.is_empty() && o.iter().all(|v| id_of(v).is_some()) =>
+        {
+            for v in o {
+                match b.iter_mut().find(|x| id_of(x) == id_of(&v)) {
+                    Some(x) => merge(x, v),
+                    None => b.push(v),
+                }
+            }
+        }
+        (b, o) => *b = o,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_overrides_merge_by_key_and_menu_id() {
+        let mut doc: YamlValue = serde_yaml::from_str(
+            "title: Ops\ntheme: {name: synthwave_dark, accent: pink}\nmenu:\n  - id: pods\n    title: Pods\n    command: kubectl get pods\n  - id: logs\n    title: Logs\n",
+        )
+        .unwrap();
+        let local: YamlValue = serde_yaml::from_str(
+            "theme: {name: light}\nkeybindings: {quit: Q}\nmenu:\n  - id: pods\n    command: kubectl -n dev get pods\n  - id: scratch\n    title: My scratch\n",
+        )
+        .unwrap();
+        merge(&mut doc, local);
+        assert_eq!(doc["title"], "Ops");
+        assert_eq!(doc["theme"]["name"], "light");
+        assert_eq!(doc["theme"]["accent"], "pink");
+        assert_eq!(doc["keybindings"]["quit"], "Q");
+        let ids: Vec<_> = doc["menu"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|m| m["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["pods", "logs", "scratch"]);
+        assert_eq!(doc["menu"][0]["title"], "Pods");
+        assert_eq!(doc["menu"][0]["command"], "kubectl -n dev get pods");
+
+        // Lists without ids are replaced
+        let mut doc: YamlValue = serde_yaml::from_str("tags: [a, b]\n").unwrap();
+        merge(&mut doc, serde_yaml::from_str("tags: [c]\n").unwrap());
+        assert_eq!(
+            doc["tags"],
+            serde_yaml::from_str::<YamlValue>("[c]").unwrap()
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use serde_yaml::{Mapping, Value as YamlValue};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// Spec versions: every config, screen, pane or form file may declare `spec_version:`; a file
+// without one is version 1. Keys deprecated since then are rewritten when the file is parsed
+// and reported with the file and key path (debug log, one toast), and `chi-tui migrate`
+// rewrites the files on disk to the current version.
+pub const SPEC_VERSION: u64 = 2;
+
+// Widget type names replaced in version 2
+const TYPE_ALIASES: [(&str, &str); 2] = [
+    ("json-viewer", "json_viewer"),
+    ("markdown-viewer", "markdown"),
+];
+
+struct Warnings {
+    // Not yet shown
+    pending: Vec<String>,
+    // Every warning reported this session; reloading a file does not repeat them
+    seen: BTreeSet<String>,
+}
+
+static WARNINGS: Mutex<Warnings> = Mutex::new(Warnings {
+    pending: Vec::new(),
+    seen: BTreeSet::new(),
+});
+
+// Rewrite deprecated keys of a parsed document in place: one "<key path>: ..." note per change
+pub fn upgrade(doc: &mut YamlValue) -> Vec<String> {
+    let mut notes = Vec::new();
+    let declared = doc.get("spec_version").and_then(YamlValue::as_u64);
+    if let Some(v) = declared.filter(|v| *v > SPEC_VERSION) {
+        notes.push(format!(
+            "spec_version: {v} is newer than this chi-tui supports ({SPEC_VERSION})"
+        ));
+    }
+    walk(doc, "", &mut notes);
+    notes
+}
+
+fn walk(v: &mut YamlValue, path: &str, notes: &mut Vec<String>) {
+    match v {
+        YamlValue::Mapping(m) => {
+            upgrade_mapping(m, path, notes);
+            for (k, x) in m.iter_mut() {
+                let key = k.as_str().unwrap_or("?");
+                let child = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                walk(x, &child, notes);
+            }
+        }
+        YamlValue::Sequence(seq) => {
+            for (i, x) in seq.iter_mut().enumerate() {
+                walk(x, &format!("{path}[{i}]"), notes);
+            }
+        }
+        YamlValue::Tagged(t) => walk(&mut t.value, path, notes),
+        _ => {}
+    }
+}
+
+fn upgrade_mapping(m: &mut Mapping, path: &str, notes: &mut Vec<String>) {
+    let at = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    // `type: json-viewer` / `widget: markdown-viewer`
+    for key in ["type", "widget"] {
+        let Some(old) = m.get(key).and_then(YamlValue::as_str) else {
+            continue;
+        };
+        let lower = old.to_ascii_lowercase();
+        if let Some((_, new)) = TYPE_ALIASES.iter().find(|(alias, _)| *alias == lower) {
+            notes.push(format!("{}: '{old}' is deprecated, use '{new}'", at(key)));
+            m.insert(key.into(), (*new).into());
+        }
+    }
+    // Form `submit_cmd: X` -> `submit: { command: X }` (it took priority over submit.command)
+    let submit_ok = matches!(m.get("submit"), None | Some(YamlValue::Mapping(_)));
+    if submit_ok && m.get("submit_cmd").is_some_and(YamlValue::is_string) {
+        let cmd = m.remove("submit_cmd").unwrap_or_default();
+        let submit = m
+            .entry("submit".into())
+            .or_insert_with(|| YamlValue::Mapping(Mapping::new()));
+        if let YamlValue::Mapping(s) = submit {
+            s.insert("command".into(), cmd);
+        }
+        notes.push(format!(
+            "{} is deprecated, use {}",
+            at("submit_cmd"),
+            at("submit.command")
+        ));
+    }
+}
+
+// Queue the notes of one file for the UI, named relative to the config directory
+pub fn report(source: &Path, notes: Vec<String>) {
+    if notes.is_empty() {
+        return;
+    }
+    let name = display_path(source);
+    let Ok(mut g) = WARNINGS.lock() else {
+        return;
+    };
+    for note in notes {
+        let line = format!("{name}: {note}");
+        if g.seen.insert(line.clone()) {
+            g.pending.push(line);
+        }
+    }
+}
+
+// Warnings reported since the last call
+pub fn take_warnings() -> Vec<String> {
+    WARNINGS
+        .lock()
+        .map(|mut g| std::mem::take(&mut g.pending))
+        .unwrap_or_default()
+}
+
+fn display_path(p: &Path) -> String {
+    let rel = std::env::var("CHI_TUI_CONFIG_DIR")
+        .ok()
+        .and_then(|dir| p.strip_prefix(dir).ok().map(Path::to_path_buf));
+    rel.unwrap_or_else(|| p.to_path_buf()).display().to_string()
+}
+
+// A file rewritten by `chi-tui migrate`
+pub struct Migrated {
+    pub path: PathBuf,
+    pub notes: Vec<String>,
+    pub text: String,
+}
+
+// Upgrade one YAML file to the current spec: None when it is already current (or not a config)
+pub fn migrate_text(text: &str) -> Option<(Vec<String>, String)> {
+    let mut doc: YamlValue = serde_yaml::from_str(text).ok()?;
+    let notes = upgrade(&mut doc);
+    let YamlValue::Mapping(m) = &doc else {
+        return None;
+    };
+    let declared = m.get("spec_version").and_then(YamlValue::as_u64);
+    if notes.is_empty() || declared.is_some_and(|v| v > SPEC_VERSION) {
+        return None;
+    }
+    // `spec_version` first, then the keys in their original order
+    let mut out = Mapping::new();
+    out.insert("spec_version".into(), SPEC_VERSION.into());
+    for (k, v) in m {
+        if k.as_str() != Some("spec_version") {
+            out.insert(k.clone(), v.clone());
+        }
+    }
+    let text = serde_yaml::to_string(&YamlValue::Mapping(out)).ok()?;
+    Some((notes, text))
+}
+
+// Every `.yaml` / `.yml` file under the config directory that needs migrating
+pub fn scan(dir: &Path) -> Vec<Migrated> {
+    let mut files = Vec::new();
+    collect_yaml(dir, &mut files);
+    files.sort();
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let text = std::fs::read_to_string(&path).ok()?;
+            let (notes, text) = migrate_text(&text)?;
+            Some(Migrated { path, notes, text })
+        })
+        .collect()
+}
+
+fn collect_yaml(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for p in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        if p.is_dir() {
+            collect_yaml(&p, out);
+        } else if p.extension().is_some_and(|e| e == "yaml" || e == "yml") {
+            out.push(p);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deprecated_keys_migrate_with_their_key_paths() {
+        let text = "menu:\n  - id: cfg\n    widget: json-viewer\n  - id: hello\n    pane_b:\n      type: form\n      submit_cmd: app hello\n";
+        let mut doc: YamlValue = serde_yaml::from_str(text).unwrap();
+        let notes = upgrade(&mut doc);
+        assert_eq!(
+            notes,
+            [
+                "menu[0].widget: 'json-viewer' is deprecated, use 'json_viewer'",
+                "menu[1].pane_b.submit_cmd is deprecated, use menu[1].pane_b.submit.command",
+            ]
+        );
+        assert_eq!(doc["menu"][0]["widget"], "json_viewer");
+        assert_eq!(doc["menu"][1]["pane_b"]["submit"]["command"], "app hello");
+        assert!(doc["menu"][1]["pane_b"].get("submit_cmd").is_none());
+
+        // Rewritten with the current version first; a migrated file has nothing left to do
+        let (_, out) = migrate_text(text).unwrap();
+        assert!(out.starts_with("spec_version: 2\nmenu:"), "{out}");
+        assert!(migrate_text(&out).is_none());
+        // A string `submit:` is left alone
+        assert!(migrate_text("type: form\nsubmit: app x\nsubmit_cmd: app y\n").is_none());
+
+        let mut newer: YamlValue =
+            serde_yaml::from_str("spec_version: 9\ntype: markdown\n").unwrap();
+        assert_eq!(
+            upgrade(&mut newer),
+            ["spec_version: 9 is newer than this chi-tui supports (2)"]
+        );
+        assert!(migrate_text("spec_version: 9\ntype: json-viewer\n").is_none());
+    }
+}
@@ -1,2 +1,13 @@
+pub mod argv;
+pub mod builtins;
+pub mod envelope;
+pub mod file_refs;
 pub mod focus;
+pub mod glob_children;
+pub mod local;
+pub mod migrate;
+pub mod nesting;
 pub mod registry;
+pub mod slots;
+pub mod vars;
+pub mod watches;
@@ -0,0 +1,137 @@
+use crate::app::Effect;
+use crate::ui::PanelPane;
+use std::path::PathBuf;
+
+// Guard rails for panel specs that load other specs: a pane YAML (or a command's output)
+// naming a `json_viewer`/`menu` spec makes the pane load that one next, which may name
+// another one, and so on. Each pane keeps the chain of sources it followed since the last
+// load started by the user; a source already in the chain is a cycle, and a chain longer
+// than the screen's `max_panel_depth` stops with an error in the pane instead of loading on.
+
+pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+#[derive(Default)]
+pub struct PanelNesting {
+    a: Trail,
+    b: Trail,
+}
+
+#[derive(Default)]
+struct Trail {
+    // (key compared for cycles, label shown in errors), oldest first
+    sources: Vec<(String, String)>,
+    // The next load of this pane was asked for by a loaded spec, not by the user
+    follow: bool,
+}
+
+impl PanelNesting {
+    fn trail(&mut self, pane: PanelPane) -> &mut Trail {
+        match pane {
+            PanelPane::A => &mut self.a,
+            PanelPane::B => &mut self.b,
+        }
+    }
+
+    // A load of `pane` starts; unless it follows a loaded spec, it begins a new chain
+    pub fn started(&mut self, pane: PanelPane, effect: &Effect) {
+        let trail = self.trail(pane);
+        if std::mem::take(&mut trail.follow) {
+            return;
+        }
+        trail.sources = source(effect).into_iter().collect();
+    }
+
+    // A spec loaded into `pane` asks for `effect`: allowed unless it closes a cycle or goes
+    // deeper than `limit` loads
+    pub fn follow(&mut self, pane: PanelPane, effect: &Effect, limit: usize) -> Result<(), String> {
+        let Some((key, label)) = source(effect) else {
+            return Ok(());
+        };
+        let trail = self.trail(pane);
+        let chain = |last: &str| {
+            let mut labels: Vec<&str> = trail.sources.iter().map(|(_, l)| l.as_str()).collect();
+            labels.push(last);
+            labels.join(" → ")
+        };
+        if trail.sources.iter().any(|(k, _)| *k == key) {
+            return Err(format!("Nested panel cycle: {}", chain(&label)));
+        }
+        if trail.sources.len() >= limit {
+            return Err(format!(
+                "Nested panel limit reached ({limit} levels): {} — raise `max_panel_depth` on this screen to allow deeper nesting",
+                chain(&label)
+            ));
+        }
+        trail.sources.push((key, label));
+        trail.follow = true;
+        Ok(())
+    }
+}
+
+// Key and label of the pane load an effect starts. YAML paths are compared once resolved,
+// so `a.yaml` and `./a.yaml` are the same source.
+fn source(effect: &Effect) -> Option<(String, String)> {
+    match effect {
+        Effect::LoadPanelYaml { path, .. } => {
+            let pb = PathBuf::from(path);
+            let full = if pb.is_absolute() {
+                pb
+            } else if let Ok(dir) = std::env::var("CHI_TUI_CONFIG_DIR") {
+                PathBuf::from(dir).join(path)
+            } else {
+                std::env::current_dir()
+                    .unwrap_or_else(|_| PathBuf::from("."))
+                    .join(path)
+            };
+            let full = std::fs::canonicalize(&full).unwrap_or(full);
+            Some((format!("yaml:{}", full.display()), path.clone()))
+        }
+        Effect::LoadPanelCmd { cmdline, .. } => Some((
+            format!("cmd:{}", cmdline.trim()),
+            format!("$ {}", cmdline.trim()),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(path: &str) -> Effect {
+        Effect::LoadPanelYaml {
+            pane: PanelPane::B,
+            path: path.into(),
+        }
+    }
+
+    #[test]
+    fn stops_at_cycles_and_at_the_depth_limit() {
+        let mut n = PanelNesting::default();
+        n.started(PanelPane::B, &yaml("/tmp/a.yaml"));
+        assert!(n.follow(PanelPane::B, &yaml("/tmp/b.yaml"), 8).is_ok());
+        n.started(PanelPane::B, &yaml("/tmp/b.yaml"));
+        let err = n.follow(PanelPane::B, &yaml("/tmp/a.yaml"), 8).unwrap_err();
+        assert_eq!(
+            err,
+            "Nested panel cycle: /tmp/a.yaml → /tmp/b.yaml → /tmp/a.yaml"
+        );
+
+        // A load the user starts begins a new chain; Pane A keeps its own
+        n.started(PanelPane::B, &yaml("/tmp/a.yaml"));
+        assert_eq!(n.b.sources.len(), 1);
+        assert!(n.a.sources.is_empty());
+        for i in 0..2 {
+            let next = Effect::LoadPanelCmd {
+                pane: PanelPane::B,
+                cmdline: format!("app spec {i}"),
+            };
+            assert!(n.follow(PanelPane::B, &next, 3).is_ok());
+            n.started(PanelPane::B, &next);
+        }
+        let err = n.follow(PanelPane::B, &yaml("/tmp/c.yaml"), 3).unwrap_err();
+        assert!(
+            err.starts_with("Nested panel limit reached (3 levels): /tmp/a.yaml → $ app spec 0")
+        );
+    }
+}
@@ -72,9 +72,8 @@ pub fn resolve_widget(v: &JsonValue) -> Option<Box<dyn crate::widgets::Widget>>
                     match crate::services::cli_runner::run_cmdline_to_json(cmd) {
                         Ok(j) => {
                             target.last_error = None;
-                            target.last_json_pretty = Some(
-                                serde_json::to_string_pretty(&j).unwrap_or_else(|_| j.to_string()),
-                            );
+                            target.last_json_pretty =
+                                Some(crate::widgets::large_json::stored_pretty(&j));
                         }
                         Err(e) => {
                             target.last_error = Some(format!("{e}"));
@@ -95,13 +94,11 @@ pub fn resolve_widget(v: &JsonValue) -> Option<Box<dyn crate::widgets::Widget>>
                         }
                     };
                     if let Ok(s) = std::fs::read_to_string(&full_path) {
-                        match serde_yaml::from_str::<serde_json::Value>(&s) {
+                        match crate::chi_core::vars::parse::<serde_json::Value>(&s, &full_path) {
                             Ok(j) => {
                                 target.last_error = None;
-                                target.last_json_pretty = Some(
-                                    serde_json::to_string_pretty(&j)
-                                        .unwrap_or_else(|_| j.to_string()),
-                                );
+                                target.last_json_pretty =
+                                    Some(crate::widgets::large_json::stored_pretty(&j));
                             }
                             Err(e) => {
                                 target.last_error = Some(format!("{e}"));
@@ -176,7 +173,9 @@ pub fn resolve_widget_for_pane(
                         .join(path)
                 };
                 if let Ok(s) = std::fs::read_to_string(&full_path) {
-                    if let Ok(cfg_v) = serde_yaml::from_str::<serde_json::Value>(&s) {
+                    if let Ok(cfg_v) =
+                        crate::chi_core::vars::parse::<serde_json::Value>(&s, &full_path)
+                    {
                         if let Ok(cfg) =
                             serde_json::from_value::<crate::model::AppConfig>(cfg_v.clone())
                         {
@@ -246,6 +245,145 @@ pub fn resolve_widget_for_pane(
             }
             None
         }
+        "runbook" => {
+            let title = v
+                .get("title")
+                .and_then(|s| s.as_str())
+                .unwrap_or("Runbook")
+                .to_string();
+            if let Some(path) = v.get("path").and_then(|s| s.as_str()) {
+                let pb = std::path::PathBuf::from(path);
+                let full = if pb.is_absolute() {
+                    pb
+                } else if let Ok(dir) = std::env::var("CHI_TUI_CONFIG_DIR") {
+                    std::path::PathBuf::from(dir).join(path)
+                } else {
+                    std::env::current_dir()
+                        .unwrap_or_else(|_| std::path::PathBuf::from("."))
+                        .join(path)
+                };
+                return Some(Box::new(crate::widgets::runbook::RunbookWidget::from_path(
+                    title, &full,
+                )));
+            }
+            v.get("text").and_then(|s| s.as_str()).map(|txt| {
+                Box::new(crate::widgets::runbook::RunbookWidget::from_text(
+                    title, txt,
+                )) as Box<dyn crate::widgets::Widget>
+            })
+        }
+        "tree" => {
+            let title = v
+                .get("title")
+                .and_then(|s| s.as_str())
+                .unwrap_or("Tree")
+                .to_string();
+            let str_of = |key: &str| v.get(key).and_then(|s| s.as_str()).map(|s| s.to_string());
+            let publish = v.get("publish").and_then(|p| {
+                Some(crate::model::PublishSpec {
+                    slot: p.get("slot")?.as_str()?.to_string(),
+                    field: p
+                        .get("field")
+                        .and_then(|s| s.as_str())
+                        .map(|s| s.to_string()),
+                })
+            });
+            let w = crate::widgets::tree::TreeWidget::new(
+                title,
+                str_of("children_key"),
+                str_of("children_cmd"),
+                publish,
+            );
+            if let Some(cmd) = str_of("cmd") {
+                return Some(Box::new(w.with_command(cmd)));
+            }
+            v.get("data")
+                .map(|data| Box::new(w.with_value(data)) as Box<dyn crate::widgets::Widget>)
+        }
+        "processes" => {
+            let title = v
+                .get("title")
+                .and_then(|s| s.as_str())
+                .unwrap_or("Processes");
+            Some(Box::new(crate::widgets::processes::ProcessesWidget::new(
+                title,
+                v.get("pattern").and_then(|s| s.as_str()),
+                v.get("refresh_secs").and_then(|n| n.as_u64()),
+            )))
+        }
+        "ports" => {
+            let title = v.get("title").and_then(|s| s.as_str()).unwrap_or("Ports");
+            let targets: Vec<String> = v
+                .get("targets")
+                .and_then(|a| a.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|x| x.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(Box::new(crate::widgets::ports::PortsWidget::new(
+                title,
+                &targets,
+                v.get("refresh_secs").and_then(|n| n.as_u64()),
+                v.get("timeout_ms").and_then(|n| n.as_u64()),
+            )))
+        }
+        "http_checks" => {
+            let title = v
+                .get("title")
+                .and_then(|s| s.as_str())
+                .unwrap_or("HTTP checks");
+            let checks: Vec<crate::model::HttpCheckSpec> = v
+                .get("checks")
+                .and_then(|c| serde_json::from_value(c.clone()).ok())
+                .unwrap_or_default();
+            Some(Box::new(
+                crate::widgets::http_checks::HttpChecksWidget::new(
+                    title,
+                    &checks,
+                    v.get("refresh_secs").and_then(|n| n.as_u64()),
+                    v.get("timeout_ms").and_then(|n| n.as_u64()),
+                ),
+            ))
+        }
+        "log_viewer" => {
+            use crate::widgets::log_viewer::{LogSource, LogViewerWidget};
+            let title = v.get("title").and_then(|s| s.as_str()).unwrap_or("Logs");
+            if let Some(cmd) = v.get("cmd").and_then(|s| s.as_str()) {
+                return Some(Box::new(LogViewerWidget::new(
+                    title,
+                    LogSource::Command(cmd.to_string()),
+                )));
+            }
+            let path = v.get("path").and_then(|s| s.as_str())?;
+            let pb = std::path::PathBuf::from(path);
+            let full = if pb.is_absolute() {
+                pb
+            } else if let Ok(dir) = std::env::var("CHI_TUI_CONFIG_DIR") {
+                std::path::PathBuf::from(dir).join(path)
+            } else {
+                std::env::current_dir()
+                    .unwrap_or_else(|_| std::path::PathBuf::from("."))
+                    .join(path)
+            };
+            Some(Box::new(LogViewerWidget::new(title, LogSource::File(full))))
+        }
+        "promql" => {
+            let title = v.get("title").and_then(|s| s.as_str()).unwrap_or("Metrics");
+            let queries: Vec<crate::model::PromQuerySpec> = v
+                .get("queries")
+                .and_then(|q| serde_json::from_value(q.clone()).ok())
+                .unwrap_or_default();
+            Some(Box::new(crate::widgets::promql::PromqlWidget::new(
+                title,
+                v.get("prometheus").and_then(|s| s.as_str()),
+                &queries,
+                Default::default(),
+                v.get("refresh_secs").and_then(|n| n.as_u64()),
+                v.get("timeout_ms").and_then(|n| n.as_u64()),
+            )))
+        }
         "watchdog" => {
             let default_title = match pane {
                 crate::ui::PanelPane::A => "Pane A — Watchdog".to_string(),
@@ -303,8 +441,10 @@ pub fn resolve_widget_for_pane(
                 .get("external_kill_cmd")
                 .and_then(|s| s.as_str())
                 .map(|s| s.to_string());
-            // Allow external-only watchdog when `external_check_cmd` is provided
-            if cmds.is_empty() && external_check_cmd.is_none() {
+            // Allow external-only watchdog when `external_check_cmd` is provided, and
+            // `unit:` / `container:` ones without plain commands
+            let targets = crate::widgets::watchdog::targets::from_value(v);
+            if cmds.is_empty() && external_check_cmd.is_none() && targets.is_empty() {
                 return None;
             }
             let stats = v
@@ -343,6 +483,7 @@ pub fn resolve_widget_for_pane(
                 stats,
                 external_check_cmd,
                 external_kill_cmd,
+                targets,
             };
             Some(Box::new(crate::widgets::watchdog::WatchdogWidget::new(
                 title, cmds, cfg,
@@ -0,0 +1,146 @@
+use crate::ui::PanelPane;
+use std::collections::HashMap;
+
+// Named values published by one pane (`publish:` on an item: the selected child's field)
+// and read by the command templates of other panes as `{slot.<name>}`. Content loaded from
+// such a template is watched and re-run when one of its slots changes.
+#[derive(Default)]
+pub struct Slots {
+    values: HashMap<String, String>,
+    watches: Vec<SlotWatch>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlotTarget {
+    // Result of the Pane B command
+    PaneB,
+    // Subpane of the nested panel shown in Pane B
+    Nested(PanelPane),
+}
+
+struct SlotWatch {
+    target: SlotTarget,
+    template: String,
+    // Command last run for the template
+    cmdline: String,
+}
+
+pub fn uses_slots(template: &str) -> bool {
+    template.contains("{slot.")
+}
+
+// Names of the `{slot.<name>}` placeholders in a template
+fn slot_names(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{slot.") {
+        rest = &rest[start + "{slot.".len()..];
+        if let Some(end) = rest.find('}') {
+            names.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+    names
+}
+
+impl Slots {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(|s| s.as_str())
+    }
+
+    pub fn values(&self) -> &HashMap<String, String> {
+        &self.values
+    }
+
+    // True when the value changed
+    pub fn set(&mut self, name: &str, value: String) -> bool {
+        if self.get(name) == Some(value.as_str()) {
+            return false;
+        }
+        self.values.insert(name.to_string(), value);
+        true
+    }
+
+    // Placeholders replaced by the shell-quoted slot values (unset slots are empty)
+    pub fn fill(&self, template: &str) -> String {
+        let mut cmd = template.to_string();
+        for name in slot_names(template) {
+            let value = self.get(name).unwrap_or("");
+            let quoted = crate::chi_core::argv::quote(value);
+            cmd = cmd.replace(&format!("{{slot.{name}}}"), &quoted);
+        }
+        cmd
+    }
+
+    // Fill a template and re-run it for `target` whenever its slots change
+    pub fn watch(&mut self, target: SlotTarget, template: &str) -> String {
+        let cmdline = self.fill(template);
+        self.watches.retain(|w| w.target != target);
+        self.watches.push(SlotWatch {
+            target,
+            template: template.to_string(),
+            cmdline: cmdline.clone(),
+        });
+        cmdline
+    }
+
+    // New content replaced whatever the watches were showing
+    pub fn clear_watches(&mut self) {
+        self.watches.clear();
+    }
+
+    // Watches that read slot `name`: (target, previous command, command to run now)
+    pub fn rerun(&mut self, name: &str) -> Vec<(SlotTarget, String, String)> {
+        let mut runs = Vec::new();
+        for i in 0..self.watches.len() {
+            if !slot_names(&self.watches[i].template).contains(&name) {
+                continue;
+            }
+            let cmdline = self.fill(&self.watches[i].template);
+            let prev = std::mem::replace(&mut self.watches[i].cmdline, cmdline.clone());
+            if prev != cmdline {
+                runs.push((self.watches[i].target, prev, cmdline));
+            }
+        }
+        runs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watched_templates_rerun_when_their_slot_changes() {
+        let mut slots = Slots::default();
+        assert_eq!(
+            slots.watch(SlotTarget::PaneB, "app pod get {slot.pod}"),
+            "app pod get ''"
+        );
+        slots.watch(
+            SlotTarget::Nested(PanelPane::B),
+            "app events {slot.pod} --ns {slot.ns}",
+        );
+        assert!(slots.set("pod", "web 1".into()));
+        assert!(!slots.set("pod", "web 1".into()));
+        let runs = slots.rerun("pod");
+        assert_eq!(runs.len(), 2);
+        assert_eq!(
+            runs[0],
+            (
+                SlotTarget::PaneB,
+                "app pod get ''".to_string(),
+                "app pod get 'web 1'".to_string()
+            )
+        );
+        assert_eq!(runs[1].2, "app events 'web 1' --ns ''");
+        // Only watches reading the slot re-run
+        slots.set("ns", "prod".into());
+        let runs = slots.rerun("ns");
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].0, SlotTarget::Nested(PanelPane::B));
+        slots.clear_watches();
+        slots.set("pod", "api".into());
+        assert!(slots.rerun("pod").is_empty());
+    }
+}
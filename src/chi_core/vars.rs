@@ -0,0 +1,225 @@
+use regex::Regex;
+use serde::de::{DeserializeOwned, Error as _};
+use serde_yaml::Value as YamlValue;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+// Config variables: `vars:` in the entry config, referenced as `{var.name}` in any string of
+// the entry config and of every screen, pane or form file loaded after it. A file's own
+// `vars:` add to (or override) the entry's within that file, and `--var name=value` on the
+// command line or `CHI_TUI_VAR_<NAME>` in the environment override both, so one config tree
+// serves several clusters or accounts. References are resolved when a file is parsed.
+
+struct Vars {
+    // `vars:` of the entry config
+    entry: BTreeMap<String, String>,
+    // `--var name=value`
+    overrides: BTreeMap<String, String>,
+}
+
+static VARS: RwLock<Vars> = RwLock::new(Vars {
+    entry: BTreeMap::new(),
+    overrides: BTreeMap::new(),
+});
+
+// Collect `--var name=value` / `--var=name=value` arguments for the session
+pub fn set_overrides(args: &[String]) -> Result<(), String> {
+    let mut overrides = BTreeMap::new();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let pair = match arg.strip_prefix("--var") {
+            Some("") => it
+                .next()
+                .ok_or_else(|| "--var needs name=value".to_string())?
+                .as_str(),
+            Some(rest) => match rest.strip_prefix('=') {
+                Some(pair) => pair,
+                None => continue,
+            },
+            None => continue,
+        };
+        let Some((name, value)) = pair.split_once('=').filter(|(n, _)| !n.trim().is_empty()) else {
+            return Err(format!("--var {pair}: expected name=value"));
+        };
+        overrides.insert(name.trim().to_string(), value.to_string());
+    }
+    if let Ok(mut g) = VARS.write() {
+        g.overrides = overrides;
+    }
+    Ok(())
+}
+
+// Parse the entry config; its `vars:` become the base for every file parsed after it
+pub fn parse_entry<T: DeserializeOwned>(text: &str, source: &Path) -> Result<T, serde_yaml::Error> {
+    parse_doc(text, true, source)
+}
+
+// Parse a screen, pane or form file included by the entry config
+pub fn parse<T: DeserializeOwned>(text: &str, source: &Path) -> Result<T, serde_yaml::Error> {
+    parse_doc(text, false, source)
+}
+
+// `source` is the file the text was read from; deprecated keys are reported against it
+fn parse_doc<T: DeserializeOwned>(
+    text: &str,
+    entry: bool,
+    source: &Path,
+) -> Result<T, serde_yaml::Error> {
+    let mut doc: YamlValue = serde_yaml::from_str(text)?;
+    crate::chi_core::migrate::report(source, crate::chi_core::migrate::upgrade(&mut doc));
+    if entry {
+        crate::chi_core::local::apply(&mut doc, source)?;
+    }
+    let local = declared(&doc)?;
+    let (base, overrides) = {
+        let mut g = VARS
+            .write()
+            .map_err(|_| serde_yaml::Error::custom("vars lock poisoned"))?;
+        if entry {
+            g.entry = local.clone();
+        }
+        (g.entry.clone(), g.overrides.clone())
+    };
+    let lookup = |name: &str| {
+        overrides
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(env_name(name)).ok())
+            .or_else(|| local.get(name).cloned())
+            .or_else(|| base.get(name).cloned())
+    };
+    let re = Regex::new(r"\{var\.([A-Za-z0-9_-]+)\}").unwrap();
+    if let YamlValue::Mapping(m) = &mut doc {
+        // `vars:` itself stays literal
+        for (k, v) in m.iter_mut() {
+            if k.as_str() != Some("vars") {
+                expand(v, &re, &lookup)?;
+            }
+        }
+    } else {
+        expand(&mut doc, &re, &lookup)?;
+    }
+    crate::chi_core::argv::normalize(&mut doc).map_err(serde_yaml::Error::custom)?;
+    serde_yaml::from_value(doc)
+}
+
+// `region` -> CHI_TUI_VAR_REGION
+fn env_name(name: &str) -> String {
+    format!(
+        "CHI_TUI_VAR_{}",
+        name.to_ascii_uppercase().replace('-', "_")
+    )
+}
+
+// Scalar `vars:` of a document as strings
+fn declared(doc: &YamlValue) -> Result<BTreeMap<String, String>, serde_yaml::Error> {
+    let mut out = BTreeMap::new();
+    let Some(vars) = doc.get("vars") else {
+        return Ok(out);
+    };
+    let Some(map) = vars.as_mapping() else {
+        return Err(serde_yaml::Error::custom(
+            "vars: expected a mapping of name: value",
+        ));
+    };
+    for (k, v) in map {
+        let Some(name) = k.as_str() else {
+            continue;
+        };
+        let value = match v {
+            YamlValue::String(s) => s.clone(),
+            YamlValue::Number(n) => n.to_string(),
+            YamlValue::Bool(b) => b.to_string(),
+            YamlValue::Null => String::new(),
+            _ => {
+                return Err(serde_yaml::Error::custom(format!(
+                    "vars.{name}: expected a string, number or boolean"
+                )))
+            }
+        };
+        out.insert(name.to_string(), value);
+    }
+    Ok(out)
+}
+
+fn expand(
+    v: &mut YamlValue,
+    re: &Regex,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), serde_yaml::Error> {
+    match v {
+        YamlValue::String(s) if s.contains("{var.") => {
+            let mut missing = None;
+            let out = re.replace_all(s, |caps: &regex::Captures| {
+                lookup(&caps[1]).unwrap_or_else(|| {
+                    missing.get_or_insert_with(|| caps[1].to_string());
+                    String::new()
+                })
+            });
+            if let Some(name) = missing {
+                return Err(serde_yaml::Error::custom(format!(
+                    "undefined variable {{var.{name}}}: add it under `vars:` or pass --var {name}=<value>"
+                )));
+            }
+            *s = out.into_owned();
+        }
+        YamlValue::Sequence(seq) => {
+            for x in seq {
+                expand(x, re, lookup)?;
+            }
+        }
+        YamlValue::Mapping(m) => {
+            for (_, x) in m.iter_mut() {
+                expand(x, re, lookup)?;
+            }
+        }
+        YamlValue::Tagged(t) => expand(&mut t.value, re, lookup)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value as JsonValue;
+
+    #[test]
+    fn vars_resolve_across_files_with_cli_and_env_overrides() {
+        set_overrides(&["ui".into(), "--var".into(), "account=ops".into()]).unwrap();
+        let cfg: crate::model::AppConfig = parse_entry(
+            "vars: {cluster: prod-eu, ns: web}\nmenu:\n  - id: pods\n    title: Pods ({var.cluster})\n    command: kubectl --context {var.cluster} -n {var.ns} get pods\n",
+            Path::new("chi-index.yaml"),
+        )
+        .unwrap();
+        assert_eq!(cfg.menu[0].title, "Pods (prod-eu)");
+        assert_eq!(
+            cfg.menu[0].command.as_deref(),
+            Some("kubectl --context prod-eu -n web get pods")
+        );
+
+        // An included file sees the entry's vars and may add its own
+        let screen: JsonValue = parse(
+            "vars: {ns: jobs}\ntype: json_viewer\ncmd: app list --cluster {var.cluster} -n {var.ns} --as {var.account}\n",
+            Path::new("screen.yaml"),
+        )
+        .unwrap();
+        assert_eq!(screen["cmd"], "app list --cluster prod-eu -n jobs --as ops");
+
+        std::env::set_var("CHI_TUI_VAR_CLUSTER_NAME", "staging");
+        let v: JsonValue =
+            parse("path: \"{var.cluster_name}.yaml\"\n", Path::new("a.yaml")).unwrap();
+        assert_eq!(v["path"], "staging.yaml");
+        std::env::remove_var("CHI_TUI_VAR_CLUSTER_NAME");
+
+        let err = parse::<JsonValue>("cmd: app --region {var.region}\n", Path::new("a.yaml"))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("undefined variable {var.region}"),
+            "{err}"
+        );
+        assert!(set_overrides(&["--var".into(), "oops".into()]).is_err());
+        set_overrides(&[]).unwrap();
+    }
+}
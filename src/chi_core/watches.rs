@@ -0,0 +1,332 @@
+use serde_json::Value as JsonValue;
+use std::time::{Duration, Instant};
+
+// Watch expressions ('=' on a result): a JSON path into the result of a pane's command
+// (`.data.replicas`, `.items[0].status`) with an optional predicate (`> 5`,
+// `!= "Healthy"`). The value is read again each time that command returns; the watch bar
+// marks the values that just changed, and a predicate turning true raises an alert.
+
+// How long a changed value stays marked in the watch bar
+pub const CHANGED_FOR: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, PartialEq)]
+enum Seg {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Predicate {
+    op: Op,
+    rhs: JsonValue,
+}
+
+fn number(v: &JsonValue) -> Option<f64> {
+    match v {
+        JsonValue::Number(n) => n.as_f64(),
+        JsonValue::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+// Values as they read in the bar and in comparisons: strings without their quotes
+pub fn display(v: &JsonValue) -> String {
+    match v {
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl Predicate {
+    fn holds(&self, v: &JsonValue) -> bool {
+        if let (Some(a), Some(b)) = (number(v), number(&self.rhs)) {
+            return match self.op {
+                Op::Eq => a == b,
+                Op::Ne => a != b,
+                Op::Gt => a > b,
+                Op::Ge => a >= b,
+                Op::Lt => a < b,
+                Op::Le => a <= b,
+            };
+        }
+        match self.op {
+            Op::Eq => display(v) == display(&self.rhs),
+            Op::Ne => display(v) != display(&self.rhs),
+            // Ordering needs numbers on both sides
+            _ => false,
+        }
+    }
+}
+
+// `.a.b[2].c` (the leading dot is optional; `.` alone is the whole result)
+fn parse_path(path: &str) -> Result<Vec<Seg>, String> {
+    let mut segs = Vec::new();
+    let rest = path.strip_prefix('.').unwrap_or(path);
+    if rest.is_empty() {
+        return Ok(segs);
+    }
+    for part in rest.split('.') {
+        let (key, mut idx) = match part.find('[') {
+            Some(i) => (&part[..i], &part[i..]),
+            None => (part, ""),
+        };
+        if key.is_empty() && idx.is_empty() {
+            return Err(format!("empty segment in '{path}'"));
+        }
+        if !key.is_empty() {
+            segs.push(Seg::Key(key.to_string()));
+        }
+        while let Some(inner) = idx.strip_prefix('[') {
+            let end = inner
+                .find(']')
+                .ok_or_else(|| format!("unclosed '[' in '{path}'"))?;
+            let n = inner[..end]
+                .parse()
+                .map_err(|_| format!("bad index '{}' in '{path}'", &inner[..end]))?;
+            segs.push(Seg::Index(n));
+            idx = &inner[end + 1..];
+        }
+        if !idx.is_empty() {
+            return Err(format!("unexpected '{idx}' in '{path}'"));
+        }
+    }
+    Ok(segs)
+}
+
+fn parse_predicate(text: &str) -> Result<Predicate, String> {
+    let ops = [
+        ("==", Op::Eq),
+        ("!=", Op::Ne),
+        (">=", Op::Ge),
+        ("<=", Op::Le),
+        (">", Op::Gt),
+        ("<", Op::Lt),
+        ("=", Op::Eq),
+    ];
+    let (op, rest) = ops
+        .iter()
+        .find_map(|(sym, op)| text.strip_prefix(sym).map(|rest| (*op, rest.trim())))
+        .ok_or_else(|| format!("expected one of == != > >= < <= before '{text}'"))?;
+    if rest.is_empty() {
+        return Err("missing the value to compare with".into());
+    }
+    // JSON literals (`5`, `"Healthy"`, `true`); anything else is a bare string
+    let rhs = serde_json::from_str(rest).unwrap_or_else(|_| JsonValue::String(rest.into()));
+    Ok(Predicate { op, rhs })
+}
+
+fn lookup<'a>(v: &'a JsonValue, path: &[Seg]) -> Option<&'a JsonValue> {
+    path.iter().try_fold(v, |cur, seg| match seg {
+        Seg::Key(k) => cur.get(k),
+        Seg::Index(i) => cur.get(i),
+    })
+}
+
+#[derive(Clone, Debug)]
+pub struct Watch {
+    // Command whose results the watch reads
+    pub source: String,
+    // As typed: `.data.replicas > 5`
+    pub expr: String,
+    path: Vec<Seg>,
+    predicate: Option<Predicate>,
+    // Last value read (None: not seen yet, Null: the path is missing)
+    pub value: Option<JsonValue>,
+    pub previous: Option<JsonValue>,
+    pub changed_at: Option<Instant>,
+    // The predicate held on the last read
+    pub firing: bool,
+}
+
+impl Watch {
+    pub fn parse(source: &str, expr: &str) -> Result<Self, String> {
+        let expr = expr.trim();
+        let (path, rest) = match expr.find(|c: char| c.is_whitespace() || "=!<>".contains(c)) {
+            Some(i) => (&expr[..i], expr[i..].trim()),
+            None => (expr, ""),
+        };
+        if path.is_empty() {
+            return Err("a watch starts with a path like .data.replicas".into());
+        }
+        Ok(Watch {
+            source: source.to_string(),
+            expr: expr.to_string(),
+            path: parse_path(path)?,
+            predicate: (!rest.is_empty())
+                .then(|| parse_predicate(rest))
+                .transpose()?,
+            value: None,
+            previous: None,
+            changed_at: None,
+            firing: false,
+        })
+    }
+
+    // Marked as changed in the bar
+    pub fn changed(&self, now: Instant) -> bool {
+        self.changed_at
+            .is_some_and(|at| now.saturating_duration_since(at) < CHANGED_FOR)
+    }
+
+    // The predicate holds on a result (without one: the path is there and not null/false)
+    pub fn holds(&self, result: &JsonValue) -> bool {
+        let value = lookup(result, &self.path).unwrap_or(&JsonValue::Null);
+        match &self.predicate {
+            Some(p) => p.holds(value),
+            None => !matches!(value, JsonValue::Null | JsonValue::Bool(false)),
+        }
+    }
+
+    // Read the value from a new result; Some(alert) when the predicate has just become true
+    fn observe(&mut self, result: &JsonValue, now: Instant) -> Option<String> {
+        let value = lookup(result, &self.path)
+            .cloned()
+            .unwrap_or(JsonValue::Null);
+        if self.value.as_ref() != Some(&value) {
+            if self.value.is_some() {
+                self.changed_at = Some(now);
+            }
+            self.previous = self.value.replace(value.clone());
+        }
+        let firing = self.predicate.as_ref().is_some_and(|p| p.holds(&value));
+        let alert =
+            (firing && !self.firing).then(|| format!("{} ({})", self.expr, display(&value)));
+        self.firing = firing;
+        alert
+    }
+}
+
+#[derive(Default)]
+pub struct Watches {
+    list: Vec<Watch>,
+}
+
+impl Watches {
+    pub fn iter(&self) -> impl Iterator<Item = &Watch> {
+        self.list.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    // Add a watch, read right away from the current result. The same expression on the same
+    // command again removes it instead (false).
+    pub fn toggle(
+        &mut self,
+        mut watch: Watch,
+        current: &JsonValue,
+        now: Instant,
+    ) -> (bool, Option<String>) {
+        let before = self.list.len();
+        self.list
+            .retain(|w| !(w.source == watch.source && w.expr == watch.expr));
+        if self.list.len() < before {
+            return (false, None);
+        }
+        let alert = watch.observe(current, now);
+        self.list.push(watch);
+        (true, alert)
+    }
+
+    // Drop every watch of a command
+    pub fn clear_source(&mut self, source: &str) -> usize {
+        let before = self.list.len();
+        self.list.retain(|w| w.source != source);
+        before - self.list.len()
+    }
+
+    // A new result of `source`: update its watches and collect the alerts
+    pub fn observe(&mut self, source: &str, result: &JsonValue, now: Instant) -> Vec<String> {
+        self.list
+            .iter_mut()
+            .filter(|w| w.source == source)
+            .filter_map(|w| w.observe(result, now))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_paths_and_predicates() {
+        let w = Watch::parse("app get", ".data.items[1].replicas >= 3").unwrap();
+        assert_eq!(
+            w.path,
+            [
+                Seg::Key("data".into()),
+                Seg::Key("items".into()),
+                Seg::Index(1),
+                Seg::Key("replicas".into())
+            ]
+        );
+        assert_eq!(w.predicate.unwrap().op, Op::Ge);
+        let w = Watch::parse("app get", r#".status!="Healthy""#).unwrap();
+        assert_eq!(w.predicate.unwrap().rhs, json!("Healthy"));
+        assert!(Watch::parse("app get", ".").unwrap().path.is_empty());
+        assert!(Watch::parse("app get", ".a[x]").is_err());
+        assert!(Watch::parse("app get", ".a ~ 3").is_err());
+        assert!(Watch::parse("app get", ".a >").is_err());
+        assert!(Watch::parse("app get", "").is_err());
+    }
+
+    #[test]
+    fn values_are_tracked_and_alerts_fire_once_per_crossing() {
+        let t0 = Instant::now();
+        let mut watches = Watches::default();
+        let replicas = Watch::parse("app get", ".data.replicas > 5").unwrap();
+        let health = Watch::parse("app get", r#".data.health != "Healthy""#).unwrap();
+        let first = json!({"data": {"replicas": 3, "health": "Healthy"}});
+        assert_eq!(watches.toggle(replicas, &first, t0), (true, None));
+        assert_eq!(watches.toggle(health, &first, t0), (true, None));
+        let w = watches.iter().next().unwrap();
+        assert_eq!(w.value, Some(json!(3)));
+        // The first read is not a change
+        assert!(!w.changed(t0));
+
+        // Results of other commands are ignored
+        let other = json!({"data": {"replicas": 9}});
+        assert!(watches.observe("app other", &other, t0).is_empty());
+
+        let t1 = t0 + Duration::from_secs(1);
+        let second = json!({"data": {"replicas": "7", "health": "Degraded"}});
+        let alerts = watches.observe("app get", &second, t1);
+        assert_eq!(
+            alerts,
+            [
+                ".data.replicas > 5 (7)",
+                r#".data.health != "Healthy" (Degraded)"#
+            ]
+        );
+        let w = watches.iter().next().unwrap();
+        assert!(w.firing && w.changed(t1));
+        assert_eq!(w.previous, Some(json!(3)));
+        assert!(!w.changed(t1 + CHANGED_FOR));
+        // Still true: no new alert
+        assert!(watches.observe("app get", &second, t1).is_empty());
+
+        // A missing path reads as null
+        let gone = json!({"data": {}});
+        assert!(watches.observe("app get", &gone, t1).is_empty());
+        assert_eq!(watches.iter().next().unwrap().value, Some(JsonValue::Null));
+
+        // Same expression again removes the watch
+        let again = Watch::parse("app get", ".data.replicas > 5").unwrap();
+        assert_eq!(watches.toggle(again, &gone, t1), (false, None));
+        assert_eq!(watches.clear_source("app get"), 1);
+        assert!(watches.is_empty());
+    }
+}
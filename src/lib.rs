@@ -0,0 +1,15 @@
+mod app;
+mod chi_core;
+mod model;
+mod nav;
+mod services;
+mod theme;
+mod ui;
+mod visuals;
+mod widgets;
+
+// Fixtures and entry points of the criterion benchmarks (benches/)
+#[doc(hidden)]
+pub mod bench;
+
+pub use ui::{migrate, print_tree, run};
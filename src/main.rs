@@ -1,15 +1,10 @@
-mod app;
-mod chi_core;
-mod model;
-mod nav;
-mod services;
-mod theme;
-mod ui;
-mod visuals;
-mod widgets;
-
 use anyhow::Result;
 
 fn main() -> Result<()> {
-    ui::run()
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("tree") => chi_tui::print_tree(&args[1..]),
+        Some("migrate") => chi_tui::migrate(&args[1..]),
+        _ => chi_tui::run(&args),
+    }
 }
@@ -1,5 +1,6 @@
 use serde::Deserialize;
 use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct MenuItem {
@@ -19,6 +20,9 @@ pub struct MenuItem {
     // Markdown: optional inline content (when widget == "markdown")
     #[serde(default)]
     pub content: Option<String>,
+    // Markdown: page generated from the current config instead of content/path (menu_tree)
+    #[serde(default)]
+    pub builtin: Option<String>,
     // Watchdog: optional list of commands (when widget == "watchdog")
     #[serde(default)]
     pub commands: Option<Vec<String>>,
@@ -27,6 +31,11 @@ pub struct MenuItem {
     pub external_check_cmd: Option<String>,
     #[serde(default)]
     pub external_kill_cmd: Option<String>,
+    // Watchdog: systemd units / docker containers to follow and restart (a name or a list)
+    #[serde(default)]
+    pub unit: Option<JsonValue>,
+    #[serde(default)]
+    pub container: Option<JsonValue>,
     #[serde(default)]
     pub sequential: Option<bool>,
     #[serde(default)]
@@ -68,18 +77,344 @@ pub struct MenuItem {
     pub pane_a_yaml: Option<String>,
     #[serde(default)]
     pub pane_b_yaml: Option<String>,
+    // Inline widget spec hosted in Pane A instead of the menu (watchdog, log_viewer, ...)
+    #[serde(default)]
+    pub pane_a_widget: Option<JsonValue>,
+    // Per-screen override of the narrow-terminal template: vertical|drawer|none
+    #[serde(default)]
+    pub narrow_layout: Option<String>,
+    // Per-screen override of focus after async pane loads: pane_b|keep|pane_a
+    #[serde(default)]
+    pub focus_on_load: Option<String>,
+    // Per-screen override of how many pane specs may load one another in a chain
+    #[serde(default)]
+    pub max_panel_depth: Option<usize>,
     #[serde(default)]
     #[allow(dead_code)]
     pub modal: Option<bool>,
+    // Optional: commands of items sharing this name never run concurrently (later runs queue)
+    #[serde(default)]
+    pub mutex: Option<String>,
+    // Optional: after the command runs, block re-triggering it for this many seconds
+    #[serde(default)]
+    pub cooldown_secs: Option<u64>,
+    // Optional: shown next to the greyed-out item when its command's program is not installed
+    #[serde(default)]
+    pub install_hint: Option<String>,
+    // Optional: commands run in the background when the item is entered / left (its view
+    // closed or another item entered); their output goes to the debug log
+    #[serde(default)]
+    pub on_enter_cmd: Option<String>,
+    #[serde(default)]
+    pub on_leave_cmd: Option<String>,
+    // Optional: extra columns shown on the rows of loaded items (lazy_items/autoload_items)
+    #[serde(default)]
+    pub fields: Option<Vec<FieldSpec>>,
+    // Optional: timestamps in the loaded items, normalized to RFC 3339 (UTC) when they load
+    #[serde(default)]
+    pub time_fields: Option<Vec<TimeFieldSpec>>,
+    // Optional: filter bar above the Pane B result (values substituted as `{name}` into the
+    // command, or matched against the result items)
+    #[serde(default)]
+    pub filters: Option<Vec<FilterSpec>>,
+    // Optional: result row fields editable in place with 'e'; `update_cmd` runs on confirm
+    // with `{field}`, `{value}` and the row's own keys (`{id}`) substituted, shell-quoted
+    #[serde(default)]
+    pub editable_fields: Option<Vec<String>>,
+    #[serde(default)]
+    pub update_cmd: Option<String>,
+    // Optional: shown in Pane B instead of an empty result (`[]`, or an envelope whose `data`
+    // is empty), with an optional key opening another item
+    #[serde(default)]
+    pub empty_text: Option<String>,
+    // Optional: widget spec (`type: markdown`, ...) shown instead of an empty result
+    #[serde(default)]
+    pub empty_widget: Option<JsonValue>,
+    #[serde(default)]
+    pub empty_action: Option<EmptyActionSpec>,
+    // Optional: regex rules restyling matched text in the item's output (any text pane)
+    #[serde(default)]
+    pub highlights: Option<Vec<HighlightSpec>>,
+    // Optional: per-item actions on a key, also offered on the item's loaded children
+    #[serde(default)]
+    pub verbs: Option<Vec<VerbSpec>>,
+    // Optional: a single key opening this item from anywhere on its screen, shown after the
+    // title (checked against the menu's own keys and the other items' hotkeys on load)
+    #[serde(default)]
+    pub hotkey: Option<String>,
+    // Optional: quick commands deciding whether the item is shown / can be opened (exit 0
+    // passes, unless the output is a JSON envelope with `ok: false`)
+    #[serde(default)]
+    pub when_cmd: Option<String>,
+    #[serde(default)]
+    pub enabled_cmd: Option<String>,
+    // Optional: the selected child's field is published into a named slot, read by other
+    // panes' command templates as `{slot.<name>}`
+    #[serde(default)]
+    pub publish: Option<PublishSpec>,
+    // Optional: markdown children generated from the files matching a glob (re-read on expand)
+    #[serde(default)]
+    pub children_from_glob: Option<String>,
+    // Board (`widget: board`): the item field holding a card's status (default `status`),
+    // the columns shown first, and the command run when a card moves (`{to}`, `{from}` and
+    // the card's own keys substituted, shell-quoted)
+    #[serde(default)]
+    pub status_field: Option<String>,
+    #[serde(default)]
+    pub board_columns: Option<Vec<String>>,
+    #[serde(default)]
+    pub move_cmd: Option<String>,
+    // Tree (`widget: tree`): the key holding a node's children (default `children`), and the
+    // command run on expand for nodes without them (the node's keys substituted)
+    #[serde(default)]
+    pub children_key: Option<String>,
+    #[serde(default)]
+    pub children_cmd: Option<String>,
+    // Process list (`widget: processes`): only processes whose command line matches this
+    // regex (case-insensitive), re-read every `refresh_secs` (default 2)
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub refresh_secs: Option<u64>,
+    // Port checks (`widget: ports`): `host:port` or `label=host:port` targets, each checked
+    // with a TCP connect of at most `timeout_ms` (default 1000) every `refresh_secs` (default 5)
+    #[serde(default)]
+    pub targets: Option<Vec<String>>,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    // HTTP checks (`widget: http_checks`): URLs polled every `refresh_secs` (default 30), each
+    // request limited to `timeout_ms` (default 5000)
+    #[serde(default)]
+    pub checks: Option<Vec<HttpCheckSpec>>,
+    // Prometheus (`widget: promql`): `queries:` sent to the `prometheus` server (default
+    // http://localhost:9090) every `refresh_secs` (default 30), each limited to `timeout_ms`
+    #[serde(default)]
+    pub prometheus: Option<String>,
+    #[serde(default)]
+    pub queries: Option<Vec<PromQuerySpec>>,
+    // Kubernetes source (`k8s: {kind: pods, namespace: prod}`): expanded on load into a
+    // kubectl command listing the resources as children, plus logs/describe/delete verbs
+    #[serde(default)]
+    pub k8s: Option<K8sSpec>,
+    // SQL source (`db: {database: app.db, query: "select ..."}`): the query's rows listed as
+    // children a page at a time, read through the database's CLI (`db` feature)
+    #[serde(default)]
+    pub db: Option<DbSpec>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct K8sSpec {
+    // Resource kind as kubectl takes it: pods, deployments, svc, ...
+    pub kind: String,
+    // Namespace (`all` for every namespace). Default: the context's namespace
+    #[serde(default)]
+    pub namespace: Option<String>,
+    // Label selector (`app=api`)
+    #[serde(default)]
+    pub selector: Option<String>,
+    // kubectl context. Default: the current one
+    #[serde(default)]
+    pub context: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[cfg_attr(not(feature = "db"), allow(dead_code))]
+pub struct DbSpec {
+    // sqlite|postgres|mysql. Default: postgres for a `postgres://` URL, otherwise sqlite
+    #[serde(default)]
+    pub driver: Option<String>,
+    // sqlite file, postgres URL / conninfo, or mysql database name
+    #[serde(default)]
+    pub database: String,
+    // SELECT whose `:name` placeholders are bound from `params` as quoted SQL literals
+    pub query: String,
+    #[serde(default)]
+    pub params: Option<BTreeMap<String, String>>,
+    // Rows per page. Default: 50
+    #[serde(default)]
+    pub page_size: Option<usize>,
+    // Extra arguments for the CLI (`-h`, `-U`, `--defaults-file=...`)
+    #[serde(default)]
+    pub args: Option<Vec<String>>,
+}
+
+// Call-to-action of an empty state: `key` (a character or `enter`, default enter) opens the
+// menu item `item`; `label` defaults to that item's title
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct EmptyActionSpec {
+    pub item: String,
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PublishSpec {
+    pub slot: String,
+    // Key of the child value to publish. Default: id
+    #[serde(default)]
+    pub field: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct StatusGlyphSpec {
+    #[serde(default)]
+    pub ok: Option<String>,
+    #[serde(default)]
+    pub fail: Option<String>,
+    #[serde(default)]
+    pub info: Option<String>,
+    #[serde(default)]
+    pub warn: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct ThemeConfig {
+    // dark | light | high-contrast | auto. Default: auto (light on a light terminal
+    // background, otherwise dark)
+    #[serde(default)]
+    pub preset: Option<String>,
+    // Colors over the preset: names (e.g. "cyan") or "#rrggbb"
+    #[serde(default)]
+    pub bg: Option<String>,
+    #[serde(default)]
+    pub primary: Option<String>,
+    #[serde(default)]
+    pub secondary: Option<String>,
+    #[serde(default)]
+    pub accent: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct BannerSpec {
+    pub text: String,
+    // RFC 3339 time the banner expires (`2024-06-01T12:00Z`); a countdown is shown until then
+    #[serde(default)]
+    pub until: Option<String>,
+    // info|warn|error. Default: warn
+    #[serde(default)]
+    pub level: Option<String>,
+    // Banners pushed with the same id replace each other. Default: one slot for pushed banners
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct HttpCheckSpec {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub url: String,
+    // Status the response must have. Default: any 2xx
+    #[serde(default)]
+    pub expect_status: Option<u16>,
+    // Text the response body must contain
+    #[serde(default)]
+    pub expect_contains: Option<String>,
+}
+
+impl HttpCheckSpec {
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.url)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PromQuerySpec {
+    #[serde(default)]
+    pub name: Option<String>,
+    // PromQL; `{slot.<name>}` takes the slot's value as-is
+    pub query: String,
+    // Shown after the value (`req/s`, `%`)
+    #[serde(default)]
+    pub unit: Option<String>,
+    // Window of a range query (`15m`, `1h`, `7d`) drawn as a chart. Default: instant query
+    #[serde(default)]
+    pub range: Option<String>,
+    // Resolution of the range query. Default: a sixtieth of the range
+    #[serde(default)]
+    pub step: Option<String>,
+    // Badge turns warn/fail from these values up (down, when `crit` is below `warn`)
+    #[serde(default)]
+    pub warn: Option<f64>,
+    #[serde(default)]
+    pub crit: Option<f64>,
+}
+
+impl PromQuerySpec {
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.query)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VerbSpec {
+    pub key: char,
+    pub title: String,
+    // `{key}` placeholders take the selected child's values (shell-quoted)
+    pub command: String,
+    // Ask y/n before running (destructive verbs)
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct HighlightSpec {
+    pub regex: String,
+    // Style words, e.g. `error bold` or `yellow on muted` (theme colors resolve at draw time)
+    pub style: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct FilterSpec {
+    // Placeholder name in the command (`{status}`) and default item key
+    pub name: String,
+    // Values to choose from; the first is the default. `all` disables the filter
+    pub options: Vec<String>,
+    // Optional: item key compared when filtering client-side. Default: name
+    #[serde(default)]
+    pub field: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct FieldSpec {
+    // Key of the value in each loaded item
+    pub name: String,
+    // Optional: label printed before the value
+    #[serde(default)]
+    pub label: Option<String>,
+    // Optional: `sparkline` draws an array of numbers as ▁▂▃▅▇, `relative_time` shows a
+    // timestamp as "3m ago" (refreshed on every tick). Default: plain value
+    #[serde(default)]
+    pub render: Option<String>,
+    // Optional: fixed column width in terminal cells; values are padded (numbers to the
+    // right) or cut with "…", so the column lines up across rows
+    #[serde(default)]
+    pub width: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TimeFieldSpec {
+    // Dotted path of the timestamp in each loaded item (`metadata.created`)
+    pub path: String,
+    // Optional: `rfc3339`, `epoch_s`, `epoch_ms` or a strftime pattern (`%d/%m/%Y %H:%M`, local
+    // time unless it has `%z`). Default: guessed per value among the first three
+    #[serde(default)]
+    pub format: Option<String>,
+    // Optional: `asc` / `desc` orders the loaded rows by this timestamp (unparsed ones last)
+    #[serde(default)]
+    pub sort: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct HorizontalMenuItem {
-    #[allow(dead_code)]
     pub id: String,
     pub title: String,
     #[serde(default)]
     pub config: Option<String>, // Path to YAML config to load when selected
+    // Optional: left out of the tab bar and F-keys; opened from the command palette (Ctrl+K)
+    #[serde(default)]
+    pub hidden: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -92,22 +427,250 @@ pub struct AppConfig {
     // Optional: auto-enter a menu item by id when this screen loads
     #[serde(default)]
     pub auto_enter: Option<String>,
+    // Optional: commands run in the background when this screen is entered / left (their
+    // output goes to the debug log)
+    #[serde(default)]
+    pub on_enter_cmd: Option<String>,
+    #[serde(default)]
+    pub on_leave_cmd: Option<String>,
     // Optional: allow closing panel view with Esc. Default: true.
     #[serde(default = "default_true")]
     pub can_close: bool,
     #[serde(default)]
     pub horizontal_menu: Vec<HorizontalMenuItem>,
+    // Optional: layout rules for narrow terminals (e.g. half-width tmux panes)
+    #[serde(default)]
+    pub responsive: Option<ResponsiveConfig>,
+    // Optional: focus policy after async pane loads: pane_b|keep|pane_a. Default: keep
+    #[serde(default)]
+    pub focus_on_load: Option<String>,
+    // Optional: how many pane specs may load one another in a chain (a spec naming another
+    // spec or command) before the pane shows an error. Default: 8
+    #[serde(default)]
+    pub max_panel_depth: Option<usize>,
+    // Optional: header banner effect for this screen: matrix|wave|starfield|none. Default: matrix
+    #[serde(default)]
+    pub banner_animation: Option<String>,
+    // Optional (entry config): short logo splash on startup. Default: false
+    #[serde(default)]
+    pub splash: bool,
+    // Optional (entry config): `animations: off` disables every effect, banner included
+    #[serde(default)]
+    pub animations: Option<JsonValue>,
+    // Optional (entry config): `reduced_motion: true` keeps the banner and side strips but
+    // draws them static (no matrix/ambient motion, loading border or splash)
+    #[serde(default)]
+    pub reduced_motion: bool,
+    // Optional: pane chrome (border type, title alignment, border colors, padding)
+    #[serde(default)]
+    pub chrome: Option<ChromeConfig>,
+    // Optional (entry config): other project config dirs reachable from the workspace switcher
+    #[serde(default)]
+    pub workspaces: Vec<WorkspaceConfig>,
+    // Optional: commands checked at startup (CLI version, auth); failures show a banner
+    #[serde(default)]
+    pub preflight: Vec<PreflightCheck>,
+    // Optional: notice across the top (freeze windows, maintenance), hidden after `until`
+    #[serde(default)]
+    pub banner: Option<BannerSpec>,
+    // Optional (entry config): high-distinction status palette instead of red/green
+    #[serde(default)]
+    pub colorblind: bool,
+    // Optional (entry config): glyphs shown next to ok/fail/info/warn statuses
+    #[serde(default)]
+    pub status_glyphs: Option<StatusGlyphSpec>,
+    // Optional (entry config, or `theme.yaml` next to it): color preset and overrides
+    #[serde(default)]
+    pub theme: Option<ThemeConfig>,
+    // Optional: session check and login command of the backing CLI
+    #[serde(default)]
+    pub auth: Option<AuthConfig>,
+    // Optional: refresh expired credentials in the background and run the failed item again
+    #[serde(default)]
+    pub auth_retry: Option<AuthRetryConfig>,
+    // Optional: opt-in usage events piped to a hook command
+    #[serde(default)]
+    pub telemetry: Option<TelemetryConfig>,
+    // Optional: on-disk cache of command results, with offline mode
+    #[serde(default)]
+    pub cache: Option<CacheConfig>,
+    // Optional: key repeat acceleration for long lists
+    #[serde(default)]
+    pub keybindings: Option<KeybindingsConfig>,
+    // Optional (entry config): ask before 'q' quits: auto|always|never. Default: auto (only
+    // while watchdogs run, jobs are scheduled or Pane B has unsaved input)
+    #[serde(default)]
+    pub confirm_quit: Option<String>,
+    #[serde(deserialize_with = "menu_items")]
     pub menu: Vec<MenuItem>,
 }
 
+impl AppConfig {
+    pub fn animations_off(&self) -> bool {
+        match &self.animations {
+            Some(JsonValue::Bool(b)) => !b,
+            Some(JsonValue::String(s)) => matches!(
+                s.to_ascii_lowercase().as_str(),
+                "off" | "false" | "no" | "none"
+            ),
+            _ => false,
+        }
+    }
+
+    pub fn banner_animation(&self) -> &str {
+        match self.banner_animation.as_deref() {
+            Some(a @ ("matrix" | "wave" | "starfield" | "none")) => a,
+            _ => "matrix",
+        }
+    }
+
+    pub fn confirm_quit(&self) -> &'static str {
+        match self.confirm_quit.as_deref() {
+            Some("always") => "always",
+            Some("never") => "never",
+            _ => "auto",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WorkspaceConfig {
+    pub title: String,
+    // Directory holding chi-index.yaml (or a project root with .tui/chi-index.yaml),
+    // relative to the config dir that declares it
+    pub dir: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PreflightCheck {
+    pub title: String,
+    // Command run in the background at startup; a non-zero exit fails the check
+    pub command: String,
+    // Optional: what to do about a failure, shown in the banner
+    #[serde(default)]
+    pub remediation: Option<String>,
+    // Optional: ids of menu items that can't be opened while the check fails
+    #[serde(default)]
+    pub blocks: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct KeybindingsConfig {
+    // Optional: Up/Down repeats before moving faster (0 disables). Default: 10
+    #[serde(default)]
+    pub accelerate_after: Option<u32>,
+    // Optional: rows per event after each further `accelerate_after` repeats. Default: [5, 25]
+    #[serde(default)]
+    pub accelerate_steps: Option<Vec<usize>>,
+    // Optional: max gap (ms) between presses that still counts as holding the key. Default: 80
+    #[serde(default)]
+    pub repeat_window_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct CacheConfig {
+    // Optional: cache directory (relative to the config dir). Default: ~/.cache/chi-tui
+    #[serde(default)]
+    pub dir: Option<String>,
+    // Optional: start in offline mode (cached results only; toggle with `O`)
+    #[serde(default)]
+    pub offline: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TelemetryConfig {
+    // Hook command; receives a JSON array of events on stdin
+    pub command: String,
+    // Optional: events per hook run. Default: 20
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+    // Optional: send a partial batch after this many seconds. Default: 60
+    #[serde(default)]
+    pub flush_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AuthConfig {
+    // Command that exits 0 while logged in (run at startup)
+    pub check: String,
+    // Command that logs in
+    pub login: String,
+    // Optional: `interactive` (default) hands the terminal to the login command,
+    // `device_code` runs it in the background and shows its output (URL and code)
+    #[serde(default)]
+    pub mode: Option<String>,
+    // Optional: exit code any command uses for "not logged in" (e.g. 77)
+    #[serde(default)]
+    pub unauthenticated_exit_code: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AuthRetryConfig {
+    // Test on a command's JSON output or error, written like a watch expression:
+    // `.error.code == "token_expired"`
+    pub detect: String,
+    // Command that refreshes the credentials without user input
+    pub refresh_cmd: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ResponsiveConfig {
+    // Terminal width (columns) below which the narrow template applies. Default: 100
+    #[serde(default)]
+    pub narrow_width: Option<u16>,
+    // Narrow template for panels: vertical (stack A over B) | drawer (collapse Pane A)
+    #[serde(default)]
+    pub narrow_layout: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ChromeConfig {
+    // plain | rounded | double | none. Default: plain
+    #[serde(default)]
+    pub border: Option<String>,
+    // left | center. Default: left
+    #[serde(default)]
+    pub title_align: Option<String>,
+    // Border colors: names (e.g. "cyan") or "#rrggbb"
+    #[serde(default)]
+    pub border_focused: Option<String>,
+    #[serde(default)]
+    pub border_unfocused: Option<String>,
+    // Horizontal padding (columns) inside pane borders. Default: 0
+    #[serde(default)]
+    pub padding: Option<u16>,
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             header: Some("CHI TUI".to_string()),
             logo: None,
             auto_enter: None,
+            on_enter_cmd: None,
+            on_leave_cmd: None,
             can_close: true,
             horizontal_menu: vec![],
+            responsive: None,
+            focus_on_load: None,
+            max_panel_depth: None,
+            banner_animation: None,
+            splash: false,
+            animations: None,
+            reduced_motion: false,
+            chrome: None,
+            workspaces: vec![],
+            preflight: vec![],
+            banner: None,
+            colorblind: false,
+            status_glyphs: None,
+            theme: None,
+            auth: None,
+            auth_retry: None,
+            telemetry: None,
+            cache: None,
+            keybindings: None,
+            confirm_quit: None,
             menu: vec![],
         }
     }
@@ -117,6 +680,25 @@ fn default_true() -> bool {
     true
 }
 
+// Menu items with source sugar (`k8s:`, `db:`) expanded into plain commands and verbs
+fn menu_items<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Vec<MenuItem>, D::Error> {
+    let mut items = Vec::<MenuItem>::deserialize(d)?;
+    for mi in &mut items {
+        crate::services::k8s::expand(mi).map_err(serde::de::Error::custom)?;
+        #[cfg(feature = "db")]
+        crate::services::db::expand(mi).map_err(serde::de::Error::custom)?;
+        #[cfg(not(feature = "db"))]
+        if mi.db.is_some() {
+            return Err(serde::de::Error::custom(format!(
+                "menu '{}': `db:` sources need a build with the `db` feature",
+                mi.id
+            )));
+        }
+    }
+    crate::nav::hotkeys::check(&items).map_err(serde::de::Error::custom)?;
+    Ok(items)
+}
+
 #[allow(dead_code)]
 pub(crate) fn validate_app_config(cfg: &AppConfig) -> Result<(), String> {
     use std::collections::HashSet;
@@ -125,16 +707,35 @@ pub(crate) fn validate_app_config(cfg: &AppConfig) -> Result<(), String> {
         if !ids.insert(&m.id) {
             return Err(format!("duplicate menu id: '{}' at index {}", m.id, i));
         }
+        if let Some(a) = &m.empty_action {
+            if !cfg.menu.iter().any(|other| other.id == a.item) {
+                return Err(format!(
+                    "menu '{}': empty_action refers to unknown item '{}'",
+                    m.id, a.item
+                ));
+            }
+            if crate::widgets::empty_state::parse_key(a.key.as_deref()).is_none() {
+                return Err(format!(
+                    "menu '{}': empty_action key must be a single character or 'enter'",
+                    m.id
+                ));
+            }
+        }
+        for tf in m.time_fields.iter().flatten() {
+            crate::services::time_fields::check(tf)
+                .map_err(|e| format!("menu '{}': time_fields: {e}", m.id))?;
+        }
         if let Some(w) = &m.widget {
             match w.as_str() {
                 "panel" => {
                     let any = m.pane_a_cmd.is_some()
                         || m.pane_b_cmd.is_some()
                         || m.pane_a_yaml.is_some()
-                        || m.pane_b_yaml.is_some();
+                        || m.pane_b_yaml.is_some()
+                        || m.pane_a_widget.is_some();
                     if !any {
                         return Err(format!(
-                            "panel '{}' must specify at least one of pane_a/b cmd/yaml",
+                            "panel '{}' must specify at least one of pane_a/b cmd/yaml or pane_a_widget",
                             m.id
                         ));
                     }
@@ -160,27 +761,53 @@ pub(crate) fn validate_app_config(cfg: &AppConfig) -> Result<(), String> {
                         }
                     }
                 }
-                "lazy_items" | "autoload_items" => {
-                    if m.command.as_deref().unwrap_or("").is_empty() {
-                        return Err(format!("menu '{}' requires 'command' for {}", m.id, w));
+                "lazy_items" | "autoload_items" | "board" | "tree"
+                    if m.command.as_deref().unwrap_or("").is_empty() =>
+                {
+                    return Err(format!("menu '{}' requires 'command' for {}", m.id, w));
+                }
+                "ports" if m.targets.as_ref().is_none_or(|t| t.is_empty()) => {
+                    return Err(format!("menu '{}' requires 'targets' for {}", m.id, w));
+                }
+                "http_checks" if m.checks.as_ref().is_none_or(|c| c.is_empty()) => {
+                    return Err(format!("menu '{}' requires 'checks' for {}", m.id, w));
+                }
+                "promql" if m.queries.as_ref().is_none_or(|q| q.is_empty()) => {
+                    return Err(format!("menu '{}' requires 'queries' for {}", m.id, w));
+                }
+                "log_viewer" => {
+                    let has_cmd = m.command.as_deref().is_some_and(|s| !s.is_empty());
+                    let has_path = m.path.as_deref().is_some_and(|s| !s.is_empty());
+                    if !(has_cmd || has_path) {
+                        return Err(format!(
+                            "menu '{}' requires either 'command' or 'path' for {}",
+                            m.id, w
+                        ));
                     }
                 }
-                "markdown" => {
+                "markdown" | "runbook" => {
                     let has_path = m.path.as_deref().map(|s| !s.is_empty()).unwrap_or(false);
                     let has_content = m.content.as_deref().map(|s| !s.is_empty()).unwrap_or(false);
                     if !(has_path || has_content) {
                         return Err(format!(
-                            "menu '{}' requires either 'path' or 'content' for markdown",
-                            m.id
+                            "menu '{}' requires either 'path' or 'content' for {}",
+                            m.id, w
                         ));
                     }
                 }
                 "watchdog" => {
                     let allows_external = m.external_check_cmd.is_some();
-                    if m.commands.as_ref().map(|v| v.is_empty()).unwrap_or(true) && !allows_external
+                    let has_targets = !crate::widgets::watchdog::targets::from_fields(
+                        m.unit.as_ref(),
+                        m.container.as_ref(),
+                    )
+                    .is_empty();
+                    if m.commands.as_ref().map(|v| v.is_empty()).unwrap_or(true)
+                        && !allows_external
+                        && !has_targets
                     {
                         return Err(format!(
-                            "menu '{}' requires non-empty 'commands' for watchdog",
+                            "menu '{}' requires non-empty 'commands' (or 'unit' / 'container') for watchdog",
                             m.id
                         ));
                     }
@@ -194,6 +821,13 @@ pub(crate) fn validate_app_config(cfg: &AppConfig) -> Result<(), String> {
             }
         }
     }
+    if let Some(retry) = &cfg.auth_retry {
+        crate::chi_core::watches::Watch::parse("", &retry.detect)
+            .map_err(|e| format!("auth_retry.detect: {e}"))?;
+    }
+    if let Some(theme) = &cfg.theme {
+        crate::theme::Theme::from_config(theme).map_err(|e| format!("theme: {e}"))?;
+    }
     Ok(())
 }
 
@@ -201,6 +835,24 @@ pub(crate) fn validate_app_config(cfg: &AppConfig) -> Result<(), String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn animation_switches_parse_from_yaml() {
+        let cfg: AppConfig =
+            serde_yaml::from_str("animations: off\nbanner_animation: wave\nmenu: []\n").unwrap();
+        assert!(cfg.animations_off());
+        assert_eq!(cfg.banner_animation(), "wave");
+        let cfg: AppConfig =
+            serde_yaml::from_str("animations: false\nbanner_animation: bogus\nmenu: []\n").unwrap();
+        assert!(cfg.animations_off());
+        assert_eq!(cfg.banner_animation(), "matrix");
+        let cfg: AppConfig = serde_yaml::from_str("menu: []\n").unwrap();
+        assert!(!cfg.animations_off());
+        assert!(!cfg.splash);
+        assert!(!cfg.reduced_motion);
+        let cfg: AppConfig = serde_yaml::from_str("reduced_motion: true\nmenu: []\n").unwrap();
+        assert!(cfg.reduced_motion && !cfg.animations_off());
+    }
+
     #[test]
     fn validate_detects_duplicate_ids() {
         let cfg = AppConfig {
@@ -223,6 +875,31 @@ mod tests {
         assert!(err.contains("duplicate menu id"));
     }
 
+    #[test]
+    fn validate_checks_empty_action_targets() {
+        let item = |id: &str, target: &str| MenuItem {
+            id: id.into(),
+            title: id.into(),
+            empty_action: Some(EmptyActionSpec {
+                item: target.into(),
+                key: Some("n".into()),
+                label: None,
+            }),
+            ..Default::default()
+        };
+        let cfg = AppConfig {
+            menu: vec![item("a", "b"), item("b", "a")],
+            ..Default::default()
+        };
+        assert!(validate_app_config(&cfg).is_ok());
+        let cfg = AppConfig {
+            menu: vec![item("a", "missing")],
+            ..Default::default()
+        };
+        let err = validate_app_config(&cfg).unwrap_err();
+        assert!(err.contains("unknown item 'missing'"), "{err}");
+    }
+
     #[test]
     fn validate_panel_requires_content() {
         let cfg = AppConfig {
@@ -238,4 +915,24 @@ mod tests {
         let err = validate_app_config(&cfg).unwrap_err();
         assert!(err.contains("must specify at least one"));
     }
+
+    #[test]
+    fn validate_watchdog_accepts_units_and_containers() {
+        let cfg: AppConfig = serde_yaml::from_str(
+            "menu:\n  - id: web\n    title: Web\n    widget: watchdog\n    unit: nginx.service\n    container: [api, worker]\n",
+        )
+        .unwrap();
+        validate_app_config(&cfg).unwrap();
+        let targets = crate::widgets::watchdog::targets::from_fields(
+            cfg.menu[0].unit.as_ref(),
+            cfg.menu[0].container.as_ref(),
+        );
+        assert_eq!(targets.len(), 3);
+        let mut empty = cfg.clone();
+        empty.menu[0].unit = None;
+        empty.menu[0].container = Some(serde_json::json!([]));
+        assert!(validate_app_config(&empty)
+            .unwrap_err()
+            .contains("requires non-empty 'commands'"));
+    }
 }
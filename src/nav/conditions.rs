@@ -0,0 +1,200 @@
+use crate::chi_core::envelope::Envelope;
+use crate::model::MenuItem;
+use crate::nav::keys::menu_key;
+use crate::ui::LoadOutcome;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+// `when_cmd:` / `enabled_cmd:` on a menu item: a quick command that passes on exit 0, unless
+// it prints a JSON envelope with `ok: false`. A failing `when_cmd` hides the item, a failing
+// `enabled_cmd` greys it out and Enter says why. They run in the background when a config
+// loads, on refresh ('r' in the menu) and after a login; until the first answer the item is
+// hidden / shown as "checking…", later runs keep the last answer until theirs arrives.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    When,
+    Enabled,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Outcome {
+    Pending,
+    Passed,
+    Failed(String),
+}
+
+#[derive(Debug, Default)]
+pub struct Conditions {
+    // Bumped when another config loads; answers for an earlier one are dropped
+    run: u64,
+    results: HashMap<(String, Kind), Outcome>,
+}
+
+// Why a command answer fails the condition (None: it passes)
+fn failure(outcome: Result<LoadOutcome, String>) -> Option<String> {
+    match outcome {
+        Ok(LoadOutcome::Fallback(JsonValue::String(out))) => {
+            let v: JsonValue = serde_json::from_str(&out).ok()?;
+            let env = Envelope::parse(&v).ok()?;
+            if env.ok != Some(false) {
+                return None;
+            }
+            Some(env.message().unwrap_or("ok: false").to_string())
+        }
+        Ok(_) => None,
+        // The command's own stderr says more than "Command failed (exit 1): …"
+        Err(e) => {
+            let mut lines = e.lines().filter(|l| !l.trim().is_empty());
+            let first = lines.next().unwrap_or("failed");
+            Some(lines.next().unwrap_or(first).trim().to_string())
+        }
+    }
+}
+
+impl Conditions {
+    // Forget the answers of the previous config
+    pub fn reset(&mut self) {
+        self.run += 1;
+        self.results.clear();
+    }
+
+    pub fn run(&self) -> u64 {
+        self.run
+    }
+
+    // (menu key, kind, command) of every condition in `menu`; unanswered ones are pending
+    pub fn start(&mut self, menu: &[MenuItem]) -> Vec<(String, Kind, String)> {
+        let mut jobs = Vec::new();
+        for mi in menu {
+            for (kind, cmd) in [(Kind::When, &mi.when_cmd), (Kind::Enabled, &mi.enabled_cmd)] {
+                if let Some(cmd) = cmd {
+                    let key = menu_key(mi);
+                    self.results
+                        .entry((key.clone(), kind))
+                        .or_insert(Outcome::Pending);
+                    jobs.push((key, kind, cmd.clone()));
+                }
+            }
+        }
+        jobs
+    }
+
+    pub fn done(
+        &mut self,
+        run: u64,
+        key: String,
+        kind: Kind,
+        outcome: Result<LoadOutcome, String>,
+    ) {
+        if run != self.run {
+            return;
+        }
+        let outcome = match failure(outcome) {
+            Some(why) => Outcome::Failed(why),
+            None => Outcome::Passed,
+        };
+        self.results.insert((key, kind), outcome);
+    }
+
+    fn outcome(&self, mi: &MenuItem, kind: Kind) -> Option<&Outcome> {
+        let cmd = match kind {
+            Kind::When => &mi.when_cmd,
+            Kind::Enabled => &mi.enabled_cmd,
+        };
+        cmd.as_ref()?;
+        Some(
+            self.results
+                .get(&(menu_key(mi), kind))
+                .unwrap_or(&Outcome::Pending),
+        )
+    }
+
+    pub fn shown(&self, mi: &MenuItem) -> bool {
+        matches!(self.outcome(mi, Kind::When), None | Some(Outcome::Passed))
+    }
+
+    // Label of a greyed-out item: "checking…" or "disabled"
+    pub fn disabled_label(&self, mi: &MenuItem) -> Option<&'static str> {
+        match self.outcome(mi, Kind::Enabled)? {
+            Outcome::Passed => None,
+            Outcome::Pending => Some("checking…"),
+            Outcome::Failed(_) => Some("disabled"),
+        }
+    }
+
+    // Why Enter on the item does nothing (hidden items can still be reached by id)
+    pub fn refusal(&self, mi: &MenuItem) -> Option<String> {
+        if !self.shown(mi) {
+            return Some(format!("{} is not available here", mi.title));
+        }
+        match self.outcome(mi, Kind::Enabled)? {
+            Outcome::Passed => None,
+            Outcome::Pending => Some(format!("{} is still being checked", mi.title)),
+            Outcome::Failed(why) => Some(format!("{} is disabled — {why}", mi.title)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_show_hide_and_disable_items() {
+        let deploy = MenuItem {
+            id: "deploy".into(),
+            title: "Deploy".into(),
+            when_cmd: Some("app whoami".into()),
+            enabled_cmd: Some("app can-deploy".into()),
+            ..Default::default()
+        };
+        let plain = MenuItem {
+            id: "logs".into(),
+            title: "Logs".into(),
+            ..Default::default()
+        };
+        let mut c = Conditions::default();
+        let jobs = c.start(&[deploy.clone(), plain.clone()]);
+        assert_eq!(jobs.len(), 2);
+        assert!(!c.shown(&deploy) && c.shown(&plain));
+        assert_eq!(c.disabled_label(&deploy), Some("checking…"));
+        assert!(c.refusal(&plain).is_none());
+
+        let run = c.run();
+        let text = |s: &str| Ok(LoadOutcome::Fallback(JsonValue::String(s.into())));
+        c.done(run, "menu:deploy".into(), Kind::When, text("alice"));
+        c.done(
+            run,
+            "menu:deploy".into(),
+            Kind::Enabled,
+            text(r#"{"ok": false, "error": {"message": "frozen until Monday"}}"#),
+        );
+        assert!(c.shown(&deploy));
+        assert_eq!(c.disabled_label(&deploy), Some("disabled"));
+        assert_eq!(
+            c.refusal(&deploy).as_deref(),
+            Some("Deploy is disabled — frozen until Monday")
+        );
+
+        // A refresh keeps the answer until the new one; a failing exit hides the item
+        c.start(std::slice::from_ref(&deploy));
+        assert!(c.shown(&deploy));
+        c.done(
+            run,
+            "menu:deploy".into(),
+            Kind::When,
+            Err("Command failed (exit 1): app whoami\nnot logged in\n".into()),
+        );
+        assert_eq!(
+            c.refusal(&deploy).as_deref(),
+            Some("Deploy is not available here")
+        );
+
+        // Answers for a previous config are dropped
+        c.reset();
+        c.start(std::slice::from_ref(&deploy));
+        c.done(run, "menu:deploy".into(), Kind::When, text("alice"));
+        assert!(!c.shown(&deploy));
+    }
+}
@@ -1,7 +1,78 @@
 use crate::nav::keys::{child_key, menu_key};
 use crate::ui::{is_header, AppState, FlatNode};
+use std::cell::RefCell;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-pub fn flatten_nodes(state: &AppState) -> Vec<FlatNode> {
+static GENERATION: AtomicU64 = AtomicU64::new(1);
+
+fn next_generation() -> u64 {
+    GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+// State the flattened menu is built from (config, children, expanded set). Any mutable
+// access stamps a new generation, which invalidates the cached rows.
+pub struct Tracked<T> {
+    value: T,
+    generation: u64,
+}
+
+impl<T> Tracked<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            generation: next_generation(),
+        }
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<T: Default> Default for Tracked<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.generation = next_generation();
+        &mut self.value
+    }
+}
+
+pub type FlatCache = RefCell<Option<([u64; 4], Rc<Vec<FlatNode>>)>>;
+
+// Flattened rows, cached until the state they were built from changes: key presses and
+// frames reuse the same list instead of walking (and cloning) the tree each time
+pub fn flatten_nodes(state: &AppState) -> Rc<Vec<FlatNode>> {
+    let stamp = [
+        state.config.generation(),
+        state.children.generation(),
+        state.expanded.generation(),
+        state.conditions.generation(),
+    ];
+    if let Some((cached, nodes)) = &*state.flat_cache.borrow() {
+        if *cached == stamp {
+            return nodes.clone();
+        }
+    }
+    let nodes = Rc::new(build(state));
+    *state.flat_cache.borrow_mut() = Some((stamp, nodes.clone()));
+    nodes
+}
+
+fn build(state: &AppState) -> Vec<FlatNode> {
     fn append_children(out: &mut Vec<FlatNode>, state: &AppState, parent_key: &str, depth: usize) {
         if let Some(children) = state.children.get(parent_key) {
             for (ci, val) in children.iter().enumerate() {
@@ -9,7 +80,8 @@ pub fn flatten_nodes(state: &AppState) -> Vec<FlatNode> {
                 out.push(FlatNode::Child {
                     key: key.clone(),
                     depth,
-                    val: val.clone(),
+                    parent: parent_key.to_string(),
+                    index: ci,
                 });
                 // Recurse into children when this node is expanded, regardless of how
                 // the children are provided (static inline, lazy or autoload).
@@ -22,6 +94,10 @@ pub fn flatten_nodes(state: &AppState) -> Vec<FlatNode> {
 
     let mut out = Vec::new();
     for (i, mi) in state.config.menu.iter().enumerate() {
+        // `when_cmd:` that has not passed
+        if !state.conditions.shown(mi) {
+            continue;
+        }
         if is_header(mi) {
             out.push(FlatNode::Header { idx: i, depth: 0 });
             continue;
@@ -60,7 +136,7 @@ mod tests {
             expand_on_enter: Some(false),
             ..Default::default()
         };
-        state.config = AppConfig {
+        *state.config = AppConfig {
             header: Some("Test".into()),
             menu: vec![mi_header.clone(), mi_lazy.clone()],
             ..Default::default()
@@ -92,5 +168,23 @@ mod tests {
         assert!(matches!(nodes[1], FlatNode::Menu { .. }));
         assert!(matches!(nodes[2], FlatNode::Child { .. }));
         assert!(matches!(nodes[3], FlatNode::Child { .. }));
+        assert_eq!(nodes[3].val(&state).unwrap()["id"], "gc1");
+    }
+
+    #[test]
+    fn cached_rows_are_rebuilt_after_changes() {
+        let mut state = make_state();
+        let first = flatten_nodes(&state);
+        assert!(Rc::ptr_eq(&first, &flatten_nodes(&state)));
+
+        let key = menu_key(&state.config.menu[1]);
+        state.expanded.remove(&key);
+        let collapsed = flatten_nodes(&state);
+        assert_eq!(collapsed.len(), 2);
+        state.expanded.insert(key.clone());
+        state
+            .children
+            .insert(key, vec![json!({"id": "c2", "title": "Other"})]);
+        assert_eq!(flatten_nodes(&state).len(), 3);
     }
 }
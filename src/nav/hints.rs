@@ -0,0 +1,108 @@
+// Quick-jump hint mode: 'f' labels every visible target with two letters,
+// typing a label jumps to (and activates) that target.
+
+// Home row first so the most common labels are the easiest to type
+const HINT_ALPHABET: &[u8] = b"asdfghjklqwertyuiopzxcvbnm";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HintTarget {
+    // Flattened index of a left menu node
+    Menu(usize),
+    // Item index in a Pane B menu widget
+    PaneBMenu(usize),
+    // Subpane index of a watchdog in Pane B
+    WatchdogPane(usize),
+    // Item index of a result list in Pane B
+    ResultItem(usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum HintStep {
+    // Typed prefix still matches several labels
+    Pending,
+    Jump(HintTarget),
+    // Typed label matches nothing; hint mode ends
+    Miss,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HintMode {
+    pub targets: Vec<(String, HintTarget)>,
+    pub typed: String,
+}
+
+impl HintMode {
+    pub fn new(targets: Vec<HintTarget>) -> Self {
+        let labels = hint_labels(targets.len());
+        Self {
+            targets: labels.into_iter().zip(targets).collect(),
+            typed: String::new(),
+        }
+    }
+
+    // Label for a target, hidden once it no longer matches the typed prefix
+    pub fn label_for(&self, target: HintTarget) -> Option<&str> {
+        self.targets
+            .iter()
+            .find(|(label, t)| *t == target && label.starts_with(&self.typed))
+            .map(|(label, _)| label.as_str())
+    }
+
+    pub fn push(&mut self, c: char) -> HintStep {
+        self.typed.push(c.to_ascii_lowercase());
+        let mut matching = self
+            .targets
+            .iter()
+            .filter(|(label, _)| label.starts_with(&self.typed));
+        match (matching.next(), matching.next()) {
+            (Some((label, t)), None) if *label == self.typed => HintStep::Jump(*t),
+            (Some(_), _) => HintStep::Pending,
+            (None, _) => HintStep::Miss,
+        }
+    }
+}
+
+// Two-letter labels for `n` targets (capped at the alphabet size squared)
+pub(crate) fn hint_labels(n: usize) -> Vec<String> {
+    let k = HINT_ALPHABET.len();
+    (0..n.min(k * k))
+        .map(|i| {
+            let a = HINT_ALPHABET[i / k] as char;
+            let b = HINT_ALPHABET[i % k] as char;
+            format!("{a}{b}")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_are_unique_two_letter_codes() {
+        let labels = hint_labels(40);
+        assert_eq!(labels.len(), 40);
+        assert!(labels.iter().all(|l| l.len() == 2));
+        let mut dedup = labels.clone();
+        dedup.sort();
+        dedup.dedup();
+        assert_eq!(dedup.len(), labels.len());
+        assert_eq!(&labels[..2], &["aa".to_string(), "as".to_string()]);
+    }
+
+    #[test]
+    fn typing_a_label_jumps_and_filters() {
+        let mut hm = HintMode::new(vec![
+            HintTarget::Menu(0),
+            HintTarget::Menu(3),
+            HintTarget::WatchdogPane(1),
+        ]);
+        assert_eq!(hm.push('a'), HintStep::Pending);
+        assert_eq!(hm.label_for(HintTarget::Menu(3)), Some("as"));
+        assert_eq!(hm.push('S'), HintStep::Jump(HintTarget::Menu(3)));
+
+        let mut hm = HintMode::new(vec![HintTarget::Menu(0)]);
+        assert_eq!(hm.push('z'), HintStep::Miss);
+        assert_eq!(hm.label_for(HintTarget::Menu(0)), None);
+    }
+}
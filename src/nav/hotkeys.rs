@@ -0,0 +1,181 @@
+use crate::model::MenuItem;
+
+// `hotkey: d` on a menu item: pressing the key on the screen (menu focused, or a pane
+// without a widget of its own, and nothing being edited) opens the item directly. A key may
+// not be taken twice on one screen nor shadow a menu key or a `verbs:` key; both are config
+// errors when the screen loads. The key is shown after the item's title. `verbs:` keys may
+// not be menu keys either (the menu's meaning would win and the verb never run).
+
+// A global key binding: how the cheat-sheet writes it, the plain characters its dispatcher
+// arm takes on the menu (so `hotkey:`/`verbs:` may not use them) and what it does
+pub struct GlobalKey {
+    pub keys: &'static str,
+    pub chars: &'static str,
+    pub action: &'static str,
+}
+
+const fn key(keys: &'static str, chars: &'static str, action: &'static str) -> GlobalKey {
+    GlobalKey {
+        keys,
+        chars,
+        action,
+    }
+}
+
+// Every key ui.rs handles before `hotkey:`/`verbs:`, in cheat-sheet order
+pub const GLOBAL_KEYS: &[GlobalKey] = &[
+    key("↑/↓, PgUp/PgDn, Home/End", "", "move / scroll"),
+    key("Enter", "", "open item / edit field"),
+    key("Tab / Shift+Tab", "", "switch panel pane"),
+    key("f", "f", "quick-jump labels"),
+    key("0-9", "0123456789", "jump to items marked [[n]]"),
+    key("r", "r", "refresh the focused pane or menu node"),
+    key("space", " ", "actions of the focused pane"),
+    key("/, Ctrl+K", "/", "command palette"),
+    key(":", ":", "command line with completion from the CLI schema"),
+    key("n", "n", "note on the selected item or row"),
+    key("=", "=", "watch expression on the result"),
+    key("[ / ]", "[]", "previous / next page of the selected list"),
+    key("t / T", "tT", "schedule item / scheduled jobs"),
+    key(
+        "H",
+        "H",
+        "results of earlier commands (re-open, diff, export)",
+    ),
+    key("W", "W", "switch workspace (with `workspaces:`)"),
+    key("O", "O", "toggle offline mode (with `cache:`)"),
+    key("s", "s", "submit the form in Pane B"),
+    key("w / j", "wj", "toggle wrap / raw JSON of the result view"),
+    key("F1-F12", "", "switch tab"),
+    key(
+        "Ctrl+Shift+←/→",
+        "",
+        "move the current tab (for this session)",
+    ),
+    key("Esc / Ctrl+X", "", "stop the running stream"),
+    key(
+        "Ctrl+C",
+        "c",
+        "copy pane content (JSON / YAML / CSV / markdown)",
+    ),
+    key("Ctrl+E", "", "export screen as HTML report"),
+    key("Ctrl+P", "", "save pane as PNG and copy the image"),
+    key("Ctrl+D", "", "debug console (filter, copy, resize)"),
+    key("Ctrl+T", "", "cycle theme presets"),
+    key("q", "q", "quit"),
+];
+
+// A key the menu already uses
+pub fn reserved(c: char) -> bool {
+    GLOBAL_KEYS.iter().any(|k| k.chars.contains(c))
+}
+
+// The item's hotkey character (None: none, or not a single character)
+pub fn key_of(mi: &MenuItem) -> Option<char> {
+    let mut chars = mi.hotkey.as_deref()?.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+// Index of the item opened by `c`
+pub fn find(menu: &[MenuItem], c: char) -> Option<usize> {
+    menu.iter().position(|m| key_of(m) == Some(c))
+}
+
+// Hotkeys of one screen's menu: single characters, each used once and by nothing else
+pub fn check(menu: &[MenuItem]) -> Result<(), String> {
+    for (i, m) in menu.iter().enumerate() {
+        if let Some(v) = m.verbs.iter().flatten().find(|v| reserved(v.key)) {
+            return Err(format!(
+                "menu '{}': verb key '{}' is a built-in menu key",
+                m.id, v.key
+            ));
+        }
+        let Some(raw) = m.hotkey.as_deref() else {
+            continue;
+        };
+        let Some(c) = key_of(m) else {
+            return Err(format!(
+                "menu '{}': hotkey must be a single character, not '{raw}'",
+                m.id
+            ));
+        };
+        if reserved(c) {
+            return Err(format!(
+                "menu '{}': hotkey '{c}' is a built-in menu key",
+                m.id
+            ));
+        }
+        if let Some(other) = menu[..i].iter().find(|o| key_of(o) == Some(c)) {
+            return Err(format!(
+                "menu '{}': hotkey '{c}' is already used by '{}'",
+                m.id, other.id
+            ));
+        }
+        let verb = menu
+            .iter()
+            .find(|o| o.verbs.iter().flatten().any(|v| v.key == c));
+        if let Some(other) = verb {
+            return Err(format!(
+                "menu '{}': hotkey '{c}' is a verb key of '{}'",
+                m.id, other.id
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, hotkey: Option<&str>) -> MenuItem {
+        MenuItem {
+            id: id.into(),
+            title: id.into(),
+            hotkey: hotkey.map(Into::into),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hotkeys_are_found_and_conflicts_rejected() {
+        let menu = vec![
+            item("pods", Some("p")),
+            item("deploy", Some("D")),
+            item("logs", None),
+        ];
+        assert!(check(&menu).is_ok());
+        assert_eq!(find(&menu, 'D'), Some(1));
+        assert_eq!(find(&menu, 'd'), None);
+
+        let dup = vec![item("a", Some("x")), item("b", Some("x"))];
+        assert_eq!(
+            check(&dup).unwrap_err(),
+            "menu 'b': hotkey 'x' is already used by 'a'"
+        );
+        assert!(check(&[item("a", Some("q"))])
+            .unwrap_err()
+            .contains("built-in menu key"));
+        assert!(check(&[item("a", Some("ab"))])
+            .unwrap_err()
+            .contains("single character"));
+
+        let verb = |key: char| MenuItem {
+            verbs: Some(vec![crate::model::VerbSpec {
+                key,
+                title: "Logs".into(),
+                command: "app logs {id}".into(),
+                confirm: false,
+            }]),
+            ..item("pods", None)
+        };
+        assert!(check(&[verb('l')]).is_ok());
+        assert_eq!(
+            check(&[verb('r')]).unwrap_err(),
+            "menu 'pods': verb key 'r' is a built-in menu key"
+        );
+    }
+}
@@ -1,2 +1,8 @@
+pub mod conditions;
 pub mod flatten;
+pub mod hints;
+pub mod hotkeys;
 pub mod keys;
+pub mod pagination;
+pub mod repeat;
+pub mod tree;
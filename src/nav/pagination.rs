@@ -0,0 +1,111 @@
+use crate::nav::keys::child_key;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+// Page state of a loaded child list whose command answered with `data.pagination` (or a
+// `db:` source with more rows). It is kept next to the list, keyed like `state.children`,
+// so the list holds only real entries; `[` / `]` load the previous / next page and the menu
+// footer shows where the list is.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pagination {
+    pub current: Option<i64>,
+    pub total_pages: Option<i64>,
+    pub total_items: Option<i64>,
+    pub prev_cmd: Option<String>,
+    pub next_cmd: Option<String>,
+}
+
+impl Pagination {
+    // `{current_page, total_pages, total_items, prev_page_cmd, next_page_cmd}`
+    pub fn from_json(v: &JsonValue) -> Self {
+        let int = |k: &str| v.get(k).and_then(JsonValue::as_i64);
+        let cmd = |k: &str| v.get(k).and_then(JsonValue::as_str).map(String::from);
+        Self {
+            current: int("current_page"),
+            total_pages: int("total_pages"),
+            total_items: int("total_items"),
+            prev_cmd: cmd("prev_page_cmd"),
+            next_cmd: cmd("next_page_cmd"),
+        }
+    }
+
+    // Command of the page before (`forward` false) or after this one
+    pub fn command(&self, forward: bool) -> Option<&str> {
+        if forward {
+            self.next_cmd.as_deref()
+        } else {
+            self.prev_cmd.as_deref()
+        }
+    }
+
+    // "Page 2/5 · 93 items · [ prev  ] next"
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        match (self.current, self.total_pages) {
+            (Some(c), Some(t)) => parts.push(format!("Page {c}/{t}")),
+            (Some(c), None) => parts.push(format!("Page {c}")),
+            _ => {}
+        }
+        if let Some(n) = self.total_items {
+            parts.push(format!("{n} items"));
+        }
+        let keys: Vec<&str> = [
+            self.prev_cmd.as_ref().map(|_| "[ prev"),
+            self.next_cmd.as_ref().map(|_| "] next"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !keys.is_empty() {
+            parts.push(keys.join("  "));
+        }
+        parts.join(" · ")
+    }
+}
+
+// The loaded child whose key is `key` (the owner of a nested list)
+pub fn child_value(children: &HashMap<String, Vec<JsonValue>>, key: &str) -> Option<JsonValue> {
+    children.iter().find_map(|(parent, list)| {
+        list.iter()
+            .enumerate()
+            .find(|(i, v)| child_key(parent, v, *i) == key)
+            .map(|(_, v)| v.clone())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn page_metadata_reads_commands_and_labels_the_footer() {
+        let p = Pagination::from_json(&json!({
+            "current_page": 2,
+            "total_pages": 5,
+            "total_items": 93,
+            "prev_page_cmd": "app list --page 1",
+            "next_page_cmd": "app list --page 3"
+        }));
+        assert_eq!(p.command(true), Some("app list --page 3"));
+        assert_eq!(p.command(false), Some("app list --page 1"));
+        assert_eq!(p.label(), "Page 2/5 · 93 items · [ prev  ] next");
+        let last = Pagination::from_json(&json!({"current_page": 4, "prev_page_cmd": "x"}));
+        assert_eq!(last.command(true), None);
+        assert_eq!(last.label(), "Page 4 · [ prev");
+
+        let mut children = HashMap::new();
+        children.insert(
+            "menu:groups".to_string(),
+            vec![
+                json!({"id": "a", "command": "app a"}),
+                json!({"title": "b"}),
+            ],
+        );
+        assert_eq!(
+            child_value(&children, "menu:groups/#1"),
+            Some(json!({"title": "b"}))
+        );
+        assert!(child_value(&children, "menu:groups/c").is_none());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::model::KeybindingsConfig;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use std::time::{Duration, Instant};
+
+// Up/Down acceleration: a key pressed again within the repeat window (a held key) counts
+// as a repeat; after `accelerate_after` repeats each event moves by the next step
+const DEFAULT_AFTER: u32 = 10;
+const DEFAULT_STEPS: [usize; 2] = [5, 25];
+const DEFAULT_WINDOW_MS: u64 = 80;
+
+#[derive(Default)]
+pub struct KeyRepeat {
+    last: Option<(KeyCode, Instant)>,
+    count: u32,
+}
+
+impl KeyRepeat {
+    // Rows to move for this event (1 for other keys and unaccelerated presses)
+    pub fn steps(
+        &mut self,
+        key: &KeyEvent,
+        now: Instant,
+        cfg: Option<&KeybindingsConfig>,
+    ) -> usize {
+        if !matches!(key.code, KeyCode::Up | KeyCode::Down) {
+            self.last = None;
+            self.count = 0;
+            return 1;
+        }
+        let window = Duration::from_millis(
+            cfg.and_then(|c| c.repeat_window_ms)
+                .unwrap_or(DEFAULT_WINDOW_MS),
+        );
+        let repeated = key.kind == KeyEventKind::Repeat
+            || self
+                .last
+                .is_some_and(|(code, at)| code == key.code && now.duration_since(at) <= window);
+        self.count = if repeated { self.count + 1 } else { 0 };
+        self.last = Some((key.code, now));
+
+        let after = cfg
+            .and_then(|c| c.accelerate_after)
+            .unwrap_or(DEFAULT_AFTER);
+        if after == 0 {
+            return 1;
+        }
+        let steps = cfg
+            .and_then(|c| c.accelerate_steps.as_deref())
+            .unwrap_or(&DEFAULT_STEPS);
+        match (self.count / after) as usize {
+            0 => 1,
+            level => steps
+                .get(level.min(steps.len()).saturating_sub(1))
+                .copied()
+                .unwrap_or(1)
+                .max(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn held_keys_accelerate_and_reset() {
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        let mut r = KeyRepeat::default();
+        let t0 = Instant::now();
+        let at = |i: u64| t0 + Duration::from_millis(i * 30);
+        let moved: Vec<usize> = (0..45).map(|i| r.steps(&down, at(i), None)).collect();
+        assert_eq!(moved[..10], [1; 10]);
+        assert_eq!(moved[10..20], [5; 10]);
+        assert_eq!(moved[20..], [25; 25]);
+        // A pause starts over
+        assert_eq!(r.steps(&down, at(45) + Duration::from_secs(1), None), 1);
+
+        let cfg = KeybindingsConfig {
+            accelerate_after: Some(0),
+            ..Default::default()
+        };
+        assert!((0..30).all(|i| r.steps(&down, at(i), Some(&cfg)) == 1));
+    }
+}
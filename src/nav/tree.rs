@@ -0,0 +1,194 @@
+use crate::model::{AppConfig, MenuItem};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+// Resolved menu hierarchy for `chi-tui tree` and the in-app `menu_tree` page
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub(crate) struct TreeNode {
+    pub id: String,
+    pub title: String,
+    // header | panel | markdown | watchdog | lazy | autoload | command | group | item | placeholder
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TreeNode>,
+}
+
+pub(crate) fn menu_tree(cfg: &AppConfig) -> Vec<TreeNode> {
+    cfg.menu.iter().map(menu_node).collect()
+}
+
+fn menu_node(mi: &MenuItem) -> TreeNode {
+    let kind = match mi.widget.as_deref() {
+        Some("header") => "header",
+        Some("panel") => "panel",
+        Some("markdown") => "markdown",
+        Some("runbook") => "runbook",
+        Some("board") => "board",
+        Some("tree") => "tree",
+        Some("processes") => "processes",
+        Some("ports") => "ports",
+        Some("http_checks") => "http_checks",
+        Some("promql") => "promql",
+        Some("log_viewer") => "log_viewer",
+        Some("watchdog") => "watchdog",
+        Some("lazy_items") => "lazy",
+        Some("autoload_items") => "autoload",
+        _ if mi.children.as_ref().is_some_and(|c| !c.is_empty()) => "group",
+        _ if mi.children_from_glob.is_some() => "glob",
+        _ if mi.command.is_some() => "command",
+        _ => "item",
+    };
+    let mut children: Vec<TreeNode> = mi
+        .children
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, v)| value_node(v, i))
+        .collect();
+    if matches!(kind, "lazy" | "autoload") {
+        children.push(placeholder(mi.command.as_deref()));
+    }
+    TreeNode {
+        id: mi.id.clone(),
+        title: mi.title.clone(),
+        kind: kind.to_string(),
+        command: mi.command.clone(),
+        children,
+    }
+}
+
+// Statically declared child (inline `children:` entries, possibly nested)
+fn value_node(v: &JsonValue, idx: usize) -> TreeNode {
+    let command = v
+        .get("command")
+        .and_then(|s| s.as_str())
+        .map(|s| s.to_string());
+    let runtime = crate::ui::is_lazy_value(v) || crate::ui::is_autoload_value(v);
+    let mut children: Vec<TreeNode> = v
+        .get("children")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, c)| value_node(c, i))
+        .collect();
+    let kind = if crate::ui::is_lazy_value(v) {
+        "lazy"
+    } else if crate::ui::is_autoload_value(v) {
+        "autoload"
+    } else if let Some(w) = v.get("widget").and_then(|s| s.as_str()) {
+        match w {
+            "markdown" | "watchdog" | "panel" => w,
+            _ => "item",
+        }
+    } else if !children.is_empty() {
+        "group"
+    } else if command.is_some() {
+        "command"
+    } else {
+        "item"
+    };
+    if runtime {
+        children.push(placeholder(command.as_deref()));
+    }
+    TreeNode {
+        id: v
+            .get("id")
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("#{idx}")),
+        title: crate::ui::title_from_value(v),
+        kind: kind.to_string(),
+        command,
+        children,
+    }
+}
+
+// Stand-in for items that are only known after running the command
+fn placeholder(command: Option<&str>) -> TreeNode {
+    TreeNode {
+        id: String::new(),
+        title: "(items loaded at runtime)".to_string(),
+        kind: "placeholder".to_string(),
+        command: command.map(|s| s.to_string()),
+        children: Vec::new(),
+    }
+}
+
+// Indented text listing: `title [kind] (id)  $ command`
+pub(crate) fn render_text(nodes: &[TreeNode]) -> String {
+    fn walk(out: &mut String, nodes: &[TreeNode], depth: usize) {
+        for n in nodes {
+            out.push_str(&"  ".repeat(depth));
+            out.push_str(&n.title);
+            out.push_str(&format!(" [{}]", n.kind));
+            if !n.id.is_empty() {
+                out.push_str(&format!(" ({})", n.id));
+            }
+            if let Some(cmd) = &n.command {
+                out.push_str(&format!("  $ {cmd}"));
+            }
+            out.push('\n');
+            walk(out, &n.children, depth + 1);
+        }
+    }
+    let mut out = String::new();
+    walk(&mut out, nodes, 0);
+    out
+}
+
+pub(crate) fn to_json(nodes: &[TreeNode]) -> JsonValue {
+    serde_json::to_value(nodes).unwrap_or(JsonValue::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn tree_includes_static_children_and_runtime_placeholders() {
+        let cfg = AppConfig {
+            menu: vec![
+                MenuItem {
+                    id: "tools".into(),
+                    title: "Tools".into(),
+                    children: Some(vec![
+                        json!({"id": "hello", "title": "Hello", "command": "app hello"}),
+                        json!({"title": "Jobs", "widget": "lazy_items", "command": "app jobs"}),
+                    ]),
+                    ..Default::default()
+                },
+                MenuItem {
+                    id: "items".into(),
+                    title: "Items".into(),
+                    widget: Some("autoload_items".into()),
+                    command: Some("app list".into()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let tree = menu_tree(&cfg);
+        assert_eq!(tree[0].kind, "group");
+        assert_eq!(tree[0].children[1].kind, "lazy");
+        assert_eq!(tree[0].children[1].id, "#1");
+        assert_eq!(tree[0].children[1].children[0].kind, "placeholder");
+        assert_eq!(tree[1].children[0].command.as_deref(), Some("app list"));
+
+        let text = render_text(&tree);
+        assert_eq!(
+            text.lines().take(3).collect::<Vec<_>>(),
+            vec![
+                "Tools [group] (tools)",
+                "  Hello [command] (hello)  $ app hello",
+                "  Jobs [lazy] (#1)  $ app jobs",
+            ]
+        );
+        let v = to_json(&tree);
+        assert_eq!(v[1]["children"][0]["kind"], "placeholder");
+        assert!(v[0].get("command").is_none());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::services::bus::Reply;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// `auth:` support: a background session check, and the login command run either with
+// the terminal handed over (interactive) or in the background with its output shown
+// (device code: the URL and code the user types in a browser)
+
+pub type LoginOutput = Arc<Mutex<Vec<String>>>;
+
+pub fn device_code_mode(cfg: &crate::model::AuthConfig) -> bool {
+    cfg.mode.as_deref() == Some("device_code")
+}
+
+// A command failure caused by an expired/missing session: the configured exit code
+pub fn is_unauthenticated(cfg: &crate::model::AuthConfig, err: &str) -> bool {
+    cfg.unauthenticated_exit_code.is_some()
+        && crate::services::cli_runner::failed_exit_code(err) == cfg.unauthenticated_exit_code
+}
+
+// `auth_retry:` the JSON a command printed (on stdout, or on stderr when it failed) matches
+// `detect`
+pub fn token_expired(
+    cfg: &crate::model::AuthRetryConfig,
+    output: Option<&serde_json::Value>,
+    err: Option<&str>,
+) -> bool {
+    let Ok(detect) = crate::chi_core::watches::Watch::parse("", &cfg.detect) else {
+        return false;
+    };
+    if output.is_some_and(|v| detect.holds(v)) {
+        return true;
+    }
+    let Some(err) = err else {
+        return false;
+    };
+    // A JSON error block after the exit status line, or a JSON line among the stderr
+    let block = err
+        .find('{')
+        .and_then(|i| serde_json::from_str::<serde_json::Value>(&err[i..]).ok());
+    block.iter().any(|v| detect.holds(v))
+        || err
+            .lines()
+            .filter_map(|l| serde_json::from_str::<serde_json::Value>(l.trim()).ok())
+            .any(|v| detect.holds(&v))
+}
+
+// Run the login command on the real terminal (caller suspends the TUI)
+pub fn run_interactive(cmdline: &str) -> Result<(), String> {
+    let parts = shlex::split(&crate::services::interpolate::env(cmdline))
+        .filter(|p| !p.is_empty())
+        .ok_or("failed to parse login command")?;
+    let status = Command::new(&parts[0])
+        .args(&parts[1..])
+        .status()
+        .map_err(|e| format!("{}: {e}", parts[0]))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("login exited with {status}"))
+    }
+}
+
+// Run the login command in the background; stdout and stderr lines land in the returned
+// buffer for the login dialog, completion arrives through `reply`
+pub fn spawn_device_login(cmdline: String, reply: Reply) -> LoginOutput {
+    let output: LoginOutput = Arc::new(Mutex::new(Vec::new()));
+    let lines = output.clone();
+    thread::spawn(move || {
+        let outcome = (|| -> Result<(), String> {
+            let parts = shlex::split(&crate::services::interpolate::env(&cmdline))
+                .filter(|p| !p.is_empty())
+                .ok_or("failed to parse login command")?;
+            let mut child = Command::new(&parts[0])
+                .args(&parts[1..])
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(|e| format!("{}: {e}", parts[0]))?;
+            let stderr = child.stderr.take().map(|err| {
+                let lines = lines.clone();
+                thread::spawn(move || {
+                    for l in BufReader::new(err).lines().map_while(Result::ok) {
+                        lines.lock().unwrap().push(l);
+                    }
+                })
+            });
+            if let Some(out) = child.stdout.take() {
+                for l in BufReader::new(out).lines().map_while(Result::ok) {
+                    lines.lock().unwrap().push(l);
+                }
+            }
+            if let Some(h) = stderr {
+                let _ = h.join();
+            }
+            let status = child.wait().map_err(|e| e.to_string())?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("login exited with {status}"))
+            }
+        })();
+        reply.send(outcome.map(|_| crate::ui::LoadOutcome::Fallback(serde_json::Value::Null)));
+    });
+    output
+}
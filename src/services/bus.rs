@@ -0,0 +1,116 @@
+use crate::app::Effect;
+use crate::ui::{AppState, LoadMsg, LoadOutcome};
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+
+// Routing of background results. Whoever starts a background job subscribes a handler first
+// and hands the returned `Reply` to the worker thread; the result comes back tagged with the
+// subscription's id and the main loop passes it to that handler. New async features bring
+// their own handler instead of adding a kind to a central match.
+
+pub type Handler = Box<dyn FnOnce(&mut AppState, Result<LoadOutcome, String>) -> Vec<Effect>>;
+
+#[derive(Default)]
+pub struct Subscriptions {
+    next_id: u64,
+    handlers: HashMap<u64, Handler>,
+}
+
+impl Subscriptions {
+    pub fn subscribe(&mut self, handler: Handler) -> u64 {
+        self.next_id += 1;
+        self.handlers.insert(self.next_id, handler);
+        self.next_id
+    }
+
+    pub fn take(&mut self, id: u64) -> Option<Handler> {
+        self.handlers.remove(&id)
+    }
+}
+
+// Where a worker sends its one result. `key` is the entry it clears from `state.loading`.
+pub struct Reply {
+    id: u64,
+    key: String,
+    tx: Sender<LoadMsg>,
+}
+
+impl Reply {
+    // False once the UI is gone
+    pub fn send(self, outcome: Result<LoadOutcome, String>) -> bool {
+        let msg = LoadMsg {
+            id: self.id,
+            key: self.key,
+            outcome,
+        };
+        self.tx.send(msg).is_ok()
+    }
+}
+
+// Register `handler` for one result; None (and nothing registered) without a result channel
+pub fn subscribe(
+    state: &mut AppState,
+    key: impl Into<String>,
+    handler: impl FnOnce(&mut AppState, Result<LoadOutcome, String>) -> Vec<Effect> + 'static,
+) -> Option<Reply> {
+    let tx = state.tx.clone()?;
+    let id = state.subscriptions.subscribe(Box::new(handler));
+    Some(Reply {
+        id,
+        key: key.into(),
+        tx,
+    })
+}
+
+// Hand a result to its handler; a result nobody subscribed to is dropped
+pub fn dispatch(state: &mut AppState, msg: LoadMsg) -> Vec<Effect> {
+    state.loading.remove(&msg.key);
+    match state.subscriptions.take(msg.id) {
+        Some(handler) => handler(state, msg.outcome),
+        None => {
+            state.dbg(format!("result {} ({}) has no subscriber", msg.id, msg.key));
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::mpsc;
+
+    #[test]
+    fn results_reach_the_handler_that_subscribed() {
+        let mut st = AppState::default();
+        assert!(subscribe(&mut st, "x", |_, _| Vec::new()).is_none());
+
+        let (tx, rx) = mpsc::channel();
+        st.tx = Some(tx);
+        st.loading.insert("menu:pods".into());
+        let first = subscribe(&mut st, "menu:pods", |st, outcome| {
+            st.last_error = outcome.err();
+            Vec::new()
+        })
+        .unwrap();
+        let second = subscribe(&mut st, "other", |st, outcome| {
+            if let Ok(LoadOutcome::Fallback(v)) = outcome {
+                st.status_text = v.as_str().map(String::from);
+            }
+            Vec::new()
+        })
+        .unwrap();
+
+        // Out of order: each lands with its own handler
+        assert!(second.send(Ok(LoadOutcome::Fallback(json!("done")))));
+        assert!(first.send(Err("boom".into())));
+        for msg in rx.try_iter() {
+            dispatch(&mut st, msg);
+        }
+        assert_eq!(st.status_text.as_deref(), Some("done"));
+        assert_eq!(st.last_error.as_deref(), Some("boom"));
+        assert!(st.loading.is_empty());
+        // Each handler runs once
+        assert!(st.subscriptions.take(1).is_none() && st.subscriptions.take(2).is_none());
+    }
+}
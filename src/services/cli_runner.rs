@@ -1,38 +1,68 @@
-use anyhow::{anyhow, Context, Result};
-use regex::Regex;
+use crate::chi_core::envelope::{self, Envelope};
+use anyhow::{anyhow, Result};
 use serde_json::Value as JsonValue;
-use std::io::{BufRead, BufReader};
-use std::process::{Command, Stdio};
-use std::sync::mpsc::Sender;
+use std::env;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::{collections::HashMap, env};
-
-fn expand_cmdline_env(cmdline: &str) -> String {
-    // Expand ${VAR} from environment; special-case ${APP_BIN}
-    // -> CHI_APP_BIN (quoted if contains whitespace) or default "example-app"
-    let re = Regex::new(r"\$\{([A-Z0-9_]+)\}").unwrap();
-    let env_map: HashMap<String, String> = env::vars().collect();
-    re.replace_all(cmdline, |caps: &regex::Captures| {
-        let key = &caps[1];
-        if key == "APP_BIN" {
-            if let Some(v) = env_map.get("CHI_APP_BIN") {
-                // Quote if contains whitespace to keep it a single arg in shlex::split
-                let needs_quote = v.chars().any(|c| c.is_whitespace());
-                if needs_quote {
-                    let escaped = v.replace('"', "\\\"");
-                    return format!("\"{escaped}\"");
-                }
-                return v.to_string();
-            }
-            return "example-app".to_string();
-        }
-        env_map.get(key).cloned().unwrap_or_default()
+use std::time::{Duration, Instant};
+
+// Error of a command whose program is not on PATH (its item is greyed out after it)
+pub fn not_found_error(program: &str) -> String {
+    format!("missing: {program} (not found on PATH)")
+}
+
+pub fn not_found_program(err: &str) -> Option<&str> {
+    err.lines()
+        .next()?
+        .strip_prefix("missing: ")?
+        .strip_suffix(" (not found on PATH)")
+}
+
+// Program a command line runs when it cannot be found; templated programs are not checked
+pub fn missing_program(cmdline: &str) -> Option<String> {
+    let expanded = crate::services::interpolate::env(cmdline);
+    let program = shlex::split(&expanded)?.into_iter().next()?;
+    if program.contains('{') || on_path(&program) {
+        return None;
+    }
+    Some(program)
+}
+
+fn on_path(program: &str) -> bool {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.is_file();
+    }
+    let exts: Vec<String> = if cfg!(windows) {
+        env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT".into())
+            .split(';')
+            .map(|e| e.trim_start_matches('.').to_string())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    env::var_os("PATH").is_some_and(|paths| {
+        env::split_paths(&paths).any(|dir| {
+            let candidate = dir.join(program);
+            candidate.is_file() || exts.iter().any(|e| candidate.with_extension(e).is_file())
+        })
     })
-    .to_string()
 }
 
 pub fn run_cmdline_to_json(cmdline: &str) -> Result<JsonValue> {
-    let expanded = expand_cmdline_env(cmdline);
+    let text = run_cmdline_to_text(cmdline)?;
+    serde_json::from_str(&text).map_err(|e| anyhow!(envelope::not_json(cmdline, &text, &e)))
+}
+
+// Stdout of a command line; a failure carries the exit code and stderr like run_cmdline_to_json
+pub fn run_cmdline_to_text(cmdline: &str) -> Result<String> {
+    let expanded = crate::services::interpolate::env(cmdline);
     let parts = shlex::split(&expanded).ok_or_else(|| anyhow!("Failed to parse command line"))?;
     if parts.is_empty() {
         return Err(anyhow!("Empty command line"));
@@ -42,24 +72,192 @@ pub fn run_cmdline_to_json(cmdline: &str) -> Result<JsonValue> {
     let output = Command::new(program)
         .args(args)
         .env("CHI_TUI_JSON", "1")
+        .env("CHI_TUI_ENVELOPE", envelope::VERSION.to_string())
         .output()
-        .with_context(|| format!("spawning {expanded}"))?;
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow!(not_found_error(program)),
+            _ => anyhow!(e).context(format!("spawning {expanded}")),
+        })?;
     if !output.status.success() {
         let err = String::from_utf8_lossy(&output.stderr).to_string();
-        return Err(anyhow!("Command failed: {}\n{}", cmdline, err));
+        return Err(anyhow!(
+            "{}\n{}",
+            failure_message(cmdline, output.status.code()),
+            err
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// First line of a failed command's error; carries the exit code for `auth:` detection
+pub fn failure_message(cmdline: &str, code: Option<i32>) -> String {
+    match code {
+        Some(code) => format!("Command failed (exit {code}): {cmdline}"),
+        None => format!("Command failed: {cmdline}"),
+    }
+}
+
+// Exit code of an error built by failure_message
+pub fn failed_exit_code(err: &str) -> Option<i32> {
+    let rest = err.trim_start().strip_prefix("Command failed (exit ")?;
+    rest[..rest.find(')')?].parse().ok()
+}
+
+// Startup check: Ok(first stdout line) on exit 0, Err(first stderr line) otherwise; the
+// command is killed once `timeout` passes
+pub fn run_check(cmdline: &str, timeout: Duration) -> Result<String, String> {
+    let expanded = crate::services::interpolate::env(cmdline);
+    let parts = shlex::split(&expanded).ok_or("failed to parse command line")?;
+    let Some((program, args)) = parts.split_first() else {
+        return Err("empty command line".to_string());
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("{program}: {e}"))?;
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let status = wait_timeout(&mut child, timeout)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("timed out after {timeout:?}"))?;
+    // A background process it started may hold the pipes open; take what arrived
+    let first_line = |rx: mpsc::Receiver<Vec<u8>>| {
+        let bytes = rx.recv_timeout(Duration::from_secs(1)).unwrap_or_default();
+        String::from_utf8_lossy(&bytes)
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    if status.success() {
+        Ok(first_line(stdout))
+    } else {
+        let err = first_line(stderr);
+        Err(if err.is_empty() {
+            format!("exited with {status}")
+        } else {
+            err
+        })
+    }
+}
+
+// Everything a pipe yields, read on its own thread
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> mpsc::Receiver<Vec<u8>> {
+    let (tx, rx) = mpsc::channel();
+    if let Some(mut pipe) = pipe {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = pipe.read_to_end(&mut buf);
+            let _ = tx.send(buf);
+        });
+    }
+    rx
+}
+
+// Wait for `child`; once `timeout` passes it is killed and None returned
+pub fn wait_timeout(child: &mut Child, timeout: Duration) -> std::io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+// Handle of a running stream: `cancel` kills its process, and events the stream would still
+// send are dropped
+#[derive(Clone, Default)]
+pub struct StreamHandle {
+    cancelled: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl StreamHandle {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Ok(mut g) = self.child.lock() {
+            if let Some(child) = g.as_mut() {
+                let _ = child.kill();
+            }
+        }
+    }
+
+    pub fn cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn attach(&self, child: Child) {
+        if let Ok(mut g) = self.child.lock() {
+            *g = Some(child);
+        }
+        // Cancelled while the process was starting
+        if self.cancelled() {
+            self.cancel();
+        }
+    }
+
+    // Exit status of the process; it stays killable while it runs on after closing stdout
+    fn wait(&self) -> Option<ExitStatus> {
+        loop {
+            let polled = self.child.lock().ok()?.as_mut()?.try_wait();
+            match polled {
+                Ok(Some(status)) => return Some(status),
+                Ok(None) => thread::sleep(Duration::from_millis(50)),
+                Err(_) => return None,
+            }
+        }
     }
-    let text = String::from_utf8_lossy(&output.stdout).to_string();
-    let v: JsonValue = serde_json::from_str(&text).with_context(|| "parsing command JSON")?;
-    Ok(v)
 }
 
-pub fn spawn_streaming_cmd(cmdline: String, tx: Sender<crate::ui::ProgressEvent>) {
+// `stream` tags every event so the UI can drop those of a stream it has replaced
+pub fn spawn_streaming_cmd(
+    stream: u64,
+    cmdline: String,
+    tx: Sender<crate::ui::ProgressEvent>,
+    ticket: Option<crate::services::mutexes::Ticket>,
+) -> StreamHandle {
+    let handle = StreamHandle::default();
+    let running = handle.clone();
     thread::spawn(move || {
-        let expanded = expand_cmdline_env(&cmdline);
+        let send = |ev: crate::ui::ProgressEvent| {
+            if !running.cancelled() {
+                let _ = tx.send(ev);
+            }
+        };
+        // Held until the stream finishes (released on drop)
+        if let Some(t) = &ticket {
+            t.wait();
+        }
+        if running.cancelled() {
+            return;
+        }
+        if let Some(v) = crate::services::result_cache::offline_hit(&cmdline) {
+            send(crate::ui::ProgressEvent {
+                stream,
+                text: None,
+                percent: None,
+                done: true,
+                result: Some(v),
+                err: None,
+            });
+            return;
+        }
+        let expanded = crate::services::interpolate::env(&cmdline);
         let parts = match shlex::split(&expanded) {
             Some(p) if !p.is_empty() => p,
             _ => {
-                let _ = tx.send(crate::ui::ProgressEvent {
+                send(crate::ui::ProgressEvent {
+                    stream,
                     text: None,
                     percent: None,
                     done: true,
@@ -74,18 +272,23 @@ pub fn spawn_streaming_cmd(cmdline: String, tx: Sender<crate::ui::ProgressEvent>
         let mut child = match Command::new(program)
             .args(args)
             .env("CHI_TUI_JSON", "1")
+            .env("CHI_TUI_ENVELOPE", envelope::VERSION.to_string())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
         {
             Ok(c) => c,
             Err(e) => {
-                let _ = tx.send(crate::ui::ProgressEvent {
+                send(crate::ui::ProgressEvent {
+                    stream,
                     text: None,
                     percent: None,
                     done: true,
                     result: None,
-                    err: Some(format!("{e}")),
+                    err: Some(match e.kind() {
+                        std::io::ErrorKind::NotFound => not_found_error(program),
+                        _ => format!("{e}"),
+                    }),
                 });
                 return;
             }
@@ -93,9 +296,13 @@ pub fn spawn_streaming_cmd(cmdline: String, tx: Sender<crate::ui::ProgressEvent>
 
         // Drop stderr to avoid blocking
         drop(child.stderr.take());
+        let stdout = child.stdout.take();
+        running.attach(child);
 
         let mut final_result: Option<JsonValue> = None;
-        if let Some(stdout) = child.stdout.take() {
+        // A line that looked like an envelope but was not one (reported instead of a result)
+        let mut malformed: Option<String> = None;
+        if let Some(stdout) = stdout {
             let reader = BufReader::new(stdout);
             for line in reader.lines().map_while(Result::ok) {
                 let l = line.trim();
@@ -103,43 +310,68 @@ pub fn spawn_streaming_cmd(cmdline: String, tx: Sender<crate::ui::ProgressEvent>
                     continue;
                 }
                 if let Ok(v) = serde_json::from_str::<JsonValue>(l) {
-                    let typ = v.get("type").and_then(|s| s.as_str()).unwrap_or("result");
-                    if typ == "progress" {
-                        let data = v.get("data").cloned().unwrap_or(JsonValue::Null);
-                        let mut text = data
-                            .get("message")
-                            .and_then(|s| s.as_str())
-                            .unwrap_or("")
-                            .to_string();
-                        if let Some(stage) = data.get("stage").and_then(|s| s.as_str()) {
-                            if !stage.is_empty() {
-                                if text.is_empty() {
-                                    text = stage.to_string();
-                                } else {
-                                    text = format!("{stage} — {text}");
+                    match Envelope::parse(&v).map(|env| env.progress()) {
+                        Ok(Some(p)) => {
+                            let text = match (p.stage.filter(|s| !s.is_empty()), p.message) {
+                                (Some(stage), Some(msg)) if !msg.is_empty() => {
+                                    format!("{stage} — {msg}")
                                 }
-                            }
+                                (Some(stage), _) => stage,
+                                (None, msg) => msg.unwrap_or_default(),
+                            };
+                            send(crate::ui::ProgressEvent {
+                                stream,
+                                text: if text.is_empty() { None } else { Some(text) },
+                                percent: p.percent,
+                                done: false,
+                                result: None,
+                                err: None,
+                            });
+                        }
+                        Ok(None) => {
+                            final_result = Some(v);
+                            break;
+                        }
+                        Err(e) => {
+                            malformed = Some(format!("{e}\n{l}"));
+                            break;
                         }
-                        let percent = data.get("percent").and_then(|p| p.as_f64());
-                        let _ = tx.send(crate::ui::ProgressEvent {
-                            text: if text.is_empty() { None } else { Some(text) },
-                            percent,
-                            done: false,
-                            result: None,
-                            err: None,
-                        });
-                    } else {
-                        final_result = Some(v);
-                        break;
                     }
                 }
             }
         }
 
-        let status = child.wait();
-        let success = status.as_ref().map(|s| s.success()).unwrap_or(false);
-        if let Some(v) = final_result {
-            let _ = tx.send(crate::ui::ProgressEvent {
+        let status = running.wait();
+        let success = status.is_some_and(|s| s.success());
+        let code = status.and_then(|s| s.code());
+        // A failed run falls back to the cached result (`cache:`)
+        let cached = if final_result.is_none() && !success {
+            crate::services::result_cache::fallback(&cmdline)
+        } else {
+            None
+        };
+        if let Some(e) = malformed {
+            send(crate::ui::ProgressEvent {
+                stream,
+                text: None,
+                percent: None,
+                done: true,
+                result: None,
+                err: Some(e),
+            });
+        } else if let Some(v) = final_result {
+            crate::services::result_cache::store(&cmdline, &v);
+            send(crate::ui::ProgressEvent {
+                stream,
+                text: None,
+                percent: None,
+                done: true,
+                result: Some(v),
+                err: None,
+            });
+        } else if let Some(v) = cached {
+            send(crate::ui::ProgressEvent {
+                stream,
                 text: None,
                 percent: None,
                 done: true,
@@ -147,15 +379,17 @@ pub fn spawn_streaming_cmd(cmdline: String, tx: Sender<crate::ui::ProgressEvent>
                 err: None,
             });
         } else if !success {
-            let _ = tx.send(crate::ui::ProgressEvent {
+            send(crate::ui::ProgressEvent {
+                stream,
                 text: None,
                 percent: None,
                 done: true,
                 result: None,
-                err: Some(format!("Command failed: {cmdline}")),
+                err: Some(failure_message(&cmdline, code)),
             });
         } else {
-            let _ = tx.send(crate::ui::ProgressEvent {
+            send(crate::ui::ProgressEvent {
+                stream,
                 text: None,
                 percent: None,
                 done: true,
@@ -164,4 +398,56 @@ pub fn spawn_streaming_cmd(cmdline: String, tx: Sender<crate::ui::ProgressEvent>
             });
         }
     });
+    handle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::{channel, RecvTimeoutError};
+
+    #[test]
+    fn missing_programs_are_found_before_and_when_running() {
+        assert_eq!(
+            missing_program("chi-tui-no-such-program --x").as_deref(),
+            Some("chi-tui-no-such-program")
+        );
+        assert_eq!(missing_program("sh -c true"), None);
+        assert_eq!(missing_program("/bin/sh -c true"), None);
+        // Templated programs are resolved later
+        assert_eq!(missing_program("{slot.tool} list"), None);
+
+        let err = run_cmdline_to_text("chi-tui-no-such-program --x").unwrap_err();
+        assert_eq!(
+            not_found_program(&err.to_string()),
+            Some("chi-tui-no-such-program")
+        );
+        assert_eq!(not_found_program("exit code 2"), None);
+    }
+
+    #[test]
+    fn checks_past_their_timeout_are_killed() {
+        let started = std::time::Instant::now();
+        let err = run_check("sleep 30", Duration::from_millis(200)).unwrap_err();
+        assert_eq!(err, "timed out after 200ms");
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(
+            run_check("echo ok", Duration::from_secs(10)).as_deref(),
+            Ok("ok")
+        );
+    }
+
+    #[test]
+    fn cancelled_stream_kills_its_process_without_sending_events() {
+        let (tx, rx) = channel();
+        let handle = spawn_streaming_cmd(1, "sleep 30".into(), tx, None);
+        thread::sleep(Duration::from_millis(200));
+        handle.cancel();
+        assert!(handle.cancelled());
+        // The thread ends (dropping its sender) long before `sleep` would, with no done event
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_secs(10)),
+            Err(RecvTimeoutError::Disconnected)
+        ));
+    }
 }
@@ -0,0 +1,148 @@
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Wall-clock helpers without a date crate: civil dates, the local UTC offset and
+// timestamps as found in command output.
+
+// Days since 1970-01-01 -> (year, month, day), proleptic Gregorian
+pub fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+pub fn unix_secs(t: SystemTime) -> i64 {
+    match t.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    }
+}
+
+pub fn from_unix(secs: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+// Local UTC offset in seconds, probed once via `date +%z` (UTC when unavailable)
+pub fn local_offset_secs() -> i64 {
+    static OFFSET: OnceLock<i64> = OnceLock::new();
+    *OFFSET.get_or_init(|| {
+        std::process::Command::new("date")
+            .arg("+%z")
+            .output()
+            .ok()
+            .and_then(|o| parse_utc_offset(String::from_utf8_lossy(&o.stdout).trim()))
+            .unwrap_or(0)
+    })
+}
+
+pub(crate) fn parse_utc_offset(s: &str) -> Option<i64> {
+    let (sign, digits) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    if digits.len() != 4 {
+        return None;
+    }
+    let h: i64 = digits[..2].parse().ok()?;
+    let m: i64 = digits[2..].parse().ok()?;
+    Some(sign * (h * 3600 + m * 60))
+}
+
+// `YYYY-MM-DD HH:MM` in local time
+pub fn format_local(t: SystemTime) -> String {
+    let local = unix_secs(t) + local_offset_secs();
+    let (y, mo, d) = civil_from_days(local.div_euclid(86_400));
+    let secs = local.rem_euclid(86_400);
+    format!(
+        "{y:04}-{mo:02}-{d:02} {:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60
+    )
+}
+
+// `2024-03-01T10:00:00Z`: UTC, sorts as text
+pub fn format_rfc3339(secs: i64) -> String {
+    let (y, mo, d) = civil_from_days(secs.div_euclid(86_400));
+    let t = secs.rem_euclid(86_400);
+    format!(
+        "{y:04}-{mo:02}-{d:02}T{:02}:{:02}:{:02}Z",
+        t / 3600,
+        t / 60 % 60,
+        t % 60
+    )
+}
+
+// (year, month, day) -> days since 1970-01-01, inverse of civil_from_days
+pub fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (m as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// Unix seconds from epoch seconds/milliseconds or an RFC 3339 string
+// (`2024-03-01T10:00:00Z`, fractional seconds and `±HH:MM` offsets allowed)
+pub fn parse_timestamp(v: &serde_json::Value) -> Option<i64> {
+    if let Some(n) = v.as_f64() {
+        // Values this large are milliseconds
+        return Some(if n.abs() >= 1e12 { n / 1000.0 } else { n } as i64);
+    }
+    let s = v.as_str()?.trim();
+    let (date, rest) = s.split_at(s.find(['T', 't', ' '])?);
+    let mut dp = date.split('-');
+    let (y, mo, d) = (
+        dp.next()?.parse::<i64>().ok()?,
+        dp.next()?.parse::<u32>().ok()?,
+        dp.next()?.parse::<u32>().ok()?,
+    );
+    let rest = &rest[1..];
+    let tz_at = rest.find(['Z', 'z', '+', '-']).unwrap_or(rest.len());
+    let (time, tz) = rest.split_at(tz_at);
+    let mut tp = time.split(':');
+    let h = tp.next()?.parse::<i64>().ok()?;
+    let mi = tp.next()?.parse::<i64>().ok()?;
+    let sec = tp
+        .next()
+        .map(|s| s.split('.').next().unwrap_or("0").parse::<i64>().ok())
+        .unwrap_or(Some(0))?;
+    let offset = match tz {
+        "" | "Z" | "z" => 0,
+        tz => parse_utc_offset(&tz.replace(':', ""))?,
+    };
+    Some(days_from_civil(y, mo, d) * 86_400 + h * 3600 + mi * 60 + sec - offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn timestamps_parse_from_numbers_and_rfc3339() {
+        let t = 1_709_287_200; // 2024-03-01T10:00:00Z
+        assert_eq!(days_from_civil(2024, 3, 1), t / 86_400);
+        assert_eq!(civil_from_days(t / 86_400), (2024, 3, 1));
+        assert_eq!(parse_timestamp(&json!(t)), Some(t));
+        assert_eq!(parse_timestamp(&json!(t * 1000)), Some(t));
+        assert_eq!(parse_timestamp(&json!("2024-03-01T10:00:00Z")), Some(t));
+        assert_eq!(
+            parse_timestamp(&json!("2024-03-01T12:00:00.250+02:00")),
+            Some(t)
+        );
+        assert_eq!(parse_timestamp(&json!("2024-03-01 05:30:00-0430")), Some(t));
+        assert_eq!(parse_timestamp(&json!("yesterday")), None);
+        assert_eq!(parse_utc_offset("-0530"), Some(-19_800));
+        assert_eq!(format_rfc3339(t + 59), "2024-03-01T10:00:59Z");
+    }
+}
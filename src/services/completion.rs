@@ -0,0 +1,262 @@
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+// Completion of command lines from the backing CLI's `schema` output: command names after
+// the program, `--flags` of the command (from its input schema, in kebab case like form
+// submits), and the enum values after a flag that has them.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Flag {
+    pub name: String,
+    // Booleans are switches: nothing to complete after them
+    pub switch: bool,
+    pub values: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Command {
+    pub name: String,
+    pub flags: Vec<Flag>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Schema {
+    pub commands: Vec<Command>,
+}
+
+fn strings(v: Option<&JsonValue>) -> Vec<String> {
+    v.and_then(|x| x.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|x| x.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Schema {
+    // `{ ok, data: { commands: [{ name, input_schema: { properties } }] } }`
+    pub fn from_json(envelope: &JsonValue) -> Self {
+        let commands = envelope
+            .get("data")
+            .unwrap_or(envelope)
+            .get("commands")
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|c| {
+                let name = c.get("name")?.as_str()?.to_string();
+                let props = c
+                    .get("input_schema")
+                    .and_then(|s| s.get("properties"))
+                    .and_then(|p| p.as_object());
+                let flags = props
+                    .into_iter()
+                    .flatten()
+                    .map(|(prop, spec)| {
+                        let items = spec.get("items");
+                        let mut values = strings(spec.get("enum"));
+                        if values.is_empty() {
+                            values = strings(items.and_then(|i| i.get("enum")));
+                        }
+                        Flag {
+                            name: format!("--{}", crate::widgets::form::kebab_case(prop)),
+                            switch: spec.get("type").and_then(|t| t.as_str()) == Some("boolean"),
+                            values,
+                        }
+                    })
+                    .collect();
+                Some(Command { name, flags })
+            })
+            .collect();
+        Schema { commands }
+    }
+
+    // Candidates for the word being typed at the end of `line` (full words, sorted)
+    pub fn candidates(&self, line: &str) -> Vec<String> {
+        let mut words: Vec<&str> = line.split_whitespace().collect();
+        let current = if line.ends_with(char::is_whitespace) || line.is_empty() {
+            ""
+        } else {
+            words.pop().unwrap_or("")
+        };
+        let mut out: Vec<String> = match words.as_slice() {
+            // Still typing the program
+            [] => Vec::new(),
+            [_program] => self.commands.iter().map(|c| c.name.clone()).collect(),
+            [_program, command, rest @ ..] => {
+                let Some(cmd) = self.commands.iter().find(|c| c.name == *command) else {
+                    return Vec::new();
+                };
+                let after = rest
+                    .last()
+                    .and_then(|w| cmd.flags.iter().find(|f| f.name == *w));
+                match after {
+                    Some(flag) if !flag.switch => flag.values.clone(),
+                    _ => cmd
+                        .flags
+                        .iter()
+                        .filter(|f| !rest.contains(&f.name.as_str()))
+                        .map(|f| f.name.clone())
+                        .collect(),
+                }
+            }
+        };
+        out.retain(|c| c.starts_with(current) && c != current);
+        out.sort();
+        out
+    }
+
+    // Rest of the first candidate, shown dimmed after the caret
+    pub fn ghost(&self, line: &str) -> Option<String> {
+        let current = line.rsplit(char::is_whitespace).next().unwrap_or("");
+        let first = self.candidates(line).into_iter().next()?;
+        Some(first[current.len()..].to_string())
+    }
+}
+
+// Tab cycling through the candidates of one word: the first Tab puts in the first candidate,
+// the next ones swap it for the following (Shift+Tab goes back)
+#[derive(Clone, Debug, Default)]
+pub struct Cycle {
+    base: String,
+    candidates: Vec<String>,
+    index: usize,
+    shown: String,
+}
+
+impl Cycle {
+    pub fn step(cycle: &mut Option<Cycle>, schema: &Schema, line: &mut String, back: bool) {
+        match cycle {
+            Some(c) if c.shown == *line && !c.candidates.is_empty() => {
+                let n = c.candidates.len();
+                c.index = if back {
+                    (c.index + n - 1) % n
+                } else {
+                    (c.index + 1) % n
+                };
+            }
+            _ => {
+                let candidates = schema.candidates(line);
+                if candidates.is_empty() {
+                    *cycle = None;
+                    return;
+                }
+                let current = line.rsplit(char::is_whitespace).next().unwrap_or("");
+                let base = line[..line.len() - current.len()].to_string();
+                *cycle = Some(Cycle {
+                    index: if back { candidates.len() - 1 } else { 0 },
+                    base,
+                    candidates,
+                    shown: String::new(),
+                });
+            }
+        }
+        let Some(c) = cycle.as_mut() else {
+            return;
+        };
+        *line = format!("{}{}", c.base, c.candidates[c.index]);
+        // A single match is final: move on to the next word
+        if c.candidates.len() == 1 {
+            line.push(' ');
+            *cycle = None;
+            return;
+        }
+        c.shown = line.clone();
+    }
+
+    // "2/5" while cycling
+    pub fn position(&self) -> String {
+        format!("{}/{}", self.index + 1, self.candidates.len())
+    }
+}
+
+type Cache = Mutex<HashMap<String, Option<Arc<Schema>>>>;
+
+fn cache() -> &'static Cache {
+    static CACHE: OnceLock<Cache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Schema of a program, fetched once in the background (`<program> schema`); None until it
+// has arrived, and an empty schema when the program has none
+pub fn schema_for(program: &str) -> Option<Arc<Schema>> {
+    let mut map = cache().lock().ok()?;
+    if let Some(entry) = map.get(program) {
+        return entry.clone();
+    }
+    map.insert(program.to_string(), None);
+    let program = program.to_string();
+    std::thread::spawn(move || {
+        let quoted = crate::chi_core::argv::quote(&program);
+        let schema = crate::services::cli_runner::run_cmdline_to_json(&format!("{quoted} schema"))
+            .map(|v| Schema::from_json(&v))
+            .unwrap_or_default();
+        if let Ok(mut map) = cache().lock() {
+            map.insert(program, Some(Arc::new(schema)));
+        }
+    });
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Schema {
+        Schema::from_json(&json!({"ok": true, "data": {"commands": [
+            {"name": "list-items", "input_schema": {"properties": {
+                "status": {"type": "string", "enum": ["open", "closed"]},
+                "page_size": {"type": "integer"},
+                "verbose": {"type": "boolean"},
+            }}},
+            {"name": "list-large"},
+            {"name": "hello"},
+        ]}}))
+    }
+
+    #[test]
+    fn completes_commands_flags_and_enum_values() {
+        let s = schema();
+        assert_eq!(s.candidates("app "), ["hello", "list-items", "list-large"]);
+        assert_eq!(s.candidates("app list"), ["list-items", "list-large"]);
+        assert_eq!(
+            s.candidates("app list-items "),
+            ["--page-size", "--status", "--verbose"]
+        );
+        assert_eq!(s.candidates("app list-items --status "), ["closed", "open"]);
+        assert_eq!(s.candidates("app list-items --status o"), ["open"]);
+        // Used flags are not offered again; values without an enum have nothing to offer
+        assert_eq!(s.candidates("app list-items --verbose --s"), ["--status"]);
+        assert!(s.candidates("app list-items --page-size ").is_empty());
+        assert!(s.candidates("app nope ").is_empty());
+        assert!(s.candidates("ap").is_empty());
+        assert_eq!(s.ghost("app list-items --st").as_deref(), Some("atus"));
+        assert_eq!(s.ghost("app he").as_deref(), Some("llo"));
+        assert_eq!(s.ghost("app hello"), None);
+    }
+
+    #[test]
+    fn tab_cycles_through_candidates() {
+        let s = schema();
+        let mut cycle = None;
+        let mut line = "app list".to_string();
+        Cycle::step(&mut cycle, &s, &mut line, false);
+        assert_eq!(line, "app list-items");
+        assert_eq!(cycle.as_ref().unwrap().position(), "1/2");
+        Cycle::step(&mut cycle, &s, &mut line, false);
+        assert_eq!(line, "app list-large");
+        Cycle::step(&mut cycle, &s, &mut line, false);
+        assert_eq!(line, "app list-items");
+        Cycle::step(&mut cycle, &s, &mut line, true);
+        assert_eq!(line, "app list-large");
+
+        // After typing, Tab completes the new word; a single match adds the space
+        let mut line = "app list-items --status c".to_string();
+        Cycle::step(&mut cycle, &s, &mut line, false);
+        assert_eq!(line, "app list-items --status closed ");
+        assert!(cycle.is_none());
+    }
+}
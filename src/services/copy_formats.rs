@@ -0,0 +1,149 @@
+use serde_json::Value as JsonValue;
+
+// Clipboard formats generated from the JSON value behind a pane (Ctrl+C picker),
+// so copies paste cleanly into tickets and spreadsheets instead of carrying the
+// rendered text
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    Json,
+    Yaml,
+    Csv,
+    Markdown,
+}
+
+impl CopyFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            CopyFormat::Json => "Copy as JSON",
+            CopyFormat::Yaml => "Copy as YAML",
+            CopyFormat::Csv => "Copy as CSV",
+            CopyFormat::Markdown => "Copy as markdown table",
+        }
+    }
+}
+
+// CSV and markdown only for tabular data: an array of objects or of scalars, or one object
+pub fn available(value: &JsonValue) -> Vec<CopyFormat> {
+    let mut formats = vec![CopyFormat::Json, CopyFormat::Yaml];
+    if table(value).is_some() {
+        formats.push(CopyFormat::Csv);
+        formats.push(CopyFormat::Markdown);
+    }
+    formats
+}
+
+pub fn render(value: &JsonValue, format: CopyFormat) -> Result<String, String> {
+    match format {
+        CopyFormat::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+        CopyFormat::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        CopyFormat::Csv => {
+            let (header, rows) = table(value).ok_or("not tabular data")?;
+            let mut out = String::new();
+            for row in std::iter::once(&header).chain(rows.iter()) {
+                let line: Vec<String> = row.iter().map(|c| csv_field(c)).collect();
+                out.push_str(&line.join(","));
+                out.push('\n');
+            }
+            Ok(out)
+        }
+        CopyFormat::Markdown => {
+            let (header, rows) = table(value).ok_or("not tabular data")?;
+            let line = |cells: &[String]| {
+                let cells: Vec<String> = cells
+                    .iter()
+                    .map(|c| c.replace('|', "\\|").replace('\n', " "))
+                    .collect();
+                format!("| {} |\n", cells.join(" | "))
+            };
+            let mut out = line(&header);
+            out.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+            for row in &rows {
+                out.push_str(&line(row));
+            }
+            Ok(out)
+        }
+    }
+}
+
+// Header and rows; columns are the union of object keys in first-seen order
+pub(crate) fn table(value: &JsonValue) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+    let items: Vec<&JsonValue> = match value {
+        JsonValue::Array(arr) if !arr.is_empty() => arr.iter().collect(),
+        JsonValue::Object(_) => vec![value],
+        _ => return None,
+    };
+    if items.iter().all(|v| v.is_object()) {
+        let mut header: Vec<String> = Vec::new();
+        for item in &items {
+            for key in item.as_object().into_iter().flat_map(|o| o.keys()) {
+                if !header.contains(key) {
+                    header.push(key.clone());
+                }
+            }
+        }
+        let rows = items
+            .iter()
+            .map(|item| {
+                header
+                    .iter()
+                    .map(|k| item.get(k).map(cell).unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+        return Some((header, rows));
+    }
+    if items.iter().all(|v| !v.is_object() && !v.is_array()) {
+        let rows = items.iter().map(|v| vec![cell(v)]).collect();
+        return Some((vec!["value".to_string()], rows));
+    }
+    None
+}
+
+// Strings unquoted, null empty, nested values as compact JSON
+fn cell(v: &JsonValue) -> String {
+    match v {
+        JsonValue::Null => String::new(),
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn tabular_values_copy_as_csv_and_markdown() {
+        let rows = json!([
+            {"id": 1, "name": "api, edge", "tags": ["a"]},
+            {"id": 2, "status": "ok|degraded", "name": null}
+        ]);
+        assert_eq!(available(&rows).len(), 4);
+        assert_eq!(
+            render(&rows, CopyFormat::Csv).unwrap(),
+            "id,name,tags,status\n1,\"api, edge\",\"[\"\"a\"\"]\",\n2,,,ok|degraded\n"
+        );
+        assert_eq!(
+            render(&rows, CopyFormat::Markdown).unwrap(),
+            "| id | name | tags | status |\n| --- | --- | --- | --- |\n\
+             | 1 | api, edge | [\"a\"] |  |\n| 2 |  |  | ok\\|degraded |\n"
+        );
+        assert_eq!(
+            render(&json!({"a": 1}), CopyFormat::Yaml).unwrap(),
+            "a: 1\n"
+        );
+        // Nested arrays are not a table
+        assert_eq!(available(&json!([[1, 2], [3]])).len(), 2);
+        assert!(render(&json!("text"), CopyFormat::Csv).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+// CSV records (RFC 4180: quoted fields may hold commas, quotes and newlines), shared by
+// `db:` query output and batch form files. With `nulls`, an empty unquoted field is None
+// (SQL NULL) while `""` stays an empty string; without, every field is Some.
+pub fn parse(text: &str, nulls: bool) -> Vec<Vec<Option<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let finish =
+        |field: String, quoted: bool| (quoted || !nulls || !field.is_empty()).then_some(field);
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_quotes = true;
+                quoted = true;
+            }
+            ',' | '\n' => {
+                record.push(finish(std::mem::take(&mut field), quoted));
+                quoted = false;
+                if c == '\n' {
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            '\r' => {}
+            _ => field.push(c),
+        }
+    }
+    if quoted || !field.is_empty() || !record.is_empty() {
+        record.push(finish(field, quoted));
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_fields_keep_separators_and_nulls_are_optional() {
+        let text = "id,note\r\n1,\"a, \"\"b\"\"\nc\"\n2,\n3,\"\"\n";
+        let s = |v: &str| Some(v.to_string());
+        assert_eq!(
+            parse(text, true),
+            vec![
+                vec![s("id"), s("note")],
+                vec![s("1"), s("a, \"b\"\nc")],
+                vec![s("2"), None],
+                vec![s("3"), s("")],
+            ]
+        );
+        assert_eq!(parse(text, false)[2], vec![s("2"), s("")]);
+        // No trailing newline
+        assert_eq!(parse("x,y", true), vec![vec![s("x"), s("y")]]);
+    }
+}
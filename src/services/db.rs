@@ -0,0 +1,422 @@
+use crate::chi_core::argv::quote;
+use crate::model::{DbSpec, MenuItem};
+use crate::services::loader::Loaded;
+use crate::widgets::text_width::{display_width, pad, pad_left};
+use anyhow::anyhow;
+use regex::Regex;
+use serde_json::{json, Map, Value as JsonValue};
+use std::collections::BTreeMap;
+
+// `db:` source sugar on a menu item: a SELECT run through the database's own CLI (sqlite3,
+// psql with CSV output, mysql in batch mode) whose rows are listed as the item's lazy
+// children, one page at a time with Previous/Next page entries. Without `fields:` the rows
+// are drawn as an aligned table under a header line.
+
+const PAGE_SIZE: usize = 50;
+// Widest a table column gets before its values are cut
+const MAX_COLUMN: usize = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Driver {
+    Sqlite,
+    Postgres,
+    Mysql,
+}
+
+fn driver(spec: &DbSpec) -> Result<Driver, String> {
+    let name = spec.driver.as_deref().map(|d| d.trim().to_lowercase());
+    match name.as_deref() {
+        Some("sqlite" | "sqlite3") => Ok(Driver::Sqlite),
+        Some("postgres" | "postgresql" | "psql" | "pg") => Ok(Driver::Postgres),
+        Some("mysql" | "mariadb") => Ok(Driver::Mysql),
+        Some(other) => Err(format!(
+            "unknown db driver '{other}' (sqlite, postgres, mysql)"
+        )),
+        None if ["postgres://", "postgresql://"]
+            .iter()
+            .any(|p| spec.database.trim().starts_with(p)) =>
+        {
+            Ok(Driver::Postgres)
+        }
+        None => Ok(Driver::Sqlite),
+    }
+}
+
+fn page_size(spec: &DbSpec) -> usize {
+    spec.page_size.filter(|&n| n > 0).unwrap_or(PAGE_SIZE)
+}
+
+// A parameter value as a quoted SQL string (MySQL also treats backslashes as escapes)
+fn sql_literal(value: &str, driver: Driver) -> String {
+    let mut v = value.replace('\'', "''");
+    if driver == Driver::Mysql {
+        v = v.replace('\\', "\\\\");
+    }
+    format!("'{v}'")
+}
+
+// `:name` placeholders replaced by their parameter; quoted text, `::type` casts and unknown
+// names are left alone
+fn bind(query: &str, params: &BTreeMap<String, String>, driver: Driver) -> String {
+    let mut out = String::new();
+    let mut quote = None;
+    let mut prev = '\0';
+    let mut chars = query.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+        } else if matches!(c, '\'' | '"' | '`') {
+            quote = Some(c);
+        } else if c == ':' && prev != ':' {
+            let name: String = query[i + 1..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            if let Some(value) = params.get(&name) {
+                let value = crate::services::interpolate::env(value);
+                out.push_str(&sql_literal(&value, driver));
+                chars.nth(name.len() - 1);
+                prev = 'a';
+                continue;
+            }
+        }
+        out.push(c);
+        prev = c;
+    }
+    out
+}
+
+// The CLI invocation reading one page (one row more than the page size, to tell whether
+// there is a next page)
+fn command(spec: &DbSpec, driver: Driver, offset: usize) -> String {
+    let query = spec.query.trim().trim_end_matches(';').trim_end();
+    let query = bind(
+        query,
+        spec.params.as_ref().unwrap_or(&BTreeMap::new()),
+        driver,
+    );
+    let sql = quote(&format!(
+        "SELECT * FROM ({query}) AS page LIMIT {} OFFSET {offset}",
+        page_size(spec) + 1
+    ));
+    let extra: String = spec
+        .args
+        .iter()
+        .flatten()
+        .map(|a| format!(" {}", quote(a)))
+        .collect();
+    let database = spec.database.trim();
+    match driver {
+        Driver::Sqlite => format!(
+            "sqlite3 -readonly -bail -csv -header{extra} {} {sql}",
+            quote(database)
+        ),
+        Driver::Postgres => {
+            let db = if database.is_empty() {
+                String::new()
+            } else {
+                format!(" -d {}", quote(database))
+            };
+            format!("psql -X -q -v ON_ERROR_STOP=1 --csv{extra}{db} -c {sql}")
+        }
+        Driver::Mysql => {
+            let db = if database.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", quote(database))
+            };
+            format!("mysql --batch{extra}{db} -e {sql}")
+        }
+    }
+}
+
+// Fill in the item's command and children source from its `db:` spec
+pub fn expand(mi: &mut MenuItem) -> Result<(), String> {
+    let Some(spec) = mi.db.clone() else {
+        return Ok(());
+    };
+    if spec.query.trim().is_empty() {
+        return Err(format!("menu '{}': db needs a `query`", mi.id));
+    }
+    let driver = driver(&spec).map_err(|e| format!("menu '{}': {e}", mi.id))?;
+    if driver == Driver::Sqlite && spec.database.trim().is_empty() {
+        return Err(format!(
+            "menu '{}': db needs a `database` (the sqlite file)",
+            mi.id
+        ));
+    }
+    if mi.command.is_none() {
+        mi.command = Some(command(&spec, driver, 0));
+    }
+    mi.widget.get_or_insert_with(|| "lazy_items".to_string());
+    Ok(())
+}
+
+// mysql --batch output: tab-separated, with `\t`, `\n`, `\\` and `\0` escaped and NULL spelled out
+fn parse_tsv(text: &str) -> Vec<Vec<Option<String>>> {
+    let unescape = |s: &str| {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('0') => out.push('\0'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        }
+        out
+    };
+    text.lines()
+        .map(|line| {
+            line.split('\t')
+                .map(|f| (f != "NULL").then(|| unescape(f)))
+                .collect()
+        })
+        .collect()
+}
+
+// CLI output as `{"columns": [...], "rows": [[...]]}` (what the result cache keeps)
+fn parse(text: &str, driver: Driver) -> JsonValue {
+    let mut records = match driver {
+        Driver::Mysql => parse_tsv(text),
+        Driver::Sqlite | Driver::Postgres => crate::services::csv::parse(text, true),
+    }
+    .into_iter();
+    let columns: Vec<String> = records
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect();
+    let rows: Vec<JsonValue> = records.map(|r| json!(r)).collect();
+    json!({"columns": columns, "rows": rows})
+}
+
+fn cell(v: &JsonValue) -> String {
+    v.as_str()
+        .map(|s| s.replace(['\n', '\t'], " "))
+        .unwrap_or_default()
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.parse::<f64>().is_ok()
+}
+
+// Page rows as children: the columns as keys, and as title either the first column (when the
+// item declares `fields:`) or the row as a line of the page's table, with a header row first
+fn items(columns: &[String], rows: &[JsonValue], table: bool) -> Vec<JsonValue> {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|r| {
+            let r = r.as_array().map(Vec::as_slice).unwrap_or_default();
+            (0..columns.len())
+                .map(|i| r.get(i).map(cell).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            cells
+                .iter()
+                .map(|r| display_width(&r[i]))
+                .chain([display_width(c)])
+                .max()
+                .unwrap_or(0)
+                .min(MAX_COLUMN)
+        })
+        .collect();
+    let line = |values: &[String]| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(v, &w)| {
+                if is_number(v) {
+                    pad_left(v, w)
+                } else {
+                    pad(v, w)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = Vec::new();
+    if table && !columns.is_empty() {
+        // Lined up with the rows' "• " marker
+        out.push(json!({
+            "id": "__db_header__",
+            "title": format!("  {}", line(columns)),
+            "__is_info": true,
+        }));
+    }
+    for (row, values) in rows.iter().zip(&cells) {
+        let mut obj = Map::new();
+        for (i, col) in columns.iter().enumerate() {
+            let v = row.get(i).cloned().unwrap_or(JsonValue::Null);
+            obj.insert(col.clone(), v);
+        }
+        if table {
+            obj.insert("title".into(), JsonValue::String(line(values)));
+        } else if !obj.contains_key("title") && !obj.contains_key("name") {
+            let first = values.first().cloned().unwrap_or_default();
+            obj.insert("title".into(), JsonValue::String(first));
+        }
+        out.push(JsonValue::Object(obj));
+    }
+    out
+}
+
+// Offset of the page a generated command reads, and the same command for another offset
+fn page_offset(cmdline: &str) -> Option<(usize, std::ops::Range<usize>)> {
+    let re = Regex::new(r"LIMIT \d+ OFFSET (\d+)").unwrap();
+    let m = re.captures_iter(cmdline).last()?.get(1)?;
+    Some((m.as_str().parse().ok()?, m.range()))
+}
+
+fn with_offset(cmdline: &str, at: std::ops::Range<usize>, offset: usize) -> String {
+    format!("{}{offset}{}", &cmdline[..at.start], &cmdline[at.end..])
+}
+
+// Run the item's (page) command and list its rows, with page controls when there is more
+pub fn load(mi: &MenuItem, spec: &DbSpec) -> anyhow::Result<Loaded> {
+    let cmdline = mi
+        .command
+        .as_ref()
+        .ok_or_else(|| anyhow!("No command configured for '{}'.", mi.title))?;
+    let driver = driver(spec).map_err(|e| anyhow!(e))?;
+    let v = crate::services::result_cache::run_cached_with(cmdline, |c| {
+        crate::services::cli_runner::run_cmdline_to_text(c).map(|text| parse(&text, driver))
+    })?;
+    let columns: Vec<String> = v["columns"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|c| c.as_str().unwrap_or_default().to_string())
+        .collect();
+    let mut rows = v["rows"].as_array().cloned().unwrap_or_default();
+    let size = page_size(spec);
+    let more = rows.len() > size;
+    rows.truncate(size);
+    let items = items(&columns, &rows, mi.fields.is_none());
+
+    let Some((offset, at)) = page_offset(cmdline) else {
+        return Ok(Loaded::Items(items));
+    };
+    if offset == 0 && !more {
+        return Ok(Loaded::Items(items));
+    }
+    let mut pagination = json!({"current_page": offset / size + 1});
+    if offset > 0 {
+        pagination["prev_page_cmd"] =
+            with_offset(cmdline, at.clone(), offset.saturating_sub(size)).into();
+    }
+    if more {
+        pagination["next_page_cmd"] = with_offset(cmdline, at, offset + size).into();
+    }
+    Ok(Loaded::ItemsWithPagination {
+        items,
+        pagination,
+        content_type: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(yaml: &str) -> DbSpec {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn db_items_expand_to_paged_cli_queries_with_bound_params() {
+        let s = spec(
+            "database: app.db\nquery: \"select id, email from users where org = :org and note <> ':org' and id::text = :id;\"\nparams: {org: \"o'reilly\", id: \"7\"}\npage_size: 20\n",
+        );
+        let mut mi = MenuItem {
+            id: "users".into(),
+            title: "Users".into(),
+            db: Some(s.clone()),
+            ..Default::default()
+        };
+        expand(&mut mi).unwrap();
+        let cmd = mi.command.clone().unwrap();
+        let argv = shlex::split(&cmd).unwrap();
+        assert_eq!(
+            &argv[..5],
+            ["sqlite3", "-readonly", "-bail", "-csv", "-header"]
+        );
+        assert_eq!(argv[5], "app.db");
+        assert_eq!(
+            argv[6],
+            "SELECT * FROM (select id, email from users where org = 'o''reilly' and note <> ':org' and id::text = '7') AS page LIMIT 21 OFFSET 0"
+        );
+        assert_eq!(mi.widget.as_deref(), Some("lazy_items"));
+
+        let (offset, at) = page_offset(&cmd).unwrap();
+        assert_eq!(offset, 0);
+        let next = shlex::split(&with_offset(&cmd, at, 40)).unwrap();
+        assert!(next[6].ends_with("LIMIT 21 OFFSET 40"));
+
+        let pg = spec("database: postgres://db/app\nquery: select 1\nargs: [-U, admin]\n");
+        assert_eq!(
+            command(&pg, driver(&pg).unwrap(), 0),
+            "psql -X -q -v ON_ERROR_STOP=1 --csv -U admin -d postgres://db/app -c 'SELECT * FROM (select 1) AS page LIMIT 51 OFFSET 0'"
+        );
+        let my = spec("driver: mysql\ndatabase: app\nquery: \"select * from t where a = :a\"\nparams: {a: 'x\\y'}\n");
+        assert_eq!(
+            shlex::split(&command(&my, driver(&my).unwrap(), 0)).unwrap(),
+            [
+                "mysql",
+                "--batch",
+                "app",
+                "-e",
+                r"SELECT * FROM (select * from t where a = 'x\\y') AS page LIMIT 51 OFFSET 0"
+            ]
+        );
+
+        let mut bad = MenuItem {
+            id: "x".into(),
+            db: Some(spec("query: select 1\n")),
+            ..Default::default()
+        };
+        assert!(expand(&mut bad).unwrap_err().contains("needs a `database`"));
+    }
+
+    #[test]
+    fn cli_output_parses_into_table_rows() {
+        let v = parse(
+            "id,email,note\n1,a@x.io,\"says \"\"hi\"\",\nthen leaves\"\n12,b@x.io,\n",
+            Driver::Postgres,
+        );
+        assert_eq!(v["columns"], json!(["id", "email", "note"]));
+        assert_eq!(v["rows"][0][2], "says \"hi\",\nthen leaves");
+        assert_eq!(v["rows"][1][2], JsonValue::Null);
+
+        let rows = v["rows"].as_array().unwrap();
+        let columns: Vec<String> = vec!["id".into(), "email".into(), "note".into()];
+        let table = items(&columns, rows, true);
+        assert_eq!(table[0]["title"], "  id  email   note");
+        assert_eq!(table[1]["title"], " 1  a@x.io  says \"hi\", then leaves");
+        assert_eq!(table[2]["title"], "12  b@x.io");
+        assert_eq!(table[2]["email"], "b@x.io");
+        let plain = items(&columns, rows, false);
+        assert_eq!((plain.len(), plain[0]["title"].clone()), (2, json!("1")));
+
+        let v = parse("id\tbio\n3\tline\\none\tx\n4\tNULL\n", Driver::Mysql);
+        assert_eq!(v["rows"][0][1], "line\none");
+        assert_eq!(v["rows"][1][1], JsonValue::Null);
+    }
+}
@@ -0,0 +1,190 @@
+use anyhow::{Context, Result};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier};
+use std::path::PathBuf;
+
+// Static HTML snapshot of a screen region (Ctrl+E): every cell of the rendered frame
+// becomes text with inline styles, so lists, JSON, stats and watchdog output look the
+// same as in the terminal without needing one.
+
+pub fn buffer_to_html(
+    buf: &Buffer,
+    area: Rect,
+    title: &str,
+    theme: &crate::theme::Theme,
+) -> String {
+    let area = area.intersection(buf.area);
+    let fg = css_color(theme.fg, "#e0e0e0");
+    let bg = css_color(theme.bg, "#000000");
+    let mut body = String::new();
+    for y in area.top()..area.bottom() {
+        let mut run = String::new();
+        let mut run_style = String::new();
+        for x in area.left()..area.right() {
+            let cell = &buf[(x, y)];
+            let style = cell_style(cell.fg, cell.bg, cell.modifier);
+            if style != run_style {
+                push_run(&mut body, &run, &run_style);
+                run.clear();
+                run_style = style;
+            }
+            run.push_str(cell.symbol());
+        }
+        push_run(&mut body, run.trim_end(), &run_style);
+        body.push('\n');
+    }
+    let title = escape(title);
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n\
+         <body style=\"margin:0;padding:16px;background:{bg};color:{fg}\">\n\
+         <h3 style=\"font-family:sans-serif;margin:0 0 12px 0\">{title}</h3>\n\
+         <pre style=\"font-family:'DejaVu Sans Mono',Menlo,Consolas,monospace;font-size:13px;line-height:1.2;margin:0\">{body}</pre>\n\
+         </body>\n</html>\n"
+    )
+}
+
+fn push_run(out: &mut String, text: &str, style: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if style.is_empty() {
+        out.push_str(&escape(text));
+    } else {
+        out.push_str(&format!("<span style=\"{style}\">{}</span>", escape(text)));
+    }
+}
+
+fn cell_style(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut css = Vec::new();
+    if let Some(c) = color_hex(fg) {
+        css.push(format!("color:{c}"));
+    }
+    if let Some(c) = color_hex(bg) {
+        css.push(format!("background:{c}"));
+    }
+    if modifier.contains(Modifier::BOLD) {
+        css.push("font-weight:bold".into());
+    }
+    if modifier.contains(Modifier::ITALIC) {
+        css.push("font-style:italic".into());
+    }
+    if modifier.contains(Modifier::UNDERLINED) {
+        css.push("text-decoration:underline".into());
+    }
+    if modifier.contains(Modifier::DIM) {
+        css.push("opacity:0.6".into());
+    }
+    css.join(";")
+}
+
+fn css_color(c: Color, fallback: &str) -> String {
+    color_hex(c).unwrap_or_else(|| fallback.to_string())
+}
+
+fn color_hex(c: Color) -> Option<String> {
+    color_rgb(c).map(|(r, g, b)| format!("#{r:02x}{g:02x}{b:02x}"))
+}
+
+// xterm defaults for named colors; Reset keeps the page color
+pub(crate) fn color_rgb(c: Color) -> Option<(u8, u8, u8)> {
+    Some(match c {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Indexed(i) => indexed_rgb(i),
+    })
+}
+
+// 256-color palette: 16 base colors, 6x6x6 cube, grayscale ramp
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..=15 => {
+            const BASE: [(u8, u8, u8); 16] = [
+                (0, 0, 0),
+                (205, 0, 0),
+                (0, 205, 0),
+                (205, 205, 0),
+                (0, 0, 238),
+                (205, 0, 205),
+                (0, 205, 205),
+                (229, 229, 229),
+                (127, 127, 127),
+                (255, 0, 0),
+                (0, 255, 0),
+                (255, 255, 0),
+                (92, 92, 255),
+                (255, 0, 255),
+                (0, 255, 255),
+                (255, 255, 255),
+            ];
+            BASE[i as usize]
+        }
+        16..=231 => {
+            let n = i - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Write the report into the working directory (same place as markdown page exports)
+pub fn write_report(html: &str) -> Result<PathBuf> {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = std::env::current_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(format!("chi-report-{stamp}.html"));
+    std::fs::write(&path, html).with_context(|| format!("writing {path:?}"))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn cells_become_escaped_styled_runs() {
+        let area = Rect::new(0, 0, 12, 2);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "<ok>", Style::default().fg(Color::Rgb(255, 120, 0)));
+        buf.set_string(5, 0, "a&b", Style::default().add_modifier(Modifier::BOLD));
+        buf.set_string(0, 1, "plain", Style::default());
+        let theme = crate::theme::Theme::synthwave_dark();
+        let html = buffer_to_html(&buf, area, "Deploy <prod>", &theme);
+        assert!(html.contains("<title>Deploy &lt;prod&gt;</title>"));
+        assert!(html.contains("<span style=\"color:#ff7800\">&lt;ok&gt;</span> "));
+        assert!(html.contains("<span style=\"font-weight:bold\">a&amp;b</span>\nplain\n"));
+        assert_eq!(indexed_rgb(196), (255, 0, 0));
+        assert_eq!(indexed_rgb(244), (128, 128, 128));
+    }
+}
@@ -0,0 +1,473 @@
+use anyhow::{Context, Result};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use std::path::PathBuf;
+use unicode_width::UnicodeWidthChar;
+
+// PNG snapshot of a widget (Ctrl+P, or "Export image" in the action bar): the cells of the
+// rendered frame are painted off-screen in the theme's colors, with a small bitmap font for
+// text, block and braille characters drawn to scale and box lines as lines, so charts and
+// dashboards can be pasted into an incident channel as a picture.
+
+// Pixels per cell (5x8 glyphs with spacing); the image is scaled up by SCALE
+const CELL_W: usize = 6;
+const CELL_H: usize = 12;
+const SCALE: usize = 2;
+// Glyph rows start this far from the top of the cell
+const GLYPH_TOP: usize = 2;
+
+type Rgb = (u8, u8, u8);
+
+// 5x8 bitmaps (5 bits per row, rows top to bottom, the last row below the baseline) for
+// printable ASCII from ' '
+const ASCII: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04, 0x00],
+    [0x0a, 0x0a, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x0a, 0x0a, 0x1f, 0x0a, 0x1f, 0x0a, 0x0a, 0x00],
+    [0x04, 0x0f, 0x14, 0x0e, 0x05, 0x1e, 0x04, 0x00],
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03, 0x00],
+    [0x0c, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0d, 0x00],
+    [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02, 0x00],
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00],
+    [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00, 0x00],
+    [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x08, 0x00],
+    [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c, 0x00],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00, 0x00],
+    [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e, 0x00],
+    [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00],
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f, 0x00],
+    [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e, 0x00],
+    [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02, 0x00],
+    [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e, 0x00],
+    [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e, 0x00],
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08, 0x00],
+    [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e, 0x00],
+    [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c, 0x00],
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00, 0x00],
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x04, 0x08, 0x00],
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02, 0x00],
+    [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00, 0x00],
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08, 0x00],
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04, 0x00],
+    [0x0e, 0x11, 0x01, 0x0d, 0x15, 0x15, 0x0e, 0x00],
+    [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11, 0x00],
+    [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e, 0x00],
+    [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e, 0x00],
+    [0x1c, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1c, 0x00],
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f, 0x00],
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10, 0x00],
+    [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f, 0x00],
+    [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11, 0x00],
+    [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00],
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c, 0x00],
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11, 0x00],
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f, 0x00],
+    [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11, 0x00],
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11, 0x00],
+    [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00],
+    [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10, 0x00],
+    [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d, 0x00],
+    [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11, 0x00],
+    [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e, 0x00],
+    [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00],
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00],
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04, 0x00],
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a, 0x00],
+    [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11, 0x00],
+    [0x11, 0x11, 0x0a, 0x04, 0x04, 0x04, 0x04, 0x00],
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f, 0x00],
+    [0x0e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0e, 0x00],
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00, 0x00],
+    [0x0e, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0e, 0x00],
+    [0x04, 0x0a, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x00],
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00],
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1e, 0x00],
+    [0x00, 0x00, 0x0e, 0x10, 0x10, 0x11, 0x0e, 0x00],
+    [0x01, 0x01, 0x0d, 0x13, 0x11, 0x11, 0x0f, 0x00],
+    [0x00, 0x00, 0x0e, 0x11, 0x1f, 0x10, 0x0e, 0x00],
+    [0x06, 0x09, 0x08, 0x1c, 0x08, 0x08, 0x08, 0x00],
+    [0x00, 0x00, 0x0f, 0x11, 0x11, 0x0f, 0x01, 0x0e],
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00],
+    [0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x0e, 0x00],
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x02, 0x12, 0x0c],
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12, 0x00],
+    [0x0c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00],
+    [0x00, 0x00, 0x1a, 0x15, 0x15, 0x11, 0x11, 0x00],
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00],
+    [0x00, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00],
+    [0x00, 0x00, 0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10],
+    [0x00, 0x00, 0x0f, 0x11, 0x11, 0x0f, 0x01, 0x01],
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10, 0x00],
+    [0x00, 0x00, 0x0f, 0x10, 0x0e, 0x01, 0x1e, 0x00],
+    [0x08, 0x08, 0x1c, 0x08, 0x08, 0x09, 0x06, 0x00],
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d, 0x00],
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0a, 0x04, 0x00],
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0a, 0x00],
+    [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x00],
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0f, 0x01, 0x0e],
+    [0x00, 0x00, 0x1f, 0x02, 0x04, 0x08, 0x1f, 0x00],
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02, 0x00],
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00],
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08, 0x00],
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00, 0x00],
+];
+
+// Symbols the app draws for statuses, markers and arrows
+const SYMBOLS: [(char, [u8; 8]); 23] = [
+    ('✓', [0x00, 0x01, 0x02, 0x12, 0x0c, 0x04, 0x00, 0x00]),
+    ('✔', [0x01, 0x03, 0x16, 0x1e, 0x0c, 0x04, 0x00, 0x00]),
+    ('✗', [0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x00, 0x00]),
+    ('✖', [0x1b, 0x1f, 0x0e, 0x0e, 0x1f, 0x1b, 0x00, 0x00]),
+    ('●', [0x00, 0x0e, 0x1f, 0x1f, 0x1f, 0x0e, 0x00, 0x00]),
+    ('○', [0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00, 0x00]),
+    ('•', [0x00, 0x00, 0x0e, 0x0e, 0x0e, 0x00, 0x00, 0x00]),
+    ('·', [0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00]),
+    ('▲', [0x00, 0x04, 0x04, 0x0e, 0x0e, 0x1f, 0x00, 0x00]),
+    ('▼', [0x00, 0x1f, 0x0e, 0x0e, 0x04, 0x04, 0x00, 0x00]),
+    ('▶', [0x08, 0x0c, 0x0e, 0x0f, 0x0e, 0x0c, 0x08, 0x00]),
+    ('▸', [0x00, 0x08, 0x0c, 0x0e, 0x0c, 0x08, 0x00, 0x00]),
+    ('▾', [0x00, 0x00, 0x1f, 0x0e, 0x04, 0x00, 0x00, 0x00]),
+    ('◆', [0x00, 0x04, 0x0e, 0x1f, 0x0e, 0x04, 0x00, 0x00]),
+    ('…', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x15, 0x00]),
+    ('—', [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00]),
+    ('→', [0x00, 0x04, 0x02, 0x1f, 0x02, 0x04, 0x00, 0x00]),
+    ('←', [0x00, 0x04, 0x08, 0x1f, 0x08, 0x04, 0x00, 0x00]),
+    ('↑', [0x04, 0x0e, 0x15, 0x04, 0x04, 0x04, 0x00, 0x00]),
+    ('↓', [0x04, 0x04, 0x04, 0x15, 0x0e, 0x04, 0x00, 0x00]),
+    ('⇅', [0x0a, 0x1a, 0x0a, 0x0a, 0x0b, 0x0a, 0x00, 0x00]),
+    ('›', [0x00, 0x08, 0x04, 0x02, 0x04, 0x08, 0x00, 0x00]),
+    ('⏳', [0x1f, 0x0a, 0x04, 0x04, 0x0a, 0x1f, 0x00, 0x00]),
+];
+
+fn glyph(c: char) -> Option<&'static [u8; 8]> {
+    match c {
+        ' '..='~' => Some(&ASCII[c as usize - 0x20]),
+        _ => SYMBOLS.iter().find(|(s, _)| *s == c).map(|(_, g)| g),
+    }
+}
+
+// RGBA pixels, row by row
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub rgba: Vec<u8>,
+}
+
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Canvas {
+    fn fill(&mut self, x: usize, y: usize, w: usize, h: usize, color: Rgb) {
+        for py in y..(y + h).min(self.height) {
+            for px in x..(x + w).min(self.width) {
+                self.pixels[py * self.width + px] = color;
+            }
+        }
+    }
+
+    fn scaled(&self, scale: usize) -> Image {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let (r, g, b) = self.pixels[(y / scale) * self.width + x / scale];
+                rgba.extend_from_slice(&[r, g, b, 255]);
+            }
+        }
+        Image {
+            width,
+            height,
+            rgba,
+        }
+    }
+}
+
+fn mix(a: Rgb, b: Rgb) -> Rgb {
+    let m = |x: u8, y: u8| ((x as u16 + y as u16) / 2) as u8;
+    (m(a.0, b.0), m(a.1, b.1), m(a.2, b.2))
+}
+
+pub fn buffer_to_image(buf: &Buffer, area: Rect, theme: &crate::theme::Theme) -> Image {
+    use crate::services::html_export::color_rgb;
+    let area = area.intersection(buf.area);
+    let default_fg = color_rgb(theme.fg).unwrap_or((224, 224, 224));
+    let default_bg = color_rgb(theme.bg).unwrap_or((0, 0, 0));
+    let (width, height) = (area.width as usize * CELL_W, area.height as usize * CELL_H);
+    let mut canvas = Canvas {
+        width,
+        height,
+        pixels: vec![default_bg; width * height],
+    };
+    let colors = |cell: &ratatui::buffer::Cell| {
+        let mut fg = color_rgb(cell.fg).unwrap_or(default_fg);
+        let mut bg = color_rgb(cell.bg).unwrap_or(default_bg);
+        if cell.modifier.contains(Modifier::REVERSED) {
+            std::mem::swap(&mut fg, &mut bg);
+        }
+        if cell.modifier.contains(Modifier::DIM) {
+            fg = mix(fg, bg);
+        }
+        (fg, bg)
+    };
+    // Backgrounds first, so wide characters can spill into the next cell
+    for pass in [false, true] {
+        for (row, y) in (area.top()..area.bottom()).enumerate() {
+            for (col, x) in (area.left()..area.right()).enumerate() {
+                let cell = &buf[(x, y)];
+                let (fg, bg) = colors(cell);
+                let (cx, cy) = (col * CELL_W, row * CELL_H);
+                if !pass {
+                    canvas.fill(cx, cy, CELL_W, CELL_H, bg);
+                    continue;
+                }
+                if let Some(c) = cell.symbol().chars().next() {
+                    let bold = cell.modifier.contains(Modifier::BOLD);
+                    draw_char(&mut canvas, cx, cy, c, fg, bold);
+                }
+                if cell.modifier.contains(Modifier::UNDERLINED) {
+                    canvas.fill(cx, cy + CELL_H - 1, CELL_W, 1, fg);
+                }
+            }
+        }
+    }
+    canvas.scaled(SCALE)
+}
+
+fn draw_char(canvas: &mut Canvas, x: usize, y: usize, c: char, fg: Rgb, bold: bool) {
+    if c == ' ' {
+        return;
+    }
+    if let Some(rows) = glyph(c) {
+        for (r, bits) in rows.iter().enumerate() {
+            for col in 0..5 {
+                if bits >> (4 - col) & 1 == 1 {
+                    // Bold smears each pixel one to the right
+                    canvas.fill(x + col, y + GLYPH_TOP + r, 1 + bold as usize, 1, fg);
+                }
+            }
+        }
+        return;
+    }
+    let code = c as u32;
+    match code {
+        // ▀ upper half, ▔ upper eighth
+        0x2580 => canvas.fill(x, y, CELL_W, CELL_H / 2, fg),
+        0x2594 => canvas.fill(x, y, CELL_W, CELL_H / 8, fg),
+        // ▁..█ lower eighths (bar charts, sparklines)
+        0x2581..=0x2588 => {
+            let h = CELL_H * (code - 0x2580) as usize / 8;
+            canvas.fill(x, y + CELL_H - h, CELL_W, h, fg);
+        }
+        // ▉..▏ left eighths (gauges)
+        0x2589..=0x258F => {
+            let w = (CELL_W * (0x2590 - code) as usize).div_ceil(8);
+            canvas.fill(x, y, w, CELL_H, fg);
+        }
+        // ▐ right half, ▕ right eighth
+        0x2590 => canvas.fill(x + CELL_W / 2, y, CELL_W / 2, CELL_H, fg),
+        0x2595 => canvas.fill(x + CELL_W - 1, y, 1, CELL_H, fg),
+        // ░▒▓ as dither patterns
+        0x2591..=0x2593 => {
+            for py in 0..CELL_H {
+                for px in 0..CELL_W {
+                    let on = match code {
+                        0x2591 => px % 2 == 0 && py % 2 == 0,
+                        0x2592 => (px + py) % 2 == 0,
+                        _ => px % 2 == 0 || py % 2 == 0,
+                    };
+                    if on {
+                        canvas.fill(x + px, y + py, 1, 1, fg);
+                    }
+                }
+            }
+        }
+        // ▖..▟ quadrants (bits: upper left, upper right, lower left, lower right)
+        0x2596..=0x259F => {
+            const QUADS: [u8; 10] = [4, 8, 1, 13, 9, 7, 11, 2, 6, 14];
+            let q = QUADS[(code - 0x2596) as usize];
+            let (hw, hh) = (CELL_W / 2, CELL_H / 2);
+            for (bit, qx, qy) in [(1, 0, 0), (2, hw, 0), (4, 0, hh), (8, hw, hh)] {
+                if q & bit != 0 {
+                    canvas.fill(x + qx, y + qy, hw, hh, fg);
+                }
+            }
+        }
+        0x2800..=0x28FF => draw_braille(canvas, x, y, (code - 0x2800) as u8, fg),
+        _ => match box_arms(c) {
+            Some(arms) => draw_box(canvas, x, y, arms, fg),
+            // Anything else (CJK, emoji, ...) is shown as a box of its width
+            None => {
+                let w = c.width().unwrap_or(1).max(1);
+                let (right, bottom) = (x + w * CELL_W - 2, y + GLYPH_TOP + 6);
+                canvas.fill(x + 1, y + GLYPH_TOP, right - x - 1, 1, fg);
+                canvas.fill(x + 1, bottom, right - x - 1, 1, fg);
+                canvas.fill(x + 1, y + GLYPH_TOP, 1, 7, fg);
+                canvas.fill(right - 1, y + GLYPH_TOP, 1, 7, fg);
+            }
+        },
+    }
+}
+
+// Braille cell: 2x4 dots, bits numbered down the left column then the right, 7 and 8 last
+fn draw_braille(canvas: &mut Canvas, x: usize, y: usize, bits: u8, fg: Rgb) {
+    const DOTS: [(usize, usize); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+    for (i, (col, row)) in DOTS.iter().enumerate() {
+        if bits >> i & 1 == 1 {
+            canvas.fill(x + 1 + col * 3, y + 1 + row * 3, 2, 2, fg);
+        }
+    }
+}
+
+// Which sides of the cell a box-drawing line reaches: (up, down, left, right, heavy)
+fn box_arms(c: char) -> Option<(bool, bool, bool, bool, bool)> {
+    let (arms, heavy) = match c {
+        '─' | '┄' | '┈' | '╌' => ("lr", false),
+        '━' | '┅' | '┉' | '╍' | '═' => ("lr", true),
+        '│' | '┆' | '┊' | '╎' => ("ud", false),
+        '┃' | '┇' | '┋' | '╏' | '║' => ("ud", true),
+        '┌' | '╭' => ("dr", false),
+        '┏' | '╔' => ("dr", true),
+        '┐' | '╮' => ("dl", false),
+        '┓' | '╗' => ("dl", true),
+        '└' | '╰' => ("ur", false),
+        '┗' | '╚' => ("ur", true),
+        '┘' | '╯' => ("ul", false),
+        '┛' | '╝' => ("ul", true),
+        '├' => ("udr", false),
+        '┣' | '╠' => ("udr", true),
+        '┤' => ("udl", false),
+        '┫' | '╣' => ("udl", true),
+        '┬' => ("dlr", false),
+        '┳' | '╦' => ("dlr", true),
+        '┴' => ("ulr", false),
+        '┻' | '╩' => ("ulr", true),
+        '┼' => ("udlr", false),
+        '╋' | '╬' => ("udlr", true),
+        '╴' => ("l", false),
+        '╵' => ("u", false),
+        '╶' => ("r", false),
+        '╷' => ("d", false),
+        _ => return None,
+    };
+    let has = |a: char| arms.contains(a);
+    Some((has('u'), has('d'), has('l'), has('r'), heavy))
+}
+
+fn draw_box(
+    canvas: &mut Canvas,
+    x: usize,
+    y: usize,
+    (up, down, left, right, heavy): (bool, bool, bool, bool, bool),
+    fg: Rgb,
+) {
+    let t = if heavy { 2 } else { 1 };
+    let (mx, my) = (x + CELL_W / 2 - t / 2, y + CELL_H / 2 - t / 2);
+    if up {
+        canvas.fill(mx, y, t, my - y + t, fg);
+    }
+    if down {
+        canvas.fill(mx, my, t, y + CELL_H - my, fg);
+    }
+    if left {
+        canvas.fill(x, my, mx - x + t, t, fg);
+    }
+    if right {
+        canvas.fill(mx, my, x + CELL_W - mx, t, fg);
+    }
+}
+
+pub fn encode_png(image: &Image) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, image.width as u32, image.height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&image.rgba)?;
+    writer.finish()?;
+    Ok(out)
+}
+
+// Write the PNG into the working directory (next to HTML reports)
+pub fn write_image(png: &[u8]) -> Result<PathBuf> {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = std::env::current_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(format!("chi-snapshot-{stamp}.png"));
+    std::fs::write(&path, png).with_context(|| format!("writing {path:?}"))?;
+    Ok(path)
+}
+
+// Put the image on the clipboard (as an image, for pasting into chat)
+pub fn copy_image(image: &Image) -> Result<()> {
+    let mut clipboard = arboard::Clipboard::new()?;
+    clipboard.set_image(arboard::ImageData {
+        width: image.width,
+        height: image.height,
+        bytes: std::borrow::Cow::Borrowed(&image.rgba),
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color, Style};
+
+    #[test]
+    fn cells_are_painted_with_glyphs_blocks_and_lines() {
+        let area = Rect::new(0, 0, 4, 2);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "A█▄─", Style::default().fg(Color::Rgb(255, 0, 0)));
+        buf.set_string(0, 1, "⣿", Style::default().bg(Color::Rgb(0, 0, 255)));
+        let theme = crate::theme::Theme::synthwave_dark();
+        let image = buffer_to_image(&buf, area, &theme);
+        assert_eq!(
+            (image.width, image.height),
+            (4 * CELL_W * SCALE, 2 * CELL_H * SCALE)
+        );
+        let px = |x: usize, y: usize| {
+            let i = ((y * SCALE) * image.width + x * SCALE) * 4;
+            (image.rgba[i], image.rgba[i + 1], image.rgba[i + 2])
+        };
+        let red = (255, 0, 0);
+        let bg = crate::services::html_export::color_rgb(theme.bg).unwrap_or((0, 0, 0));
+        // 'A' has its apex in the middle of the first glyph row
+        assert_eq!(px(2, GLYPH_TOP), red);
+        assert_eq!(px(0, GLYPH_TOP), bg);
+        // Full block fills the cell, lower half only the bottom
+        assert_eq!(px(CELL_W, 0), red);
+        assert_eq!(px(2 * CELL_W, 0), bg);
+        assert_eq!(px(2 * CELL_W, CELL_H - 1), red);
+        // Box line runs through the middle
+        assert_eq!(px(3 * CELL_W, CELL_H / 2), red);
+        assert_eq!(px(3 * CELL_W, 0), bg);
+        // Braille dots on the cell background
+        assert_eq!(px(0, CELL_H), (0, 0, 255));
+        assert_ne!(px(1, CELL_H + 1), (0, 0, 255));
+
+        let png = encode_png(&image).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+}
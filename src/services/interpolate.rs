@@ -0,0 +1,97 @@
+use regex::Regex;
+use serde_json::Value as JsonValue;
+
+// Placeholders in command lines (`command`, `pane_a_cmd`, `submit_cmd`, watchdog commands):
+// - `${ENV_VAR}` from the environment; `${APP_BIN}` is CHI_APP_BIN or `example-app`
+// - `${config_dir}` the directory of the entry config
+// - `${selected.<path>}` a field of the item selected when the command was triggered
+// The environment is filled in right before a command runs. The selection is filled in when
+// the item is entered, since it has usually moved on by the time a spec it loads runs.
+
+// `${ENV_VAR}` and `${config_dir}`; unset variables are empty
+pub fn env(cmdline: &str) -> String {
+    let re = Regex::new(r"\$\{([A-Z0-9_]+|config_dir)\}").unwrap();
+    re.replace_all(cmdline, |caps: &regex::Captures| {
+        let path = match &caps[1] {
+            "APP_BIN" => std::env::var("CHI_APP_BIN").unwrap_or_else(|_| "example-app".into()),
+            "config_dir" => std::env::var("CHI_TUI_CONFIG_DIR").unwrap_or_else(|_| ".".into()),
+            key => return std::env::var(key).unwrap_or_default(),
+        };
+        // Quote paths with whitespace to keep them a single arg in shlex::split
+        if path.chars().any(|c| c.is_whitespace()) {
+            format!("\"{}\"", path.replace('"', "\\\""))
+        } else {
+            path
+        }
+    })
+    .into_owned()
+}
+
+// `${selected.<path>}` replaced by the shell-quoted field of the selected item (a dotted path
+// into its value; missing fields are empty)
+pub fn selected(template: &str, selected: &JsonValue) -> String {
+    if !template.contains("${selected.") {
+        return template.to_string();
+    }
+    let re = Regex::new(r"\$\{selected\.([A-Za-z0-9_.-]+)\}").unwrap();
+    re.replace_all(template, |caps: &regex::Captures| {
+        let field = caps[1].split('.').try_fold(selected, |v, k| {
+            v.get(k)
+                .or_else(|| k.parse::<usize>().ok().and_then(|i| v.get(i)))
+        });
+        let text = match field {
+            Some(JsonValue::String(s)) => s.clone(),
+            Some(JsonValue::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        };
+        crate::chi_core::argv::quote(&text)
+    })
+    .into_owned()
+}
+
+// Every string of a value (an inline child, a widget spec) with `${selected.<path>}` filled
+pub fn selected_in_value(v: &mut JsonValue, sel: &JsonValue) {
+    match v {
+        JsonValue::String(s) if s.contains("${selected.") => *s = selected(s, sel),
+        JsonValue::Array(items) => items.iter_mut().for_each(|x| selected_in_value(x, sel)),
+        JsonValue::Object(map) => map.values_mut().for_each(|x| selected_in_value(x, sel)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn placeholders_fill_from_env_config_dir_and_selection() {
+        std::env::set_var("CHI_TUI_TEST_REGION", "eu-west-1");
+        assert_eq!(
+            env("app --region ${CHI_TUI_TEST_REGION} --x ${CHI_TUI_TEST_UNSET}"),
+            "app --region eu-west-1 --x "
+        );
+        let dir = env("cat ${config_dir}/notes.md");
+        assert!(dir.ends_with("/notes.md") && !dir.contains('$'), "{dir}");
+
+        let pod = json!({"id": "web-1", "meta": {"ns": "prod"}, "ports": [80, 443], "note": "a b"});
+        assert_eq!(
+            selected(
+                "kubectl -n ${selected.meta.ns} logs ${selected.id} --port ${selected.ports.1}",
+                &pod
+            ),
+            "kubectl -n prod logs web-1 --port 443"
+        );
+        assert_eq!(
+            selected("echo ${selected.note} ${selected.missing}", &pod),
+            "echo 'a b' ''"
+        );
+        // Left for the environment pass
+        assert_eq!(selected("${APP_BIN} get", &pod), "${APP_BIN} get");
+
+        let mut spec = json!({"type": "form", "submit_cmd": "app scale ${selected.id}", "fields": [{"default": "${selected.meta.ns}"}]});
+        selected_in_value(&mut spec, &pod);
+        assert_eq!(spec["submit_cmd"], "app scale web-1");
+        assert_eq!(spec["fields"][0]["default"], "prod");
+    }
+}
@@ -0,0 +1,348 @@
+use crate::model::{FieldSpec, K8sSpec, MenuItem, VerbSpec};
+use serde_json::{Map, Value as JsonValue};
+
+// `k8s:` source sugar on a menu item: the resources of a kind are listed with
+// `kubectl get -o json` as the item's lazy children (named after their metadata, with a
+// status and age column), and the item gets the usual verbs — logs, describe, and delete
+// behind a y/n confirmation. Anything set explicitly on the item wins.
+
+// Kinds without a namespace (verbs leave out `-n`)
+const CLUSTER_SCOPED: &[&str] = &[
+    "node",
+    "namespace",
+    "persistentvolume",
+    "storageclass",
+    "clusterrole",
+    "clusterrolebinding",
+    "customresourcedefinition",
+    "ingressclass",
+    "priorityclass",
+];
+
+// Kinds `kubectl logs` works on
+const WITH_LOGS: &[&str] = &[
+    "pod",
+    "deployment",
+    "statefulset",
+    "daemonset",
+    "replicaset",
+    "job",
+];
+
+// Singular lowercase kind for the common plurals and short names (`pods`, `deploy`, `sts`)
+fn singular(kind: &str) -> String {
+    let kind = kind.trim().to_lowercase();
+    let short = match kind.as_str() {
+        "po" => "pod",
+        "deploy" => "deployment",
+        "sts" => "statefulset",
+        "ds" => "daemonset",
+        "rs" => "replicaset",
+        "svc" => "service",
+        "cm" => "configmap",
+        "ns" => "namespace",
+        "no" => "node",
+        "pv" => "persistentvolume",
+        "pvc" => "persistentvolumeclaim",
+        "ing" => "ingress",
+        "sc" => "storageclass",
+        "crd" | "crds" => "customresourcedefinition",
+        _ => "",
+    };
+    if !short.is_empty() {
+        return short.to_string();
+    }
+    if let Some(stem) = kind.strip_suffix("sses") {
+        // ingresses, storageclasses
+        format!("{stem}ss")
+    } else if let Some(stem) = kind.strip_suffix("ies") {
+        // networkpolicies
+        format!("{stem}y")
+    } else {
+        kind.strip_suffix('s').unwrap_or(&kind).to_string()
+    }
+}
+
+// A value for the command line: placeholders (`{slot.ns}`) are filled in (and quoted) later
+fn arg(s: &str) -> String {
+    if s.contains('{') {
+        return s.to_string();
+    }
+    crate::chi_core::argv::quote(s)
+}
+
+// `--context x` (and `-n`/`-A`, `-l` for listing) shared by the generated commands
+fn list_scope(spec: &K8sSpec) -> String {
+    let mut out = String::new();
+    match spec.namespace.as_deref().map(str::trim) {
+        None | Some("") => {}
+        Some("all" | "*") => out.push_str(" -A"),
+        Some(ns) => out.push_str(&format!(" -n {}", arg(ns))),
+    }
+    if let Some(sel) = spec.selector.as_deref().filter(|s| !s.trim().is_empty()) {
+        out.push_str(&format!(" -l {}", arg(sel.trim())));
+    }
+    out.push_str(&context(spec));
+    out
+}
+
+fn context(spec: &K8sSpec) -> String {
+    match spec.context.as_deref().filter(|c| !c.trim().is_empty()) {
+        Some(c) => format!(" --context {}", arg(c.trim())),
+        None => String::new(),
+    }
+}
+
+// Fill in the item's command, children source, columns and verbs from its `k8s:` spec
+pub fn expand(mi: &mut MenuItem) -> Result<(), String> {
+    let Some(spec) = mi.k8s.clone() else {
+        return Ok(());
+    };
+    let kind = spec.kind.trim();
+    if kind.is_empty() {
+        return Err(format!(
+            "menu '{}': k8s needs a `kind` (pods, deployments, ...)",
+            mi.id
+        ));
+    }
+    let one = singular(kind);
+    if mi.command.is_none() {
+        mi.command = Some(format!(
+            "kubectl get {} -o json{}",
+            arg(kind),
+            list_scope(&spec)
+        ));
+    }
+    mi.widget.get_or_insert_with(|| "lazy_items".to_string());
+    mi.unwrap.get_or_insert_with(|| "items".to_string());
+    mi.fields.get_or_insert_with(|| {
+        vec![
+            FieldSpec {
+                name: "phase".into(),
+                ..Default::default()
+            },
+            FieldSpec {
+                name: "created".into(),
+                render: Some("relative_time".into()),
+                ..Default::default()
+            },
+        ]
+    });
+
+    let ns = if CLUSTER_SCOPED.contains(&one.as_str()) {
+        String::new()
+    } else {
+        " -n {namespace}".to_string()
+    };
+    let ctx = context(&spec);
+    let mut verbs = Vec::new();
+    if WITH_LOGS.contains(&one.as_str()) {
+        let target = if one == "pod" {
+            "{name} --all-containers".to_string()
+        } else {
+            format!("{one}/{{name}}")
+        };
+        verbs.push(VerbSpec {
+            key: 'l',
+            title: "Logs".into(),
+            command: format!("kubectl logs {target} --tail 200{ns}{ctx}"),
+            confirm: false,
+        });
+    }
+    verbs.push(VerbSpec {
+        key: 'd',
+        title: "Describe".into(),
+        command: format!("kubectl describe {one} {{name}}{ns}{ctx}"),
+        confirm: false,
+    });
+    verbs.push(VerbSpec {
+        key: 'D',
+        title: "Delete".into(),
+        command: format!("kubectl delete {one} {{name}}{ns}{ctx}"),
+        confirm: true,
+    });
+    let own = mi.verbs.get_or_insert_with(Vec::new);
+    for v in verbs {
+        if !own.iter().any(|o| o.key == v.key) {
+            own.push(v);
+        }
+    }
+    Ok(())
+}
+
+fn path<'a>(v: &'a JsonValue, keys: &[&str]) -> Option<&'a JsonValue> {
+    keys.iter().try_fold(v, |cur, k| cur.get(k))
+}
+
+// One-word state of a resource: why a pod's container is waiting, ready/desired replicas
+// of a workload, or its `status.phase`
+fn phase(item: &JsonValue) -> Option<String> {
+    if path(item, &["metadata", "deletionTimestamp"]).is_some() {
+        return Some("Terminating".into());
+    }
+    let waiting = path(item, &["status", "containerStatuses"])
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .find_map(|c| path(c, &["state", "waiting", "reason"])?.as_str());
+    if let Some(reason) = waiting {
+        return Some(reason.to_string());
+    }
+    if let Some(desired) = path(item, &["spec", "replicas"]).and_then(|r| r.as_u64()) {
+        let ready = path(item, &["status", "readyReplicas"])
+            .and_then(|r| r.as_u64())
+            .unwrap_or(0);
+        return Some(format!("{ready}/{desired}"));
+    }
+    path(item, &["status", "phase"])
+        .and_then(|p| p.as_str())
+        .map(String::from)
+}
+
+// A `kubectl get -o json` item with `name`, `namespace`, `phase` and `created` lifted to the
+// top level (row title, columns and verb placeholders); the rest is kept as-is
+pub fn row(item: JsonValue) -> JsonValue {
+    let JsonValue::Object(obj) = &item else {
+        return item;
+    };
+    let mut out = Map::new();
+    for (key, at) in [
+        ("name", &["metadata", "name"][..]),
+        ("namespace", &["metadata", "namespace"]),
+        ("created", &["metadata", "creationTimestamp"]),
+    ] {
+        if let Some(v) = path(&item, at) {
+            out.insert(key.into(), v.clone());
+        }
+    }
+    if let Some(p) = phase(&item) {
+        out.insert("phase".into(), JsonValue::String(p));
+    }
+    for (k, v) in obj {
+        out.entry(k.clone()).or_insert_with(|| v.clone());
+    }
+    JsonValue::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item(spec: K8sSpec) -> MenuItem {
+        let mut mi = MenuItem {
+            id: "pods".into(),
+            title: "Pods".into(),
+            k8s: Some(spec),
+            ..Default::default()
+        };
+        expand(&mut mi).unwrap();
+        mi
+    }
+
+    #[test]
+    fn k8s_items_expand_to_kubectl_commands_and_verbs() {
+        let mi = item(K8sSpec {
+            kind: "pods".into(),
+            namespace: Some("{slot.ns}".into()),
+            selector: Some("app=api".into()),
+            context: Some("prod".into()),
+        });
+        assert_eq!(
+            mi.command.as_deref(),
+            Some("kubectl get pods -o json -n {slot.ns} -l 'app=api' --context prod")
+        );
+        assert_eq!(
+            (mi.widget.as_deref(), mi.unwrap.as_deref()),
+            (Some("lazy_items"), Some("items"))
+        );
+        let verbs: Vec<_> = mi
+            .verbs
+            .iter()
+            .flatten()
+            .map(|v| (v.key, v.command.as_str(), v.confirm))
+            .collect();
+        assert_eq!(
+            verbs,
+            [
+                (
+                    'l',
+                    "kubectl logs {name} --all-containers --tail 200 -n {namespace} --context prod",
+                    false
+                ),
+                (
+                    'd',
+                    "kubectl describe pod {name} -n {namespace} --context prod",
+                    false
+                ),
+                (
+                    'D',
+                    "kubectl delete pod {name} -n {namespace} --context prod",
+                    true
+                ),
+            ]
+        );
+
+        // Cluster-scoped kinds have no -n and no logs; own verbs keep their keys
+        let mut mi = MenuItem {
+            id: "nodes".into(),
+            title: "Nodes".into(),
+            k8s: Some(K8sSpec {
+                kind: "nodes".into(),
+                namespace: Some("all".into()),
+                ..Default::default()
+            }),
+            verbs: Some(vec![VerbSpec {
+                key: 'd',
+                title: "Drain".into(),
+                command: "kubectl drain {name}".into(),
+                confirm: true,
+            }]),
+            ..Default::default()
+        };
+        expand(&mut mi).unwrap();
+        assert_eq!(mi.command.as_deref(), Some("kubectl get nodes -o json -A"));
+        let verbs = mi.verbs.unwrap();
+        assert_eq!(verbs.len(), 2);
+        assert_eq!(verbs[0].title, "Drain");
+        assert_eq!(verbs[1].command, "kubectl delete node {name}");
+
+        let deploy = item(K8sSpec {
+            kind: "deploy".into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            deploy.verbs.unwrap()[0].command,
+            "kubectl logs deployment/{name} --tail 200 -n {namespace}"
+        );
+        assert_eq!(singular("ingresses"), "ingress");
+        assert_eq!(singular("services"), "service");
+        assert_eq!(singular("StorageClasses"), "storageclass");
+        assert_eq!(singular("networkpolicies"), "networkpolicy");
+    }
+
+    #[test]
+    fn rows_lift_name_namespace_and_phase() {
+        let pod = row(json!({
+            "kind": "Pod",
+            "metadata": {"name": "api-7f9", "namespace": "prod", "creationTimestamp": "2024-06-01T12:00:00Z"},
+            "status": {
+                "phase": "Running",
+                "containerStatuses": [{"state": {"waiting": {"reason": "CrashLoopBackOff"}}}]
+            }
+        }));
+        assert_eq!(pod["name"], "api-7f9");
+        assert_eq!(pod["namespace"], "prod");
+        assert_eq!(pod["phase"], "CrashLoopBackOff");
+        assert_eq!(pod["created"], "2024-06-01T12:00:00Z");
+        assert_eq!(pod["status"]["phase"], "Running");
+
+        let deploy = row(json!({
+            "metadata": {"name": "api"},
+            "spec": {"replicas": 3},
+            "status": {"readyReplicas": 2}
+        }));
+        assert_eq!(deploy["phase"], "2/3");
+        assert_eq!(crate::ui::title_from_value(&deploy), "api");
+    }
+}
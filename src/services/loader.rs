@@ -1,19 +1,22 @@
+use crate::chi_core::envelope::{self, Envelope};
 use crate::model::MenuItem;
+use crate::services::bus::Reply;
 use crate::services::cli_runner::run_cmdline_to_json;
 use anyhow::{anyhow, Result};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::mpsc::Sender;
 use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
 pub enum Loaded {
     Items(Vec<JsonValue>),
+    // Rows with the envelope's pagination (null when it had none) or `content_type`
     ItemsWithPagination {
         items: Vec<JsonValue>,
         pagination: JsonValue,
+        content_type: Option<String>,
     },
     Fallback(JsonValue),
 }
@@ -49,13 +52,7 @@ fn options_ttl() -> Option<Duration> {
     }
 }
 
-pub fn spawn_load_options_cmd(
-    cmdline: String,
-    unwrap: Option<String>,
-    key: String,
-    force: bool,
-    tx: Sender<crate::ui::LoadMsg>,
-) {
+pub fn spawn_load_options_cmd(cmdline: String, unwrap: Option<String>, force: bool, reply: Reply) {
     thread::spawn(move || {
         let outcome = (|| -> Result<crate::ui::LoadOutcome, String> {
             let cache_key = format!("{}|{}", cmdline, unwrap.clone().unwrap_or_default());
@@ -88,11 +85,7 @@ pub fn spawn_load_options_cmd(
             }
             Ok(crate::ui::LoadOutcome::Fallback(out))
         })();
-        let _ = tx.send(crate::ui::LoadMsg {
-            key,
-            outcome,
-            kind: crate::ui::LoadKind::FormOptions,
-        });
+        reply.send(outcome);
     });
 }
 
@@ -137,9 +130,10 @@ pub(crate) fn parse_options_from_json(
         }
         return out;
     }
+    let env = Envelope::parse(v).unwrap_or_default();
     if let Some(arr) = get_by_path(v, uw)
-        .or_else(|| v.get("data").and_then(|d| d.get("items")))
         .and_then(|x| x.as_array())
+        .or_else(|| env.items())
     {
         for item in arr {
             if let Some(s) = item.as_str() {
@@ -173,95 +167,128 @@ pub(crate) fn parse_options_from_json(
 }
 
 pub fn load_lazy_children_cmd(mi: &MenuItem) -> Result<Loaded> {
+    let loaded = load_menu_rows(mi)?;
+    Ok(with_time_fields(
+        loaded,
+        mi.time_fields.as_deref().unwrap_or_default(),
+    ))
+}
+
+fn load_menu_rows(mi: &MenuItem) -> Result<Loaded> {
+    #[cfg(feature = "db")]
+    if let Some(spec) = &mi.db {
+        return crate::services::db::load(mi, spec);
+    }
     let cmdline = mi
         .command
         .as_ref()
         .ok_or_else(|| anyhow!("No command configured for '{}'.", mi.title))?;
-    let v = run_cmdline_to_json(cmdline)?;
-    let target = if let Some(path) = mi.unwrap.as_deref() {
-        get_by_path(&v, path)
-    } else {
-        v.get("data").and_then(|d| d.get("items"))
+    let v = crate::services::result_cache::run_cached(cmdline)?;
+    match rows(v, mi.unwrap.as_deref(), cmdline)? {
+        Loaded::Items(arr) if mi.k8s.is_some() => Ok(Loaded::Items(
+            arr.into_iter().map(crate::services::k8s::row).collect(),
+        )),
+        Loaded::ItemsWithPagination {
+            items,
+            pagination,
+            content_type,
+        } if mi.k8s.is_some() => Ok(Loaded::ItemsWithPagination {
+            items: items.into_iter().map(crate::services::k8s::row).collect(),
+            pagination,
+            content_type,
+        }),
+        other => Ok(other),
+    }
+}
+
+// Rows of a command's output: the array at the `unwrap:` path, or the envelope's
+// `data.items` with its pagination; anything else is shown as is
+fn rows(v: JsonValue, unwrap: Option<&str>, cmdline: &str) -> Result<Loaded> {
+    if let Some(path) = unwrap {
+        return Ok(match get_by_path(&v, path).and_then(|x| x.as_array()) {
+            Some(arr) => Loaded::Items(arr.clone()),
+            None => Loaded::Fallback(v),
+        });
+    }
+    let env = Envelope::parse(&v).map_err(|e| anyhow!("{e}\n(output of {cmdline})"))?;
+    let Some(items) = env.items().cloned() else {
+        return Ok(Loaded::Fallback(v));
     };
+    Ok(match (env.pagination, env.content_type) {
+        (None, None) => Loaded::Items(items),
+        (pagination, content_type) => Loaded::ItemsWithPagination {
+            items,
+            pagination: serde_json::to_value(pagination)?,
+            content_type,
+        },
+    })
+}
 
-    // Check for pagination metadata
-    let pagination = v.get("data").and_then(|d| d.get("pagination"));
+pub fn load_lazy_children_value_cmd(val: &JsonValue) -> Result<Loaded> {
+    let specs: Vec<crate::model::TimeFieldSpec> = val
+        .get("time_fields")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    Ok(with_time_fields(load_value_rows(val)?, &specs))
+}
 
-    if let Some(arr) = target.and_then(|x| x.as_array()) {
-        if let Some(pagination_data) = pagination {
-            Ok(Loaded::ItemsWithPagination {
-                items: arr.clone(),
-                pagination: pagination_data.clone(),
-            })
-        } else {
-            Ok(Loaded::Items(arr.clone()))
+// `time_fields:` of the item applied to the rows it loaded
+fn with_time_fields(loaded: Loaded, specs: &[crate::model::TimeFieldSpec]) -> Loaded {
+    match loaded {
+        Loaded::Items(mut items) => {
+            crate::services::time_fields::normalize(&mut items, specs);
+            Loaded::Items(items)
         }
-    } else {
-        Ok(Loaded::Fallback(v))
+        Loaded::ItemsWithPagination {
+            mut items,
+            pagination,
+            content_type,
+        } => {
+            crate::services::time_fields::normalize(&mut items, specs);
+            Loaded::ItemsWithPagination {
+                items,
+                pagination,
+                content_type,
+            }
+        }
+        other => other,
     }
 }
 
-pub fn load_lazy_children_value_cmd(val: &JsonValue) -> Result<Loaded> {
+fn load_value_rows(val: &JsonValue) -> Result<Loaded> {
     let cmdline = val
         .get("command")
         .and_then(|s| s.as_str())
         .ok_or_else(|| anyhow!("No command configured for this node"))?;
-    let v = run_cmdline_to_json(cmdline)?;
-    let target = if let Some(path) = val.get("unwrap").and_then(|s| s.as_str()) {
-        get_by_path(&v, path)
-    } else {
-        v.get("data").and_then(|d| d.get("items"))
-    };
-
-    // Check for pagination metadata
-    let pagination = v.get("data").and_then(|d| d.get("pagination"));
-
-    if let Some(arr) = target.and_then(|x| x.as_array()) {
-        if let Some(pagination_data) = pagination {
-            Ok(Loaded::ItemsWithPagination {
-                items: arr.clone(),
-                pagination: pagination_data.clone(),
-            })
-        } else {
-            Ok(Loaded::Items(arr.clone()))
-        }
-    } else {
-        Ok(Loaded::Fallback(v))
-    }
+    let v = crate::services::result_cache::run_cached(cmdline)?;
+    rows(v, val.get("unwrap").and_then(|s| s.as_str()), cmdline)
 }
 
-// Panel helpers: load panel content (cmd or yaml) and send via LoadMsg
+// Panel helpers: load panel content (cmd or yaml) and send it back through `reply`
 pub fn spawn_load_panel_cmd(
     cmdline: String,
-    kind: crate::ui::LoadKind,
-    tx: Sender<crate::ui::LoadMsg>,
+    reply: Reply,
+    ticket: Option<crate::services::mutexes::Ticket>,
 ) {
     thread::spawn(move || {
-        let outcome: Result<crate::ui::LoadOutcome, String> = match run_cmdline_to_json(&cmdline) {
-            Ok(v) => Ok(crate::ui::LoadOutcome::Fallback(v)),
-            Err(e) => Err(format!("{e}")),
-        };
-        let key = match kind {
-            crate::ui::LoadKind::PanelA => "panel:A",
-            crate::ui::LoadKind::PanelB => "panel:B",
-            _ => "panel:?",
-        };
-        let _ = tx.send(crate::ui::LoadMsg {
-            key: key.to_string(),
-            outcome,
-            kind,
-        });
+        if let Some(t) = &ticket {
+            t.wait();
+        }
+        let outcome: Result<crate::ui::LoadOutcome, String> =
+            match crate::services::result_cache::run_cached(&cmdline) {
+                Ok(v) => Ok(crate::ui::LoadOutcome::Fallback(v)),
+                Err(e) => Err(format!("{e}")),
+            };
+        drop(ticket);
+        reply.send(outcome);
     });
 }
 
 #[cfg(test)]
 mod loader_tests;
 
-pub fn spawn_load_panel_yaml(
-    path: String,
-    kind: crate::ui::LoadKind,
-    tx: Sender<crate::ui::LoadMsg>,
-) {
+pub fn spawn_load_panel_yaml(path: String, reply: Reply) {
     thread::spawn(move || {
         let outcome = (|| -> Result<crate::ui::LoadOutcome, String> {
             let full_path = {
@@ -279,25 +306,46 @@ pub fn spawn_load_panel_yaml(
             };
             let s = std::fs::read_to_string(&full_path)
                 .map_err(|e| format!("reading {full_path:?}: {e}"))?;
-            let v: serde_json::Value = serde_yaml::from_str(&s).map_err(|e| {
-                if let Some(loc) = e.location() {
-                    format!("{}:{}:{}: {}", path, loc.line(), loc.column(), e)
-                } else {
-                    format!("{path}: {e}")
-                }
-            })?;
+            let v: serde_json::Value =
+                crate::chi_core::vars::parse(&s, &full_path).map_err(|e| {
+                    if let Some(loc) = e.location() {
+                        format!("{}:{}:{}: {}", path, loc.line(), loc.column(), e)
+                    } else {
+                        format!("{path}: {e}")
+                    }
+                })?;
             Ok(crate::ui::LoadOutcome::Fallback(v))
         })();
-        let key = match kind {
-            crate::ui::LoadKind::PanelA => "panel:A",
-            crate::ui::LoadKind::PanelB => "panel:B",
-            _ => "panel:?",
-        };
-        let _ = tx.send(crate::ui::LoadMsg {
-            key: key.to_string(),
-            outcome,
-            kind,
-        });
+        reply.send(outcome);
+    });
+}
+
+// Inline cell edit: run the item's `update_cmd`. A JSON reply on stdout patches the row;
+// other output is ignored (Null)
+pub fn spawn_update_row(cmdline: String, reply: Reply) {
+    thread::spawn(move || {
+        let outcome = (|| -> Result<crate::ui::LoadOutcome, String> {
+            let expanded = crate::services::interpolate::env(&cmdline);
+            let parts = shlex::split(&expanded)
+                .filter(|p| !p.is_empty())
+                .ok_or("Failed to parse command line")?;
+            let output = std::process::Command::new(&parts[0])
+                .args(&parts[1..])
+                .env("CHI_TUI_JSON", "1")
+                .env("CHI_TUI_ENVELOPE", envelope::VERSION.to_string())
+                .output()
+                .map_err(|e| format!("{}: {e}", parts[0]))?;
+            if !output.status.success() {
+                return Err(format!(
+                    "{}\n{}",
+                    crate::services::cli_runner::failure_message(&cmdline, output.status.code()),
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+            let patch = serde_json::from_slice(&output.stdout).unwrap_or(serde_json::Value::Null);
+            Ok(crate::ui::LoadOutcome::Fallback(patch))
+        })();
+        reply.send(outcome);
     });
 }
 
@@ -305,80 +353,139 @@ pub fn spawn_load_panel_yaml(
 // or stderr (error envelope). Send the JSON back as Fallback so UI can decide.
 pub fn spawn_submit_form(
     cmdline: String,
-    kind: crate::ui::LoadKind,
-    tx: Sender<crate::ui::LoadMsg>,
+    reply: Reply,
+    ticket: Option<crate::services::mutexes::Ticket>,
 ) {
     thread::spawn(move || {
-        let outcome = (|| -> Result<crate::ui::LoadOutcome, String> {
-            let parts =
-                shlex::split(&cmdline).ok_or_else(|| "Failed to parse command line".to_string())?;
-            if parts.is_empty() {
-                return Err("Empty command".into());
-            }
-            let program = &parts[0];
-            let args = &parts[1..];
-            let output = std::process::Command::new(program)
-                .args(args)
-                .env("CHI_TUI_JSON", "1")
-                .output()
-                .map_err(|e| format!("spawn: {e}"))?;
-            if output.status.success() {
-                let text = String::from_utf8_lossy(&output.stdout).to_string();
-                let v: JsonValue =
-                    serde_json::from_str(&text).map_err(|e| format!("parse json: {e}"))?;
-                Ok(crate::ui::LoadOutcome::Fallback(v))
-            } else {
-                // Try parse stderr as JSON error envelope; fallback to plain text
-                let err_text = String::from_utf8_lossy(&output.stderr).to_string();
-                if let Ok(v) = serde_json::from_str::<JsonValue>(&err_text) {
-                    Ok(crate::ui::LoadOutcome::Fallback(v))
-                } else {
-                    Err(format!("Command failed: {cmdline}\n{err_text}"))
-                }
+        if let Some(t) = &ticket {
+            t.wait();
+        }
+        let outcome = submit_once(&cmdline);
+        drop(ticket);
+        reply.send(outcome);
+    });
+}
+
+// Batch form submission: the rows run one after another on a single thread, each
+// reported through its own reply as it finishes; a `mutex:` ticket is held for the whole batch
+pub fn spawn_batch_submit(
+    rows: Vec<(String, Reply)>,
+    ticket: Option<crate::services::mutexes::Ticket>,
+) {
+    thread::spawn(move || {
+        if let Some(t) = &ticket {
+            t.wait();
+        }
+        for (cmdline, reply) in rows {
+            if !reply.send(submit_once(&cmdline)) {
+                break;
             }
-        })();
-        let _ = tx.send(crate::ui::LoadMsg {
-            key: "panel:B".into(),
-            outcome,
-            kind,
-        });
+        }
+    });
+}
+
+fn submit_once(cmdline: &str) -> Result<crate::ui::LoadOutcome, String> {
+    let parts = shlex::split(cmdline).ok_or_else(|| "Failed to parse command line".to_string())?;
+    if parts.is_empty() {
+        return Err("Empty command".into());
+    }
+    let program = &parts[0];
+    let args = &parts[1..];
+    let output = std::process::Command::new(program)
+        .args(args)
+        .env("CHI_TUI_JSON", "1")
+        .env("CHI_TUI_ENVELOPE", envelope::VERSION.to_string())
+        .output()
+        .map_err(|e| format!("spawn: {e}"))?;
+    if output.status.success() {
+        let text = String::from_utf8_lossy(&output.stdout).to_string();
+        let v: JsonValue =
+            serde_json::from_str(&text).map_err(|e| envelope::not_json(cmdline, &text, &e))?;
+        Ok(crate::ui::LoadOutcome::Fallback(v))
+    } else {
+        // Try parse stderr as JSON error envelope; fallback to plain text
+        let err_text = String::from_utf8_lossy(&output.stderr).to_string();
+        if let Ok(v) = serde_json::from_str::<JsonValue>(&err_text) {
+            Ok(crate::ui::LoadOutcome::Fallback(v))
+        } else {
+            Err(format!(
+                "{}\n{err_text}",
+                crate::services::cli_runner::failure_message(cmdline, output.status.code())
+            ))
+        }
+    }
+}
+
+// Run a scheduled job in the background; the UI records the outcome in the schedule history
+pub fn spawn_scheduled_cmd(
+    cmdline: String,
+    reply: Reply,
+    ticket: Option<crate::services::mutexes::Ticket>,
+) {
+    thread::spawn(move || {
+        if let Some(t) = &ticket {
+            t.wait();
+        }
+        let outcome = run_cmdline_to_json(&cmdline)
+            .map(crate::ui::LoadOutcome::Fallback)
+            .map_err(|e| format!("{e}"));
+        drop(ticket);
+        reply.send(outcome);
+    });
+}
+
+// Checks run without user input; one that hangs is given up after this
+const CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Startup check, enter/leave hook, `auth:` session check or `auth_retry:` refresh; the first
+// output line travels as a JSON string
+pub fn spawn_check(cmdline: String, reply: Reply) {
+    thread::spawn(move || {
+        let outcome = crate::services::cli_runner::run_check(&cmdline, CHECK_TIMEOUT)
+            .map(|line| crate::ui::LoadOutcome::Fallback(JsonValue::String(line)));
+        reply.send(outcome);
+    });
+}
+
+// `when_cmd` / `enabled_cmd`: the whole stdout (an envelope may say `ok: false`)
+pub fn spawn_condition(cmdline: String, reply: Reply) {
+    thread::spawn(move || {
+        let outcome = crate::services::cli_runner::run_cmdline_to_text(&cmdline)
+            .map(|out| crate::ui::LoadOutcome::Fallback(JsonValue::String(out)))
+            .map_err(|e| format!("{e}"));
+        reply.send(outcome);
     });
 }
 
 // Async wrappers used by autoload to fetch children off-thread and report back
-pub fn spawn_load_for_menu(mi: MenuItem, key: String, tx: Sender<crate::ui::LoadMsg>) {
+pub fn spawn_load_for_menu(mi: MenuItem, reply: Reply) {
     thread::spawn(move || {
-        let outcome: Result<crate::ui::LoadOutcome, String> = match load_lazy_children_cmd(&mi) {
-            Ok(Loaded::Items(arr)) => Ok(crate::ui::LoadOutcome::Items(arr)),
-            Ok(Loaded::ItemsWithPagination { items, pagination }) => {
-                Ok(crate::ui::LoadOutcome::ItemsWithPagination { items, pagination })
-            }
-            Ok(Loaded::Fallback(v)) => Ok(crate::ui::LoadOutcome::Fallback(v)),
-            Err(e) => Err(format!("{e}")),
-        };
-        let _ = tx.send(crate::ui::LoadMsg {
-            key,
-            outcome,
-            kind: crate::ui::LoadKind::Menu,
-        });
+        let outcome = load_lazy_children_cmd(&mi)
+            .map(menu_outcome)
+            .map_err(|e| format!("{e}"));
+        reply.send(outcome);
     });
 }
 
-pub fn spawn_load_for_value(val: serde_json::Value, key: String, tx: Sender<crate::ui::LoadMsg>) {
+pub fn spawn_load_for_value(val: serde_json::Value, reply: Reply) {
     thread::spawn(move || {
-        let outcome: Result<crate::ui::LoadOutcome, String> =
-            match load_lazy_children_value_cmd(&val) {
-                Ok(Loaded::Items(arr)) => Ok(crate::ui::LoadOutcome::Items(arr)),
-                Ok(Loaded::ItemsWithPagination { items, pagination }) => {
-                    Ok(crate::ui::LoadOutcome::ItemsWithPagination { items, pagination })
-                }
-                Ok(Loaded::Fallback(v)) => Ok(crate::ui::LoadOutcome::Fallback(v)),
-                Err(e) => Err(format!("{e}")),
-            };
-        let _ = tx.send(crate::ui::LoadMsg {
-            key,
-            outcome,
-            kind: crate::ui::LoadKind::Child,
-        });
+        let outcome = load_lazy_children_value_cmd(&val)
+            .map(menu_outcome)
+            .map_err(|e| format!("{e}"));
+        reply.send(outcome);
     });
 }
+
+// Rows for the menu: the pagination travels along when there is one
+fn menu_outcome(loaded: Loaded) -> crate::ui::LoadOutcome {
+    match loaded {
+        Loaded::Items(items) => crate::ui::LoadOutcome::Items(items),
+        Loaded::ItemsWithPagination {
+            items, pagination, ..
+        } if pagination.is_null() => crate::ui::LoadOutcome::Items(items),
+        Loaded::ItemsWithPagination {
+            items, pagination, ..
+        } => crate::ui::LoadOutcome::ItemsWithPagination { items, pagination },
+        Loaded::Fallback(v) => crate::ui::LoadOutcome::Fallback(v),
+    }
+}
@@ -43,3 +43,36 @@ fn parse_options_supports_multiple_unwrap_patterns() {
     assert_eq!(pairs2[1].0, "normal");
     assert_eq!(pairs2[1].1, "normal");
 }
+
+#[test]
+fn raw_json_that_looks_like_an_envelope_is_shown_as_is() {
+    let raw = json!({"ok": 1, "error": false, "type": ["string", "null"]});
+    assert!(matches!(
+        rows(raw.clone(), None, "app schema"),
+        Ok(Loaded::Fallback(v)) if v == raw
+    ));
+    let Err(err) = rows(json!({"ok": true, "error": 3}), None, "app list") else {
+        panic!("a malformed envelope is an error");
+    };
+    assert!(err.to_string().ends_with("(output of app list)"), "{err}");
+}
+
+#[test]
+fn a_queued_submit_waits_for_the_mutex() {
+    let mut st = crate::ui::AppState::default();
+    let (tx, rx) = std::sync::mpsc::channel();
+    st.tx = Some(tx);
+    let reply = crate::services::bus::subscribe(&mut st, "panel:B", |_, _| Vec::new()).unwrap();
+    let (running, _) = crate::services::mutexes::enqueue("test-submit");
+    let (queued, ahead) = crate::services::mutexes::enqueue("test-submit");
+    assert_eq!(ahead, 1);
+    spawn_submit_form("echo {}".into(), reply, Some(queued));
+    let wait = Duration::from_millis(200);
+    assert!(rx.recv_timeout(wait).is_err());
+    drop(running);
+    let msg = rx.recv_timeout(Duration::from_secs(5)).expect("submitted");
+    assert!(matches!(
+        msg.outcome,
+        Ok(crate::ui::LoadOutcome::Fallback(_))
+    ));
+}
@@ -1,2 +1,22 @@
+pub mod auth;
+pub mod bus;
 pub mod cli_runner;
+pub mod clock;
+pub mod completion;
+pub mod copy_formats;
+pub mod csv;
+#[cfg(feature = "db")]
+pub mod db;
+pub mod html_export;
+pub mod image_export;
+pub mod interpolate;
+pub mod k8s;
 pub mod loader;
+pub mod mutexes;
+pub mod notes;
+pub mod result_cache;
+pub mod scheduler;
+pub mod setup_wizard;
+pub mod telemetry;
+pub mod terminal_bg;
+pub mod time_fields;
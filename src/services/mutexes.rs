@@ -0,0 +1,99 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Condvar, Mutex, OnceLock};
+
+// Named command mutexes: items sharing `mutex: <name>` never run concurrently.
+// Every run takes a ticket on the UI thread (so the queue order is the trigger
+// order) and its worker thread waits until the ticket reaches the front.
+
+#[derive(Default)]
+struct Queues {
+    next_id: u64,
+    by_name: HashMap<String, VecDeque<u64>>,
+}
+
+fn queues() -> &'static (Mutex<Queues>, Condvar) {
+    static QUEUES: OnceLock<(Mutex<Queues>, Condvar)> = OnceLock::new();
+    QUEUES.get_or_init(|| (Mutex::new(Queues::default()), Condvar::new()))
+}
+
+// Place in a mutex queue; dropping it releases the mutex (or leaves the queue)
+pub struct Ticket {
+    name: String,
+    id: u64,
+}
+
+// Returns the ticket and how many runs are ahead of it
+pub fn enqueue(name: &str) -> (Ticket, usize) {
+    let (lock, _) = queues();
+    let mut q = lock.lock().unwrap_or_else(|e| e.into_inner());
+    q.next_id += 1;
+    let id = q.next_id;
+    let queue = q.by_name.entry(name.to_string()).or_default();
+    queue.push_back(id);
+    let ahead = queue.len() - 1;
+    (
+        Ticket {
+            name: name.to_string(),
+            id,
+        },
+        ahead,
+    )
+}
+
+impl Ticket {
+    // Block until every earlier run holding this mutex has finished
+    pub fn wait(&self) {
+        let (lock, cvar) = queues();
+        let mut q = lock.lock().unwrap_or_else(|e| e.into_inner());
+        while q.by_name.get(&self.name).and_then(|v| v.front()) != Some(&self.id) {
+            q = cvar.wait(q).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        let (lock, cvar) = queues();
+        let mut q = lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(queue) = q.by_name.get_mut(&self.name) {
+            queue.retain(|id| *id != self.id);
+            if queue.is_empty() {
+                q.by_name.remove(&self.name);
+            }
+        }
+        cvar.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn same_name_runs_queue_in_trigger_order() {
+        let (first, ahead) = enqueue("test-deploy");
+        assert_eq!(ahead, 0);
+        let (second, ahead) = enqueue("test-deploy");
+        assert_eq!(ahead, 1);
+        // Another name is independent
+        let (other, ahead) = enqueue("test-other");
+        assert_eq!(ahead, 0);
+        other.wait();
+
+        let ran = Arc::new(AtomicBool::new(false));
+        let ran2 = ran.clone();
+        let h = std::thread::spawn(move || {
+            second.wait();
+            ran2.store(true, Ordering::SeqCst);
+        });
+        first.wait();
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!ran.load(Ordering::SeqCst));
+        drop(first);
+        h.join().unwrap();
+        assert!(ran.load(Ordering::SeqCst));
+        assert_eq!(enqueue("test-deploy").1, 0);
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Free-text notes attached to menu items and result rows ('n'). They are keyed like the menu
+// rows (`menu:<id>`, `menu:<id>/<row id>`) and kept in `.chi-notes.json` of the config dir, so
+// each workspace has its own. Rows without an `id` are keyed by position (`/#3`).
+
+pub const FILE_NAME: &str = ".chi-notes.json";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub text: String,
+    // Unix seconds of the last edit
+    pub updated: i64,
+}
+
+#[derive(Debug, Default)]
+pub struct Notes {
+    // None: nowhere to save (no config dir), notes last for the session only
+    path: Option<PathBuf>,
+    notes: BTreeMap<String, Note>,
+}
+
+impl Notes {
+    // Notes of a config dir; a missing or unreadable file starts empty
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(FILE_NAME);
+        let notes = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Notes {
+            path: Some(path),
+            notes,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Note> {
+        self.notes.get(key)
+    }
+
+    pub fn has(&self, key: &str) -> bool {
+        self.notes.contains_key(key)
+    }
+
+    // Set the note of `key` (blank text removes it) and write the file
+    pub fn set(&mut self, key: &str, text: &str, now: SystemTime) -> Result<(), String> {
+        let text = text.trim();
+        if text.is_empty() {
+            self.notes.remove(key);
+        } else {
+            let note = Note {
+                text: text.to_string(),
+                updated: crate::services::clock::unix_secs(now),
+            };
+            self.notes.insert(key.to_string(), note);
+        }
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let text = serde_json::to_string_pretty(&self.notes).map_err(|e| e.to_string())?;
+        std::fs::write(path, text + "\n").map_err(|e| format!("{}: {e}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_round_trip_through_the_config_dir() {
+        let dir = std::env::temp_dir().join(format!("chi-notes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let _ = std::fs::remove_file(dir.join(FILE_NAME));
+        let now = crate::services::clock::from_unix(1_700_000_000);
+
+        let mut notes = Notes::load(&dir);
+        assert!(!notes.has("menu:pods"));
+        notes.set("menu:pods", "  flaky on Mondays ", now).unwrap();
+        notes.set("menu:pods/api-1", "owner: team-a", now).unwrap();
+
+        let reloaded = Notes::load(&dir);
+        assert!(reloaded.has("menu:pods/api-1"));
+        let note = reloaded.get("menu:pods").unwrap();
+        assert_eq!(note.text, "flaky on Mondays");
+        assert_eq!(note.updated, 1_700_000_000);
+
+        // Blank text deletes the note
+        notes.set("menu:pods", " ", now).unwrap();
+        let reloaded = Notes::load(&dir);
+        assert!(!reloaded.has("menu:pods"));
+        assert!(reloaded.has("menu:pods/api-1"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
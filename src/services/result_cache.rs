@@ -0,0 +1,182 @@
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+// On-disk cache of command results (`cache:`), one JSON file per expanded cmdline.
+// Failed commands fall back to the cached result; offline mode serves it without
+// running the command. Results served from the cache are remembered as stale
+// (cmdline -> saved-at) so the UI can flag them.
+
+static DIR: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+static STALE: OnceLock<Mutex<HashMap<String, i64>>> = OnceLock::new();
+
+fn dir_slot() -> &'static Mutex<Option<PathBuf>> {
+    DIR.get_or_init(|| Mutex::new(None))
+}
+
+fn stale() -> &'static Mutex<HashMap<String, i64>> {
+    STALE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Apply the entry config's `cache:` (None disables caching and offline mode)
+pub fn configure(cfg: Option<&crate::model::CacheConfig>) {
+    let dir = cfg.map(|c| match &c.dir {
+        Some(d) => {
+            let p = PathBuf::from(d);
+            match std::env::var("CHI_TUI_CONFIG_DIR") {
+                Ok(base) if p.is_relative() => PathBuf::from(base).join(p),
+                _ => p,
+            }
+        }
+        None => default_dir(),
+    });
+    OFFLINE.store(cfg.is_some_and(|c| c.offline), Ordering::Relaxed);
+    *dir_slot().lock().unwrap() = dir;
+}
+
+fn default_dir() -> PathBuf {
+    let base = std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|h| PathBuf::from(h).join(".cache")))
+        .unwrap_or_else(|_| std::env::temp_dir());
+    base.join("chi-tui")
+}
+
+pub fn enabled() -> bool {
+    dir_slot().lock().unwrap().is_some()
+}
+
+pub fn offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+pub fn set_offline(on: bool) {
+    OFFLINE.store(on, Ordering::Relaxed);
+}
+
+// FNV-1a: stable across builds, unlike the std hasher
+fn file_name(cmdline: &str) -> String {
+    let expanded = crate::services::interpolate::env(cmdline);
+    let hash = expanded.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}.json")
+}
+
+fn path(cmdline: &str) -> Option<PathBuf> {
+    let dir = dir_slot().lock().unwrap().clone()?;
+    Some(dir.join(file_name(cmdline)))
+}
+
+// Save a fresh result; it is no longer stale
+pub fn store(cmdline: &str, value: &JsonValue) {
+    let Some(path) = path(cmdline) else {
+        return;
+    };
+    stale().lock().unwrap().remove(cmdline);
+    let entry = json!({
+        "cmdline": cmdline,
+        "saved_at": crate::services::clock::unix_secs(SystemTime::now()),
+        "value": value,
+    });
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(path, entry.to_string());
+}
+
+// Cached result and when it was saved
+pub fn lookup(cmdline: &str) -> Option<(JsonValue, i64)> {
+    let text = std::fs::read_to_string(path(cmdline)?).ok()?;
+    let mut entry: JsonValue = serde_json::from_str(&text).ok()?;
+    let saved_at = entry.get("saved_at")?.as_i64()?;
+    Some((entry.get_mut("value")?.take(), saved_at))
+}
+
+// Serve the cached result in place of a fresh one, remembering it as stale
+pub fn fallback(cmdline: &str) -> Option<JsonValue> {
+    let (value, saved_at) = lookup(cmdline)?;
+    stale()
+        .lock()
+        .unwrap()
+        .insert(cmdline.to_string(), saved_at);
+    Some(value)
+}
+
+// Offline mode: the cached result, without running the command
+pub fn offline_hit(cmdline: &str) -> Option<JsonValue> {
+    if offline() {
+        fallback(cmdline)
+    } else {
+        None
+    }
+}
+
+// When the result currently held for `cmdline` was cached, if it came from the cache
+pub fn stale_since(cmdline: &str) -> Option<i64> {
+    stale().lock().unwrap().get(cmdline).copied()
+}
+
+#[cfg(test)]
+pub fn mark_stale(cmdline: &str, saved_at: i64) {
+    stale()
+        .lock()
+        .unwrap()
+        .insert(cmdline.to_string(), saved_at);
+}
+
+// run_cmdline_to_json through the cache
+pub fn run_cached(cmdline: &str) -> anyhow::Result<JsonValue> {
+    run_cached_with(cmdline, crate::services::cli_runner::run_cmdline_to_json)
+}
+
+// A command whose output `run` turns into JSON, through the cache
+pub fn run_cached_with(
+    cmdline: &str,
+    run: impl FnOnce(&str) -> anyhow::Result<JsonValue>,
+) -> anyhow::Result<JsonValue> {
+    if let Some(v) = offline_hit(cmdline) {
+        return Ok(v);
+    }
+    match run(cmdline) {
+        Ok(v) => {
+            store(cmdline, &v);
+            Ok(v)
+        }
+        Err(e) => fallback(cmdline).ok_or(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_and_offline_runs_serve_the_cached_result() {
+        let dir = std::env::temp_dir().join(format!("chi-tui-cache-{}", std::process::id()));
+        configure(Some(&crate::model::CacheConfig {
+            dir: Some(dir.to_string_lossy().into_owned()),
+            offline: false,
+        }));
+        let cmd = "sh -c 'echo {\\\"n\\\": 1}'";
+        assert_eq!(run_cached(cmd).unwrap(), json!({"n": 1}));
+        assert_eq!(stale_since(cmd), None);
+
+        // A failing command with a cached result falls back to it, flagged stale
+        store("false", &json!([1, 2]));
+        assert_eq!(run_cached("false").unwrap(), json!([1, 2]));
+        assert!(stale_since("false").is_some());
+        assert!(run_cached("false --never-cached").is_err());
+
+        set_offline(true);
+        assert_eq!(offline_hit(cmd), Some(json!({"n": 1})));
+        assert!(stale_since(cmd).is_some());
+        configure(None);
+        assert!(!offline() && offline_hit(cmd).is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,354 @@
+use crate::services::clock::{civil_from_days, from_unix, local_offset_secs, unix_secs};
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+// Scheduled execution of menu commands ('t' on an item, listed on the Scheduled screen).
+// Jobs live in memory for the session; due jobs are handed to the UI loop which runs
+// them off-thread and records the outcome in `history`.
+
+const MAX_HISTORY: usize = 50;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schedule {
+    Once(SystemTime),
+    Cron(Cron),
+}
+
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: u64,
+    pub title: String,
+    pub cmdline: String,
+    // `mutex:` of the scheduled item; every run queues on it
+    pub mutex: Option<String>,
+    // What the user typed (shown on the Scheduled screen)
+    pub spec: String,
+    pub schedule: Schedule,
+    pub next_run: SystemTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct Finished {
+    pub title: String,
+    pub at: SystemTime,
+    pub ok: bool,
+    pub summary: String,
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    pub jobs: Vec<Job>,
+    // Most recent first
+    pub history: VecDeque<Finished>,
+    // Job id -> title of runs that are in flight
+    running: Vec<(u64, String)>,
+    next_id: u64,
+}
+
+impl Scheduler {
+    pub fn add(
+        &mut self,
+        title: &str,
+        cmdline: &str,
+        mutex: Option<String>,
+        spec: &str,
+        now: SystemTime,
+    ) -> Result<&Job, String> {
+        let schedule = parse_schedule(spec, now, local_offset_secs())?;
+        let next_run = next_run(&schedule, now, local_offset_secs())
+            .ok_or_else(|| format!("'{spec}' never fires"))?;
+        self.next_id += 1;
+        self.jobs.push(Job {
+            id: self.next_id,
+            title: title.to_string(),
+            cmdline: cmdline.to_string(),
+            mutex,
+            spec: spec.trim().to_string(),
+            schedule,
+            next_run,
+        });
+        self.jobs.sort_by_key(|j| j.next_run);
+        Ok(self.jobs.iter().find(|j| j.id == self.next_id).unwrap())
+    }
+
+    pub fn cancel(&mut self, id: u64) -> Option<Job> {
+        let pos = self.jobs.iter().position(|j| j.id == id)?;
+        Some(self.jobs.remove(pos))
+    }
+
+    // Jobs whose time has come: one-shots are removed, cron jobs move to their next slot
+    pub fn take_due(&mut self, now: SystemTime) -> Vec<Job> {
+        let mut due = Vec::new();
+        let offset = local_offset_secs();
+        self.jobs.retain_mut(|j| {
+            if j.next_run > now {
+                return true;
+            }
+            due.push(j.clone());
+            match next_run(&j.schedule, now, offset) {
+                Some(t) if matches!(j.schedule, Schedule::Cron(_)) => {
+                    j.next_run = t;
+                    true
+                }
+                _ => false,
+            }
+        });
+        self.jobs.sort_by_key(|j| j.next_run);
+        self.running
+            .extend(due.iter().map(|j| (j.id, j.title.clone())));
+        due
+    }
+
+    pub fn finish(&mut self, id: u64, ok: bool, summary: String, now: SystemTime) -> Finished {
+        let title = match self.running.iter().position(|(i, _)| *i == id) {
+            Some(pos) => self.running.remove(pos).1,
+            None => format!("job #{id}"),
+        };
+        let done = Finished {
+            title,
+            at: now,
+            ok,
+            summary,
+        };
+        if self.history.len() >= MAX_HISTORY {
+            self.history.pop_back();
+        }
+        self.history.push_front(done.clone());
+        done
+    }
+}
+
+// Accepted forms: `10`, `10m`, `in 2h`, `in 30 minutes` (relative), `at 14:30` / `14:30`
+// (next occurrence, local time) and five-field cron strings, optionally prefixed by `cron`.
+pub fn parse_schedule(input: &str, now: SystemTime, offset: i64) -> Result<Schedule, String> {
+    let s = input.trim();
+    if s.is_empty() {
+        return Err("empty schedule".into());
+    }
+    let fields: Vec<&str> = s.split_whitespace().collect();
+    if fields.len() == 5 || (fields.len() == 6 && fields[0] == "cron") {
+        return Cron::parse(&fields[fields.len() - 5..].join(" ")).map(Schedule::Cron);
+    }
+    let rest = s.strip_prefix("at ").map(str::trim);
+    if let Some((h, m)) = parse_clock(rest.unwrap_or(s)) {
+        let local = unix_secs(now) + offset;
+        let day_start = local - local.rem_euclid(86_400);
+        let mut at = day_start + (h * 3600 + m * 60) as i64;
+        if at <= local {
+            at += 86_400;
+        }
+        return Ok(Schedule::Once(from_unix(at - offset)));
+    }
+    if let Some(rest) = rest {
+        return Err(format!("expected HH:MM after 'at', got '{rest}'"));
+    }
+    let rel = s.strip_prefix("in ").unwrap_or(s).replace(' ', "");
+    let split = rel.find(|c: char| !c.is_ascii_digit()).unwrap_or(rel.len());
+    let (num, unit) = rel.split_at(split);
+    let n: u64 = num
+        .parse()
+        .map_err(|_| format!("unrecognized schedule '{s}' (try 10m, at 14:30 or a cron string)"))?;
+    let secs = match unit {
+        "" | "m" | "min" | "mins" | "minute" | "minutes" => n * 60,
+        "s" | "sec" | "secs" | "second" | "seconds" => n,
+        "h" | "hour" | "hours" => n * 3600,
+        "d" | "day" | "days" => n * 86_400,
+        _ => return Err(format!("unknown unit '{unit}'")),
+    };
+    Ok(Schedule::Once(now + Duration::from_secs(secs)))
+}
+
+fn parse_clock(s: &str) -> Option<(u32, u32)> {
+    let (h, m) = s.split_once(':')?;
+    let (h, m) = (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?);
+    (h < 24 && m < 60).then_some((h, m))
+}
+
+// Next fire time strictly after `now` (one-shots in the past yield None)
+pub fn next_run(schedule: &Schedule, now: SystemTime, offset: i64) -> Option<SystemTime> {
+    match schedule {
+        Schedule::Once(t) => (*t > now).then_some(*t),
+        Schedule::Cron(c) => c.next_after(now, offset),
+    }
+}
+
+// Minimal cron: minute hour day-of-month month day-of-week with `*`, lists, ranges and steps
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_any: bool,
+    weekdays_any: bool,
+}
+
+impl Cron {
+    pub fn parse(s: &str) -> Result<Cron, String> {
+        let f: Vec<&str> = s.split_whitespace().collect();
+        if f.len() != 5 {
+            return Err(format!("cron needs 5 fields, got {}", f.len()));
+        }
+        let mut weekdays = field(f[4], 0, 7)?;
+        // 7 is another spelling of Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(Cron {
+            minutes: field(f[0], 0, 59)?,
+            hours: field(f[1], 0, 23)?,
+            days: field(f[2], 1, 31)?,
+            months: field(f[3], 1, 12)?,
+            weekdays,
+            days_any: f[2] == "*",
+            weekdays_any: f[4] == "*",
+        })
+    }
+
+    fn matches(&self, local_secs: i64) -> bool {
+        let days = local_secs.div_euclid(86_400);
+        let (_, month, day) = civil_from_days(days);
+        let secs = local_secs.rem_euclid(86_400);
+        let weekday = (days + 4).rem_euclid(7); // 1970-01-01 was a Thursday
+        let bit = |mask: u64, v: i64| mask & (1 << v) != 0;
+        let dom = bit(self.days, day as i64);
+        let dow = bit(self.weekdays, weekday);
+        // Like cron(8): with both day fields restricted either one may match
+        let day_ok = match (self.days_any, self.weekdays_any) {
+            (false, false) => dom || dow,
+            _ => dom && dow,
+        };
+        bit(self.minutes, secs / 60 % 60)
+            && bit(self.hours, secs / 3600)
+            && bit(self.months, month as i64)
+            && day_ok
+    }
+
+    fn next_after(&self, now: SystemTime, offset: i64) -> Option<SystemTime> {
+        let local = unix_secs(now) + offset;
+        let mut t = local - local.rem_euclid(60) + 60;
+        // Every valid expression fires within a leap cycle of minutes
+        for _ in 0..(4 * 366 * 24 * 60) {
+            if self.matches(t) {
+                return Some(from_unix(t - offset));
+            }
+            t += 60;
+        }
+        None
+    }
+}
+
+fn field(s: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in s.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, st)) => (
+                r,
+                st.parse::<u32>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("bad step in '{part}'"))?,
+            ),
+            None => (part, 1),
+        };
+        let num = |v: &str| {
+            v.parse::<u32>()
+                .ok()
+                .filter(|n| (min..=max).contains(n))
+                .ok_or_else(|| format!("'{v}' is outside {min}-{max}"))
+        };
+        let (lo, hi) = match range {
+            "*" => (min, max),
+            r => match r.split_once('-') {
+                Some((a, b)) => (num(a)?, num(b)?),
+                None if step > 1 => (num(r)?, max),
+                None => (num(r)?, num(r)?),
+            },
+        };
+        if lo > hi {
+            return Err(format!("empty range '{range}'"));
+        }
+        for v in (lo..=hi).step_by(step as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+// Compact time until `t`: `in 45s`, `in 9m`, `in 2h05m`, `in 3d`
+pub fn format_eta(t: SystemTime, now: SystemTime) -> String {
+    let secs = t.duration_since(now).map(|d| d.as_secs()).unwrap_or(0);
+    match secs {
+        0..=59 => format!("in {secs}s"),
+        60..=3599 => format!("in {}m", secs.div_ceil(60)),
+        3600..=86_399 => format!("in {}h{:02}m", secs / 3600, secs / 60 % 60),
+        _ => format!("in {}d", secs / 86_400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    // 2024-03-01 10:00:00 UTC, a Friday
+    const NOW: u64 = 1_709_287_200;
+
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(NOW)
+    }
+
+    fn at(schedule: &Schedule, offset: i64) -> i64 {
+        unix_secs(next_run(schedule, now(), offset).unwrap()) - NOW as i64
+    }
+
+    #[test]
+    fn parses_relative_clock_and_cron_schedules() {
+        assert_eq!(at(&parse_schedule("10", now(), 0).unwrap(), 0), 600);
+        assert_eq!(at(&parse_schedule("in 2h", now(), 0).unwrap(), 0), 7200);
+        assert_eq!(
+            at(&parse_schedule("in 30 seconds", now(), 0).unwrap(), 0),
+            30
+        );
+        // Clock times are local: 10:30 today, 09:00 tomorrow; UTC+2 shifts them back
+        assert_eq!(at(&parse_schedule("at 10:30", now(), 0).unwrap(), 0), 1800);
+        assert_eq!(
+            at(&parse_schedule("09:00", now(), 0).unwrap(), 0),
+            23 * 3600
+        );
+        assert_eq!(
+            at(&parse_schedule("13:00", now(), 7200).unwrap(), 7200),
+            3600
+        );
+        // Every 15 minutes; weekdays at 08:00 (Friday 10:00 -> Monday 08:00)
+        let every = parse_schedule("*/15 * * * *", now(), 0).unwrap();
+        assert_eq!(at(&every, 0), 900);
+        let weekdays = parse_schedule("cron 0 8 * * 1-5", now(), 0).unwrap();
+        assert_eq!(at(&weekdays, 0), 70 * 3600);
+        assert!(parse_schedule("soon", now(), 0).is_err());
+        assert!(parse_schedule("61 * * * *", now(), 0).is_err());
+        assert!(parse_schedule("at 25:00", now(), 0).is_err());
+    }
+
+    #[test]
+    fn due_jobs_are_taken_once_and_cron_jobs_rescheduled() {
+        let mut s = Scheduler::default();
+        let once = s.add("Backup", "app backup", None, "5m", now()).unwrap().id;
+        let cron = s
+            .add("Sync", "app sync", None, "* * * * *", now())
+            .unwrap()
+            .id;
+        assert!(s.take_due(now()).is_empty());
+        let later = now() + Duration::from_secs(600);
+        let due: Vec<u64> = s.take_due(later).iter().map(|j| j.id).collect();
+        assert_eq!(due, vec![cron, once]);
+        assert_eq!(s.jobs.len(), 1);
+        assert!(s.jobs[0].next_run > later);
+        let done = s.finish(once, false, "boom".into(), later);
+        assert_eq!(done.title, "Backup");
+        assert_eq!(s.history[0].summary, "boom");
+        assert!(s.cancel(cron).is_some());
+        assert!(s.jobs.is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+// First-run onboarding: when no chi-index.yaml is found, ask for the backing CLI,
+// probe `<cli> schema`, scaffold ./.tui/chi-index.yaml and load it.
+
+// Only offer the wizard to a human at a terminal (never in headless/CI runs)
+pub fn wizard_available() -> bool {
+    let headless = std::env::var("CHI_TUI_HEADLESS")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("yes"))
+        .unwrap_or(false);
+    !headless && std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+// Returns the directory holding the new chi-index.yaml, or None when the user declined
+pub fn run(not_found: &anyhow::Error) -> Result<Option<std::path::PathBuf>> {
+    let stdin = std::io::stdin();
+    let mut input = stdin.lock();
+    let mut out = std::io::stdout();
+    writeln!(out, "{not_found}\n")?;
+    if !confirm(&mut input, &mut out, "Run the setup wizard to create one?")? {
+        return Ok(None);
+    }
+    let default_cli = std::env::var("CHI_APP_BIN").unwrap_or_else(|_| "example-app".to_string());
+    let cli = loop {
+        let answer = ask(
+            &mut input,
+            &mut out,
+            &format!("Backing CLI command [{default_cli}]: "),
+        )?;
+        let cli = if answer.is_empty() {
+            default_cli.clone()
+        } else {
+            answer
+        };
+        if !cli.is_empty() {
+            break cli;
+        }
+    };
+    write!(out, "Probing `{cli} schema` ... ")?;
+    out.flush()?;
+    let schema = match crate::services::cli_runner::run_cmdline_to_json(&format!("{cli} schema")) {
+        Ok(v) => {
+            writeln!(out, "found {} command(s)", schema_commands(&v).len())?;
+            Some(v)
+        }
+        Err(e) => {
+            writeln!(out, "failed ({e}); a minimal config will be created")?;
+            None
+        }
+    };
+    let dir = std::env::current_dir()
+        .unwrap_or_else(|_| std::path::PathBuf::from("."))
+        .join(".tui");
+    let entry = dir.join("chi-index.yaml");
+    if !confirm(
+        &mut input,
+        &mut out,
+        &format!("Write starter config to {}?", entry.display()),
+    )? {
+        return Ok(None);
+    }
+    write_starter(&dir, &scaffold_config(&cli, schema.as_ref()))?;
+    writeln!(out, "Created {}. Opening it now...", entry.display())?;
+    Ok(Some(dir))
+}
+
+fn write_starter(dir: &Path, yaml: &str) -> Result<()> {
+    let entry = dir.join("chi-index.yaml");
+    if entry.exists() {
+        anyhow::bail!("{entry:?} already exists; not overwriting");
+    }
+    std::fs::create_dir_all(dir).with_context(|| format!("creating {dir:?}"))?;
+    // Personal overrides stay out of the repository
+    let ignore = dir.join(".gitignore");
+    if !ignore.exists() {
+        let line = format!("{}\n", crate::chi_core::local::LOCAL_FILE);
+        std::fs::write(&ignore, line).with_context(|| format!("writing {ignore:?}"))?;
+    }
+    std::fs::write(&entry, yaml).with_context(|| format!("writing {entry:?}"))
+}
+
+fn ask(input: &mut impl BufRead, out: &mut impl Write, prompt: &str) -> Result<String> {
+    write!(out, "{prompt}")?;
+    out.flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        anyhow::bail!("setup wizard aborted (end of input)");
+    }
+    Ok(line.trim().to_string())
+}
+
+fn confirm(input: &mut impl BufRead, out: &mut impl Write, prompt: &str) -> Result<bool> {
+    let answer = ask(input, out, &format!("{prompt} [Y/n] "))?;
+    Ok(!matches!(answer.to_ascii_lowercase().as_str(), "n" | "no"))
+}
+
+// `{ ok, data: { commands: [...] } }` as printed by `<cli> schema`
+fn schema_commands(schema: &JsonValue) -> Vec<JsonValue> {
+    schema
+        .get("data")
+        .and_then(|d| d.get("commands"))
+        .and_then(|c| c.as_array())
+        .cloned()
+        .unwrap_or_default()
+}
+
+// Starter chi-index.yaml: one menu item per schema command plus the generated cheat-sheet
+pub fn scaffold_config(cli: &str, schema: Option<&JsonValue>) -> String {
+    let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+    let mut yaml = String::new();
+    yaml.push_str(&format!("header: {}\n", quote(cli)));
+    yaml.push_str("menu:\n");
+    yaml.push_str(
+        "  - id: \"header_commands\"\n    title: \"── Commands ──\"\n    widget: \"header\"\n",
+    );
+    let commands = schema.map(schema_commands).unwrap_or_default();
+    let names: Vec<&str> = commands
+        .iter()
+        .filter_map(|c| c.get("name").and_then(|s| s.as_str()))
+        .filter(|n| *n != "schema")
+        .collect();
+    if names.is_empty() {
+        yaml.push_str(&format!(
+            "  - id: \"schema\"\n    title: \"Schema\"\n    command: {}\n",
+            quote(&format!("{cli} schema"))
+        ));
+    }
+    for name in names {
+        yaml.push_str(&format!(
+            "  - id: {}\n    title: {}\n    command: {}\n",
+            quote(name),
+            quote(name),
+            quote(&format!("{cli} {name}"))
+        ));
+    }
+    yaml.push_str("  - id: \"header_help\"\n    title: \"── Help ──\"\n    widget: \"header\"\n");
+    yaml.push_str("  - id: \"cheatsheet\"\n    title: \"Cheat-sheet\"\n    widget: \"markdown\"\n    builtin: \"cheatsheet\"\n");
+    yaml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn scaffold_lists_schema_commands_and_parses() {
+        let schema = json!({"ok": true, "data": {"commands": [
+            {"name": "hello"}, {"name": "schema"}, {"name": "list-items"}
+        ]}});
+        let yaml = scaffold_config("my-app", Some(&schema));
+        let cfg: crate::model::AppConfig = serde_yaml::from_str(&yaml).unwrap();
+        let ids: Vec<&str> = cfg.menu.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "header_commands",
+                "hello",
+                "list-items",
+                "header_help",
+                "cheatsheet"
+            ]
+        );
+        assert_eq!(cfg.menu[2].command.as_deref(), Some("my-app list-items"));
+
+        // Without a schema the starter still has something runnable
+        let cfg: crate::model::AppConfig =
+            serde_yaml::from_str(&scaffold_config("my-app", None)).unwrap();
+        assert_eq!(cfg.menu[1].command.as_deref(), Some("my-app schema"));
+    }
+}
@@ -0,0 +1,115 @@
+use serde_json::{json, Value as JsonValue};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Opt-in usage telemetry (`telemetry:`): one event per item run (id, duration, success),
+// batched and piped as a JSON array to the configured hook command in the background
+
+const DEFAULT_BATCH_SIZE: usize = 20;
+const DEFAULT_FLUSH_SECS: u64 = 60;
+
+#[derive(Default)]
+pub struct Telemetry {
+    // Item whose command is running: (id, title, started)
+    running: Option<(String, String, Instant)>,
+    pub events: Vec<JsonValue>,
+    first_queued: Option<Instant>,
+}
+
+impl Telemetry {
+    pub fn start(&mut self, id: &str, title: &str) {
+        self.running = Some((id.to_string(), title.to_string(), Instant::now()));
+    }
+
+    // Close the running item, if any, and queue its event
+    pub fn finish(&mut self, err: Option<&str>) {
+        let Some((id, title, started)) = self.running.take() else {
+            return;
+        };
+        self.events.push(json!({
+            "event": "item_run",
+            "item": id,
+            "title": title,
+            "duration_ms": started.elapsed().as_millis() as u64,
+            "success": err.is_none(),
+            "ts": crate::services::clock::unix_secs(std::time::SystemTime::now()),
+        }));
+        self.first_queued.get_or_insert_with(Instant::now);
+    }
+
+    // The queued batch once it is full or its oldest event has waited long enough
+    // (`force` on quit)
+    pub fn take_batch(
+        &mut self,
+        cfg: &crate::model::TelemetryConfig,
+        force: bool,
+    ) -> Option<Vec<JsonValue>> {
+        let full = self.events.len() >= cfg.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+        let waited = self.first_queued.is_some_and(|t| {
+            t.elapsed() >= Duration::from_secs(cfg.flush_secs.unwrap_or(DEFAULT_FLUSH_SECS))
+        });
+        if self.events.is_empty() || !(force || full || waited) {
+            return None;
+        }
+        self.first_queued = None;
+        Some(std::mem::take(&mut self.events))
+    }
+}
+
+// Run the hook with the batch on stdin; failures are ignored so telemetry never
+// gets in the user's way
+pub fn spawn_send(cmdline: String, events: Vec<JsonValue>) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let Some(parts) =
+            shlex::split(&crate::services::interpolate::env(&cmdline)).filter(|p| !p.is_empty())
+        else {
+            return;
+        };
+        let Ok(mut child) = Command::new(&parts[0])
+            .args(&parts[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(JsonValue::Array(events).to_string().as_bytes());
+        }
+        let _ = child.wait();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_batched_until_full() {
+        let cfg = crate::model::TelemetryConfig {
+            command: "true".into(),
+            batch_size: Some(2),
+            flush_secs: None,
+        };
+        let mut t = Telemetry::default();
+        // Nothing running: nothing recorded
+        t.finish(None);
+        assert!(t.events.is_empty());
+
+        t.start("deploy", "Deploy");
+        t.finish(Some("Command failed (exit 1): app deploy"));
+        assert_eq!(t.events[0]["item"], "deploy");
+        assert_eq!(t.events[0]["success"], false);
+        assert!(t.take_batch(&cfg, false).is_none());
+
+        t.start("status", "Status");
+        t.finish(None);
+        let batch = t.take_batch(&cfg, false).unwrap();
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch[1]["success"], true);
+        assert!(t.take_batch(&cfg, true).is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+// Terminal background detection for `theme: {preset: auto}` (the default): the terminal is
+// asked for its background color (OSC 11) right after raw mode is on, and answers within
+// CHI_TUI_BG_QUERY_MS (default 500, 0 skips the query). A device-attributes query (DA1)
+// follows it; every terminal answers that one, so the reply is read up to it and a terminal
+// without OSC 11 costs no wait. Without an answer `COLORFGBG` (set by rxvt, Konsole, …) is
+// used, and failing that the theme stays dark.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Background {
+    Dark,
+    Light,
+}
+
+const DEFAULT_QUERY_MS: u64 = 500;
+
+fn query_timeout() -> Duration {
+    let ms = std::env::var("CHI_TUI_BG_QUERY_MS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_QUERY_MS);
+    Duration::from_millis(ms)
+}
+
+pub fn detect() -> Option<Background> {
+    query(query_timeout()).or_else(|| {
+        std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|v| from_colorfgbg(&v))
+    })
+}
+
+// One channel of `rgb:` (1 to 4 hex digits) as 0.0..=1.0
+fn channel(hex: &str) -> Option<f64> {
+    if hex.is_empty() || hex.len() > 4 {
+        return None;
+    }
+    let max = (1u32 << (4 * hex.len())) - 1;
+    Some(u32::from_str_radix(hex, 16).ok()? as f64 / max as f64)
+}
+
+// `ESC ] 11 ; rgb:RRRR/GGGG/BBBB` (ended by BEL or ST) anywhere in the terminal's reply
+pub fn parse_reply(reply: &str) -> Option<Background> {
+    let start = reply.find("]11;rgb:")? + "]11;rgb:".len();
+    let rgb: String = reply[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_hexdigit() || *c == '/')
+        .collect();
+    let mut parts = rgb.split('/').map(channel);
+    let (r, g, b) = (parts.next()??, parts.next()??, parts.next()??);
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luminance < 0.5 {
+        Background::Dark
+    } else {
+        Background::Light
+    })
+}
+
+// `fg;bg` (or `fg;default;bg`): the last field is an ANSI color index
+pub fn from_colorfgbg(v: &str) -> Option<Background> {
+    match v.rsplit(';').next()?.parse::<u8>().ok()? {
+        7 | 9..=15 => Some(Background::Light),
+        0..=6 | 8 => Some(Background::Dark),
+        _ => None,
+    }
+}
+
+// The DA1 answer (`ESC [ ? … c`) closes the reply
+fn da1_done(reply: &[u8]) -> bool {
+    reply
+        .windows(3)
+        .rposition(|w| w == b"\x1b[?")
+        .is_some_and(|i| reply.len() > i + 3 && reply.ends_with(b"c"))
+}
+
+#[cfg(unix)]
+fn query(timeout: Duration) -> Option<Background> {
+    use std::io::{IsTerminal, Read, Write};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    if timeout.is_zero() || !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let mut reader = tty.try_clone().ok()?;
+    tty.write_all(b"\x1b]11;?\x1b\\\x1b[c").ok()?;
+    tty.flush().ok()?;
+    let (tx, rx) = std::sync::mpsc::channel();
+    let abandoned = Arc::new(AtomicBool::new(false));
+    let gave_up = abandoned.clone();
+    std::thread::spawn(move || {
+        let mut reply = Vec::new();
+        let mut byte = [0u8; 1];
+        // A reader given up on stops at its next byte rather than keep taking key presses
+        while !gave_up.load(Ordering::Relaxed) && reader.read(&mut byte).is_ok_and(|n| n == 1) {
+            reply.push(byte[0]);
+            if da1_done(&reply) {
+                break;
+            }
+        }
+        let _ = tx.send(reply);
+    });
+    let reply = rx.recv_timeout(timeout);
+    abandoned.store(true, Ordering::Relaxed);
+    parse_reply(&String::from_utf8_lossy(&reply.ok()?))
+}
+
+#[cfg(not(unix))]
+fn query(_timeout: Duration) -> Option<Background> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies_and_colorfgbg_tell_dark_from_light() {
+        let light = "\x1b]11;rgb:ffff/fdfd/f6f6\x1b\\\x1b[?62;22c";
+        assert_eq!(parse_reply(light), Some(Background::Light));
+        assert_eq!(
+            parse_reply("\x1b]11;rgb:1e/1e/2e\x07\x1b[?1;2c"),
+            Some(Background::Dark)
+        );
+        // Only the DA1 answer: no OSC 11 support
+        assert_eq!(parse_reply("\x1b[?1;2c"), None);
+        assert!(da1_done(light.as_bytes()));
+        assert!(!da1_done(b"\x1b]11;rgb:ffff/ffff/ffff\x07\x1b[?"));
+
+        assert_eq!(from_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("15;default;0"), Some(Background::Dark));
+        assert_eq!(from_colorfgbg("default"), None);
+    }
+}
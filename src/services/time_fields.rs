@@ -0,0 +1,275 @@
+use crate::model::TimeFieldSpec;
+use crate::services::clock;
+use serde_json::Value as JsonValue;
+
+// `time_fields:` on a lazy_items / autoload_items item (or an inline child): the timestamps
+// its command emits, in whatever format, are rewritten to RFC 3339 in UTC as the rows load.
+// `render: relative_time`, sorting and anything else reading them then sees one format.
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+// Strftime directives understood in custom formats
+const DIRECTIVES: &str = "YymdeHIMSpbBhaAfzs%";
+
+// A spec the loader can apply: a known format and sort order
+pub fn check(spec: &TimeFieldSpec) -> Result<(), String> {
+    if spec.path.trim().is_empty() {
+        return Err("path is empty".into());
+    }
+    match spec.sort.as_deref() {
+        None | Some("asc") | Some("desc") => {}
+        Some(other) => {
+            return Err(format!(
+                "{}: sort must be asc or desc, not '{other}'",
+                spec.path
+            ))
+        }
+    }
+    let Some(fmt) = spec.format.as_deref() else {
+        return Ok(());
+    };
+    if matches!(fmt, "rfc3339" | "epoch_s" | "epoch_ms") {
+        return Ok(());
+    }
+    if !fmt.contains('%') {
+        return Err(format!(
+            "{}: unknown format '{fmt}' (expected rfc3339, epoch_s, epoch_ms or a strftime pattern)",
+            spec.path
+        ));
+    }
+    let mut chars = fmt.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            match chars.next() {
+                Some(d) if DIRECTIVES.contains(d) => {}
+                Some(d) => return Err(format!("{}: unsupported directive %{d}", spec.path)),
+                None => return Err(format!("{}: format ends with %", spec.path)),
+            }
+        }
+    }
+    Ok(())
+}
+
+// Unix seconds of a value in the given format (None: epoch or RFC 3339, guessed)
+pub fn parse(v: &JsonValue, format: Option<&str>) -> Option<i64> {
+    let number = || {
+        v.as_f64()
+            .or_else(|| v.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+    };
+    match format {
+        None => clock::parse_timestamp(v),
+        Some("rfc3339") => v.is_string().then(|| clock::parse_timestamp(v)).flatten(),
+        Some("epoch_s") => number().map(|n| n as i64),
+        Some("epoch_ms") => number().map(|n| (n / 1000.0) as i64),
+        Some(fmt) => parse_strftime(v.as_str()?.trim(), fmt),
+    }
+}
+
+// Digits at the start of `s`, at most `max`
+fn take_num(s: &mut &str, max: usize) -> Option<i64> {
+    let len = s.bytes().take(max).take_while(u8::is_ascii_digit).count();
+    let n = s[..len].parse().ok()?;
+    *s = &s[len..];
+    Some(n)
+}
+
+// A month by English name or three-letter abbreviation, case-insensitive
+fn take_month(s: &mut &str) -> Option<u32> {
+    let lower = s.to_ascii_lowercase();
+    let (i, name) = MONTHS
+        .iter()
+        .enumerate()
+        .find(|(_, m)| lower.starts_with(&m[..3]))?;
+    let len = if lower.starts_with(name) {
+        name.len()
+    } else {
+        3
+    };
+    *s = &s[len..];
+    Some(i as u32 + 1)
+}
+
+fn parse_strftime(text: &str, fmt: &str) -> Option<i64> {
+    let mut s = text;
+    let (mut y, mut mo, mut d) = (1970, 1u32, 1u32);
+    let (mut h, mut mi, mut sec) = (0i64, 0i64, 0i64);
+    let mut pm = None;
+    let mut offset = None;
+    let mut chars = fmt.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            if c.is_whitespace() {
+                s = s.trim_start();
+            } else {
+                s = s.strip_prefix(c)?;
+            }
+            continue;
+        }
+        match chars.next()? {
+            'Y' => y = take_num(&mut s, 4)?,
+            'y' => {
+                let n = take_num(&mut s, 2)?;
+                y = if n < 69 { 2000 + n } else { 1900 + n };
+            }
+            'm' => mo = take_num(&mut s, 2)? as u32,
+            'd' => d = take_num(&mut s, 2)? as u32,
+            'e' => {
+                s = s.trim_start();
+                d = take_num(&mut s, 2)? as u32;
+            }
+            'H' => h = take_num(&mut s, 2)?,
+            'I' => {
+                h = take_num(&mut s, 2)?;
+                pm.get_or_insert(false);
+            }
+            'M' => mi = take_num(&mut s, 2)?,
+            'S' => sec = take_num(&mut s, 2)?,
+            'f' => {
+                take_num(&mut s, 9)?;
+            }
+            'p' => {
+                let lower = s.get(..2)?.to_ascii_lowercase();
+                pm = Some(match lower.as_str() {
+                    "am" => false,
+                    "pm" => true,
+                    _ => return None,
+                });
+                s = &s[2..];
+            }
+            'b' | 'B' | 'h' => mo = take_month(&mut s)?,
+            'a' | 'A' => s = s.trim_start_matches(|c: char| c.is_ascii_alphabetic()),
+            'z' => {
+                if let Some(rest) = s.strip_prefix(['Z', 'z']) {
+                    s = rest;
+                    offset = Some(0);
+                } else {
+                    let len = s
+                        .char_indices()
+                        .skip(1)
+                        .find(|(_, c)| !(c.is_ascii_digit() || *c == ':'))
+                        .map_or(s.len(), |(i, _)| i);
+                    offset = Some(clock::parse_utc_offset(&s[..len].replace(':', ""))?);
+                    s = &s[len..];
+                }
+            }
+            's' => return take_num(&mut s, 12),
+            '%' => s = s.strip_prefix('%')?,
+            _ => return None,
+        }
+    }
+    if let Some(pm) = pm {
+        h = h % 12 + if pm { 12 } else { 0 };
+    }
+    if !(1..=12).contains(&mo) || !(1..=31).contains(&d) || h > 23 || mi > 59 || sec > 60 {
+        return None;
+    }
+    // Without `%z` the text is local time
+    let offset = offset.unwrap_or_else(clock::local_offset_secs);
+    Some(clock::days_from_civil(y, mo, d) * 86_400 + h * 3600 + mi * 60 + sec - offset)
+}
+
+// `metadata.created` -> `/metadata/created`
+fn pointer(path: &str) -> String {
+    path.split('.')
+        .map(|seg| format!("/{}", seg.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+// Rewrite every timestamp the specs name to RFC 3339 (values that do not parse stay as they
+// are), then order the rows by the first spec with `sort`
+pub fn normalize(items: &mut [JsonValue], specs: &[TimeFieldSpec]) {
+    if specs.is_empty() {
+        return;
+    }
+    for item in items.iter_mut() {
+        for spec in specs {
+            let Some(v) = item.pointer_mut(&pointer(&spec.path)) else {
+                continue;
+            };
+            if let Some(ts) = parse(v, spec.format.as_deref()) {
+                *v = JsonValue::String(clock::format_rfc3339(ts));
+            }
+        }
+    }
+    let Some(spec) = specs.iter().find(|s| s.sort.is_some()) else {
+        return;
+    };
+    let desc = spec.sort.as_deref() == Some("desc");
+    let at = pointer(&spec.path);
+    items.sort_by_cached_key(|item| {
+        let ts = item.pointer(&at).and_then(clock::parse_timestamp);
+        // Missing or unparsed timestamps sort last either way
+        (ts.is_none(), if desc { ts.map(|t| -t) } else { ts })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(path: &str, format: Option<&str>, sort: Option<&str>) -> TimeFieldSpec {
+        TimeFieldSpec {
+            path: path.into(),
+            format: format.map(Into::into),
+            sort: sort.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn timestamps_in_any_format_normalize_and_sort() {
+        let t = 1_709_287_200; // 2024-03-01T10:00:00Z
+        assert_eq!(parse(&json!(t * 1000), Some("epoch_ms")), Some(t));
+        assert_eq!(parse(&json!("1709287200"), Some("epoch_s")), Some(t));
+        assert_eq!(parse(&json!(t), Some("rfc3339")), None);
+        assert_eq!(
+            parse(
+                &json!("01/Mar/2024:10:00:00 +0000"),
+                Some("%d/%b/%Y:%H:%M:%S %z")
+            ),
+            Some(t)
+        );
+        assert_eq!(
+            parse(
+                &json!("Fri, March 1 2024 11:00 AM +01:00"),
+                Some("%a, %B %e %Y %I:%M %p %z")
+            ),
+            Some(t)
+        );
+        assert_eq!(parse(&json!("2024-13-01"), Some("%Y-%m-%d")), None);
+
+        let mut rows = vec![
+            json!({"id": "a", "meta": {"at": "01/Mar/2024:10:00:00 +0000"}}),
+            json!({"id": "b", "meta": {"at": "n/a"}}),
+            json!({"id": "c", "meta": {"at": "02/Mar/2024:10:00:00 +0000"}}),
+        ];
+        normalize(
+            &mut rows,
+            &[spec("meta.at", Some("%d/%b/%Y:%H:%M:%S %z"), Some("desc"))],
+        );
+        let ids: Vec<_> = rows.iter().map(|r| r["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["c", "a", "b"]);
+        assert_eq!(rows[1]["meta"]["at"], "2024-03-01T10:00:00Z");
+        assert_eq!(rows[2]["meta"]["at"], "n/a");
+
+        assert!(check(&spec("at", Some("%d.%m.%Y %k"), None))
+            .unwrap_err()
+            .contains("unsupported directive %k"));
+        assert!(check(&spec("at", Some("iso"), None)).is_err());
+        assert!(check(&spec("at", None, Some("newest"))).is_err());
+        assert!(check(&spec("at", Some("%Y-%m-%dT%H:%M:%S%z"), Some("asc"))).is_ok());
+    }
+}
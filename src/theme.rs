@@ -12,7 +12,6 @@ pub struct Theme {
     #[allow(dead_code)]
     pub mode: ThemeMode,
     pub bg: Color,
-    #[allow(dead_code)]
     pub fg: Color,
     pub primary: Color,
     pub secondary: Color,
@@ -22,7 +21,47 @@ pub struct Theme {
     pub selected: Color,
     pub success: Color,
     pub error: Color,
+    pub warn: Color,
     pub muted: Color,
+    // Content syntax colors (JSON values, markdown inline markup)
+    pub syn_key: Color,
+    pub syn_string: Color,
+    pub syn_number: Color,
+    pub syn_bool: Color,
+    pub syn_code: Color,
+    pub syn_link: Color,
+    // Glyph next to every colored status, so status never depends on color alone
+    pub glyphs: StatusGlyphs,
+    // Status colors moved to the colorblind palette
+    pub colorblind: bool,
+}
+
+// Kind of a status shown with a color, a glyph and (in gauges) a fill pattern
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Fail,
+    Info,
+    Warn,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusGlyphs {
+    pub ok: String,
+    pub fail: String,
+    pub info: String,
+    pub warn: String,
+}
+
+impl Default for StatusGlyphs {
+    fn default() -> Self {
+        Self {
+            ok: "✓".into(),
+            fail: "✗".into(),
+            info: "●".into(),
+            warn: "▲".into(),
+        }
+    }
 }
 
 impl Theme {
@@ -38,11 +77,19 @@ impl Theme {
             selected: Color::Rgb(255, 120, 0),
             success: Color::Green,
             error: Color::Red,
+            warn: Color::Yellow,
             muted: Color::DarkGray,
+            syn_key: Color::Cyan,
+            syn_string: Color::Green,
+            syn_number: Color::Yellow,
+            syn_bool: Color::Magenta,
+            syn_code: Color::Rgb(255, 170, 90),
+            syn_link: Color::Rgb(64, 160, 255),
+            glyphs: StatusGlyphs::default(),
+            colorblind: false,
         }
     }
 
-    #[allow(dead_code)]
     pub fn synthwave_light() -> Self {
         Self {
             mode: ThemeMode::Light,
@@ -55,8 +102,131 @@ impl Theme {
             selected: Color::Rgb(220, 100, 0),
             success: Color::Rgb(0, 150, 0),
             error: Color::Rgb(200, 0, 0),
+            warn: Color::Rgb(170, 110, 0),
             muted: Color::Rgb(120, 120, 130),
+            syn_key: Color::Rgb(0, 120, 150),
+            syn_string: Color::Rgb(0, 130, 0),
+            syn_number: Color::Rgb(170, 110, 0),
+            syn_bool: Color::Rgb(160, 0, 160),
+            syn_code: Color::Rgb(180, 80, 0),
+            syn_link: Color::Rgb(40, 120, 220),
+            glyphs: StatusGlyphs::default(),
+            colorblind: false,
+        }
+    }
+
+    // Black background with saturated, bright colors and white frames
+    pub fn high_contrast() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            bg: Color::Black,
+            fg: Color::White,
+            primary: Color::Yellow,
+            secondary: Color::Cyan,
+            accent: Color::LightBlue,
+            frame: Color::White,
+            selected: Color::Yellow,
+            success: Color::LightGreen,
+            error: Color::LightRed,
+            warn: Color::Yellow,
+            muted: Color::Gray,
+            syn_key: Color::LightCyan,
+            syn_string: Color::LightGreen,
+            syn_number: Color::LightYellow,
+            syn_bool: Color::LightMagenta,
+            syn_code: Color::Yellow,
+            syn_link: Color::LightBlue,
+            glyphs: StatusGlyphs::default(),
+            colorblind: false,
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::synthwave_dark()),
+            "light" => Some(Self::synthwave_light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    // `theme:` config: its preset with the bg/primary/secondary/accent overrides
+    pub fn from_config(cfg: &crate::model::ThemeConfig) -> Result<Self, String> {
+        let name = preset_name(Some(cfg));
+        let mut theme = Self::preset(name).ok_or_else(|| {
+            format!(
+                "unknown preset '{name}' (expected {}, auto)",
+                PRESETS.join(", ")
+            )
+        })?;
+        for (slot, key, value) in [
+            (&mut theme.bg, "bg", &cfg.bg),
+            (&mut theme.primary, "primary", &cfg.primary),
+            (&mut theme.secondary, "secondary", &cfg.secondary),
+            (&mut theme.accent, "accent", &cfg.accent),
+        ] {
+            if let Some(v) = value {
+                *slot = v
+                    .parse::<Color>()
+                    .map_err(|_| format!("{key}: invalid color '{v}'"))?;
+            }
         }
+        Ok(theme)
+    }
+
+    // Theme of a preset: the configured colors apply over their own preset only
+    pub fn for_preset(cfg: Option<&crate::model::ThemeConfig>, preset: &str) -> Self {
+        cfg.filter(|t| preset_name(Some(t)) == preset)
+            .and_then(|t| Self::from_config(t).ok())
+            .or_else(|| Self::preset(preset))
+            .unwrap_or_default()
+    }
+
+    // The same theme in another preset: colorblind mode and status glyphs carry over
+    pub fn switched(&self, cfg: Option<&crate::model::ThemeConfig>, preset: &str) -> Self {
+        let mut next = Self::for_preset(cfg, preset);
+        if self.colorblind {
+            next = next.colorblind();
+        }
+        next.glyphs = self.glyphs.clone();
+        next
+    }
+
+    // `colorblind: true`: statuses move off red/green to a blue/vermillion/yellow palette
+    // (Okabe–Ito) that stays distinct with the common color-vision deficiencies, and the
+    // default glyphs get heavier
+    pub fn colorblind(mut self) -> Self {
+        self.success = Color::Rgb(0, 114, 178);
+        self.error = Color::Rgb(213, 94, 0);
+        self.warn = Color::Rgb(240, 228, 66);
+        self.accent = Color::Rgb(86, 180, 233);
+        self.syn_string = Color::Rgb(86, 180, 233);
+        self.glyphs.ok = "✔".into();
+        self.glyphs.fail = "✖".into();
+        self.colorblind = true;
+        self
+    }
+
+    // Entry config accessibility settings: `colorblind:` and `status_glyphs:` overrides
+    pub fn with_config(self, cfg: &crate::model::AppConfig) -> Self {
+        let mut theme = if cfg.colorblind {
+            self.colorblind()
+        } else {
+            self
+        };
+        if let Some(g) = &cfg.status_glyphs {
+            for (slot, glyph) in [
+                (&mut theme.glyphs.ok, &g.ok),
+                (&mut theme.glyphs.fail, &g.fail),
+                (&mut theme.glyphs.info, &g.info),
+                (&mut theme.glyphs.warn, &g.warn),
+            ] {
+                if let Some(glyph) = glyph.as_ref().filter(|s| !s.is_empty()) {
+                    *slot = glyph.clone();
+                }
+            }
+        }
+        theme
     }
 
     #[allow(dead_code)]
@@ -74,6 +244,22 @@ impl Default for Theme {
     }
 }
 
+// Presets cycled with Ctrl+T, in order
+pub const PRESETS: [&str; 3] = ["dark", "light", "high-contrast"];
+
+// `auto` (or no preset) follows the terminal background once it is known
+pub fn is_auto(cfg: Option<&crate::model::ThemeConfig>) -> bool {
+    matches!(cfg.and_then(|t| t.preset.as_deref()), None | Some("auto"))
+}
+
+// The configured preset; `auto` is dark until the terminal says otherwise
+pub fn preset_name(cfg: Option<&crate::model::ThemeConfig>) -> &str {
+    match cfg.and_then(|t| t.preset.as_deref()) {
+        None | Some("auto") => "dark",
+        Some(name) => name,
+    }
+}
+
 // Style helpers that use the theme
 impl Theme {
     pub fn border_focused(&self) -> Style {
@@ -105,11 +291,48 @@ impl Theme {
         Style::default().fg(self.error)
     }
 
-    #[allow(dead_code)]
     pub fn text_success(&self) -> Style {
         Style::default().fg(self.success)
     }
 
+    pub fn status_color(&self, status: Status) -> Color {
+        match status {
+            Status::Ok => self.success,
+            Status::Fail => self.error,
+            Status::Info => self.accent,
+            Status::Warn => self.warn,
+        }
+    }
+
+    pub fn status_style(&self, status: Status) -> Style {
+        Style::default().fg(self.status_color(status))
+    }
+
+    pub fn status_glyph(&self, status: Status) -> &str {
+        match status {
+            Status::Ok => &self.glyphs.ok,
+            Status::Fail => &self.glyphs.fail,
+            Status::Info => &self.glyphs.info,
+            Status::Warn => &self.glyphs.warn,
+        }
+    }
+
+    // Error message of a pane, marked with the fail glyph
+    pub fn error_line(&self, err: &str) -> ratatui::text::Line<'static> {
+        ratatui::text::Line::from(format!("{} {err}", self.glyphs.fail))
+            .style(self.status_style(Status::Fail))
+    }
+
+    // Gauge fill per status, telling segments apart without color
+    pub fn gauge_fill(&self, status: Status) -> char {
+        match status {
+            Status::Ok => '█',
+            Status::Fail => '▚',
+            Status::Warn => '▒',
+            Status::Info => '░',
+        }
+    }
+
     pub fn list_cursor_style(&self) -> Style {
         Style::default()
             .fg(self.bg)
@@ -123,7 +346,6 @@ impl Theme {
         Style::default().fg(border_color)
     }
 
-    #[allow(dead_code)]
     pub fn title_style(&self) -> Style {
         Style::default().fg(self.accent)
     }
@@ -133,15 +355,72 @@ impl Theme {
         Style::default().bg(self.bg).fg(self.fg)
     }
 
+    pub fn json_key(&self) -> Style {
+        Style::default().fg(self.syn_key)
+    }
+
+    pub fn json_string(&self) -> Style {
+        Style::default().fg(self.syn_string)
+    }
+
+    pub fn json_number(&self) -> Style {
+        Style::default().fg(self.syn_number)
+    }
+
+    pub fn json_bool(&self) -> Style {
+        Style::default().fg(self.syn_bool)
+    }
+
+    pub fn md_heading(&self) -> Style {
+        Style::default()
+            .fg(self.accent)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn md_strong(&self) -> Style {
+        Style::default()
+            .fg(self.primary)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn md_emphasis(&self) -> Style {
+        Style::default()
+            .fg(self.secondary)
+            .add_modifier(Modifier::ITALIC)
+    }
+
+    pub fn md_code(&self) -> Style {
+        Style::default().fg(self.syn_code)
+    }
+
+    pub fn md_link(&self) -> Style {
+        Style::default()
+            .fg(self.syn_link)
+            .add_modifier(Modifier::UNDERLINED)
+    }
+
     pub fn toast_color(&self, level: crate::ui::ToastLevel) -> Color {
-        match level {
-            crate::ui::ToastLevel::Success => self.success,
-            crate::ui::ToastLevel::Error => self.error,
-            crate::ui::ToastLevel::Info => self.accent,
-        }
+        self.status_color(level.status())
     }
 }
 
+// Theme used by widgets that render content without access to AppState
+static ACTIVE_THEME: std::sync::RwLock<Option<Theme>> = std::sync::RwLock::new(None);
+
+pub fn set_active(theme: Theme) {
+    if let Ok(mut g) = ACTIVE_THEME.write() {
+        *g = Some(theme);
+    }
+}
+
+pub fn active() -> Theme {
+    ACTIVE_THEME
+        .read()
+        .ok()
+        .and_then(|g| g.clone())
+        .unwrap_or_default()
+}
+
 // Legacy compatibility mappings
 pub const ACCENT: Color = Color::Rgb(64, 160, 255);
 pub const PRIMARY: Color = Color::Rgb(255, 0, 153);
@@ -156,6 +435,7 @@ pub const MUTED: Color = Color::DarkGray;
 pub const INVERT: Color = Color::Black;
 
 // Legacy helper functions that now use default theme
+#[allow(dead_code)]
 pub fn border_focused() -> Style {
     Theme::default().border_focused()
 }
@@ -176,10 +456,58 @@ pub fn text_error() -> Style {
     Theme::default().text_error()
 }
 
-pub fn toast_color(level: crate::ui::ToastLevel) -> Color {
-    Theme::default().toast_color(level)
-}
-
 pub fn list_cursor_style() -> Style {
     Theme::default().list_cursor_style()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colorblind_mode_and_glyph_overrides_apply_from_config() {
+        let cfg: crate::model::AppConfig = serde_yaml::from_str(
+            "colorblind: true\nstatus_glyphs: { warn: \"!\", info: \"\" }\nmenu: []\n",
+        )
+        .unwrap();
+        let theme = Theme::synthwave_dark().with_config(&cfg);
+        assert_ne!(theme.success, Color::Green);
+        assert_ne!(theme.error, Color::Red);
+        assert_eq!(theme.status_glyph(Status::Ok), "✔");
+        assert_eq!(theme.status_glyph(Status::Warn), "!");
+        // Empty overrides keep the default
+        assert_eq!(theme.status_glyph(Status::Info), "●");
+        assert_eq!(theme.toast_color(crate::ui::ToastLevel::Error), theme.error);
+
+        let plain = Theme::synthwave_dark().with_config(&crate::model::AppConfig::default());
+        assert_eq!(plain.status_glyph(Status::Fail), "✗");
+        assert_eq!(plain.success, Color::Green);
+    }
+
+    #[test]
+    fn presets_and_color_overrides_load_from_config() {
+        let cfg: crate::model::AppConfig = serde_yaml::from_str(
+            "theme: { preset: light, bg: \"#101014\", accent: cyan }\ncolorblind: true\nmenu: []\n",
+        )
+        .unwrap();
+        let theme_cfg = cfg.theme.as_ref();
+        let light = Theme::for_preset(theme_cfg, "light").with_config(&cfg);
+        assert_eq!(light.mode, ThemeMode::Light);
+        assert_eq!(light.bg, Color::Rgb(0x10, 0x10, 0x14));
+        assert_eq!(light.primary, Theme::synthwave_light().primary);
+
+        // Overrides belong to their preset; colorblind mode carries over a switch
+        let contrast = light.switched(theme_cfg, "high-contrast");
+        assert_eq!(contrast.bg, Color::Black);
+        assert!(contrast.colorblind && contrast.error != Color::LightRed);
+        let back = contrast.switched(theme_cfg, "light");
+        assert_eq!(back.bg, light.bg);
+
+        let bad = |yaml: &str| {
+            let t: crate::model::ThemeConfig = serde_yaml::from_str(yaml).unwrap();
+            Theme::from_config(&t).unwrap_err()
+        };
+        assert!(bad("preset: solarized").starts_with("unknown preset 'solarized'"));
+        assert_eq!(bad("primary: \"#12\""), "primary: invalid color '#12'");
+    }
+}
@@ -1,15 +1,15 @@
 use crate::app::{update, AppMsg, Effect};
 use crate::model::{AppConfig, MenuItem};
-use crate::nav::flatten::flatten_nodes;
+use crate::nav::flatten::{flatten_nodes, Tracked};
 use crate::nav::keys::menu_key;
 use crate::services::cli_runner::spawn_streaming_cmd;
 use crate::widgets::json_viewer::{draw_json, JsonViewerWidget};
 // use crate::widgets::form::{draw_form, FormState};
 use crate::widgets::menu::draw_menu;
-use crate::widgets::Widget;
 use anyhow::{Context, Result};
 use crossterm::event::{
-    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers,
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
 };
 use crossterm::execute;
 use crossterm::terminal::{
@@ -37,6 +37,161 @@ pub(crate) fn compute_scroll_window_menu(
     let end = (start + ih).min(total);
     (start, end)
 }
+fn run_stream(
+    state: &mut AppState,
+    cmdline: String,
+    title: String,
+    ticket: Option<crate::services::mutexes::Ticket>,
+) {
+    // One stream at a time: the one still running is stopped, not left without a handle
+    cancel_stream(state);
+    state.dbg(format!("run stream: {title} :: {cmdline}"));
+    state.stream_source = Some(cmdline.clone());
+    state.stream_title = Some(title.clone());
+    state.status_text = Some(format!("Running: {title}"));
+    state.status_percent = None;
+    // In panel mode the stream shows its progress in Pane B instead of taking over the screen
+    state.stream_in_panel = state.view == View::Panel && state.panel.is_some();
+    if state.stream_in_panel {
+        // Running it again replaces the finished viewer instead of stacking another one
+        let rerun = matches!(
+            state.panel.as_ref().map(|ps| &ps.b_content),
+            Some(PaneContent::Widget(w))
+                if w.as_any().is::<crate::widgets::stream_viewer::StreamViewerWidget>()
+        );
+        pane_b_replace_with_widget(
+            state,
+            Box::new(crate::widgets::stream_viewer::StreamViewerWidget::new(
+                &title, &cmdline,
+            )),
+            !rerun,
+        );
+    }
+    // Restart animation when stream starts
+    if state.motion() {
+        state.animation_start_frame = state.frame();
+    }
+    state.stream_id += 1;
+    if let Some(ptx) = &state.p_tx {
+        state.stream_handle = Some(spawn_streaming_cmd(
+            state.stream_id,
+            cmdline,
+            ptx.clone(),
+            ticket,
+        ));
+    }
+}
+
+// Progress events waiting on the channel; those of a stream replaced since are dropped
+fn drain_progress(state: &mut AppState) -> Vec<ProgressEvent> {
+    let Some(prx) = &state.p_rx else {
+        return Vec::new();
+    };
+    std::iter::from_fn(|| prx.try_recv().ok())
+        .filter(|ev| ev.stream == state.stream_id)
+        .collect()
+}
+
+fn apply_progress(state: &mut AppState, ev: ProgressEvent) {
+    let effects = if ev.done {
+        update(
+            state,
+            AppMsg::StreamDone {
+                result: ev.result,
+                err: ev.err,
+            },
+        )
+    } else {
+        update(
+            state,
+            AppMsg::StreamProgress {
+                text: ev.text,
+                percent: ev.percent,
+            },
+        )
+    };
+    run_effects(state, effects);
+}
+
+// Kill the running stream's process and reset the status line
+fn cancel_stream(state: &mut AppState) {
+    if let Some(handle) = state.stream_handle.take() {
+        handle.cancel();
+        let effects = update(state, AppMsg::StreamCancelled);
+        run_effects(state, effects);
+    }
+}
+
+fn load_panel_cmd(
+    state: &mut AppState,
+    pane: PanelPane,
+    cmdline: String,
+    ticket: Option<crate::services::mutexes::Ticket>,
+) {
+    state.dbg(format!("load panel {pane:?} cmd -> {cmdline}"));
+    let effect = Effect::LoadPanelCmd {
+        pane,
+        cmdline: cmdline.clone(),
+    };
+    state.panel_nesting.started(pane, &effect);
+    if matches!(pane, PanelPane::B) {
+        state.pane_b_source = Some(cmdline.clone());
+    }
+    if let Some(reply) = subscribe_panel(state, pane) {
+        crate::services::loader::spawn_load_panel_cmd(cmdline, reply, ticket);
+    }
+}
+
+// Content for a pane of the panel (command output or YAML)
+fn subscribe_panel(state: &mut AppState, pane: PanelPane) -> Option<crate::services::bus::Reply> {
+    let key = match pane {
+        PanelPane::A => "panel:A",
+        PanelPane::B => "panel:B",
+    };
+    crate::services::bus::subscribe(state, key, move |st, outcome| {
+        update(st, AppMsg::LoadedPanel { pane, outcome })
+    })
+}
+
+// The end of a form submission into Pane B: re-enable the form
+fn submit_done(state: &mut AppState, outcome: Result<LoadOutcome, String>) -> Vec<Effect> {
+    let effects = update(
+        state,
+        AppMsg::LoadedSubmitForm {
+            pane: PanelPane::B,
+            outcome,
+        },
+    );
+    state.submitting = false;
+    state.status_text = None;
+    state.status_percent = None;
+    if let Some(ps) = &mut state.panel {
+        if let PaneContent::Widget(ref mut w) = ps.b_content {
+            if let Some(fw) = crate::widgets::wizard::current_form_mut(w.as_any_mut()) {
+                fw.form.disabled = false;
+            }
+        }
+    }
+    effects
+}
+
+// Place in the queue of a `mutex:`, with a toast when other runs are ahead
+fn mutex_ticket(state: &mut AppState, mutex: &str) -> crate::services::mutexes::Ticket {
+    let (ticket, ahead) = crate::services::mutexes::enqueue(mutex);
+    if ahead > 0 {
+        state.dbg(format!("mutex {mutex}: queued behind {ahead}"));
+        run_effects(
+            state,
+            vec![Effect::ShowToast {
+                text: format!("'{mutex}' is busy — queued ({ahead} ahead)"),
+                level: ToastLevel::Info,
+                seconds: 3,
+            }],
+        );
+    }
+    ticket
+}
+
 fn run_effects(state: &mut AppState, effects: Vec<Effect>) {
     for eff in effects {
         match eff {
@@ -46,8 +201,12 @@ fn run_effects(state: &mut AppState, effects: Vec<Effect>) {
                 } else {
                     state.dbg(format!("load menu {key}"));
                 }
-                if let Some(tx) = &state.tx {
-                    crate::services::loader::spawn_load_for_menu(mi, key, tx.clone());
+                let k = key.clone();
+                let reply = crate::services::bus::subscribe(state, key, move |st, outcome| {
+                    update(st, AppMsg::LoadedMenu { key: k, outcome })
+                });
+                if let Some(reply) = reply {
+                    crate::services::loader::spawn_load_for_menu(mi, reply);
                 }
             }
             Effect::LoadChild { val, key } => {
@@ -56,40 +215,198 @@ fn run_effects(state: &mut AppState, effects: Vec<Effect>) {
                 } else {
                     state.dbg(format!("load child {key}"));
                 }
-                if let Some(tx) = &state.tx {
-                    crate::services::loader::spawn_load_for_value(val, key, tx.clone());
+                let k = key.clone();
+                let reply = crate::services::bus::subscribe(state, key, move |st, outcome| {
+                    update(st, AppMsg::LoadedChild { key: k, outcome })
+                });
+                if let Some(reply) = reply {
+                    crate::services::loader::spawn_load_for_value(val, reply);
+                }
+            }
+            Effect::RunStream { cmdline, title } => run_stream(state, cmdline, title, None),
+            Effect::LoadPanelCmd { pane, cmdline } => load_panel_cmd(state, pane, cmdline, None),
+            Effect::Enter(target) => {
+                let effs = continue_nav(state, NavTarget::Enter(target));
+                run_effects(state, effs);
+            }
+            Effect::CheckConditions => check_conditions(state, false),
+            Effect::OpenItem(id) => {
+                let Some(idx) = state.config.menu.iter().position(|m| m.id == id) else {
+                    let text = format!("No menu item '{id}'");
+                    run_effects(
+                        state,
+                        vec![Effect::ShowToast {
+                            text,
+                            level: ToastLevel::Error,
+                            seconds: 3,
+                        }],
+                    );
+                    continue;
+                };
+                select_menu_row(
+                    state,
+                    |n| matches!(n, FlatNode::Menu { idx: i, .. } if *i == idx),
+                );
+                let mi = state.config.menu[idx].clone();
+                let effs = update(state, AppMsg::EnterMenu(mi));
+                run_effects(state, effs);
+            }
+            Effect::Guarded { mutex, effect } => {
+                let ticket = mutex_ticket(state, &mutex);
+                match *effect {
+                    Effect::RunStream { cmdline, title } => {
+                        run_stream(state, cmdline, title, Some(ticket))
+                    }
+                    Effect::LoadPanelCmd { pane, cmdline } => {
+                        load_panel_cmd(state, pane, cmdline, Some(ticket))
+                    }
+                    other => run_effects(state, vec![other]),
+                }
+            }
+            Effect::CheckAuth => {
+                let Some(check) = state.config.auth.as_ref().map(|a| a.check.clone()) else {
+                    continue;
+                };
+                let reply = crate::services::bus::subscribe(state, "auth:check", |st, outcome| {
+                    update(st, AppMsg::AuthChecked { outcome })
+                });
+                if let Some(reply) = reply {
+                    crate::services::loader::spawn_check(check, reply);
+                }
+            }
+            Effect::RefreshAuth => {
+                let Some(cmd) = state
+                    .config
+                    .auth_retry
+                    .as_ref()
+                    .map(|a| a.refresh_cmd.clone())
+                else {
+                    continue;
+                };
+                state.dbg(format!("auth refresh: {cmd}"));
+                let reply =
+                    crate::services::bus::subscribe(state, "auth:refresh", |st, outcome| {
+                        update(st, AppMsg::AuthRefreshed { outcome })
+                    });
+                if let Some(reply) = reply {
+                    crate::services::loader::spawn_check(cmd, reply);
                 }
             }
-            Effect::RunStream { cmdline, title } => {
-                state.dbg(format!("run stream: {title} :: {cmdline}"));
-                state.status_text = Some(format!("Running: {title}"));
-                state.status_percent = None;
-                // Restart animation when stream starts
-                if state.animations_enabled {
-                    state.animation_start_tick = state.tick;
+            Effect::Login => {
+                let Some(auth) = state.config.auth.clone() else {
+                    continue;
+                };
+                if let Some(prompt) = state.auth_prompt.as_mut() {
+                    prompt.running = true;
+                    prompt.error = None;
+                }
+                if crate::services::auth::device_code_mode(&auth) {
+                    let reply =
+                        crate::services::bus::subscribe(state, "auth:login", |st, outcome| {
+                            update(st, AppMsg::AuthLoginDone { outcome })
+                        });
+                    if let Some(reply) = reply {
+                        let output = crate::services::auth::spawn_device_login(auth.login, reply);
+                        if let Some(prompt) = state.auth_prompt.as_mut() {
+                            prompt.output = Some(output);
+                        }
+                    }
+                } else {
+                    // Needs the terminal: run right after the next draw
+                    state.login_pending = true;
                 }
-                if let Some(ptx) = &state.p_tx {
-                    spawn_streaming_cmd(cmdline, ptx.clone());
+            }
+            Effect::SubmitBatch { rows, mutex } => {
+                state.dbg(format!("submit batch: {} rows", rows.len()));
+                if state.tx.is_some() {
+                    let ticket = mutex.map(|m| mutex_ticket(state, &m));
+                    state.status_text = Some(format!("Batch: 0/{}", rows.len()));
+                    state.status_percent = Some(0.0);
+                    let rows = rows
+                        .into_iter()
+                        .filter_map(|(index, cmdline)| {
+                            let reply = crate::services::bus::subscribe(
+                                state,
+                                format!("batch:{index}"),
+                                move |st, outcome| {
+                                    update(st, AppMsg::BatchRowDone { index, outcome })
+                                },
+                            )?;
+                            Some((cmdline, reply))
+                        })
+                        .collect();
+                    crate::services::loader::spawn_batch_submit(rows, ticket);
                 }
             }
-            Effect::LoadPanelCmd { pane, cmdline } => {
-                state.dbg(format!("load panel {pane:?} cmd -> {cmdline}"));
-                if let Some(tx) = &state.tx {
-                    let kind = match pane {
-                        PanelPane::A => LoadKind::PanelA,
-                        PanelPane::B => LoadKind::PanelB,
-                    };
-                    crate::services::loader::spawn_load_panel_cmd(cmdline, kind, tx.clone());
+            Effect::UpdateRow { cmdline } => {
+                state.dbg(format!("update row -> {cmdline}"));
+                let reply = crate::services::bus::subscribe(state, "row:update", |st, outcome| {
+                    update(st, AppMsg::RowUpdated { outcome })
+                });
+                if let Some(reply) = reply {
+                    crate::services::loader::spawn_update_row(cmdline, reply);
+                }
+            }
+            Effect::LoadNestedCmd { subpane, cmdline } => {
+                state.dbg(format!("load nested {subpane:?} cmd -> {cmdline}"));
+                let reply =
+                    crate::services::bus::subscribe(state, "panel:B", move |st, outcome| {
+                        update(st, AppMsg::LoadedNested { subpane, outcome })
+                    });
+                if let Some(reply) = reply {
+                    crate::services::loader::spawn_load_panel_cmd(cmdline, reply, None);
+                }
+            }
+            Effect::RunPreflight { index } => {
+                let Some(status) = state.preflight.get(index) else {
+                    continue;
+                };
+                if status.pending {
+                    continue;
+                }
+                let cmdline = status.check.command.clone();
+                let reply = crate::services::bus::subscribe(
+                    state,
+                    format!("preflight:{index}"),
+                    move |st, outcome| update(st, AppMsg::PreflightDone { index, outcome }),
+                );
+                if let Some(reply) = reply {
+                    state.preflight[index].pending = true;
+                    crate::services::loader::spawn_check(cmdline, reply);
+                }
+            }
+            Effect::RunHook { label, cmdline } => {
+                state.dbg(format!("hook {label}: {cmdline}"));
+                let reply = crate::services::bus::subscribe(
+                    state,
+                    format!("hook:{label}"),
+                    move |st, outcome| {
+                        match outcome {
+                            Ok(LoadOutcome::Fallback(JsonValue::String(line))) => {
+                                st.dbg(format!("hook {label} ok: {line}"))
+                            }
+                            Ok(_) => st.dbg(format!("hook {label} ok")),
+                            Err(e) => st.dbg(format!("hook {label} failed: {e}")),
+                        }
+                        Vec::new()
+                    },
+                );
+                if let Some(reply) = reply {
+                    crate::services::loader::spawn_check(cmdline, reply);
                 }
             }
             Effect::LoadPanelYaml { pane, path } => {
                 state.dbg(format!("load panel {pane:?} yaml -> {path}"));
-                if let Some(tx) = &state.tx {
-                    let kind = match pane {
-                        PanelPane::A => LoadKind::PanelA,
-                        PanelPane::B => LoadKind::PanelB,
-                    };
-                    crate::services::loader::spawn_load_panel_yaml(path, kind, tx.clone());
+                let effect = Effect::LoadPanelYaml {
+                    pane,
+                    path: path.clone(),
+                };
+                state.panel_nesting.started(pane, &effect);
+                if matches!(pane, PanelPane::B) {
+                    state.pane_b_source = None;
+                }
+                if let Some(reply) = subscribe_panel(state, pane) {
+                    crate::services::loader::spawn_load_panel_yaml(path, reply);
                 }
             }
             Effect::CancelForm { pane } => {
@@ -122,32 +439,46 @@ fn run_effects(state: &mut AppState, effects: Vec<Effect>) {
                 state.dbg(format!(
                     "load form options field={field} cmd={cmdline} unwrap={unwrap:?} force={force}"
                 ));
-                if let Some(tx) = &state.tx {
-                    let key = format!("form:opt:{field}");
-                    // Show a short status while refreshing options
-                    state.status_text = Some(format!("Refreshing options: {field}"));
-                    state.status_percent = None;
-                    crate::services::loader::spawn_load_options_cmd(
-                        cmdline,
-                        unwrap,
-                        key,
-                        force,
-                        tx.clone(),
-                    );
+                let key = format!("form:opt:{field}");
+                let name = field.clone();
+                let reply = crate::services::bus::subscribe(state, key, move |st, outcome| {
+                    update(
+                        st,
+                        AppMsg::LoadedFormOptions {
+                            field: name,
+                            outcome,
+                        },
+                    )
+                });
+                if let Some(reply) = reply {
+                    // Show a short status for manual refreshes; background prefetch
+                    // is indicated by the per-field spinner instead
+                    if force {
+                        state.status_text = Some(format!("Refreshing options: {field}"));
+                        state.status_percent = None;
+                    }
+                    crate::services::loader::spawn_load_options_cmd(cmdline, unwrap, force, reply);
                 }
             }
-            Effect::SubmitForm { pane, cmdline } => {
+            Effect::SubmitForm {
+                pane,
+                cmdline,
+                mutex,
+            } => {
                 state.dbg(format!("submit form {pane:?} :: {cmdline}"));
-                if let Some(tx) = &state.tx {
+                let reply = match pane {
+                    PanelPane::A => subscribe_panel(state, pane),
+                    PanelPane::B => crate::services::bus::subscribe(state, "panel:B", submit_done),
+                };
+                if let Some(reply) = reply {
                     // show submitting spinner and disable form inputs
                     state.status_text = Some("Submitting...".into());
                     state.status_percent = None;
                     state.submitting = true;
                     if let Some(ps) = &mut state.panel {
                         if let PaneContent::Widget(ref mut w) = &mut ps.b_content {
-                            if let Some(fw) = w
-                                .as_any_mut()
-                                .downcast_mut::<crate::widgets::form_widget::FormWidget>()
+                            if let Some(fw) =
+                                crate::widgets::wizard::current_form_mut(w.as_any_mut())
                             {
                                 fw.form.disabled = true;
                                 fw.form.editing = false;
@@ -155,11 +486,8 @@ fn run_effects(state: &mut AppState, effects: Vec<Effect>) {
                             }
                         }
                     }
-                    let kind = match pane {
-                        PanelPane::A => LoadKind::PanelA,
-                        PanelPane::B => LoadKind::SubmitForm,
-                    };
-                    crate::services::loader::spawn_submit_form(cmdline, kind, tx.clone());
+                    let ticket = mutex.map(|m| mutex_ticket(state, &m));
+                    crate::services::loader::spawn_submit_form(cmdline, reply, ticket);
                 }
             }
             Effect::ShowToast {
@@ -188,20 +516,26 @@ use std::time::{Duration, Instant};
 // std::io, std::process helpers moved to services
 #[derive(Default)]
 pub(crate) struct AppState {
-    pub(crate) config: AppConfig,
+    pub(crate) config: Tracked<AppConfig>,
     pub(crate) header_h: u16,
     pub(crate) logo_lines: Vec<String>,
     pub(crate) selected: usize,
     pub(crate) view: View,
-    pub(crate) children: HashMap<String, Vec<JsonValue>>,
-    pub(crate) expanded: HashSet<String>,
+    pub(crate) children: Tracked<HashMap<String, Vec<JsonValue>>>,
+    // Page state of the paginated lists in `children`, by the same key
+    pub(crate) pages: HashMap<String, crate::nav::pagination::Pagination>,
+    pub(crate) expanded: Tracked<HashSet<String>>,
+    // Rows of the left menu, keyed by the generations of the three fields above
+    pub(crate) flat_cache: crate::nav::flatten::FlatCache,
     pub(crate) last_json_pretty: Option<String>,
     pub(crate) last_error: Option<String>,
     pub(crate) tick: u64,
     pub(crate) boot_autoload_done: bool,
     pub(crate) loading: HashSet<String>,
-    tx: Option<Sender<LoadMsg>>,
+    // Background results come back on this channel and go to the handler subscribed for them
+    pub(crate) tx: Option<Sender<LoadMsg>>,
     rx: Option<Receiver<LoadMsg>>,
+    pub(crate) subscriptions: crate::services::bus::Subscriptions,
     // JSON view state
     pub(crate) json_scroll_y: u16,
     #[allow(dead_code)]
@@ -210,8 +544,10 @@ pub(crate) struct AppState {
     pub(crate) json_total_lines: u16,
     #[allow(dead_code)]
     pub(crate) json_wrap: bool,
-    // Pretty JSON viewer for global (non-panel) results
+    // Pretty JSON viewer for global (non-panel) results, and the renderer the result's
+    // `content_type` picked, shown (and given the keys) in its place
     pub(crate) json_viewer: Option<crate::widgets::result_viewer::ResultViewerWidget>,
+    pub(crate) json_rendered: Option<Box<dyn crate::widgets::Widget>>,
     // Left menu viewport (for PgUp/PgDn)
     pub(crate) menu_viewport_h: u16,
     // Left menu scroll offset (persistent)
@@ -233,17 +569,154 @@ pub(crate) struct AppState {
     pub(crate) pane_b_title: Option<String>,
     // Stack of Pane B titles to restore on Back
     pub(crate) pane_b_title_stack: Vec<Option<String>>,
+    // `highlights:` of the item shown in Pane B (restored on Back like titles) and of the
+    // last streamed item
+    pub(crate) pane_b_highlights: Option<crate::widgets::highlight::Highlights>,
+    pub(crate) pane_b_highlights_stack: Vec<Option<crate::widgets::highlight::Highlights>>,
+    // Shown instead of an empty Pane B result (`empty_text:` / `empty_widget:`)
+    pub(crate) pane_b_empty: Option<crate::widgets::empty_state::EmptyState>,
+    pub(crate) stream_highlights: Option<crate::widgets::highlight::Highlights>,
+    // Named values passed between panes (`publish:` / `{slot.<name>}`)
+    pub(crate) slots: crate::chi_core::slots::Slots,
+    // Selection the last entered item was triggered with; specs it loads fill their
+    // `${selected.<path>}` from it
+    pub(crate) selection_context: Option<JsonValue>,
+    // `on_leave_cmd` of the screen shown and of the item entered last
+    pub(crate) screen_leave_hook: Option<LeaveHook>,
+    pub(crate) item_leave_hook: Option<LeaveHook>,
+    // Command of the Pane B load in flight; handed to the viewer that shows its result
+    pub(crate) pane_b_source: Option<String>,
     // Theme
     pub(crate) theme: crate::theme::Theme,
+    // Index into theme::PRESETS of the preset shown (Ctrl+T cycles)
+    pub(crate) theme_index: usize,
+    // `theme:` of the entry config (or theme.yaml); screen configs don't change the theme
+    pub(crate) entry_theme: Option<crate::model::ThemeConfig>,
     pub(crate) animations_enabled: bool,
-    pub(crate) animation_start_tick: u64,
+    // `reduced_motion: true`: effects are drawn static
+    pub(crate) reduced_motion: bool,
+    pub(crate) anim_clock: crate::visuals::AnimClock,
+    // Frame the startup/loading animation (re)started at
+    pub(crate) animation_start_frame: u64,
+    // `CHI_TUI_FRAME_BUDGET_MS`: draws and key handling slower than this are logged
+    pub(crate) frame_budget: Option<Duration>,
+    pub(crate) slow_frames: u64,
+    // Startup splash is drawn instead of the UI until this tick (any key skips it)
+    pub(crate) splash_until_tick: u64,
+    // Chrome of the entry config; screens without their own `chrome:` fall back to it
+    pub(crate) entry_chrome: Option<crate::model::ChromeConfig>,
     // Horizontal menu state
     pub(crate) horizontal_tab_index: usize,
     pub(crate) current_config_path: Option<String>,
     // Debug log (rendered in bottom debug pane)
     pub(crate) debug_log: VecDeque<String>,
+    // Debug console (Ctrl+D): the debug log in a log viewer, created on first focus
+    pub(crate) debug_console: Option<crate::widgets::log_viewer::LogViewerWidget>,
+    pub(crate) debug_focused: bool,
+    // Debug pane height set with +/- while focused (default: DEBUG_H, DEBUG_FOCUSED_H)
+    pub(crate) debug_h: Option<u16>,
     // Persistent watchdog sessions keyed by menu key (menu:<id>)
     pub(crate) watchdog_sessions: HashMap<String, crate::widgets::watchdog::WatchdogSessionRef>,
+    // Menu key -> instant until which the item's command stays blocked (`cooldown_secs`)
+    pub(crate) cooldowns: HashMap<String, Instant>,
+    // Menu key -> program its commands run that is not on PATH (the item is greyed out)
+    pub(crate) missing_commands: HashMap<String, String>,
+    // Files referenced by the screen's items that do not exist (warning badge, `builtin: doctor`)
+    pub(crate) broken_refs: Vec<crate::chi_core::file_refs::BrokenRef>,
+    // Answers of the items' `when_cmd` / `enabled_cmd` (hidden and greyed-out items)
+    pub(crate) conditions: crate::nav::flatten::Tracked<crate::nav::conditions::Conditions>,
+    // Scheduled jobs ('t') and the Scheduled screen ('T')
+    pub(crate) scheduler: crate::services::scheduler::Scheduler,
+    pub(crate) schedule_prompt: Option<SchedulePrompt>,
+    // ':' command line with schema completion
+    pub(crate) command_prompt: Option<CommandPrompt>,
+    // Notes on menu items and rows ('n') and the popup showing or editing one
+    pub(crate) notes: crate::services::notes::Notes,
+    pub(crate) note_prompt: Option<NotePrompt>,
+    // Watch expressions on results ('=') and the prompt adding one
+    pub(crate) watches: crate::chi_core::watches::Watches,
+    pub(crate) watch_prompt: Option<WatchPrompt>,
+    // Horizontal tab ids in the order set with Ctrl+Shift+←/→ (session only)
+    pub(crate) tab_order: Vec<String>,
+    // Config path ("" for the entry config) -> menu and panel state of a tab switched away from
+    pub(crate) tab_states: HashMap<String, TabState>,
+    // Command palette (Ctrl+K)
+    pub(crate) palette: Option<crate::widgets::palette::Palette>,
+    pub(crate) scheduled_selected: usize,
+    // View to go back to from the Scheduled/Workspaces/Results screens
+    pub(crate) screen_return: View,
+    // Workspace switcher ('W'): roots captured from the entry config, index of the active one
+    pub(crate) workspaces: Vec<Workspace>,
+    pub(crate) workspace_index: usize,
+    pub(crate) workspace_selected: usize,
+    // Watchdog sessions of inactive workspaces keyed by config dir (they keep running)
+    pub(crate) parked_sessions:
+        HashMap<PathBuf, HashMap<String, crate::widgets::watchdog::WatchdogSessionRef>>,
+    // Per-screen narrow-layout override captured from the menu item that opened the panel
+    pub(crate) panel_narrow_layout: Option<String>,
+    // Per-screen focus-on-load override captured from the menu item that opened the panel
+    pub(crate) panel_focus_on_load: Option<String>,
+    // Per-screen limit of spec-to-spec pane loads captured from the menu item
+    pub(crate) panel_max_depth: Option<usize>,
+    // Chains of pane loads asked for by loaded specs (cycle and depth checks)
+    pub(crate) panel_nesting: crate::chi_core::nesting::PanelNesting,
+    // Short highlight of the pane that just received content
+    pub(crate) pane_flash: Option<PaneFlash>,
+    // Active quick-jump hint labels ('f')
+    pub(crate) hint_mode: Option<crate::nav::hints::HintMode>,
+    // Main content area of the last frame and a pending Ctrl+E export of it
+    pub(crate) content_area: Rect,
+    pub(crate) html_export_pending: bool,
+    // Widget area of Pane B in the last frame and a pending Ctrl+P image export
+    pub(crate) pane_b_area: Rect,
+    pub(crate) image_export_pending: bool,
+    // Action bar (space) of the focused widget, and content waiting to be opened in $EDITOR
+    pub(crate) action_bar: Option<ActionBar>,
+    pub(crate) editor_pending: Option<String>,
+    // Startup checks (`preflight:`) of the entry config and their latest results
+    pub(crate) preflight: Vec<PreflightStatus>,
+    // Config `banner:` and banners pushed by commands
+    pub(crate) notices: Vec<crate::widgets::notice::Notice>,
+    // The item that opened Pane B or the running stream (autoload lists leave it alone)
+    pub(crate) pane_origin: Option<RetryTarget>,
+    // `auth:` login dialog, what to re-open once logged in, and a pending interactive login
+    pub(crate) auth_prompt: Option<AuthPrompt>,
+    pub(crate) login_retry: Option<RetryTarget>,
+    // `auth_retry:` what to run again once the refresh finishes, and whether the run in
+    // flight already is that retry
+    pub(crate) refresh_retry: Option<RetryTarget>,
+    pub(crate) refresh_retried: bool,
+    pub(crate) login_pending: bool,
+    pub(crate) telemetry: crate::services::telemetry::Telemetry,
+    // Cmdline of the last streamed item (its result fills the JSON view)
+    pub(crate) stream_source: Option<String>,
+    // Title of the running stream, for the Results screen
+    pub(crate) stream_title: Option<String>,
+    // The running stream was started in panel mode: its progress and result go to Pane B
+    pub(crate) stream_in_panel: bool,
+    // Kills the running stream (Esc / Ctrl+X)
+    pub(crate) stream_handle: Option<crate::services::cli_runner::StreamHandle>,
+    // Id of the latest stream; events of earlier ones are dropped
+    pub(crate) stream_id: u64,
+    // Completed stream results ('H') and the Results screen's selection
+    pub(crate) results: crate::widgets::results::ResultArchive,
+    pub(crate) results_screen: crate::widgets::results::ResultsScreen,
+    pub(crate) key_repeat: crate::nav::repeat::KeyRepeat,
+    // Filter bar of the item whose Pane B command is loading (`filters:`)
+    pub(crate) pane_b_filters: Option<crate::widgets::filter_bar::FilterBar>,
+    // Inline cell editor of the item whose Pane B command is loading (`editable_fields:`)
+    pub(crate) pane_b_row_editor: Option<crate::widgets::row_edit::RowEditor>,
+    // Navigation waiting on the unsaved-changes dialog, and one continued after a save
+    pub(crate) nav_guard: Option<NavTarget>,
+    pub(crate) nav_after_save: Option<NavTarget>,
+    // `confirm_quit:` of the entry config, and the open quit confirmation with what quitting
+    // would stop
+    pub(crate) confirm_quit: &'static str,
+    pub(crate) quit_confirm: Option<Vec<String>>,
+    // A `confirm: true` verb waiting for y/n
+    pub(crate) verb_confirm: Option<crate::model::MenuItem>,
+    // Characters of a paste no form field took, fed to the loop as typed keys
+    pub(crate) pending_keys: VecDeque<KeyEvent>,
 }
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum View {
@@ -252,6 +725,9 @@ pub(crate) enum View {
     Welcome,
     Json,
     Panel,
+    Scheduled,
+    Workspaces,
+    Results,
 }
 
 impl AppState {
@@ -260,7 +736,52 @@ impl AppState {
         if self.debug_log.len() >= MAX_LOG_LINES {
             self.debug_log.pop_front();
         }
-        self.debug_log.push_back(msg.into());
+        let msg = msg.into();
+        if let Some(console) = &self.debug_console {
+            console.push(msg.clone());
+        }
+        self.debug_log.push_back(msg);
+    }
+
+    // Animation frame from the wall clock; drives every visual effect and spinner
+    pub(crate) fn frame(&self) -> u64 {
+        self.anim_clock.frame()
+    }
+
+    // Budget check (`CHI_TUI_FRAME_BUDGET_MS`) of a draw or key that started at `started`
+    pub(crate) fn check_budget(&mut self, what: &str, started: Instant) {
+        let Some(budget) = self.frame_budget else {
+            return;
+        };
+        let took = started.elapsed();
+        if took > budget {
+            self.slow_frames += 1;
+            self.dbg(format!(
+                "slow frame: {what} took {}ms (budget {}ms)",
+                took.as_millis(),
+                budget.as_millis()
+            ));
+        }
+    }
+
+    // A command's result arrived: update its watches, toasting the predicates that became true
+    pub(crate) fn observe_watches(&mut self, source: &str, value: &JsonValue) {
+        let alerts = self.watches.observe(source, value, Instant::now());
+        if alerts.is_empty() {
+            return;
+        }
+        let text = format!("Watch: {}", alerts.join(" • "));
+        self.dbg(&text);
+        self.toast = Some(Toast {
+            text,
+            level: ToastLevel::Error,
+            expires_at_tick: self.tick.saturating_add(25),
+        });
+    }
+
+    // Moving effects are drawn (not turned off, not reduced to static styling)
+    pub(crate) fn motion(&self) -> bool {
+        self.animations_enabled && !self.reduced_motion
     }
 }
 
@@ -270,23 +791,115 @@ pub(crate) fn pane_b_replace_with_widget(
     widget: Box<dyn crate::widgets::Widget>,
     push_old: bool,
 ) {
+    let mut widget = widget;
+    let mut empty_widget = None;
+    let source = state.pane_b_source.take();
+    if let (Some(src), Some(rv)) = (
+        &source,
+        widget
+            .as_any()
+            .downcast_ref::<crate::widgets::result_viewer::ResultViewerWidget>(),
+    ) {
+        state.observe_watches(src, rv.value());
+    }
     if let Some(ps) = &mut state.panel {
-        if push_old {
+        if let Some(rv) = widget
+            .as_any_mut()
+            .downcast_mut::<crate::widgets::result_viewer::ResultViewerWidget>()
+        {
+            rv.set_source(source.clone());
+            // The item's filter bar goes on the viewer showing its command
+            if let Some(bar) = state.pane_b_filters.take() {
+                if source.as_deref() == Some(bar.command().as_str()) {
+                    rv.set_filters(bar);
+                } else {
+                    state.pane_b_filters = Some(bar);
+                }
+            }
+            if let Some(editor) = state.pane_b_row_editor.take() {
+                if source.as_deref() == Some(editor.source()) {
+                    rv.set_row_editor(editor);
+                } else {
+                    state.pane_b_row_editor = Some(editor);
+                }
+            }
+            // An empty result shows the item's empty state (unless a filter bar narrowed it
+            // down: the bar has to stay usable)
+            if !rv.has_filters() && crate::widgets::empty_state::is_empty_result(rv.value()) {
+                if let Some(empty) = &state.pane_b_empty {
+                    let title = state.pane_b_title.as_deref().unwrap_or("Pane B");
+                    empty_widget = Some(empty.widget(title, source.clone()));
+                }
+            }
+            // Re-running the command behind the current viewer (or board) refreshes it in place
+            if let (None, PaneContent::Widget(cur)) = (&empty_widget, &mut ps.b_content) {
+                if let Some(cur) = cur
+                    .as_any_mut()
+                    .downcast_mut::<crate::widgets::result_viewer::ResultViewerWidget>()
+                {
+                    if source.is_some() && cur.source() == source.as_deref() {
+                        cur.refreshed(rv.value().clone());
+                        return;
+                    }
+                }
+                if let Some(board) = cur
+                    .as_any_mut()
+                    .downcast_mut::<crate::widgets::board::BoardWidget>()
+                {
+                    if source.as_deref() == Some(board.source()) {
+                        board.set_items(rv.value());
+                        return;
+                    }
+                }
+            }
+        }
+        // An empty state and the result of the same command replace each other in place
+        let same_source = source.is_some()
+            && matches!(&ps.b_content, PaneContent::Widget(cur) if {
+                let cur = cur.as_any();
+                match cur.downcast_ref::<crate::widgets::empty_state::EmptyStateWidget>() {
+                    Some(e) => e.source() == source.as_deref(),
+                    None => {
+                        empty_widget.is_some()
+                            && cur
+                                .downcast_ref::<crate::widgets::result_viewer::ResultViewerWidget>()
+                                .is_some_and(|rv| rv.source() == source.as_deref())
+                    }
+                }
+            });
+        if let Some(empty) = empty_widget {
+            widget = empty;
+        }
+        if push_old && !same_source {
             // Move current content into history
             let old = std::mem::replace(&mut ps.b_content, PaneContent::Json);
             ps.b_history.push(old);
             state.pane_b_title_stack.push(state.pane_b_title.clone());
+            state
+                .pane_b_highlights_stack
+                .push(state.pane_b_highlights.clone());
         }
         ps.b_content = PaneContent::Widget(widget);
     }
 }
 
+pub(crate) fn item_highlights(
+    mi: &crate::model::MenuItem,
+) -> Option<crate::widgets::highlight::Highlights> {
+    mi.highlights
+        .as_deref()
+        .and_then(crate::widgets::highlight::Highlights::new)
+}
+
 pub(crate) fn pane_b_back(state: &mut AppState) -> bool {
     if let Some(ps) = &mut state.panel {
         if let Some(prev) = ps.b_history.pop() {
             if let Some(prev_title) = state.pane_b_title_stack.pop() {
                 state.pane_b_title = prev_title;
             }
+            if let Some(prev) = state.pane_b_highlights_stack.pop() {
+                state.pane_b_highlights = prev;
+            }
             ps.b_content = prev;
             return true;
         }
@@ -294,96 +907,2290 @@ pub(crate) fn pane_b_back(state: &mut AppState) -> bool {
     false
 }
 
-#[derive(Clone, Copy)]
-pub enum ToastLevel {
-    Info,
-    Success,
-    Error,
+// The global JSON view shows `v`: through the renderer its envelope's `content_type` picks,
+// else in the result viewer (which keeps the value for copying either way)
+pub(crate) fn show_json_result(state: &mut AppState, title: impl Into<String>, v: JsonValue) {
+    let title = title.into();
+    state.json_rendered = crate::widgets::renderers::renderer(&title, &v);
+    state.json_viewer = Some(crate::widgets::result_viewer::ResultViewerWidget::new(
+        title, v,
+    ));
 }
 
-pub struct Toast {
-    pub text: String,
-    pub level: ToastLevel,
-    pub expires_at_tick: u64,
+// A key for the global JSON view's widget
+fn json_view_key(state: &mut AppState, code: KeyCode) {
+    let _ = match &mut state.json_rendered {
+        Some(w) => widget_key(w.as_mut(), code),
+        None => match &mut state.json_viewer {
+            Some(w) => widget_key(w, code),
+            None => Vec::new(),
+        },
+    };
 }
-#[derive(Clone)]
-pub(crate) enum FlatNode {
-    Header {
-        idx: usize,
-        depth: usize,
-    },
-    Menu {
-        idx: usize,
-        depth: usize,
-    },
-    Child {
-        key: String,
-        depth: usize,
-        val: JsonValue,
-    },
+
+// Result viewer that currently receives keys (global JSON view or Pane B)
+fn active_result_viewer(
+    state: &mut AppState,
+) -> Option<&mut crate::widgets::result_viewer::ResultViewerWidget> {
+    match state.view {
+        View::Json if state.json_rendered.is_some() => None,
+        View::Json => state.json_viewer.as_mut(),
+        View::Panel if matches!(state.panel_focus, PanelPane::B) => match &mut state.panel {
+            Some(PanelState {
+                b_content: PaneContent::Widget(w),
+                ..
+            }) => w.as_any_mut().downcast_mut(),
+            _ => None,
+        },
+        _ => None,
+    }
 }
-// Default is derived for View
-pub fn run() -> Result<()> {
-    // Load config anchored by CHI_TUI_CONFIG_DIR or by discovering chi-index.yaml
-    let cfg = load_config()?;
-    let mut state = AppState {
-        config: cfg,
-        header_h: 3,
-        logo_lines: Vec::new(),
-        panel_focus: PanelPane::A,
-        panel_nested_focus: PanelPane::A,
-        theme: crate::theme::Theme::synthwave_dark(),
-        animations_enabled: true,
-        animation_start_tick: 0,
-        horizontal_tab_index: 0,
-        current_config_path: None,
-        ..Default::default()
+
+// Log viewer in focused Pane B
+fn focused_log_viewer(
+    state: &mut AppState,
+) -> Option<&mut crate::widgets::log_viewer::LogViewerWidget> {
+    focused_widget(state)?.as_any_mut().downcast_mut()
+}
+
+// Widget that receives keys: the global result viewer, Pane B or a widget hosted in Pane A
+fn focused_widget(state: &mut AppState) -> Option<&mut dyn crate::widgets::Widget> {
+    match state.view {
+        View::Json => match &mut state.json_rendered {
+            Some(w) => Some(w.as_mut()),
+            None => state
+                .json_viewer
+                .as_mut()
+                .map(|w| w as &mut dyn crate::widgets::Widget),
+        },
+        View::Panel if matches!(state.panel_focus, PanelPane::A) => {
+            pane_a_widget(state).map(|w| w as &mut dyn crate::widgets::Widget)
+        }
+        View::Panel if matches!(state.panel_focus, PanelPane::B) => match &mut state.panel {
+            Some(PanelState {
+                b_content: PaneContent::Widget(w),
+                ..
+            }) => Some(w.as_mut()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// A raw-text viewer with a line selection open ('V')
+fn text_selection_active(w: &dyn crate::widgets::Widget) -> bool {
+    let any = w.as_any();
+    any.downcast_ref::<crate::widgets::json_viewer::JsonViewerWidget>()
+        .is_some_and(|jv| jv.selecting())
+        || any
+            .downcast_ref::<crate::widgets::result_viewer::ResultViewerWidget>()
+            .is_some_and(|rv| rv.selecting())
+}
+
+// Keys reach a widget through the error boundary: a panic leaves the key unhandled
+// and the widget is swapped for an error view (`pane_a_key`, or `recover_widget` for Pane B)
+fn widget_key(w: &mut dyn crate::widgets::Widget, code: KeyCode) -> Vec<Effect> {
+    crate::widgets::error_boundary::call(|| w.on_key(code))
+}
+
+// Keys for the widget hosted in Pane A; a panic swaps that widget (not Pane B) for an error view
+fn pane_a_key(state: &mut AppState, code: KeyCode) {
+    let effects = pane_a_widget(state)
+        .map(|w| widget_key(w, code))
+        .unwrap_or_default();
+    if let Some(message) = crate::widgets::error_boundary::take_failure() {
+        crash_pane_a(state, message);
+        return;
+    }
+    run_effects(state, effects);
+}
+
+// Put an error view in place of the Pane A widget that panicked
+fn crash_pane_a(state: &mut AppState, message: String) {
+    state.dbg(format!("Pane A widget panicked: {message}"));
+    if let Some(ps) = state.panel.as_mut() {
+        ps.a_content = Some(Box::new(crate::widgets::error_boundary::ErrorWidget::new(
+            "Pane A".to_string(),
+            message,
+            None,
+        )));
+    }
+}
+
+// Replace a Pane B widget that panicked with an error view whose reload enters the item again
+fn recover_widget(state: &mut AppState) -> bool {
+    let Some(message) = crate::widgets::error_boundary::take_failure() else {
+        return false;
     };
-    // Load logo from config (if any) and adjust header height
-    init_logo_and_header(&mut state);
-    let (tx, rx) = mpsc::channel::<LoadMsg>();
-    state.tx = Some(tx);
-    state.rx = Some(rx);
-    let (p_tx, p_rx) = mpsc::channel::<ProgressEvent>();
-    state.p_tx = Some(p_tx);
-    state.p_rx = Some(p_rx);
-    // Headless smoke mode
-    let headless = std::env::var("CHI_TUI_HEADLESS")
-        .ok()
-        .map(|v| v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("yes"))
-        .unwrap_or(false);
-    let headless_ticks: u64 = std::env::var("CHI_TUI_TICKS")
-        .ok()
-        .and_then(|s| s.parse::<u64>().ok())
-        .unwrap_or(10);
-    let headless_enter_id: Option<String> = std::env::var("CHI_TUI_HEADLESS_ENTER_ID").ok();
-    let headless_summary: bool = std::env::var("CHI_TUI_SMOKE_SUMMARY")
-        .ok()
-        .map(|v| v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("yes"))
-        .unwrap_or(false);
-    if headless {
-        let backend = ratatui::backend::TestBackend::new(80, 24);
-        let mut terminal = Terminal::new(backend)?;
-        let tick_rate = Duration::from_millis(200);
-        let mut last_tick = Instant::now();
-        let mut headless_enter_done = false;
-        let mut progress_seen = false;
-        let mut status_seen = false;
-        for _ in 0..headless_ticks {
-            if !state.boot_autoload_done {
-                trigger_initial_autoloads(&mut state);
-                state.boot_autoload_done = true;
+    state.dbg(format!("widget panicked: {message}"));
+    if matches!(state.view, View::Json) {
+        // The global result viewer has no slot for another widget: close it
+        state.json_viewer = None;
+        state.json_rendered = None;
+        state.view = View::Menu;
+        run_effects(
+            state,
+            vec![Effect::ShowToast {
+                text: format!("Result viewer crashed: {message}"),
+                level: ToastLevel::Error,
+                seconds: 5,
+            }],
+        );
+        return true;
+    }
+    let title = state
+        .pane_b_title
+        .clone()
+        .unwrap_or_else(|| "Widget".to_string());
+    let reload = state.pane_origin.clone();
+    if let Some(ps) = state.panel.as_mut() {
+        ps.b_content = PaneContent::Widget(Box::new(
+            crate::widgets::error_boundary::ErrorWidget::new(title, message, reload),
+        ));
+    }
+    true
+}
+
+// Readable content of a widget (Ctrl+C, action bar copy / open in editor)
+fn widget_text(w: &dyn crate::widgets::Widget) -> String {
+    let any = w.as_any();
+    if let Some(md) = any.downcast_ref::<crate::widgets::markdown::MarkdownWidget>() {
+        md.raw_content.clone()
+    } else if let Some(jv) = any.downcast_ref::<crate::widgets::json_viewer::JsonViewerWidget>() {
+        jv.selected_text().unwrap_or_else(|| jv.text.clone())
+    } else if let Some(rv) = any.downcast_ref::<crate::widgets::result_viewer::ResultViewerWidget>()
+    {
+        rv.selected_text()
+            .unwrap_or_else(|| serde_json::to_string_pretty(rv.value()).unwrap_or_default())
+    } else if let Some(pw) = any.downcast_ref::<crate::widgets::panel::PanelWidget>() {
+        let sub = match pw.nested_focus() {
+            PanelPane::A => &pw.a,
+            PanelPane::B => &pw.b,
+        };
+        sub.last_json_pretty
+            .clone()
+            .or_else(|| sub.last_error.clone())
+            .unwrap_or_default()
+    } else if let Some(fw) = crate::widgets::wizard::current_form(any) {
+        // Copy form data as text
+        fw.form
+            .fields
+            .iter()
+            .map(|field| format!("{}: {:?}", field.name, field.value))
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else if let Some(wd) = any.downcast_ref::<crate::widgets::watchdog::WatchdogWidget>() {
+        // Copy watchdog output
+        wd.cmds
+            .iter()
+            .map(|cmd| {
+                let lines = cmd.output.to_vec();
+                format!("=== {} ===\n{}", cmd.cmd, lines.join("\n"))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    } else if let Some(lv) = any.downcast_ref::<crate::widgets::log_viewer::LogViewerWidget>() {
+        lv.text()
+    } else if let Some(ew) = any.downcast_ref::<crate::widgets::error_boundary::ErrorWidget>() {
+        ew.message().to_string()
+    } else {
+        String::new()
+    }
+}
+
+// -------- Action bar (space) -----------------------------------------------
+pub(crate) struct ActionBar {
+    pub title: String,
+    pub actions: Vec<crate::widgets::WidgetAction>,
+    pub selected: usize,
+}
+
+fn open_action_bar(state: &mut AppState) {
+    let Some(w) = focused_widget(state) else {
+        return;
+    };
+    let actions = w.actions();
+    if actions.is_empty() {
+        return;
+    }
+    let title = match state.view {
+        View::Json => "Actions".to_string(),
+        _ => match &state.pane_b_title {
+            Some(t) => format!("Actions: {t}"),
+            None => "Actions".to_string(),
+        },
+    };
+    state.action_bar = Some(ActionBar {
+        title,
+        actions,
+        selected: 0,
+    });
+}
+
+fn action_bar_key(state: &mut AppState, code: KeyCode) {
+    let Some(bar) = state.action_bar.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Up => bar.selected = bar.selected.saturating_sub(1),
+        KeyCode::Down => bar.selected = (bar.selected + 1).min(bar.actions.len().saturating_sub(1)),
+        KeyCode::Esc | KeyCode::Char(' ') => state.action_bar = None,
+        KeyCode::Enter => {
+            let action = state
+                .action_bar
+                .take()
+                .and_then(|b| b.actions.get(b.selected).cloned());
+            if let Some(action) = action {
+                run_widget_action(state, action.kind);
             }
-            if !headless_enter_done {
-                if let Some(ref id) = headless_enter_id {
-                    if let Some(mi) = state.config.menu.iter().find(|m| &m.id == id).cloned() {
-                        let effs = update(&mut state, AppMsg::EnterMenu(mi));
-                        run_effects(&mut state, effs);
-                        headless_enter_done = true;
-                    }
-                }
+        }
+        _ => {}
+    }
+}
+
+fn run_widget_action(state: &mut AppState, kind: crate::widgets::ActionKind) {
+    use crate::widgets::ActionKind;
+    match kind {
+        ActionKind::Copy => {
+            if !open_copy_picker(state) {
+                let content = focused_widget(state)
+                    .map(|w| widget_text(w))
+                    .unwrap_or_default();
+                copy_to_clipboard(state, &content);
             }
-            terminal.draw(|f| ui(f, &mut state))?;
+            return;
+        }
+        ActionKind::CopyAs(format) => {
+            let Some(value) = focused_value(state) else {
+                return;
+            };
+            match crate::services::copy_formats::render(&value, format) {
+                Ok(text) => copy_to_clipboard(state, &text),
+                Err(e) => run_effects(
+                    state,
+                    vec![Effect::ShowToast {
+                        text: format!("Copy failed: {e}"),
+                        level: ToastLevel::Error,
+                        seconds: 3,
+                    }],
+                ),
+            }
+            return;
+        }
+        _ => {}
+    }
+    let Some(w) = focused_widget(state) else {
+        return;
+    };
+    let effects = match kind {
+        ActionKind::Key(code) => widget_key(w, code),
+        ActionKind::Refresh => w.refresh(),
+        ActionKind::Copy | ActionKind::CopyAs(_) => Vec::new(),
+        ActionKind::ExportImage => {
+            // Like Ctrl+P: rendered right after the next draw
+            state.image_export_pending = true;
+            Vec::new()
+        }
+        ActionKind::OpenInEditor => {
+            // Opened right after the next draw, where the terminal can be handed over
+            state.editor_pending = Some(widget_text(w));
+            Vec::new()
+        }
+    };
+    run_effects(state, effects);
+}
+
+fn copy_to_clipboard(state: &mut AppState, content: &str) {
+    if !content.is_empty() {
+        if let Ok(mut clipboard) = arboard::Clipboard::new() {
+            let _ = clipboard.set_text(content);
+            state.status_text = Some("Copied to clipboard!".to_string());
+        }
+    }
+}
+
+// JSON value behind the focused viewer, for structured copies (none while lines are selected)
+fn focused_value(state: &mut AppState) -> Option<JsonValue> {
+    let w = focused_widget(state)?;
+    if text_selection_active(w) {
+        return None;
+    }
+    let any = w.as_any();
+    if let Some(rv) = any.downcast_ref::<crate::widgets::result_viewer::ResultViewerWidget>() {
+        return Some(rv.value().clone());
+    }
+    let text = if let Some(jv) = any.downcast_ref::<crate::widgets::json_viewer::JsonViewerWidget>()
+    {
+        jv.text.clone()
+    } else if any.is::<crate::widgets::panel::PanelWidget>() {
+        widget_text(w)
+    } else {
+        return None;
+    };
+    serde_json::from_str(&text).ok()
+}
+
+// Ctrl+C on structured data: pick JSON / YAML / CSV / markdown first
+fn open_copy_picker(state: &mut AppState) -> bool {
+    let Some(value) = focused_value(state) else {
+        return false;
+    };
+    let actions = crate::services::copy_formats::available(&value)
+        .into_iter()
+        .map(|format| crate::widgets::WidgetAction {
+            label: format.label(),
+            keys: "",
+            kind: crate::widgets::ActionKind::CopyAs(format),
+        })
+        .collect();
+    state.action_bar = Some(ActionBar {
+        title: "Copy".to_string(),
+        actions,
+        selected: 0,
+    });
+    true
+}
+
+// Give the real terminal to `f` (editor, interactive login) and redraw from scratch after
+fn with_terminal_suspended<B: Backend, T>(
+    terminal: &mut Terminal<B>,
+    f: impl FnOnce() -> T,
+) -> Result<T> {
+    disable_raw_mode()?;
+    execute!(
+        std::io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste
+    )?;
+    let out = f();
+    enable_raw_mode()?;
+    execute!(
+        std::io::stdout(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    terminal.clear()?;
+    Ok(out)
+}
+
+// Suspend the TUI, open `text` in $VISUAL/$EDITOR (vi by default) and restore the screen
+fn open_in_editor<B: Backend>(terminal: &mut Terminal<B>, state: &mut AppState, text: &str) {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let path = std::env::temp_dir().join(format!("chi-tui-{}.txt", std::process::id()));
+    let result = std::fs::write(&path, text)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| {
+            let mut parts = shlex::split(&editor).unwrap_or_default();
+            if parts.is_empty() {
+                anyhow::bail!("empty editor command");
+            }
+            let program = parts.remove(0);
+            let status = with_terminal_suspended(terminal, || {
+                std::process::Command::new(program)
+                    .args(parts)
+                    .arg(&path)
+                    .status()
+            })?;
+            status.with_context(|| format!("running {editor}"))?;
+            Ok(())
+        });
+    let _ = std::fs::remove_file(&path);
+    if let Err(e) = result {
+        run_effects(
+            state,
+            vec![Effect::ShowToast {
+                text: format!("Open in editor failed: {e}"),
+                level: ToastLevel::Error,
+                seconds: 3,
+            }],
+        );
+    }
+}
+
+// -------- Quick-jump hints ('f') -------------------------------------------
+fn start_hint_mode(state: &mut AppState) {
+    use crate::nav::hints::{HintMode, HintTarget};
+    let mut targets = Vec::new();
+    // Left menu: visible rows only (hidden while the narrow drawer is closed)
+    let width = crossterm::terminal::size()
+        .map(|(w, _)| w)
+        .unwrap_or(u16::MAX);
+    let drawer_closed = state.view == View::Panel
+        && matches!(state.panel_focus, PanelPane::B)
+        && matches!(
+            narrow_panel_layout(state, width),
+            Some(NarrowLayout::Drawer)
+        );
+    if !drawer_closed {
+        let nodes = flatten_nodes(state);
+        let ih = state.menu_viewport_h as usize;
+        let start = state.menu_offset.min(nodes.len().saturating_sub(ih));
+        for (i, node) in nodes.iter().enumerate().skip(start).take(ih) {
+            if !matches!(node, FlatNode::Header { .. }) {
+                targets.push(HintTarget::Menu(i));
+            }
+        }
+    }
+    // Pane B: menu items, watchdog subpanes or result list items
+    if state.view == View::Panel {
+        if let Some(PanelState {
+            b_content: PaneContent::Widget(w),
+            ..
+        }) = &state.panel
+        {
+            if let Some(mw) = w
+                .as_any()
+                .downcast_ref::<crate::widgets::menu::MenuWidget>()
+            {
+                targets.extend(mw.visible_range().map(HintTarget::PaneBMenu));
+            } else if let Some(ww) = w
+                .as_any()
+                .downcast_ref::<crate::widgets::watchdog::WatchdogWidget>()
+            {
+                targets.extend((0..ww.pane_count()).map(HintTarget::WatchdogPane));
+            } else if let Some(rv) = w
+                .as_any()
+                .downcast_ref::<crate::widgets::result_viewer::ResultViewerWidget>()
+            {
+                targets.extend(rv.visible_items().into_iter().map(HintTarget::ResultItem));
+            }
+        }
+    }
+    if targets.is_empty() {
+        return;
+    }
+    state.hint_mode = Some(HintMode::new(targets));
+    sync_pane_b_hints(state);
+}
+
+fn hint_mode_key(state: &mut AppState, code: KeyCode) {
+    use crate::nav::hints::HintStep;
+    let step = match (code, state.hint_mode.as_mut()) {
+        (KeyCode::Char(c), Some(hm)) if c.is_ascii_alphabetic() => hm.push(c),
+        _ => HintStep::Miss,
+    };
+    if !matches!(step, HintStep::Pending) {
+        state.hint_mode = None;
+    }
+    sync_pane_b_hints(state);
+    if let HintStep::Jump(target) = step {
+        let effects = jump_to_hint(state, target);
+        run_effects(state, effects);
+    }
+}
+
+// Mirror the active labels into the Pane B widget so it can draw them
+fn sync_pane_b_hints(state: &mut AppState) {
+    use crate::nav::hints::HintTarget;
+    let hm = state.hint_mode.as_ref();
+    let Some(PanelState {
+        b_content: PaneContent::Widget(w),
+        ..
+    }) = &mut state.panel
+    else {
+        return;
+    };
+    let labels = |f: fn(HintTarget) -> Option<usize>| -> Vec<(usize, String)> {
+        hm.map(|hm| {
+            hm.targets
+                .iter()
+                .filter(|(label, _)| label.starts_with(&hm.typed))
+                .filter_map(|(label, t)| f(*t).map(|i| (i, label.clone())))
+                .collect()
+        })
+        .unwrap_or_default()
+    };
+    if let Some(mw) = w
+        .as_any_mut()
+        .downcast_mut::<crate::widgets::menu::MenuWidget>()
+    {
+        mw.hints = labels(|t| match t {
+            HintTarget::PaneBMenu(i) => Some(i),
+            _ => None,
+        });
+    } else if let Some(ww) = w
+        .as_any_mut()
+        .downcast_mut::<crate::widgets::watchdog::WatchdogWidget>()
+    {
+        ww.hints = labels(|t| match t {
+            HintTarget::WatchdogPane(i) => Some(i),
+            _ => None,
+        });
+    } else if let Some(rv) = w
+        .as_any_mut()
+        .downcast_mut::<crate::widgets::result_viewer::ResultViewerWidget>()
+    {
+        rv.hints = labels(|t| match t {
+            HintTarget::ResultItem(i) => Some(i),
+            _ => None,
+        });
+    }
+}
+
+fn jump_to_hint(state: &mut AppState, target: crate::nav::hints::HintTarget) -> Vec<Effect> {
+    use crate::nav::hints::HintTarget;
+    match target {
+        HintTarget::Menu(idx) => {
+            if state.view == View::Panel {
+                state.panel_focus = PanelPane::A;
+            }
+            state.selected = idx;
+            let nodes = flatten_nodes(state);
+            match nodes.get(idx) {
+                Some(FlatNode::Menu { idx, .. }) => match state.config.menu.get(*idx).cloned() {
+                    Some(mi) => update(state, AppMsg::EnterMenu(mi)),
+                    None => Vec::new(),
+                },
+                Some(node @ FlatNode::Child { key, .. }) => match node.val(state).cloned() {
+                    Some(val) => update(
+                        state,
+                        AppMsg::EnterChild {
+                            key: key.clone(),
+                            val,
+                        },
+                    ),
+                    None => Vec::new(),
+                },
+                _ => Vec::new(),
+            }
+        }
+        HintTarget::PaneBMenu(i) => {
+            state.panel_focus = PanelPane::B;
+            let mut enter = None;
+            if let Some(PanelState {
+                b_content: PaneContent::Widget(w),
+                ..
+            }) = &mut state.panel
+            {
+                if let Some(mw) = w
+                    .as_any_mut()
+                    .downcast_mut::<crate::widgets::menu::MenuWidget>()
+                {
+                    mw.selected = i;
+                    enter = mw.config.menu.get(i).cloned();
+                }
+            }
+            match enter {
+                Some(mi) => update(state, AppMsg::EnterMenu(mi)),
+                None => Vec::new(),
+            }
+        }
+        HintTarget::WatchdogPane(i) => {
+            state.panel_focus = PanelPane::B;
+            if let Some(PanelState {
+                b_content: PaneContent::Widget(w),
+                ..
+            }) = &mut state.panel
+            {
+                if let Some(ww) = w
+                    .as_any_mut()
+                    .downcast_mut::<crate::widgets::watchdog::WatchdogWidget>()
+                {
+                    ww.set_focused_pane(i);
+                }
+            }
+            Vec::new()
+        }
+        HintTarget::ResultItem(i) => {
+            state.panel_focus = PanelPane::B;
+            if let Some(PanelState {
+                b_content: PaneContent::Widget(w),
+                ..
+            }) = &mut state.panel
+            {
+                if let Some(rv) = w
+                    .as_any_mut()
+                    .downcast_mut::<crate::widgets::result_viewer::ResultViewerWidget>()
+                {
+                    rv.open_item(i);
+                }
+            }
+            Vec::new()
+        }
+    }
+}
+
+// Ctrl+E: write the main content area of the frame as a standalone HTML report
+fn export_html(state: &mut AppState, buf: &ratatui::buffer::Buffer) {
+    let screen = match state.view {
+        View::Menu => "Menu",
+        View::Welcome => "Welcome",
+        View::Json => "JSON Output",
+        View::Panel => state.pane_b_title.as_deref().unwrap_or("Panel"),
+        View::Scheduled => "Scheduled",
+        View::Workspaces => "Workspaces",
+        View::Results => "Results",
+    };
+    let title = match state.config.header.as_deref() {
+        Some(h) => format!("{h} — {screen}"),
+        None => screen.to_string(),
+    };
+    let html =
+        crate::services::html_export::buffer_to_html(buf, state.content_area, &title, &state.theme);
+    let toast = match crate::services::html_export::write_report(&html) {
+        Ok(path) => {
+            state.dbg(format!("html report: {}", path.display()));
+            Effect::ShowToast {
+                text: format!("Exported to {}", path.display()),
+                level: ToastLevel::Success,
+                seconds: 3,
+            }
+        }
+        Err(e) => Effect::ShowToast {
+            text: format!("Export failed: {e}"),
+            level: ToastLevel::Error,
+            seconds: 3,
+        },
+    };
+    run_effects(state, vec![toast]);
+}
+
+// Ctrl+P: save the focused Pane B widget (or the whole content area) as a PNG and copy it
+fn export_image(state: &mut AppState, buf: &ratatui::buffer::Buffer) {
+    use crate::services::image_export;
+    let widget_focused = matches!(state.view, View::Panel)
+        && matches!(state.panel_focus, PanelPane::B)
+        && state
+            .panel
+            .as_ref()
+            .is_some_and(|p| matches!(p.b_content, PaneContent::Widget(_)));
+    let area = if widget_focused {
+        state.pane_b_area
+    } else {
+        state.content_area
+    };
+    let image = image_export::buffer_to_image(buf, area, &state.theme);
+    let saved = image_export::encode_png(&image).and_then(|png| image_export::write_image(&png));
+    let toast = match saved {
+        Ok(path) => {
+            state.dbg(format!("image snapshot: {}", path.display()));
+            let copied = match image_export::copy_image(&image) {
+                Ok(()) => " (copied to clipboard)".to_string(),
+                Err(e) => {
+                    state.dbg(format!("image clipboard: {e}"));
+                    String::new()
+                }
+            };
+            Effect::ShowToast {
+                text: format!("Saved {}{copied}", path.display()),
+                level: ToastLevel::Success,
+                seconds: 3,
+            }
+        }
+        Err(e) => Effect::ShowToast {
+            text: format!("Image export failed: {e}"),
+            level: ToastLevel::Error,
+            seconds: 3,
+        },
+    };
+    run_effects(state, vec![toast]);
+}
+
+// -------- Startup checks (`preflight:`) ------------------------------------
+pub(crate) struct PreflightStatus {
+    pub check: crate::model::PreflightCheck,
+    pub pending: bool,
+    // First error line of the last failed run
+    pub failure: Option<String>,
+}
+
+impl PreflightStatus {
+    pub(crate) fn blocks(&self, item_id: &str) -> bool {
+        self.failure.is_some() && self.check.blocks.iter().any(|b| b == item_id)
+    }
+}
+
+// Run every check of the (entry or workspace) config in the background
+fn start_preflight(state: &mut AppState) {
+    state.preflight = state
+        .config
+        .preflight
+        .iter()
+        .map(|check| PreflightStatus {
+            check: check.clone(),
+            pending: false,
+            failure: None,
+        })
+        .collect();
+    let effects = (0..state.preflight.len())
+        .map(|index| Effect::RunPreflight { index })
+        .collect();
+    run_effects(state, effects);
+}
+
+// -------- Login (`auth:`) ---------------------------------------------------
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum RetryTarget {
+    Menu(MenuItem),
+    Child { key: String, val: JsonValue },
+    // A list that failed to load in the menu: load it again
+    MenuList(MenuItem),
+    ChildList { key: String, val: JsonValue },
+}
+
+pub(crate) struct AuthPrompt {
+    pub reason: String,
+    // Login command is running (device-code output below, or the terminal is handed over)
+    pub running: bool,
+    pub output: Option<crate::services::auth::LoginOutput>,
+    pub error: Option<String>,
+}
+
+// -------- Unsaved changes guard -------------------------------------------
+// Where the user was going when Pane B still had unsaved input
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum NavTarget {
+    // Esc: back to the menu view
+    Close,
+    // Backspace: previous Pane B content
+    Back,
+    // Enter on another item
+    Enter(RetryTarget),
+}
+
+impl NavTarget {
+    fn label(&self) -> &'static str {
+        match self {
+            NavTarget::Close => "Close the panel",
+            NavTarget::Back => "Go back",
+            NavTarget::Enter(_) => "Open another item",
+        }
+    }
+}
+
+pub(crate) fn pane_b_unsaved(state: &AppState) -> bool {
+    match &state.panel {
+        Some(PanelState {
+            b_content: PaneContent::Widget(w),
+            ..
+        }) => w.unsaved(),
+        _ => false,
+    }
+}
+
+// Ask before leaving unsaved Pane B input; true when the navigation is held back
+pub(crate) fn guard_nav(state: &mut AppState, target: NavTarget) -> bool {
+    if !pane_b_unsaved(state) {
+        return false;
+    }
+    state.nav_guard = Some(target);
+    true
+}
+
+// Carry out a navigation that was held back by the guard
+pub(crate) fn continue_nav(state: &mut AppState, target: NavTarget) -> Vec<Effect> {
+    match target {
+        NavTarget::Close => {
+            if state.config.can_close {
+                state.view = View::Menu;
+            }
+            Vec::new()
+        }
+        NavTarget::Back => {
+            let _ = pane_b_back(state);
+            Vec::new()
+        }
+        NavTarget::Enter(target) => crate::app::retry(state, target),
+    }
+}
+
+fn nav_guard_key(state: &mut AppState, code: KeyCode) {
+    let Some(target) = state.nav_guard.clone() else {
+        return;
+    };
+    let widget = match &mut state.panel {
+        Some(PanelState {
+            b_content: PaneContent::Widget(w),
+            ..
+        }) => Some(w),
+        _ => None,
+    };
+    match code {
+        // Save, then continue once the save succeeds; a save that does not start
+        // (validation errors) keeps the user on the pane
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            state.nav_guard = None;
+            let effects = widget.map(|w| w.save()).unwrap_or_default();
+            if !effects.is_empty() {
+                state.nav_after_save = Some(target);
+            }
+            run_effects(state, effects);
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            state.nav_guard = None;
+            if let Some(w) = widget {
+                w.discard();
+            }
+            let effects = continue_nav(state, target);
+            run_effects(state, effects);
+        }
+        KeyCode::Esc | KeyCode::Char('c') => state.nav_guard = None,
+        _ => {}
+    }
+}
+
+// What quitting would stop or lose: running watchdogs (all workspaces), scheduled jobs
+// and unsaved Pane B input
+pub(crate) fn quit_blockers(state: &AppState) -> Vec<String> {
+    let mut items = Vec::new();
+    let sessions = state
+        .watchdog_sessions
+        .iter()
+        .chain(state.parked_sessions.values().flatten());
+    let mut watchdogs: Vec<String> = sessions
+        .filter_map(|(key, session)| {
+            let s = session.lock().ok()?;
+            let running = s.summary().running;
+            let name = key.strip_prefix("menu:").unwrap_or(key);
+            if running > 0 {
+                Some(format!("watchdog {name} ({running} running)"))
+            } else if s.external_running {
+                Some(format!("watchdog {name} (external process)"))
+            } else {
+                None
+            }
+        })
+        .collect();
+    watchdogs.sort();
+    items.extend(watchdogs);
+    let jobs = state.scheduler.jobs.len();
+    if jobs > 0 {
+        items.push(format!("{jobs} scheduled job(s)"));
+    }
+    if pane_b_unsaved(state) {
+        items.push("unsaved changes in Pane B".to_string());
+    }
+    items
+}
+
+// 'q': true when the app should quit now, otherwise the confirmation is opened
+pub(crate) fn request_quit(state: &mut AppState) -> bool {
+    let items = match state.confirm_quit {
+        "never" => return true,
+        "always" => quit_blockers(state),
+        _ => {
+            let items = quit_blockers(state);
+            if items.is_empty() {
+                return true;
+            }
+            items
+        }
+    };
+    state.quit_confirm = Some(items);
+    false
+}
+
+// Keys of the quit confirmation; true when the quit is confirmed
+fn quit_confirm_key(state: &mut AppState, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Char('q') | KeyCode::Enter => {
+            state.quit_confirm = None;
+            true
+        }
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+            state.quit_confirm = None;
+            false
+        }
+        _ => false,
+    }
+}
+
+// Stop watchdog processes before quitting so none outlive the app
+fn stop_background(state: &mut AppState) {
+    let sessions = state
+        .watchdog_sessions
+        .values()
+        .chain(state.parked_sessions.values().flat_map(|m| m.values()));
+    for session in sessions {
+        if let Ok(mut s) = session.lock() {
+            s.stop_all();
+        }
+    }
+}
+
+// Open the login dialog unless it is already up
+pub(crate) fn offer_login(state: &mut AppState, reason: String) {
+    state.dbg(format!("auth: {reason}"));
+    if state.auth_prompt.is_none() && state.config.auth.is_some() {
+        state.auth_prompt = Some(AuthPrompt {
+            reason,
+            running: false,
+            output: None,
+            error: None,
+        });
+    }
+}
+
+fn auth_prompt_key(state: &mut AppState, code: KeyCode) {
+    let running = state.auth_prompt.as_ref().is_some_and(|p| p.running);
+    match code {
+        KeyCode::Enter if !running => run_effects(state, vec![Effect::Login]),
+        KeyCode::Esc => {
+            // Dismissed: nothing to retry (a device login still running finishes quietly)
+            state.auth_prompt = None;
+            state.login_retry = None;
+        }
+        _ => {}
+    }
+}
+
+// -------- Scheduling ('t' / 'T') -------------------------------------------
+pub(crate) struct SchedulePrompt {
+    pub title: String,
+    pub cmdline: String,
+    pub mutex: Option<String>,
+    pub input: String,
+    pub error: Option<String>,
+}
+
+// -------- Command line (':') -----------------------------------------------
+pub(crate) struct CommandPrompt {
+    // Backing CLI the schema comes from (`${APP_BIN}`)
+    pub program: String,
+    pub input: String,
+    pub cycle: Option<crate::services::completion::Cycle>,
+}
+
+fn start_command_prompt(state: &mut AppState) {
+    let program = crate::services::interpolate::env("${APP_BIN}");
+    // Start fetching the schema while the user types
+    let _ = crate::services::completion::schema_for(&program);
+    state.command_prompt = Some(CommandPrompt {
+        input: format!("{program} "),
+        program,
+        cycle: None,
+    });
+}
+
+fn command_prompt_key(state: &mut AppState, code: KeyCode) {
+    use crate::services::completion::{schema_for, Cycle};
+    let Some(prompt) = state.command_prompt.as_mut() else {
+        return;
+    };
+    let schema = schema_for(&prompt.program);
+    match code {
+        KeyCode::Esc => state.command_prompt = None,
+        KeyCode::Tab | KeyCode::BackTab => {
+            if let Some(schema) = schema {
+                let back = code == KeyCode::BackTab;
+                Cycle::step(&mut prompt.cycle, &schema, &mut prompt.input, back);
+            }
+        }
+        KeyCode::Right => {
+            if let Some(ghost) = schema.and_then(|s| s.ghost(&prompt.input)) {
+                prompt.input.push_str(&ghost);
+            }
+            prompt.cycle = None;
+        }
+        KeyCode::Backspace => {
+            prompt.input.pop();
+            prompt.cycle = None;
+        }
+        KeyCode::Char(c) => {
+            prompt.input.push(c);
+            prompt.cycle = None;
+        }
+        KeyCode::Enter => {
+            let cmdline = prompt.input.trim().to_string();
+            state.command_prompt = None;
+            if cmdline.is_empty() {
+                return;
+            }
+            state.dbg(format!("command line: {cmdline}"));
+            let mi = MenuItem {
+                id: "command-line".into(),
+                title: format!("$ {cmdline}"),
+                command: Some(cmdline),
+                ..Default::default()
+            };
+            let effs = update(state, AppMsg::EnterMenu(mi));
+            run_effects(state, effs);
+        }
+        _ => {}
+    }
+}
+
+// -------- Notes ('n') -----------------------------------------------------
+pub(crate) struct NotePrompt {
+    pub key: String,
+    pub title: String,
+    // Showing the saved note; editing starts right away when there is none
+    pub editing: bool,
+    pub input: String,
+    pub error: Option<String>,
+}
+
+// Notes key and title of the selected left-menu row (not headers or info rows)
+fn note_target(state: &AppState) -> Option<(String, String)> {
+    let nodes = flatten_nodes(state);
+    let node = nodes.get(state.selected)?;
+    match node {
+        FlatNode::Menu { idx, .. } => {
+            let mi = state.config.menu.get(*idx)?;
+            Some((crate::nav::keys::menu_key(mi), mi.title.clone()))
+        }
+        FlatNode::Child { key, .. } => {
+            let val = node.val(state)?;
+            let info = val
+                .get("__is_info")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            (!info).then(|| (key.clone(), title_from_value(val)))
+        }
+        FlatNode::Header { .. } => None,
+    }
+}
+
+fn start_note_prompt(state: &mut AppState) {
+    let Some((key, title)) = note_target(state) else {
+        return;
+    };
+    let saved = state.notes.get(&key).map(|n| n.text.clone());
+    state.note_prompt = Some(NotePrompt {
+        key,
+        title,
+        editing: saved.is_none(),
+        input: saved.unwrap_or_default(),
+        error: None,
+    });
+}
+
+fn note_prompt_key(state: &mut AppState, code: KeyCode) {
+    let Some(prompt) = state.note_prompt.as_mut() else {
+        return;
+    };
+    if !prompt.editing {
+        match code {
+            KeyCode::Char('e') | KeyCode::Enter => prompt.editing = true,
+            KeyCode::Char('d') => {
+                prompt.input.clear();
+                save_note(state);
+            }
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => state.note_prompt = None,
+            _ => {}
+        }
+        return;
+    }
+    match code {
+        // Back to the saved note, or out when there is none
+        KeyCode::Esc => match state.notes.get(&prompt.key) {
+            Some(note) => {
+                prompt.input = note.text.clone();
+                prompt.editing = false;
+                prompt.error = None;
+            }
+            None => state.note_prompt = None,
+        },
+        KeyCode::Backspace => {
+            prompt.input.pop();
+            prompt.error = None;
+        }
+        KeyCode::Char(c) => {
+            prompt.input.push(c);
+            prompt.error = None;
+        }
+        KeyCode::Enter => save_note(state),
+        _ => {}
+    }
+}
+
+// Write the prompt's text as the row's note (empty removes it) and close the prompt
+fn save_note(state: &mut AppState) {
+    let Some(prompt) = state.note_prompt.as_mut() else {
+        return;
+    };
+    let had = state.notes.has(&prompt.key);
+    let now = std::time::SystemTime::now();
+    if let Err(e) = state.notes.set(&prompt.key, &prompt.input, now) {
+        prompt.editing = true;
+        prompt.error = Some(e);
+        return;
+    }
+    let Some(prompt) = state.note_prompt.take() else {
+        return;
+    };
+    let text = if state.notes.has(&prompt.key) {
+        format!("Note saved for '{}'", prompt.title)
+    } else if had {
+        format!("Note removed from '{}'", prompt.title)
+    } else {
+        return;
+    };
+    let toast = Effect::ShowToast {
+        text,
+        level: ToastLevel::Success,
+        seconds: 2,
+    };
+    run_effects(state, vec![toast]);
+}
+
+// -------- Watches ('=') ------------------------------------------------------
+pub(crate) struct WatchPrompt {
+    // Command behind the result the watch reads
+    pub source: String,
+    pub input: String,
+    pub error: Option<String>,
+}
+
+// Command behind the focused result viewer, which watches can be added on
+fn watch_source(state: &mut AppState) -> Option<String> {
+    match state.view {
+        View::Json if state.json_viewer.is_some() => state.stream_source.clone(),
+        _ => active_result_viewer(state)?.source().map(String::from),
+    }
+}
+
+fn start_watch_prompt(state: &mut AppState) {
+    let Some(source) = watch_source(state) else {
+        return;
+    };
+    state.watch_prompt = Some(WatchPrompt {
+        source,
+        input: ".".into(),
+        error: None,
+    });
+}
+
+// Menu and panel state of a horizontal tab, parked while another tab is shown
+#[derive(Default)]
+pub(crate) struct TabState {
+    selected: usize,
+    menu_offset: usize,
+    view: View,
+    expanded: HashSet<String>,
+    children: HashMap<String, Vec<JsonValue>>,
+    pages: HashMap<String, crate::nav::pagination::Pagination>,
+    panel: Option<PanelState>,
+    panel_focus: PanelPane,
+    panel_nested_focus: PanelPane,
+    pane_b_title: Option<String>,
+    pane_b_title_stack: Vec<Option<String>>,
+    pane_b_highlights: Option<crate::widgets::highlight::Highlights>,
+    pane_b_highlights_stack: Vec<Option<crate::widgets::highlight::Highlights>>,
+    pane_b_empty: Option<crate::widgets::empty_state::EmptyState>,
+    pane_b_filters: Option<crate::widgets::filter_bar::FilterBar>,
+    pane_b_row_editor: Option<crate::widgets::row_edit::RowEditor>,
+    json_viewer: Option<crate::widgets::result_viewer::ResultViewerWidget>,
+    json_rendered: Option<Box<dyn crate::widgets::Widget>>,
+    last_json_pretty: Option<String>,
+    json_scroll_y: u16,
+    // What the panel was opened from: reloads, retries and the item's `on_leave_cmd` stay with it
+    pane_origin: Option<RetryTarget>,
+    panel_nesting: crate::chi_core::nesting::PanelNesting,
+    pane_a_title: Option<String>,
+    item_leave_hook: Option<LeaveHook>,
+    selection_context: Option<JsonValue>,
+    // The running stream shows its progress in this tab's Pane B
+    stream_in_panel: bool,
+}
+
+impl TabState {
+    // Moves the tab state out of `state`, leaving a fresh menu behind
+    fn take(state: &mut AppState) -> Self {
+        Self {
+            selected: std::mem::take(&mut state.selected),
+            menu_offset: std::mem::take(&mut state.menu_offset),
+            view: std::mem::take(&mut state.view),
+            expanded: std::mem::take(&mut *state.expanded),
+            children: std::mem::take(&mut *state.children),
+            pages: std::mem::take(&mut state.pages),
+            panel: state.panel.take(),
+            panel_focus: std::mem::take(&mut state.panel_focus),
+            panel_nested_focus: std::mem::take(&mut state.panel_nested_focus),
+            pane_b_title: state.pane_b_title.take(),
+            pane_b_title_stack: std::mem::take(&mut state.pane_b_title_stack),
+            pane_b_highlights: state.pane_b_highlights.take(),
+            pane_b_highlights_stack: std::mem::take(&mut state.pane_b_highlights_stack),
+            pane_b_empty: state.pane_b_empty.take(),
+            pane_b_filters: state.pane_b_filters.take(),
+            pane_b_row_editor: state.pane_b_row_editor.take(),
+            json_viewer: state.json_viewer.take(),
+            json_rendered: state.json_rendered.take(),
+            last_json_pretty: state.last_json_pretty.take(),
+            json_scroll_y: std::mem::take(&mut state.json_scroll_y),
+            pane_origin: state.pane_origin.take(),
+            panel_nesting: std::mem::take(&mut state.panel_nesting),
+            pane_a_title: state.pane_a_title.take(),
+            item_leave_hook: state.item_leave_hook.take(),
+            selection_context: state.selection_context.take(),
+            stream_in_panel: std::mem::take(&mut state.stream_in_panel),
+        }
+    }
+
+    fn restore(self, state: &mut AppState) {
+        let (selected, menu_offset) = (self.selected, self.menu_offset);
+        self.put_back(state);
+        // The screen's config was re-read: its menu may have shrunk
+        let rows = crate::nav::flatten::flatten_nodes(state).len();
+        state.selected = selected.min(rows.saturating_sub(1));
+        state.menu_offset = menu_offset.min(state.selected);
+    }
+
+    // Moves the tab state back as it was taken
+    fn put_back(self, state: &mut AppState) {
+        *state.expanded = self.expanded;
+        *state.children = self.children;
+        state.pages = self.pages;
+        state.selected = self.selected;
+        state.menu_offset = self.menu_offset;
+        state.view = self.view;
+        state.panel = self.panel;
+        state.panel_focus = self.panel_focus;
+        state.panel_nested_focus = self.panel_nested_focus;
+        state.pane_b_title = self.pane_b_title;
+        state.pane_b_title_stack = self.pane_b_title_stack;
+        state.pane_b_highlights = self.pane_b_highlights;
+        state.pane_b_highlights_stack = self.pane_b_highlights_stack;
+        state.pane_b_empty = self.pane_b_empty;
+        state.pane_b_filters = self.pane_b_filters;
+        state.pane_b_row_editor = self.pane_b_row_editor;
+        state.json_viewer = self.json_viewer;
+        state.json_rendered = self.json_rendered;
+        state.last_json_pretty = self.last_json_pretty;
+        state.json_scroll_y = self.json_scroll_y;
+        state.pane_origin = self.pane_origin;
+        state.panel_nesting = self.panel_nesting;
+        state.pane_a_title = self.pane_a_title;
+        state.item_leave_hook = self.item_leave_hook;
+        state.selection_context = self.selection_context;
+        state.stream_in_panel = self.stream_in_panel;
+    }
+}
+
+// Key of the parked tab whose Pane B shows the running stream
+pub(crate) fn parked_stream_tab(state: &AppState) -> Option<String> {
+    state
+        .tab_states
+        .iter()
+        .find(|(_, ts)| ts.stream_in_panel)
+        .map(|(key, _)| key.clone())
+}
+
+// Runs `f` on the tab parked under `key`, swapped in for the call and parked again after
+// (e.g. to end the stream viewer of a tab that is not shown)
+pub(crate) fn in_parked_tab<T>(
+    state: &mut AppState,
+    key: &str,
+    f: impl FnOnce(&mut AppState) -> T,
+) -> Option<T> {
+    let parked = state.tab_states.remove(key)?;
+    let shown = TabState::take(state);
+    parked.put_back(state);
+    let out = f(state);
+    let parked = TabState::take(state);
+    shown.put_back(state);
+    state.tab_states.insert(key.to_string(), parked);
+    Some(out)
+}
+
+// Parks the state of the tab being left under `key` and brings back the state the current
+// screen had when it was last left. False when the screen is shown for the first time.
+fn swap_tab_state(state: &mut AppState, key: String, parked: TabState) -> bool {
+    state.tab_states.insert(key, parked);
+    let current = state.current_config_path.clone().unwrap_or_default();
+    match state.tab_states.remove(&current) {
+        Some(ts) => {
+            ts.restore(state);
+            true
+        }
+        None => false,
+    }
+}
+
+// Switch to horizontal tab `index`: load its screen config, or the main one for a tab without
+// config (Home)
+fn open_tab(state: &mut AppState, index: usize) {
+    let prev_index = state.horizontal_tab_index;
+    let prev_key = state.current_config_path.clone().unwrap_or_default();
+    if let Some(config_path) = crate::widgets::horizontal_menu::select_tab(state, index) {
+        state.dbg(format!("load config: {config_path}"));
+        // Parked before the screen changes: the item open in this tab is not left
+        let parked = TabState::take(state);
+        // Load the new config file
+        if let Err(e) = load_config_from_path(state, &config_path) {
+            parked.restore(state);
+            let msg = format!("Failed to load {config_path}: {e}");
+            state.dbg(&msg);
+            state.last_error = Some(msg);
+        } else {
+            state.dbg(format!("loaded config: {config_path}"));
+            // A tab opened before comes back as it was left
+            if swap_tab_state(state, prev_key, parked) {
+                return;
+            }
+
+            // Trigger autoloads for the new config
+            trigger_initial_autoloads(state);
+
+            // Auto-enter a default menu item if specified by the screen config
+            if let Some(id) = state.config.auto_enter.clone() {
+                if let Some(mi) = state.config.menu.iter().find(|m| m.id == id).cloned() {
+                    let effs = crate::app::update(state, crate::app::AppMsg::EnterMenu(mi));
+                    run_effects(state, effs);
+                    // UX: when auto-opened, keep focus on left/menu (Pane A)
+                    if matches!(state.view, View::Panel) {
+                        state.panel_focus = PanelPane::A;
+                        state.panel_nested_focus = PanelPane::A;
+                    }
+                }
+            }
+        }
+    } else {
+        // select_tab returned None.
+        // Two possible cases:
+        // 1) Same tab pressed again -> do nothing.
+        // 2) Switched to a tab without config (Home) -> load main config.
+        let switched = state.horizontal_tab_index != prev_index;
+        if switched && index < state.config.horizontal_menu.len() {
+            let item = &state.config.horizontal_menu[index];
+            if item.config.is_none() && state.current_config_path.is_some() {
+                // This is a "Home" tab - reload main config
+                state.dbg("load config: main (home)");
+                let parked = TabState::take(state);
+                *state.config = load_config().unwrap_or_default();
+                state.current_config_path = None;
+                init_logo_and_header(state);
+                apply_chrome(state);
+                check_commands(state);
+                check_conditions(state, true);
+                enter_screen(state);
+                state.horizontal_tab_index = index;
+                if !swap_tab_state(state, prev_key, parked) {
+                    // Trigger autoloads for the main config
+                    trigger_initial_autoloads(state);
+                }
+                // No auto-enter on home by default
+            }
+        }
+    }
+}
+
+fn palette_key(state: &mut AppState, code: KeyCode) {
+    use crate::widgets::palette::PaletteTarget;
+    let Some(palette) = state.palette.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Esc => state.palette = None,
+        KeyCode::Up => palette.step(false),
+        KeyCode::Down => palette.step(true),
+        KeyCode::Backspace => palette.pop(),
+        KeyCode::Char(c) => palette.push(c),
+        KeyCode::Enter => {
+            let target = palette.chosen().map(|e| e.target.clone());
+            state.palette = None;
+            let effects = match target {
+                Some(PaletteTarget::Tab(index)) => {
+                    open_tab(state, index);
+                    Vec::new()
+                }
+                Some(PaletteTarget::Menu(idx)) => {
+                    select_menu_row(
+                        state,
+                        |n| matches!(n, FlatNode::Menu { idx: i, .. } if *i == idx),
+                    );
+                    match state.config.menu.get(idx).cloned() {
+                        Some(mi) => update(state, AppMsg::EnterMenu(mi)),
+                        None => Vec::new(),
+                    }
+                }
+                Some(PaletteTarget::Child {
+                    key,
+                    val,
+                    ancestors,
+                }) => {
+                    // Expand the way down so the row shows (and stays selected) in the menu
+                    state.expanded.extend(ancestors);
+                    select_menu_row(
+                        state,
+                        |n| matches!(n, FlatNode::Child { key: k, .. } if *k == key),
+                    );
+                    update(state, AppMsg::EnterChild { key, val })
+                }
+                None => Vec::new(),
+            };
+            run_effects(state, effects);
+        }
+        _ => {}
+    }
+}
+
+// Select the first left-menu row matching `is_row` (focusing the menu in the panel view)
+fn select_menu_row(state: &mut AppState, is_row: impl Fn(&FlatNode) -> bool) {
+    let Some(pos) = flatten_nodes(state).iter().position(is_row) else {
+        return;
+    };
+    if state.view == View::Panel {
+        state.panel_focus = PanelPane::A;
+    }
+    state.selected = pos;
+    let ih = state.menu_viewport_h as usize;
+    if pos < state.menu_offset {
+        state.menu_offset = pos;
+    } else if ih > 0 && pos >= state.menu_offset + ih {
+        state.menu_offset = pos + 1 - ih;
+    }
+}
+
+fn watch_prompt_key(state: &mut AppState, code: KeyCode) {
+    use crate::chi_core::watches::Watch;
+    let Some(prompt) = state.watch_prompt.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Esc => state.watch_prompt = None,
+        KeyCode::Backspace => {
+            prompt.input.pop();
+            prompt.error = None;
+        }
+        KeyCode::Char(c) => {
+            prompt.input.push(c);
+            prompt.error = None;
+        }
+        KeyCode::Enter => {
+            let source = prompt.source.clone();
+            // Nothing typed: drop the watches on this result
+            if matches!(prompt.input.trim(), "" | ".") {
+                state.watch_prompt = None;
+                let n = state.watches.clear_source(&source);
+                if n > 0 {
+                    let toast = Effect::ShowToast {
+                        text: format!("Removed {n} watch(es) from this result"),
+                        level: ToastLevel::Info,
+                        seconds: 2,
+                    };
+                    run_effects(state, vec![toast]);
+                }
+                return;
+            }
+            let watch = match Watch::parse(&source, &prompt.input) {
+                Ok(w) => w,
+                Err(e) => {
+                    prompt.error = Some(e);
+                    return;
+                }
+            };
+            state.watch_prompt = None;
+            let current = active_result_viewer(state)
+                .map(|rv| rv.value().clone())
+                .unwrap_or_default();
+            let expr = watch.expr.clone();
+            let (added, alert) = state.watches.toggle(watch, &current, Instant::now());
+            let text = match (added, alert) {
+                (true, Some(alert)) => format!("Watch: {alert}"),
+                (true, None) => format!("Watching {expr}"),
+                (false, _) => format!("Stopped watching {expr}"),
+            };
+            state.dbg(&text);
+            let toast = Effect::ShowToast {
+                text,
+                level: ToastLevel::Info,
+                seconds: 2,
+            };
+            run_effects(state, vec![toast]);
+        }
+        _ => {}
+    }
+}
+
+// Menu view, or panel view with the left menu focused
+fn left_menu_focused(state: &AppState) -> bool {
+    state.view == View::Menu
+        || (state.view == View::Panel
+            && matches!(state.panel_focus, PanelPane::A)
+            && state.panel.as_ref().is_none_or(|ps| ps.a_content.is_none()))
+}
+
+// Paginated list the selected left-menu row owns or belongs to
+pub(crate) fn paged_list(state: &AppState) -> Option<String> {
+    let nodes = flatten_nodes(state);
+    let candidates = match nodes.get(state.selected)? {
+        FlatNode::Menu { idx, .. } => {
+            vec![crate::nav::keys::menu_key(state.config.menu.get(*idx)?)]
+        }
+        FlatNode::Child { key, parent, .. } => vec![key.clone(), parent.clone()],
+        FlatNode::Header { .. } => return None,
+    };
+    candidates.into_iter().find(|k| state.pages.contains_key(k))
+}
+
+// Item hotkeys work on the menu and over panes without a widget taking the keys
+fn hotkeys_active(state: &mut AppState) -> bool {
+    left_menu_focused(state)
+        || (matches!(state.view, View::Menu | View::Panel) && focused_widget(state).is_none())
+}
+
+// Focused watchdog hosted in Pane A
+fn pane_a_watchdog(state: &mut AppState) -> Option<&mut crate::widgets::watchdog::WatchdogWidget> {
+    if state.view != View::Panel || !matches!(state.panel_focus, PanelPane::A) {
+        return None;
+    }
+    pane_a_widget(state)?.as_any_mut().downcast_mut()
+}
+
+// Widget shown in Pane A instead of the menu
+fn pane_a_widget(state: &mut AppState) -> Option<&mut (dyn crate::widgets::Widget + 'static)> {
+    state.panel.as_mut()?.a_content.as_deref_mut()
+}
+
+// (title, command) of the selected left-menu row when it runs a command
+fn runnable_selection(state: &AppState) -> Option<(String, String, Option<String>)> {
+    let nodes = flatten_nodes(state);
+    let node = nodes.get(state.selected)?;
+    match node {
+        FlatNode::Menu { idx, .. } => {
+            let mi = state.config.menu.get(*idx)?;
+            let plain = !(is_header(mi) || is_lazy(mi) || is_autoload(mi) || is_watchdog(mi));
+            plain
+                .then(|| {
+                    mi.command
+                        .clone()
+                        .map(|c| (mi.title.clone(), c, mi.mutex.clone()))
+                })
+                .flatten()
+        }
+        FlatNode::Child { .. } => {
+            let val = node.val(state)?;
+            if is_lazy_value(val) || is_autoload_value(val) {
+                return None;
+            }
+            let cmd = val.get("command").and_then(|s| s.as_str())?;
+            let mutex = val.get("mutex").and_then(|s| s.as_str()).map(String::from);
+            Some((title_from_value(val), cmd.to_string(), mutex))
+        }
+        FlatNode::Header { .. } => None,
+    }
+}
+
+// Item owning the selected left-menu row (itself, or the top-level item of a loaded child)
+// and the row's value for `verbs:` placeholders and `publish:`
+fn selection_source(state: &AppState) -> Option<(&crate::model::MenuItem, JsonValue)> {
+    let nodes = flatten_nodes(state);
+    let node = nodes.get(state.selected)?;
+    match node {
+        FlatNode::Menu { idx, .. } => {
+            let mi = state.config.menu.get(*idx)?;
+            Some((mi, serde_json::json!({"id": mi.id, "title": mi.title})))
+        }
+        FlatNode::Child { key, .. } => {
+            let mi = state.config.menu.iter().find(|mi| {
+                key.strip_prefix(&crate::nav::keys::menu_key(mi))
+                    .is_some_and(|rest| rest.starts_with('/'))
+            })?;
+            Some((mi, node.val(state)?.clone()))
+        }
+        FlatNode::Header { .. } => None,
+    }
+}
+
+// Value of the loaded child selected in the left menu (`${selected.<path>}` of the items it
+// triggers)
+pub(crate) fn selected_child_value(state: &AppState) -> Option<JsonValue> {
+    let nodes = flatten_nodes(state);
+    if !matches!(nodes.get(state.selected)?, FlatNode::Child { .. }) {
+        return None;
+    }
+    selection_source(state).map(|(_, row)| row)
+}
+
+// `publish:` (slot, value) of the selected child
+fn published_selection(state: &AppState) -> Option<(String, String)> {
+    let nodes = flatten_nodes(state);
+    if !matches!(nodes.get(state.selected)?, FlatNode::Child { .. }) {
+        return None;
+    }
+    let (mi, row) = selection_source(state)?;
+    let spec = mi.publish.as_ref()?;
+    let value = match row.get(spec.field.as_deref().unwrap_or("id"))? {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Null => return None,
+        other => other.to_string(),
+    };
+    Some((spec.slot.clone(), value))
+}
+
+// `publish:` (slot, value) of the node selected in a tree shown in Pane B or one of its subpanes
+fn tree_selection(state: &AppState) -> Option<(String, String)> {
+    use crate::widgets::tree::TreeWidget;
+    let PaneContent::Widget(w) = &state.panel.as_ref()?.b_content else {
+        return None;
+    };
+    if let Some(tree) = w.as_any().downcast_ref::<TreeWidget>() {
+        return tree.published();
+    }
+    let panel = w
+        .as_any()
+        .downcast_ref::<crate::widgets::panel::PanelWidget>()?;
+    [PanelPane::A, PanelPane::B].into_iter().find_map(|sub| {
+        panel
+            .subpane_widget(sub)?
+            .as_any()
+            .downcast_ref::<TreeWidget>()?
+            .published()
+    })
+}
+
+// Moving the selection onto another child (or tree node) updates its slot (re-running the
+// panes reading it)
+fn publish_selection(state: &mut AppState) {
+    for (slot, value) in [published_selection(state), tree_selection(state)]
+        .into_iter()
+        .flatten()
+    {
+        if state.slots.get(&slot) != Some(value.as_str()) {
+            let effects = update(state, AppMsg::Publish { slot, value });
+            run_effects(state, effects);
+        }
+    }
+}
+
+// `verbs:` the item to open for key `c` on the selected row (its command in Pane B)
+fn selected_verb(state: &AppState, c: char) -> Option<crate::model::MenuItem> {
+    let (mi, row) = selection_source(state)?;
+    let verb = mi.verbs.as_ref()?.iter().find(|v| v.key == c)?;
+    let name = match row.get("id").and_then(|s| s.as_str()) {
+        Some(id) if row.get("title").is_none() && row.get("name").is_none() => id.to_string(),
+        _ => title_from_value(&row),
+    };
+    let title = format!("{name} — {}", verb.title);
+    Some(crate::model::MenuItem {
+        id: format!("{}:{}", mi.id, verb.key),
+        title: title.clone(),
+        command: Some(crate::widgets::row_edit::fill_placeholders(
+            &verb.command,
+            &row,
+        )),
+        pane_b_title: Some(title),
+        highlights: mi.highlights.clone(),
+        mutex: mi.mutex.clone(),
+        ..Default::default()
+    })
+}
+
+// Whether the verb on key `c` asks before running
+fn verb_confirms(state: &AppState, c: char) -> bool {
+    selection_source(state)
+        .and_then(|(mi, _)| mi.verbs.as_ref()?.iter().find(|v| v.key == c).cloned())
+        .is_some_and(|v| v.confirm)
+}
+
+fn verb_confirm_key(state: &mut AppState, code: KeyCode) {
+    let Some(mi) = state.verb_confirm.take() else {
+        return;
+    };
+    if matches!(code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+        let effects = update(state, AppMsg::EnterMenu(mi));
+        run_effects(state, effects);
+    }
+}
+
+// " • l logs • d describe" for the selected row
+fn verb_hint(state: &AppState) -> String {
+    let Some(verbs) = selection_source(state).and_then(|(mi, _)| mi.verbs.as_ref()) else {
+        return String::new();
+    };
+    verbs
+        .iter()
+        .map(|v| format!(" • {} {}", v.key, v.title.to_lowercase()))
+        .collect()
+}
+
+fn start_schedule_prompt(state: &mut AppState) {
+    let Some((title, cmdline, mutex)) = runnable_selection(state) else {
+        let toast = Effect::ShowToast {
+            text: "Nothing to schedule: the selected item does not run a command".into(),
+            level: ToastLevel::Info,
+            seconds: 2,
+        };
+        run_effects(state, vec![toast]);
+        return;
+    };
+    state.schedule_prompt = Some(SchedulePrompt {
+        title,
+        cmdline,
+        mutex,
+        input: String::new(),
+        error: None,
+    });
+}
+
+fn schedule_prompt_key(state: &mut AppState, code: KeyCode) {
+    let Some(prompt) = state.schedule_prompt.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Esc => state.schedule_prompt = None,
+        KeyCode::Backspace => {
+            prompt.input.pop();
+            prompt.error = None;
+        }
+        KeyCode::Char(c) => {
+            prompt.input.push(c);
+            prompt.error = None;
+        }
+        KeyCode::Enter => {
+            let now = std::time::SystemTime::now();
+            let added = state
+                .scheduler
+                .add(
+                    &prompt.title,
+                    &prompt.cmdline,
+                    prompt.mutex.clone(),
+                    &prompt.input,
+                    now,
+                )
+                .map(|job| {
+                    format!(
+                        "Scheduled '{}' — next run {} ({})",
+                        job.title,
+                        crate::services::clock::format_local(job.next_run),
+                        crate::services::scheduler::format_eta(job.next_run, now)
+                    )
+                });
+            match added {
+                Ok(text) => {
+                    state.schedule_prompt = None;
+                    state.dbg(&text);
+                    let toast = Effect::ShowToast {
+                        text,
+                        level: ToastLevel::Success,
+                        seconds: 3,
+                    };
+                    run_effects(state, vec![toast]);
+                }
+                Err(e) => prompt.error = Some(e),
+            }
+        }
+        _ => {}
+    }
+}
+
+// The key press a terminal event stands for. Key releases (reported on Windows and by
+// terminals with keyboard enhancements) are dropped so characters aren't typed twice. A paste
+// (also how some input methods commit composed text) goes into the form field being edited,
+// or is replayed as typed characters into the prompt or field collecting text. With no text
+// input focused it is dropped, so pasted text never fires shortcuts.
+fn input_key(state: &mut AppState, event: Event) -> Option<KeyEvent> {
+    match event {
+        Event::Key(key) if key.kind != KeyEventKind::Release => Some(key),
+        Event::Paste(text) => {
+            if let Some(fw) = editing_form_b(state) {
+                let effs = fw.paste(&text);
+                run_effects(state, effs);
+            } else if text_input_focused(state) {
+                state.pending_keys.extend(
+                    text.chars()
+                        .filter(|c| !c.is_control())
+                        .map(|c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)),
+                );
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+// A prompt, search or filter is collecting typed text
+fn text_input_focused(state: &mut AppState) -> bool {
+    if state.hint_mode.is_some() {
+        return false;
+    }
+    if state.debug_focused {
+        return state
+            .debug_console
+            .as_ref()
+            .is_some_and(|c| c.filter_editing());
+    }
+    state.schedule_prompt.is_some()
+        || state.command_prompt.is_some()
+        || state.note_prompt.is_some()
+        || state.watch_prompt.is_some()
+        || state.palette.is_some()
+        || active_result_viewer(state).is_some_and(|w| w.search_active() || w.editing())
+        || focused_log_viewer(state).is_some_and(|w| w.filter_editing())
+}
+
+// The form in focused Pane B, while one of its fields is being edited
+fn editing_form_b(state: &mut AppState) -> Option<&mut crate::widgets::form_widget::FormWidget> {
+    if !matches!(state.view, View::Panel) || !matches!(state.panel_focus, PanelPane::B) {
+        return None;
+    }
+    let PaneContent::Widget(w) = &mut state.panel.as_mut()?.b_content else {
+        return None;
+    };
+    crate::widgets::wizard::current_form_mut(w.as_any_mut()).filter(|fw| fw.form.editing)
+}
+
+fn scheduled_screen_key(state: &mut AppState, code: KeyCode) {
+    let total = state.scheduler.jobs.len();
+    match code {
+        KeyCode::Up => state.scheduled_selected = state.scheduled_selected.saturating_sub(1),
+        KeyCode::Down if state.scheduled_selected + 1 < total => state.scheduled_selected += 1,
+        KeyCode::Char('d') | KeyCode::Delete => {
+            let id = state
+                .scheduler
+                .jobs
+                .get(state.scheduled_selected)
+                .map(|j| j.id);
+            if let Some(job) = id.and_then(|id| state.scheduler.cancel(id)) {
+                state.dbg(format!("scheduled job {} cancelled", job.id));
+                state.scheduled_selected = state
+                    .scheduled_selected
+                    .min(state.scheduler.jobs.len().saturating_sub(1));
+                let toast = Effect::ShowToast {
+                    text: format!("Cancelled '{}'", job.title),
+                    level: ToastLevel::Info,
+                    seconds: 2,
+                };
+                run_effects(state, vec![toast]);
+            }
+        }
+        KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('T') => {
+            state.view = state.screen_return;
+        }
+        _ => {}
+    }
+}
+
+// Results screen ('H' or a `builtin: results` item)
+pub(crate) fn open_results(state: &mut AppState) {
+    if state.view != View::Results {
+        state.screen_return = state.view;
+    }
+    state.results_screen = Default::default();
+    state.view = View::Results;
+}
+
+fn results_screen_key(state: &mut AppState, code: KeyCode) {
+    let screen = &mut state.results_screen;
+    if screen.diff.is_some() {
+        match code {
+            KeyCode::Up => screen.scroll = screen.scroll.saturating_sub(1),
+            KeyCode::Down => screen.scroll = screen.scroll.saturating_add(1),
+            KeyCode::PageUp => screen.scroll = screen.scroll.saturating_sub(10),
+            KeyCode::PageDown => screen.scroll = screen.scroll.saturating_add(10),
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('D') => {
+                screen.diff = None;
+                screen.scroll = 0;
+            }
+            _ => {}
+        }
+        return;
+    }
+    let total = state.results.len();
+    match code {
+        KeyCode::Up => screen.selected = screen.selected.saturating_sub(1),
+        KeyCode::Down if screen.selected + 1 < total => screen.selected += 1,
+        KeyCode::Home => screen.selected = 0,
+        KeyCode::End => screen.selected = total.saturating_sub(1),
+        KeyCode::Char(' ') => {
+            let id = state.results.get(screen.selected).map(|e| e.id);
+            screen.marked = if screen.marked == id { None } else { id };
+        }
+        KeyCode::Char('D') => match screen.diff_pair(&state.results) {
+            Some(pair) => screen.diff = Some(pair),
+            None => {
+                let toast = Effect::ShowToast {
+                    text: "Mark a result with space to compare it, or select an older one".into(),
+                    level: ToastLevel::Info,
+                    seconds: 3,
+                };
+                run_effects(state, vec![toast]);
+            }
+        },
+        KeyCode::Enter => {
+            let Some(entry) = state.results.get(screen.selected) else {
+                return;
+            };
+            let (title, cmdline, value) = (
+                entry.title.clone(),
+                entry.cmdline.clone(),
+                entry.value.clone(),
+            );
+            state.dbg(format!("re-open result: {title}"));
+            state.last_error = None;
+            state.last_json_pretty = Some(crate::widgets::large_json::stored_pretty(&value));
+            state.stream_source = cmdline;
+            show_json_result(state, title, value);
+            state.json_scroll_y = 0;
+            state.view = View::Json;
+        }
+        KeyCode::Char('e') => {
+            let Some(entry) = state.results.get(screen.selected) else {
+                return;
+            };
+            let toast = match crate::widgets::results::export(entry) {
+                Ok(path) => Effect::ShowToast {
+                    text: format!("Exported to {}", path.display()),
+                    level: ToastLevel::Success,
+                    seconds: 3,
+                },
+                Err(e) => Effect::ShowToast {
+                    text: format!("Export failed: {e}"),
+                    level: ToastLevel::Error,
+                    seconds: 3,
+                },
+            };
+            run_effects(state, vec![toast]);
+        }
+        KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('H') => {
+            state.view = state.screen_return;
+        }
+        _ => {}
+    }
+}
+
+// Start jobs whose time has come, whatever screen is open; results arrive as ScheduledDone
+// `O`: serve cached results only (`cache:`), e.g. while the network is down
+fn toggle_offline(state: &mut AppState) {
+    use crate::services::result_cache;
+    let text = if !result_cache::enabled() {
+        "Offline mode needs `cache:` in the config"
+    } else if result_cache::offline() {
+        result_cache::set_offline(false);
+        "Online: commands run again"
+    } else {
+        result_cache::set_offline(true);
+        "Offline: showing cached results"
+    };
+    run_effects(
+        state,
+        vec![Effect::ShowToast {
+            text: text.into(),
+            level: crate::ui::ToastLevel::Info,
+            seconds: 2,
+        }],
+    );
+}
+
+// -------- Enter/leave hooks (`on_enter_cmd` / `on_leave_cmd`) -------------
+// `on_leave_cmd` waiting for its screen or item to be left
+pub(crate) struct LeaveHook {
+    pub label: String,
+    pub cmdline: String,
+    // The item's view was seen open: going back to the menu leaves the item too
+    pub opened: bool,
+}
+
+impl LeaveHook {
+    pub(crate) fn effect(self) -> Effect {
+        Effect::RunHook {
+            label: self.label,
+            cmdline: self.cmdline,
+        }
+    }
+}
+
+// A screen was loaded: the item and screen left behind run their `on_leave_cmd` (an item kept
+// open in a parked tab is not left), then this screen its `on_enter_cmd`
+fn enter_screen(state: &mut AppState) {
+    let label = state
+        .current_config_path
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+    let mut effects: Vec<Effect> = [state.item_leave_hook.take(), state.screen_leave_hook.take()]
+        .into_iter()
+        .flatten()
+        .map(LeaveHook::effect)
+        .collect();
+    if let Some(cmdline) = state.config.on_enter_cmd.clone() {
+        effects.push(Effect::RunHook {
+            label: format!("enter screen {label}"),
+            cmdline,
+        });
+    }
+    state.screen_leave_hook = state.config.on_leave_cmd.clone().map(|cmdline| LeaveHook {
+        label: format!("leave screen {label}"),
+        cmdline,
+        opened: false,
+    });
+    run_effects(state, effects);
+}
+
+// The view an item opened was closed (back to the menu)
+fn run_leave_hooks(state: &mut AppState) {
+    let Some(hook) = &mut state.item_leave_hook else {
+        return;
+    };
+    if state.view != View::Menu {
+        hook.opened = true;
+    } else if hook.opened {
+        let effects = state.item_leave_hook.take().map(LeaveHook::effect);
+        run_effects(state, effects.into_iter().collect());
+    }
+}
+
+// Hand a due telemetry batch to the hook (everything left on quit)
+fn flush_telemetry(state: &mut AppState, force: bool) -> Option<std::thread::JoinHandle<()>> {
+    let cfg = state.config.telemetry.clone()?;
+    let batch = state.telemetry.take_batch(&cfg, force)?;
+    state.dbg(format!("telemetry: sending {} event(s)", batch.len()));
+    Some(crate::services::telemetry::spawn_send(cfg.command, batch))
+}
+
+// Deprecated config keys found since the last pass: each goes to the debug log, plus one toast
+fn report_config_warnings(state: &mut AppState) {
+    let warnings = crate::chi_core::migrate::take_warnings();
+    if warnings.is_empty() {
+        return;
+    }
+    for w in &warnings {
+        state.dbg(format!("deprecated config: {w}"));
+    }
+    let toast = Effect::ShowToast {
+        text: format!(
+            "{} deprecated config key(s): see the debug log (Ctrl+D) or run `chi-tui migrate`",
+            warnings.len()
+        ),
+        level: ToastLevel::Info,
+        seconds: 5,
+    };
+    run_effects(state, vec![toast]);
+}
+
+fn run_due_jobs(state: &mut AppState) {
+    let due = state.scheduler.take_due(std::time::SystemTime::now());
+    for job in due {
+        state.dbg(format!("scheduled run: {} :: {}", job.title, job.cmdline));
+        let id = job.id;
+        let reply =
+            crate::services::bus::subscribe(state, format!("sched:{id}"), move |st, outcome| {
+                update(st, AppMsg::ScheduledDone { id, outcome })
+            });
+        if let Some(reply) = reply {
+            let ticket = job.mutex.map(|m| mutex_ticket(state, &m));
+            crate::services::loader::spawn_scheduled_cmd(job.cmdline, reply, ticket);
+        }
+        let toast = Effect::ShowToast {
+            text: format!("Running scheduled '{}'", job.title),
+            level: ToastLevel::Info,
+            seconds: 2,
+        };
+        run_effects(state, vec![toast]);
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Error,
+}
+
+impl ToastLevel {
+    pub fn status(self) -> crate::theme::Status {
+        match self {
+            ToastLevel::Info => crate::theme::Status::Info,
+            ToastLevel::Success => crate::theme::Status::Ok,
+            ToastLevel::Error => crate::theme::Status::Fail,
+        }
+    }
+}
+
+pub struct Toast {
+    pub text: String,
+    pub level: ToastLevel,
+    pub expires_at_tick: u64,
+}
+
+// Number of ticks (200ms each) a pane border stays highlighted after it received content
+pub(crate) const PANE_FLASH_TICKS: u64 = 3;
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PaneFlash {
+    pub pane: PanelPane,
+    pub until_tick: u64,
+}
+#[derive(Clone)]
+pub(crate) enum FlatNode {
+    Header {
+        idx: usize,
+        depth: usize,
+    },
+    Menu {
+        idx: usize,
+        depth: usize,
+    },
+    // Child `index` of `parent`; the value stays in `state.children`
+    Child {
+        key: String,
+        depth: usize,
+        parent: String,
+        index: usize,
+    },
+}
+
+impl FlatNode {
+    pub(crate) fn val<'a>(&self, state: &'a AppState) -> Option<&'a JsonValue> {
+        match self {
+            FlatNode::Child { parent, index, .. } => state.children.get(parent)?.get(*index),
+            _ => None,
+        }
+    }
+}
+// `chi-tui tree [--json]`: print the resolved menu hierarchy and exit
+pub fn print_tree(args: &[String]) -> Result<()> {
+    crate::chi_core::vars::set_overrides(args).map_err(anyhow::Error::msg)?;
+    let cfg = load_config()?;
+    let tree = crate::nav::tree::menu_tree(&cfg);
+    let out = if args.iter().any(|a| a == "--json") {
+        serde_json::to_string_pretty(&crate::nav::tree::to_json(&tree))? + "\n"
+    } else {
+        crate::nav::tree::render_text(&tree)
+    };
+    // Piping into `head` closes stdout early; that is not an error
+    match std::io::Write::write_all(&mut std::io::stdout().lock(), out.as_bytes()) {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        r => Ok(r?),
+    }
+}
+
+// `chi-tui migrate [--dry-run]`: rewrite config files with deprecated keys to the current
+// spec_version, keeping each original as `<file>.bak`
+pub fn migrate(args: &[String]) -> Result<()> {
+    crate::chi_core::vars::set_overrides(args).map_err(anyhow::Error::msg)?;
+    load_config()?;
+    let dir = PathBuf::from(std::env::var("CHI_TUI_CONFIG_DIR")?);
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let files = crate::chi_core::migrate::scan(&dir);
+    let mut out = String::new();
+    for f in &files {
+        let name = f.path.strip_prefix(&dir).unwrap_or(&f.path).display();
+        for note in &f.notes {
+            out.push_str(&format!("{name}: {note}\n"));
+        }
+        if !dry_run {
+            let mut bak = f.path.clone().into_os_string();
+            bak.push(".bak");
+            fs::copy(&f.path, &bak).with_context(|| format!("backing up {:?}", f.path))?;
+            fs::write(&f.path, &f.text).with_context(|| format!("writing {:?}", f.path))?;
+        }
+    }
+    let version = crate::chi_core::migrate::SPEC_VERSION;
+    out.push_str(&match (files.len(), dry_run) {
+        (0, _) => format!("Config is up to date (spec_version {version})\n"),
+        (n, true) => format!("{n} file(s) would be migrated to spec_version {version}\n"),
+        (n, false) => {
+            format!("{n} file(s) migrated to spec_version {version}; originals kept as *.bak\n")
+        }
+    });
+    print!("{out}");
+    Ok(())
+}
+
+// Default is derived for View
+pub fn run(args: &[String]) -> Result<()> {
+    // `--var name=value` overrides the config's `vars:`
+    crate::chi_core::vars::set_overrides(args).map_err(anyhow::Error::msg)?;
+    // Load config anchored by CHI_TUI_CONFIG_DIR or by discovering chi-index.yaml
+    let cfg = load_config_or_setup()?;
+    crate::widgets::error_boundary::install_hook();
+    let mut state = AppState {
+        config: Tracked::new(cfg),
+        header_h: 3,
+        logo_lines: Vec::new(),
+        panel_focus: PanelPane::A,
+        panel_nested_focus: PanelPane::A,
+        theme: crate::theme::Theme::synthwave_dark(),
+        animations_enabled: true,
+        animation_start_frame: 0,
+        horizontal_tab_index: 0,
+        current_config_path: None,
+        ..Default::default()
+    };
+    // `theme:` (or theme.yaml), `colorblind:` and `status_glyphs:` of the entry config apply
+    // for the session
+    load_theme_file(&mut state);
+    let preset = state
+        .config
+        .theme
+        .as_ref()
+        .and_then(|t| t.preset.as_deref());
+    state.theme_index = crate::theme::PRESETS
+        .iter()
+        .position(|p| Some(*p) == preset)
+        .unwrap_or(0);
+    let preset = crate::theme::PRESETS[state.theme_index];
+    state.theme = crate::theme::Theme::for_preset(state.config.theme.as_ref(), preset)
+        .with_config(&state.config);
+    state.entry_theme = state.config.theme.clone();
+    crate::theme::set_active(state.theme.clone());
+    // `animations: off` in the entry config disables every effect for the session
+    state.animations_enabled = !state.config.animations_off();
+    state.reduced_motion = state.config.reduced_motion;
+    state.entry_chrome = state.config.chrome.clone();
+    state.confirm_quit = state.config.confirm_quit();
+    apply_chrome(&state);
+    check_commands(&mut state);
+    if let Ok(dir) = std::env::var("CHI_TUI_CONFIG_DIR") {
+        state.workspaces = resolve_workspaces(&state.config, &PathBuf::from(&dir));
+        state.notes = crate::services::notes::Notes::load(std::path::Path::new(&dir));
+    }
+    // Load logo from config (if any) and adjust header height
+    init_logo_and_header(&mut state);
+    let (tx, rx) = mpsc::channel::<LoadMsg>();
+    state.tx = Some(tx);
+    state.rx = Some(rx);
+    let (p_tx, p_rx) = mpsc::channel::<ProgressEvent>();
+    state.p_tx = Some(p_tx);
+    state.p_rx = Some(p_rx);
+    // Headless smoke mode
+    let headless = std::env::var("CHI_TUI_HEADLESS")
+        .ok()
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("yes"))
+        .unwrap_or(false);
+    let headless_ticks: u64 = std::env::var("CHI_TUI_TICKS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(10);
+    let headless_enter_id: Option<String> = std::env::var("CHI_TUI_HEADLESS_ENTER_ID").ok();
+    state.frame_budget = std::env::var("CHI_TUI_FRAME_BUDGET_MS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis);
+    let headless_summary: bool = std::env::var("CHI_TUI_SMOKE_SUMMARY")
+        .ok()
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("yes"))
+        .unwrap_or(false);
+    if headless {
+        let backend = ratatui::backend::TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend)?;
+        let tick_rate = Duration::from_millis(200);
+        let mut last_tick = Instant::now();
+        let mut headless_enter_done = false;
+        let mut progress_seen = false;
+        let mut status_seen = false;
+        for _ in 0..headless_ticks {
+            if !state.boot_autoload_done {
+                trigger_initial_autoloads(&mut state);
+                crate::services::result_cache::configure(state.config.cache.as_ref());
+                start_preflight(&mut state);
+                check_conditions(&mut state, false);
+                enter_screen(&mut state);
+                crate::widgets::notice::load_config(&mut state);
+                run_effects(&mut state, vec![Effect::CheckAuth]);
+                state.boot_autoload_done = true;
+            }
+            if !headless_enter_done {
+                if let Some(ref id) = headless_enter_id {
+                    if let Some(mi) = state.config.menu.iter().find(|m| &m.id == id).cloned() {
+                        let effs = update(&mut state, AppMsg::EnterMenu(mi));
+                        run_effects(&mut state, effs);
+                        headless_enter_done = true;
+                    }
+                }
+            }
+            publish_selection(&mut state);
+            let started = Instant::now();
+            terminal.draw(|f| ui(f, &mut state))?;
+            state.check_budget("draw", started);
             // Pump async loader results
             let mut drained_msgs: Vec<LoadMsg> = Vec::new();
             if let Some(rx) = &state.rx {
@@ -392,98 +3199,41 @@ pub fn run() -> Result<()> {
                 }
             }
             for msg in drained_msgs {
-                state.loading.remove(&msg.key);
-                let key = msg.key;
-                let outcome = msg.outcome;
-                let effects = match msg.kind {
-                    LoadKind::Menu => update(&mut state, AppMsg::LoadedMenu { key, outcome }),
-                    LoadKind::Child => update(&mut state, AppMsg::LoadedChild { key, outcome }),
-                    LoadKind::PanelA => update(
-                        &mut state,
-                        AppMsg::LoadedPanel {
-                            pane: PanelPane::A,
-                            outcome,
-                        },
-                    ),
-                    LoadKind::PanelB => update(
-                        &mut state,
-                        AppMsg::LoadedPanel {
-                            pane: PanelPane::B,
-                            outcome,
-                        },
-                    ),
-                    LoadKind::PanelBNestedA => update(
-                        &mut state,
-                        AppMsg::LoadedNested {
-                            subpane: PanelPane::A,
-                            outcome,
-                        },
-                    ),
-                    LoadKind::PanelBNestedB => update(
-                        &mut state,
-                        AppMsg::LoadedNested {
-                            subpane: PanelPane::B,
-                            outcome,
-                        },
-                    ),
-                    LoadKind::SubmitForm => update(
-                        &mut state,
-                        AppMsg::LoadedSubmitForm {
-                            pane: PanelPane::B,
-                            outcome,
-                        },
-                    ),
-                    LoadKind::FormOptions => {
-                        update(&mut state, AppMsg::LoadedFormOptions { key, outcome })
-                    }
-                };
+                let effects = crate::services::bus::dispatch(&mut state, msg);
                 run_effects(&mut state, effects);
             }
             // Pump streaming progress/results
-            let mut drained_pev: Vec<ProgressEvent> = Vec::new();
-            if let Some(prx) = &state.p_rx {
-                while let Ok(ev) = prx.try_recv() {
-                    drained_pev.push(ev);
-                }
-            }
-            for ev in drained_pev {
+            for ev in drain_progress(&mut state) {
                 if !ev.done {
                     progress_seen = true;
                 }
                 if ev.text.is_some() {
                     status_seen = true;
                 }
-                let effects = if ev.done {
-                    update(
-                        &mut state,
-                        AppMsg::StreamDone {
-                            result: ev.result,
-                            err: ev.err,
-                        },
-                    )
-                } else {
-                    update(
-                        &mut state,
-                        AppMsg::StreamProgress {
-                            text: ev.text,
-                            percent: ev.percent,
-                        },
-                    )
-                };
-                run_effects(&mut state, effects);
+                apply_progress(&mut state, ev);
             }
+            run_due_jobs(&mut state);
+            report_config_warnings(&mut state);
+            run_leave_hooks(&mut state);
+            flush_telemetry(&mut state, false);
             if last_tick.elapsed() >= tick_rate {
                 state.tick = state.tick.wrapping_add(1);
                 last_tick = Instant::now();
             }
             std::thread::sleep(std::cmp::min(tick_rate, Duration::from_millis(200)));
         }
+        if let Some(h) = flush_telemetry(&mut state, true) {
+            let _ = h.join();
+        }
         if headless_summary {
             let view = match state.view {
                 View::Menu => "Menu",
                 View::Welcome => "Welcome",
                 View::Json => "Json",
                 View::Panel => "Panel",
+                View::Scheduled => "Scheduled",
+                View::Workspaces => "Workspaces",
+                View::Results => "Results",
             };
             let ok = state.last_error.is_none();
             let result_present = state.last_json_pretty.is_some()
@@ -492,7 +3242,7 @@ pub fn run() -> Result<()> {
                     .as_ref()
                     .and_then(|ps| ps.b.last_json_pretty.as_ref())
                     .is_some();
-            let summary = serde_json::json!({
+            let mut summary = serde_json::json!({
                 "ok": ok,
                 "progress_seen": progress_seen,
                 "status_seen": status_seen,
@@ -500,125 +3250,269 @@ pub fn run() -> Result<()> {
                 "result_present": result_present,
                 "enter_done": headless_enter_done,
             });
+            if state.frame_budget.is_some() {
+                summary["slow_frames"] = JsonValue::from(state.slow_frames);
+            }
             println!("{summary}");
         }
         return Ok(());
     }
+    if state.config.splash && state.motion() {
+        state.splash_until_tick = SPLASH_TICKS;
+    }
     // Setup terminal (interactive)
     enable_raw_mode()?;
+    // The terminal answers the background query only in raw mode
+    if crate::theme::is_auto(state.entry_theme.as_ref()) {
+        let bg = crate::services::terminal_bg::detect();
+        apply_terminal_background(&mut state, bg);
+        crate::theme::set_active(state.theme.clone());
+    }
     let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     let tick_rate = Duration::from_millis(200);
     let mut last_tick = Instant::now();
+    // Start of the key handled in the last iteration (checked against the frame budget)
+    let mut key_started: Option<Instant> = None;
     let res = loop {
         if !state.boot_autoload_done {
             trigger_initial_autoloads(&mut state);
+            crate::services::result_cache::configure(state.config.cache.as_ref());
+            start_preflight(&mut state);
+            check_conditions(&mut state, false);
+            enter_screen(&mut state);
+            crate::widgets::notice::load_config(&mut state);
+            run_effects(&mut state, vec![Effect::CheckAuth]);
             state.boot_autoload_done = true;
         }
-        terminal.draw(|f| ui(f, &mut state))?;
+        if let Some(started) = key_started.take() {
+            state.check_budget("key", started);
+        }
+        // A widget that panicked on the last key is swapped out before drawing
+        recover_widget(&mut state);
+        publish_selection(&mut state);
+        let started = Instant::now();
+        let frame = terminal.draw(|f| ui(f, &mut state))?;
+        state.check_budget("draw", started);
+        if state.html_export_pending {
+            state.html_export_pending = false;
+            export_html(&mut state, frame.buffer);
+        }
+        if state.image_export_pending {
+            state.image_export_pending = false;
+            export_image(&mut state, frame.buffer);
+        }
+        if let Some(text) = state.editor_pending.take() {
+            open_in_editor(&mut terminal, &mut state, &text);
+        }
+        if std::mem::take(&mut state.login_pending) {
+            let login = state.config.auth.as_ref().map(|a| a.login.clone());
+            if let Some(login) = login {
+                let outcome = with_terminal_suspended(&mut terminal, || {
+                    crate::services::auth::run_interactive(&login)
+                })
+                .map_err(|e| e.to_string())
+                .and_then(|r| r)
+                .map(|_| LoadOutcome::Fallback(JsonValue::Null));
+                let effs = update(&mut state, AppMsg::AuthLoginDone { outcome });
+                run_effects(&mut state, effs);
+            }
+        }
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_millis(0));
-        if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
+        let replayed = state.pending_keys.pop_front();
+        if replayed.is_some() || event::poll(timeout)? {
+            let event = match replayed {
+                Some(key) => Event::Key(key),
+                None => event::read()?,
+            };
+            key_started = Some(Instant::now());
+            if let Some(key) = input_key(&mut state, event) {
                 // Check if a form in Pane B is in editing/confirm to gate global shortcuts
                 let mut form_editing_b = false;
                 let mut form_confirm_b = false;
+                let mut form_space_b = false;
                 if matches!(state.view, View::Panel) && matches!(state.panel_focus, PanelPane::B) {
                     if let Some(ps) = &state.panel {
                         if let PaneContent::Widget(w) = &ps.b_content {
-                            if let Some(fw) = w
-                                .as_any()
-                                .downcast_ref::<crate::widgets::form_widget::FormWidget>()
-                            {
-                                form_editing_b = fw.form.editing;
+                            if let Some(fw) = crate::widgets::wizard::current_form(w.as_any()) {
+                                // The command preview popup is modal like field editing
+                                form_editing_b =
+                                    fw.form.editing || fw.preview_open() || fw.batch_open();
                                 form_confirm_b = fw.form.confirm.is_some();
+                                form_space_b = fw.wants_space();
                             }
                         }
                     }
                 }
+                // Held Up/Down moves several rows per event
+                let steps =
+                    state
+                        .key_repeat
+                        .steps(&key, Instant::now(), state.config.keybindings.as_ref());
                 match key.code {
+                    _ if state.tick < state.splash_until_tick => state.splash_until_tick = 0,
+                    // Quick-jump hint mode swallows keys until a label is typed or it is cancelled
+                    code if state.hint_mode.is_some() => hint_mode_key(&mut state, code),
+                    // The schedule prompt and the Scheduled screen are modal
+                    code if state.schedule_prompt.is_some() => {
+                        schedule_prompt_key(&mut state, code)
+                    }
+                    code if state.command_prompt.is_some() => command_prompt_key(&mut state, code),
+                    code if state.note_prompt.is_some() => note_prompt_key(&mut state, code),
+                    code if state.watch_prompt.is_some() => watch_prompt_key(&mut state, code),
+                    code if state.palette.is_some() => palette_key(&mut state, code),
+                    code if state.view == View::Scheduled && code != KeyCode::Char('q') => {
+                        scheduled_screen_key(&mut state, code)
+                    }
+                    code if state.view == View::Workspaces && code != KeyCode::Char('q') => {
+                        workspaces_screen_key(&mut state, code)
+                    }
+                    code if state.view == View::Results && code != KeyCode::Char('q') => {
+                        results_screen_key(&mut state, code)
+                    }
+                    code if state.action_bar.is_some() => action_bar_key(&mut state, code),
+                    code if state.auth_prompt.is_some() => auth_prompt_key(&mut state, code),
+                    code if state.nav_guard.is_some() => nav_guard_key(&mut state, code),
+                    code if state.quit_confirm.is_some() => {
+                        let confirmed = quit_confirm_key(&mut state, code);
+                        if confirmed {
+                            stop_background(&mut state);
+                            break Ok(());
+                        }
+                    }
+                    code if state.verb_confirm.is_some() => verb_confirm_key(&mut state, code),
+                    _ if state.debug_focused => debug_console_key(&mut state, key),
+                    KeyCode::Char('d')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && !form_editing_b =>
+                    {
+                        toggle_debug_console(&mut state)
+                    }
+                    // A result viewer collecting a search query or a cell value (or showing
+                    // column stats) gets every key
+                    code if active_result_viewer(&mut state)
+                        .is_some_and(|w| w.search_active() || w.editing() || w.stats_open()) =>
+                    {
+                        let effects = active_result_viewer(&mut state)
+                            .map(|w| widget_key(w, code))
+                            .unwrap_or_default();
+                        run_effects(&mut state, effects);
+                    }
+                    // As does a text viewer selecting lines (Ctrl+C still copies the selection)
+                    code if !key.modifiers.contains(KeyModifiers::CONTROL)
+                        && focused_widget(&mut state).is_some_and(|w| text_selection_active(w)) =>
+                    {
+                        let effects = focused_widget(&mut state)
+                            .map(|w| widget_key(w, code))
+                            .unwrap_or_default();
+                        run_effects(&mut state, effects);
+                    }
+                    // A result viewer in tree mode moves its cursor with the arrows and Enter
+                    code if matches!(
+                        code,
+                        KeyCode::Up
+                            | KeyCode::Down
+                            | KeyCode::Left
+                            | KeyCode::Right
+                            | KeyCode::Enter
+                            | KeyCode::PageUp
+                            | KeyCode::PageDown
+                            | KeyCode::Home
+                            | KeyCode::End
+                    ) && active_result_viewer(&mut state)
+                        .is_some_and(|w| w.tree_active()) =>
+                    {
+                        let effects = active_result_viewer(&mut state)
+                            .map(|w| widget_key(w, code))
+                            .unwrap_or_default();
+                        run_effects(&mut state, effects);
+                    }
+                    // So does a log viewer typing its filter
+                    code if focused_log_viewer(&mut state).is_some_and(|w| w.filter_editing()) => {
+                        let effects = focused_log_viewer(&mut state)
+                            .map(|w| widget_key(w, code))
+                            .unwrap_or_default();
+                        run_effects(&mut state, effects);
+                    }
+                    // Esc / Ctrl+X stop a running stream before doing anything else
+                    KeyCode::Esc if state.stream_handle.is_some() && !form_editing_b => {
+                        cancel_stream(&mut state)
+                    }
+                    KeyCode::Char('x')
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && state.stream_handle.is_some() =>
+                    {
+                        cancel_stream(&mut state)
+                    }
+                    // A widget hosted in Pane A gets its keys while focused; Tab, Esc, q and
+                    // Ctrl shortcuts keep their panel meaning
+                    code if state.view == View::Panel
+                        && matches!(state.panel_focus, PanelPane::A)
+                        && !key.modifiers.contains(KeyModifiers::CONTROL)
+                        && !matches!(
+                            code,
+                            KeyCode::Tab
+                                | KeyCode::BackTab
+                                | KeyCode::Esc
+                                | KeyCode::Char('q')
+                                | KeyCode::Char(' ')
+                                | KeyCode::F(_)
+                        )
+                        && pane_a_widget(&mut state).is_some() =>
+                    {
+                        pane_a_key(&mut state, code);
+                    }
                     // Handle F1-F12 for horizontal menu
                     KeyCode::F(n) if (1..=12).contains(&n) => {
-                        let prev_index = state.horizontal_tab_index;
-                        if let Some(config_path) =
-                            crate::widgets::horizontal_menu::handle_function_key(&mut state, n)
+                        if let Some(index) = crate::widgets::horizontal_menu::tab_for_key(&state, n)
                         {
-                            state.dbg(format!("load config: {config_path}"));
-                            // Load the new config file
-                            if let Err(e) = load_config_from_path(&mut state, &config_path) {
-                                let msg = format!("Failed to load {config_path}: {e}");
-                                state.dbg(&msg);
-                                state.last_error = Some(msg);
-                            } else {
-                                state.dbg(format!("loaded config: {config_path}"));
-                                // Reset menu state for new config
-                                state.selected = 0;
-                                state.menu_offset = 0;
-                                state.expanded.clear();
-                                state.children.clear();
-                                state.view = View::Menu;
-
-                                // Trigger autoloads for the new config
-                                trigger_initial_autoloads(&mut state);
-
-                                // Auto-enter a default menu item if specified by the screen config
-                                if let Some(id) = state.config.auto_enter.clone() {
-                                    if let Some(mi) =
-                                        state.config.menu.iter().find(|m| m.id == id).cloned()
-                                    {
-                                        let effs = crate::app::update(
-                                            &mut state,
-                                            crate::app::AppMsg::EnterMenu(mi),
-                                        );
-                                        run_effects(&mut state, effs);
-                                        // UX: when auto-opened, keep focus on left/menu (Pane A)
-                                        if matches!(state.view, View::Panel) {
-                                            state.panel_focus = PanelPane::A;
-                                            state.panel_nested_focus = PanelPane::A;
-                                        }
-                                    }
-                                }
-                            }
-                        } else {
-                            // handle_function_key returned None.
-                            // Two possible cases:
-                            // 1) Same tab pressed again -> do nothing.
-                            // 2) Switched to a tab without config (Home) -> load main config.
-                            let index = (n - 1) as usize;
-                            let switched = state.horizontal_tab_index != prev_index;
-                            if switched && index < state.config.horizontal_menu.len() {
-                                let item = &state.config.horizontal_menu[index];
-                                if item.config.is_none() && state.current_config_path.is_some() {
-                                    // This is a "Home" tab - reload main config
-                                    state.dbg("load config: main (home)");
-                                    state.config = load_config().unwrap_or_default();
-                                    state.current_config_path = None;
-                                    init_logo_and_header(&mut state);
-
-                                    // Reset menu state
-                                    state.selected = 0;
-                                    state.menu_offset = 0;
-                                    state.expanded.clear();
-                                    state.children.clear();
-                                    state.view = View::Menu;
-                                    state.horizontal_tab_index = index;
-
-                                    // Trigger autoloads for the main config
-                                    trigger_initial_autoloads(&mut state);
-                                    // No auto-enter on home by default
-                                }
-                            }
+                            open_tab(&mut state, index);
                         }
                     }
+                    // Ctrl+Shift+←/→ moves the current tab (order kept for the session)
+                    KeyCode::Left | KeyCode::Right
+                        if key
+                            .modifiers
+                            .contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) =>
+                    {
+                        let delta = if key.code == KeyCode::Left { -1 } else { 1 };
+                        crate::widgets::horizontal_menu::move_tab(&mut state, delta);
+                    }
+                    // Ctrl+K (or '/' on the menu): command palette
+                    KeyCode::Char('k')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && !form_editing_b =>
+                    {
+                        state.palette = Some(crate::widgets::palette::Palette::new(&state));
+                    }
+                    KeyCode::Char('/') if left_menu_focused(&state) => {
+                        state.palette = Some(crate::widgets::palette::Palette::new(&state));
+                    }
+                    KeyCode::Char('t')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && !form_editing_b =>
+                    {
+                        cycle_theme(&mut state)
+                    }
                     KeyCode::Char('c') => {
                         // Ctrl+C copies panel content to clipboard
                         if key.modifiers.contains(KeyModifiers::CONTROL) {
-                            if state.view == View::Panel {
+                            if open_copy_picker(&mut state) {
+                                // Structured data: copied once a format is picked
+                            } else if state.view == View::Panel {
                                 if let Some(ps) = &state.panel {
                                     let content = match state.panel_focus {
+                                        PanelPane::A if ps.a_content.is_some() => ps
+                                            .a_content
+                                            .as_deref()
+                                            .map(widget_text)
+                                            .unwrap_or_default(),
                                         PanelPane::A => {
                                             // Copy Pane A content (menu items)
                                             ps.a.last_json_pretty
@@ -634,9 +3528,10 @@ pub fn run() -> Result<()> {
                                                                     .title
                                                                     .clone()
                                                             }
-                                                            FlatNode::Child { val, .. } => {
-                                                                title_from_value(val)
-                                                            }
+                                                            FlatNode::Child { .. } => node
+                                                                .val(&state)
+                                                                .map(title_from_value)
+                                                                .unwrap_or_default(),
                                                             FlatNode::Header { .. } => {
                                                                 String::new()
                                                             }
@@ -649,32 +3544,7 @@ pub fn run() -> Result<()> {
                                         PanelPane::B => {
                                             // Copy Pane B content
                                             match &ps.b_content {
-                                                PaneContent::Widget(w) => {
-                                                    // Try to get content from widget
-                                                    if let Some(md) = w.as_any().downcast_ref::<crate::widgets::markdown::MarkdownWidget>() {
-                                                        md.raw_content.clone()
-                                                    } else if let Some(jv) = w.as_any().downcast_ref::<crate::widgets::json_viewer::JsonViewerWidget>() {
-                                                        jv.text.clone()
-                                                    } else if let Some(fw) = w.as_any().downcast_ref::<crate::widgets::form_widget::FormWidget>() {
-                                                        // Copy form data as text
-                                                        fw.form.fields.iter()
-                                                            .map(|field| format!("{}: {:?}", field.name, field.value))
-                                                            .collect::<Vec<_>>()
-                                                            .join("\n")
-                                                    } else if let Some(wd) = w.as_any().downcast_ref::<crate::widgets::watchdog::WatchdogWidget>() {
-                                                        // Copy watchdog output
-                                                        wd.cmds.iter()
-                                                            .map(|cmd| {
-                                                                let output = cmd.output.lock().unwrap();
-                                                                let lines: Vec<String> = output.iter().cloned().collect();
-                                                                format!("=== {} ===\n{}", cmd.cmd, lines.join("\n"))
-                                                            })
-                                                            .collect::<Vec<_>>()
-                                                            .join("\n\n")
-                                                    } else {
-                                                        String::new()
-                                                    }
-                                                }
+                                                PaneContent::Widget(w) => widget_text(w.as_ref()),
                                                 PaneContent::Panel(_) => {
                                                     // Nested panel - copy from last JSON
                                                     ps.b.last_json_pretty
@@ -725,7 +3595,7 @@ pub fn run() -> Result<()> {
                             {
                                 if let Some(ps) = &mut state.panel {
                                     if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                        let effs = w.on_key(KeyCode::Char('c'));
+                                        let effs = widget_key(w.as_mut(), KeyCode::Char('c'));
                                         run_effects(&mut state, effs);
                                     }
                                 }
@@ -740,10 +3610,9 @@ pub fn run() -> Result<()> {
                         {
                             if let Some(ps) = &mut state.panel {
                                 if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    if let Some(fw) = w
-                                        .as_any_mut()
-                                        .downcast_mut::<crate::widgets::form_widget::FormWidget>(
-                                    ) {
+                                    if let Some(fw) =
+                                        crate::widgets::wizard::current_form_mut(w.as_any_mut())
+                                    {
                                         let _ = fw.commit_textarea();
                                     }
                                 }
@@ -755,7 +3624,7 @@ pub fn run() -> Result<()> {
                             {
                                 if let Some(ps) = &mut state.panel {
                                     if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                        let effs = w.on_key(KeyCode::Char('s'));
+                                        let effs = widget_key(w.as_mut(), KeyCode::Char('s'));
                                         run_effects(&mut state, effs);
                                     }
                                 }
@@ -770,83 +3639,82 @@ pub fn run() -> Result<()> {
                             {
                                 if let Some(ps) = &mut state.panel {
                                     if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                        let effs = w.on_key(KeyCode::Char('q'));
+                                        let effs = widget_key(w.as_mut(), KeyCode::Char('q'));
                                         run_effects(&mut state, effs);
                                     }
                                 }
                             }
-                        } else {
+                        } else if request_quit(&mut state) {
+                            stop_background(&mut state);
                             break Ok(());
                         }
                     }
                     KeyCode::Up => {
-                        if state.view == View::Json {
-                            if state.json_scroll_y > 0 {
-                                state.json_scroll_y -= 1;
-                            }
-                        } else if state.view == View::Panel
-                            && matches!(state.panel_focus, PanelPane::B)
-                        {
-                            if let Some(ps) = &mut state.panel {
-                                match ps.b_content {
-                                    PaneContent::Widget(ref mut w) => {
-                                        let effs = w.on_key(KeyCode::Up);
-                                        run_effects(&mut state, effs);
+                        for _ in 0..steps {
+                            if state.view == View::Json {
+                                json_view_key(&mut state, KeyCode::Up);
+                            } else if state.view == View::Panel
+                                && matches!(state.panel_focus, PanelPane::B)
+                            {
+                                if let Some(ps) = &mut state.panel {
+                                    match ps.b_content {
+                                        PaneContent::Widget(ref mut w) => {
+                                            let effs = widget_key(w.as_mut(), KeyCode::Up);
+                                            run_effects(&mut state, effs);
+                                        }
+                                        PaneContent::Panel(_) => {}
+                                        _ => {}
                                     }
-                                    PaneContent::Panel(_) => {}
-                                    _ => {}
                                 }
-                            }
-                        } else {
-                            let total_sel = flatten_nodes(&state).len();
-                            if total_sel > 0 && state.selected > 0 {
-                                state.selected -= 1;
-                                // adjust persistent offset to keep selected in view
-                                let ih = state.menu_viewport_h as usize;
-                                if state.selected < state.menu_offset {
-                                    state.menu_offset = state.selected;
-                                } else if ih > 0 && state.selected >= state.menu_offset + ih {
-                                    state.menu_offset =
-                                        state.selected.saturating_sub(ih.saturating_sub(1));
+                            } else {
+                                let total_sel = flatten_nodes(&state).len();
+                                if total_sel > 0 && state.selected > 0 {
+                                    state.selected -= 1;
+                                    // adjust persistent offset to keep selected in view
+                                    let ih = state.menu_viewport_h as usize;
+                                    if state.selected < state.menu_offset {
+                                        state.menu_offset = state.selected;
+                                    } else if ih > 0 && state.selected >= state.menu_offset + ih {
+                                        state.menu_offset =
+                                            state.selected.saturating_sub(ih.saturating_sub(1));
+                                    }
                                 }
                             }
                         }
                     }
                     KeyCode::Down => {
-                        if state.view == View::Json {
-                            if let Some(w) = &mut state.json_viewer {
-                                let _ = w.on_key(KeyCode::Down);
-                            }
-                        } else if state.view == View::Panel
-                            && matches!(state.panel_focus, PanelPane::B)
-                        {
-                            if let Some(ps) = &mut state.panel {
-                                match ps.b_content {
-                                    PaneContent::Widget(ref mut w) => {
-                                        let effs = w.on_key(KeyCode::Down);
-                                        run_effects(&mut state, effs);
+                        for _ in 0..steps {
+                            if state.view == View::Json {
+                                json_view_key(&mut state, KeyCode::Down);
+                            } else if state.view == View::Panel
+                                && matches!(state.panel_focus, PanelPane::B)
+                            {
+                                if let Some(ps) = &mut state.panel {
+                                    match ps.b_content {
+                                        PaneContent::Widget(ref mut w) => {
+                                            let effs = widget_key(w.as_mut(), KeyCode::Down);
+                                            run_effects(&mut state, effs);
+                                        }
+                                        PaneContent::Panel(_) => {}
+                                        _ => {}
                                     }
-                                    PaneContent::Panel(_) => {}
-                                    _ => {}
                                 }
-                            }
-                        } else {
-                            let total_sel = flatten_nodes(&state).len();
-                            if total_sel > 0 && state.selected + 1 < total_sel {
-                                state.selected += 1;
-                                let ih = state.menu_viewport_h as usize;
-                                if ih > 0 && state.selected >= state.menu_offset + ih {
-                                    state.menu_offset =
-                                        state.selected.saturating_sub(ih.saturating_sub(1));
+                            } else {
+                                let total_sel = flatten_nodes(&state).len();
+                                if total_sel > 0 && state.selected + 1 < total_sel {
+                                    state.selected += 1;
+                                    let ih = state.menu_viewport_h as usize;
+                                    if ih > 0 && state.selected >= state.menu_offset + ih {
+                                        state.menu_offset =
+                                            state.selected.saturating_sub(ih.saturating_sub(1));
+                                    }
                                 }
                             }
                         }
                     }
                     KeyCode::PageUp => {
                         if state.view == View::Json {
-                            if let Some(w) = &mut state.json_viewer {
-                                let _ = w.on_key(KeyCode::PageUp);
-                            }
+                            json_view_key(&mut state, KeyCode::PageUp);
                         } else if state.view == View::Panel
                             && matches!(state.panel_focus, PanelPane::B)
                         {
@@ -854,7 +3722,7 @@ pub fn run() -> Result<()> {
                                 if let Some(ps) = &mut state.panel {
                                     match ps.b_content {
                                         PaneContent::Widget(ref mut w) => {
-                                            let effs = w.on_key(KeyCode::PageUp);
+                                            let effs = widget_key(w.as_mut(), KeyCode::PageUp);
                                             run_effects(&mut state, effs);
                                         }
                                         PaneContent::Panel(_) => {}
@@ -876,9 +3744,7 @@ pub fn run() -> Result<()> {
                     }
                     KeyCode::PageDown => {
                         if state.view == View::Json {
-                            if let Some(w) = &mut state.json_viewer {
-                                let _ = w.on_key(KeyCode::PageDown);
-                            }
+                            json_view_key(&mut state, KeyCode::PageDown);
                         } else if state.view == View::Panel
                             && matches!(state.panel_focus, PanelPane::B)
                         {
@@ -886,7 +3752,7 @@ pub fn run() -> Result<()> {
                                 if let Some(ps) = &mut state.panel {
                                     match ps.b_content {
                                         PaneContent::Widget(ref mut w) => {
-                                            let effs = w.on_key(KeyCode::PageDown);
+                                            let effs = widget_key(w.as_mut(), KeyCode::PageDown);
                                             run_effects(&mut state, effs);
                                         }
                                         PaneContent::Panel(_) => {}
@@ -910,50 +3776,50 @@ pub fn run() -> Result<()> {
                     }
                     KeyCode::Home => {
                         if state.view == View::Json {
-                            if let Some(w) = &mut state.json_viewer {
-                                let _ = w.on_key(KeyCode::Home);
-                            }
+                            json_view_key(&mut state, KeyCode::Home);
                         } else if state.view == View::Panel
                             && matches!(state.panel_focus, PanelPane::B)
-                            && !form_editing_b
                         {
                             if let Some(ps) = &mut state.panel {
                                 if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    let effs = w.on_key(KeyCode::Home);
+                                    let effs = widget_key(w.as_mut(), KeyCode::Home);
                                     run_effects(&mut state, effs);
                                 }
                             }
+                        } else if left_menu_focused(&state) {
+                            state.selected = 0;
+                            state.menu_offset = 0;
                         }
                     }
                     KeyCode::End => {
                         if state.view == View::Json {
-                            if let Some(w) = &mut state.json_viewer {
-                                let _ = w.on_key(KeyCode::End);
-                            }
+                            json_view_key(&mut state, KeyCode::End);
                         } else if state.view == View::Panel
                             && matches!(state.panel_focus, PanelPane::B)
-                            && !form_editing_b
                         {
                             if let Some(ps) = &mut state.panel {
                                 if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    let effs = w.on_key(KeyCode::End);
+                                    let effs = widget_key(w.as_mut(), KeyCode::End);
                                     run_effects(&mut state, effs);
                                 }
                             }
+                        } else if left_menu_focused(&state) {
+                            let total = flatten_nodes(&state).len();
+                            state.selected = total.saturating_sub(1);
+                            state.menu_offset =
+                                total.saturating_sub(state.menu_viewport_h as usize);
                         }
                     }
                     KeyCode::Char('w') => {
                         if state.view == View::Json {
-                            if let Some(w) = &mut state.json_viewer {
-                                let _ = w.on_key(KeyCode::Char('w'));
-                            }
+                            json_view_key(&mut state, KeyCode::Char('w'));
                         } else if state.view == View::Panel
                             && matches!(state.panel_focus, PanelPane::B)
                         {
                             // Always pass to widget so textareas can type 'w'
                             if let Some(ps) = &mut state.panel {
                                 if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    let effs = w.on_key(KeyCode::Char('w'));
+                                    let effs = widget_key(w.as_mut(), KeyCode::Char('w'));
                                     run_effects(&mut state, effs);
                                 }
                             }
@@ -961,193 +3827,201 @@ pub fn run() -> Result<()> {
                     }
                     KeyCode::Char('j') => {
                         if state.view == View::Json {
-                            if let Some(w) = &mut state.json_viewer {
-                                let _ = w.on_key(KeyCode::Char('j'));
-                            }
+                            json_view_key(&mut state, KeyCode::Char('j'));
                         } else if state.view == View::Panel
                             && matches!(state.panel_focus, PanelPane::B)
                         {
                             if let Some(ps) = &mut state.panel {
                                 if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    let effs = w.on_key(KeyCode::Char('j'));
+                                    let effs = widget_key(w.as_mut(), KeyCode::Char('j'));
                                     run_effects(&mut state, effs);
                                 }
                             }
                         }
                     }
-                    KeyCode::Tab => {
-                        if state.view == View::Panel && !form_editing_b {
-                            if matches!(state.panel_focus, PanelPane::A) {
-                                // A -> if nested panel exists in B, go to B.A; else go to B
-                                if let Some(ps) = &mut state.panel {
-                                    let has_nested_panel =
-                                        matches!(ps.b_content, PaneContent::Panel(_));
-                                    let is_panel_widget = if let PaneContent::Widget(ref mut w) =
-                                        ps.b_content
-                                    {
+                    // A watchdog in Pane A: Tab walks its sections before moving on to Pane B
+                    KeyCode::Tab
+                        if pane_a_watchdog(&mut state)
+                            .is_some_and(|wd| wd.focused_pane() + 1 < wd.pane_count()) =>
+                    {
+                        if let Some(wd) = pane_a_watchdog(&mut state) {
+                            wd.set_focused_pane(wd.focused_pane() + 1);
+                        }
+                    }
+                    KeyCode::BackTab
+                        if pane_a_watchdog(&mut state).is_some_and(|wd| wd.focused_pane() > 0) =>
+                    {
+                        if let Some(wd) = pane_a_watchdog(&mut state) {
+                            wd.set_focused_pane(wd.focused_pane() - 1);
+                        }
+                    }
+                    KeyCode::Tab if state.view == View::Panel && !form_editing_b => {
+                        if matches!(state.panel_focus, PanelPane::A) {
+                            // A -> if nested panel exists in B, go to B.A; else go to B
+                            if let Some(ps) = &mut state.panel {
+                                let has_nested_panel =
+                                    matches!(ps.b_content, PaneContent::Panel(_));
+                                let is_panel_widget =
+                                    if let PaneContent::Widget(ref mut w) = ps.b_content {
                                         w.as_any()
                                             .downcast_ref::<crate::widgets::panel::PanelWidget>()
                                             .is_some()
                                     } else {
                                         false
                                     };
-                                    let is_watchdog_widget = if let PaneContent::Widget(ref mut w) =
-                                        ps.b_content
-                                    {
-                                        w.as_any().downcast_ref::<crate::widgets::watchdog::WatchdogWidget>()
-                                            .is_some()
+                                let is_watchdog_widget =
+                                    if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                        w.as_any()
+                                        .downcast_ref::<crate::widgets::watchdog::WatchdogWidget>()
+                                        .is_some()
                                     } else {
                                         false
                                     };
-                                    state.panel_focus = PanelPane::B;
-                                    if has_nested_panel {
-                                        state.panel_nested_focus = PanelPane::A;
-                                    } else if is_panel_widget {
-                                        if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                            if let Some(pw) = w
-                                                .as_any_mut()
-                                                .downcast_mut::<crate::widgets::panel::PanelWidget>(
-                                            ) {
-                                                pw.set_nested_focus(PanelPane::A);
-                                            }
+                                state.panel_focus = PanelPane::B;
+                                if has_nested_panel {
+                                    state.panel_nested_focus = PanelPane::A;
+                                } else if is_panel_widget {
+                                    if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                        if let Some(pw) = w
+                                            .as_any_mut()
+                                            .downcast_mut::<crate::widgets::panel::PanelWidget>(
+                                        ) {
+                                            pw.set_nested_focus(PanelPane::A);
                                         }
-                                    } else if is_watchdog_widget {
-                                        if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                            if let Some(wd) = w
-                                                .as_any_mut()
-                                                .downcast_mut::<crate::widgets::watchdog::WatchdogWidget>()
-                                            {
-                                                wd.set_focused_pane(0);
-                                            }
+                                    }
+                                } else if is_watchdog_widget {
+                                    if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                        if let Some(wd) = w
+                                            .as_any_mut()
+                                            .downcast_mut::<crate::widgets::watchdog::WatchdogWidget>()
+                                        {
+                                            wd.set_focused_pane(0);
                                         }
                                     }
                                 }
-                            } else if let Some(ps) = &mut state.panel {
-                                // Currently focused on B; cycle B.A -> B.B -> A
-                                if let PaneContent::Panel(_) = ps.b_content {
-                                    if matches!(state.panel_nested_focus, PanelPane::A) {
-                                        state.panel_nested_focus = PanelPane::B;
+                            }
+                        } else if let Some(ps) = &mut state.panel {
+                            // Currently focused on B; cycle B.A -> B.B -> A
+                            if let PaneContent::Panel(_) = ps.b_content {
+                                if matches!(state.panel_nested_focus, PanelPane::A) {
+                                    state.panel_nested_focus = PanelPane::B;
+                                } else {
+                                    state.panel_focus = PanelPane::A;
+                                }
+                            } else if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                if let Some(pw) = w
+                                    .as_any_mut()
+                                    .downcast_mut::<crate::widgets::panel::PanelWidget>()
+                                {
+                                    if matches!(pw.nested_focus(), PanelPane::A) {
+                                        pw.set_nested_focus(PanelPane::B);
                                     } else {
                                         state.panel_focus = PanelPane::A;
                                     }
-                                } else if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    if let Some(pw) = w
-                                        .as_any_mut()
-                                        .downcast_mut::<crate::widgets::panel::PanelWidget>()
-                                    {
-                                        if matches!(pw.nested_focus(), PanelPane::A) {
-                                            pw.set_nested_focus(PanelPane::B);
-                                        } else {
-                                            state.panel_focus = PanelPane::A;
-                                        }
-                                    } else if let Some(wd) = w
-                                        .as_any_mut()
+                                } else if let Some(wd) =
+                                    w.as_any_mut()
                                         .downcast_mut::<crate::widgets::watchdog::WatchdogWidget>()
-                                    {
-                                        let n = wd.pane_count();
-                                        if n > 1 {
-                                            let cur = wd.focused_pane();
-                                            if cur + 1 < n {
-                                                wd.set_focused_pane(cur + 1);
-                                            } else {
-                                                state.panel_focus = PanelPane::A;
-                                            }
+                                {
+                                    let n = wd.pane_count();
+                                    if n > 1 {
+                                        let cur = wd.focused_pane();
+                                        if cur + 1 < n {
+                                            wd.set_focused_pane(cur + 1);
                                         } else {
                                             state.panel_focus = PanelPane::A;
                                         }
                                     } else {
-                                        // No nested: B -> A
                                         state.panel_focus = PanelPane::A;
                                     }
                                 } else {
-                                    // Not a widget or nested panel: B -> A
+                                    // No nested: B -> A
                                     state.panel_focus = PanelPane::A;
                                 }
+                            } else {
+                                // Not a widget or nested panel: B -> A
+                                state.panel_focus = PanelPane::A;
                             }
                         }
                     }
-                    KeyCode::BackTab => {
-                        if state.view == View::Panel && !form_editing_b {
-                            if matches!(state.panel_focus, PanelPane::A) {
-                                // Reverse from A: prefer B.B (or last watchdog pane) if nested exists
-                                if let Some(ps) = &mut state.panel {
-                                    let has_nested_panel =
-                                        matches!(ps.b_content, PaneContent::Panel(_));
-                                    let is_panel_widget = if let PaneContent::Widget(ref mut w) =
-                                        ps.b_content
-                                    {
+                    KeyCode::BackTab if state.view == View::Panel && !form_editing_b => {
+                        if matches!(state.panel_focus, PanelPane::A) {
+                            // Reverse from A: prefer B.B (or last watchdog pane) if nested exists
+                            if let Some(ps) = &mut state.panel {
+                                let has_nested_panel =
+                                    matches!(ps.b_content, PaneContent::Panel(_));
+                                let is_panel_widget =
+                                    if let PaneContent::Widget(ref mut w) = ps.b_content {
                                         w.as_any()
                                             .downcast_ref::<crate::widgets::panel::PanelWidget>()
                                             .is_some()
                                     } else {
                                         false
                                     };
-                                    let is_watchdog_widget = if let PaneContent::Widget(ref mut w) =
-                                        ps.b_content
-                                    {
-                                        w.as_any().downcast_ref::<crate::widgets::watchdog::WatchdogWidget>()
-                                            .is_some()
+                                let is_watchdog_widget =
+                                    if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                        w.as_any()
+                                        .downcast_ref::<crate::widgets::watchdog::WatchdogWidget>()
+                                        .is_some()
                                     } else {
                                         false
                                     };
-                                    state.panel_focus = PanelPane::B;
-                                    if has_nested_panel {
-                                        state.panel_nested_focus = PanelPane::B;
-                                    } else if is_panel_widget {
-                                        if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                            if let Some(pw) = w
-                                                .as_any_mut()
-                                                .downcast_mut::<crate::widgets::panel::PanelWidget>(
-                                            ) {
-                                                pw.set_nested_focus(PanelPane::B);
-                                            }
+                                state.panel_focus = PanelPane::B;
+                                if has_nested_panel {
+                                    state.panel_nested_focus = PanelPane::B;
+                                } else if is_panel_widget {
+                                    if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                        if let Some(pw) = w
+                                            .as_any_mut()
+                                            .downcast_mut::<crate::widgets::panel::PanelWidget>(
+                                        ) {
+                                            pw.set_nested_focus(PanelPane::B);
                                         }
-                                    } else if is_watchdog_widget {
-                                        if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                            if let Some(wd) = w
-                                                .as_any_mut()
-                                                .downcast_mut::<crate::widgets::watchdog::WatchdogWidget>()
-                                            {
-                                                let n = wd.pane_count();
-                                                if n > 0 { wd.set_focused_pane(n - 1); }
-                                            }
+                                    }
+                                } else if is_watchdog_widget {
+                                    if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                        if let Some(wd) = w
+                                            .as_any_mut()
+                                            .downcast_mut::<crate::widgets::watchdog::WatchdogWidget>()
+                                        {
+                                            let n = wd.pane_count();
+                                            if n > 0 { wd.set_focused_pane(n - 1); }
                                         }
                                     }
                                 }
-                            } else if let Some(ps) = &mut state.panel {
-                                // Reverse within B: for nested panel, B.B -> B.A -> A; for watchdog, last -> ... -> 0 -> A
-                                if let PaneContent::Panel(_) = ps.b_content {
-                                    if matches!(state.panel_nested_focus, PanelPane::B) {
-                                        state.panel_nested_focus = PanelPane::A;
+                            }
+                        } else if let Some(ps) = &mut state.panel {
+                            // Reverse within B: for nested panel, B.B -> B.A -> A; for watchdog, last -> ... -> 0 -> A
+                            if let PaneContent::Panel(_) = ps.b_content {
+                                if matches!(state.panel_nested_focus, PanelPane::B) {
+                                    state.panel_nested_focus = PanelPane::A;
+                                } else {
+                                    state.panel_focus = PanelPane::A;
+                                }
+                            } else if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                if let Some(pw) = w
+                                    .as_any_mut()
+                                    .downcast_mut::<crate::widgets::panel::PanelWidget>()
+                                {
+                                    if matches!(pw.nested_focus(), PanelPane::B) {
+                                        pw.set_nested_focus(PanelPane::A);
                                     } else {
                                         state.panel_focus = PanelPane::A;
                                     }
-                                } else if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    if let Some(pw) = w
-                                        .as_any_mut()
-                                        .downcast_mut::<crate::widgets::panel::PanelWidget>()
-                                    {
-                                        if matches!(pw.nested_focus(), PanelPane::B) {
-                                            pw.set_nested_focus(PanelPane::A);
-                                        } else {
-                                            state.panel_focus = PanelPane::A;
-                                        }
-                                    } else if let Some(wd) = w
-                                        .as_any_mut()
+                                } else if let Some(wd) =
+                                    w.as_any_mut()
                                         .downcast_mut::<crate::widgets::watchdog::WatchdogWidget>()
-                                    {
-                                        let cur = wd.focused_pane();
-                                        if cur > 0 {
-                                            wd.set_focused_pane(cur - 1);
-                                        } else {
-                                            state.panel_focus = PanelPane::A;
-                                        }
+                                {
+                                    let cur = wd.focused_pane();
+                                    if cur > 0 {
+                                        wd.set_focused_pane(cur - 1);
                                     } else {
                                         state.panel_focus = PanelPane::A;
                                     }
                                 } else {
                                     state.panel_focus = PanelPane::A;
                                 }
+                            } else {
+                                state.panel_focus = PanelPane::A;
                             }
                         }
                     }
@@ -1163,7 +4037,7 @@ pub fn run() -> Result<()> {
                                         .as_any_mut()
                                         .downcast_mut::<crate::widgets::form_widget::FormWidget>(
                                     ) {
-                                        let effs = w.on_key(KeyCode::Enter);
+                                        let effs = widget_key(w.as_mut(), KeyCode::Enter);
                                         run_effects(&mut state, effs);
                                     } else if let Some(mw) =
                                         w.as_any()
@@ -1174,7 +4048,7 @@ pub fn run() -> Result<()> {
                                         }
                                     } else {
                                         // generic enter to widget
-                                        let effs = w.on_key(KeyCode::Enter);
+                                        let effs = widget_key(w.as_mut(), KeyCode::Enter);
                                         run_effects(&mut state, effs);
                                     }
                                 }
@@ -1196,32 +4070,46 @@ pub fn run() -> Result<()> {
                                             effects = update(&mut state, AppMsg::EnterMenu(mi));
                                         }
                                     }
-                                    FlatNode::Child { key, val, .. } => {
-                                        effects =
-                                            update(&mut state, AppMsg::EnterChild { key, val });
+                                    FlatNode::Child { ref key, .. } => {
+                                        if let Some(val) = node.val(&state).cloned() {
+                                            let key = key.clone();
+                                            effects =
+                                                update(&mut state, AppMsg::EnterChild { key, val });
+                                        }
                                     }
                                 }
                                 run_effects(&mut state, effects);
                             }
                         }
                     }
-                    KeyCode::Left => {
-                        if state.view == View::Panel && matches!(state.panel_focus, PanelPane::B) {
-                            if let Some(ps) = &mut state.panel {
-                                if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    let effs = w.on_key(KeyCode::Left);
-                                    run_effects(&mut state, effs);
-                                }
+                    KeyCode::Left
+                        if state.view == View::Panel
+                            && matches!(state.panel_focus, PanelPane::B) =>
+                    {
+                        if let Some(ps) = &mut state.panel {
+                            if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                let effs = widget_key(w.as_mut(), KeyCode::Left);
+                                run_effects(&mut state, effs);
                             }
                         }
                     }
-                    KeyCode::Right => {
-                        if state.view == View::Panel && matches!(state.panel_focus, PanelPane::B) {
-                            if let Some(ps) = &mut state.panel {
-                                if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    let effs = w.on_key(KeyCode::Right);
-                                    run_effects(&mut state, effs);
-                                }
+                    // Deletes at the caret of the form field being edited
+                    KeyCode::Delete if form_editing_b => {
+                        if let Some(ps) = &mut state.panel {
+                            if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                let effs = widget_key(w.as_mut(), KeyCode::Delete);
+                                run_effects(&mut state, effs);
+                            }
+                        }
+                    }
+                    KeyCode::Right
+                        if state.view == View::Panel
+                            && matches!(state.panel_focus, PanelPane::B) =>
+                    {
+                        if let Some(ps) = &mut state.panel {
+                            if let PaneContent::Widget(ref mut w) = ps.b_content {
+                                let effs = widget_key(w.as_mut(), KeyCode::Right);
+                                run_effects(&mut state, effs);
                             }
                         }
                     }
@@ -1229,12 +4117,15 @@ pub fn run() -> Result<()> {
                         if state.view == View::Panel && matches!(state.panel_focus, PanelPane::B) {
                             if let Some(ps) = &mut state.panel {
                                 if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    let effs = w.on_key(KeyCode::Backspace);
+                                    let effs = widget_key(w.as_mut(), KeyCode::Backspace);
                                     run_effects(&mut state, effs);
                                 }
                             }
                             // If not editing/confirming a form, treat Backspace as "Back"
-                            if !form_editing_b && !form_confirm_b {
+                            if !form_editing_b
+                                && !form_confirm_b
+                                && !guard_nav(&mut state, NavTarget::Back)
+                            {
                                 let _ = pane_b_back(&mut state);
                             }
                         } else if matches!(state.view, View::Json) {
@@ -1243,12 +4134,16 @@ pub fn run() -> Result<()> {
                         }
                     }
                     KeyCode::Char('r') => {
-                        // Always pass to widget first so textareas can type 'r'.
+                        // Always pass to widget first so textareas can type 'r'; then let
+                        // the focused widget reload its own content
                         let mut handled_by_widget = false;
                         if state.view == View::Panel && matches!(state.panel_focus, PanelPane::B) {
                             if let Some(ps) = &mut state.panel {
                                 if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    let effs = w.on_key(KeyCode::Char('r'));
+                                    let mut effs = widget_key(w.as_mut(), KeyCode::Char('r'));
+                                    if effs.is_empty() && !form_editing_b {
+                                        effs = w.refresh();
+                                    }
                                     handled_by_widget = !effs.is_empty();
                                     run_effects(&mut state, effs);
                                 }
@@ -1265,9 +4160,14 @@ pub fn run() -> Result<()> {
                                             effects = update(&mut state, AppMsg::RefreshMenu(mi));
                                         }
                                     }
-                                    FlatNode::Child { key, val, .. } => {
-                                        effects =
-                                            update(&mut state, AppMsg::RefreshChild { key, val });
+                                    FlatNode::Child { ref key, .. } => {
+                                        if let Some(val) = node.val(&state).cloned() {
+                                            let key = key.clone();
+                                            effects = update(
+                                                &mut state,
+                                                AppMsg::RefreshChild { key, val },
+                                            );
+                                        }
                                     }
                                     FlatNode::Header { .. } => {}
                                 }
@@ -1280,10 +4180,10 @@ pub fn run() -> Result<()> {
                         let consumed = form_editing_b || form_confirm_b;
                         if let Some(ps) = &mut state.panel {
                             if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                let _ = w.on_key(KeyCode::Esc);
+                                let _ = widget_key(w.as_mut(), KeyCode::Esc);
                             }
                         }
-                        if !consumed {
+                        if !consumed && !guard_nav(&mut state, NavTarget::Close) {
                             // Fallback: leave Panel to Menu (unless screen locks layout)
                             if state.config.can_close {
                                 state.view = View::Menu;
@@ -1292,15 +4192,109 @@ pub fn run() -> Result<()> {
                             }
                         }
                     }
+                    KeyCode::Char('f')
+                        if !form_editing_b
+                            && !key
+                                .modifiers
+                                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                            && matches!(state.view, View::Menu | View::Panel) =>
+                    {
+                        start_hint_mode(&mut state);
+                    }
+                    KeyCode::Char('e')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && !form_editing_b =>
+                    {
+                        // Exported right after the next draw, from the rendered frame
+                        state.html_export_pending = true;
+                    }
+                    KeyCode::Char('p')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && !form_editing_b =>
+                    {
+                        state.image_export_pending = true;
+                    }
+                    KeyCode::Char(' ')
+                        if !form_editing_b
+                            && !form_space_b
+                            && focused_widget(&mut state).is_some() =>
+                    {
+                        open_action_bar(&mut state)
+                    }
+                    // Scheduling works on the left menu, so Pane B widgets keep their keys
+                    KeyCode::Char('t') if left_menu_focused(&state) => {
+                        start_schedule_prompt(&mut state)
+                    }
+                    KeyCode::Char(':') if left_menu_focused(&state) => {
+                        start_command_prompt(&mut state)
+                    }
+                    KeyCode::Char('n') if left_menu_focused(&state) => {
+                        start_note_prompt(&mut state)
+                    }
+                    KeyCode::Char('=') if !form_editing_b && watch_source(&mut state).is_some() => {
+                        start_watch_prompt(&mut state)
+                    }
+                    KeyCode::Char('T') if left_menu_focused(&state) => {
+                        state.screen_return = state.view;
+                        state.scheduled_selected = 0;
+                        state.view = View::Scheduled;
+                    }
+                    KeyCode::Char('H') if left_menu_focused(&state) => open_results(&mut state),
+                    KeyCode::Char('O') if left_menu_focused(&state) => toggle_offline(&mut state),
+                    KeyCode::Char('W')
+                        if left_menu_focused(&state) && state.workspaces.len() > 1 =>
+                    {
+                        state.screen_return = state.view;
+                        state.workspace_selected = state.workspace_index;
+                        state.view = View::Workspaces;
+                    }
+                    // Previous / next page of the selected paginated list
+                    KeyCode::Char(c @ ('[' | ']')) if left_menu_focused(&state) => {
+                        if let Some(key) = paged_list(&state) {
+                            let forward = c == ']';
+                            let effects = update(&mut state, AppMsg::TurnPage { key, forward });
+                            run_effects(&mut state, effects);
+                        }
+                    }
+                    // `verbs:` of the selected item (or of the item that loaded the child);
+                    // plain keys only, so Ctrl/Alt shortcuts never fire a verb
+                    KeyCode::Char(c)
+                        if !key
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                            && left_menu_focused(&state)
+                            && selected_verb(&state, c).is_some() =>
+                    {
+                        if let Some(mi) = selected_verb(&state, c) {
+                            if verb_confirms(&state, c) {
+                                state.verb_confirm = Some(mi);
+                            } else {
+                                let effects = update(&mut state, AppMsg::EnterMenu(mi));
+                                run_effects(&mut state, effects);
+                            }
+                        }
+                    }
+                    // `hotkey:` of an item on this screen
+                    KeyCode::Char(c)
+                        if !form_editing_b
+                            && !key
+                                .modifiers
+                                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                            && hotkeys_active(&mut state)
+                            && crate::nav::hotkeys::find(&state.config.menu, c).is_some() =>
+                    {
+                        if let Some(idx) = crate::nav::hotkeys::find(&state.config.menu, c) {
+                            let id = state.config.menu[idx].id.clone();
+                            run_effects(&mut state, vec![Effect::OpenItem(id)]);
+                        }
+                    }
                     KeyCode::Char(c) => {
                         // Form input/editing + submit shortcut
                         if state.view == View::Panel && matches!(state.panel_focus, PanelPane::B) {
-                            let mut submit_cmd_from_char: Option<String> = None;
+                            let mut submit_effects: Vec<Effect> = Vec::new();
                             // 1) Let widget process the character
                             let mut effs_from_widget: Vec<Effect> = Vec::new();
                             if let Some(ps) = &mut state.panel {
                                 if let PaneContent::Widget(ref mut w) = ps.b_content {
-                                    effs_from_widget = w.on_key(KeyCode::Char(c));
+                                    effs_from_widget = widget_key(w.as_mut(), KeyCode::Char(c));
                                 }
                             }
                             run_effects(&mut state, effs_from_widget);
@@ -1309,26 +4303,17 @@ pub fn run() -> Result<()> {
                                 if let Some(ps) = &mut state.panel {
                                     if let PaneContent::Widget(ref mut w) = ps.b_content {
                                         if let Some(fw) = w.as_any_mut().downcast_mut::<crate::widgets::form_widget::FormWidget>() {
-                                            let form = &mut fw.form;
-                    if !form.editing
-                        && !form.disabled
-                        && crate::widgets::form::validate_form(form)
-                    {
-                        if let Some(cmdline) = crate::widgets::form::build_cmdline(form) {
-                            submit_cmd_from_char = Some(cmdline);
-                        }
-                    }
+                                            if !fw.form.editing && !fw.form.disabled && !fw.preview_open() && !fw.batch_open() {
+                                                submit_effects = fw.submit_effects();
+                                            }
                                         }
                                     }
                                 }
                             }
-                            if let Some(cmdline) = submit_cmd_from_char {
-                                let effects = vec![Effect::SubmitForm {
-                                    pane: PanelPane::B,
-                                    cmdline,
-                                }];
-                                run_effects(&mut state, effects);
-                            }
+                            run_effects(&mut state, submit_effects);
+                        } else if state.view == View::Json {
+                            // Paging/search/toggles of the global result viewer
+                            json_view_key(&mut state, KeyCode::Char(c));
                         } else {
                             // Quick numeric jump in left menu: match titles containing "[[n]]"
                             if c.is_ascii_digit() {
@@ -1371,95 +4356,17 @@ pub fn run() -> Result<()> {
             }
         }
         for msg in drained_msgs {
-            state.loading.remove(&msg.key);
-            let key = msg.key;
-            let outcome = msg.outcome;
-            let effects = match msg.kind {
-                LoadKind::Menu => update(&mut state, AppMsg::LoadedMenu { key, outcome }),
-                LoadKind::Child => update(&mut state, AppMsg::LoadedChild { key, outcome }),
-                LoadKind::PanelA => update(
-                    &mut state,
-                    AppMsg::LoadedPanel {
-                        pane: PanelPane::A,
-                        outcome,
-                    },
-                ),
-                LoadKind::PanelB => update(
-                    &mut state,
-                    AppMsg::LoadedPanel {
-                        pane: PanelPane::B,
-                        outcome,
-                    },
-                ),
-                LoadKind::PanelBNestedA => update(
-                    &mut state,
-                    AppMsg::LoadedNested {
-                        subpane: PanelPane::A,
-                        outcome,
-                    },
-                ),
-                LoadKind::PanelBNestedB => update(
-                    &mut state,
-                    AppMsg::LoadedNested {
-                        subpane: PanelPane::B,
-                        outcome,
-                    },
-                ),
-                LoadKind::SubmitForm => update(
-                    &mut state,
-                    AppMsg::LoadedSubmitForm {
-                        pane: PanelPane::B,
-                        outcome,
-                    },
-                ),
-                LoadKind::FormOptions => {
-                    update(&mut state, AppMsg::LoadedFormOptions { key, outcome })
-                }
-            };
+            let effects = crate::services::bus::dispatch(&mut state, msg);
             run_effects(&mut state, effects);
-            if matches!(msg.kind, LoadKind::SubmitForm) {
-                state.submitting = false;
-                state.status_text = None;
-                state.status_percent = None;
-                if let Some(ps) = &mut state.panel {
-                    if let PaneContent::Widget(ref mut w) = ps.b_content {
-                        if let Some(fw) = w
-                            .as_any_mut()
-                            .downcast_mut::<crate::widgets::form_widget::FormWidget>()
-                        {
-                            fw.form.disabled = false;
-                        }
-                    }
-                }
-            }
         }
         // Pump streaming progress/results
-        let mut drained_pev: Vec<ProgressEvent> = Vec::new();
-        if let Some(prx) = &state.p_rx {
-            while let Ok(ev) = prx.try_recv() {
-                drained_pev.push(ev);
-            }
-        }
-        for ev in drained_pev {
-            let effects = if ev.done {
-                update(
-                    &mut state,
-                    AppMsg::StreamDone {
-                        result: ev.result,
-                        err: ev.err,
-                    },
-                )
-            } else {
-                update(
-                    &mut state,
-                    AppMsg::StreamProgress {
-                        text: ev.text,
-                        percent: ev.percent,
-                    },
-                )
-            };
-            run_effects(&mut state, effects);
+        for ev in drain_progress(&mut state) {
+            apply_progress(&mut state, ev);
         }
+        run_due_jobs(&mut state);
+        report_config_warnings(&mut state);
+        run_leave_hooks(&mut state);
+        flush_telemetry(&mut state, false);
         if last_tick.elapsed() >= tick_rate {
             state.tick = state.tick.wrapping_add(1);
             last_tick = Instant::now();
@@ -1468,8 +4375,16 @@ pub fn run() -> Result<()> {
     // Restore
     disable_raw_mode()?;
     let mut stdout = std::io::stdout();
-    execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
+    execute!(
+        stdout,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste
+    )?;
     terminal.show_cursor()?;
+    if let Some(h) = flush_telemetry(&mut state, true) {
+        let _ = h.join();
+    }
     res
 }
 fn load_config_from_path(state: &mut AppState, relative_path: &str) -> Result<()> {
@@ -1486,78 +4401,307 @@ fn load_config_from_path(state: &mut AppState, relative_path: &str) -> Result<()
 
     let s =
         fs::read_to_string(&cfg_path).with_context(|| format!("reading config: {cfg_path:?}"))?;
-    let new_config: AppConfig =
-        serde_yaml::from_str(&s).with_context(|| format!("parsing config: {cfg_path:?}"))?;
-    state.config = new_config;
+    let new_config: AppConfig = crate::chi_core::vars::parse(&s, &cfg_path)
+        .with_context(|| format!("parsing config: {cfg_path:?}"))?;
+    *state.config = new_config;
     state.current_config_path = Some(relative_path.to_string());
     init_logo_and_header(state);
+    apply_chrome(state);
+    check_commands(state);
+    check_conditions(state, true);
+    enter_screen(state);
     Ok(())
 }
 
 fn load_config() -> Result<AppConfig> {
-    // 1) If CHI_TUI_CONFIG_DIR is set, expect chi-index.yaml inside it
-    if let Ok(base) = std::env::var("CHI_TUI_CONFIG_DIR") {
-        let base_dir = PathBuf::from(&base);
-        let entry = base_dir.join("chi-index.yaml");
-        let s = fs::read_to_string(&entry).with_context(|| format!("reading {entry:?}"))?;
-        // Ensure normalized for relative includes
-        std::env::set_var("CHI_TUI_CONFIG_DIR", &base_dir);
-        let cfg: AppConfig =
-            serde_yaml::from_str(&s).with_context(|| format!("parsing {entry:?}"))?;
-        return Ok(cfg);
-    }
-
-    // 2) Discover chi-index.yaml from CWD and upwards
+    let home = std::env::var("HOME")
+        .ok()
+        .or_else(|| std::env::var("USERPROFILE").ok())
+        .map(PathBuf::from);
     let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    // Try CWD/chi-index.yaml
+    let env_dir = std::env::var("CHI_TUI_CONFIG_DIR").ok().map(PathBuf::from);
+    let entry = find_config(env_dir, &cwd, home)?;
+    // Ensure normalized for relative includes
+    let base_dir = entry.parent().unwrap_or(&cwd).to_path_buf();
+    std::env::set_var("CHI_TUI_CONFIG_DIR", &base_dir);
+    read_entry_config(&entry)
+}
+
+// The entry chi-index.yaml: inside CHI_TUI_CONFIG_DIR when set (it must be there), else
+// discovered in CWD, CWD/.tui, <ancestor>/.tui and finally ~/.tui
+fn find_config(
+    env_dir: Option<PathBuf>,
+    cwd: &std::path::Path,
+    home: Option<PathBuf>,
+) -> Result<PathBuf> {
+    if let Some(base_dir) = env_dir {
+        return Ok(base_dir.join("chi-index.yaml"));
+    }
     let candidates = [
         cwd.join("chi-index.yaml"),
         cwd.join(".tui").join("chi-index.yaml"),
     ];
-    for p in &candidates {
-        if p.exists() {
-            let base_dir = p.parent().unwrap_or(&cwd).to_path_buf();
-            let s = fs::read_to_string(p).with_context(|| format!("reading {p:?}"))?;
-            std::env::set_var("CHI_TUI_CONFIG_DIR", &base_dir);
-            let cfg: AppConfig =
-                serde_yaml::from_str(&s).with_context(|| format!("parsing {p:?}"))?;
-            return Ok(cfg);
-        }
+    if let Some(p) = candidates.into_iter().find(|p| p.exists()) {
+        return Ok(p);
     }
     // Walk up ancestors looking for <ancestor>/.tui/chi-index.yaml
-    let mut cur = cwd.as_path();
-    while let Some(parent) = cur.parent() {
-        let p = parent.join(".tui").join("chi-index.yaml");
-        if p.exists() {
-            let base_dir = p.parent().unwrap_or(parent).to_path_buf();
-            let s = fs::read_to_string(&p).with_context(|| format!("reading {p:?}"))?;
-            std::env::set_var("CHI_TUI_CONFIG_DIR", &base_dir);
-            let cfg: AppConfig =
-                serde_yaml::from_str(&s).with_context(|| format!("parsing {p:?}"))?;
-            return Ok(cfg);
-        }
-        cur = parent;
+    let ancestors = cwd.ancestors().skip(1);
+    if let Some(p) = ancestors
+        .map(|a| a.join(".tui").join("chi-index.yaml"))
+        .find(|p| p.exists())
+    {
+        return Ok(p);
     }
     // Last attempt: ~/.tui/chi-index.yaml
-    if let Some(home) = std::env::var("HOME")
-        .ok()
-        .or_else(|| std::env::var("USERPROFILE").ok())
-        .map(PathBuf::from)
+    if let Some(p) = home
+        .map(|h| h.join(".tui").join("chi-index.yaml"))
+        .filter(|p| p.exists())
     {
-        let p = home.join(".tui").join("chi-index.yaml");
-        if p.exists() {
-            let base_dir = p.parent().unwrap_or(&home).to_path_buf();
-            let s = fs::read_to_string(&p).with_context(|| format!("reading {p:?}"))?;
-            std::env::set_var("CHI_TUI_CONFIG_DIR", &base_dir);
-            let cfg: AppConfig =
-                serde_yaml::from_str(&s).with_context(|| format!("parsing {p:?}"))?;
-            return Ok(cfg);
+        return Ok(p);
+    }
+    Err(ConfigNotFound.into())
+}
+
+fn read_entry_config(entry: &std::path::Path) -> Result<AppConfig> {
+    let s = fs::read_to_string(entry).with_context(|| format!("reading {entry:?}"))?;
+    crate::chi_core::vars::parse_entry(&s, entry).with_context(|| format!("parsing {entry:?}"))
+}
+
+// No chi-index.yaml anywhere (the only load error that offers the setup wizard)
+#[derive(Debug)]
+struct ConfigNotFound;
+
+impl std::fmt::Display for ConfigNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("No config found. Set CHI_TUI_CONFIG_DIR=<dir with chi-index.yaml> or place chi-index.yaml in CWD/.tui and ancestors")
+    }
+}
+
+impl std::error::Error for ConfigNotFound {}
+
+// No config anywhere: offer the first-run setup wizard (interactive terminals only). A config
+// that exists but fails to load reports its own error
+fn load_config_or_setup() -> Result<AppConfig> {
+    let explicit_dir = std::env::var("CHI_TUI_CONFIG_DIR").is_ok();
+    match load_config() {
+        Ok(cfg) => Ok(cfg),
+        Err(e)
+            if wants_setup(&e, explicit_dir)
+                && crate::services::setup_wizard::wizard_available() =>
+        {
+            match crate::services::setup_wizard::run(&e)? {
+                Some(dir) => {
+                    std::env::set_var("CHI_TUI_CONFIG_DIR", &dir);
+                    load_config()
+                }
+                None => Err(e),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// Only a missing config (not one that fails to read or parse) offers the wizard, and only
+// when no CHI_TUI_CONFIG_DIR points at a directory
+fn wants_setup(e: &anyhow::Error, explicit_dir: bool) -> bool {
+    e.is::<ConfigNotFound>() && !explicit_dir
+}
+
+// -------- Workspaces ('W') ------------------------------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Workspace {
+    pub title: String,
+    pub dir: PathBuf,
+}
+
+// The entry config itself plus its `workspaces:`; dirs may hold chi-index.yaml directly
+// or be a project root with .tui/chi-index.yaml. Empty when none are declared.
+pub(crate) fn resolve_workspaces(cfg: &AppConfig, base: &std::path::Path) -> Vec<Workspace> {
+    if cfg.workspaces.is_empty() {
+        return Vec::new();
+    }
+    let home = Workspace {
+        title: cfg.header.clone().unwrap_or_else(|| "Home".to_string()),
+        dir: base.to_path_buf(),
+    };
+    let others = cfg.workspaces.iter().map(|w| {
+        let dir = base.join(&w.dir);
+        let nested = dir.join(".tui");
+        let dir = if !dir.join("chi-index.yaml").exists() && nested.join("chi-index.yaml").exists()
+        {
+            nested
+        } else {
+            dir
+        };
+        Workspace {
+            title: w.title.clone(),
+            dir,
+        }
+    });
+    std::iter::once(home).chain(others).collect()
+}
+
+// Swap the whole config root: menus, chrome, animations and watchdog sessions
+fn switch_workspace(state: &mut AppState, idx: usize) -> Result<()> {
+    let Some(target) = state.workspaces.get(idx).cloned() else {
+        return Ok(());
+    };
+    let previous = std::env::var("CHI_TUI_CONFIG_DIR").ok();
+    std::env::set_var("CHI_TUI_CONFIG_DIR", &target.dir);
+    let config = match load_config() {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            match &previous {
+                Some(dir) => std::env::set_var("CHI_TUI_CONFIG_DIR", dir),
+                None => std::env::remove_var("CHI_TUI_CONFIG_DIR"),
+            }
+            return Err(e);
+        }
+    };
+    if let Some(current) = state.workspaces.get(state.workspace_index) {
+        let sessions = std::mem::take(&mut state.watchdog_sessions);
+        state.parked_sessions.insert(current.dir.clone(), sessions);
+    }
+    state.watchdog_sessions = state
+        .parked_sessions
+        .remove(&target.dir)
+        .unwrap_or_default();
+    state.workspace_index = idx;
+    state.notes = crate::services::notes::Notes::load(&target.dir);
+    *state.config = config;
+    state.current_config_path = None;
+    state.horizontal_tab_index = 0;
+    state.entry_chrome = state.config.chrome.clone();
+    state.confirm_quit = state.config.confirm_quit();
+    state.animations_enabled = !state.config.animations_off();
+    state.reduced_motion = state.config.reduced_motion;
+    init_logo_and_header(state);
+    apply_chrome(state);
+    check_commands(state);
+    check_conditions(state, true);
+    state.selected = 0;
+    state.menu_offset = 0;
+    state.expanded.clear();
+    state.children.clear();
+    state.pages.clear();
+    state.loading.clear();
+    state.cooldowns.clear();
+    state.tab_states.clear();
+    state.panel = None;
+    state.json_viewer = None;
+    state.json_rendered = None;
+    state.last_error = None;
+    state.last_json_pretty = None;
+    state.view = View::Menu;
+    state.boot_autoload_done = false;
+    Ok(())
+}
+
+fn workspaces_screen_key(state: &mut AppState, code: KeyCode) {
+    let total = state.workspaces.len();
+    match code {
+        KeyCode::Up => state.workspace_selected = state.workspace_selected.saturating_sub(1),
+        KeyCode::Down if state.workspace_selected + 1 < total => state.workspace_selected += 1,
+        KeyCode::Enter if state.workspace_selected == state.workspace_index => {
+            state.view = state.screen_return;
+        }
+        KeyCode::Enter => {
+            let idx = state.workspace_selected;
+            let title = state.workspaces[idx].title.clone();
+            let toast = match switch_workspace(state, idx) {
+                Ok(()) => {
+                    state.dbg(format!("workspace: {title}"));
+                    Effect::ShowToast {
+                        text: format!("Workspace: {title}"),
+                        level: ToastLevel::Success,
+                        seconds: 2,
+                    }
+                }
+                Err(e) => {
+                    state.dbg(format!("workspace {title} failed: {e:#}"));
+                    Effect::ShowToast {
+                        text: format!("Cannot open workspace '{title}': {e}"),
+                        level: ToastLevel::Error,
+                        seconds: 4,
+                    }
+                }
+            };
+            run_effects(state, vec![toast]);
+        }
+        KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('W') => {
+            state.view = state.screen_return;
         }
+        _ => {}
+    }
+}
+
+// Pane chrome of the current screen (or the entry config) for every panel_block
+// `theme.yaml` next to the entry config, when the entry config has no `theme:` of its own
+fn load_theme_file(state: &mut AppState) {
+    if state.config.theme.is_some() {
+        return;
     }
+    let Ok(dir) = std::env::var("CHI_TUI_CONFIG_DIR") else {
+        return;
+    };
+    let Ok(s) = fs::read_to_string(PathBuf::from(dir).join("theme.yaml")) else {
+        return;
+    };
+    let parsed = serde_yaml::from_str::<crate::model::ThemeConfig>(&s)
+        .map_err(|e| e.to_string())
+        .and_then(|t| crate::theme::Theme::from_config(&t).map(|_| t));
+    match parsed {
+        Ok(theme) => state.config.theme = Some(theme),
+        Err(e) => state.dbg(format!("theme.yaml ignored: {e}")),
+    }
+}
+
+// `theme: {preset: auto}` (the default) on a light terminal: the light preset, with the
+// configured colors applied to it
+fn apply_terminal_background(
+    state: &mut AppState,
+    bg: Option<crate::services::terminal_bg::Background>,
+) {
+    use crate::services::terminal_bg::Background;
+    if !crate::theme::is_auto(state.entry_theme.as_ref()) || bg != Some(Background::Light) {
+        return;
+    }
+    let mut entry = state.entry_theme.clone().unwrap_or_default();
+    entry.preset = Some("light".into());
+    state.entry_theme = Some(entry);
+    state.theme_index = crate::theme::PRESETS
+        .iter()
+        .position(|p| *p == "light")
+        .unwrap_or(0);
+    state.theme = state.theme.switched(state.entry_theme.as_ref(), "light");
+    state.dbg("Light terminal background: using the light theme");
+}
+
+// Next theme preset for the session
+fn next_theme(state: &mut AppState) -> &'static str {
+    state.theme_index = (state.theme_index + 1) % crate::theme::PRESETS.len();
+    let preset = crate::theme::PRESETS[state.theme_index];
+    state.theme = state.theme.switched(state.entry_theme.as_ref(), preset);
+    preset
+}
+
+// Ctrl+T: next theme preset, applied right away
+fn cycle_theme(state: &mut AppState) {
+    let preset = next_theme(state);
+    crate::theme::set_active(state.theme.clone());
+    run_effects(
+        state,
+        vec![Effect::ShowToast {
+            text: format!("Theme: {preset}"),
+            level: ToastLevel::Info,
+            seconds: 2,
+        }],
+    );
+}
 
-    Err(anyhow::anyhow!(
-        "No config found. Set CHI_TUI_CONFIG_DIR=<dir with chi-index.yaml> or place chi-index.yaml in CWD/.tui and ancestors"
-    ))
+fn apply_chrome(state: &AppState) {
+    let chrome = state.config.chrome.as_ref().or(state.entry_chrome.as_ref());
+    crate::widgets::chrome::set_active(chrome);
 }
 
 fn init_logo_and_header(state: &mut AppState) {
@@ -1591,6 +4735,8 @@ fn init_logo_and_header(state: &mut AppState) {
 // -------- Streaming progress runner (NDJSON envelopes) ---------------------
 #[derive(Debug)]
 pub(crate) struct ProgressEvent {
+    // Id of the stream that sent it (`AppState::stream_id` when it started)
+    pub(crate) stream: u64,
     pub(crate) text: Option<String>,
     pub(crate) percent: Option<f64>,
     pub(crate) done: bool,
@@ -1638,8 +4784,11 @@ pub(crate) fn title_from_value(v: &JsonValue) -> String {
     if let Some(n) = v.get("name").and_then(|s| s.as_str()) {
         return n.to_string();
     }
-    v.to_string().chars().take(60).collect()
+    crate::widgets::text_width::truncate(&v.to_string(), 60)
 }
+// Startup splash length: 2 seconds @ 200ms
+const SPLASH_TICKS: u64 = 10;
+
 fn ui(f: &mut Frame, state: &mut AppState) {
     // Clear expired toast
     if let Some(t) = &state.toast {
@@ -1653,6 +4802,11 @@ fn ui(f: &mut Frame, state: &mut AppState) {
     let bg = Block::default().style(Style::default().bg(state.theme.bg));
     f.render_widget(bg, screen);
 
+    if state.tick < state.splash_until_tick {
+        crate::widgets::banner::draw_splash(f, screen, state);
+        return;
+    }
+
     // Split screen: 5% left margin, 90% content, 5% right margin
     let layout_h = Layout::default()
         .direction(Direction::Horizontal)
@@ -1668,16 +4822,21 @@ fn ui(f: &mut Frame, state: &mut AppState) {
     let right_side = layout_h[2];
 
     // Draw animated backgrounds on side strips
-    // Start with a vivid animation for at least MIN ticks; extend while loading/streaming.
-    const ANIMATION_MIN_TICKS: u64 = 15; // 3 seconds @ 200ms
+    // Start with a vivid animation for at least MIN frames; extend while loading/streaming.
+    const ANIMATION_MIN_FRAMES: u64 = 15; // 3 seconds @ 200ms
+    let frame = state.frame();
 
-    if state.animations_enabled {
-        let elapsed_ticks = state.tick.saturating_sub(state.animation_start_tick);
+    if state.animations_enabled && state.reduced_motion {
+        // Reduced motion: the ambient dots, standing still
+        crate::visuals::draw_ambient_bg(f, left_side, &state.theme, 0);
+        crate::visuals::draw_ambient_bg(f, right_side, &state.theme, 0);
+    } else if state.animations_enabled {
+        let elapsed = frame.saturating_sub(state.animation_start_frame);
         let loading_active = !state.loading.is_empty()
             || state.status_text.is_some()
             || state.status_percent.is_some();
 
-        if elapsed_ticks < ANIMATION_MIN_TICKS || loading_active {
+        if elapsed < ANIMATION_MIN_FRAMES || loading_active {
             // Full matrix animation during startup and while loading
             let palette = [
                 state.theme.primary,
@@ -1692,24 +4851,24 @@ fn ui(f: &mut Frame, state: &mut AppState) {
                 state.theme.primary,
             ];
 
-            crate::visuals::draw_matrix_bg_custom(f, left_side, &palette, state.tick);
-            crate::visuals::draw_matrix_bg_custom(f, right_side, &palette_r, state.tick);
+            crate::visuals::draw_matrix_bg_custom(f, left_side, &palette, frame);
+            crate::visuals::draw_matrix_bg_custom(f, right_side, &palette_r, frame);
         } else {
             // After 3 seconds - switch to subtle ambient dots (like in banner)
-            crate::visuals::draw_ambient_bg(f, left_side, &state.theme, state.tick);
-            crate::visuals::draw_ambient_bg(f, right_side, &state.theme, state.tick);
+            crate::visuals::draw_ambient_bg(f, left_side, &state.theme, frame);
+            crate::visuals::draw_ambient_bg(f, right_side, &state.theme, frame);
         }
     }
 
     // Show a small loading indicator at top-left when loading/streaming
     if !state.loading.is_empty() || state.status_text.is_some() || state.status_percent.is_some() {
         // Animated spinner
-        let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.tick as usize % 6];
+        let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][frame as usize % 6];
         let msg = format!(" {spinner} loading...");
         let overlay = Rect {
             x: screen.x,
             y: screen.y,
-            width: msg.len() as u16 + 1,
+            width: crate::widgets::text_width::display_width(&msg) as u16 + 1,
             height: 1,
         };
         let p = Paragraph::new(msg).style(Style::default().fg(Color::DarkGray));
@@ -1722,10 +4881,20 @@ fn ui(f: &mut Frame, state: &mut AppState) {
     // Add space for horizontal menu (always shown)
     constraints.push(Constraint::Length(2)); // Horizontal menu height
 
+    // Notices and failed startup checks, capped so the main content keeps most of the screen
+    let mut banner = crate::widgets::notice::banner_lines(state);
+    banner.extend(crate::widgets::preflight::banner_lines(state));
+    banner.extend(crate::widgets::offline::banner_lines(state));
+    banner.extend(crate::widgets::watches::banner_lines(state));
+    let banner_h = (banner.len() as u16).min(content_area.height / 4);
+    if banner_h > 0 {
+        constraints.push(Constraint::Length(banner_h));
+    }
+
     constraints.push(Constraint::Min(0)); // Main content
-                                          // Dedicated debug pane (fixed height)
-    const DEBUG_H: u16 = 4;
-    constraints.push(Constraint::Length(DEBUG_H));
+                                          // Debug pane (resizable while focused)
+    let debug_h = debug_height(state).min((content_area.height / 2).max(DEBUG_H));
+    constraints.push(Constraint::Length(debug_h));
     constraints.push(Constraint::Length(1)); // Footer
 
     let dual_footer = state.status_text.is_some() && content_area.height >= 6;
@@ -1748,13 +4917,19 @@ fn ui(f: &mut Frame, state: &mut AppState) {
     crate::widgets::horizontal_menu::draw_horizontal_menu(f, chunks[chunk_idx], state);
     chunk_idx += 1;
 
+    if banner_h > 0 {
+        crate::widgets::preflight::draw_banner(f, chunks[chunk_idx], banner);
+        chunk_idx += 1;
+    }
+
     let main_content_chunk = chunks[chunk_idx];
+    state.content_area = main_content_chunk;
     let debug_chunk = chunks[chunk_idx + 1];
     let footer_chunk = chunks[chunk_idx + 2];
 
     // Demo: Show loading border animation when something is loading
-    if !state.loading.is_empty() && state.animations_enabled {
-        crate::visuals::draw_loading_border(f, main_content_chunk, &state.theme, state.tick);
+    if !state.loading.is_empty() && state.motion() {
+        crate::visuals::draw_loading_border(f, main_content_chunk, &state.theme, state.frame());
     }
 
     match state.view {
@@ -1765,25 +4940,94 @@ fn ui(f: &mut Frame, state: &mut AppState) {
         View::Welcome => draw_welcome(f, main_content_chunk, state),
         View::Json => draw_json(f, main_content_chunk, state),
         View::Panel => draw_panel(f, main_content_chunk, state),
+        View::Scheduled => crate::widgets::scheduled::draw_scheduled(f, main_content_chunk, state),
+        View::Results => crate::widgets::results::draw_results(f, main_content_chunk, state),
+        View::Workspaces => {
+            crate::widgets::workspaces::draw_workspaces(f, main_content_chunk, state)
+        }
+    }
+    // Debug pane (bottom): the console widget while focused
+    match state.debug_console.as_mut() {
+        Some(console) if state.debug_focused => {
+            crate::widgets::Widget::render(console, f, debug_chunk, true, state.tick)
+        }
+        _ => draw_debug(f, debug_chunk, state),
     }
-    // Debug pane (bottom, fixed height)
-    draw_debug(f, debug_chunk, state);
     let help_text: String = match state.view {
+        // Hints rendered inside the focused debug console
+        _ if state.debug_focused => String::new(),
         View::Json => {
-            "↑/↓ scroll • PgUp/PgDn • Home/End • w wrap • Backspace/Esc back • q quit".to_string()
+            let global = "space actions • Backspace/Esc back • q quit";
+            let hints = match &state.json_rendered {
+                Some(w) => Some(w.hints()),
+                None => state.json_viewer.as_ref().map(crate::widgets::Widget::hints),
+            };
+            match hints {
+                Some(h) if h.modal => h.text(),
+                Some(h) if !h.keys.is_empty() => format!("{} • {global}", h.text()),
+                _ => format!("↑/↓ scroll • PgUp/PgDn • Home/End • w wrap • {global}"),
+            }
         }
         View::Panel => String::new(), // Hints rendered inside the focused panel bar
-        _ => "↑/↓ select • Enter open • r refresh • esc back • q quit".to_string(),
+        View::Scheduled => "↑/↓ select • d cancel job • esc back • q quit".to_string(),
+        View::Workspaces => "↑/↓ select • Enter switch • esc back • q quit".to_string(),
+        View::Results if state.results_screen.diff.is_some() => {
+            "↑/↓ scroll • D/esc back to the list • q quit".to_string()
+        }
+        View::Results => {
+            "↑/↓ select • Enter open • space mark • D diff • e export • esc back • q quit"
+                .to_string()
+        }
+        _ if state.hint_mode.is_some() => "type a label to jump • esc cancel".to_string(),
+        _ => format!(
+            "↑/↓ select • Enter open{} • f jump • t schedule • T scheduled • H results • : command • / search • n note • r refresh • esc back • q quit",
+            verb_hint(state)
+        ),
     };
     if dual_footer {
         draw_status(f, footer_chunk, state);
-        let help = Paragraph::new(help_text.as_str()).style(Style::default().fg(Color::DarkGray));
         // Last chunk exists when dual_footer is true
-        f.render_widget(help, chunks[chunk_idx + 3]);
+        let help_chunk = chunks[chunk_idx + 3];
+        let help_text =
+            crate::widgets::text_width::fit_hints(&help_text, help_chunk.width as usize);
+        let help = Paragraph::new(help_text).style(Style::default().fg(Color::DarkGray));
+        f.render_widget(help, help_chunk);
     } else {
         draw_footer_combined(f, footer_chunk, state, help_text.as_str());
     }
 
+    if let Some(prompt) = &state.schedule_prompt {
+        crate::widgets::scheduled::draw_prompt(f, screen, prompt);
+    }
+    if let Some(prompt) = &state.command_prompt {
+        crate::widgets::command_prompt::draw_prompt(f, screen, prompt);
+    }
+    if let Some(prompt) = &state.note_prompt {
+        crate::widgets::notes::draw_prompt(f, screen, prompt, &state.notes);
+    }
+    if let Some(prompt) = &state.watch_prompt {
+        crate::widgets::watches::draw_prompt(f, screen, prompt);
+    }
+    if let Some(palette) = &state.palette {
+        crate::widgets::palette::draw_palette(f, screen, palette);
+    }
+    if let Some(bar) = &state.action_bar {
+        crate::widgets::action_bar::draw_action_bar(f, screen, bar);
+    }
+    if let Some(prompt) = &state.auth_prompt {
+        crate::widgets::auth::draw_login(f, screen, prompt);
+    }
+    if let Some(target) = &state.nav_guard {
+        crate::widgets::unsaved::draw_guard(f, screen, target.label());
+    }
+    if let Some(items) = &state.quit_confirm {
+        crate::widgets::unsaved::draw_quit(f, screen, items);
+    }
+    if let Some(mi) = &state.verb_confirm {
+        let command = mi.command.as_deref().unwrap_or_default();
+        crate::widgets::unsaved::draw_confirm(f, screen, &mi.title, command);
+    }
+
     // Draw color palette bars LAST so they appear on top of everything else
     crate::visuals::draw_color_bars(f, screen, &state.theme);
 }
@@ -1799,6 +5043,69 @@ fn draw_welcome(f: &mut Frame, area: Rect, state: &AppState) {
     f.render_widget(p, area);
 }
 use crate::widgets::status_bar::{draw_footer_combined, draw_status};
+// Debug pane heights: the passive strip, the focused console, and the +/- limits
+const DEBUG_H: u16 = 4;
+const DEBUG_FOCUSED_H: u16 = 12;
+const DEBUG_MIN_H: u16 = 3;
+const DEBUG_MAX_H: u16 = 40;
+
+fn debug_height(state: &AppState) -> u16 {
+    state.debug_h.unwrap_or(if state.debug_focused {
+        DEBUG_FOCUSED_H
+    } else {
+        DEBUG_H
+    })
+}
+
+// Ctrl+D: focus the debug pane as a log console over the debug log, or give focus back
+fn toggle_debug_console(state: &mut AppState) {
+    state.debug_focused = !state.debug_focused;
+    if state.debug_focused && state.debug_console.is_none() {
+        let console = crate::widgets::log_viewer::LogViewerWidget::new(
+            "Debug",
+            crate::widgets::log_viewer::LogSource::Lines,
+        );
+        for line in &state.debug_log {
+            console.push(line.clone());
+        }
+        state.debug_console = Some(console);
+    }
+}
+
+// Keys while the debug console is focused: +/- resize it, Ctrl+C copies the lines matching
+// its filter, Esc/Ctrl+D leave it; the rest scroll, follow and filter in the log viewer
+fn debug_console_key(state: &mut AppState, key: KeyEvent) {
+    let Some(console) = state.debug_console.as_mut() else {
+        state.debug_focused = false;
+        return;
+    };
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    if console.filter_editing() {
+        let _ = widget_key(console, key.code);
+        return;
+    }
+    match key.code {
+        KeyCode::Esc => state.debug_focused = false,
+        KeyCode::Char('d') if ctrl => state.debug_focused = false,
+        KeyCode::Char('c') if ctrl => {
+            let text = console.text();
+            if let Ok(mut clipboard) = arboard::Clipboard::new() {
+                let _ = clipboard.set_text(&text);
+                state.status_text = Some("Copied to clipboard!".to_string());
+            }
+        }
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            state.debug_h = Some((debug_height(state) + 2).min(DEBUG_MAX_H))
+        }
+        KeyCode::Char('-') => {
+            state.debug_h = Some(debug_height(state).saturating_sub(2).max(DEBUG_MIN_H))
+        }
+        code => {
+            let _ = widget_key(console, code);
+        }
+    }
+}
+
 fn draw_debug(f: &mut Frame, area: Rect, state: &AppState) {
     let b = Block::default()
         .borders(Borders::TOP)
@@ -1838,6 +5145,119 @@ pub(crate) fn is_panel(mi: &MenuItem) -> bool {
 pub(crate) fn is_markdown(mi: &MenuItem) -> bool {
     matches!(mi.widget.as_deref(), Some("markdown"))
 }
+pub(crate) fn is_runbook(mi: &MenuItem) -> bool {
+    matches!(mi.widget.as_deref(), Some("runbook"))
+}
+pub(crate) fn is_board(mi: &MenuItem) -> bool {
+    matches!(mi.widget.as_deref(), Some("board"))
+}
+pub(crate) fn is_tree(mi: &MenuItem) -> bool {
+    matches!(mi.widget.as_deref(), Some("tree"))
+}
+pub(crate) fn is_processes(mi: &MenuItem) -> bool {
+    matches!(mi.widget.as_deref(), Some("processes"))
+}
+pub(crate) fn is_ports(mi: &MenuItem) -> bool {
+    matches!(mi.widget.as_deref(), Some("ports"))
+}
+pub(crate) fn is_http_checks(mi: &MenuItem) -> bool {
+    matches!(mi.widget.as_deref(), Some("http_checks"))
+}
+pub(crate) fn is_promql(mi: &MenuItem) -> bool {
+    matches!(mi.widget.as_deref(), Some("promql"))
+}
+pub(crate) fn is_log_viewer(mi: &MenuItem) -> bool {
+    matches!(mi.widget.as_deref(), Some("log_viewer"))
+}
+// Whole seconds left on the item's cooldown, if it is still cooling down
+pub(crate) fn cooldown_left(state: &AppState, mi: &MenuItem) -> Option<u64> {
+    let until = state.cooldowns.get(&crate::nav::keys::menu_key(mi))?;
+    let left = until.saturating_duration_since(Instant::now());
+    (!left.is_zero()).then(|| left.as_secs_f64().ceil() as u64)
+}
+// Program run by one of the item's commands that is not on PATH
+pub(crate) fn item_missing_program(mi: &MenuItem) -> Option<String> {
+    [&mi.command, &mi.pane_a_cmd, &mi.pane_b_cmd]
+        .into_iter()
+        .flatten()
+        .find_map(|c| crate::services::cli_runner::missing_program(c))
+}
+// Run the `when_cmd` / `enabled_cmd` of the loaded config's items in the background;
+// `reset` first drops the answers of the config loaded before
+pub(crate) fn check_conditions(state: &mut AppState, reset: bool) {
+    if reset {
+        state.conditions.reset();
+    }
+    let run = state.conditions.run();
+    for (key, kind, cmdline) in state.conditions.start(&state.config.menu) {
+        let reply = crate::services::bus::subscribe(
+            state,
+            format!("condition:{key}"),
+            move |st, outcome| {
+                st.conditions.done(run, key, kind, outcome);
+                Vec::new()
+            },
+        );
+        if let Some(reply) = reply {
+            crate::services::loader::spawn_condition(cmdline, reply);
+        }
+    }
+}
+// Grey out the items of the loaded config whose program is not installed
+fn check_commands(state: &mut AppState) {
+    state.missing_commands = state
+        .config
+        .menu
+        .iter()
+        .filter_map(|mi| Some((menu_key(mi), item_missing_program(mi)?)))
+        .collect();
+    state.broken_refs = crate::chi_core::file_refs::broken(&state.config.menu);
+    let msgs: Vec<String> = state
+        .broken_refs
+        .iter()
+        .map(|b| format!("config: {} `{}`: {} not found", b.id, b.field, b.path))
+        .collect();
+    for msg in msgs {
+        state.dbg(msg);
+    }
+}
+// "pane_b_yaml not found" while a file the item reads is missing (first one when several are)
+pub(crate) fn broken_ref_label(state: &AppState, mi: &MenuItem) -> Option<String> {
+    let b = state.broken_refs.iter().find(|b| b.id == mi.id)?;
+    Some(format!("{} not found", b.field))
+}
+// "missing: mycli (brew install mycli)" while the item's program is not installed
+pub(crate) fn missing_label(state: &AppState, mi: &MenuItem) -> Option<String> {
+    let program = state.missing_commands.get(&menu_key(mi))?;
+    Some(match &mi.install_hint {
+        Some(hint) => format!("missing: {program} ({hint})"),
+        None => format!("missing: {program}"),
+    })
+}
+// Columns declared by the item that loaded this row (`fields:` on the menu item or child value)
+pub(crate) fn row_fields(state: &AppState, row_key: &str) -> Vec<crate::model::FieldSpec> {
+    let Some((parent, _)) = row_key.rsplit_once('/') else {
+        return Vec::new();
+    };
+    let fields = match parent.rsplit_once('/') {
+        None => state
+            .config
+            .menu
+            .iter()
+            .find(|m| menu_key(m) == parent)
+            .and_then(|m| m.fields.clone()),
+        Some((grandparent, _)) => state
+            .children
+            .get(grandparent)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .find(|(i, v)| crate::nav::keys::child_key(grandparent, v, *i) == parent)
+            .and_then(|(_, v)| v.get("fields").cloned())
+            .and_then(|f| serde_json::from_value(f).ok()),
+    };
+    fields.unwrap_or_default()
+}
 pub(crate) fn is_watchdog(mi: &MenuItem) -> bool {
     matches!(mi.widget.as_deref(), Some("watchdog"))
 }
@@ -1864,36 +5284,25 @@ pub(crate) enum LoadOutcome {
     },
     Fallback(JsonValue),
 }
+// A background result, tagged with the subscription waiting for it (services::bus)
 pub(crate) struct LoadMsg {
+    pub(crate) id: u64,
+    // Entry cleared from `loading` when it arrives
     pub(crate) key: String,
     pub(crate) outcome: Result<LoadOutcome, String>,
-    pub(crate) kind: LoadKind,
-}
-#[derive(Clone, Copy)]
-pub(crate) enum LoadKind {
-    Menu,
-    Child,
-    PanelA,
-    PanelB,
-    #[allow(dead_code)]
-    PanelBNestedA,
-    #[allow(dead_code)]
-    PanelBNestedB,
-    SubmitForm,
-    FormOptions,
 }
 // spawn_load_for_* moved to services::loader
 fn trigger_initial_autoloads(state: &mut AppState) {
-    let Some(tx) = state.tx.clone() else {
+    if state.tx.is_none() {
         return;
-    };
+    }
     for mi in state.config.menu.clone() {
         if is_autoload(&mi) && auto_expand_menu(&mi) {
             let key = menu_key(&mi);
             if !state.children.contains_key(&key) && !state.loading.contains(&key) {
                 state.loading.insert(key.clone());
                 state.expanded.insert(key.clone());
-                crate::services::loader::spawn_load_for_menu(mi, key, tx.clone());
+                run_effects(state, vec![Effect::LoadMenu { mi, key }]);
             }
         }
     }
@@ -1941,6 +5350,9 @@ pub(crate) struct PanelState {
     pub b_content: PaneContent,
     // History of Pane B content for Back navigation
     pub b_history: Vec<PaneContent>,
+    // Widget hosted in Pane A instead of the menu (`pane_a_widget:` or a widget spec loaded
+    // by `pane_a_yaml`/`pane_a_cmd`), e.g. a watchdog running dev servers
+    pub a_content: Option<Box<dyn crate::widgets::Widget>>,
 }
 
 impl Default for PanelState {
@@ -1952,6 +5364,7 @@ impl Default for PanelState {
             b: PaneData::default(),
             b_content: PaneContent::Widget(Box::new(JsonViewerWidget::from_text("Pane B", ""))),
             b_history: Vec::new(),
+            a_content: None,
         }
     }
 }
@@ -1959,7 +5372,7 @@ impl Default for PanelState {
 pub(crate) enum PaneContent {
     Json,
     Menu {
-        config: crate::model::AppConfig,
+        config: Box<crate::model::AppConfig>,
         selected: usize,
     },
     Panel(Box<PanelState>),
@@ -1973,6 +5386,37 @@ pub(crate) fn parse_panel_layout(s: Option<&str>) -> PanelLayout {
     }
 }
 
+// Template applied to the panel view when the terminal is narrower than the configured width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NarrowLayout {
+    // Stack Pane A above Pane B
+    Vertical,
+    // Hide Pane A; it slides in as a drawer while focused (Tab)
+    Drawer,
+}
+
+const DEFAULT_NARROW_WIDTH: u16 = 100;
+
+// Resolve the narrow template for the given terminal width (None => use the panel's own layout)
+pub(crate) fn narrow_panel_layout(state: &AppState, width: u16) -> Option<NarrowLayout> {
+    let rules = state.config.responsive.as_ref();
+    let name = state
+        .panel_narrow_layout
+        .as_deref()
+        .or_else(|| rules.and_then(|r| r.narrow_layout.as_deref()))?;
+    let threshold = rules
+        .and_then(|r| r.narrow_width)
+        .unwrap_or(DEFAULT_NARROW_WIDTH);
+    if width >= threshold {
+        return None;
+    }
+    match name.to_ascii_lowercase().as_str() {
+        "vertical" => Some(NarrowLayout::Vertical),
+        "drawer" => Some(NarrowLayout::Drawer),
+        _ => None,
+    }
+}
+
 pub(crate) fn parse_panel_ratio(s: Option<&str>) -> PanelRatio {
     match s.unwrap_or("1:1") {
         "1:3" => PanelRatio::OneToThree,
@@ -1985,6 +5429,23 @@ pub(crate) fn parse_panel_ratio(s: Option<&str>) -> PanelRatio {
     }
 }
 
+// Pane A: the widget it hosts (behind the error boundary) or the main menu
+fn draw_pane_a(f: &mut Frame, area: Rect, state: &mut AppState) {
+    let frame = state.frame();
+    let focused = matches!(state.view, View::Panel) && matches!(state.panel_focus, PanelPane::A);
+    let Some(w) = pane_a_widget(state) else {
+        draw_menu(f, area, state);
+        return;
+    };
+    crate::widgets::error_boundary::call(|| w.render(f, area, focused, frame));
+    if let Some(message) = crate::widgets::error_boundary::take_failure() {
+        crash_pane_a(state, message);
+        if let Some(err) = pane_a_widget(state) {
+            err.render(f, area, focused, frame);
+        }
+    }
+}
+
 fn draw_panel(f: &mut Frame, area: Rect, state: &mut AppState) {
     let Some(ps_ref) = state.panel.as_ref() else {
         let p = Paragraph::new("Panel not initialized")
@@ -2001,7 +5462,11 @@ fn draw_panel(f: &mut Frame, area: Rect, state: &mut AppState) {
         PanelRatio::TwoToThree => [Constraint::Percentage(40), Constraint::Percentage(60)],
         PanelRatio::ThreeToTwo => [Constraint::Percentage(60), Constraint::Percentage(40)],
     };
-    let chunks = if matches!(ps_ref.layout, PanelLayout::Horizontal) {
+    // Responsive template: narrow terminals may stack panes or collapse Pane A into a drawer
+    let narrow = narrow_panel_layout(state, f.area().width);
+    let horizontal = matches!(ps_ref.layout, PanelLayout::Horizontal)
+        && !matches!(narrow, Some(NarrowLayout::Vertical));
+    let chunks = if horizontal {
         Layout::default()
             .direction(Direction::Horizontal)
             .constraints(constraints)
@@ -2014,12 +5479,25 @@ fn draw_panel(f: &mut Frame, area: Rect, state: &mut AppState) {
     };
 
     // Compute help text for focused pane (rendered as an inner bottom bar)
-    let help = panel_help_text(state);
+    let mut help = panel_help_text(state);
     let focus_on_a = matches!(state.view, View::Panel) && matches!(state.panel_focus, PanelPane::A);
 
     // Prepare areas for A and B; reserve one line for help in the focused pane
     let mut area_a = chunks[0];
     let mut area_b = chunks[1];
+    let mut b_full = chunks[1];
+    if matches!(narrow, Some(NarrowLayout::Drawer)) {
+        // B takes the whole area; A is drawn on top of it (left side) only while focused
+        area_b = area;
+        b_full = area;
+        area_a = Rect {
+            width: (area.width * 2 / 3).max(24).min(area.width),
+            ..area
+        };
+        if !focus_on_a {
+            help = format!("Tab menu • {help}");
+        }
+    }
     let mut help_area = None;
     if focus_on_a {
         if area_a.height > 2 {
@@ -2042,28 +5520,101 @@ fn draw_panel(f: &mut Frame, area: Rect, state: &mut AppState) {
     }
 
     // Left/Top pane (A): render the main menu directly (no extra wrapper)
-    draw_menu(f, area_a, state);
+    state.menu_viewport_h = area_a.height.saturating_sub(2);
+    if !matches!(narrow, Some(NarrowLayout::Drawer)) {
+        draw_pane_a(f, area_a, state);
+    }
 
     // Right/Bottom pane (B)
+    state.pane_b_area = area_b;
+    let Some(ps_ref) = state.panel.as_ref() else {
+        return;
+    };
     match &ps_ref.b_content {
         PaneContent::Panel(nested) => {
             // Draw nested panel inside Pane B area (highlight nested focus)
-            draw_nested_panel(f, chunks[1], nested, state.panel_nested_focus);
+            draw_nested_panel(f, b_full, nested, state.panel_nested_focus);
+            if let Some(h) = &state.pane_b_highlights {
+                h.paint(f.buffer_mut(), b_full);
+            }
         }
         PaneContent::Widget(_) => {
+            let frame = state.frame();
             if let Some(ps_mut) = state.panel.as_mut() {
                 if let PaneContent::Widget(ref mut w) = ps_mut.b_content {
                     let area_b = area_b;
                     let highlight = matches!(state.view, View::Panel)
                         && matches!(state.panel_focus, PanelPane::B);
-                    w.render(f, area_b, highlight, state.tick);
+                    crate::widgets::error_boundary::call(|| w.render(f, area_b, highlight, frame));
+                }
+            }
+            if recover_widget(state) {
+                let highlight =
+                    matches!(state.view, View::Panel) && matches!(state.panel_focus, PanelPane::B);
+                if let Some(PanelState {
+                    b_content: PaneContent::Widget(w),
+                    ..
+                }) = state.panel.as_mut()
+                {
+                    w.render(f, area_b, highlight, frame);
                 }
             }
+            if let Some(h) = &state.pane_b_highlights {
+                h.paint(f.buffer_mut(), area_b);
+            }
         }
         PaneContent::Json => {}
         PaneContent::Menu { .. } => {}
     }
 
+    // Drawer: Pane A slides over Pane B while it has focus
+    if matches!(narrow, Some(NarrowLayout::Drawer)) && focus_on_a {
+        f.render_widget(Clear, area_a);
+        draw_pane_a(f, area_a, state);
+    }
+
+    // Flash the border of the pane that just received content
+    if let Some(flash) = state.pane_flash {
+        if state.tick < flash.until_tick {
+            let target = match flash.pane {
+                PanelPane::A if !matches!(narrow, Some(NarrowLayout::Drawer)) || focus_on_a => {
+                    Some(area_a)
+                }
+                PanelPane::A => None,
+                PanelPane::B => Some(b_full),
+            };
+            if let Some(rect) = target {
+                // Restyle only the edge cells so pane titles stay readable
+                let style = Style::default()
+                    .fg(state.theme.accent)
+                    .add_modifier(Modifier::BOLD);
+                let bottom = rect.y + rect.height.saturating_sub(1);
+                let right = rect.x + rect.width.saturating_sub(1);
+                let buf = f.buffer_mut();
+                buf.set_style(Rect { height: 1, ..rect }, style);
+                buf.set_style(
+                    Rect {
+                        y: bottom,
+                        height: 1,
+                        ..rect
+                    },
+                    style,
+                );
+                buf.set_style(Rect { width: 1, ..rect }, style);
+                buf.set_style(
+                    Rect {
+                        x: right,
+                        width: 1,
+                        ..rect
+                    },
+                    style,
+                );
+            }
+        } else {
+            state.pane_flash = None;
+        }
+    }
+
     // Draw help text inside the focused panel's bottom bar
     if let Some(hrect) = help_area {
         let theme = &state.theme;
@@ -2074,7 +5625,13 @@ fn draw_panel(f: &mut Frame, area: Rect, state: &mut AppState) {
                     .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(help, Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                crate::widgets::text_width::fit_hints(
+                    &help,
+                    hrect.width.saturating_sub(6) as usize,
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
         ]);
         let p = Paragraph::new(line);
         f.render_widget(p, hrect);
@@ -2083,60 +5640,42 @@ fn draw_panel(f: &mut Frame, area: Rect, state: &mut AppState) {
 
 fn panel_help_text(state: &AppState) -> String {
     // Default when no panel
-    let default = "↑/↓ select • Enter open • r refresh • esc back • q quit".to_string();
+    if state.hint_mode.is_some() {
+        return "type a label to jump • esc cancel".to_string();
+    }
+    let verbs = if matches!(state.panel_focus, PanelPane::A) {
+        verb_hint(state)
+    } else {
+        String::new()
+    };
+    let default = format!(
+        "↑/↓ select • Enter open{verbs} • f jump • t schedule • T scheduled • H results • : command • / search • n note • r refresh • esc back • q quit"
+    );
     let Some(ps) = &state.panel else {
         return default;
     };
-    // Focused pane decides hints. If B and hosts a form, tailor hints; else use generic.
-    let focus_b = matches!(state.panel_focus, PanelPane::B);
-    if focus_b {
-        if let PaneContent::Widget(w) = &ps.b_content {
-            if let Some(fw) = w
-                .as_any()
-                .downcast_ref::<crate::widgets::form_widget::FormWidget>()
-            {
-                let form = &fw.form;
-                if form.editing {
-                    if let Some(fld) = form.fields.get(form.selected) {
-                        return match fld.kind {
-                            crate::widgets::form::FieldKind::Select { .. } =>
-                                "↑/↓ move • Enter select • ←/→ commit • esc exit edit • s submit • q quit".to_string(),
-                            crate::widgets::form::FieldKind::MultiSelect { .. } =>
-                                "↑/↓ move • Space/Enter toggle • esc exit edit • s submit • q quit".to_string(),
-                            crate::widgets::form::FieldKind::TextArea { .. } =>
-                                "Type • Enter newline • esc finish • s submit • q quit".to_string(),
-                            _ => "↑/↓ move • Enter finish • esc exit edit • s submit • q quit".to_string(),
-                        };
-                    } else {
-                        return "↑/↓ move • Enter • esc exit edit • s submit • q quit".to_string();
-                    }
-                } else if let Some(fld) = form.fields.get(form.selected) {
-                    let refresh_hint = if fld.dyn_options_cmd.is_some() {
-                        " • r refresh"
-                    } else {
-                        ""
-                    };
-                    return match fld.kind {
-                        crate::widgets::form::FieldKind::Select { .. } =>
-                            format!("↑/↓ select field • Enter edit • ←/→ change{refresh_hint} • s submit • esc back • q quit"),
-                        crate::widgets::form::FieldKind::TextArea { .. } =>
-                            format!("↑/↓ select field • Enter edit • esc back • q quit{refresh_hint}"),
-                        _ => format!("↑/↓ select field • Enter edit{refresh_hint} • s submit • esc back • q quit"),
-                    };
-                } else {
-                    return "↑/↓ select • Enter edit • s submit • esc back • q quit".to_string();
-                }
-            }
-            // Watchdog-specific hints
-            if w.as_any()
-                .downcast_ref::<crate::widgets::watchdog::WatchdogWidget>()
-                .is_some()
-            {
-                return "Tab next pane • Shift+Tab prev • ↑/↓/PgUp/PgDn/Home/End scroll (all panes) • f/End follow • s start/stop • r restart • esc back • q quit".to_string();
-            }
+    // The focused widget lists its own keys; the panel adds its keys after them unless the
+    // widget takes every key right now
+    let (hosted, global) = if matches!(state.panel_focus, PanelPane::B) {
+        match &ps.b_content {
+            PaneContent::Widget(w) => (Some(w), "space actions • esc back • q quit"),
+            _ => (None, ""),
         }
+    } else {
+        (
+            ps.a_content.as_ref(),
+            "Tab Pane B • space actions • esc back • q quit",
+        )
+    };
+    let Some(w) = hosted else {
+        return default;
+    };
+    let hints = w.hints();
+    match (hints.keys.is_empty(), hints.modal) {
+        (true, _) => global.to_string(),
+        (false, true) => hints.text(),
+        (false, false) => format!("{} • {global}", hints.text()),
     }
-    default
 }
 
 #[cfg(test)]
@@ -2151,6 +5690,1312 @@ mod tests {
         assert_eq!(compute_scroll_window_menu(12, 4, 4), (1, 5));
         assert_eq!(compute_scroll_window_menu(12, 11, 4), (8, 12));
     }
+
+    #[test]
+    fn pastes_only_reach_a_focused_text_input() {
+        use super::{input_key, AppState};
+        use crossterm::event::Event;
+        let mut st = AppState::default();
+        // Nothing collects text: the pasted characters must not run as shortcuts
+        assert!(input_key(&mut st, Event::Paste("qx".into())).is_none());
+        assert!(st.pending_keys.is_empty());
+        st.palette = Some(crate::widgets::palette::Palette::default());
+        let _ = input_key(&mut st, Event::Paste("de\nv".into()));
+        let typed: String = st
+            .pending_keys
+            .iter()
+            .filter_map(|k| match k.code {
+                crossterm::event::KeyCode::Char(c) => Some(c),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(typed, "dev");
+    }
+
+    #[test]
+    fn verbs_run_templated_commands_for_item_and_children() {
+        use super::{selected_verb, verb_hint, AppState};
+        let mut st = AppState::default();
+        st.config.menu = vec![crate::model::MenuItem {
+            id: "pods".into(),
+            title: "Pods".into(),
+            children: Some(vec![serde_json::json!({"id": "web-1", "ns": "prod env"})]),
+            verbs: Some(vec![crate::model::VerbSpec {
+                key: 'l',
+                title: "Logs".into(),
+                command: "app logs {id} -n {ns}".into(),
+                confirm: false,
+            }]),
+            ..Default::default()
+        }];
+        st.children.insert(
+            "menu:pods".into(),
+            st.config.menu[0].children.clone().unwrap(),
+        );
+        st.expanded.insert("menu:pods".into());
+        assert_eq!(verb_hint(&st), " • l logs");
+        assert!(selected_verb(&st, 'x').is_none());
+        st.selected = 1;
+        let mi = selected_verb(&st, 'l').expect("child verb");
+        assert_eq!(mi.command.as_deref(), Some("app logs web-1 -n 'prod env'"));
+        assert_eq!(mi.title, "web-1 — Logs");
+        assert_eq!(mi.pane_b_title.as_deref(), Some(mi.title.as_str()));
+    }
+
+    #[test]
+    fn k8s_sources_get_verbs_and_delete_waits_for_confirmation() {
+        use super::{selected_verb, verb_confirm_key, verb_confirms, verb_hint, AppState};
+        use crossterm::event::KeyCode;
+        let mut st = AppState::default();
+        let cfg: crate::model::AppConfig = serde_yaml::from_str(
+            "menu:\n  - id: pods\n    title: Pods\n    k8s: {kind: pods, namespace: prod, selector: app=api}\n",
+        )
+        .unwrap();
+        *st.config = cfg;
+        assert_eq!(
+            st.config.menu[0].command.as_deref(),
+            Some("kubectl get pods -o json -n prod -l 'app=api'")
+        );
+        let row = crate::services::k8s::row(serde_json::json!({
+            "metadata": {"name": "api-7f9", "namespace": "prod"},
+            "status": {"phase": "Running"}
+        }));
+        st.children.insert("menu:pods".into(), vec![row]);
+        st.expanded.insert("menu:pods".into());
+        st.selected = 1;
+        assert_eq!(verb_hint(&st), " • l logs • d describe • D delete");
+        assert!(!verb_confirms(&st, 'd'));
+        assert!(verb_confirms(&st, 'D'));
+        let mi = selected_verb(&st, 'D').unwrap();
+        assert_eq!(
+            mi.command.as_deref(),
+            Some("kubectl delete pod api-7f9 -n prod")
+        );
+        // Anything but y cancels
+        st.verb_confirm = Some(mi);
+        verb_confirm_key(&mut st, KeyCode::Esc);
+        assert!(st.verb_confirm.is_none());
+
+        let bad: Result<crate::model::AppConfig, _> =
+            serde_yaml::from_str("menu:\n  - id: x\n    title: X\n    k8s: {kind: ''}\n");
+        assert!(bad.unwrap_err().to_string().contains("k8s needs a `kind`"));
+    }
+
+    #[test]
+    fn hint_mode_labels_visible_menu_rows_and_jumps() {
+        use super::{hint_mode_key, start_hint_mode, AppState};
+        use crossterm::event::KeyCode;
+        let mut st = AppState::default();
+        st.config.menu = ["Group", "Second", "Third"]
+            .iter()
+            .enumerate()
+            .map(|(i, t)| crate::model::MenuItem {
+                id: format!("m{i}"),
+                title: t.to_string(),
+                children: (i == 0).then(|| vec![serde_json::json!({"title": "leaf"})]),
+                ..Default::default()
+            })
+            .collect();
+        // Only the first two rows fit the viewport
+        st.menu_viewport_h = 2;
+        start_hint_mode(&mut st);
+        let hm = st.hint_mode.as_ref().expect("hint mode active");
+        assert_eq!(hm.targets.len(), 2);
+        // A miss cancels without moving the selection
+        hint_mode_key(&mut st, KeyCode::Esc);
+        assert!(st.hint_mode.is_none());
+        // Typing a full label selects and enters the row
+        start_hint_mode(&mut st);
+        hint_mode_key(&mut st, KeyCode::Char('a'));
+        assert!(st.hint_mode.is_some());
+        hint_mode_key(&mut st, KeyCode::Char('a'));
+        assert!(st.hint_mode.is_none());
+        assert_eq!(st.selected, 0);
+        assert!(st.expanded.contains("menu:m0"));
+    }
+
+    #[test]
+    fn hint_mode_labels_result_items_and_opens_them() {
+        use super::{hint_mode_key, start_hint_mode, ui, AppState, PaneContent, PanelPane};
+        use super::{PanelState, View};
+        use crate::nav::hints::HintTarget;
+        use crate::widgets::result_viewer::ResultViewerWidget;
+        use crossterm::event::KeyCode;
+        let items: Vec<_> = (0..30)
+            .map(|i| serde_json::json!({"title": format!("pod-{i}")}))
+            .collect();
+        let mut st = AppState {
+            view: View::Panel,
+            panel_focus: PanelPane::B,
+            panel: Some(PanelState {
+                b_content: PaneContent::Widget(Box::new(ResultViewerWidget::new(
+                    "Pods",
+                    serde_json::Value::Array(items),
+                ))),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        st.config.banner_animation = Some("none".into());
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 20)).unwrap();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        start_hint_mode(&mut st);
+        let hm = st.hint_mode.as_ref().expect("hint mode active");
+        let items: Vec<usize> = hm
+            .targets
+            .iter()
+            .filter_map(|(_, t)| match t {
+                HintTarget::ResultItem(i) => Some(*i),
+                _ => None,
+            })
+            .collect();
+        // Only the items in view are labelled
+        assert_eq!(items.first(), Some(&0));
+        assert!(items.len() < 30, "{items:?}");
+        let label = hm.label_for(HintTarget::ResultItem(2)).unwrap().to_string();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains(&format!("{label}pod-2")), "{screen}");
+
+        for c in label.chars() {
+            hint_mode_key(&mut st, KeyCode::Char(c));
+        }
+        assert!(st.hint_mode.is_none());
+        let Some(PanelState {
+            b_content: PaneContent::Widget(w),
+            ..
+        }) = &mut st.panel
+        else {
+            panic!("no panel");
+        };
+        let rv = w.as_any().downcast_ref::<ResultViewerWidget>().unwrap();
+        assert!(rv.tree_active());
+        assert!(rv.hints.is_empty());
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Pods — /2"), "{screen}");
+    }
+
+    #[test]
+    fn hotkeys_open_their_item_and_conflicts_fail_the_load() {
+        use super::{hotkeys_active, run_effects, AppState, Effect, PanelPane, View};
+        let mut st = AppState::default();
+        *st.config = serde_yaml::from_str(
+            "menu:\n  - id: pods\n    title: Pods\n  - id: deploy\n    title: Deploy\n    hotkey: D\n    command: echo '{\"ok\":true}'\n",
+        )
+        .unwrap();
+        assert!(hotkeys_active(&mut st));
+        let idx = crate::nav::hotkeys::find(&st.config.menu, 'D').unwrap();
+        let id = st.config.menu[idx].id.clone();
+        run_effects(&mut st, vec![Effect::OpenItem(id)]);
+        assert_eq!(st.selected, 1);
+        // A widget in the focused pane keeps its keys
+        st.view = View::Panel;
+        st.panel_focus = PanelPane::B;
+        st.panel = Some(Default::default());
+        super::pane_b_replace_with_widget(
+            &mut st,
+            Box::new(crate::widgets::markdown::MarkdownWidget::from_text(
+                "notes", "# hi",
+            )),
+            false,
+        );
+        assert!(!hotkeys_active(&mut st));
+
+        let err = serde_yaml::from_str::<crate::model::AppConfig>(
+            "menu:\n  - id: pods\n    title: Pods\n    k8s: {kind: pods}\n  - id: docs\n    title: Docs\n    hotkey: d\n",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("menu 'docs': hotkey 'd' is a verb key of 'pods'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn footer_hints_follow_the_focused_widget() {
+        use super::{panel_help_text, AppState, PaneContent, PanelPane, View};
+        use crossterm::event::KeyCode;
+        let mut st = AppState {
+            view: View::Panel,
+            panel_focus: PanelPane::B,
+            panel: Some(Default::default()),
+            ..Default::default()
+        };
+        super::pane_b_replace_with_widget(
+            &mut st,
+            Box::new(crate::widgets::result_viewer::ResultViewerWidget::new(
+                "Pods",
+                serde_json::json!([{"name": "web"}]),
+            )),
+            false,
+        );
+        assert_eq!(
+            panel_help_text(&st),
+            "↑/↓ scroll • j raw • w wrap • t tree • / search • space actions • esc back • q quit"
+        );
+        // Typing a search query: only the keys that work now
+        if let Some(PaneContent::Widget(w)) = st.panel.as_mut().map(|p| &mut p.b_content) {
+            w.on_key(KeyCode::Char('/'));
+        }
+        assert_eq!(
+            panel_help_text(&st),
+            "type a query • Enter search • Esc cancel"
+        );
+        // Other widgets list their action keys
+        super::pane_b_replace_with_widget(
+            &mut st,
+            Box::new(crate::widgets::markdown::MarkdownWidget::from_text(
+                "notes", "# hi",
+            )),
+            false,
+        );
+        assert_eq!(
+            panel_help_text(&st),
+            "w toggle wrap • Ctrl+C copy • space actions • esc back • q quit"
+        );
+    }
+
+    #[test]
+    fn narrow_layout_applies_below_threshold_only() {
+        use super::{narrow_panel_layout, AppState, NarrowLayout};
+        let mut st = AppState::default();
+        // No rules configured: never switch
+        assert_eq!(narrow_panel_layout(&st, 40), None);
+        st.config.responsive = Some(crate::model::ResponsiveConfig {
+            narrow_width: Some(90),
+            narrow_layout: Some("vertical".into()),
+        });
+        assert_eq!(narrow_panel_layout(&st, 89), Some(NarrowLayout::Vertical));
+        assert_eq!(narrow_panel_layout(&st, 90), None);
+        // Per-screen override wins over the global template
+        st.panel_narrow_layout = Some("drawer".into());
+        assert_eq!(narrow_panel_layout(&st, 60), Some(NarrowLayout::Drawer));
+        st.panel_narrow_layout = Some("none".into());
+        assert_eq!(narrow_panel_layout(&st, 60), None);
+    }
+
+    #[test]
+    fn splash_replaces_ui_until_its_last_tick() {
+        use super::{ui, AppState};
+        let mut st = AppState {
+            splash_until_tick: 10,
+            tick: 4,
+            ..Default::default()
+        };
+        st.config.banner_animation = Some("none".into());
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(40, 12)).unwrap();
+        let screen = |term: &ratatui::Terminal<ratatui::backend::TestBackend>| {
+            let buf = term.backend().buffer();
+            buf.content().iter().map(|c| c.symbol()).collect::<String>()
+        };
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        assert!(screen(&term).contains("chi-tui"));
+        assert!(!screen(&term).contains("[F1] Main"));
+        st.tick = 10;
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        assert!(screen(&term).contains("[F1] Main"));
+    }
+
+    #[test]
+    fn notes_are_edited_shown_and_removed_from_the_menu() {
+        use super::{note_prompt_key, start_note_prompt, ui, AppState};
+        use crossterm::event::KeyCode;
+        let mut st = AppState::default();
+        st.config.banner_animation = Some("none".into());
+        st.config.menu = vec![crate::model::MenuItem {
+            id: "pods".into(),
+            title: "Pods".into(),
+            ..Default::default()
+        }];
+        start_note_prompt(&mut st);
+        assert!(st.note_prompt.as_ref().unwrap().editing);
+        for code in [KeyCode::Char('o'), KeyCode::Char('k'), KeyCode::Enter] {
+            note_prompt_key(&mut st, code);
+        }
+        assert!(st.note_prompt.is_none());
+        assert_eq!(st.notes.get("menu:pods").unwrap().text, "ok");
+
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 16)).unwrap();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Pods ✎"), "{screen}");
+
+        // A saved note opens in the popup; 'd' deletes it
+        start_note_prompt(&mut st);
+        let prompt = st.note_prompt.as_ref().unwrap();
+        assert!(!prompt.editing);
+        assert_eq!(prompt.input, "ok");
+        note_prompt_key(&mut st, KeyCode::Char('d'));
+        assert!(st.note_prompt.is_none());
+        assert!(!st.notes.has("menu:pods"));
+    }
+
+    #[test]
+    fn tabs_are_reordered_for_the_session_and_hidden_ones_open_from_the_palette() {
+        use super::{palette_key, ui, AppState};
+        use crate::model::HorizontalMenuItem;
+        use crate::widgets::horizontal_menu::{move_tab, tab_for_key};
+        use crossterm::event::KeyCode;
+        let tab = |id: &str, hidden| HorizontalMenuItem {
+            id: id.into(),
+            title: id.to_uppercase(),
+            config: None,
+            hidden,
+        };
+        let mut st = AppState::default();
+        st.config.banner_animation = Some("none".into());
+        st.config.horizontal_menu =
+            vec![tab("home", false), tab("admin", true), tab("logs", false)];
+        // The hidden tab takes no F-key
+        assert_eq!(tab_for_key(&st, 2), Some(2));
+        assert_eq!(tab_for_key(&st, 3), None);
+
+        super::open_tab(&mut st, 2);
+        assert!(move_tab(&mut st, -1));
+        assert!(!move_tab(&mut st, -1));
+        assert_eq!(st.tab_order, ["logs", "home"]);
+        assert_eq!(tab_for_key(&st, 1), Some(2));
+        assert_eq!(tab_for_key(&st, 2), Some(0));
+
+        st.palette = Some(crate::widgets::palette::Palette::new(&st));
+        for c in "adm".chars() {
+            palette_key(&mut st, KeyCode::Char(c));
+        }
+        palette_key(&mut st, KeyCode::Enter);
+        assert!(st.palette.is_none());
+        assert_eq!(st.horizontal_tab_index, 1);
+
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 20)).unwrap();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(
+            screen.contains("[F1] LOGS  │  [F2] HOME  │  [·] ADMIN"),
+            "{screen}"
+        );
+    }
+
+    #[test]
+    fn palette_jumps_to_a_collapsed_child() {
+        use super::{flatten_nodes, palette_key, AppState, FlatNode};
+        use crate::model::MenuItem;
+        use crossterm::event::KeyCode;
+        use serde_json::json;
+        let mut st = AppState::default();
+        st.config.menu = ["pods", "nodes"]
+            .map(|id| MenuItem {
+                id: id.into(),
+                title: id.into(),
+                ..Default::default()
+            })
+            .to_vec();
+        st.children.insert(
+            "menu:nodes".into(),
+            vec![
+                json!({"id": "n1"}),
+                json!({"id": "n2", "title": "worker-2"}),
+            ],
+        );
+        st.palette = Some(crate::widgets::palette::Palette::new(&st));
+        for c in "wrk2".chars() {
+            palette_key(&mut st, KeyCode::Char(c));
+        }
+        palette_key(&mut st, KeyCode::Enter);
+        assert!(st.palette.is_none());
+        assert!(st.expanded.contains("menu:nodes"));
+        let nodes = flatten_nodes(&st);
+        assert!(
+            matches!(&nodes[st.selected], FlatNode::Child { key, .. } if key == "menu:nodes/n2")
+        );
+    }
+
+    #[test]
+    fn finished_streams_are_kept_on_the_results_screen() {
+        use super::{open_results, results_screen_key, ui, AppState, View};
+        use crate::app::{update, AppMsg};
+        use crossterm::event::KeyCode;
+        use serde_json::json;
+        let mut st = AppState::default();
+        st.config.banner_animation = Some("none".into());
+        for (title, replicas) in [("Deploy v1", 3), ("Deploy v2", 5)] {
+            st.stream_title = Some(title.into());
+            st.stream_source = Some("app get-deploy".into());
+            update(
+                &mut st,
+                AppMsg::StreamDone {
+                    result: Some(json!({"replicas": replicas})),
+                    err: None,
+                },
+            );
+        }
+        assert_eq!(st.results.len(), 2);
+
+        open_results(&mut st);
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(90, 20)).unwrap();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Results (2)"), "{screen}");
+        assert!(
+            screen.contains("Deploy v2  1 keys  $ app get-deploy"),
+            "{screen}"
+        );
+
+        // Newest first: without a mark, D compares the selected result with the one before
+        results_screen_key(&mut st, KeyCode::Char('D'));
+        assert_eq!(st.results_screen.diff, Some((1, 2)));
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("~ .replicas  3 → 5"), "{screen}");
+        results_screen_key(&mut st, KeyCode::Esc);
+        assert!(st.results_screen.diff.is_none());
+
+        // Enter re-opens the older one in the JSON view
+        results_screen_key(&mut st, KeyCode::Down);
+        results_screen_key(&mut st, KeyCode::Enter);
+        assert_eq!(st.view, View::Json);
+        assert_eq!(
+            st.json_viewer.as_ref().map(|v| v.value().clone()),
+            Some(json!({"replicas": 3}))
+        );
+    }
+
+    #[test]
+    fn quit_asks_first_while_jobs_are_scheduled() {
+        use super::{quit_confirm_key, request_quit, ui, AppState};
+        use crossterm::event::KeyCode;
+        let mut st = AppState::default();
+        st.config.banner_animation = Some("none".into());
+        // auto: nothing running, quit right away
+        assert!(request_quit(&mut st));
+        st.scheduler
+            .add(
+                "Pods",
+                "app pods",
+                None,
+                "10m",
+                std::time::SystemTime::now(),
+            )
+            .unwrap();
+        assert!(!request_quit(&mut st));
+        assert_eq!(
+            st.quit_confirm.as_deref(),
+            Some(&["1 scheduled job(s)".to_string()][..])
+        );
+
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 20)).unwrap();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Quitting now will stop:"), "{screen}");
+        assert!(screen.contains("• 1 scheduled job(s)"), "{screen}");
+
+        assert!(!quit_confirm_key(&mut st, KeyCode::Esc));
+        assert!(st.quit_confirm.is_none());
+        assert!(!request_quit(&mut st));
+        assert!(quit_confirm_key(&mut st, KeyCode::Char('y')));
+
+        st.confirm_quit = "never";
+        assert!(request_quit(&mut st));
+        // always: asks even with nothing to stop
+        st.scheduler.jobs.clear();
+        st.config.confirm_quit = Some("always".into());
+        st.confirm_quit = st.config.confirm_quit();
+        assert!(!request_quit(&mut st));
+        assert_eq!(st.quit_confirm.as_deref(), Some(&[][..]));
+    }
+
+    #[test]
+    fn watches_follow_a_result_and_alert_when_met() {
+        use super::{start_watch_prompt, ui, watch_prompt_key, AppState, ToastLevel, View};
+        use crate::app::{update, AppMsg};
+        use crossterm::event::KeyCode;
+        use serde_json::json;
+        let mut st = AppState {
+            view: View::Json,
+            stream_source: Some("app get-deploy".into()),
+            json_viewer: Some(crate::widgets::result_viewer::ResultViewerWidget::new(
+                "JSON Output",
+                json!({"data": {"replicas": 3}}),
+            )),
+            ..Default::default()
+        };
+        st.config.banner_animation = Some("none".into());
+        start_watch_prompt(&mut st);
+        for c in "data.replicas > 5".chars() {
+            watch_prompt_key(&mut st, KeyCode::Char(c));
+        }
+        watch_prompt_key(&mut st, KeyCode::Enter);
+        assert!(st.watch_prompt.is_none());
+        assert_eq!(st.watches.iter().count(), 1);
+
+        let _ = update(
+            &mut st,
+            AppMsg::StreamDone {
+                result: Some(json!({"data": {"replicas": 8}})),
+                err: None,
+            },
+        );
+        let toast = st.toast.as_ref().unwrap();
+        assert!(matches!(toast.level, ToastLevel::Error));
+        assert_eq!(toast.text, "Watch: .data.replicas > 5 (8)");
+
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 20)).unwrap();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("WATCH"), "{screen}");
+        assert!(screen.contains("! .data.replicas > 5 8 ▲"), "{screen}");
+
+        // The same expression again stops watching
+        start_watch_prompt(&mut st);
+        for c in "data.replicas > 5".chars() {
+            watch_prompt_key(&mut st, KeyCode::Char(c));
+        }
+        watch_prompt_key(&mut st, KeyCode::Enter);
+        assert!(st.watches.is_empty());
+    }
+
+    #[test]
+    fn panicking_pane_b_widget_is_replaced_by_an_error_view() {
+        use super::{recover_widget, ui, AppState, PaneContent, PanelState, View};
+        use crate::widgets::error_boundary::ErrorWidget;
+        use crate::widgets::Widget;
+
+        struct Broken;
+        impl Widget for Broken {
+            fn render(
+                &mut self,
+                _f: &mut ratatui::Frame,
+                _area: ratatui::layout::Rect,
+                _focused: bool,
+                _tick: u64,
+            ) {
+                panic!("bad column width");
+            }
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+        }
+
+        crate::widgets::error_boundary::install_hook();
+        let mut st = AppState {
+            view: View::Panel,
+            panel: Some(PanelState {
+                b_content: PaneContent::Widget(Box::new(Broken)),
+                ..Default::default()
+            }),
+            pane_b_title: Some("Disks".into()),
+            ..Default::default()
+        };
+        st.config.banner_animation = Some("none".into());
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 20)).unwrap();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Disks — crashed"), "{screen}");
+        let Some(PanelState {
+            b_content: PaneContent::Widget(w),
+            ..
+        }) = &st.panel
+        else {
+            panic!("no panel");
+        };
+        let err = w.as_any().downcast_ref::<ErrorWidget>().unwrap();
+        assert!(err.message().starts_with("bad column width"));
+        assert!(!recover_widget(&mut st));
+    }
+
+    #[test]
+    fn panicking_pane_a_key_handler_leaves_pane_b_alone() {
+        use super::{
+            pane_a_key, recover_widget, AppState, PaneContent, PanelPane, PanelState, View,
+        };
+        use crate::app::Effect;
+        use crate::widgets::error_boundary::ErrorWidget;
+        use crate::widgets::json_viewer::JsonViewerWidget;
+        use crate::widgets::Widget;
+        use crossterm::event::KeyCode;
+
+        struct Broken;
+        impl Widget for Broken {
+            fn render(
+                &mut self,
+                _f: &mut ratatui::Frame,
+                _area: ratatui::layout::Rect,
+                _focused: bool,
+                _tick: u64,
+            ) {
+            }
+            fn on_key(&mut self, _key: KeyCode) -> Vec<Effect> {
+                panic!("bad selection");
+            }
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+        }
+
+        crate::widgets::error_boundary::install_hook();
+        let mut st = AppState {
+            view: View::Panel,
+            panel_focus: PanelPane::A,
+            panel: Some(PanelState {
+                a_content: Some(Box::new(Broken)),
+                b_content: PaneContent::Widget(Box::new(JsonViewerWidget::from_text(
+                    "Disks", "sda",
+                ))),
+                ..Default::default()
+            }),
+            pane_b_title: Some("Disks".into()),
+            ..Default::default()
+        };
+        pane_a_key(&mut st, KeyCode::Down);
+        let ps = st.panel.as_ref().unwrap();
+        let err = ps.a_content.as_ref().unwrap();
+        let err = err.as_any().downcast_ref::<ErrorWidget>().unwrap();
+        assert!(err.message().starts_with("bad selection"));
+        let PaneContent::Widget(b) = &ps.b_content else {
+            panic!("Pane B content replaced");
+        };
+        let jv = b.as_any().downcast_ref::<JsonViewerWidget>().unwrap();
+        assert_eq!(jv.text, "sda");
+        // Nothing is left over for the Pane B recovery before the next draw
+        assert!(!recover_widget(&mut st));
+    }
+
+    #[test]
+    fn reduced_motion_draws_the_same_frame_over_time() {
+        use super::{ui, AppState};
+        use crate::visuals::AnimClock;
+        use std::time::Duration;
+        let screen_at = |reduced: bool, ago: u64| {
+            // Nothing is loading: no spinner, only the effects change
+            let mut st = AppState {
+                animations_enabled: true,
+                reduced_motion: reduced,
+                anim_clock: AnimClock::started(Duration::from_millis(ago)),
+                ..Default::default()
+            };
+            let mut term =
+                ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 20)).unwrap();
+            term.draw(|f| ui(f, &mut st)).unwrap();
+            term.backend().buffer().clone()
+        };
+        // Frames follow the wall clock: 1.2s is six frames, however few loop ticks ran
+        assert_eq!(AnimClock::started(Duration::from_millis(1_250)).frame(), 6);
+        assert_ne!(screen_at(false, 0), screen_at(false, 1_400));
+        assert_eq!(screen_at(true, 0), screen_at(true, 1_400));
+    }
+
+    #[test]
+    fn frames_over_the_budget_are_logged() {
+        use super::AppState;
+        use std::time::{Duration, Instant};
+        let mut st = AppState::default();
+        let slow = Instant::now() - Duration::from_millis(40);
+        st.check_budget("draw", slow);
+        assert_eq!(st.slow_frames, 0);
+        st.frame_budget = Some(Duration::from_millis(20));
+        st.check_budget("draw", Instant::now());
+        st.check_budget("key", slow);
+        assert_eq!(st.slow_frames, 1);
+        let last = st.debug_log.back().unwrap();
+        assert!(last.starts_with("slow frame: key took"), "{last}");
+        assert!(last.ends_with("(budget 20ms)"), "{last}");
+    }
+
+    #[test]
+    fn only_a_missing_config_offers_the_setup_wizard() {
+        use super::{find_config, read_entry_config, wants_setup, ConfigNotFound};
+        let dir = std::env::temp_dir().join(format!("chi-setup-{}", std::process::id()));
+        let project = dir.join("project");
+        std::fs::create_dir_all(&project).unwrap();
+
+        let missing = find_config(None, &project, None).unwrap_err();
+        assert!(missing.is::<ConfigNotFound>());
+        assert!(wants_setup(&missing, false));
+        // CHI_TUI_CONFIG_DIR set: the user chose the place, no wizard
+        assert!(!wants_setup(&missing, true));
+
+        std::fs::create_dir_all(project.join(".tui")).unwrap();
+        std::fs::write(project.join(".tui/chi-index.yaml"), "menu: [\n").unwrap();
+        let entry = find_config(None, &project, None).unwrap();
+        let broken = read_entry_config(&entry).unwrap_err();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(!broken.is::<ConfigNotFound>());
+        assert!(!wants_setup(&broken, false));
+        assert!(format!("{broken:#}").contains("parsing"), "{broken:#}");
+    }
+
+    #[test]
+    fn switching_back_to_a_tab_restores_its_menu_and_view() {
+        use super::{open_tab, AppState, LeaveHook, RetryTarget, View};
+        let dir = std::env::temp_dir().join(format!("chi-tabs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (a, b) = (dir.join("a.yaml"), dir.join("b.yaml"));
+        let tabs = format!(
+            "horizontal_menu:\n  - {{ id: home, title: Home }}\n  - {{ id: a, title: A, config: {} }}\n  - {{ id: b, title: B, config: {} }}\n",
+            a.display(),
+            b.display()
+        );
+        let items = "menu:\n  - { id: pods, title: Pods }\n  - { id: jobs, title: Jobs }\n";
+        std::fs::write(&a, format!("{tabs}{items}")).unwrap();
+        std::fs::write(&b, format!("{tabs}{items}")).unwrap();
+        let mut st = AppState::default();
+        st.config.banner_animation = Some("none".into());
+        st.config.horizontal_menu =
+            serde_yaml::from_str::<crate::model::AppConfig>(&format!("{tabs}menu: []\n"))
+                .unwrap()
+                .horizontal_menu;
+
+        open_tab(&mut st, 1);
+        st.selected = 1;
+        st.expanded.insert("menu:jobs".into());
+        st.children.insert(
+            "menu:jobs".into(),
+            vec![serde_json::json!({"id": "nightly"})],
+        );
+        st.view = View::Json;
+        st.last_json_pretty = Some("{}".into());
+        st.pane_origin = Some(RetryTarget::Menu(crate::model::MenuItem {
+            id: "jobs".into(),
+            ..Default::default()
+        }));
+        st.item_leave_hook = Some(LeaveHook {
+            label: "leave Jobs".into(),
+            cmdline: "app track jobs".into(),
+            opened: true,
+        });
+
+        // A tab opened for the first time starts fresh
+        open_tab(&mut st, 2);
+        assert_eq!(st.selected, 0);
+        assert!(st.expanded.is_empty() && st.children.is_empty());
+        assert_eq!(st.view, View::Menu);
+        assert!(st.last_json_pretty.is_none());
+        // Reloads and leave hooks of the parked item do not act on this tab
+        assert!(st.pane_origin.is_none() && st.item_leave_hook.is_none());
+        assert!(!st.debug_log.iter().any(|l| l.contains("leave Jobs")));
+        st.selected = 1;
+
+        open_tab(&mut st, 1);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(st.selected, 1);
+        assert!(st.expanded.contains("menu:jobs"));
+        assert_eq!(st.children["menu:jobs"].len(), 1);
+        assert_eq!(st.view, View::Json);
+        assert_eq!(st.last_json_pretty.as_deref(), Some("{}"));
+        assert!(matches!(&st.pane_origin, Some(RetryTarget::Menu(mi)) if mi.id == "jobs"));
+        assert_eq!(st.item_leave_hook.as_ref().unwrap().label, "leave Jobs");
+        // The tab left behind is parked under its config path
+        assert_eq!(st.tab_states[&b.display().to_string()].selected, 1);
+    }
+
+    #[test]
+    fn debug_console_takes_focus_filters_and_resizes() {
+        use super::{debug_console_key, debug_height, toggle_debug_console, ui, AppState};
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let mut st = AppState::default();
+        st.config.banner_animation = Some("none".into());
+        st.dbg("load menu: pods");
+        toggle_debug_console(&mut st);
+        assert!(st.debug_focused);
+        assert_eq!(debug_height(&st), 12);
+        // Lines logged before and after focusing both reach the console
+        st.dbg("load failed: jobs");
+        for c in "/failed".chars() {
+            debug_console_key(&mut st, key(c));
+        }
+        debug_console_key(&mut st, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(
+            st.debug_console.as_ref().unwrap().text(),
+            "load failed: jobs"
+        );
+        debug_console_key(&mut st, key('+'));
+        assert_eq!(st.debug_h, Some(14));
+        for _ in 0..10 {
+            debug_console_key(&mut st, key('-'));
+        }
+        assert_eq!(st.debug_h, Some(3));
+        debug_console_key(&mut st, key('+'));
+
+        let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 30)).unwrap();
+        term.draw(|f| ui(f, &mut st)).unwrap();
+        let screen: String = term
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(
+            screen.contains("Debug (1 lines, following) /failed"),
+            "{screen}"
+        );
+
+        debug_console_key(&mut st, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!st.debug_focused);
+        // The size set while focused stays for the strip
+        assert_eq!(debug_height(&st), 5);
+    }
+
+    #[test]
+    fn ctrl_t_cycles_theme_presets() {
+        use super::{next_theme, AppState};
+        let mut st = AppState {
+            entry_theme: Some(crate::model::ThemeConfig {
+                primary: Some("#ff8800".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        // Not the global theme: other tests render with it
+        assert_eq!(next_theme(&mut st), "light");
+        assert_eq!(st.theme.mode, crate::theme::ThemeMode::Light);
+        assert_eq!(next_theme(&mut st), "high-contrast");
+        assert_eq!(next_theme(&mut st), "dark");
+        // Back on the configured preset with its colors
+        assert_eq!(st.theme_index, 0);
+        assert_eq!(st.theme.primary, ratatui::style::Color::Rgb(0xff, 0x88, 0));
+    }
+
+    #[test]
+    fn auto_theme_follows_a_light_terminal_background() {
+        use super::{apply_terminal_background, AppState};
+        use crate::services::terminal_bg::Background;
+        let mut st = AppState {
+            entry_theme: Some(crate::model::ThemeConfig {
+                preset: Some("auto".into()),
+                accent: Some("cyan".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        apply_terminal_background(&mut st, Some(Background::Dark));
+        assert_eq!(st.theme.mode, crate::theme::ThemeMode::Dark);
+        apply_terminal_background(&mut st, Some(Background::Light));
+        assert_eq!(st.theme.mode, crate::theme::ThemeMode::Light);
+        assert_eq!(st.theme.accent, ratatui::style::Color::Cyan);
+        assert_eq!(st.theme_index, 1);
+
+        // A preset set in the config wins
+        let mut st = AppState {
+            entry_theme: Some(crate::model::ThemeConfig {
+                preset: Some("dark".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        apply_terminal_background(&mut st, Some(Background::Light));
+        assert_eq!(st.theme.mode, crate::theme::ThemeMode::Dark);
+    }
+
+    #[test]
+    fn workspaces_resolve_against_the_entry_dir() {
+        use super::{resolve_workspaces, Workspace};
+        let root = std::env::temp_dir().join(format!("chi-ws-{}", std::process::id()));
+        let base = root.join("platform");
+        let nested = root.join("svc-b/.tui");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("chi-index.yaml"), "menu: []\n").unwrap();
+        let mut cfg: crate::model::AppConfig = serde_yaml::from_str(
+            "header: Platform\nworkspaces:\n  - { title: A, dir: ../svc-a }\n  - { title: B, dir: ../svc-b }\nmenu: []\n",
+        )
+        .unwrap();
+        let ws = resolve_workspaces(&cfg, &base);
+        let _ = std::fs::remove_dir_all(&root);
+        assert_eq!(
+            ws,
+            vec![
+                Workspace {
+                    title: "Platform".into(),
+                    dir: base.clone()
+                },
+                Workspace {
+                    title: "A".into(),
+                    dir: base.join("../svc-a")
+                },
+                // A project root resolves to its .tui dir
+                Workspace {
+                    title: "B".into(),
+                    dir: base.join("../svc-b/.tui")
+                },
+            ]
+        );
+        cfg.workspaces.clear();
+        assert!(resolve_workspaces(&cfg, &base).is_empty());
+    }
+
+    #[test]
+    fn action_bar_lists_and_runs_widget_actions() {
+        use super::{action_bar_key, open_action_bar, AppState, View};
+        use crate::widgets::{ActionKind, Widget};
+        use crossterm::event::KeyCode;
+        let mut state = AppState {
+            view: View::Json,
+            json_viewer: Some(crate::widgets::result_viewer::ResultViewerWidget::new(
+                "Result",
+                serde_json::json!([{"id": 1}, {"id": 2}]),
+            )),
+            ..Default::default()
+        };
+        open_action_bar(&mut state);
+        let labels: Vec<&str> = state
+            .action_bar
+            .as_ref()
+            .map(|b| b.actions.iter().map(|a| a.label).collect())
+            .unwrap_or_default();
+        // No source command: nothing to refresh
+        assert_eq!(
+            labels,
+            [
+                "Toggle raw JSON",
+                "Toggle wrap",
+                "Toggle tree view",
+                "Search items",
+                "Copy",
+                "Open in editor"
+            ]
+        );
+        action_bar_key(&mut state, KeyCode::Down);
+        action_bar_key(&mut state, KeyCode::Down);
+        action_bar_key(&mut state, KeyCode::Down);
+        action_bar_key(&mut state, KeyCode::Enter);
+        assert!(state.action_bar.is_none());
+        let viewer = state.json_viewer.as_ref().unwrap();
+        assert!(viewer.search_active());
+        assert!(viewer
+            .actions()
+            .iter()
+            .any(|a| a.kind == ActionKind::Key(KeyCode::Char('/'))));
+
+        // Copying structured data asks for a format first
+        super::run_widget_action(&mut state, ActionKind::Copy);
+        let bar = state.action_bar.as_ref().expect("format picker");
+        assert_eq!(bar.title, "Copy");
+        assert_eq!(
+            bar.actions.iter().map(|a| a.label).collect::<Vec<_>>(),
+            [
+                "Copy as JSON",
+                "Copy as YAML",
+                "Copy as CSV",
+                "Copy as markdown table"
+            ]
+        );
+    }
+
+    #[test]
+    fn streams_started_in_panel_mode_run_in_pane_b() {
+        use super::{run_effects, AppState, PaneContent, PanelState, View};
+        use crate::app::{update, AppMsg, Effect};
+        use crate::widgets::result_viewer::ResultViewerWidget;
+        use crate::widgets::stream_viewer::StreamViewerWidget;
+        let mut st = AppState {
+            view: View::Panel,
+            panel: Some(PanelState::default()),
+            ..Default::default()
+        };
+        let run = |st: &mut AppState| {
+            run_effects(
+                st,
+                vec![Effect::RunStream {
+                    cmdline: "app deploy".into(),
+                    title: "Deploy".into(),
+                }],
+            )
+        };
+        let pane_b = |st: &AppState| match &st.panel.as_ref().unwrap().b_content {
+            PaneContent::Widget(w) => (
+                st.panel.as_ref().unwrap().b_history.len(),
+                w.as_any()
+                    .downcast_ref::<StreamViewerWidget>()
+                    .map(|s| s.running()),
+                w.as_any()
+                    .downcast_ref::<ResultViewerWidget>()
+                    .and_then(|rv| rv.source().map(String::from)),
+            ),
+            _ => panic!("expected a widget in Pane B"),
+        };
+        run(&mut st);
+        assert_eq!(pane_b(&st), (1, Some(true), None));
+        let _ = update(
+            &mut st,
+            AppMsg::StreamProgress {
+                text: Some("build".into()),
+                percent: Some(50.0),
+            },
+        );
+        let _ = update(
+            &mut st,
+            AppMsg::StreamDone {
+                result: Some(serde_json::json!({"ok": true})),
+                err: None,
+            },
+        );
+        // The result replaces the stream viewer; the screen stays on the panel
+        assert_eq!(pane_b(&st), (1, None, Some("app deploy".into())));
+        assert_eq!(st.view, View::Panel);
+        assert!(st.json_viewer.is_none());
+
+        // A failure stays on the viewer
+        run(&mut st);
+        let _ = update(
+            &mut st,
+            AppMsg::StreamDone {
+                result: None,
+                err: Some("exit code 2".into()),
+            },
+        );
+        assert_eq!(pane_b(&st), (2, Some(false), None));
+        assert_eq!(st.view, View::Panel);
+    }
+
+    #[test]
+    fn leave_hooks_fire_when_the_item_view_closes_or_the_screen_changes() {
+        use super::{enter_screen, run_leave_hooks, AppState, LeaveHook, View};
+        let mut st = AppState::default();
+        let logged = |st: &AppState, text: &str| st.debug_log.iter().any(|l| l.contains(text));
+        st.config.on_enter_cmd = Some("app warm-cache".into());
+        st.config.on_leave_cmd = Some("app flush".into());
+        enter_screen(&mut st);
+        assert!(logged(&st, "hook enter screen main: app warm-cache"));
+
+        st.item_leave_hook = Some(LeaveHook {
+            label: "leave Pods".into(),
+            cmdline: "app track pods".into(),
+            opened: false,
+        });
+        // Still on the menu (the stream has not opened its view yet)
+        run_leave_hooks(&mut st);
+        assert!(st.item_leave_hook.is_some());
+        st.view = View::Json;
+        run_leave_hooks(&mut st);
+        assert!(st.item_leave_hook.as_ref().unwrap().opened);
+        st.view = View::Menu;
+        run_leave_hooks(&mut st);
+        assert!(st.item_leave_hook.is_none());
+        assert!(logged(&st, "hook leave Pods: app track pods"));
+
+        // Loading another screen leaves this one
+        st.config.on_enter_cmd = None;
+        st.current_config_path = Some("screens/k8s.yaml".into());
+        enter_screen(&mut st);
+        assert!(logged(&st, "hook leave screen main: app flush"));
+        assert_eq!(
+            st.screen_leave_hook.as_ref().unwrap().label,
+            "leave screen screens/k8s.yaml"
+        );
+    }
+
+    #[test]
+    fn esc_cancels_a_running_stream() {
+        use super::{cancel_stream, run_effects, AppState, PaneContent, PanelState, View};
+        use crate::app::Effect;
+        use crate::widgets::stream_viewer::StreamViewerWidget;
+        let (p_tx, _p_rx) = std::sync::mpsc::channel();
+        let mut st = AppState {
+            view: View::Panel,
+            panel: Some(PanelState::default()),
+            p_tx: Some(p_tx),
+            ..Default::default()
+        };
+        run_effects(
+            &mut st,
+            vec![Effect::RunStream {
+                cmdline: "app deploy".into(),
+                title: "Deploy".into(),
+            }],
+        );
+        assert!(st.stream_handle.is_some());
+        let handle = st.stream_handle.clone().unwrap();
+        st.status_text = Some("build".into());
+        cancel_stream(&mut st);
+        assert!(handle.cancelled());
+        assert!(st.stream_handle.is_none());
+        assert!(st.status_text.is_none());
+        let running = match &st.panel.as_ref().unwrap().b_content {
+            PaneContent::Widget(w) => w
+                .as_any()
+                .downcast_ref::<StreamViewerWidget>()
+                .map(|s| s.running()),
+            _ => None,
+        };
+        assert_eq!(running, Some(false));
+        assert!(st
+            .toast
+            .as_ref()
+            .is_some_and(|t| t.text == "Cancelled: Deploy"));
+    }
+
+    #[test]
+    fn a_stream_ending_in_another_tab_finishes_its_parked_viewer() {
+        use super::{run_effects, AppState, PaneContent, PanelState, TabState, View};
+        use crate::app::{update, AppMsg, Effect};
+        use crate::widgets::stream_viewer::StreamViewerWidget;
+        let (p_tx, _p_rx) = std::sync::mpsc::channel();
+        let mut st = AppState {
+            view: View::Panel,
+            panel: Some(PanelState::default()),
+            p_tx: Some(p_tx),
+            ..Default::default()
+        };
+        let stream = |title: &str| Effect::RunStream {
+            cmdline: "true".into(),
+            title: title.into(),
+        };
+        let viewer = |ts: &TabState| match ts.panel.as_ref().map(|ps| &ps.b_content) {
+            Some(PaneContent::Widget(w)) => Some(w.as_any().is::<StreamViewerWidget>()),
+            _ => None,
+        };
+        run_effects(&mut st, vec![stream("Deploy")]);
+        // Switch tabs while it runs: the stream stays with the tab it started in
+        let parked = TabState::take(&mut st);
+        assert!(parked.stream_in_panel && !st.stream_in_panel);
+        st.tab_states.insert("tabs/a.yaml".into(), parked);
+
+        let effects = update(
+            &mut st,
+            AppMsg::StreamDone {
+                result: Some(serde_json::json!({"deployed": true})),
+                err: None,
+            },
+        );
+        run_effects(&mut st, effects);
+        // The tab shown is left alone; the parked viewer gave way to the result
+        assert_eq!(st.view, View::Menu);
+        let parked = &st.tab_states["tabs/a.yaml"];
+        assert!(!parked.stream_in_panel);
+        assert_eq!(viewer(parked), Some(false));
+        assert!(st.toast.as_ref().is_some_and(|t| t.text.contains("Deploy")));
+
+        // A cancelled stream stops its parked viewer too
+        let tab = st.tab_states.remove("tabs/a.yaml").unwrap();
+        tab.put_back(&mut st);
+        run_effects(&mut st, vec![stream("Deploy again")]);
+        let parked = TabState::take(&mut st);
+        st.tab_states.insert("tabs/a.yaml".into(), parked);
+        super::cancel_stream(&mut st);
+        let parked = &st.tab_states["tabs/a.yaml"];
+        assert!(!parked.stream_in_panel);
+        let running = match parked.panel.as_ref().map(|ps| &ps.b_content) {
+            Some(PaneContent::Widget(w)) => w
+                .as_any()
+                .downcast_ref::<StreamViewerWidget>()
+                .map(|s| s.running()),
+            _ => None,
+        };
+        assert_eq!(running, Some(false));
+    }
+
+    #[test]
+    fn a_second_stream_stops_the_first_and_drops_its_events() {
+        use super::{apply_progress, drain_progress, run_effects, AppState, ProgressEvent};
+        use super::{PaneContent, PanelState, View};
+        use crate::app::Effect;
+        use crate::widgets::stream_viewer::StreamViewerWidget;
+        let (p_tx, p_rx) = std::sync::mpsc::channel();
+        let mut st = AppState {
+            view: View::Panel,
+            panel: Some(PanelState::default()),
+            p_tx: Some(p_tx.clone()),
+            p_rx: Some(p_rx),
+            ..Default::default()
+        };
+        let stream = |cmdline: &str, title: &str| Effect::RunStream {
+            cmdline: cmdline.into(),
+            title: title.into(),
+        };
+        run_effects(&mut st, vec![stream("sleep 30", "Deploy")]);
+        let first = st.stream_handle.clone().unwrap();
+        let first_id = st.stream_id;
+        run_effects(&mut st, vec![stream("sleep 30", "Deploy again")]);
+        assert!(first.cancelled());
+        assert!(!st.stream_handle.as_ref().unwrap().cancelled());
+        assert_ne!(st.stream_id, first_id);
+
+        // A late event of the first stream neither ends the second nor reaches its viewer
+        p_tx.send(ProgressEvent {
+            stream: first_id,
+            text: None,
+            percent: None,
+            done: true,
+            result: Some(serde_json::json!({"ok": true})),
+            err: None,
+        })
+        .unwrap();
+        for ev in drain_progress(&mut st) {
+            apply_progress(&mut st, ev);
+        }
+        assert!(st.stream_handle.is_some());
+        let running = match &st.panel.as_ref().unwrap().b_content {
+            PaneContent::Widget(w) => w
+                .as_any()
+                .downcast_ref::<StreamViewerWidget>()
+                .map(|s| s.running()),
+            _ => None,
+        };
+        assert_eq!(running, Some(true));
+        super::cancel_stream(&mut st);
+    }
 }
 
 #[cfg(test)]
@@ -6,6 +6,38 @@ use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
 
 use crate::theme::Theme;
 
+// Length of one animation frame (the nominal UI tick)
+pub const FRAME_MS: u64 = 200;
+
+// Wall-clock source of animation frames: effects keep their speed when the event loop is
+// slowed down by busy loads, instead of advancing once per loop tick
+#[derive(Clone, Copy, Debug)]
+pub struct AnimClock {
+    start: std::time::Instant,
+}
+
+impl Default for AnimClock {
+    fn default() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+impl AnimClock {
+    pub fn frame(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64 / FRAME_MS
+    }
+
+    // Clock that started `ago`
+    #[cfg(test)]
+    pub fn started(ago: std::time::Duration) -> Self {
+        Self {
+            start: std::time::Instant::now() - ago,
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub fn panel_block(active: bool, theme: &Theme) -> Block<'static> {
     let border = if active { theme.selected } else { theme.frame };
@@ -311,3 +343,69 @@ pub fn draw_matrix_bg_custom(f: &mut Frame, area: Rect, palette: &[Color], tick:
     let p = Paragraph::new(out).wrap(Wrap { trim: false });
     f.render_widget(p, area);
 }
+
+/// Draw a slow horizontal wave of block glyphs across the area (banner effect).
+pub fn draw_wave_bg(f: &mut Frame, area: Rect, theme: &Theme, tick: u64) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let h = area.height as f32;
+    let palette = [theme.primary, theme.accent, theme.secondary];
+    let mut out: Vec<Line> = Vec::with_capacity(area.height as usize);
+    for y in 0..area.height {
+        let mut spans: Vec<Span> = Vec::with_capacity(area.width as usize);
+        for x in 0..area.width {
+            let phase = (x as f32 * 0.35) + (tick as f32 * 0.6);
+            // Crest row of the wave at this column, 0..h
+            let crest = (phase.sin() * 0.5 + 0.5) * (h - 1.0).max(0.0);
+            let dist = (y as f32 - crest).abs();
+            let col = palette[((x as u64 + tick) / 8) as usize % palette.len()];
+            let (ch, style) = if dist < 0.5 {
+                ('≈', Style::default().fg(col).add_modifier(Modifier::BOLD))
+            } else if dist < 1.5 {
+                ('~', Style::default().fg(col).add_modifier(Modifier::DIM))
+            } else {
+                (' ', Style::default())
+            };
+            spans.push(Span::styled(ch.to_string(), style));
+        }
+        out.push(Line::from(spans));
+    }
+    let p = Paragraph::new(out)
+        .style(Style::default().bg(theme.bg))
+        .wrap(Wrap { trim: false });
+    f.render_widget(p, area);
+}
+
+/// Draw a twinkling starfield drifting to the left (banner effect).
+pub fn draw_starfield_bg(f: &mut Frame, area: Rect, theme: &Theme, tick: u64) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let w = area.width as u64;
+    let mut out: Vec<Line> = Vec::with_capacity(area.height as usize);
+    for y in 0..area.height as u64 {
+        // Far rows drift slower than near rows
+        let speed = 1 + (y % 3);
+        let mut spans: Vec<Span> = Vec::with_capacity(area.width as usize);
+        for x in 0..w {
+            let sx = (x + tick * speed / 2) % w.max(1);
+            let hash = (sx * 73 + y * 151 + 11) % 29;
+            let (ch, style) = match hash {
+                0 => (
+                    '✦',
+                    Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
+                ),
+                1 if (tick + sx) % 4 < 2 => ('*', Style::default().fg(theme.secondary)),
+                2 | 3 => ('·', Style::default().fg(theme.muted)),
+                _ => (' ', Style::default()),
+            };
+            spans.push(Span::styled(ch.to_string(), style));
+        }
+        out.push(Line::from(spans));
+    }
+    let p = Paragraph::new(out)
+        .style(Style::default().bg(theme.bg))
+        .wrap(Wrap { trim: false });
+    f.render_widget(p, area);
+}
@@ -0,0 +1,52 @@
+use crate::ui::ActionBar;
+use crate::widgets::text_width::{max_width, pad};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+// Action bar (space): popup listing what the focused widget can do, with its shortcut
+pub fn draw_action_bar(f: &mut Frame, screen: Rect, bar: &ActionBar) {
+    let theme = crate::theme::active();
+    let label_w = max_width(bar.actions.iter().map(|a| a.label));
+    let keys_w = max_width(bar.actions.iter().map(|a| a.keys));
+    let width = ((label_w + keys_w + 8) as u16)
+        .max(30)
+        .min(screen.width.saturating_sub(4));
+    let height = (bar.actions.len() as u16 + 3).min(screen.height);
+    let rect = Rect {
+        x: screen.x + screen.width.saturating_sub(width) / 2,
+        y: screen.y + screen.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let mut lines: Vec<Line> = bar
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let sel = i == bar.selected;
+            let style = if sel {
+                theme.list_cursor_style()
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{} {}  ",
+                        if sel { "▶" } else { " " },
+                        pad(a.label, label_w)
+                    ),
+                    style,
+                ),
+                Span::styled(a.keys, theme.text_muted()),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(Span::styled(
+        "Enter run • Esc close",
+        theme.text_muted(),
+    )));
+    let p = Paragraph::new(lines).block(crate::widgets::chrome::panel_block(&bar.title, true));
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
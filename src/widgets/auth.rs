@@ -0,0 +1,41 @@
+use crate::ui::AuthPrompt;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+// Login dialog (`auth:`): why login is needed, then the device-code output while it runs
+pub fn draw_login(f: &mut Frame, screen: Rect, prompt: &AuthPrompt) {
+    let theme = crate::theme::active();
+    let mut lines = vec![Line::from(prompt.reason.clone()), Line::from("")];
+    if let Some(output) = &prompt.output {
+        for l in output.lock().unwrap().iter() {
+            lines.push(Line::from(l.clone()));
+        }
+    }
+    if let Some(err) = &prompt.error {
+        lines.push(Line::from(Span::styled(
+            format!("Login failed: {err}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        if prompt.running {
+            "waiting for login… • Esc dismiss"
+        } else {
+            "Enter log in • Esc dismiss"
+        },
+        theme.text_muted(),
+    )));
+    let width = screen.width.saturating_sub(4).min(72);
+    let height = (lines.len() as u16 + 2).min(screen.height);
+    let rect = Rect {
+        x: screen.x + screen.width.saturating_sub(width) / 2,
+        y: screen.y + screen.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let p = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(crate::widgets::chrome::panel_block("Login required", true));
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
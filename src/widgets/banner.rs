@@ -2,7 +2,7 @@ use ratatui::layout::Rect;
 use ratatui::prelude::Frame;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
 use crate::ui::AppState;
 
@@ -17,19 +17,104 @@ pub fn draw_banner(f: &mut Frame, area: Rect, state: &AppState) {
             crate::theme::MUTED
         }));
     let inner = border.inner(area);
+    let effect = if state.animations_enabled {
+        state.config.banner_animation()
+    } else {
+        "none"
+    };
+
+    // Background effect behind the logo (matrix keeps the very subtle ambient dots)
+    let frame = state.frame();
+    match effect {
+        // Reduced motion: every effect becomes the ambient dots, standing still
+        "none" => {}
+        _ if state.reduced_motion => crate::visuals::draw_ambient_bg(f, inner, &state.theme, 0),
+        "wave" => crate::visuals::draw_wave_bg(f, inner, &state.theme, frame),
+        "starfield" => crate::visuals::draw_starfield_bg(f, inner, &state.theme, frame),
+        "matrix" => crate::visuals::draw_ambient_bg(f, inner, &state.theme, frame),
+        _ => {}
+    }
+
+    let (lx, logo_w) = draw_logo(f, inner, state);
+
+    // Side strips with matrix-like subtle animation
+    if effect == "matrix" && !state.reduced_motion {
+        draw_matrix_strips(f, inner, lx, logo_w, frame);
+    }
 
-    // Ambient background (very subtle)
-    crate::visuals::draw_ambient_bg(f, inner, &state.theme, state.tick);
+    // Render the separating bottom border last
+    f.render_widget(border, area);
+}
+
+/// Full-screen startup splash: the project logo revealed line by line over the
+/// screen's banner effect. Shown for the first ticks when `splash: true`.
+pub fn draw_splash(f: &mut Frame, area: Rect, state: &AppState) {
+    f.render_widget(Clear, area);
+    match state.config.banner_animation() {
+        "wave" => crate::visuals::draw_wave_bg(f, area, &state.theme, state.tick),
+        "starfield" => crate::visuals::draw_starfield_bg(f, area, &state.theme, state.tick),
+        "none" => {}
+        _ => crate::visuals::draw_matrix_bg_custom(
+            f,
+            area,
+            &[
+                state.theme.primary,
+                state.theme.accent,
+                state.theme.secondary,
+            ],
+            state.tick,
+        ),
+    }
+    // Reveal two more columns of the logo per tick
+    let reveal = (state.tick as usize + 1) * 2;
+    let logo: Vec<String> = logo_lines(state)
+        .into_iter()
+        .map(|l| l.chars().take(reveal).collect())
+        .collect();
+    let lw = logo_lines(state)
+        .iter()
+        .map(|s| crate::widgets::text_width::display_width(s) as u16)
+        .max()
+        .unwrap_or(0)
+        .min(area.width);
+    let lh = (logo.len() as u16).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(lw) / 2,
+        y: area.y + area.height.saturating_sub(lh) / 2,
+        width: lw,
+        height: lh,
+    };
+    f.render_widget(Clear, rect);
+    let lines: Vec<Line> = logo
+        .into_iter()
+        .map(|s| Line::from(Span::styled(s, logo_style())))
+        .collect();
+    f.render_widget(
+        Paragraph::new(lines).style(Style::default().bg(state.theme.bg)),
+        rect,
+    );
+}
 
-    // Centered ASCII logo from state.logo_lines
-    let logo = if state.logo_lines.is_empty() {
+fn logo_lines(state: &AppState) -> Vec<String> {
+    if state.logo_lines.is_empty() {
         vec!["".to_string(), "chi-tui".to_string(), "".to_string()]
     } else {
         state.logo_lines.clone()
-    };
+    }
+}
+
+fn logo_style() -> Style {
+    Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD)
+}
+
+// Centered ASCII logo from state.logo_lines; returns (x, width) of the logo
+fn draw_logo(f: &mut Frame, inner: Rect, state: &AppState) -> (u16, u16) {
+    let logo = logo_lines(state);
     let lw = logo
         .iter()
-        .map(|s| s.chars().count() as u16)
+        .map(|s| crate::widgets::text_width::display_width(s) as u16)
         .max()
         .unwrap_or(0);
     let logo_w = lw.min(inner.width);
@@ -45,9 +130,7 @@ pub fn draw_banner(f: &mut Frame, area: Rect, state: &AppState) {
     } else {
         inner.y
     };
-    let logo_style = Style::default()
-        .fg(Color::White)
-        .add_modifier(Modifier::BOLD);
+    let logo_style = logo_style();
     for (i, s) in logo.iter().enumerate() {
         let area_i = Rect {
             x: lx,
@@ -61,8 +144,10 @@ pub fn draw_banner(f: &mut Frame, area: Rect, state: &AppState) {
             .wrap(Wrap { trim: true });
         f.render_widget(p, area_i);
     }
+    (lx, logo_w)
+}
 
-    // Side strips with matrix-like subtle animation
+fn draw_matrix_strips(f: &mut Frame, inner: Rect, lx: u16, logo_w: u16, tick: u64) {
     let palette = [
         crate::theme::PRIMARY,
         crate::theme::ACCENT,
@@ -81,7 +166,7 @@ pub fn draw_banner(f: &mut Frame, area: Rect, state: &AppState) {
                 width: w,
                 height: inner.height,
             };
-            crate::visuals::draw_matrix_bg_custom(f, left, &palette, state.tick);
+            crate::visuals::draw_matrix_bg_custom(f, left, &palette, tick);
         }
     }
     // Right strip
@@ -101,10 +186,7 @@ pub fn draw_banner(f: &mut Frame, area: Rect, state: &AppState) {
                 crate::theme::ACCENT,
                 crate::theme::PRIMARY,
             ];
-            crate::visuals::draw_matrix_bg_custom(f, right, &palette_r, state.tick);
+            crate::visuals::draw_matrix_bg_custom(f, right, &palette_r, tick);
         }
     }
-
-    // Render the separating bottom border last
-    f.render_widget(border, area);
 }
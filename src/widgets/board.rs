@@ -0,0 +1,334 @@
+use crate::widgets::chrome::{fit_title, panel_block};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use serde_json::Value as JsonValue;
+
+// `widget: board`: the items of the Pane B command laid out in one column per value of
+// their status field (`status_field:`, default `status`). ←/→ pick a column, ↑/↓ a card,
+// and `<`/`>` move the card to the neighbouring column, running `move_cmd` when set.
+pub struct BoardWidget {
+    title: String,
+    items: Vec<JsonValue>,
+    field: String,
+    // Column order: `board_columns:` first, then statuses seen in the items
+    configured: Vec<String>,
+    columns: Vec<String>,
+    move_cmd: Option<String>,
+    // Pane B command that produced the items; 'r' re-runs it
+    source: String,
+    col: usize,
+    card: usize,
+    // Move waiting for `move_cmd`: (item index, target column)
+    pending: Option<(usize, String)>,
+    loaded: bool,
+}
+
+fn card_title(v: &JsonValue) -> String {
+    for key in ["title", "name", "id"] {
+        match v.get(key) {
+            Some(JsonValue::String(s)) => return s.clone(),
+            Some(JsonValue::Number(n)) => return n.to_string(),
+            _ => {}
+        }
+    }
+    crate::ui::title_from_value(v)
+}
+
+impl BoardWidget {
+    pub fn new(
+        title: impl Into<String>,
+        source: String,
+        field: Option<String>,
+        columns: Vec<String>,
+        move_cmd: Option<String>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            items: Vec::new(),
+            field: field.unwrap_or_else(|| "status".to_string()),
+            columns: columns.clone(),
+            configured: columns,
+            move_cmd,
+            source,
+            col: 0,
+            card: 0,
+            pending: None,
+            loaded: false,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    fn status(&self, item: &JsonValue) -> String {
+        match item.get(&self.field) {
+            Some(JsonValue::String(s)) => s.clone(),
+            Some(JsonValue::Null) | None => "none".to_string(),
+            Some(other) => other.to_string(),
+        }
+    }
+
+    // Replace the cards (a refresh keeps the selected column)
+    pub fn set_items(&mut self, value: &JsonValue) {
+        self.items = value.as_array().cloned().unwrap_or_default();
+        self.loaded = true;
+        self.pending = None;
+        let mut columns = self.configured.clone();
+        for item in &self.items {
+            let status = self.status(item);
+            if !columns.contains(&status) {
+                columns.push(status);
+            }
+        }
+        self.columns = columns;
+        self.col = self.col.min(self.columns.len().saturating_sub(1));
+        self.clamp_card();
+    }
+
+    // Item indices of the cards in column `col`
+    fn cards(&self, col: usize) -> Vec<usize> {
+        let Some(name) = self.columns.get(col) else {
+            return Vec::new();
+        };
+        (0..self.items.len())
+            .filter(|i| self.status(&self.items[*i]) == *name)
+            .collect()
+    }
+
+    fn clamp_card(&mut self) {
+        self.card = self.card.min(self.cards(self.col).len().saturating_sub(1));
+    }
+
+    fn selected_item(&self) -> Option<usize> {
+        self.cards(self.col).get(self.card).copied()
+    }
+
+    // Move the selected card `step` columns over
+    fn move_card(&mut self, step: isize) -> Vec<crate::app::Effect> {
+        let Some(item) = self.selected_item() else {
+            return Vec::new();
+        };
+        let target = self.col as isize + step;
+        if target < 0 || target as usize >= self.columns.len() {
+            return Vec::new();
+        }
+        if self.pending.is_some() {
+            return vec![crate::app::Effect::ShowToast {
+                text: "A move is still running".into(),
+                level: crate::ui::ToastLevel::Info,
+                seconds: 2,
+            }];
+        }
+        let to = self.columns[target as usize].clone();
+        match &self.move_cmd {
+            Some(template) => {
+                // `{to}`/`{from}` and the card's own keys, shell-quoted
+                let mut vars = self.items[item].clone();
+                if let Some(obj) = vars.as_object_mut() {
+                    obj.insert("from".into(), self.columns[self.col].clone().into());
+                    obj.insert("to".into(), to.clone().into());
+                }
+                let cmdline = crate::widgets::row_edit::fill_placeholders(template, &vars);
+                self.pending = Some((item, to));
+                vec![crate::app::Effect::UpdateRow { cmdline }]
+            }
+            None => {
+                self.set_status(item, &to);
+                Vec::new()
+            }
+        }
+    }
+
+    // Put the item in column `to` and keep it selected
+    fn set_status(&mut self, item: usize, to: &str) {
+        if let Some(obj) = self.items[item].as_object_mut() {
+            obj.insert(self.field.clone(), JsonValue::String(to.to_string()));
+        }
+        if let Some(col) = self.columns.iter().position(|c| c == to) {
+            self.col = col;
+            self.card = self.cards(col).iter().position(|i| *i == item).unwrap_or(0);
+        }
+    }
+
+    // `move_cmd` finished: an object reply is merged into the card, then the card moves.
+    // Returns "<card> → <column>", or the error.
+    pub fn move_finished(&mut self, outcome: Result<JsonValue, String>) -> Result<String, String> {
+        let Some((item, to)) = self.pending.take() else {
+            return Err("no move pending".into());
+        };
+        let reply = outcome?;
+        if item >= self.items.len() {
+            return Err("the board was reloaded".into());
+        }
+        if let (JsonValue::Object(updated), Some(obj)) = (reply, self.items[item].as_object_mut()) {
+            obj.extend(updated);
+        }
+        self.set_status(item, &to);
+        Ok(format!("{} → {to}", card_title(&self.items[item])))
+    }
+}
+
+impl crate::widgets::Widget for BoardWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, _tick: u64) {
+        let theme = crate::theme::active();
+        let title = fit_title(&format!("{} ({})", self.title, self.items.len()), area);
+        let block = panel_block(&title, focused);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        if self.columns.is_empty() {
+            let text = if self.loaded {
+                "No items"
+            } else {
+                "Loading…"
+            };
+            f.render_widget(Paragraph::new(text).style(theme.text_muted()), inner);
+            return;
+        }
+        let n = self.columns.len() as u32;
+        let areas = Layout::horizontal((0..n).map(|_| Constraint::Ratio(1, n))).split(inner);
+        for (c, col_area) in areas.iter().enumerate() {
+            let cards = self.cards(c);
+            let current = c == self.col;
+            let title = fit_title(&format!("{} ({})", self.columns[c], cards.len()), *col_area);
+            let block = panel_block(&title, focused && current);
+            let visible = block.inner(*col_area).height as usize;
+            // Keep the selected card in view
+            let skip = if current {
+                (self.card + 1).saturating_sub(visible)
+            } else {
+                0
+            };
+            let lines: Vec<Line> = cards
+                .iter()
+                .enumerate()
+                .skip(skip)
+                .map(|(k, i)| {
+                    let pending = self.pending.as_ref().is_some_and(|(p, _)| p == i);
+                    let mut line = Line::from(format!(
+                        "{}{}",
+                        if pending { "… " } else { "" },
+                        card_title(&self.items[*i])
+                    ));
+                    if current && k == self.card {
+                        line = line.style(theme.list_cursor_style());
+                    }
+                    line
+                })
+                .collect();
+            f.render_widget(Paragraph::new(lines).block(block), *col_area);
+        }
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        match key {
+            KeyCode::Left => {
+                self.col = self.col.saturating_sub(1);
+                self.clamp_card();
+            }
+            KeyCode::Right => {
+                self.col = (self.col + 1).min(self.columns.len().saturating_sub(1));
+                self.clamp_card();
+            }
+            KeyCode::Up => self.card = self.card.saturating_sub(1),
+            KeyCode::Down => {
+                self.card += 1;
+                self.clamp_card();
+            }
+            KeyCode::Home => self.card = 0,
+            KeyCode::End => self.card = usize::MAX,
+            KeyCode::Char('<') => return self.move_card(-1),
+            KeyCode::Char('>') => return self.move_card(1),
+            _ => {}
+        }
+        self.clamp_card();
+        Vec::new()
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = Vec::new();
+        if self.selected_item().is_some() {
+            actions.push(WidgetAction::key("Move card left", "<", KeyCode::Char('<')));
+            actions.push(WidgetAction::key(
+                "Move card right",
+                ">",
+                KeyCode::Char('>'),
+            ));
+        }
+        actions.push(WidgetAction::refresh());
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        vec![crate::app::Effect::LoadPanelCmd {
+            pane: crate::ui::PanelPane::B,
+            cmdline: self.source.clone(),
+        }]
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Widget;
+    use serde_json::json;
+
+    #[test]
+    fn cards_move_between_status_columns() {
+        let mut w = BoardWidget::new(
+            "Tickets",
+            "app tickets".into(),
+            None,
+            vec!["todo".into(), "doing".into(), "done".into()],
+            Some("app move {id} --to {to} --from {from}".into()),
+        );
+        w.set_items(&json!([
+            {"id": 1, "title": "Login page", "status": "todo"},
+            {"id": 2, "title": "Fix CI", "status": "doing"},
+            {"id": 3, "title": "Audit", "status": "todo"},
+            {"id": 4, "title": "Docs", "status": "blocked"},
+        ]));
+        assert_eq!(w.columns, ["todo", "doing", "done", "blocked"]);
+        assert_eq!(w.cards(0), [0, 2]);
+
+        w.on_key(KeyCode::Down);
+        let effects = w.on_key(KeyCode::Char('>'));
+        assert!(matches!(
+            &effects[..],
+            [crate::app::Effect::UpdateRow { cmdline }]
+                if cmdline == "app move 3 --to doing --from todo"
+        ));
+        // The card stays put until the command succeeds
+        assert_eq!(w.cards(0), [0, 2]);
+        assert_eq!(
+            w.move_finished(Ok(json!({"assignee": "kim"}))),
+            Ok("Audit → doing".into())
+        );
+        assert_eq!(w.cards(1), [1, 2]);
+        assert_eq!(w.items[2]["assignee"], "kim");
+        // The moved card stays selected
+        assert_eq!((w.col, w.selected_item()), (1, Some(2)));
+
+        // A failed move leaves the card alone; the first column has no left neighbour
+        w.on_key(KeyCode::Char('>'));
+        assert_eq!(w.move_finished(Err("denied".into())), Err("denied".into()));
+        assert_eq!(w.status(&w.items[2]), "doing");
+        w.on_key(KeyCode::Left);
+        assert!(w.on_key(KeyCode::Char('<')).is_empty());
+
+        // Without `move_cmd` cards move locally
+        w.move_cmd = None;
+        w.on_key(KeyCode::Char('>'));
+        assert_eq!(w.status(&w.items[0]), "doing");
+    }
+}
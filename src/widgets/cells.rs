@@ -0,0 +1,185 @@
+use crate::model::FieldSpec;
+use crate::theme::Theme;
+use crate::widgets::text_width;
+use ratatui::prelude::*;
+use serde_json::Value as JsonValue;
+
+// Inline cells appended to list rows for `fields:` columns
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Unicode sparkline scaled from 0 (or the lowest negative value) to the maximum
+pub fn sparkline(values: &[f64]) -> String {
+    let lo = values.iter().copied().fold(0.0_f64, f64::min);
+    let hi = values.iter().copied().fold(lo, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if hi <= lo {
+                return BARS[0];
+            }
+            let level = ((v - lo) / (hi - lo) * (BARS.len() - 1) as f64).round() as usize;
+            BARS[level.min(BARS.len() - 1)]
+        })
+        .collect()
+}
+
+// Horizontal gauge of `width` cells: one segment per (count, status) out of `total`, each
+// filled with its status pattern and color (patterns keep segments apart without color)
+pub fn gauge_spans(
+    segments: &[(usize, crate::theme::Status)],
+    total: usize,
+    width: usize,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut used = 0;
+    for (count, status) in segments {
+        let cells = (*count * width).checked_div(total).unwrap_or(0);
+        let cells = cells.min(width - used);
+        if cells > 0 {
+            spans.push(Span::styled(
+                theme.gauge_fill(*status).to_string().repeat(cells),
+                theme.status_style(*status),
+            ));
+            used += cells;
+        }
+    }
+    spans.push(Span::styled("·".repeat(width - used), theme.text_muted()));
+    spans
+}
+
+// "now", "42s ago", "3m ago", "2h ago", "5d ago" (or "in 3m" for future times)
+pub fn relative_time(ts: i64, now: i64) -> String {
+    let delta = now - ts;
+    let secs = delta.unsigned_abs();
+    if secs < 5 {
+        return "now".to_string();
+    }
+    let amount = match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3_599 => format!("{}m", secs / 60),
+        3_600..=86_399 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
+    };
+    if delta >= 0 {
+        format!("{amount} ago")
+    } else {
+        format!("in {amount}")
+    }
+}
+
+pub fn field_spans(val: &JsonValue, fields: &[FieldSpec], theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for spec in fields {
+        let Some(v) = val.get(&spec.name).filter(|v| !v.is_null()) else {
+            continue;
+        };
+        spans.push(Span::raw("  "));
+        if let Some(label) = &spec.label {
+            spans.push(Span::styled(format!("{label} "), theme.text_muted()));
+        }
+        let numbers: Option<Vec<f64>> = v
+            .as_array()
+            .map(|arr| arr.iter().map(|n| n.as_f64()).collect::<Option<Vec<_>>>())
+            .unwrap_or(None);
+        match (spec.render.as_deref(), numbers) {
+            (Some("relative_time"), _) if crate::services::clock::parse_timestamp(v).is_some() => {
+                // Recomputed from the raw value on every draw, so "3m ago" keeps ticking
+                // while the pane sits open without re-running the command
+                let ts = crate::services::clock::parse_timestamp(v).unwrap_or_default();
+                let now = crate::services::clock::unix_secs(std::time::SystemTime::now());
+                spans.push(Span::styled(relative_time(ts, now), theme.text_muted()));
+            }
+            (Some("sparkline"), Some(nums)) if !nums.is_empty() => {
+                spans.push(Span::styled(
+                    sparkline(&nums),
+                    Style::default().fg(theme.accent),
+                ));
+            }
+            _ => {
+                let text = match (v, spec.width) {
+                    (JsonValue::String(s), None) => s.clone(),
+                    (JsonValue::String(s), Some(w)) => text_width::pad(s, w),
+                    (JsonValue::Number(n), Some(w)) => text_width::pad_left(&n.to_string(), w),
+                    (other, Some(w)) => text_width::pad(&other.to_string(), w),
+                    (other, None) => other.to_string(),
+                };
+                spans.push(Span::styled(text, theme.json_string()));
+            }
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn numeric_arrays_render_as_sparklines() {
+        assert_eq!(sparkline(&[0.0, 1.0, 7.0, 3.5]), "▁▂█▅");
+        assert_eq!(sparkline(&[0.0, 0.0]), "▁▁");
+        let fields: Vec<FieldSpec> = serde_yaml::from_str(
+            "- { name: errors, label: 24h, render: sparkline }\n- { name: region }\n- { name: missing }\n",
+        )
+        .unwrap();
+        let row = json!({"title": "api", "errors": [0, 2, 4], "region": "eu"});
+        let text: String = field_spans(&row, &fields, &Theme::synthwave_dark())
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect();
+        assert_eq!(text, "  24h ▁▅█  eu");
+
+        // Fixed-width columns line up by display width, whatever the script
+        let fields: Vec<FieldSpec> =
+            serde_yaml::from_str("- { name: owner, width: 6 }\n- { name: n, width: 4 }\n").unwrap();
+        let cells = |row: JsonValue| -> String {
+            field_spans(&row, &fields, &Theme::synthwave_dark())
+                .iter()
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+        assert_eq!(cells(json!({"owner": "ops", "n": 7})), "  ops        7");
+        assert_eq!(
+            cells(json!({"owner": "運用チーム", "n": 12})),
+            "  運用…     12"
+        );
+    }
+
+    #[test]
+    fn gauge_segments_use_status_patterns() {
+        use crate::theme::Status;
+        let theme = Theme::synthwave_dark();
+        let text: String = gauge_spans(&[(2, Status::Ok), (1, Status::Fail)], 5, 10, &theme)
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect();
+        assert_eq!(text, "████▚▚····");
+        let empty: String = gauge_spans(&[(0, Status::Ok)], 0, 4, &theme)
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect();
+        assert_eq!(empty, "····");
+    }
+
+    #[test]
+    fn relative_times_are_formatted_against_now() {
+        let now = 1_709_287_200;
+        assert_eq!(relative_time(now - 2, now), "now");
+        assert_eq!(relative_time(now - 42, now), "42s ago");
+        assert_eq!(relative_time(now - 200, now), "3m ago");
+        assert_eq!(relative_time(now - 7_300, now), "2h ago");
+        assert_eq!(relative_time(now - 5 * 86_400, now), "5d ago");
+        assert_eq!(relative_time(now + 180, now), "in 3m");
+        let fields: Vec<FieldSpec> =
+            serde_yaml::from_str("- { name: seen, render: relative_time }\n").unwrap();
+        let row = json!({"seen": "not a date"});
+        let text: String = field_spans(&row, &fields, &Theme::synthwave_dark())
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect();
+        assert_eq!(text, "  not a date");
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+// Rounds of concurrent background checks for the polling widgets (ports, http_checks,
+// promql): every job of a round runs on its own thread, replies of an older round are
+// dropped, and the next round is due once the last one finished and `interval` passed.
+
+// (round, job index, outcome)
+type Reply<T> = (u64, usize, T);
+
+pub struct CheckRounds<T> {
+    interval: Duration,
+    // Bumped per round so replies of an older round are dropped
+    round: u64,
+    last_round: Option<Instant>,
+    // Jobs of the current round still running
+    checking: Vec<bool>,
+    tx: Sender<Reply<T>>,
+    rx: Receiver<Reply<T>>,
+}
+
+impl<T: Send + 'static> CheckRounds<T> {
+    pub fn new(interval: Duration) -> Self {
+        let (tx, rx) = channel();
+        Self {
+            interval,
+            round: 0,
+            last_round: None,
+            checking: Vec::new(),
+            tx,
+            rx,
+        }
+    }
+
+    // Start a round with one job per row, each on its own thread
+    pub fn start<F>(&mut self, jobs: impl IntoIterator<Item = F>)
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        self.round += 1;
+        self.last_round = Some(Instant::now());
+        self.checking.clear();
+        for (i, job) in jobs.into_iter().enumerate() {
+            self.checking.push(true);
+            let (tx, round) = (self.tx.clone(), self.round);
+            std::thread::spawn(move || {
+                let _ = tx.send((round, i, job()));
+            });
+        }
+    }
+
+    // Outcomes of the current round that arrived since the last call
+    pub fn finished(&mut self) -> Vec<(usize, T)> {
+        let mut out = Vec::new();
+        while let Ok((round, i, outcome)) = self.rx.try_recv() {
+            if round != self.round {
+                continue;
+            }
+            if let Some(c) = self.checking.get_mut(i) {
+                *c = false;
+            }
+            out.push((i, outcome));
+        }
+        out
+    }
+
+    // No round ran yet, or the last one finished more than `interval` after it started
+    pub fn due(&self) -> bool {
+        self.last_round
+            .is_none_or(|t| self.idle() && t.elapsed() >= self.interval)
+    }
+
+    pub fn checking(&self, i: usize) -> bool {
+        self.checking.get(i).copied().unwrap_or(false)
+    }
+
+    pub fn idle(&self) -> bool {
+        !self.checking.contains(&true)
+    }
+
+    #[cfg(test)]
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+}
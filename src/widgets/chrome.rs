@@ -1,21 +1,138 @@
+use crate::model::ChromeConfig;
 use crate::theme::Theme;
-use ratatui::widgets::{Block, Borders};
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, BorderType, Borders, Padding};
 
-pub fn panel_block<'a>(title: &'a str, focused: bool) -> Block<'a> {
-    let mut b = Block::default().borders(Borders::ALL).title(title);
-    if focused {
-        b = b.border_style(crate::theme::border_focused());
+// Resolved pane chrome; `panel_block` applies the active one everywhere
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChromeStyle {
+    // None hides the borders
+    pub border: Option<BorderType>,
+    pub title_align: Alignment,
+    pub focused: Option<Color>,
+    pub unfocused: Option<Color>,
+    pub padding: u16,
+}
+
+impl ChromeStyle {
+    pub fn from_config(cfg: &ChromeConfig) -> Self {
+        let border = match cfg.border.as_deref().map(|s| s.to_ascii_lowercase()) {
+            Some(b) if b == "none" => None,
+            Some(b) if b == "rounded" => Some(BorderType::Rounded),
+            Some(b) if b == "double" => Some(BorderType::Double),
+            _ => Some(BorderType::Plain),
+        };
+        let title_align = match cfg.title_align.as_deref() {
+            Some(a) if a.eq_ignore_ascii_case("center") => Alignment::Center,
+            _ => Alignment::Left,
+        };
+        let color = |s: &Option<String>| s.as_deref().and_then(|c| c.parse::<Color>().ok());
+        Self {
+            border,
+            title_align,
+            focused: color(&cfg.border_focused),
+            unfocused: color(&cfg.border_unfocused),
+            padding: cfg.padding.unwrap_or(0),
+        }
     }
-    b
+
+    pub fn block<'a>(&self, title: &'a str, focused: bool, theme: &Theme) -> Block<'a> {
+        let mut b = Block::default()
+            .title(title)
+            .title_alignment(self.title_align)
+            .padding(Padding::horizontal(self.padding));
+        match self.border {
+            Some(t) => b = b.borders(Borders::ALL).border_type(t),
+            None => b = b.borders(Borders::NONE),
+        }
+        if focused {
+            b.border_style(match self.focused {
+                Some(c) => Style::default().fg(c),
+                None => theme.border_focused(),
+            })
+        } else if let Some(c) = self.unfocused {
+            b.border_style(Style::default().fg(c))
+        } else {
+            b
+        }
+    }
+}
+
+static ACTIVE_CHROME: std::sync::RwLock<Option<ChromeStyle>> = std::sync::RwLock::new(None);
+
+pub fn set_active(cfg: Option<&ChromeConfig>) {
+    if let Ok(mut g) = ACTIVE_CHROME.write() {
+        *g = cfg.map(ChromeStyle::from_config);
+    }
+}
+
+fn active() -> ChromeStyle {
+    ACTIVE_CHROME
+        .read()
+        .ok()
+        .and_then(|g| g.clone())
+        .unwrap_or_else(|| ChromeStyle::from_config(&ChromeConfig::default()))
+}
+
+pub fn panel_block<'a>(title: &'a str, focused: bool) -> Block<'a> {
+    active().block(title, focused, &crate::theme::active())
 }
 
 #[allow(dead_code)]
 pub fn panel_block_themed<'a>(title: &'a str, focused: bool, theme: &Theme) -> Block<'a> {
-    let mut b = Block::default().borders(Borders::ALL).title(title);
-    if focused {
-        b = b.border_style(theme.border_focused());
-    } else {
+    let mut b = active().block(title, focused, theme);
+    if !focused {
         b = b.border_style(theme.border_unfocused());
     }
     b
 }
+
+// `title` cut to fit the top border of a pane drawn over `area` (by display width, so CJK
+// and emoji titles end in "…" instead of being clipped mid-character)
+pub fn fit_title(title: &str, area: ratatui::layout::Rect) -> String {
+    let chrome = active();
+    let used = if chrome.border.is_some() { 2 } else { 0 } + chrome.padding * 2;
+    crate::widgets::text_width::truncate(title, area.width.saturating_sub(used) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn chrome_config_sets_border_alignment_and_colors() {
+        let cfg = ChromeConfig {
+            border: Some("double".into()),
+            title_align: Some("center".into()),
+            border_focused: Some("#00ff00".into()),
+            border_unfocused: Some("blue".into()),
+            padding: Some(1),
+        };
+        let style = ChromeStyle::from_config(&cfg);
+        assert_eq!(style.focused, Some(Color::Rgb(0, 255, 0)));
+        assert_eq!(style.unfocused, Some(Color::Blue));
+        let mut term = Terminal::new(TestBackend::new(12, 3)).unwrap();
+        term.draw(|f| {
+            let b = style.block("ab", true, &Theme::default());
+            f.render_widget(b, f.area());
+        })
+        .unwrap();
+        let buf = term.backend().buffer();
+        let top: String = (0..12).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(top, "╔════ab════╗");
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(0, 255, 0));
+
+        // Defaults keep the plain box with a left-aligned title
+        let plain = ChromeStyle::from_config(&ChromeConfig::default());
+        assert_eq!(plain.border, Some(BorderType::Plain));
+        assert_eq!(plain.title_align, Alignment::Left);
+        let none = ChromeStyle::from_config(&ChromeConfig {
+            border: Some("none".into()),
+            ..Default::default()
+        });
+        assert_eq!(none.border, None);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::widgets::text_width::{max_width, pad};
+use ratatui::prelude::*;
+use ratatui::widgets::{Clear, Paragraph};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+// Column statistics ('%' in the result viewer): one field across the items of an array.
+// Numbers get min / max / mean, anything else its distinct values with counts. The column
+// is the field under the tree cursor (the innermost array it sits in gives the rows) or the
+// field picked for inline editing.
+
+// Distinct values listed before "… n more"
+const TOP_VALUES: usize = 10;
+
+#[derive(Debug, PartialEq)]
+pub enum Summary {
+    Numbers { min: f64, max: f64, mean: f64 },
+    // Most frequent first, then by value
+    Values(Vec<(String, usize)>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ColumnStats {
+    pub column: String,
+    pub rows: usize,
+    // Rows without the field (or with null)
+    pub missing: usize,
+    pub summary: Summary,
+}
+
+fn unescape(seg: &str) -> String {
+    seg.replace("~1", "/").replace("~0", "~")
+}
+
+// Column name, rows and column path of a tree cursor: the innermost array on the pointer,
+// and the fields after the item index (empty for an array of scalars, named after the array)
+pub fn column_at<'a>(
+    root: &'a JsonValue,
+    pointer: &str,
+) -> Option<(String, &'a [JsonValue], Vec<String>)> {
+    let segs: Vec<String> = pointer.split('/').skip(1).map(unescape).collect();
+    let mut found = None;
+    let mut v = root;
+    for (i, seg) in segs.iter().enumerate() {
+        if let (Some(arr), Ok(_)) = (v.as_array(), seg.parse::<usize>()) {
+            let path = segs[i + 1..].to_vec();
+            let name = if path.is_empty() {
+                &segs[..i]
+            } else {
+                &path[..]
+            };
+            found = Some((name.join("."), arr.as_slice(), path));
+        }
+        v = match v {
+            JsonValue::Array(a) => a.get(seg.parse::<usize>().ok()?)?,
+            JsonValue::Object(m) => m.get(seg)?,
+            _ => return None,
+        };
+    }
+    // The cursor on a whole item of objects is not a column
+    match found {
+        Some((_, rows, path)) if path.is_empty() && rows.iter().any(|r| r.is_object()) => None,
+        found => found,
+    }
+}
+
+fn cell<'a>(row: &'a JsonValue, path: &[String]) -> Option<&'a JsonValue> {
+    path.iter()
+        .try_fold(row, |v, k| v.get(k.as_str()))
+        .filter(|v| !v.is_null())
+}
+
+fn text(v: &JsonValue) -> String {
+    match v {
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub fn compute(column: String, rows: &[JsonValue], path: &[String]) -> ColumnStats {
+    let cells: Vec<&JsonValue> = rows.iter().filter_map(|r| cell(r, path)).collect();
+    let missing = rows.len() - cells.len();
+    let numbers: Option<Vec<f64>> = cells.iter().map(|v| v.as_f64()).collect();
+    let summary = match numbers {
+        Some(ns) if !ns.is_empty() => Summary::Numbers {
+            min: ns.iter().copied().fold(f64::INFINITY, f64::min),
+            max: ns.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean: ns.iter().sum::<f64>() / ns.len() as f64,
+        },
+        _ => {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for v in &cells {
+                *counts.entry(text(v)).or_default() += 1;
+            }
+            let mut values: Vec<(String, usize)> = counts.into_iter().collect();
+            values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            Summary::Values(values)
+        }
+    };
+    ColumnStats {
+        column,
+        rows: rows.len(),
+        missing,
+        summary,
+    }
+}
+
+// 12, 3.5, 0.33 (two decimals at most)
+fn number(n: f64) -> String {
+    let s = format!("{n:.2}");
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+impl ColumnStats {
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        let theme = crate::theme::active();
+        let mut head = format!("{} rows", self.rows);
+        if self.missing > 0 {
+            head.push_str(&format!(" · {} missing", self.missing));
+        }
+        let mut lines = vec![Line::from(Span::styled(head, theme.text_muted()))];
+        match &self.summary {
+            Summary::Numbers { min, max, mean } => {
+                for (label, n) in [("min", min), ("max", max), ("mean", mean)] {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("{label:<6}"), theme.text_muted()),
+                        Span::raw(number(*n)),
+                    ]));
+                }
+            }
+            Summary::Values(values) => {
+                lines.push(Line::from(format!("{} distinct", values.len())));
+                let shown = &values[..values.len().min(TOP_VALUES)];
+                let w = max_width(shown.iter().map(|(v, _)| v.as_str())).min(40);
+                for (v, n) in shown {
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {}  ", pad(v, w))),
+                        Span::styled(n.to_string(), theme.text_muted()),
+                    ]));
+                }
+                if values.len() > TOP_VALUES {
+                    lines.push(Line::from(Span::styled(
+                        format!("  … {} more", values.len() - TOP_VALUES),
+                        theme.text_muted(),
+                    )));
+                }
+            }
+        }
+        lines
+    }
+}
+
+// Popup centered over the viewer; None shows how to pick a column
+pub fn draw(f: &mut Frame, area: Rect, stats: Option<&ColumnStats>) {
+    let theme = crate::theme::active();
+    let (title, mut lines) = match stats {
+        Some(s) => (format!("Column {}", s.column), s.lines()),
+        None => (
+            "Column stats".to_string(),
+            vec![Line::from(
+                "Put the tree cursor (t) on a field of an item, or pick an editable field",
+            )],
+        ),
+    };
+    lines.push(Line::from(Span::styled("Esc close", theme.text_muted())));
+    let width = (lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4)
+        .max(title.chars().count() as u16 + 6)
+        .max(30)
+        .min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let p = Paragraph::new(lines).block(crate::widgets::chrome::panel_block(&title, true));
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn columns_summarize_numbers_and_count_values() {
+        let root = json!({"data": {"items": [
+            {"name": "a", "size": 3, "state": "up"},
+            {"name": "b", "size": 1.5, "state": "down"},
+            {"name": "c", "state": "up"},
+            {"name": "d", "size": 7, "state": null}
+        ]}});
+        let (name, rows, path) = column_at(&root, "/data/items/1/size").unwrap();
+        assert_eq!((name.as_str(), rows.len()), ("size", 4));
+        let s = compute(name, rows, &path);
+        assert_eq!(s.missing, 1);
+        let Summary::Numbers { min, max, mean } = s.summary else {
+            panic!("{:?}", s.summary);
+        };
+        assert_eq!((min, max, number(mean)), (1.5, 7.0, "3.83".to_string()));
+
+        let s = compute("state".into(), rows, &["state".to_string()]);
+        assert_eq!(
+            s.summary,
+            Summary::Values(vec![("up".into(), 2), ("down".into(), 1)])
+        );
+        assert_eq!(s.missing, 1);
+
+        // A whole item is no column; an array of scalars is
+        assert!(column_at(&root, "/data/items/0").is_none());
+        assert!(column_at(&root, "/data").is_none());
+        let tags = json!({"tags": ["x", "y", "x"]});
+        let (name, rows, path) = column_at(&tags, "/tags/2").unwrap();
+        assert_eq!((name.as_str(), path.len()), ("tags", 0));
+        assert_eq!(
+            compute(name, rows, &path).summary,
+            Summary::Values(vec![("x".into(), 2), ("y".into(), 1)])
+        );
+    }
+}
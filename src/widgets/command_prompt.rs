@@ -0,0 +1,43 @@
+use crate::ui::CommandPrompt;
+use ratatui::prelude::*;
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+
+// Modal command line (':'): the backing CLI with schema completion — Tab cycles the
+// candidates for the word being typed, the rest of the first one shows dimmed after the caret
+pub fn draw_prompt(f: &mut Frame, screen: Rect, prompt: &CommandPrompt) {
+    let theme = crate::theme::active();
+    let width = screen.width.saturating_sub(4).min(80);
+    let rect = Rect {
+        x: screen.x + (screen.width.saturating_sub(width)) / 2,
+        y: screen.y + screen.height.saturating_sub(6) / 2,
+        width,
+        height: 6.min(screen.height),
+    };
+    let schema = crate::services::completion::schema_for(&prompt.program);
+    let ghost = schema
+        .as_ref()
+        .and_then(|s| s.ghost(&prompt.input))
+        .unwrap_or_default();
+    let mut lines = vec![Line::from(vec![
+        Span::styled("$ ", theme.text_active_bold()),
+        Span::raw(prompt.input.clone()),
+        Span::raw("▏"),
+        Span::styled(ghost, theme.text_muted()),
+    ])];
+    let status = match (&prompt.cycle, &schema) {
+        (Some(c), _) => format!("completion {}", c.position()),
+        (None, None) => format!("loading `{} schema`…", prompt.program),
+        (None, Some(s)) if s.commands.is_empty() => "no schema: completion off".to_string(),
+        (None, Some(_)) => String::new(),
+    };
+    lines.push(Line::from(Span::styled(status, theme.text_muted())));
+    lines.push(Line::from(Span::styled(
+        "Tab/Shift+Tab complete • → accept • Enter run • Esc cancel",
+        theme.text_muted(),
+    )));
+    let p = Paragraph::new(lines)
+        .block(crate::widgets::chrome::panel_block("Run command", true))
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
@@ -0,0 +1,289 @@
+use crate::app::Effect;
+use crate::model::{EmptyActionSpec, MenuItem};
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::{Widget, WidgetAction};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Paragraph, Wrap};
+use serde_json::Value as JsonValue;
+use std::any::Any;
+
+// Empty state of Pane B: when the command behind the pane returns an empty list, the item's
+// (or pane spec's) `empty_text:` / `empty_widget:` is shown instead of `[]`, optionally with
+// `empty_action: {key, item}` opening another item ("No deployments yet — press n to create
+// one").
+
+#[derive(Clone, Debug)]
+pub struct EmptyState {
+    text: Option<String>,
+    widget: Option<JsonValue>,
+    action: Option<EmptyAction>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmptyAction {
+    pub key: KeyCode,
+    pub item: String,
+    pub label: String,
+}
+
+// `n` -> Char('n'), `enter` (or nothing) -> Enter
+pub fn parse_key(key: Option<&str>) -> Option<KeyCode> {
+    let Some(key) = key else {
+        return Some(KeyCode::Enter);
+    };
+    if key.eq_ignore_ascii_case("enter") {
+        return Some(KeyCode::Enter);
+    }
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(KeyCode::Char(c)),
+        _ => None,
+    }
+}
+
+fn key_label(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(c) => c.to_string(),
+        _ => "Enter".to_string(),
+    }
+}
+
+// `[]`, `null`, or an envelope whose `data`/`items` list is empty
+pub fn is_empty_result(v: &JsonValue) -> bool {
+    match v {
+        JsonValue::Null => true,
+        JsonValue::Array(a) => a.is_empty(),
+        JsonValue::Object(o) => ["data", "items"]
+            .iter()
+            .find_map(|k| o.get(*k))
+            .is_some_and(|d| d.as_array().is_some_and(|a| a.is_empty())),
+        _ => false,
+    }
+}
+
+impl EmptyState {
+    fn new(
+        text: Option<String>,
+        widget: Option<JsonValue>,
+        action: Option<EmptyActionSpec>,
+        menu: &[MenuItem],
+    ) -> Option<Self> {
+        let action = action.and_then(|a| {
+            let key = parse_key(a.key.as_deref())?;
+            let label = a.label.clone().unwrap_or_else(|| {
+                menu.iter()
+                    .find(|m| m.id == a.item)
+                    .map(|m| m.title.clone())
+                    .unwrap_or_else(|| a.item.clone())
+            });
+            Some(EmptyAction {
+                key,
+                item: a.item,
+                label,
+            })
+        });
+        if text.is_none() && widget.is_none() && action.is_none() {
+            return None;
+        }
+        Some(Self {
+            text,
+            widget,
+            action,
+        })
+    }
+
+    pub fn from_item(mi: &MenuItem, menu: &[MenuItem]) -> Option<Self> {
+        Self::new(
+            mi.empty_text.clone(),
+            mi.empty_widget.clone(),
+            mi.empty_action.clone(),
+            menu,
+        )
+    }
+
+    // Same keys on a pane spec (`type: json_viewer` with `cmd:`/`yaml:`)
+    pub fn from_spec(v: &JsonValue, menu: &[MenuItem]) -> Option<Self> {
+        let text = v
+            .get("empty_text")
+            .and_then(|s| s.as_str())
+            .map(String::from);
+        let action = v
+            .get("empty_action")
+            .and_then(|a| serde_json::from_value::<EmptyActionSpec>(a.clone()).ok());
+        Self::new(text, v.get("empty_widget").cloned(), action, menu)
+    }
+
+    // What Pane B shows for an empty result of `source`
+    pub fn widget(&self, title: &str, source: Option<String>) -> Box<dyn Widget> {
+        let custom = self.widget.as_ref().and_then(|spec| {
+            crate::chi_core::registry::resolve_widget_for_pane(crate::ui::PanelPane::B, spec)
+        });
+        Box::new(EmptyStateWidget {
+            title: title.to_string(),
+            text: self
+                .text
+                .clone()
+                .unwrap_or_else(|| "Nothing here yet".to_string()),
+            custom,
+            action: self.action.clone(),
+            source,
+        })
+    }
+}
+
+pub struct EmptyStateWidget {
+    title: String,
+    text: String,
+    // `empty_widget:` drawn above the call-to-action
+    custom: Option<Box<dyn Widget>>,
+    action: Option<EmptyAction>,
+    // Command whose empty result this is; `r` runs it again
+    source: Option<String>,
+}
+
+impl EmptyStateWidget {
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+}
+
+impl Widget for EmptyStateWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
+        let theme = crate::theme::active();
+        let hint = self
+            .action
+            .as_ref()
+            .map(|a| format!("{} → {}", key_label(a.key), a.label));
+        if let Some(w) = self.custom.as_mut() {
+            let mut area = area;
+            if let Some(hint) = hint {
+                if area.height > 3 {
+                    area.height -= 1;
+                    let bar = Rect {
+                        y: area.y + area.height,
+                        height: 1,
+                        ..area
+                    };
+                    f.render_widget(
+                        Paragraph::new(Line::from(Span::styled(
+                            format!(" {hint}"),
+                            theme.text_active_bold(),
+                        ))),
+                        bar,
+                    );
+                }
+            }
+            w.render(f, area, focused, tick);
+            return;
+        }
+        let title = fit_title(&self.title, area);
+        let block = panel_block(&title, focused);
+        let inner_h = area.height.saturating_sub(2);
+        let mut lines = vec![Line::from(Span::styled(
+            self.text.clone(),
+            theme.text_muted(),
+        ))];
+        if let Some(hint) = hint {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(hint, theme.text_active_bold())));
+        }
+        // Roughly centered vertically
+        let pad = inner_h.saturating_sub(lines.len() as u16) / 2;
+        let mut padded = vec![Line::from(""); pad as usize];
+        padded.extend(lines);
+        f.render_widget(
+            Paragraph::new(padded)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .block(block),
+            area,
+        );
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<Effect> {
+        if let Some(a) = self.action.as_ref().filter(|a| a.key == key) {
+            return vec![Effect::OpenItem(a.item.clone())];
+        }
+        match self.custom.as_mut() {
+            Some(w) => w.on_key(key),
+            None => Vec::new(),
+        }
+    }
+
+    fn refresh(&mut self) -> Vec<Effect> {
+        match &self.source {
+            Some(cmdline) => vec![Effect::LoadPanelCmd {
+                pane: crate::ui::PanelPane::B,
+                cmdline: cmdline.clone(),
+            }],
+            None => Vec::new(),
+        }
+    }
+
+    fn actions(&self) -> Vec<WidgetAction> {
+        let mut out = Vec::new();
+        if let Some(a) = &self.action {
+            out.push(WidgetAction::key("Open linked item", "", a.key));
+        }
+        if self.source.is_some() {
+            out.push(WidgetAction::refresh());
+        }
+        out
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn render_text(w: &mut dyn Widget) -> String {
+        let backend = ratatui::backend::TestBackend::new(60, 8);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| w.render(f, f.area(), true, 0)).unwrap();
+        let buf = terminal.backend().buffer().clone();
+        buf.content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn empty_results_show_the_text_and_open_the_linked_item() {
+        assert!(is_empty_result(&json!([])));
+        assert!(is_empty_result(&json!({"ok": true, "data": []})));
+        assert!(!is_empty_result(&json!([{"id": 1}])));
+        assert!(!is_empty_result(&json!({"ok": true})));
+
+        let menu = vec![MenuItem {
+            id: "new_deploy".into(),
+            title: "New deployment".into(),
+            ..Default::default()
+        }];
+        let spec = json!({
+            "type": "json_viewer",
+            "cmd": "app deploys",
+            "empty_text": "No deployments yet",
+            "empty_action": {"key": "n", "item": "new_deploy"}
+        });
+        let empty = EmptyState::from_spec(&spec, &menu).unwrap();
+        let mut w = empty.widget("Deploys", Some("app deploys".into()));
+        let text = render_text(w.as_mut());
+        assert!(text.contains("No deployments yet"), "{text}");
+        assert!(text.contains("n → New deployment"), "{text}");
+        assert!(matches!(
+            w.on_key(KeyCode::Char('n')).as_slice(),
+            [Effect::OpenItem(id)] if id == "new_deploy"
+        ));
+        assert!(w.on_key(KeyCode::Char('x')).is_empty());
+
+        assert!(EmptyState::from_spec(&json!({"type": "json_viewer"}), &menu).is_none());
+        assert_eq!(parse_key(Some("Enter")), Some(KeyCode::Enter));
+        assert_eq!(parse_key(Some("nn")), None);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::app::Effect;
+use crate::ui::RetryTarget;
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::{Widget, WidgetAction};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Paragraph, Wrap};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+
+// Error boundary around Pane B widgets: a panic in render/on_key is caught, its message kept,
+// and the widget swapped for an ErrorWidget (with a reload) while the rest of the UI runs on.
+
+thread_local! {
+    // Inside `call`: the panic hook records the message instead of printing over the screen
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+    static MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+    // Failure waiting for the caller to swap the widget out
+    static FAILED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Chain a panic hook that stays quiet for guarded calls; anything else reaches the old hook
+pub fn install_hook() {
+    static ONCE: std::sync::Once = std::sync::Once::new();
+    ONCE.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !GUARDED.with(|g| g.get()) {
+                previous(info);
+                return;
+            }
+            let mut message = payload_text(info.payload());
+            if let Some(at) = info.location() {
+                message.push_str(&format!(" ({}:{})", at.file(), at.line()));
+            }
+            MESSAGE.with(|m| *m.borrow_mut() = Some(message));
+        }));
+    });
+}
+
+fn payload_text(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "widget panicked".to_string()
+    }
+}
+
+// Run a widget call; a panic is recorded for `take_failure` and the call yields the default
+pub fn call<R: Default>(f: impl FnOnce() -> R) -> R {
+    let outer = GUARDED.with(|g| g.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    GUARDED.with(|g| g.set(outer));
+    match result {
+        Ok(r) => r,
+        Err(payload) => {
+            let message = MESSAGE
+                .with(|m| m.borrow_mut().take())
+                .unwrap_or_else(|| payload_text(payload.as_ref()));
+            FAILED.with(|f| {
+                f.borrow_mut().get_or_insert(message);
+            });
+            R::default()
+        }
+    }
+}
+
+// Message of the last caught panic (once)
+pub fn take_failure() -> Option<String> {
+    FAILED.with(|f| f.borrow_mut().take())
+}
+
+// Stands in for a widget that panicked: shows the message, `r` enters its item again
+pub struct ErrorWidget {
+    title: String,
+    message: String,
+    reload: Option<RetryTarget>,
+}
+
+impl ErrorWidget {
+    pub fn new(title: String, message: String, reload: Option<RetryTarget>) -> Self {
+        Self {
+            title,
+            message,
+            reload,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Widget for ErrorWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, _tick: u64) {
+        let theme = crate::theme::active();
+        let title = fit_title(&format!("{} — crashed", self.title), area);
+        let hint = if self.reload.is_some() {
+            "r reload • Ctrl+C copy the message"
+        } else {
+            "Ctrl+C copy the message"
+        };
+        let lines = vec![
+            Line::from(Span::styled(
+                "This widget stopped after an internal error:",
+                theme.text_error(),
+            )),
+            Line::from(""),
+            Line::from(self.message.clone()),
+            Line::from(""),
+            Line::from(Span::styled(hint, theme.text_muted())),
+        ];
+        f.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(panel_block(&title, focused)),
+            area,
+        );
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<Effect> {
+        match key {
+            KeyCode::Char('r') => self.refresh(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn refresh(&mut self) -> Vec<Effect> {
+        self.reload.clone().map(Effect::Enter).into_iter().collect()
+    }
+
+    fn actions(&self) -> Vec<WidgetAction> {
+        let mut out = Vec::new();
+        if self.reload.is_some() {
+            out.push(WidgetAction {
+                label: "Reload",
+                keys: "r",
+                kind: crate::widgets::ActionKind::Refresh,
+            });
+        }
+        out.extend(WidgetAction::content());
+        out
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::MenuItem;
+
+    struct Crashing;
+
+    impl Widget for Crashing {
+        fn render(&mut self, _f: &mut Frame, _area: Rect, _focused: bool, _tick: u64) {}
+        fn on_key(&mut self, _key: KeyCode) -> Vec<Effect> {
+            let rows: Vec<u32> = Vec::new();
+            vec![Effect::UpdateRow {
+                cmdline: rows[3].to_string(),
+            }]
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn panics_are_caught_and_reload_reenters_the_item() {
+        install_hook();
+        let mut w: Box<dyn Widget> = Box::new(Crashing);
+        let effs = call(|| w.on_key(KeyCode::Down));
+        assert!(effs.is_empty());
+        let message = take_failure().unwrap();
+        assert!(message.contains("index out of bounds"), "{message}");
+        assert!(message.contains("error_boundary.rs"), "{message}");
+        assert_eq!(take_failure(), None);
+        assert_eq!(call(|| 7), 7);
+        assert_eq!(take_failure(), None);
+
+        let mi = MenuItem {
+            id: "pods".into(),
+            ..Default::default()
+        };
+        let mut err = ErrorWidget::new("Pods".into(), message, Some(RetryTarget::Menu(mi)));
+        match err.on_key(KeyCode::Char('r')).as_slice() {
+            [Effect::Enter(RetryTarget::Menu(mi))] => assert_eq!(mi.id, "pods"),
+            _ => panic!("expected a reload"),
+        }
+        assert!(ErrorWidget::new("x".into(), "boom".into(), None)
+            .refresh()
+            .is_empty());
+    }
+}
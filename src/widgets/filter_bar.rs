@@ -0,0 +1,177 @@
+use crate::model::FilterSpec;
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use serde_json::Value as JsonValue;
+
+// Filter bar above a result pane (`filters:` on the item). A filter whose `{name}`
+// appears in the command re-runs it with the value substituted; other filters keep
+// the items whose `field` (default: name) equals the value. `all` matches everything.
+pub struct FilterBar {
+    specs: Vec<FilterSpec>,
+    // Selected option per filter (the first option initially)
+    selected: Vec<usize>,
+    focus: usize,
+    // Command with `{name}` placeholders
+    template: String,
+}
+
+impl FilterBar {
+    pub fn new(specs: Vec<FilterSpec>, template: String) -> Self {
+        Self {
+            selected: vec![0; specs.len()],
+            specs,
+            focus: 0,
+            template,
+        }
+    }
+
+    fn value(&self, i: usize) -> &str {
+        self.specs[i]
+            .options
+            .get(self.selected[i])
+            .map(|s| s.as_str())
+            .unwrap_or("all")
+    }
+
+    fn server_side(&self, i: usize) -> bool {
+        self.template
+            .contains(&format!("{{{}}}", self.specs[i].name))
+    }
+
+    // The command with the selected values substituted
+    pub fn command(&self) -> String {
+        (0..self.specs.len()).fold(self.template.clone(), |cmd, i| {
+            cmd.replace(&format!("{{{}}}", self.specs[i].name), self.value(i))
+        })
+    }
+
+    // Client-side filters applied to a top-level array (other values pass through)
+    pub fn apply(&self, value: &JsonValue) -> JsonValue {
+        let Some(items) = value.as_array() else {
+            return value.clone();
+        };
+        let active: Vec<(&str, &str)> = (0..self.specs.len())
+            .filter(|&i| !self.server_side(i) && self.value(i) != "all")
+            .map(|i| {
+                let spec = &self.specs[i];
+                (spec.field.as_deref().unwrap_or(&spec.name), self.value(i))
+            })
+            .collect();
+        JsonValue::Array(
+            items
+                .iter()
+                .filter(|item| {
+                    active.iter().all(|(field, want)| match item.get(*field) {
+                        Some(JsonValue::String(s)) => s.eq_ignore_ascii_case(want),
+                        Some(JsonValue::Null) | None => false,
+                        // Numbers and booleans: "3", "true"
+                        Some(other) => want.parse::<JsonValue>().ok().as_ref() == Some(other),
+                    })
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+
+    // "status=failed, env=prod" for the pane title (filters not set to `all`)
+    pub fn summary(&self) -> String {
+        (0..self.specs.len())
+            .filter(|&i| self.value(i) != "all")
+            .map(|i| format!("{}={}", self.specs[i].name, self.value(i)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn line(&self) -> Line<'static> {
+        let theme = crate::theme::active();
+        let mut spans = vec![Span::styled(" ⏷ ", theme.text_muted())];
+        for (i, spec) in self.specs.iter().enumerate() {
+            let name_style = if i == self.focus {
+                theme.list_cursor_style()
+            } else {
+                theme.text_muted()
+            };
+            spans.push(Span::styled(format!("{}:", spec.name), name_style));
+            for (j, opt) in spec.options.iter().enumerate() {
+                let style = if j == self.selected[i] {
+                    Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else {
+                    theme.text_muted()
+                };
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(format!(" {opt} "), style));
+            }
+            spans.push(Span::raw("   "));
+        }
+        spans.push(Span::styled("v value • < > filter", theme.text_muted()));
+        Line::from(spans)
+    }
+
+    pub fn multiple(&self) -> bool {
+        self.specs.len() > 1
+    }
+
+    // Some(true) when the change needs the command re-run, Some(false) when it filters
+    // locally, None when the key is not for the bar
+    pub fn on_key(&mut self, key: KeyCode) -> Option<bool> {
+        if self.specs.is_empty() {
+            return None;
+        }
+        match key {
+            KeyCode::Char('>') => self.focus = (self.focus + 1) % self.specs.len(),
+            KeyCode::Char('<') => {
+                self.focus = (self.focus + self.specs.len() - 1) % self.specs.len()
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                let n = self.specs[self.focus].options.len().max(1);
+                let cur = &mut self.selected[self.focus];
+                *cur = if key == KeyCode::Char('v') {
+                    (*cur + 1) % n
+                } else {
+                    (*cur + n - 1) % n
+                };
+                return Some(self.server_side(self.focus));
+            }
+            _ => return None,
+        }
+        Some(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(name: &str, options: &[&str]) -> FilterSpec {
+        FilterSpec {
+            name: name.into(),
+            options: options.iter().map(|s| s.to_string()).collect(),
+            field: None,
+        }
+    }
+
+    #[test]
+    fn filters_substitute_or_filter_locally() {
+        let mut bar = FilterBar::new(
+            vec![
+                spec("env", &["prod", "dev"]),
+                spec("status", &["all", "failed"]),
+            ],
+            "app jobs --env {env}".into(),
+        );
+        assert_eq!(bar.command(), "app jobs --env prod");
+        assert_eq!(bar.summary(), "env=prod");
+        // env is in the command: changing it re-runs
+        assert_eq!(bar.on_key(KeyCode::Char('v')), Some(true));
+        assert_eq!(bar.command(), "app jobs --env dev");
+
+        let rows = json!([{"status": "Failed"}, {"status": "running"}, {"id": 3}]);
+        assert_eq!(bar.apply(&rows).as_array().unwrap().len(), 3);
+        bar.on_key(KeyCode::Char('>'));
+        assert_eq!(bar.on_key(KeyCode::Char('v')), Some(false));
+        assert_eq!(bar.apply(&rows), json!([{"status": "Failed"}]));
+        assert_eq!(bar.summary(), "env=dev, status=failed");
+        assert_eq!(bar.on_key(KeyCode::Char('x')), None);
+    }
+}
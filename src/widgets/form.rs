@@ -94,6 +94,14 @@ pub struct FormState {
     pub dirty: bool,
     pub initial: Vec<FieldInitial>,
     pub confirm: Option<ConfirmAction>,
+    // Names of fields whose dynamic options are being fetched (rendered with a spinner)
+    pub options_loading: Vec<String>,
+    // Render tick, drives the per-field loading spinners
+    pub tick: u64,
+    // Caret (byte offset) in the single-line field being edited; None = after the text
+    pub caret: Option<usize>,
+    // Step (index, count) when the form is one page of a wizard: Next/Back replace Save/Reset
+    pub step: Option<(usize, usize)>,
 }
 
 #[derive(Clone, Debug)]
@@ -284,7 +292,7 @@ pub fn draw_form(
                     }
                 };
                 if form.editing && i == form.selected && cursor_on {
-                    val.push('▏');
+                    val = crate::widgets::line_edit::with_marker(&val, form.caret, '▏');
                 }
                 let value_style = if i == form.selected {
                     if form.editing {
@@ -304,11 +312,12 @@ pub fn draw_form(
                 // Render masked, keep actual text in value
                 let mut masked = String::new();
                 if let FieldValue::Text(s) = &fld.value {
-                    let n = s.chars().count();
+                    // One dot per character as typed (an accented letter is one)
+                    let (n, before) = crate::widgets::line_edit::grapheme_counts(s, form.caret);
                     masked = "•".repeat(n);
-                }
-                if form.editing && i == form.selected && cursor_on {
-                    masked.push('▏');
+                    if form.editing && i == form.selected && cursor_on {
+                        masked.insert(before * '•'.len_utf8(), '▏');
+                    }
                 }
                 let value_style = if i == form.selected {
                     if form.editing {
@@ -425,7 +434,7 @@ pub fn draw_form(
                     }
                 };
                 if form.editing && i == form.selected && cursor_on {
-                    val.push('▏');
+                    val = crate::widgets::line_edit::with_marker(&val, form.caret, '▏');
                 }
                 let value_style = if i == form.selected {
                     if form.editing {
@@ -447,7 +456,7 @@ pub fn draw_form(
                     FieldValue::Bool(_) => String::new(),
                 };
                 if form.editing && i == form.selected && cursor_on {
-                    val.push('▏');
+                    val = crate::widgets::line_edit::with_marker(&val, form.caret, '▏');
                 }
                 let value_style = if i == form.selected {
                     if form.editing {
@@ -486,10 +495,13 @@ pub fn draw_form(
                 ..
             } => {
                 // Header line with current selection summary
-                let summary = options
+                let mut summary = options
                     .get(*selected)
                     .cloned()
                     .unwrap_or_else(|| "(none)".into());
+                if form.options_loading.contains(&fld.name) {
+                    summary.push_str(&options_spinner(form.tick));
+                }
                 let header_style = if i == form.selected && form.editing {
                     crate::theme::text_editing_bold()
                 } else if i == form.selected {
@@ -529,7 +541,10 @@ pub fn draw_form(
             } => {
                 // Header with count summary
                 let count = selected.iter().filter(|b| **b).count();
-                let summary = format!("{count} selected");
+                let mut summary = format!("{count} selected");
+                if form.options_loading.contains(&fld.name) {
+                    summary.push_str(&options_spinner(form.tick));
+                }
                 let header_style = if i == form.selected && form.editing {
                     Style::default()
                         .fg(Color::Rgb(255, 165, 0))
@@ -585,15 +600,25 @@ pub fn draw_form(
     let save_idx = form.fields.len();
     let reset_idx = form.fields.len() + 1;
     let cancel_idx = form.fields.len() + 2;
-    let can_save = form.submit_cmd.is_some() && !form.disabled && form.dirty;
-    let can_reset = form.dirty && !form.disabled;
-    let save_label = "[ Save ]";
+    let (can_save, can_reset, save_label, reset_label) = match form.step {
+        Some((i, n)) => (
+            !form.disabled,
+            i > 0 && !form.disabled,
+            if i + 1 == n { "[ Submit ]" } else { "[ Next ]" },
+            "Back",
+        ),
+        None => (
+            form.submit_cmd.is_some() && !form.disabled && form.dirty,
+            form.dirty && !form.disabled,
+            "[ Save ]",
+            "Reset",
+        ),
+    };
     let mut save_style = if can_save {
         crate::theme::text_active_bold()
     } else {
         crate::theme::text_muted()
     };
-    let reset_label = "Reset";
     let mut reset_style = if can_reset {
         Style::default().fg(crate::theme::ACTIVE)
     } else {
@@ -626,11 +651,18 @@ pub fn draw_form(
             crate::theme::text_muted(),
         )));
     }
-    let title = if form.editing {
-        format!("{} — editing", form.title)
-    } else {
-        form.title.clone()
-    };
+    let mut title = form.title.clone();
+    if let Some((i, n)) = form.step {
+        title = format!("{title} ({}/{n})", i + 1);
+    }
+    if form.editing {
+        title.push_str(" — editing");
+    }
+    // Unsaved changes badge
+    if form.dirty {
+        title.push_str(" •");
+    }
+    let title = crate::widgets::chrome::fit_title(&title, area);
     let block = panel_block(&title, highlight);
     let p = Paragraph::new(lines)
         .block(block)
@@ -638,6 +670,12 @@ pub fn draw_form(
     f.render_widget(p, area);
 }
 
+// Spinner suffix shown next to select fields while their options load
+fn options_spinner(tick: u64) -> String {
+    let frame = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][tick as usize % 6];
+    format!("  {frame} loading options…")
+}
+
 pub fn kebab_case(name: &str) -> String {
     let mut out = String::new();
     for (i, ch) in name.chars().enumerate() {
@@ -662,10 +700,8 @@ pub fn build_cmdline(form: &FormState) -> Option<String> {
     let mut parts: Vec<String> = vec![base];
     for fld in &form.fields {
         match (&fld.kind, &fld.value) {
-            (FieldKind::Checkbox, FieldValue::Bool(b)) => {
-                if *b {
-                    parts.push(format!("--{}", kebab_case(&fld.name)));
-                }
+            (FieldKind::Checkbox, FieldValue::Bool(b)) if *b => {
+                parts.push(format!("--{}", kebab_case(&fld.name)));
             }
             (
                 FieldKind::Select {
@@ -676,18 +712,20 @@ pub fn build_cmdline(form: &FormState) -> Option<String> {
                 },
                 _,
             ) => {
-                if !options.is_empty() {
-                    let v = values
+                let v = if options.is_empty() {
+                    String::new()
+                } else {
+                    values
                         .get(*selected)
                         .cloned()
-                        .unwrap_or_else(|| options.get(*selected).cloned().unwrap_or_default());
-                    if !v.is_empty() {
-                        parts.push(format!("--{}", kebab_case(&fld.name)));
-                        if v.contains(' ') {
-                            parts.push(format!("'{}'", v.replace("'", "'\\''")));
-                        } else {
-                            parts.push(v);
-                        }
+                        .unwrap_or_else(|| options.get(*selected).cloned().unwrap_or_default())
+                };
+                if !v.is_empty() {
+                    parts.push(format!("--{}", kebab_case(&fld.name)));
+                    if v.contains(' ') {
+                        parts.push(format!("'{}'", v.replace("'", "'\\''")));
+                    } else {
+                        parts.push(v);
                     }
                 }
             }
@@ -733,15 +771,15 @@ pub fn build_cmdline(form: &FormState) -> Option<String> {
                     }
                 }
             }
-            (FieldKind::Number { .. }, FieldValue::Text(s)) | (_, FieldValue::Text(s)) => {
-                if !s.is_empty() {
-                    parts.push(format!("--{}", kebab_case(&fld.name)));
-                    // naive quoting if whitespace/newlines present
-                    if s.contains(' ') || s.contains('\n') || s.contains('\t') {
-                        parts.push(format!("'{}'", s.replace("'", "'\\''")));
-                    } else {
-                        parts.push(s.clone());
-                    }
+            (FieldKind::Number { .. }, FieldValue::Text(s)) | (_, FieldValue::Text(s))
+                if !s.is_empty() =>
+            {
+                parts.push(format!("--{}", kebab_case(&fld.name)));
+                // naive quoting if whitespace/newlines present
+                if s.contains(' ') || s.contains('\n') || s.contains('\t') {
+                    parts.push(format!("'{}'", s.replace("'", "'\\''")));
+                } else {
+                    parts.push(s.clone());
                 }
             }
             _ => {}
@@ -750,6 +788,28 @@ pub fn build_cmdline(form: &FormState) -> Option<String> {
     Some(parts.join(" "))
 }
 
+// Field names that are treated as secrets in previews even when not declared as password
+fn looks_secret(name: &str) -> bool {
+    let n = name.to_ascii_lowercase();
+    ["password", "passwd", "secret", "token", "api_key", "apikey"]
+        .iter()
+        .any(|k| n.contains(k))
+}
+
+// Same as build_cmdline, but with password/secret-looking values masked (for previews)
+pub fn build_cmdline_masked(form: &FormState) -> Option<String> {
+    let mut masked = form.clone();
+    for fld in &mut masked.fields {
+        let secret = matches!(fld.kind, FieldKind::Password) || looks_secret(&fld.name);
+        if let (true, FieldValue::Text(s)) = (secret, &mut fld.value) {
+            if !s.is_empty() {
+                *s = "********".to_string();
+            }
+        }
+    }
+    build_cmdline(&masked)
+}
+
 /// Build form fields from a JSON Schema-like object (Pydantic input_schema).
 /// Supports: required flags, enums -> select, arrays with items.enum -> multiselect,
 /// numbers/integers -> number, booleans -> checkbox, strings -> text.
@@ -1115,11 +1175,9 @@ pub fn validate_form(form: &mut FormState) -> bool {
                     }
                 }
             }
-            (FieldKind::Select { options, .. }, _) => {
-                if fld.required && options.is_empty() {
-                    fld.error = Some("No options available".into());
-                    ok = false;
-                }
+            (FieldKind::Select { options, .. }, _) if fld.required && options.is_empty() => {
+                fld.error = Some("No options available".into());
+                ok = false;
             }
             (
                 FieldKind::MultiSelect {
@@ -1241,6 +1299,10 @@ mod tests {
             dirty: false,
             initial: vec![],
             confirm: None,
+            options_loading: vec![],
+            tick: 0,
+            caret: None,
+            step: None,
         };
         assert!(validate_form(&mut form));
         match &form.fields[0].value {
@@ -1270,6 +1332,10 @@ mod tests {
             dirty: false,
             initial: vec![],
             confirm: None,
+            options_loading: vec![],
+            tick: 0,
+            caret: None,
+            step: None,
         };
         assert!(!validate_form(&mut form));
         assert!(form.fields[0].error.as_deref().unwrap().contains("< 1"));
@@ -1310,6 +1376,10 @@ mod tests {
             dirty: false,
             initial: vec![],
             confirm: None,
+            options_loading: vec![],
+            tick: 0,
+            caret: None,
+            step: None,
         };
         assert!(!validate_form(&mut form));
         assert!(form.fields[0]
@@ -1341,6 +1411,10 @@ mod tests {
             dirty: false,
             initial: vec![],
             confirm: None,
+            options_loading: vec![],
+            tick: 0,
+            caret: None,
+            step: None,
         };
         form.fields.push(FormField {
             name: "name".into(),
@@ -1458,6 +1532,45 @@ mod tests {
         assert!(cmd.contains("--nums b"));
     }
 
+    #[test]
+    fn build_cmdline_masked_hides_secrets() {
+        let mut form = FormState {
+            submit_cmd: Some("prog login".into()),
+            ..Default::default()
+        };
+        for (name, kind, val) in [
+            ("user", FieldKind::Text, "ada"),
+            ("pass", FieldKind::Password, "hunter2"),
+            ("api_token", FieldKind::Text, "abc123"),
+        ] {
+            form.fields.push(FormField {
+                name: name.into(),
+                label: name.into(),
+                required: false,
+                kind,
+                value: FieldValue::Text(val.into()),
+                error: None,
+                text_min_len: None,
+                text_max_len: None,
+                text_pattern: None,
+                textarea_max_lines: None,
+                dyn_options_cmd: None,
+                dyn_unwrap: None,
+                dyn_loaded: false,
+                dyn_loaded_at: None,
+                group: None,
+                order: None,
+            });
+        }
+        let masked = build_cmdline_masked(&form).unwrap();
+        assert!(masked.contains("--user ada"));
+        assert!(masked.contains("--pass ********"));
+        assert!(masked.contains("--api-token ********"));
+        assert!(!masked.contains("hunter2"));
+        // The real command line is untouched
+        assert!(build_cmdline(&form).unwrap().contains("--pass hunter2"));
+    }
+
     #[test]
     fn golden_select_editor_renders_expected_window() {
         // Prepare a form with a single required Select field in editing mode
@@ -1507,6 +1620,10 @@ mod tests {
             dirty: false,
             initial: vec![],
             confirm: None,
+            options_loading: vec![],
+            tick: 0,
+            caret: None,
+            step: None,
         };
         let backend = TestBackend::new(40, 12);
         let mut terminal = Terminal::new(backend).unwrap();
@@ -1600,6 +1717,10 @@ mod tests {
             dirty: false,
             initial: vec![],
             confirm: None,
+            options_loading: vec![],
+            tick: 0,
+            caret: None,
+            step: None,
         };
         let backend = TestBackend::new(40, 12);
         let mut terminal = Terminal::new(backend).unwrap();
@@ -0,0 +1,446 @@
+use crate::widgets::form::{build_cmdline, build_cmdline_masked, validate_form};
+use crate::widgets::form::{FieldKind, FieldValue, FormState};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+// Batch submission (`batch:` on a form spec): the rows of a CSV or JSON-array file are
+// mapped onto the form's fields (by name or label, or through `batch.columns`), each row
+// is validated like a manual submit, and the valid ones are submitted one after another.
+// Columns missing from the file keep the value currently in the form.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowStatus {
+    Pending,
+    // Failed validation; never submitted
+    Invalid(String),
+    Done,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchRow {
+    pub values: Map<String, JsonValue>,
+    pub cmdline: Option<String>,
+    // Shown in the preview (secrets masked)
+    pub masked: Option<String>,
+    pub status: RowStatus,
+}
+
+pub struct Batch {
+    pub path: String,
+    pub rows: Vec<BatchRow>,
+    pub running: bool,
+    // First row shown in the report
+    pub scroll: usize,
+}
+
+// `batch: true` or `batch: { columns: { "<file column>": <field name> } }`
+pub fn columns_from_spec(v: Option<&JsonValue>) -> Option<HashMap<String, String>> {
+    match v? {
+        JsonValue::Bool(true) => Some(HashMap::new()),
+        JsonValue::Object(o) => Some(
+            o.get("columns")
+                .and_then(|c| c.as_object())
+                .map(|c| {
+                    c.iter()
+                        .filter_map(|(col, f)| Some((col.clone(), f.as_str()?.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        ),
+        _ => None,
+    }
+}
+
+// Rows of a JSON array of objects, or of a CSV file with a header line
+pub fn read_rows(path: &str) -> Result<Vec<Map<String, JsonValue>>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    if path.to_ascii_lowercase().ends_with(".json") || text.trim_start().starts_with('[') {
+        let v: JsonValue = serde_json::from_str(&text).map_err(|e| format!("{path}: {e}"))?;
+        let arr = v
+            .as_array()
+            .ok_or_else(|| format!("{path}: expected a JSON array of objects"))?;
+        return arr
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                r.as_object()
+                    .cloned()
+                    .ok_or_else(|| format!("{path}: item {} is not an object", i + 1))
+            })
+            .collect();
+    }
+    let mut records = crate::services::csv::parse(&text, false)
+        .into_iter()
+        .map(|r| {
+            r.into_iter()
+                .map(Option::unwrap_or_default)
+                .collect::<Vec<_>>()
+        });
+    let header = records
+        .next()
+        .ok_or_else(|| format!("{path}: empty file"))?;
+    Ok(records
+        .filter(|r| r.iter().any(|c| !c.is_empty()))
+        .map(|r| {
+            header
+                .iter()
+                .zip(r)
+                .map(|(h, c)| (h.trim().to_string(), JsonValue::String(c)))
+                .collect()
+        })
+        .collect())
+}
+
+fn cell_text(v: &JsonValue) -> String {
+    match v {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Null => String::new(),
+        JsonValue::Array(items) => items.iter().map(cell_text).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+// Fill a copy of the form from one row; Err names a value that fits no option
+fn apply_row(
+    form: &mut FormState,
+    row: &Map<String, JsonValue>,
+    columns: &HashMap<String, String>,
+) -> Result<(), String> {
+    for fld in &mut form.fields {
+        let mapped = columns
+            .iter()
+            .find(|(_, f)| **f == fld.name)
+            .and_then(|(col, _)| row.get(col));
+        let Some(raw) = mapped.or_else(|| {
+            row.iter()
+                .find(|(k, _)| {
+                    k.eq_ignore_ascii_case(&fld.name) || k.eq_ignore_ascii_case(&fld.label)
+                })
+                .map(|(_, v)| v)
+        }) else {
+            continue;
+        };
+        let text = cell_text(raw);
+        match &mut fld.kind {
+            FieldKind::Checkbox => {
+                let on = raw.as_bool().unwrap_or_else(|| {
+                    matches!(
+                        text.trim().to_ascii_lowercase().as_str(),
+                        "true" | "yes" | "y" | "1" | "x"
+                    )
+                });
+                fld.value = FieldValue::Bool(on);
+            }
+            FieldKind::Select {
+                options,
+                values,
+                selected,
+                cursor,
+                ..
+            } => {
+                let want = text.trim();
+                if want.is_empty() {
+                    continue;
+                }
+                let idx = values
+                    .iter()
+                    .chain(options.iter())
+                    .position(|o| o.eq_ignore_ascii_case(want))
+                    .map(|i| {
+                        if i >= values.len() {
+                            i - values.len()
+                        } else {
+                            i
+                        }
+                    })
+                    .ok_or_else(|| format!("{}: '{want}' is not an option", fld.name))?;
+                *selected = idx;
+                *cursor = idx;
+            }
+            FieldKind::MultiSelect {
+                options,
+                values,
+                selected,
+                ..
+            } => {
+                selected.iter_mut().for_each(|s| *s = false);
+                for want in text
+                    .split([',', ';'])
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                {
+                    let idx = (0..options.len())
+                        .find(|&i| {
+                            values
+                                .get(i)
+                                .unwrap_or(&options[i])
+                                .eq_ignore_ascii_case(want)
+                                || options[i].eq_ignore_ascii_case(want)
+                        })
+                        .ok_or_else(|| format!("{}: '{want}' is not an option", fld.name))?;
+                    if let Some(s) = selected.get_mut(idx) {
+                        *s = true;
+                    }
+                }
+            }
+            _ => fld.value = FieldValue::Text(text),
+        }
+    }
+    Ok(())
+}
+
+// One planned submission per row: the command, or why the row is skipped
+pub fn plan(
+    form: &FormState,
+    rows: Vec<Map<String, JsonValue>>,
+    columns: &HashMap<String, String>,
+) -> Vec<BatchRow> {
+    rows.into_iter()
+        .map(|values| {
+            let mut f = form.clone();
+            let status = match apply_row(&mut f, &values, columns) {
+                Err(e) => RowStatus::Invalid(e),
+                Ok(()) if !validate_form(&mut f) => RowStatus::Invalid(
+                    f.fields
+                        .iter()
+                        .filter_map(|fld| Some(format!("{}: {}", fld.name, fld.error.as_ref()?)))
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                ),
+                Ok(()) => RowStatus::Pending,
+            };
+            let valid = status == RowStatus::Pending;
+            BatchRow {
+                cmdline: build_cmdline(&f).filter(|_| valid),
+                masked: build_cmdline_masked(&f).filter(|_| valid),
+                values,
+                status,
+            }
+        })
+        .collect()
+}
+
+// Error text of a submit reply (`ok: false` or `type: error` envelope), if it failed
+pub fn envelope_error(v: &JsonValue) -> Option<String> {
+    match crate::chi_core::envelope::Envelope::parse(v) {
+        Ok(env) => env
+            .failed()
+            .then(|| env.message().unwrap_or("Submit failed").to_string()),
+        Err(e) => Some(e),
+    }
+}
+
+impl Batch {
+    pub fn new(path: String, rows: Vec<BatchRow>) -> Self {
+        Self {
+            path,
+            rows,
+            running: false,
+            scroll: 0,
+        }
+    }
+
+    // (row index, command) of every row still to submit
+    pub fn pending(&self) -> Vec<(usize, String)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.status == RowStatus::Pending)
+            .filter_map(|(i, r)| Some((i, r.cmdline.clone()?)))
+            .collect()
+    }
+
+    // (finished, to run) over the rows that are submitted
+    pub fn progress(&self) -> (usize, usize) {
+        let runnable = self.rows.iter().filter(|r| r.cmdline.is_some());
+        let done = runnable
+            .clone()
+            .filter(|r| matches!(r.status, RowStatus::Done | RowStatus::Failed(_)))
+            .count();
+        (done, runnable.count())
+    }
+
+    pub fn finished(&self) -> bool {
+        let (done, total) = self.progress();
+        done == total
+    }
+
+    pub fn row_done(&mut self, index: usize, result: Result<(), String>) {
+        if let Some(row) = self.rows.get_mut(index) {
+            row.status = match result {
+                Ok(()) => RowStatus::Done,
+                Err(e) => RowStatus::Failed(e),
+            };
+        }
+        if self.finished() {
+            self.running = false;
+        }
+    }
+
+    pub fn failures(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|r| matches!(r.status, RowStatus::Invalid(_) | RowStatus::Failed(_)))
+            .count()
+    }
+
+    // Invalid and failed rows with their values, as written by the error export
+    pub fn errors(&self) -> JsonValue {
+        JsonValue::Array(
+            self.rows
+                .iter()
+                .enumerate()
+                .filter_map(|(i, r)| {
+                    let err = match &r.status {
+                        RowStatus::Invalid(e) | RowStatus::Failed(e) => e,
+                        _ => return None,
+                    };
+                    let mut out = Map::new();
+                    out.insert("row".into(), JsonValue::from(i + 1));
+                    out.insert("error".into(), JsonValue::String(err.clone()));
+                    out.extend(r.values.clone());
+                    Some(JsonValue::Object(out))
+                })
+                .collect(),
+        )
+    }
+
+    // Write the error list next to the input file (`<name>.errors.csv` or `.json`)
+    pub fn export_errors(&self) -> Result<String, String> {
+        let errors = self.errors();
+        let csv = !self.path.to_ascii_lowercase().ends_with(".json");
+        let (text, ext) = if csv {
+            let t = crate::services::copy_formats::render(
+                &errors,
+                crate::services::copy_formats::CopyFormat::Csv,
+            )?;
+            (t, "errors.csv")
+        } else {
+            let t = crate::services::copy_formats::render(
+                &errors,
+                crate::services::copy_formats::CopyFormat::Json,
+            )?;
+            (t, "errors.json")
+        };
+        let p = std::path::Path::new(&self.path);
+        let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("batch");
+        let out = p.with_file_name(format!("{stem}.{ext}"));
+        std::fs::write(&out, text).map_err(|e| format!("{}: {e}", out.display()))?;
+        Ok(out.display().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::form::FormField;
+    use serde_json::json;
+
+    fn field(name: &str, kind: FieldKind, required: bool) -> FormField {
+        FormField {
+            name: name.into(),
+            label: name.to_uppercase(),
+            required,
+            kind,
+            value: FieldValue::Text(String::new()),
+            error: None,
+            text_min_len: None,
+            text_max_len: None,
+            text_pattern: None,
+            textarea_max_lines: None,
+            dyn_options_cmd: None,
+            dyn_unwrap: None,
+            dyn_loaded: false,
+            dyn_loaded_at: None,
+            group: None,
+            order: None,
+        }
+    }
+
+    #[test]
+    fn csv_rows_become_validated_submissions() {
+        let form = FormState {
+            submit_cmd: Some("app users add".into()),
+            fields: vec![
+                field("email", FieldKind::Text, true),
+                field(
+                    "role",
+                    FieldKind::Select {
+                        options: vec!["Admin".into(), "Viewer".into()],
+                        values: vec!["admin".into(), "viewer".into()],
+                        cursor: 0,
+                        selected: 0,
+                        offset: 0,
+                    },
+                    false,
+                ),
+            ],
+            ..Default::default()
+        };
+        let dir = std::env::temp_dir().join(format!("chi-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("users.csv");
+        std::fs::write(
+            &path,
+            "Mail,ROLE\na@x,viewer\n\"b, jr\"\"@x\",Admin\n,viewer\nc@x,owner\n",
+        )
+        .unwrap();
+        let rows = read_rows(path.to_str().unwrap()).unwrap();
+        assert_eq!(rows.len(), 4);
+        let columns = HashMap::from([("Mail".to_string(), "email".to_string())]);
+        let mut batch = Batch::new(path.to_str().unwrap().into(), plan(&form, rows, &columns));
+        assert_eq!(
+            batch.pending(),
+            vec![
+                (0, "app users add --email a@x --role viewer".to_string()),
+                (
+                    1,
+                    "app users add --email 'b, jr\"@x' --role admin".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            batch.rows[2].status,
+            RowStatus::Invalid("email: This field is required".into())
+        );
+        assert_eq!(
+            batch.rows[3].status,
+            RowStatus::Invalid("role: 'owner' is not an option".into())
+        );
+        assert_eq!(batch.progress(), (0, 2));
+
+        batch.row_done(0, Ok(()));
+        batch.row_done(1, Err("exists".into()));
+        assert!(batch.finished());
+        assert_eq!(batch.failures(), 3);
+        let errors = batch.errors();
+        assert_eq!(
+            errors[0],
+            json!({"row": 2, "error": "exists", "Mail": "b, jr\"@x", "ROLE": "Admin"})
+        );
+        let out = batch.export_errors().unwrap();
+        assert!(out.ends_with("users.errors.csv"));
+        let written = std::fs::read_to_string(&out).unwrap();
+        assert!(written.contains("exists,2\n"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn envelope_errors_are_detected() {
+        assert_eq!(envelope_error(&json!({"ok": true})), None);
+        assert_eq!(
+            envelope_error(&json!({"ok": false, "data": {"message": "nope"}})),
+            Some("nope".into())
+        );
+        assert_eq!(
+            envelope_error(&json!({"type": "error"})),
+            Some("Submit failed".into())
+        );
+        // Raw JSON that only looks like an envelope is a success
+        assert_eq!(envelope_error(&json!({"ok": 1, "error": false})), None);
+        assert!(envelope_error(&json!({"ok": false, "error": 3}))
+            .unwrap()
+            .starts_with("malformed envelope: `error`"));
+    }
+}
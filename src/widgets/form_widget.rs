@@ -1,4 +1,6 @@
+use crate::theme::Status;
 use crate::widgets::form::{draw_form, FieldKind, FieldValue, FormState, OPTIONS_VISIBLE};
+use crate::widgets::line_edit;
 use crossterm::event::KeyCode;
 use ratatui::crossterm::event as rt_event;
 use ratatui::prelude::*;
@@ -15,9 +17,65 @@ struct NumParams {
     multiple_of: Option<f64>,
 }
 
+// The single-line text being edited (text, password and number fields) and its caret
+fn editing_line(form: &mut FormState) -> Option<(&mut String, &mut Option<usize>)> {
+    if !form.editing {
+        return None;
+    }
+    let fld = form.fields.get_mut(form.selected)?;
+    match (&fld.kind, &mut fld.value) {
+        (FieldKind::Text | FieldKind::Password | FieldKind::Number { .. }, FieldValue::Text(s)) => {
+            Some((s, &mut form.caret))
+        }
+        _ => None,
+    }
+}
+
+// Submit preview behaviour from the form spec (`preview: true|required`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PreviewMode {
+    #[default]
+    Off,
+    // 'v' shows the command that would run
+    On,
+    // Like On, and submitting always goes through the preview first
+    Required,
+}
+
+impl PreviewMode {
+    pub fn from_spec(v: Option<&serde_json::Value>) -> Self {
+        match v {
+            Some(serde_json::Value::Bool(true)) => PreviewMode::On,
+            Some(serde_json::Value::String(s)) => match s.to_ascii_lowercase().as_str() {
+                "required" => PreviewMode::Required,
+                "true" | "on" => PreviewMode::On,
+                _ => PreviewMode::Off,
+            },
+            _ => PreviewMode::Off,
+        }
+    }
+}
+
+// Upper bound of concurrent dynamic-options commands per form
+const MAX_OPTION_FETCHES: usize = 2;
+
 pub struct FormWidget {
     pub form: FormState,
     ta_map: std::collections::HashMap<String, TextArea<'static>>,
+    // Dynamic-options fields waiting for a free fetch slot
+    options_queue: std::collections::VecDeque<String>,
+    pub preview_mode: PreviewMode,
+    // Masked command line shown in the preview popup (Some => popup open)
+    preview: Option<String>,
+    // Exact command line confirmed through the preview
+    previewed_cmd: Option<String>,
+    // `batch:` column → field mapping (Some => batch submission enabled), the input file
+    // path being typed, and the loaded batch (preview, progress and report popup)
+    pub batch_columns: Option<std::collections::HashMap<String, String>>,
+    batch_prompt: Option<String>,
+    batch: Option<crate::widgets::form_batch::Batch>,
+    // `mutex:` of the item that opened the form (submits and batches queue on it)
+    pub mutex: Option<String>,
 }
 
 impl FormWidget {
@@ -42,7 +100,339 @@ impl FormWidget {
                 ta_map.insert(f.name.clone(), ta);
             }
         }
-        Self { form, ta_map }
+        Self {
+            form,
+            ta_map,
+            options_queue: std::collections::VecDeque::new(),
+            preview_mode: PreviewMode::Off,
+            preview: None,
+            previewed_cmd: None,
+            batch_columns: None,
+            batch_prompt: None,
+            batch: None,
+            mutex: None,
+        }
+    }
+
+    pub fn preview_open(&self) -> bool {
+        self.preview.is_some()
+    }
+
+    // The batch file prompt or the batch popup is open (modal like field editing)
+    pub fn batch_open(&self) -> bool {
+        self.batch_prompt.is_some() || self.batch.is_some()
+    }
+
+    // Keys of the batch file prompt and popup: Enter loads the file, then runs the batch;
+    // x exports the error list once finished; Esc closes (not while running)
+    fn batch_on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        use crate::app::Effect;
+        if let Some(path) = &mut self.batch_prompt {
+            match key {
+                KeyCode::Char(c) => path.push(c),
+                KeyCode::Backspace => {
+                    path.pop();
+                }
+                KeyCode::Esc => self.batch_prompt = None,
+                KeyCode::Enter => {
+                    let path = self.batch_prompt.take().unwrap_or_default();
+                    let path = match (path.strip_prefix("~/"), std::env::var("HOME")) {
+                        (Some(rest), Ok(home)) => format!("{home}/{rest}"),
+                        _ => path,
+                    };
+                    let columns = self.batch_columns.clone().unwrap_or_default();
+                    match crate::widgets::form_batch::read_rows(path.trim()) {
+                        Ok(rows) if rows.is_empty() => {
+                            self.form.message = Some(format!("{path}: no rows"));
+                        }
+                        Ok(rows) => {
+                            let planned =
+                                crate::widgets::form_batch::plan(&self.form, rows, &columns);
+                            self.batch = Some(crate::widgets::form_batch::Batch::new(
+                                path.trim().to_string(),
+                                planned,
+                            ));
+                        }
+                        Err(e) => self.form.message = Some(e),
+                    }
+                }
+                _ => {}
+            }
+            return Vec::new();
+        }
+        let Some(batch) = &mut self.batch else {
+            return Vec::new();
+        };
+        let started = batch.rows.iter().any(|r| {
+            matches!(
+                r.status,
+                crate::widgets::form_batch::RowStatus::Done
+                    | crate::widgets::form_batch::RowStatus::Failed(_)
+            )
+        });
+        match key {
+            KeyCode::Up => batch.scroll = batch.scroll.saturating_sub(1),
+            KeyCode::Down => {
+                batch.scroll = (batch.scroll + 1).min(batch.rows.len().saturating_sub(1))
+            }
+            KeyCode::Enter if !batch.running && !started => {
+                let rows = batch.pending();
+                if !rows.is_empty() {
+                    batch.running = true;
+                    return vec![Effect::SubmitBatch {
+                        rows,
+                        mutex: self.mutex.clone(),
+                    }];
+                }
+            }
+            KeyCode::Char('x') if !batch.running && batch.failures() > 0 => {
+                return vec![match batch.export_errors() {
+                    Ok(path) => Effect::ShowToast {
+                        text: format!("Errors written to {path}"),
+                        level: crate::ui::ToastLevel::Success,
+                        seconds: 3,
+                    },
+                    Err(e) => Effect::ShowToast {
+                        text: format!("Export failed: {e}"),
+                        level: crate::ui::ToastLevel::Error,
+                        seconds: 4,
+                    },
+                }];
+            }
+            KeyCode::Esc if !batch.running => self.batch = None,
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    // One batch row finished; returns (finished, total) for the status bar
+    pub fn batch_row_done(
+        &mut self,
+        index: usize,
+        result: Result<(), String>,
+    ) -> Option<(usize, usize)> {
+        let batch = self.batch.as_mut()?;
+        batch.row_done(index, result);
+        Some(batch.progress())
+    }
+
+    // (submitted ok, failed or skipped) rows of the current batch
+    pub fn batch_counts(&self) -> (usize, usize) {
+        self.batch
+            .as_ref()
+            .map(|b| {
+                let ok = b
+                    .rows
+                    .iter()
+                    .filter(|r| r.status == crate::widgets::form_batch::RowStatus::Done)
+                    .count();
+                (ok, b.failures())
+            })
+            .unwrap_or_default()
+    }
+
+    fn draw_batch(&self, f: &mut Frame, area: Rect) {
+        use crate::widgets::form_batch::RowStatus;
+        let theme = crate::theme::active();
+        if let Some(path) = &self.batch_prompt {
+            let rect = centered_rect(70, 30, area);
+            let lines = vec![
+                Line::from(vec![Span::raw("File: "), Span::raw(format!("{path}▌"))]),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "CSV with a header line or a JSON array of objects • Enter load • Esc cancel",
+                    theme.text_muted(),
+                )),
+            ];
+            let p = ratatui::widgets::Paragraph::new(lines)
+                .block(crate::widgets::chrome::panel_block("Batch submit", true))
+                .wrap(ratatui::widgets::Wrap { trim: false });
+            f.render_widget(ratatui::widgets::Clear, rect);
+            f.render_widget(p, rect);
+            return;
+        }
+        let Some(batch) = &self.batch else {
+            return;
+        };
+        let rect = centered_rect(85, 75, area);
+        let name = std::path::Path::new(&batch.path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&batch.path);
+        let title = format!("Batch submit — {name} ({} rows)", batch.rows.len());
+        let block = crate::widgets::chrome::panel_block(&title, true);
+        let inner = block.inner(rect);
+        f.render_widget(ratatui::widgets::Clear, rect);
+        f.render_widget(block, rect);
+        if inner.height < 4 {
+            return;
+        }
+        let (done, total) = batch.progress();
+        // Submitted, failed and skipped rows as patterned segments, readable without color
+        let count = |f: fn(&RowStatus) -> bool| batch.rows.iter().filter(|r| f(&r.status)).count();
+        let segments = [
+            (count(|s| matches!(s, RowStatus::Done)), Status::Ok),
+            (count(|s| matches!(s, RowStatus::Failed(_))), Status::Fail),
+            (count(|s| matches!(s, RowStatus::Invalid(_))), Status::Warn),
+        ];
+        let label = format!("  {done}/{total} submitted");
+        let bar_w = (inner.width as usize)
+            .saturating_sub(crate::widgets::text_width::display_width(&label));
+        let mut bar = crate::widgets::cells::gauge_spans(&segments, total, bar_w, &theme);
+        bar.push(Span::raw(label));
+        f.render_widget(
+            ratatui::widgets::Paragraph::new(Line::from(bar)),
+            Rect { height: 1, ..inner },
+        );
+        let list_h = inner.height.saturating_sub(3) as usize;
+        let lines: Vec<Line> = batch
+            .rows
+            .iter()
+            .enumerate()
+            .skip(batch.scroll)
+            .take(list_h)
+            .map(|(i, r)| {
+                let (mark, style, text) = match &r.status {
+                    RowStatus::Pending => (
+                        "·",
+                        theme.text_muted(),
+                        r.masked.clone().unwrap_or_default(),
+                    ),
+                    RowStatus::Done => (
+                        theme.status_glyph(Status::Ok),
+                        theme.status_style(Status::Ok),
+                        r.masked.clone().unwrap_or_default(),
+                    ),
+                    RowStatus::Invalid(e) => (
+                        theme.status_glyph(Status::Warn),
+                        theme.status_style(Status::Warn),
+                        format!("skipped: {e}"),
+                    ),
+                    RowStatus::Failed(e) => (
+                        theme.status_glyph(Status::Fail),
+                        theme.status_style(Status::Fail),
+                        e.clone(),
+                    ),
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:>4} {mark} ", i + 1), style),
+                    Span::raw(text),
+                ])
+            })
+            .collect();
+        f.render_widget(
+            ratatui::widgets::Paragraph::new(lines),
+            Rect {
+                y: inner.y + 2,
+                height: list_h as u16,
+                ..inner
+            },
+        );
+        let started = done > 0 || batch.running;
+        let hint = if batch.running {
+            "Submitting… ↑/↓ scroll".to_string()
+        } else if !started {
+            let runnable = batch.pending().len();
+            format!(
+                "Enter submit {runnable} row(s){} • ↑/↓ scroll • Esc cancel",
+                match batch.rows.len() - runnable {
+                    0 => String::new(),
+                    n => format!(" ({n} invalid skipped)"),
+                }
+            )
+        } else if batch.failures() > 0 {
+            format!(
+                "Done: {} failed • x export errors • Esc close",
+                batch.failures()
+            )
+        } else {
+            "Done • Esc close".to_string()
+        };
+        f.render_widget(
+            ratatui::widgets::Paragraph::new(Line::from(Span::styled(hint, theme.text_muted()))),
+            Rect {
+                y: inner.y + inner.height - 1,
+                height: 1,
+                ..inner
+            },
+        );
+    }
+
+    // Space toggles the option under the cursor of a selected multi-select field
+    pub fn wants_space(&self) -> bool {
+        self.form
+            .fields
+            .get(self.form.selected)
+            .is_some_and(|f| matches!(f.kind, FieldKind::MultiSelect { .. }))
+    }
+
+    // Validate and show the command that would run (secrets masked)
+    fn open_preview(&mut self) {
+        if !crate::widgets::form::validate_form(&mut self.form) {
+            return;
+        }
+        self.previewed_cmd = crate::widgets::form::build_cmdline(&self.form);
+        self.preview = crate::widgets::form::build_cmdline_masked(&self.form);
+        if self.preview.is_none() {
+            self.form.message = Some("No submit command configured".into());
+        }
+    }
+
+    // Validate and submit; with `preview: required` the exact command must be previewed first
+    pub fn submit_effects(&mut self) -> Vec<crate::app::Effect> {
+        if !crate::widgets::form::validate_form(&mut self.form) {
+            return Vec::new();
+        }
+        let Some(cmdline) = crate::widgets::form::build_cmdline(&self.form) else {
+            return Vec::new();
+        };
+        if self.preview_mode == PreviewMode::Required
+            && self.previewed_cmd.as_deref() != Some(cmdline.as_str())
+        {
+            self.open_preview();
+            return Vec::new();
+        }
+        self.preview = None;
+        vec![crate::app::Effect::SubmitForm {
+            pane: crate::ui::PanelPane::B,
+            cmdline,
+            mutex: self.mutex.clone(),
+        }]
+    }
+
+    // Keys while the preview popup is open: Enter submit, y copy, Esc/v close
+    fn preview_on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        use crate::app::Effect;
+        match key {
+            KeyCode::Enter => self.submit_effects(),
+            KeyCode::Esc | KeyCode::Char('v') => {
+                self.preview = None;
+                Vec::new()
+            }
+            KeyCode::Char('y') => {
+                let Some(cmd) = self.previewed_cmd.clone() else {
+                    return Vec::new();
+                };
+                let has_secrets = self.preview.as_deref() != Some(cmd.as_str());
+                match arboard::Clipboard::new().and_then(|mut c| c.set_text(cmd)) {
+                    Ok(()) => vec![Effect::ShowToast {
+                        text: if has_secrets {
+                            "Command copied (contains secrets)".into()
+                        } else {
+                            "Command copied".into()
+                        },
+                        level: crate::ui::ToastLevel::Success,
+                        seconds: 2,
+                    }],
+                    Err(e) => vec![Effect::ShowToast {
+                        text: format!("Clipboard unavailable: {e}"),
+                        level: crate::ui::ToastLevel::Error,
+                        seconds: 3,
+                    }],
+                }
+            }
+            _ => Vec::new(),
+        }
     }
     fn options_ttl() -> Option<Duration> {
         match std::env::var("CHI_TUI_OPTIONS_TTL_SEC")
@@ -69,6 +459,59 @@ impl FormWidget {
         false
     }
 
+    // Build a LoadFormOptions effect for field `idx`, skipping fields already in flight
+    // unless forced. Existing options stay visible until the new ones arrive.
+    fn options_effect(&mut self, idx: usize, force: bool) -> Option<crate::app::Effect> {
+        let fld = self.form.fields.get(idx)?;
+        let cmdline = fld.dyn_options_cmd.clone()?;
+        let loading = self.form.options_loading.contains(&fld.name);
+        if loading && !force {
+            return None;
+        }
+        let field = fld.name.clone();
+        let unwrap = fld.dyn_unwrap.clone();
+        if !loading {
+            self.form.options_loading.push(field.clone());
+        }
+        Some(crate::app::Effect::LoadFormOptions {
+            field,
+            cmdline,
+            unwrap,
+            force,
+        })
+    }
+
+    // Queue every dynamic-options field that is missing or stale and start the first batch.
+    // Called once when the form opens.
+    pub fn prefetch_options(&mut self) -> Vec<crate::app::Effect> {
+        for f in &self.form.fields {
+            if Self::should_fetch_options(f) && !self.options_queue.contains(&f.name) {
+                self.options_queue.push_back(f.name.clone());
+            }
+        }
+        self.drain_options_queue()
+    }
+
+    // Start queued option fetches while fewer than MAX_OPTION_FETCHES are in flight
+    fn drain_options_queue(&mut self) -> Vec<crate::app::Effect> {
+        let mut effects = Vec::new();
+        while self.form.options_loading.len() < MAX_OPTION_FETCHES {
+            let Some(name) = self.options_queue.pop_front() else {
+                break;
+            };
+            if let Some(idx) = self.form.fields.iter().position(|f| f.name == name) {
+                effects.extend(self.options_effect(idx, false));
+            }
+        }
+        effects
+    }
+
+    // Mark a field's options fetch as finished (success or error) and start the next one
+    pub fn options_finished(&mut self, field: &str) -> Vec<crate::app::Effect> {
+        self.form.options_loading.retain(|n| n != field);
+        self.drain_options_queue()
+    }
+
     pub fn commit_textarea(&mut self) -> bool {
         if !self.form.editing {
             return false;
@@ -92,6 +535,60 @@ impl FormWidget {
         false
     }
 
+    // Pasted text (bracketed paste, or a string an input method commits at once) goes in at
+    // the caret of the field being edited; outside editing it is dropped
+    pub fn paste(&mut self, text: &str) -> Vec<crate::app::Effect> {
+        if let Some(ta) = self.editing_textarea() {
+            ta.insert_str(text);
+            return Vec::new();
+        }
+        let numeric = matches!(
+            self.form.fields.get(self.form.selected).map(|f| &f.kind),
+            Some(FieldKind::Number { .. })
+        );
+        if numeric {
+            // Typed one by one so the number rules still apply
+            let mut effects = Vec::new();
+            for c in text.chars().filter(|c| !c.is_control()) {
+                effects.extend(crate::widgets::Widget::on_key(self, KeyCode::Char(c)));
+            }
+            return effects;
+        }
+        // A single-line field takes the lines joined with spaces
+        let line: String = text
+            .lines()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect();
+        if let Some((s, caret)) = editing_line(&mut self.form) {
+            line_edit::insert(s, caret, &line);
+            self.field_edited();
+        }
+        Vec::new()
+    }
+
+    // The TextArea of the textarea field being edited
+    fn editing_textarea(&mut self) -> Option<&mut TextArea<'static>> {
+        if !self.form.editing {
+            return None;
+        }
+        let fld = self.form.fields.get(self.form.selected)?;
+        match fld.kind {
+            FieldKind::TextArea { .. } => self.ta_map.get_mut(&fld.name),
+            _ => None,
+        }
+    }
+
+    // Re-validate the selected field after its text changed
+    fn field_edited(&mut self) {
+        if let Some(fld) = self.form.fields.get_mut(self.form.selected) {
+            crate::widgets::form::validate_text_inline(fld);
+        }
+        crate::widgets::form::compute_dirty(&mut self.form);
+    }
+
     #[allow(dead_code)]
     pub fn cancel_textarea(&mut self) -> bool {
         if !self.form.editing {
@@ -114,7 +611,8 @@ impl FormWidget {
 
 impl crate::widgets::Widget for FormWidget {
     fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
-        let mut cursor_on = tick % 2 == 0;
+        self.form.tick = tick;
+        let mut cursor_on = tick.is_multiple_of(2);
         // Suppress underlying blinking cursor when textarea modal is active to avoid layout jitter
         if self.form.editing {
             let sel = self
@@ -152,9 +650,35 @@ impl crate::widgets::Widget for FormWidget {
                 }
             }
         }
+        self.draw_batch(f, area);
+        // Command preview popup
+        if let Some(cmd) = &self.preview {
+            let rect = centered_rect(80, 50, area);
+            let hint = if self.preview_mode == PreviewMode::Required {
+                "Review required • Enter submit • y copy • Esc close"
+            } else {
+                "Enter submit • y copy • Esc close"
+            };
+            let lines = vec![
+                Line::from(Span::raw(cmd.clone())),
+                Line::from(""),
+                Line::from(Span::styled(hint, crate::theme::text_muted())),
+            ];
+            let p = ratatui::widgets::Paragraph::new(lines)
+                .block(crate::widgets::chrome::panel_block("Command preview", true))
+                .wrap(ratatui::widgets::Wrap { trim: false });
+            f.render_widget(ratatui::widgets::Clear, rect);
+            f.render_widget(p, rect);
+        }
     }
     fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
         use crate::app::Effect;
+        if self.preview.is_some() {
+            return self.preview_on_key(key);
+        }
+        if self.batch_open() {
+            return self.batch_on_key(key);
+        }
         let mut effects: Vec<Effect> = Vec::new();
         match key {
             KeyCode::Up => {
@@ -229,17 +753,14 @@ impl crate::widgets::Widget for FormWidget {
                 } else if self.form.selected > 0 {
                     self.form.selected -= 1;
                     let sel = self.form.selected;
-                    if let Some(fld) = self.form.fields.get(sel) {
-                        if Self::should_fetch_options(fld) {
-                            if let Some(cmd) = &fld.dyn_options_cmd {
-                                effects.push(Effect::LoadFormOptions {
-                                    field: fld.name.clone(),
-                                    cmdline: cmd.clone(),
-                                    unwrap: fld.dyn_unwrap.clone(),
-                                    force: false,
-                                });
-                            }
-                        }
+                    if self
+                        .form
+                        .fields
+                        .get(sel)
+                        .map(Self::should_fetch_options)
+                        .unwrap_or(false)
+                    {
+                        effects.extend(self.options_effect(sel, false));
                     }
                 }
                 effects
@@ -335,17 +856,14 @@ impl crate::widgets::Widget for FormWidget {
                         .form
                         .selected
                         .min(self.form.fields.len().saturating_sub(1));
-                    if let Some(fld) = self.form.fields.get(sel) {
-                        if Self::should_fetch_options(fld) {
-                            if let Some(cmd) = &fld.dyn_options_cmd {
-                                effects.push(Effect::LoadFormOptions {
-                                    field: fld.name.clone(),
-                                    cmdline: cmd.clone(),
-                                    unwrap: fld.dyn_unwrap.clone(),
-                                    force: false,
-                                });
-                            }
-                        }
+                    if self
+                        .form
+                        .fields
+                        .get(sel)
+                        .map(Self::should_fetch_options)
+                        .unwrap_or(false)
+                    {
+                        effects.extend(self.options_effect(sel, false));
                     }
                 }
                 effects
@@ -364,6 +882,10 @@ impl crate::widgets::Widget for FormWidget {
                         }
                     }
                 }
+                if let Some((s, caret)) = editing_line(&mut self.form) {
+                    line_edit::left(s, caret);
+                    return effects;
+                }
                 if self.form.editing {
                     let sel = self.form.selected;
                     if let Some(fld) = self.form.fields.get_mut(sel) {
@@ -423,6 +945,10 @@ impl crate::widgets::Widget for FormWidget {
                         }
                     }
                 }
+                if let Some((s, caret)) = editing_line(&mut self.form) {
+                    line_edit::right(s, caret);
+                    return effects;
+                }
                 if self.form.editing {
                     let sel = self.form.selected;
                     if let Some(fld) = self.form.fields.get_mut(sel) {
@@ -468,6 +994,7 @@ impl crate::widgets::Widget for FormWidget {
                 effects
             }
             KeyCode::Enter => {
+                self.form.caret = None;
                 if self.form.editing {
                     if let Some(fld) = self.form.fields.get(self.form.selected) {
                         if let FieldKind::TextArea { .. } = fld.kind {
@@ -485,14 +1012,7 @@ impl crate::widgets::Widget for FormWidget {
                 let reset_idx = self.form.fields.len() + 1;
                 let cancel_idx = self.form.fields.len() + 2;
                 if !self.form.editing && self.form.selected == save_idx {
-                    if crate::widgets::form::validate_form(&mut self.form) {
-                        if let Some(cmdline) = crate::widgets::form::build_cmdline(&self.form) {
-                            effects.push(Effect::SubmitForm {
-                                pane: crate::ui::PanelPane::B,
-                                cmdline,
-                            });
-                        }
-                    }
+                    effects.extend(self.submit_effects());
                 } else if !self.form.editing && self.form.selected == reset_idx {
                     if self.form.dirty {
                         // two-step confirm
@@ -548,27 +1068,27 @@ impl crate::widgets::Widget for FormWidget {
                                     self.form.editing = true;
                                 }
                             }
-                            (FieldKind::TextArea { .. }, FieldValue::Text(_)) => {
+                            (FieldKind::TextArea { .. }, FieldValue::Text(_))
+                                if !self.form.editing =>
+                            {
                                 // Enter editing, initialize TextArea content
-                                if !self.form.editing {
-                                    self.form.editing = true;
-                                    let name = fld.name.clone();
-                                    if let Some(ta) = self.ta_map.get_mut(&name) {
-                                        *ta = TextArea::default();
-                                        if let FieldValue::Text(txt) = &fld.value {
-                                            if !txt.is_empty() {
-                                                ta.insert_str(txt);
-                                            }
+                                self.form.editing = true;
+                                let name = fld.name.clone();
+                                if let Some(ta) = self.ta_map.get_mut(&name) {
+                                    *ta = TextArea::default();
+                                    if let FieldValue::Text(txt) = &fld.value {
+                                        if !txt.is_empty() {
+                                            ta.insert_str(txt);
                                         }
-                                        ta.set_block(
-                                            ratatui::widgets::Block::default()
-                                                .borders(ratatui::widgets::Borders::ALL)
-                                                .title(format!(
-                                                    "Editing: {} — Ctrl+S Save • Esc Cancel",
-                                                    fld.label
-                                                )),
-                                        );
                                     }
+                                    ta.set_block(
+                                        ratatui::widgets::Block::default()
+                                            .borders(ratatui::widgets::Borders::ALL)
+                                            .title(format!(
+                                                "Editing: {} — Ctrl+S Save • Esc Cancel",
+                                                fld.label
+                                            )),
+                                    );
                                 }
                             }
                             (
@@ -604,17 +1124,14 @@ impl crate::widgets::Widget for FormWidget {
                             }
                             _ => {}
                         }
-                        if let Some(f) = self.form.fields.get(sel) {
-                            if Self::should_fetch_options(f) {
-                                if let Some(cmd) = &f.dyn_options_cmd {
-                                    effects.push(Effect::LoadFormOptions {
-                                        field: f.name.clone(),
-                                        cmdline: cmd.clone(),
-                                        unwrap: f.dyn_unwrap.clone(),
-                                        force: false,
-                                    });
-                                }
-                            }
+                        if self
+                            .form
+                            .fields
+                            .get(sel)
+                            .map(Self::should_fetch_options)
+                            .unwrap_or(false)
+                        {
+                            effects.extend(self.options_effect(sel, false));
                         }
                     }
                 }
@@ -662,14 +1179,12 @@ impl crate::widgets::Widget for FormWidget {
                                     }
                                 } else {
                                     // Default: delete one character
-                                    s.pop();
+                                    line_edit::backspace(s, &mut self.form.caret);
                                 }
                             }
-                            // Default single-character delete for other text-like fields
+                            // Default single-character delete (at the caret) for other text-like fields
                             (_, FieldValue::Text(s)) => {
-                                if !s.is_empty() {
-                                    s.pop();
-                                }
+                                line_edit::backspace(s, &mut self.form.caret);
                             }
                             _ => {}
                         }
@@ -687,10 +1202,34 @@ impl crate::widgets::Widget for FormWidget {
                     self.form.message = None;
                 } else if self.form.editing {
                     self.form.editing = false;
+                    self.form.caret = None;
                     self.form.message = None;
                 }
                 effects
             }
+            KeyCode::Home | KeyCode::End | KeyCode::Delete => {
+                if let Some(ta) = self.editing_textarea() {
+                    let code = match key {
+                        KeyCode::Home => rt_event::KeyCode::Home,
+                        KeyCode::End => rt_event::KeyCode::End,
+                        _ => rt_event::KeyCode::Delete,
+                    };
+                    let _ = ta.input(rt_event::KeyEvent::new(code, rt_event::KeyModifiers::NONE));
+                    return effects;
+                }
+                let mut edited = false;
+                if let Some((s, caret)) = editing_line(&mut self.form) {
+                    match key {
+                        KeyCode::Home => line_edit::home(s, caret),
+                        KeyCode::End => *caret = None,
+                        _ => edited = line_edit::delete(s, caret),
+                    }
+                }
+                if edited {
+                    self.field_edited();
+                }
+                effects
+            }
             KeyCode::Char(c) => {
                 if self.form.editing {
                     if let Some(fld) = self.form.fields.get(self.form.selected) {
@@ -711,17 +1250,17 @@ impl crate::widgets::Widget for FormWidget {
                         .form
                         .selected
                         .min(self.form.fields.len().saturating_sub(1));
-                    if let Some(fld) = self.form.fields.get(sel) {
-                        if let Some(cmd) = &fld.dyn_options_cmd {
-                            effects.push(Effect::LoadFormOptions {
-                                field: fld.name.clone(),
-                                cmdline: cmd.clone(),
-                                unwrap: fld.dyn_unwrap.clone(),
-                                force: true,
-                            });
-                            return effects;
-                        }
-                    }
+                    effects.extend(self.options_effect(sel, true));
+                    return effects;
+                }
+                // 'b' submits the form once per row of a CSV/JSON file
+                if !self.form.editing && c == 'b' && self.batch_columns.is_some() {
+                    self.batch_prompt = Some(String::new());
+                    return effects;
+                }
+                // 'v' previews the command that Save would run
+                if !self.form.editing && c == 'v' && self.preview_mode != PreviewMode::Off {
+                    self.open_preview();
                     return effects;
                 }
                 if self.form.editing {
@@ -731,7 +1270,11 @@ impl crate::widgets::Widget for FormWidget {
                             (FieldKind::Text, FieldValue::Text(s))
                             | (FieldKind::Password, FieldValue::Text(s))
                             | (FieldKind::TextArea { .. }, FieldValue::Text(s)) => {
-                                s.push(c);
+                                line_edit::insert(
+                                    s,
+                                    &mut self.form.caret,
+                                    c.encode_utf8(&mut [0; 4]),
+                                );
                                 crate::widgets::form::validate_text_inline(fld);
                             }
                             (FieldKind::Number { is_integer, .. }, FieldValue::Text(s)) => {
@@ -739,7 +1282,11 @@ impl crate::widgets::Widget for FormWidget {
                                     || (c == '.' && !*is_integer && !s.contains('.'))
                                     || (c == '-' && s.is_empty())
                                 {
-                                    s.push(c);
+                                    line_edit::insert(
+                                        s,
+                                        &mut self.form.caret,
+                                        c.encode_utf8(&mut [0; 4]),
+                                    );
                                 }
                                 // Validate number-like input inline too (shared validator handles bounds/type later)
                                 crate::widgets::form::validate_text_inline(fld);
@@ -782,6 +1329,99 @@ impl crate::widgets::Widget for FormWidget {
             _ => effects,
         }
     }
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![WidgetAction::key("Edit field", "Enter", KeyCode::Enter)];
+        let sel = self
+            .form
+            .selected
+            .min(self.form.fields.len().saturating_sub(1));
+        if self
+            .form
+            .fields
+            .get(sel)
+            .is_some_and(|f| f.dyn_options_cmd.is_some())
+        {
+            actions.push(WidgetAction::key(
+                "Refresh options",
+                "r",
+                KeyCode::Char('r'),
+            ));
+        }
+        if self.preview_mode != PreviewMode::Off {
+            actions.push(WidgetAction::key(
+                "Preview command",
+                "v",
+                KeyCode::Char('v'),
+            ));
+        }
+        if self.batch_columns.is_some() {
+            actions.push(WidgetAction::key(
+                "Submit from file",
+                "b",
+                KeyCode::Char('b'),
+            ));
+        }
+        actions.extend(WidgetAction::content());
+        actions
+    }
+    fn hints(&self) -> crate::widgets::KeyHints {
+        use crate::widgets::KeyHints;
+        if self.preview_open() {
+            return KeyHints::modal("Enter submit • y copy command • v/esc close preview");
+        }
+        if self.batch_open() {
+            return KeyHints::modal("Enter load/submit • ↑/↓ scroll • x export errors • esc close");
+        }
+        let form = &self.form;
+        let field = form.fields.get(form.selected);
+        // Keys typed while editing go to the field
+        if form.editing {
+            return KeyHints::modal(match field.map(|f| &f.kind) {
+                Some(FieldKind::Select { .. }) => {
+                    "↑/↓ move • Enter select • ←/→ commit • esc exit edit"
+                }
+                Some(FieldKind::MultiSelect { .. }) => {
+                    "↑/↓ move • Space/Enter toggle • esc exit edit"
+                }
+                Some(FieldKind::TextArea { .. }) => {
+                    "Type • Enter newline • Ctrl+S save • esc finish"
+                }
+                _ => "Type • Enter finish • esc exit edit",
+            });
+        }
+        let mut hints = KeyHints::new(if field.is_some() {
+            "↑/↓ select field • Enter edit"
+        } else {
+            "↑/↓ select • Enter edit"
+        });
+        if let Some(FieldKind::Select { .. }) = field.map(|f| &f.kind) {
+            hints.push("←/→ change");
+        }
+        if field.is_some_and(|f| f.dyn_options_cmd.is_some()) {
+            hints.push("r refresh");
+        }
+        if self.preview_mode != PreviewMode::Off {
+            hints.push("v preview");
+        }
+        if self.batch_columns.is_some() {
+            hints.push("b from file");
+        }
+        hints.push("s submit");
+        hints
+    }
+    fn unsaved(&self) -> bool {
+        self.form.dirty && !self.form.disabled
+    }
+    fn save(&mut self) -> Vec<crate::app::Effect> {
+        self.submit_effects()
+    }
+    fn discard(&mut self) {
+        crate::widgets::form::reset_to_initial(&mut self.form);
+        self.form.editing = false;
+        self.form.confirm = None;
+        self.form.message = None;
+    }
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,164 @@
+use ratatui::buffer::Buffer;
+use ratatui::prelude::*;
+use regex::Regex;
+
+use crate::model::HighlightSpec;
+
+// `highlights:` of an item: substrings matching a rule's regex are restyled wherever the
+// item's output is drawn (result viewer, raw text, stream output, watchdog logs). Rules
+// are applied to the rendered cells, row by row, so every text pane gets them for free.
+#[derive(Clone)]
+pub struct Highlights {
+    rules: Vec<(Regex, String)>,
+}
+
+// Style words: theme colors (`accent`, `selected`, `success`, `error`, `muted`,
+// `primary`, `secondary`), plain colors (`yellow`, `#ff8800`), `on <color>` for the
+// background, and `bold`/`italic`/`underline`/`reversed`/`dim`
+fn parse_style(spec: &str, theme: &crate::theme::Theme) -> Result<Style, String> {
+    let color = |word: &str| -> Result<Color, String> {
+        Ok(match word {
+            "accent" => theme.accent,
+            "selected" => theme.selected,
+            "success" => theme.success,
+            "error" => theme.error,
+            "muted" => theme.muted,
+            "primary" => theme.primary,
+            "secondary" => theme.secondary,
+            other => other
+                .parse::<Color>()
+                .map_err(|_| format!("unknown color `{other}`"))?,
+        })
+    };
+    let mut style = Style::default();
+    let mut words = spec.split_whitespace().map(|w| w.to_ascii_lowercase());
+    while let Some(word) = words.next() {
+        style = match word.as_str() {
+            "bold" => style.add_modifier(Modifier::BOLD),
+            "italic" => style.add_modifier(Modifier::ITALIC),
+            "underline" => style.add_modifier(Modifier::UNDERLINED),
+            "reversed" => style.add_modifier(Modifier::REVERSED),
+            "dim" => style.add_modifier(Modifier::DIM),
+            "on" => {
+                let bg = words.next().ok_or("`on` needs a color")?;
+                style.bg(color(&bg)?)
+            }
+            other => style.fg(color(other)?),
+        };
+    }
+    Ok(style)
+}
+
+impl Highlights {
+    // Invalid rules are skipped (see `check`)
+    pub fn new(specs: &[HighlightSpec]) -> Option<Self> {
+        let theme = crate::theme::Theme::default();
+        let rules: Vec<(Regex, String)> = specs
+            .iter()
+            .filter(|s| parse_style(&s.style, &theme).is_ok())
+            .filter_map(|s| Some((Regex::new(&s.regex).ok()?, s.style.clone())))
+            .collect();
+        (!rules.is_empty()).then_some(Self { rules })
+    }
+
+    // First invalid rule, for a config error toast
+    pub fn check(specs: &[HighlightSpec]) -> Option<String> {
+        let theme = crate::theme::Theme::default();
+        specs.iter().find_map(|s| {
+            let err = match Regex::new(&s.regex) {
+                Err(e) => e.to_string(),
+                Ok(_) => parse_style(&s.style, &theme).err()?,
+            };
+            Some(format!("highlight `{}`: {err}", s.regex))
+        })
+    }
+
+    // Restyle matches inside `area`'s border (each row is matched on its own)
+    pub fn paint(&self, buf: &mut Buffer, area: Rect) {
+        let area = area.intersection(buf.area);
+        if area.width < 3 || area.height < 3 {
+            return;
+        }
+        let theme = crate::theme::active();
+        let styles: Vec<Style> = self
+            .rules
+            .iter()
+            .map(|(_, s)| parse_style(s, &theme).unwrap_or_default())
+            .collect();
+        let (left, right) = (area.x + 1, area.x + area.width - 1);
+        for y in area.y + 1..area.y + area.height - 1 {
+            // Row text and the column each byte offset starts at
+            let mut text = String::new();
+            let mut cols: Vec<(usize, u16)> = Vec::new();
+            for x in left..right {
+                let sym = buf[(x, y)].symbol();
+                if sym.is_empty() {
+                    // Second half of a wide character
+                    continue;
+                }
+                cols.push((text.len(), x));
+                text.push_str(sym);
+            }
+            let col_at = |offset: usize| {
+                let i = cols.partition_point(|(o, _)| *o < offset);
+                cols.get(i).map(|(_, x)| *x).unwrap_or(right)
+            };
+            for ((re, _), style) in self.rules.iter().zip(&styles) {
+                for m in re.find_iter(&text) {
+                    if m.is_empty() {
+                        continue;
+                    }
+                    let (from, to) = (col_at(m.start()), col_at(m.end()));
+                    buf.set_style(Rect::new(from, y, to - from, 1), *style);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(regex: &str, style: &str) -> HighlightSpec {
+        HighlightSpec {
+            regex: regex.into(),
+            style: style.into(),
+        }
+    }
+
+    #[test]
+    fn restyles_matches_inside_the_border() {
+        let specs = vec![
+            spec(r"req-[0-9a-f]+", "accent bold"),
+            spec(r"\b[1-9]\d*\.\d+s\b", "error on yellow"),
+        ];
+        assert!(Highlights::check(&specs).is_none());
+        let h = Highlights::new(&specs).unwrap();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 4));
+        buf.set_string(0, 0, "req-aa title", Style::default());
+        buf.set_string(1, 1, "GET req-1f ok 2.5s", Style::default());
+        buf.set_string(1, 2, "é req-2 0.3s", Style::default());
+        let area = buf.area;
+        h.paint(&mut buf, area);
+
+        let theme = crate::theme::active();
+        // Border rows are left alone
+        assert_eq!(buf[(0, 0)].fg, Color::Reset);
+        assert_eq!(buf[(5, 1)].fg, theme.accent);
+        assert!(buf[(10, 1)].modifier.contains(Modifier::BOLD));
+        assert_eq!(buf[(11, 1)].fg, Color::Reset);
+        assert_eq!(buf[(15, 1)].bg, Color::Yellow);
+        assert_eq!(buf[(18, 1)].bg, Color::Yellow);
+        // Multi-byte text keeps columns aligned; 0.3s is under a second
+        assert_eq!(buf[(3, 2)].fg, theme.accent);
+        assert_eq!(buf[(9, 2)].bg, Color::Reset);
+
+        let bad = vec![spec("(", "bold"), spec("x", "sparkly")];
+        assert!(Highlights::check(&bad).unwrap().contains("highlight `(`"));
+        assert!(Highlights::new(&bad).is_none());
+        assert!(Highlights::check(&bad[1..])
+            .unwrap()
+            .contains("unknown color `sparkly`"));
+    }
+}
@@ -3,9 +3,40 @@ use ratatui::widgets::{Block, Borders, Tabs};
 
 use crate::ui::AppState;
 
+// Tabs of the bar in the order shown (indices into `horizontal_menu`): `hidden: true` tabs are
+// left out, and tabs moved with Ctrl+Shift+←/→ follow the session's order (by id, so it
+// holds across screens that repeat the bar); the rest keep their config order
+pub fn visible_tabs(state: &AppState) -> Vec<usize> {
+    ordered_tabs(&state.config.horizontal_menu, &state.tab_order)
+}
+
+pub fn ordered_tabs(tabs: &[crate::model::HorizontalMenuItem], order: &[String]) -> Vec<usize> {
+    let mut shown: Vec<usize> = (0..tabs.len()).filter(|&i| !tabs[i].hidden).collect();
+    shown.sort_by_key(|&i| {
+        let pos = order.iter().position(|id| *id == tabs[i].id);
+        (pos.unwrap_or(usize::MAX), i)
+    });
+    shown
+}
+
+// Move the current tab one place left (-1) or right (1) in the session's order
+pub fn move_tab(state: &mut AppState, delta: isize) -> bool {
+    let mut shown = visible_tabs(state);
+    let Some(pos) = shown.iter().position(|&i| i == state.horizontal_tab_index) else {
+        return false;
+    };
+    let Some(to) = pos.checked_add_signed(delta).filter(|&to| to < shown.len()) else {
+        return false;
+    };
+    shown.swap(pos, to);
+    let tabs = &state.config.horizontal_menu;
+    state.tab_order = shown.iter().map(|&i| tabs[i].id.clone()).collect();
+    true
+}
+
 pub fn draw_horizontal_menu(f: &mut Frame, area: Rect, state: &AppState) {
     let theme = &state.theme;
-    let current_index = state.horizontal_tab_index;
+    let mut current_index = state.horizontal_tab_index;
 
     // Build tab titles with function key indicators
     let mut titles: Vec<Line> = Vec::new();
@@ -39,9 +70,21 @@ pub fn draw_horizontal_menu(f: &mut Frame, area: Rect, state: &AppState) {
 
         titles.push(line);
     } else {
-        for (i, item) in state.config.horizontal_menu.iter().enumerate() {
-            let is_selected = i == current_index;
-            let fn_key = format!("F{}", i + 1);
+        let mut shown: Vec<(usize, String)> = visible_tabs(state)
+            .into_iter()
+            .enumerate()
+            .map(|(pos, i)| (i, format!("F{}", pos + 1)))
+            .collect();
+        // A hidden tab opened from the palette shows at the end while it is current
+        let tabs = &state.config.horizontal_menu;
+        if tabs.get(current_index).is_some_and(|t| t.hidden) {
+            shown.push((current_index, "·".to_string()));
+        }
+        let current = current_index;
+        current_index = shown.iter().position(|(i, _)| *i == current).unwrap_or(0);
+        for (i, fn_key) in shown {
+            let item = &tabs[i];
+            let is_selected = i == current;
 
             // Style for the tab
             let text_style = if is_selected {
@@ -92,15 +135,23 @@ pub fn draw_horizontal_menu(f: &mut Frame, area: Rect, state: &AppState) {
     f.render_widget(tabs.block(block), area);
 }
 
-/// Handle function key presses for horizontal menu
-/// Returns Some(config_path) if a new config should be loaded
-pub fn handle_function_key(state: &mut AppState, key_num: u8) -> Option<String> {
-    // F1 = 1, F2 = 2, etc.
-    let index = (key_num - 1) as usize;
+/// Tab an F-key stands for (F1 = first tab shown); None when there is no such tab
+pub fn tab_for_key(state: &AppState, key_num: u8) -> Option<usize> {
+    if state.config.horizontal_menu.is_empty() {
+        // Default [F1] Main when no menu configured
+        return (key_num == 1).then_some(0);
+    }
+    visible_tabs(state)
+        .get(usize::from(key_num).checked_sub(1)?)
+        .copied()
+}
 
+/// Select tab `index` (from `tab_for_key` or the palette)
+/// Returns Some(config_path) if a new config should be loaded
+pub fn select_tab(state: &mut AppState, index: usize) -> Option<String> {
     // Handle default [F1] Main when no menu configured
     if state.config.horizontal_menu.is_empty() {
-        if key_num == 1 {
+        if index == 0 {
             state.horizontal_tab_index = 0;
         }
         return None;
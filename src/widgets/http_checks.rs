@@ -0,0 +1,405 @@
+use crate::model::HttpCheckSpec;
+use crate::theme::Status;
+use crate::widgets::check_rounds::CheckRounds;
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::text_width;
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Paragraph, Wrap};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+// `widget: http_checks`: `checks:` URLs polled concurrently (with `curl`) every
+// `refresh_secs`. A check passes when the status matches `expect_status` (default any 2xx)
+// and the body contains `expect_contains`. Rows show the status code, latency, when the
+// result last flipped and failure streaks; the lower pane shows the selected response.
+
+// Response bytes kept for the detail pane
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+struct Outcome {
+    // None when no response came back (connection refused, timeout, …)
+    status: Option<u16>,
+    latency: Duration,
+    // Why the check failed
+    failure: Option<String>,
+    body: String,
+}
+
+struct Check {
+    spec: HttpCheckSpec,
+    last: Option<Outcome>,
+    // Consecutive failed checks
+    streak: u32,
+    // Unix time the pass/fail result last changed
+    changed_at: Option<i64>,
+}
+
+// `curl -w '\n%{http_code}'` output: (body, status code)
+fn split_response(out: &str) -> (&str, Option<u16>) {
+    match out.rsplit_once('\n') {
+        Some((body, code)) => (body, code.trim().parse().ok().filter(|c| *c != 0)),
+        None => ("", out.trim().parse().ok().filter(|c| *c != 0)),
+    }
+}
+
+// Why a response fails `spec`, if it does
+fn evaluate(spec: &HttpCheckSpec, status: u16, body: &str) -> Option<String> {
+    match spec.expect_status {
+        Some(want) if status != want => return Some(format!("expected {want}, got {status}")),
+        None if !(200..300).contains(&status) => return Some(format!("status {status}")),
+        _ => {}
+    }
+    match &spec.expect_contains {
+        Some(text) if !body.contains(text.as_str()) => {
+            Some(format!("body does not contain {text:?}"))
+        }
+        _ => None,
+    }
+}
+
+fn run_check(spec: &HttpCheckSpec, timeout: Duration) -> Outcome {
+    let start = Instant::now();
+    let out = Command::new("curl")
+        .args(["-sS", "--max-time"])
+        .arg(format!("{:.1}", timeout.as_secs_f64()))
+        .args(["-w", "\n%{http_code}"])
+        .arg(&spec.url)
+        .output();
+    let latency = start.elapsed();
+    let out = match out {
+        Ok(out) => out,
+        Err(e) => {
+            return Outcome {
+                status: None,
+                latency,
+                failure: Some(format!("curl: {e}")),
+                body: String::new(),
+            }
+        }
+    };
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let (body, status) = split_response(&stdout);
+    let mut body = body.to_string();
+    if body.len() > MAX_BODY_BYTES {
+        let cut = (0..=MAX_BODY_BYTES)
+            .rev()
+            .find(|i| body.is_char_boundary(*i))
+            .unwrap_or(0);
+        body.truncate(cut);
+        body.push_str("\n… (truncated)");
+    }
+    let failure = match status {
+        Some(code) => evaluate(spec, code, &body),
+        None => Some(
+            String::from_utf8_lossy(&out.stderr)
+                .trim()
+                .trim_start_matches("curl: ")
+                .to_string(),
+        )
+        .filter(|e| !e.is_empty())
+        .or_else(|| Some("no response".to_string())),
+    };
+    Outcome {
+        status,
+        latency,
+        failure,
+        body,
+    }
+}
+
+fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+pub struct HttpChecksWidget {
+    title: String,
+    checks: Vec<Check>,
+    timeout: Duration,
+    selected: usize,
+    body_scroll: u16,
+    rounds: CheckRounds<Outcome>,
+}
+
+impl HttpChecksWidget {
+    pub fn new(
+        title: impl Into<String>,
+        specs: &[HttpCheckSpec],
+        refresh_secs: Option<u64>,
+        timeout_ms: Option<u64>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            checks: specs
+                .iter()
+                .map(|spec| Check {
+                    spec: spec.clone(),
+                    last: None,
+                    streak: 0,
+                    changed_at: None,
+                })
+                .collect(),
+            timeout: Duration::from_millis(timeout_ms.unwrap_or(5000).max(100)),
+            selected: 0,
+            body_scroll: 0,
+            rounds: CheckRounds::new(Duration::from_secs(refresh_secs.unwrap_or(30).max(1))),
+        }
+    }
+
+    // Poll every URL concurrently
+    fn check_all(&mut self) {
+        let timeout = self.timeout;
+        self.rounds.start(self.checks.iter().map(|c| {
+            let spec = c.spec.clone();
+            move || run_check(&spec, timeout)
+        }));
+    }
+
+    // Store a check's outcome, tracking its failure streak and when it flipped
+    fn record(&mut self, i: usize, outcome: Outcome, now: i64) {
+        let Some(c) = self.checks.get_mut(i) else {
+            return;
+        };
+        let was_ok = c.last.as_ref().map(|o| o.failure.is_none());
+        let ok = outcome.failure.is_none();
+        if was_ok != Some(ok) {
+            c.changed_at = Some(now);
+        }
+        c.streak = if ok { 0 } else { c.streak + 1 };
+        c.last = Some(outcome);
+    }
+
+    // Apply finished checks, and start the next round when due
+    fn poll(&mut self) {
+        for (i, outcome) in self.rounds.finished() {
+            self.record(i, outcome, now_unix());
+        }
+        if self.rounds.due() {
+            self.check_all();
+        }
+    }
+
+    fn passing(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|c| c.last.as_ref().is_some_and(|o| o.failure.is_none()))
+            .count()
+    }
+
+    fn check_lines(&self, tick: u64, height: usize) -> Vec<Line<'static>> {
+        let theme = crate::theme::active();
+        let now = now_unix();
+        let name_w = self
+            .checks
+            .iter()
+            .map(|c| text_width::display_width(c.spec.label()))
+            .max()
+            .unwrap_or(0);
+        let skip = (self.selected + 1).saturating_sub(height);
+        let mut lines = Vec::new();
+        for (i, c) in self.checks.iter().enumerate().skip(skip) {
+            let (badge, style) = match &c.last {
+                None => ("○ ....".to_string(), theme.text_muted()),
+                Some(o) if o.failure.is_none() => (
+                    format!("{} OK  ", theme.status_glyph(Status::Ok)),
+                    theme.status_style(Status::Ok),
+                ),
+                Some(_) => (
+                    format!("{} FAIL", theme.status_glyph(Status::Fail)),
+                    theme.status_style(Status::Fail),
+                ),
+            };
+            let spinner = if self.rounds.checking(i) {
+                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][tick as usize % 6]
+            } else {
+                " "
+            };
+            let mut spans = vec![
+                Span::styled(badge, style),
+                Span::raw(format!(
+                    " {spinner} {}  ",
+                    text_width::pad(c.spec.label(), name_w)
+                )),
+            ];
+            if let Some(o) = &c.last {
+                let code = o
+                    .status
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "---".into());
+                spans.push(Span::raw(format!(
+                    "{code:>3} {:>6}  ",
+                    format!("{}ms", o.latency.as_millis())
+                )));
+            }
+            if let Some(at) = c.changed_at {
+                spans.push(Span::styled(
+                    format!("since {}  ", crate::widgets::cells::relative_time(at, now)),
+                    theme.text_muted(),
+                ));
+            }
+            if c.streak > 1 {
+                spans.push(Span::styled(
+                    format!("failing ×{}", c.streak),
+                    theme
+                        .text_error()
+                        .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                ));
+            } else if let Some(reason) = c.last.as_ref().and_then(|o| o.failure.as_ref()) {
+                spans.push(Span::styled(reason.clone(), theme.text_error()));
+            }
+            let mut line = Line::from(spans);
+            if i == self.selected {
+                line = line.style(theme.list_cursor_style());
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+impl crate::widgets::Widget for HttpChecksWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
+        self.poll();
+        let theme = crate::theme::active();
+        let [top, bottom] =
+            Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(area);
+        let title = format!(
+            "{} — {}/{} passing",
+            self.title,
+            self.passing(),
+            self.checks.len()
+        );
+        let title = fit_title(&title, top);
+        let block = panel_block(&title, focused);
+        let mut lines = self.check_lines(tick, block.inner(top).height as usize);
+        if self.checks.is_empty() {
+            lines.push(Line::from(Span::styled("No checks", theme.text_muted())));
+        }
+        f.render_widget(Paragraph::new(lines).block(block), top);
+
+        let selected = self.checks.get(self.selected);
+        let detail_title = match selected {
+            Some(c) => format!("Response — {}", c.spec.url),
+            None => "Response".to_string(),
+        };
+        let mut detail: Vec<Line> = Vec::new();
+        match selected.and_then(|c| c.last.as_ref()) {
+            None => detail.push(Line::from(Span::styled(
+                "No response yet",
+                theme.text_muted(),
+            ))),
+            Some(o) => {
+                if let Some(reason) = &o.failure {
+                    detail.push(Line::from(Span::styled(reason.clone(), theme.text_error())));
+                }
+                detail.extend(o.body.lines().map(|l| Line::from(l.to_string())));
+            }
+        }
+        f.render_widget(
+            Paragraph::new(detail)
+                .block(panel_block(&fit_title(&detail_title, bottom), false))
+                .wrap(Wrap { trim: false })
+                .scroll((self.body_scroll, 0)),
+            bottom,
+        );
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        self.poll();
+        let last = self.checks.len().saturating_sub(1);
+        let before = self.selected;
+        match key {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            KeyCode::PageDown => self.body_scroll = self.body_scroll.saturating_add(10),
+            KeyCode::PageUp => self.body_scroll = self.body_scroll.saturating_sub(10),
+            _ => {}
+        }
+        if self.selected != before {
+            self.body_scroll = 0;
+        }
+        Vec::new()
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![WidgetAction::refresh()];
+        actions.push(WidgetAction::export_image());
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        self.check_all();
+        Vec::new()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(status: Option<u16>, failure: Option<&str>) -> Outcome {
+        Outcome {
+            status,
+            latency: Duration::from_millis(12),
+            failure: failure.map(|s| s.to_string()),
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn evaluates_responses_and_tracks_streaks() {
+        assert_eq!(
+            split_response("{\"ok\":true}\n200"),
+            ("{\"ok\":true}", Some(200))
+        );
+        assert_eq!(split_response("\n000"), ("", None));
+
+        let spec = HttpCheckSpec {
+            name: Some("api".into()),
+            url: "http://localhost/health".into(),
+            expect_status: None,
+            expect_contains: Some("\"ok\"".into()),
+        };
+        assert_eq!(evaluate(&spec, 204, "{\"ok\":true}"), None);
+        assert_eq!(evaluate(&spec, 503, "").as_deref(), Some("status 503"));
+        assert!(evaluate(&spec, 200, "{}")
+            .unwrap()
+            .contains("does not contain"));
+        let redirect = HttpCheckSpec {
+            expect_status: Some(301),
+            expect_contains: None,
+            ..spec.clone()
+        };
+        assert_eq!(evaluate(&redirect, 301, ""), None);
+        assert_eq!(
+            evaluate(&redirect, 200, "").as_deref(),
+            Some("expected 301, got 200")
+        );
+
+        let mut w = HttpChecksWidget::new("Health", &[spec], None, None);
+        w.record(0, outcome(Some(200), None), 100);
+        assert_eq!((w.checks[0].streak, w.checks[0].changed_at), (0, Some(100)));
+        w.record(0, outcome(Some(200), None), 110);
+        assert_eq!(w.checks[0].changed_at, Some(100));
+        w.record(0, outcome(Some(503), Some("status 503")), 120);
+        w.record(0, outcome(None, Some("Connection refused")), 130);
+        assert_eq!((w.checks[0].streak, w.checks[0].changed_at), (2, Some(120)));
+        assert_eq!(w.passing(), 0);
+        w.record(0, outcome(Some(200), None), 140);
+        assert_eq!((w.checks[0].streak, w.checks[0].changed_at), (0, Some(140)));
+    }
+}
@@ -0,0 +1,275 @@
+use crate::theme::Theme;
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use serde_json::Value as JsonValue;
+use std::collections::HashSet;
+
+// Tree mode of the result viewer ('t'): objects and arrays expand and collapse under a
+// cursor, addressed by JSON pointers (`/data/items/3/name`), so a large response can be
+// walked down to one value instead of scrolled through. Expanded pointers survive refreshes.
+pub struct JsonTree {
+    // Pointers of the expanded objects / arrays; the root ("") starts expanded
+    expanded: HashSet<String>,
+    cursor: usize,
+    top: usize,
+    viewport: usize,
+}
+
+// A visible row
+struct Row {
+    pointer: String,
+    depth: usize,
+    // Object key or array index; None for the root
+    label: Option<String>,
+}
+
+// `a/b` in a pointer segment -> `a~1b`
+fn escape(seg: &str) -> String {
+    seg.replace('~', "~0").replace('/', "~1")
+}
+
+fn is_container(v: &JsonValue) -> bool {
+    match v {
+        JsonValue::Object(m) => !m.is_empty(),
+        JsonValue::Array(a) => !a.is_empty(),
+        _ => false,
+    }
+}
+
+impl Default for JsonTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonTree {
+    pub fn new() -> Self {
+        Self {
+            expanded: HashSet::from([String::new()]),
+            cursor: 0,
+            top: 0,
+            viewport: 0,
+        }
+    }
+
+    fn rows(&self, root: &JsonValue) -> Vec<Row> {
+        let mut out = Vec::new();
+        self.push_rows(root, String::new(), 0, None, &mut out);
+        out
+    }
+
+    fn push_rows(
+        &self,
+        v: &JsonValue,
+        pointer: String,
+        depth: usize,
+        label: Option<String>,
+        out: &mut Vec<Row>,
+    ) {
+        let open = is_container(v) && self.expanded.contains(&pointer);
+        out.push(Row {
+            pointer: pointer.clone(),
+            depth,
+            label,
+        });
+        if !open {
+            return;
+        }
+        match v {
+            JsonValue::Object(m) => {
+                for (k, x) in m {
+                    let p = format!("{pointer}/{}", escape(k));
+                    self.push_rows(x, p, depth + 1, Some(k.clone()), out);
+                }
+            }
+            JsonValue::Array(a) => {
+                for (i, x) in a.iter().enumerate() {
+                    let p = format!("{pointer}/{i}");
+                    self.push_rows(x, p, depth + 1, Some(format!("[{i}]")), out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // JSON pointer of the row under the cursor ("" is the whole value)
+    pub fn pointer(&self, root: &JsonValue) -> String {
+        let rows = self.rows(root);
+        rows.get(self.cursor.min(rows.len().saturating_sub(1)))
+            .map(|r| r.pointer.clone())
+            .unwrap_or_default()
+    }
+
+    // Value under the cursor
+    pub fn selected<'a>(&self, root: &'a JsonValue) -> Option<&'a JsonValue> {
+        root.pointer(&self.pointer(root))
+    }
+
+    // Put the cursor on `pointer`, expanding it and every node above it
+    pub fn open(&mut self, root: &JsonValue, pointer: &str) {
+        let mut at = String::new();
+        self.expanded.insert(at.clone());
+        for seg in pointer.split('/').skip(1) {
+            at = format!("{at}/{seg}");
+            self.expanded.insert(at.clone());
+        }
+        if let Some(row) = self.rows(root).iter().position(|r| r.pointer == pointer) {
+            self.cursor = row;
+        }
+    }
+
+    fn move_to(&mut self, row: usize, total: usize) {
+        self.cursor = row.min(total.saturating_sub(1));
+    }
+
+    // Navigation keys; false for keys the tree does not use
+    pub fn on_key(&mut self, key: KeyCode, root: &JsonValue) -> bool {
+        let rows = self.rows(root);
+        let total = rows.len();
+        let Some(row) = rows.get(self.cursor.min(total.saturating_sub(1))) else {
+            return false;
+        };
+        let here = root.pointer(&row.pointer);
+        let container = here.is_some_and(is_container);
+        let open = container && self.expanded.contains(&row.pointer);
+        let page = self.viewport.max(1);
+        match key {
+            KeyCode::Up => self.move_to(self.cursor.saturating_sub(1), total),
+            KeyCode::Down => self.move_to(self.cursor + 1, total),
+            KeyCode::PageUp => self.move_to(self.cursor.saturating_sub(page), total),
+            KeyCode::PageDown => self.move_to(self.cursor + page, total),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.move_to(total, total),
+            // Expand, or step into an expanded node
+            KeyCode::Right if container && !open => {
+                self.expanded.insert(row.pointer.clone());
+            }
+            KeyCode::Right if open => self.move_to(self.cursor + 1, total),
+            // Collapse, or step out to the parent
+            KeyCode::Left if open => {
+                self.expanded.remove(&row.pointer);
+            }
+            KeyCode::Left => {
+                if let Some(parent) = rows[..self.cursor]
+                    .iter()
+                    .rposition(|r| r.depth + 1 == row.depth)
+                {
+                    self.cursor = parent;
+                }
+            }
+            KeyCode::Enter | KeyCode::Char(' ') if container => {
+                if !self.expanded.remove(&row.pointer) {
+                    self.expanded.insert(row.pointer.clone());
+                }
+            }
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char(' ') => {}
+            _ => return false,
+        }
+        true
+    }
+
+    // Lines of the rows in view (`height` rows), the cursor row highlighted
+    pub fn lines(&mut self, root: &JsonValue, height: usize, theme: &Theme) -> Vec<Line<'static>> {
+        let rows = self.rows(root);
+        self.viewport = height;
+        self.cursor = self.cursor.min(rows.len().saturating_sub(1));
+        if self.cursor < self.top {
+            self.top = self.cursor;
+        } else if height > 0 && self.cursor >= self.top + height {
+            self.top = self.cursor + 1 - height;
+        }
+        self.top = self.top.min(rows.len().saturating_sub(height.max(1)));
+        rows.iter()
+            .enumerate()
+            .skip(self.top)
+            .take(height)
+            .map(|(i, row)| {
+                let v = root.pointer(&row.pointer).unwrap_or(&JsonValue::Null);
+                let marker = match (is_container(v), self.expanded.contains(&row.pointer)) {
+                    (false, _) => "  ",
+                    (true, true) => "▾ ",
+                    (true, false) => "▸ ",
+                };
+                let mut spans = vec![Span::raw("  ".repeat(row.depth)), Span::raw(marker)];
+                if let Some(label) = &row.label {
+                    spans.push(Span::styled(format!("{label}: "), theme.json_key()));
+                }
+                spans.push(match v {
+                    JsonValue::Object(m) => {
+                        Span::styled(format!("{{{} keys}}", m.len()), theme.text_muted())
+                    }
+                    JsonValue::Array(a) => {
+                        Span::styled(format!("[{} items]", a.len()), theme.text_muted())
+                    }
+                    JsonValue::String(s) => Span::styled(format!("{s:?}"), theme.json_string()),
+                    JsonValue::Number(n) => Span::styled(n.to_string(), theme.json_number()),
+                    JsonValue::Bool(b) => Span::styled(b.to_string(), theme.json_bool()),
+                    JsonValue::Null => Span::styled("null", theme.text_muted()),
+                });
+                let line = Line::from(spans);
+                if i == self.cursor {
+                    line.style(theme.list_cursor_style())
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+}
+
+// Text copied for a value: strings as they are, anything else as pretty JSON
+pub fn copy_text(v: &JsonValue) -> String {
+    match v {
+        JsonValue::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn arrows_expand_collapse_and_track_the_pointer() {
+        let v = json!({"data": {"items": [{"name": "a/b"}, {"name": "c"}]}, "ok": true});
+        let mut t = JsonTree::new();
+        assert_eq!(t.rows(&v).len(), 3); // root, data, ok
+        t.on_key(KeyCode::Down, &v);
+        assert_eq!(t.pointer(&v), "/data");
+        t.on_key(KeyCode::Right, &v); // expand data
+        t.on_key(KeyCode::Right, &v); // into items
+        t.on_key(KeyCode::Enter, &v); // expand items
+        t.on_key(KeyCode::Down, &v);
+        t.on_key(KeyCode::Right, &v);
+        t.on_key(KeyCode::Down, &v);
+        assert_eq!(t.pointer(&v), "/data/items/0/name");
+        assert_eq!(copy_text(t.selected(&v).unwrap()), "a/b");
+        // Left on a leaf goes to its parent, on an expanded node collapses it
+        t.on_key(KeyCode::Left, &v);
+        assert_eq!(t.pointer(&v), "/data/items/0");
+        t.on_key(KeyCode::Left, &v);
+        assert_eq!(t.rows(&v).len(), 6);
+        assert_eq!(
+            copy_text(t.selected(&v).unwrap()),
+            "{\n  \"name\": \"a/b\"\n}"
+        );
+        t.on_key(KeyCode::End, &v);
+        assert_eq!(t.pointer(&v), "/ok");
+        let mut opened = JsonTree::new();
+        opened.open(&v, "/data/items/1");
+        assert_eq!(opened.pointer(&v), "/data/items/1");
+        assert_eq!(opened.rows(&v).len(), 7);
+        assert!(!t.on_key(KeyCode::Char('q'), &v));
+
+        let text: String = t
+            .lines(&v, 3, &Theme::synthwave_dark())
+            .iter()
+            .flat_map(|l| l.spans.iter().map(|s| s.content.to_string()))
+            .collect();
+        assert!(
+            text.contains("▸ [1]: {1 keys}") && text.ends_with("  ok: true"),
+            "{text}"
+        );
+    }
+}
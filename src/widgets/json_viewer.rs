@@ -1,5 +1,6 @@
 use crate::widgets::chrome::panel_block;
 use crate::widgets::result_viewer::ResultViewerWidget;
+use crate::widgets::soft_wrap::{SoftWrap, WrapKey};
 use crate::widgets::Widget;
 use crossterm::event::KeyCode;
 use ratatui::prelude::*;
@@ -10,15 +11,16 @@ use crate::ui::AppState;
 pub fn draw_json(f: &mut Frame, area: Rect, state: &mut AppState) {
     if let Some(err) = &state.last_error {
         // Show error in simple paragraph
-        let lines = vec![
-            Line::from(err.clone()).style(Style::default().fg(Color::Red)),
-            Line::from(""),
-        ];
+        let lines = vec![state.theme.error_line(err), Line::from("")];
         let block = panel_block("JSON Output", !matches!(state.view, crate::ui::View::Panel));
         let p = Paragraph::new(lines).block(block);
         f.render_widget(p, area);
         return;
     }
+    if let Some(w) = &mut state.json_rendered {
+        w.render(f, area, !matches!(state.view, crate::ui::View::Panel), 0);
+        return;
+    }
     // Ensure viewer is present; seed from last_json_pretty if needed
     if state.json_viewer.is_none() {
         if let Some(txt) = &state.last_json_pretty {
@@ -36,10 +38,14 @@ pub fn draw_json(f: &mut Frame, area: Rect, state: &mut AppState) {
             !matches!(state.view, crate::ui::View::Panel),
             0,
         );
+        if let Some(h) = &state.stream_highlights {
+            h.paint(f.buffer_mut(), area);
+        }
     } else {
-        // Fallback: nothing to render
+        // Fallback: the stored text as is (a truncated huge result no longer parses)
         let block = panel_block("JSON Output", !matches!(state.view, crate::ui::View::Panel));
-        let p = Paragraph::new("").block(block);
+        let text = state.last_json_pretty.as_deref().unwrap_or("");
+        let p = Paragraph::new(text).block(block);
         f.render_widget(p, area);
     }
 }
@@ -51,6 +57,8 @@ pub struct JsonViewerWidget {
     pub scroll_y: u16,
     pub wrap: bool,
     last_viewport_h: u16,
+    // Visual rows of the text and the line selection ('V')
+    soft: SoftWrap,
 }
 
 impl JsonViewerWidget {
@@ -62,6 +70,7 @@ impl JsonViewerWidget {
             scroll_y: 0,
             wrap: false,
             last_viewport_h: 0,
+            soft: SoftWrap::default(),
         }
     }
     #[allow(dead_code)]
@@ -73,40 +82,92 @@ impl JsonViewerWidget {
             scroll_y: 0,
             wrap: false,
             last_viewport_h: 0,
+            soft: SoftWrap::default(),
         }
     }
+
+    // A line selection is open ('V'): the viewer wants every key
+    pub fn selecting(&self) -> bool {
+        self.soft.selecting()
+    }
+
+    // The selected lines as written (no wrap breaks)
+    pub fn selected_text(&self) -> Option<String> {
+        self.soft
+            .selected_text(&self.text.lines().collect::<Vec<_>>())
+    }
 }
 
 impl crate::widgets::Widget for JsonViewerWidget {
     fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, _tick: u64) {
         let mut lines: Vec<Line> = Vec::new();
         if let Some(err) = &self.error {
-            lines.push(Line::from(err.clone()).style(Style::default().fg(Color::Red)));
+            lines.push(crate::theme::active().error_line(err));
             lines.push(Line::from(""));
         }
-        for l in self.text.lines() {
-            lines.push(Line::from(l.to_string()));
-        }
+        let prefix = lines.len() as u16;
+        let theme = crate::theme::active();
+        // Wrapped here rather than by the Paragraph so rows map back to the text's lines
+        let text: Vec<&str> = self.text.lines().collect();
+        let width = if self.wrap {
+            area.width.saturating_sub(2)
+        } else {
+            0
+        };
+        self.soft.layout(&text, width as usize);
+        let styled: Vec<Line<'static>> = text
+            .iter()
+            .map(|l| crate::widgets::syntax::json_line(l, &theme))
+            .collect();
+        lines.extend(self.soft.wrap(&styled, theme.list_cursor_style()));
         // viewport
         self.last_viewport_h = area.height.saturating_sub(2);
+        if self.soft.selecting() {
+            let y = self
+                .soft
+                .follow(self.scroll_y.saturating_sub(prefix), self.last_viewport_h);
+            self.scroll_y = y + prefix;
+        }
         let total_lines = lines.len() as u16;
         let max_scroll = total_lines.saturating_sub(self.last_viewport_h);
         if self.scroll_y > max_scroll {
             self.scroll_y = max_scroll;
         }
-        let block = panel_block(&self.title, focused);
+        let title = match self.soft.selected_lines() {
+            Some(r) => format!(
+                "{} — lines {}-{} (y copy, Esc cancel)",
+                self.title,
+                r.start() + 1,
+                r.end() + 1
+            ),
+            None => self.title.clone(),
+        };
+        let title = crate::widgets::chrome::fit_title(&title, area);
+        let block = panel_block(&title, focused);
         let p = Paragraph::new(lines)
             .block(block)
-            .wrap(Wrap { trim: !self.wrap })
             .scroll((self.scroll_y, 0));
         f.render_widget(p, area);
     }
     fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        let prefix = if self.error.is_some() { 2 } else { 0 };
+        let text: Vec<&str> = self.text.lines().collect();
+        let top = self.scroll_y.saturating_sub(prefix) as usize;
+        match self
+            .soft
+            .on_key(key, &text, top, self.last_viewport_h as usize)
+        {
+            WrapKey::Ignored => {}
+            WrapKey::Handled => return Vec::new(),
+            WrapKey::Scroll(d) => {
+                self.scroll_y = self.scroll_y.saturating_add_signed(d as i16);
+                return Vec::new();
+            }
+            WrapKey::Copy(text) => return crate::widgets::soft_wrap::copy_lines(text),
+        }
         match key {
             KeyCode::Up => {
-                if self.scroll_y > 0 {
-                    self.scroll_y -= 1;
-                }
+                self.scroll_y = self.scroll_y.saturating_sub(1);
             }
             KeyCode::Down => {
                 self.scroll_y = self.scroll_y.saturating_add(1);
@@ -127,7 +188,7 @@ impl crate::widgets::Widget for JsonViewerWidget {
                 if self.error.is_some() {
                     total = total.saturating_add(2);
                 }
-                total = total.saturating_add(self.text.lines().count() as u16);
+                total = total.saturating_add(self.soft.rows().len() as u16);
                 let max_scroll = total.saturating_sub(self.last_viewport_h);
                 self.scroll_y = max_scroll;
             }
@@ -138,6 +199,15 @@ impl crate::widgets::Widget for JsonViewerWidget {
         }
         Vec::new()
     }
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![
+            WidgetAction::key("Toggle wrap", "w", KeyCode::Char('w')),
+            WidgetAction::key("Select lines", "V", KeyCode::Char('V')),
+        ];
+        actions.extend(WidgetAction::content());
+        actions
+    }
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -181,4 +251,33 @@ mod tests {
         let _ = w.on_key(KeyCode::Char('w'));
         assert!(w.wrap);
     }
+
+    #[test]
+    fn wrapped_lines_select_and_copy_as_written() {
+        let long = format!("\"{}\"", "x".repeat(50));
+        let mut w = JsonViewerWidget::from_text("JSON", format!("{{\n  \"k\": {long}\n}}"));
+        w.on_key(KeyCode::Char('w'));
+        let backend = TestBackend::new(22, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| w.render(f, f.area(), true, 0)).unwrap();
+        // 20 cells inside the border: the second line takes three rows
+        assert_eq!(w.soft.rows().len(), 5);
+        let row = |t: &Terminal<TestBackend>, y: u16| -> String {
+            (1..21)
+                .map(|x| t.backend().buffer()[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert_eq!(row(&terminal, 3), "x".repeat(20));
+
+        w.on_key(KeyCode::Char('V'));
+        w.on_key(KeyCode::Down);
+        assert!(w.selecting());
+        // gj steps inside the wrapped line; the copy has it whole
+        w.on_key(KeyCode::Char('g'));
+        w.on_key(KeyCode::Char('j'));
+        assert_eq!(w.soft.cursor(), Some(2));
+        assert_eq!(w.selected_text().unwrap(), format!("{{\n  \"k\": {long}"));
+        w.on_key(KeyCode::Esc);
+        assert!(!w.selecting());
+    }
 }
@@ -0,0 +1,196 @@
+use ratatui::prelude::*;
+use serde_json::Value as JsonValue;
+use std::io::Write;
+
+// Results whose JSON is larger than this open as a summary; 'X' expands them anyway
+const DEFAULT_LARGE_BYTES: usize = 4 * 1024 * 1024;
+
+pub fn large_bytes_from_env() -> usize {
+    std::env::var("CHI_TUI_LARGE_RESULT_BYTES")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_LARGE_BYTES)
+}
+
+// Collects at most `limit` bytes, then fails so serialization stops early
+struct Capped {
+    buf: Vec<u8>,
+    limit: usize,
+    // Bytes seen, including the ones past the limit of a counting-only writer
+    seen: usize,
+    keep: bool,
+}
+
+impl Write for Capped {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.seen += data.len();
+        if self.seen > self.limit {
+            if self.keep {
+                let room = self.limit.saturating_sub(self.buf.len());
+                self.buf.extend_from_slice(&data[..room.min(data.len())]);
+            }
+            return Err(std::io::Error::other("size limit"));
+        }
+        if self.keep {
+            self.buf.extend_from_slice(data);
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// Compact JSON size, or None once it exceeds `limit`
+pub fn size_within(v: &JsonValue, limit: usize) -> Option<usize> {
+    let mut w = Capped {
+        buf: Vec::new(),
+        limit,
+        seen: 0,
+        keep: false,
+    };
+    serde_json::to_writer(&mut w, v).ok().map(|_| w.seen)
+}
+
+fn size(v: &JsonValue) -> usize {
+    size_within(v, usize::MAX).unwrap_or(0)
+}
+
+// Pretty JSON, stopped after `limit` bytes with a note (for text copies of a result)
+pub fn pretty_capped(v: &JsonValue, limit: usize) -> String {
+    let mut w = Capped {
+        buf: Vec::new(),
+        limit,
+        seen: 0,
+        keep: true,
+    };
+    if serde_json::to_writer_pretty(&mut w, v).is_ok() {
+        return String::from_utf8(w.buf).unwrap_or_default();
+    }
+    let mut text = String::from_utf8_lossy(&w.buf).into_owned();
+    // Drop a character cut in half at the limit
+    if text.ends_with('\u{FFFD}') {
+        text.pop();
+    }
+    text.push_str(&format!(
+        "\n… (truncated: the result is larger than {})",
+        human_bytes(limit)
+    ));
+    text
+}
+
+// Text copies of results (`last_json_pretty`) keep at most this much
+const STORED_PRETTY_BYTES: usize = 1024 * 1024;
+
+pub fn stored_pretty(v: &JsonValue) -> String {
+    pretty_capped(v, STORED_PRETTY_BYTES)
+}
+
+pub fn human_bytes(n: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = n as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{n} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn kind(v: &JsonValue) -> String {
+    match v {
+        JsonValue::Object(m) => format!("object {{{} keys}}", m.len()),
+        JsonValue::Array(a) => format!("array [{} items]", a.len()),
+        JsonValue::String(s) => format!("string ({} chars)", s.chars().count()),
+        JsonValue::Number(_) => "number".into(),
+        JsonValue::Bool(_) => "bool".into(),
+        JsonValue::Null => "null".into(),
+    }
+}
+
+// Items listed in the summary of a large array
+const SUMMARY_ITEMS: usize = 50;
+
+// Top level of a large value: its keys (or first items) with their kinds and sizes
+pub fn summary_lines(v: &JsonValue, total: usize) -> Vec<Line<'static>> {
+    let theme = crate::theme::active();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "Large result ({}) — showing a summary. Press X to expand anyway (may be slow)",
+                human_bytes(total)
+            ),
+            Style::default().fg(theme.selected),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(kind(v), theme.text_muted())),
+    ];
+    let row = |label: String, child: &JsonValue| {
+        Line::from(vec![
+            Span::styled(format!("  {label}: "), Style::default().fg(theme.syn_key)),
+            Span::raw(kind(child)),
+            Span::styled(
+                format!("  {}", human_bytes(size(child))),
+                theme.text_muted(),
+            ),
+        ])
+    };
+    match v {
+        JsonValue::Object(m) => {
+            for (k, child) in m {
+                lines.push(row(k.clone(), child));
+            }
+        }
+        JsonValue::Array(a) => {
+            for (i, child) in a.iter().take(SUMMARY_ITEMS).enumerate() {
+                lines.push(row(format!("#{i}"), child));
+            }
+            if a.len() > SUMMARY_ITEMS {
+                lines.push(Line::from(Span::styled(
+                    format!("  … {} more items", a.len() - SUMMARY_ITEMS),
+                    theme.text_muted(),
+                )));
+            }
+        }
+        JsonValue::String(s) => {
+            let head: String = s.chars().take(200).collect();
+            lines.push(Line::from(format!("  {head}…")));
+        }
+        _ => {}
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sizes_and_caps_stop_early() {
+        let big = json!({"items": (0..1000).collect::<Vec<_>>(), "name": "x"});
+        let exact = serde_json::to_string(&big).unwrap().len();
+        assert_eq!(size_within(&big, usize::MAX), Some(exact));
+        assert_eq!(size_within(&big, 100), None);
+
+        let text = pretty_capped(&big, 64);
+        assert!(text.starts_with("{\n  \"items\": [\n"));
+        assert!(text.ends_with("(truncated: the result is larger than 64 B)"));
+        assert_eq!(pretty_capped(&json!({"a": 1}), 64), "{\n  \"a\": 1\n}");
+        // A multi-byte character cut at the limit is dropped
+        let text = pretty_capped(&json!("ééééé"), 4);
+        assert!(text.starts_with("\"é\n"));
+
+        let lines = summary_lines(&big, exact);
+        let rendered: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert!(rendered[0].contains("Press X"));
+        assert!(rendered[2].contains("object {2 keys}"));
+        assert!(rendered[3].contains("items: array [1000 items]"));
+        assert_eq!(human_bytes(3 * 1024 * 1024 + 1), "3.0 MB");
+    }
+}
@@ -0,0 +1,137 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+// Single-line field editing by grapheme cluster: a letter composed through a dead key or an
+// IME (`e` + U+0301), a flag or a ZWJ emoji moves and deletes as one character. The caret is
+// a byte offset into the text; None keeps it after the last character (the default, so typing
+// appends).
+
+// Caret position in `s` (a stale or out-of-range caret counts as the end)
+pub fn caret_at(s: &str, caret: Option<usize>) -> usize {
+    caret
+        .filter(|&c| c <= s.len() && s.is_char_boundary(c))
+        .unwrap_or(s.len())
+}
+
+fn set(s: &str, caret: &mut Option<usize>, at: usize) {
+    *caret = (at < s.len()).then_some(at);
+}
+
+fn prev_boundary(s: &str, at: usize) -> usize {
+    s[..at]
+        .grapheme_indices(true)
+        .next_back()
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+fn next_boundary(s: &str, at: usize) -> usize {
+    s[at..]
+        .graphemes(true)
+        .next()
+        .map(|g| at + g.len())
+        .unwrap_or(s.len())
+}
+
+// Insert typed or pasted text at the caret, leaving the caret after it
+pub fn insert(s: &mut String, caret: &mut Option<usize>, text: &str) {
+    let at = caret_at(s, *caret);
+    s.insert_str(at, text);
+    if caret.is_some() {
+        set(s, caret, at + text.len());
+    }
+}
+
+// Delete the character before the caret; false when the caret is at the start
+pub fn backspace(s: &mut String, caret: &mut Option<usize>) -> bool {
+    let at = caret_at(s, *caret);
+    if at == 0 {
+        return false;
+    }
+    let from = prev_boundary(s, at);
+    s.replace_range(from..at, "");
+    if caret.is_some() {
+        set(s, caret, from);
+    }
+    true
+}
+
+// Delete the character under the caret; false when the caret is at the end
+pub fn delete(s: &mut String, caret: &mut Option<usize>) -> bool {
+    let at = caret_at(s, *caret);
+    if at == s.len() {
+        return false;
+    }
+    let to = next_boundary(s, at);
+    s.replace_range(at..to, "");
+    set(s, caret, at);
+    true
+}
+
+pub fn left(s: &str, caret: &mut Option<usize>) {
+    let at = caret_at(s, *caret);
+    set(s, caret, prev_boundary(s, at));
+}
+
+pub fn right(s: &str, caret: &mut Option<usize>) {
+    let at = caret_at(s, *caret);
+    set(s, caret, next_boundary(s, at));
+}
+
+pub fn home(s: &str, caret: &mut Option<usize>) {
+    set(s, caret, 0);
+}
+
+// `s` with `marker` drawn at the caret
+pub fn with_marker(s: &str, caret: Option<usize>, marker: char) -> String {
+    let mut out = s.to_string();
+    out.insert(caret_at(s, caret), marker);
+    out
+}
+
+// Characters (grapheme clusters) in `s`, and how many of them are before the caret
+pub fn grapheme_counts(s: &str, caret: Option<usize>) -> (usize, usize) {
+    let at = caret_at(s, caret);
+    (s.graphemes(true).count(), s[..at].graphemes(true).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caret_moves_and_deletes_whole_graphemes() {
+        // "café" with a combining accent, then a family emoji (one ZWJ sequence)
+        let mut s = "cafe\u{301}👨‍👩‍👧".to_string();
+        let mut caret = None;
+        assert_eq!(grapheme_counts(&s, caret), (5, 5));
+
+        left(&s, &mut caret);
+        assert_eq!(caret, Some("cafe\u{301}".len()));
+        left(&s, &mut caret);
+        assert_eq!(caret, Some(3));
+        assert_eq!(with_marker(&s, caret, '|'), "caf|e\u{301}👨‍👩‍👧");
+
+        // Delete drops the letter with its accent; typing goes in at the caret
+        assert!(delete(&mut s, &mut caret));
+        assert_eq!(s, "caf👨‍👩‍👧");
+        insert(&mut s, &mut caret, "é");
+        assert_eq!(s, "café👨‍👩‍👧");
+        assert_eq!(grapheme_counts(&s, caret), (5, 4));
+
+        // Back at the end, Backspace removes the emoji in one go
+        right(&s, &mut caret);
+        assert_eq!(caret, None);
+        assert!(!delete(&mut s, &mut caret));
+        assert!(backspace(&mut s, &mut caret));
+        assert_eq!(s, "café");
+
+        home(&s, &mut caret);
+        assert!(!backspace(&mut s, &mut caret));
+        insert(&mut s, &mut caret, "日本 ");
+        assert_eq!((s.as_str(), caret), ("日本 café", Some("日本 ".len())));
+
+        // A caret left over from another field falls back to the end
+        assert_eq!(caret_at("ab", Some(7)), 2);
+        assert_eq!(caret_at("日本", Some(1)), "日本".len());
+    }
+}
@@ -0,0 +1,586 @@
+use crate::theme::Status;
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::watchdog::output::{OutputBuffer, SharedOutput};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// `widget: log_viewer`: tails a command's output (`command:`) or a file (`path:`) into the
+// same capped buffer the watchdog uses. The view follows new lines until it is scrolled
+// (`F` toggles, End resumes); `/` filters by regex as you type, and lines are colored by the
+// first log level word they carry. `r` restarts the command or re-reads the file.
+// The debug console (Ctrl+D) is the same widget over lines the app pushes itself.
+
+const FILE_POLL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogSource {
+    Command(String),
+    File(PathBuf),
+    // Lines pushed with `push` (nothing to tail or restart)
+    Lines,
+}
+
+pub struct LogViewerWidget {
+    title: String,
+    source: LogSource,
+    lines: SharedOutput,
+    // Set to stop the current tail thread (restart or drop)
+    stop: Arc<AtomicBool>,
+    started: bool,
+    follow: bool,
+    // Top row of the view, among the lines shown (all, or those matching the filter)
+    top: usize,
+    viewport: usize,
+    filter: Option<regex::Regex>,
+    filter_input: String,
+    // Pattern to go back to when typing is cancelled
+    filter_before: String,
+    filter_editing: bool,
+    filter_error: Option<String>,
+    // Sequence numbers of the lines matching `filter`, matched up to `matched_to`
+    matched: Vec<u64>,
+    matched_to: u64,
+    matched_clears: u64,
+}
+
+// Status of the first level word among a line's leading words (timestamps and the like come
+// first; a level further in is part of the message)
+fn line_level(line: &str) -> Option<Option<Status>> {
+    line.split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|w| !w.is_empty())
+        .take(6)
+        .find_map(|w| {
+            let w = w.to_ascii_uppercase();
+            match w.as_str() {
+                "ERROR" | "ERR" | "FATAL" | "PANIC" | "CRITICAL" | "CRIT" => {
+                    Some(Some(Status::Fail))
+                }
+                "WARN" | "WARNING" => Some(Some(Status::Warn)),
+                "INFO" | "NOTICE" => Some(Some(Status::Info)),
+                // Quieter than the rest
+                "DEBUG" | "TRACE" => Some(None),
+                _ => None,
+            }
+        })
+}
+
+fn level_style(line: &str, theme: &crate::theme::Theme) -> Style {
+    match line_level(line) {
+        Some(Some(Status::Info)) | None => Style::default(),
+        Some(Some(status)) => theme.status_style(status),
+        Some(None) => theme.text_muted(),
+    }
+}
+
+// Run `cmdline` and append its stdout and stderr until it exits or `stop` is set
+fn tail_command(cmdline: &str, lines: &OutputBuffer, stop: &AtomicBool) {
+    let parts = shlex::split(&crate::services::interpolate::env(cmdline)).unwrap_or_default();
+    let Some((program, args)) = parts.split_first() else {
+        lines.push("[error] empty command".to_string());
+        return;
+    };
+    let mut child = match Command::new(program)
+        .args(args)
+        .env("CHI_TUI_JSON", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            lines.push(format!("[spawn error] {e}"));
+            return;
+        }
+    };
+    let readers: Vec<Box<dyn Read + Send>> = [
+        child
+            .stdout
+            .take()
+            .map(|r| Box::new(r) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|r| Box::new(r) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let status = thread::scope(|s| {
+        for r in readers {
+            s.spawn(move || {
+                for line in BufReader::new(r).lines().map_while(Result::ok) {
+                    lines.push(line);
+                }
+            });
+        }
+        loop {
+            if stop.load(Ordering::SeqCst) {
+                let _ = child.kill();
+            }
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) | Err(_) => thread::sleep(Duration::from_millis(50)),
+            }
+        }
+    });
+    // The scope joined the readers, so the marker comes after the last line
+    if !stop.load(Ordering::SeqCst) {
+        let code = status.code().map_or("signal".into(), |c| c.to_string());
+        lines.push(format!("[exit {code}]"));
+    }
+}
+
+// Append the lines of `path` and then whatever is written to it; a file that shrinks was
+// truncated or rotated and is read again from the start
+fn tail_file(path: &PathBuf, lines: &OutputBuffer, stop: &AtomicBool) {
+    let mut offset = 0u64;
+    let mut partial = String::new();
+    let mut missing_noted = false;
+    while !stop.load(Ordering::SeqCst) {
+        match std::fs::File::open(path) {
+            Ok(mut file) => {
+                missing_noted = false;
+                let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+                if size < offset {
+                    lines.push("[file truncated]".to_string());
+                    offset = 0;
+                    partial.clear();
+                }
+                if size > offset && file.seek(SeekFrom::Start(offset)).is_ok() {
+                    let mut buf = Vec::new();
+                    if let Ok(n) = file.take(size - offset).read_to_end(&mut buf) {
+                        offset += n as u64;
+                        partial.push_str(&String::from_utf8_lossy(&buf));
+                        // Keep an unfinished last line until its newline arrives
+                        while let Some(nl) = partial.find('\n') {
+                            let line: String = partial.drain(..=nl).collect();
+                            lines.push(line.trim_end_matches(['\n', '\r']).to_string());
+                        }
+                    }
+                }
+            }
+            Err(e) if !missing_noted => {
+                lines.push(format!("[waiting for {}] {e}", path.display()));
+                missing_noted = true;
+            }
+            Err(_) => {}
+        }
+        thread::sleep(FILE_POLL);
+    }
+}
+
+impl LogViewerWidget {
+    pub fn new(title: impl Into<String>, source: LogSource) -> Self {
+        Self {
+            title: title.into(),
+            source,
+            lines: Arc::new(OutputBuffer::new()),
+            stop: Arc::new(AtomicBool::new(false)),
+            started: false,
+            follow: true,
+            top: 0,
+            viewport: 0,
+            filter: None,
+            filter_input: String::new(),
+            filter_before: String::new(),
+            filter_editing: false,
+            filter_error: None,
+            matched: Vec::new(),
+            matched_to: 0,
+            matched_clears: 0,
+        }
+    }
+
+    // True while `/` collects a pattern; the UI then sends every key here
+    pub fn filter_editing(&self) -> bool {
+        self.filter_editing
+    }
+
+    fn start(&mut self) {
+        self.started = true;
+        let lines = Arc::clone(&self.lines);
+        let stop = Arc::clone(&self.stop);
+        let source = self.source.clone();
+        if source == LogSource::Lines {
+            return;
+        }
+        thread::spawn(move || match &source {
+            LogSource::Command(cmd) => tail_command(cmd, &lines, &stop),
+            LogSource::File(path) => tail_file(path, &lines, &stop),
+            LogSource::Lines => {}
+        });
+    }
+
+    pub fn push(&self, line: String) {
+        self.lines.push(line);
+    }
+
+    fn restart(&mut self) {
+        if self.source == LogSource::Lines {
+            return;
+        }
+        self.stop.store(true, Ordering::SeqCst);
+        self.stop = Arc::new(AtomicBool::new(false));
+        self.lines.clear();
+        self.follow = true;
+        self.start();
+    }
+
+    fn set_filter(&mut self, pattern: &str) {
+        self.filter_error = None;
+        self.filter = None;
+        if !pattern.is_empty() {
+            match regex::RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+            {
+                Ok(re) => self.filter = Some(re),
+                Err(e) => self.filter_error = Some(format!("Invalid filter: {e}")),
+            }
+        }
+        self.matched.clear();
+        self.matched_to = 0;
+        self.top = 0;
+    }
+
+    // Bring the matches up to date: only lines written since the last call are tested
+    fn update_matches(&mut self) {
+        let Some(re) = &self.filter else {
+            return;
+        };
+        let (matched, matched_to) = (&mut self.matched, self.matched_to);
+        let clears = self.matched_clears;
+        let (to, first, now_clears) = self.lines.read(|l| {
+            if l.clears() != clears {
+                matched.clear();
+            }
+            let from = if l.clears() != clears { 0 } else { matched_to };
+            let start = from.max(l.first_seq());
+            for (seq, line) in (start..).zip(l.since(start)) {
+                if re.is_match(line) {
+                    matched.push(seq);
+                }
+            }
+            (l.end_seq(), l.first_seq(), l.clears())
+        });
+        // Matches trimmed out of the buffer
+        let gone = self.matched.partition_point(|&seq| seq < first);
+        self.matched.drain(..gone);
+        self.matched_to = to;
+        self.matched_clears = now_clears;
+    }
+
+    fn shown_len(&self) -> usize {
+        match self.filter {
+            Some(_) => self.matched.len(),
+            None => self.lines.len(),
+        }
+    }
+
+    // Lines on screen from row `top` of the shown lines
+    fn visible(&self, top: usize, rows: usize) -> Vec<String> {
+        match self.filter {
+            None => self.lines.window(top, rows),
+            Some(_) => {
+                let seqs = self.matched.iter().skip(top).take(rows);
+                self.lines.read(|l| {
+                    seqs.filter_map(|&seq| l.get(seq).map(String::from))
+                        .collect()
+                })
+            }
+        }
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        self.follow = false;
+        let max = self.shown_len().saturating_sub(self.viewport);
+        self.top = self.top.saturating_add_signed(delta).min(max);
+    }
+
+    fn filter_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Enter => self.filter_editing = false,
+            KeyCode::Esc => {
+                self.filter_editing = false;
+                self.filter_input = std::mem::take(&mut self.filter_before);
+                let pattern = self.filter_input.clone();
+                self.set_filter(&pattern);
+            }
+            KeyCode::Backspace => {
+                self.filter_input.pop();
+                let pattern = self.filter_input.clone();
+                self.set_filter(&pattern);
+            }
+            KeyCode::Char(c) => {
+                self.filter_input.push(c);
+                let pattern = self.filter_input.clone();
+                self.set_filter(&pattern);
+            }
+            _ => {}
+        }
+    }
+
+    // Kept lines matching the filter (all without one), for Ctrl+C
+    pub fn text(&self) -> String {
+        let re = self.filter.as_ref();
+        self.lines.read(|l| {
+            l.since(0)
+                .filter(|s| re.is_none_or(|re| re.is_match(s)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+}
+
+impl Drop for LogViewerWidget {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+impl crate::widgets::Widget for LogViewerWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, _tick: u64) {
+        if !self.started {
+            self.start();
+        }
+        self.update_matches();
+        let theme = crate::theme::active();
+        let total = self.shown_len();
+        let mode = if self.follow { "following" } else { "paused" };
+        let mut title = format!("{} ({total} lines, {mode})", self.title);
+        if self.filter.is_some() && !self.filter_editing {
+            title.push_str(&format!(" /{}", self.filter_input));
+        }
+        let title = fit_title(&title, area);
+        let block = panel_block(&title, focused);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let [body, foot] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        self.viewport = body.height as usize;
+        let max_top = total.saturating_sub(self.viewport);
+        self.top = if self.follow {
+            max_top
+        } else {
+            self.top.min(max_top)
+        };
+        let lines: Vec<Line> = self
+            .visible(self.top, self.viewport)
+            .into_iter()
+            .map(|s| {
+                let style = level_style(&s, &theme);
+                Line::styled(s, style)
+            })
+            .collect();
+        if lines.is_empty() {
+            let empty = if self.filter.is_some() {
+                "No matching lines"
+            } else {
+                "Waiting for output…"
+            };
+            f.render_widget(
+                Paragraph::new(Span::styled(empty, theme.text_muted())),
+                body,
+            );
+        } else {
+            f.render_widget(Paragraph::new(lines), body);
+        }
+        let hint = if let Some(e) = &self.filter_error {
+            Line::from(Span::styled(e.clone(), theme.text_error()))
+        } else if self.filter_editing {
+            Line::from(vec![
+                Span::styled("Filter: ", theme.text_active_bold()),
+                Span::raw(format!("{}▏", self.filter_input)),
+            ])
+        } else if self.source == LogSource::Lines {
+            Line::from(Span::styled(
+                "F follow • / filter • ↑/↓ PgUp/PgDn Home/End scroll • +/- resize • Ctrl+C copy • Esc back",
+                theme.text_muted(),
+            ))
+        } else {
+            Line::from(Span::styled(
+                "F follow • / filter • ↑/↓ PgUp/PgDn Home/End scroll • r restart",
+                theme.text_muted(),
+            ))
+        };
+        f.render_widget(Paragraph::new(hint), foot);
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        if self.filter_editing {
+            self.filter_key(key);
+            return Vec::new();
+        }
+        let page = self.viewport.max(1) as isize;
+        match key {
+            KeyCode::Up => self.scroll_by(-1),
+            KeyCode::Down => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::Home => {
+                self.follow = false;
+                self.top = 0;
+            }
+            KeyCode::End => self.follow = true,
+            KeyCode::Char('F') => self.follow = !self.follow,
+            KeyCode::Char('/') => {
+                self.filter_editing = true;
+                self.filter_before = self.filter_input.clone();
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let follow = if self.follow {
+            "Pause following"
+        } else {
+            "Follow new lines"
+        };
+        let mut actions = vec![
+            WidgetAction::key(follow, "F", KeyCode::Char('F')),
+            WidgetAction::key("Filter lines", "/", KeyCode::Char('/')),
+        ];
+        if self.source != LogSource::Lines {
+            actions.push(WidgetAction::refresh());
+        }
+        actions.push(WidgetAction::export_image());
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        self.restart();
+        Vec::new()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Widget;
+    use std::time::Instant;
+
+    fn wait_for(w: &LogViewerWidget, pred: impl Fn(&[String]) -> bool) -> Vec<String> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let lines = w.lines.to_vec();
+            if pred(&lines) || Instant::now() > deadline {
+                return lines;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn filters_as_you_type_and_scrolling_pauses_follow() {
+        let mut w = LogViewerWidget::new("Logs", LogSource::Command(String::new()));
+        w.started = true;
+        w.viewport = 2;
+        for i in 0..10 {
+            let level = if i % 3 == 0 { "ERROR" } else { "INFO" };
+            w.lines.push(format!("12:00:0{i} {level} request {i}"));
+        }
+        w.on_key(KeyCode::Char('/'));
+        assert!(w.filter_editing());
+        for c in "error".chars() {
+            w.on_key(KeyCode::Char(c));
+        }
+        w.update_matches();
+        assert_eq!(w.shown_len(), 4);
+        w.on_key(KeyCode::Enter);
+        // New lines are matched incrementally
+        w.lines.push("12:00:10 ERROR late".into());
+        w.update_matches();
+        assert_eq!(w.matched, [0, 3, 6, 9, 10]);
+        assert_eq!(
+            w.visible(3, 5),
+            ["12:00:09 ERROR request 9", "12:00:10 ERROR late"]
+        );
+
+        w.on_key(KeyCode::Up);
+        assert!(!w.follow);
+        w.on_key(KeyCode::End);
+        assert!(w.follow);
+        w.on_key(KeyCode::Char('F'));
+        assert!(!w.follow);
+
+        // Esc while typing restores the previous filter
+        w.on_key(KeyCode::Char('/'));
+        w.on_key(KeyCode::Char('('));
+        assert!(w.filter_error.is_some());
+        w.on_key(KeyCode::Esc);
+        assert_eq!(w.filter_input, "error");
+        assert!(w.filter.is_some() && w.filter_error.is_none());
+    }
+
+    #[test]
+    fn lines_are_styled_by_their_level() {
+        let theme = crate::theme::Theme::default();
+        assert_eq!(
+            line_level("2024-06-01T12:00:00Z ERROR boom"),
+            Some(Some(Status::Fail))
+        );
+        assert_eq!(line_level("[warn] disk at 91%"), Some(Some(Status::Warn)));
+        assert_eq!(line_level("DEBUG cache hit"), Some(None));
+        assert_eq!(line_level("plain text"), None);
+        // A level word deep in the message doesn't count
+        assert_eq!(line_level("a b c d e f g error"), None);
+        assert_eq!(
+            level_style("E ERROR x", &theme),
+            theme.status_style(Status::Fail)
+        );
+        assert_eq!(level_style("TRACE x", &theme), theme.text_muted());
+    }
+
+    #[test]
+    fn tails_a_command_and_a_growing_file() {
+        let mut w = LogViewerWidget::new(
+            "Logs",
+            LogSource::Command("sh -c 'echo one; echo two >&2; exit 3'".into()),
+        );
+        w.start();
+        let lines = wait_for(&w, |l| l.iter().any(|s| s.starts_with("[exit")));
+        assert!(lines.contains(&"one".to_string()) && lines.contains(&"two".to_string()));
+        assert_eq!(lines.last().map(String::as_str), Some("[exit 3]"));
+
+        let path = std::env::temp_dir().join(format!("chi-log-viewer-{}.log", std::process::id()));
+        std::fs::write(&path, "first\nsecond\npart").unwrap();
+        let mut w = LogViewerWidget::new("File", LogSource::File(path.clone()));
+        w.start();
+        assert_eq!(wait_for(&w, |l| l.len() == 2), ["first", "second"]);
+        let mut f = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        std::io::Write::write_all(&mut f, b"ial\nthird\n").unwrap();
+        assert_eq!(
+            wait_for(&w, |l| l.len() == 4),
+            ["first", "second", "partial", "third"]
+        );
+        // Truncation starts over
+        std::fs::write(&path, "fresh\n").unwrap();
+        let lines = wait_for(&w, |l| l.last().is_some_and(|s| s == "fresh"));
+        assert_eq!(&lines[4..], ["[file truncated]", "fresh"]);
+        drop(w);
+        let _ = std::fs::remove_file(path);
+    }
+}
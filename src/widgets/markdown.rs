@@ -13,7 +13,7 @@ use syntect::parsing::{SyntaxReference, SyntaxSet};
 /// MVP:
 /// - Headers (#, ##, ###) styled bold
 /// - Code blocks (``` ... ```) styled with a distinct color
-/// - Simple paragraph lines otherwise
+/// - Inline `code`, **strong**, *emphasis* and [links](url) colored from the theme
 pub struct MarkdownWidget {
     title: String,
     lines: Vec<Line<'static>>,
@@ -21,81 +21,133 @@ pub struct MarkdownWidget {
     wrap: bool,
     last_viewport_h: u16,
     pub raw_content: String,
+    // File name used by 'e' to export the page (generated pages only)
+    export_name: Option<String>,
+    // File the page was read from; 'r' re-reads it
+    source_path: Option<std::path::PathBuf>,
 }
 
 impl MarkdownWidget {
-    pub fn from_text(title: impl Into<String>, text: &str) -> Self {
-        let raw_content = text.to_string();
-        let mut lines: Vec<Line<'static>> = Vec::new();
-        // Parse line by line and syntax-highlight fenced code blocks using syntect
-        let mut in_code = false;
-        let mut code_buf: Vec<String> = Vec::new();
-        let mut code_lang: Option<String> = None;
-        for raw in text.lines() {
-            let trimmed = raw.trim_end_matches('\r');
-            if trimmed.starts_with("```") {
-                if in_code {
-                    // flush code_buf as highlighted lines
-                    let code_text = code_buf.join("\n");
-                    let mut hlines = highlight_code(&code_text, code_lang.as_deref());
-                    lines.append(&mut hlines);
-                    code_buf.clear();
-                    code_lang = None;
-                } else {
-                    // opening fence can specify language: ```rust
-                    let lang = trimmed.trim_start_matches("```").trim();
-                    if !lang.is_empty() {
-                        code_lang = Some(lang.to_string());
-                    }
-                }
-                in_code = !in_code;
-                // Show fence line faint
-                lines.push(Line::from(Span::styled(
-                    trimmed.to_string(),
-                    Style::default().fg(Color::DarkGray),
-                )));
-                continue;
-            }
-            if in_code {
-                code_buf.push(trimmed.to_string());
-                continue;
-            }
-            // Headings and plain lines
-            if trimmed.starts_with("### ")
-                || trimmed.starts_with("## ")
-                || trimmed.starts_with("# ")
-            {
-                lines.push(Line::from(Span::styled(
-                    trimmed.to_string(),
-                    Style::default().add_modifier(Modifier::BOLD),
-                )));
-            } else {
-                lines.push(Line::from(trimmed.to_string()));
-            }
-        }
-        // If file ended within a code block, flush it
-        if in_code && !code_buf.is_empty() {
-            let code_text = code_buf.join("\n");
-            let mut hlines = highlight_code(&code_text, code_lang.as_deref());
-            lines.append(&mut hlines);
+    // Text rendered elsewhere (diff, ANSI or table results); `raw` is what copying gives
+    pub fn from_lines(title: impl Into<String>, lines: Vec<Line<'static>>, raw: &str) -> Self {
+        Self {
+            lines,
+            raw_content: raw.to_string(),
+            ..Self::from_text(title, "")
         }
+    }
+
+    pub fn from_text(title: impl Into<String>, text: &str) -> Self {
         Self {
             title: title.into(),
-            lines,
+            lines: markdown_lines(text),
             scroll_y: 0,
             wrap: true,
             last_viewport_h: 0,
-            raw_content,
+            raw_content: text.to_string(),
+            export_name: None,
+            source_path: None,
+        }
+    }
+
+    pub fn with_export_name(mut self, name: impl Into<String>) -> Self {
+        self.export_name = Some(name.into());
+        self
+    }
+
+    // Write the raw markdown next to the working directory
+    fn export(&self) -> crate::app::Effect {
+        let Some(name) = &self.export_name else {
+            return crate::app::Effect::ShowToast {
+                text: "Nothing to export".into(),
+                level: crate::ui::ToastLevel::Info,
+                seconds: 2,
+            };
+        };
+        let path = std::env::current_dir()
+            .unwrap_or_else(|_| std::path::PathBuf::from("."))
+            .join(name);
+        match std::fs::write(&path, &self.raw_content) {
+            Ok(()) => crate::app::Effect::ShowToast {
+                text: format!("Exported to {}", path.display()),
+                level: crate::ui::ToastLevel::Success,
+                seconds: 3,
+            },
+            Err(e) => crate::app::Effect::ShowToast {
+                text: format!("Export failed: {e}"),
+                level: crate::ui::ToastLevel::Error,
+                seconds: 3,
+            },
         }
     }
 
     pub fn from_path(title: impl Into<String>, path: &std::path::Path) -> Self {
         let content = std::fs::read_to_string(path)
             .unwrap_or_else(|_| format!("# Error\nFailed to read file: {}", path.display()));
-        Self::from_text(title, &content)
+        let mut w = Self::from_text(title, &content);
+        w.source_path = Some(path.to_path_buf());
+        w
     }
 }
 
+// Styled lines of a markdown page (also used by the runbook widget for its prose)
+pub(crate) fn markdown_lines(text: &str) -> Vec<Line<'static>> {
+    let theme = crate::theme::active();
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    // Parse line by line and syntax-highlight fenced code blocks using syntect
+    let mut in_code = false;
+    let mut code_buf: Vec<String> = Vec::new();
+    let mut code_lang: Option<String> = None;
+    for raw in text.lines() {
+        let trimmed = raw.trim_end_matches('\r');
+        if trimmed.starts_with("```") {
+            if in_code {
+                // flush code_buf as highlighted lines
+                let code_text = code_buf.join("\n");
+                let mut hlines = highlight_code(&code_text, code_lang.as_deref());
+                lines.append(&mut hlines);
+                code_buf.clear();
+                code_lang = None;
+            } else {
+                // opening fence can specify language: ```rust
+                let lang = trimmed.trim_start_matches("```").trim();
+                if !lang.is_empty() {
+                    code_lang = Some(lang.to_string());
+                }
+            }
+            in_code = !in_code;
+            // Show fence line faint
+            lines.push(Line::from(Span::styled(
+                trimmed.to_string(),
+                theme.text_muted(),
+            )));
+            continue;
+        }
+        if in_code {
+            code_buf.push(trimmed.to_string());
+            continue;
+        }
+        // Headings and plain lines
+        if trimmed.starts_with("### ") || trimmed.starts_with("## ") || trimmed.starts_with("# ") {
+            lines.push(Line::from(Span::styled(
+                trimmed.to_string(),
+                theme.md_heading(),
+            )));
+        } else {
+            lines.push(Line::from(crate::widgets::syntax::markdown_inline(
+                trimmed, &theme,
+            )));
+        }
+    }
+    // If file ended within a code block, flush it
+    if in_code && !code_buf.is_empty() {
+        let code_text = code_buf.join("\n");
+        let mut hlines = highlight_code(&code_text, code_lang.as_deref());
+        lines.append(&mut hlines);
+    }
+    lines
+}
+
 // ---------------- Syntax highlighting helpers ----------------
 static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
 static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
@@ -119,7 +171,7 @@ fn syn_to_tui_color(c: syntect::highlighting::Color) -> Color {
     Color::Rgb(c.r, c.g, c.b)
 }
 
-fn highlight_code(code: &str, lang: Option<&str>) -> Vec<Line<'static>> {
+pub(crate) fn highlight_code(code: &str, lang: Option<&str>) -> Vec<Line<'static>> {
     let ps = get_syntax_set();
     let theme = get_theme();
     let syn: &SyntaxReference = match lang {
@@ -163,7 +215,8 @@ impl crate::widgets::Widget for MarkdownWidget {
         if self.scroll_y > max_scroll {
             self.scroll_y = max_scroll;
         }
-        let block = panel_block(&self.title, focused);
+        let title = crate::widgets::chrome::fit_title(&self.title, area);
+        let block = panel_block(&title, focused);
         let p = Paragraph::new(self.lines.clone())
             .block(block)
             .wrap(Wrap { trim: !self.wrap })
@@ -174,9 +227,7 @@ impl crate::widgets::Widget for MarkdownWidget {
     fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
         match key {
             KeyCode::Up => {
-                if self.scroll_y > 0 {
-                    self.scroll_y -= 1;
-                }
+                self.scroll_y = self.scroll_y.saturating_sub(1);
             }
             KeyCode::Down => {
                 self.scroll_y = self.scroll_y.saturating_add(1);
@@ -199,11 +250,40 @@ impl crate::widgets::Widget for MarkdownWidget {
             KeyCode::Char('w') | KeyCode::Char('W') => {
                 self.wrap = !self.wrap;
             }
+            KeyCode::Char('e') if self.export_name.is_some() => return vec![self.export()],
             _ => {}
         }
         Vec::new()
     }
 
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![WidgetAction::key("Toggle wrap", "w", KeyCode::Char('w'))];
+        if self.export_name.is_some() {
+            actions.push(WidgetAction::key("Export page", "e", KeyCode::Char('e')));
+        }
+        if self.source_path.is_some() {
+            actions.push(WidgetAction::refresh());
+        }
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        let Some(path) = self.source_path.clone() else {
+            return Vec::new();
+        };
+        // Rebuild from the file but keep the reader's position
+        let fresh = Self::from_path(self.title.clone(), &path);
+        self.lines = fresh.lines;
+        self.raw_content = fresh.raw_content;
+        vec![crate::app::Effect::ShowToast {
+            text: format!("Reloaded {}", path.display()),
+            level: crate::ui::ToastLevel::Info,
+            seconds: 2,
+        }]
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -5,7 +5,8 @@ use ratatui::widgets::*;
 use crate::nav::flatten::flatten_nodes;
 use crate::nav::keys::menu_key;
 use crate::ui::AppState;
-use crate::widgets::chrome::panel_block;
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::text_width::truncate;
 
 #[allow(dead_code)]
 pub(crate) fn compute_scroll_window(total: usize, selected: usize, inner_h: u16) -> (usize, usize) {
@@ -28,6 +29,22 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
     let max_start = total.saturating_sub(ih);
     let start = state.menu_offset.min(max_start);
     let end = (start + ih).min(total);
+    // Titles are cut to the row (by display width) so wide text ends in "…"; the 2+2 cells
+    // are the selection marker and the chevron or bullet in front of a title
+    let inner_w = area.width.saturating_sub(2) as usize;
+    let title_w = |depth: usize| inner_w.saturating_sub(4 + 2 * depth);
+    // Titles of rows with a note ('n') end in a marker, items with a hotkey in the key
+    let noted = |key: &str, title: &str, depth: usize, hotkey: Option<char>| {
+        let mut marker = String::new();
+        if state.notes.has(key) {
+            marker.push_str(crate::widgets::notes::MARKER);
+        }
+        if let Some(c) = hotkey {
+            marker.push_str(&format!(" [{c}]"));
+        }
+        let w = title_w(depth).saturating_sub(marker.chars().count());
+        format!("{}{marker}", truncate(title, w))
+    };
     let items: Vec<ListItem> = nodes
         .iter()
         .enumerate()
@@ -40,7 +57,7 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                 crate::ui::FlatNode::Header { idx, depth } => {
                     let m = &state.config.menu[*idx];
                     let indent = "  ".repeat(*depth);
-                    let text = format!("{sel}{indent}{}", m.title);
+                    let text = format!("{sel}{indent}{}", truncate(&m.title, title_w(*depth)));
                     let mut item = ListItem::new(text);
                     item = item.style(Style::default().fg(Color::Yellow));
                     item
@@ -48,7 +65,12 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                 crate::ui::FlatNode::Menu { idx, depth } => {
                     let m = &state.config.menu[*idx];
                     let indent = "  ".repeat(*depth);
-                    let mut text = m.title.clone();
+                    let mut text = noted(
+                        &menu_key(m),
+                        &m.title,
+                        *depth,
+                        crate::nav::hotkeys::key_of(m),
+                    );
                     if crate::ui::is_lazy(m) {
                         let hint = m
                             .initial_text
@@ -61,7 +83,8 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                             "▸"
                         };
                         text = if state.loading.contains(&key) {
-                            let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.tick as usize % 6];
+                            let spinner =
+                                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.frame() as usize % 6];
                             format!("{chevron} {text} ({spinner} loading) — {hint}")
                         } else if state.children.contains_key(&key) {
                             format!("{chevron} {text} (loaded) — {hint}")
@@ -77,7 +100,8 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                         };
                         let on_enter = crate::ui::expand_on_enter_menu(m);
                         text = if state.loading.contains(&key) {
-                            let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.tick as usize % 6];
+                            let spinner =
+                                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.frame() as usize % 6];
                             format!("{chevron} {text} ({spinner} loading)")
                         } else if state.children.contains_key(&key) {
                             format!("{chevron} {text} (auto-loaded)")
@@ -86,6 +110,14 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                         } else {
                             format!("{chevron} {text} (auto)")
                         };
+                    } else if m.children_from_glob.is_some() {
+                        let key = menu_key(m);
+                        text = match state.children.get(&key) {
+                            Some(files) if state.expanded.contains(&key) => {
+                                format!("▾ {text} ({} files)", files.len())
+                            }
+                            _ => format!("▸ {text}"),
+                        };
                     }
                     // Watchdog running indicator (blinking orange '* running...' or '(external init)')
                     if crate::ui::is_watchdog(m) {
@@ -95,7 +127,8 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                                 let internal = g.started;
                                 let external = g.external && g.external_running;
                                 if internal || external {
-                                    let blink_on = (state.tick / 2) % 2 == 0; // slower blink
+                                    let blink_on = state.reduced_motion
+                                        || (state.frame() / 2).is_multiple_of(2); // slower blink
                                     let star = if blink_on { "*" } else { " " };
                                     let mut spans: Vec<Span<'_>> = Vec::new();
                                     spans.push(Span::raw(format!("{sel}{indent}{text}  ")));
@@ -108,7 +141,22 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                                     if external && !internal {
                                         spans.push(Span::raw(" running (external init)"));
                                     } else {
-                                        spans.push(Span::raw(" running..."));
+                                        // Aggregate health badge, marked when a command failed
+                                        let summary = g.summary();
+                                        let fail = crate::theme::Status::Fail;
+                                        let (mark, badge_style) = if summary.failed > 0 {
+                                            (
+                                                format!("{} ", state.theme.status_glyph(fail)),
+                                                state.theme.status_style(fail),
+                                            )
+                                        } else {
+                                            (String::new(), Style::default())
+                                        };
+                                        spans.push(Span::raw(" running... "));
+                                        spans.push(Span::styled(
+                                            format!("({mark}{})", summary.badge(g.cmds.len())),
+                                            badge_style,
+                                        ));
                                     }
                                     return ListItem::new(Line::from(spans));
                                 }
@@ -117,7 +165,8 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                     } else if crate::ui::is_panel(m) {
                         // If a panel spawns a watchdog (via YAML/spec), we register its session
                         // under the parent menu key. Show running indicator here as well, including
-                        // nested subpane sessions keyed as "menu:<id>/nested:A|B".
+                        // nested subpane sessions keyed as "menu:<id>/nested:A|B" and a Pane A
+                        // watchdog keyed as "menu:<id>/pane:A".
                         let key = menu_key(m);
                         let mut status: Option<&'static str> = None;
                         if let Some(s) = state.watchdog_sessions.get(&key) {
@@ -130,7 +179,7 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                             }
                         }
                         if status.is_none() {
-                            let prefix = format!("{key}/nested:");
+                            let prefix = format!("{key}/");
                             for (k, s) in &state.watchdog_sessions {
                                 if k.starts_with(&prefix) {
                                     if let Ok(g) = s.lock() {
@@ -146,7 +195,8 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                             }
                         }
                         if let Some(lbl) = status {
-                            let blink_on = (state.tick / 2) % 2 == 0; // slower blink
+                            let blink_on =
+                                state.reduced_motion || (state.frame() / 2).is_multiple_of(2); // slower blink
                             let star = if blink_on { "*" } else { " " };
                             let mut spans: Vec<Span<'_>> = Vec::new();
                             spans.push(Span::raw(format!("{sel}{indent}{text}  ")));
@@ -160,11 +210,45 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                             return ListItem::new(Line::from(spans));
                         }
                     }
+                    // Greyed out while the program its commands run is not installed
+                    if let Some(label) = crate::ui::missing_label(state, m) {
+                        return ListItem::new(format!("{sel}{indent}{text}  ✗ {label}"))
+                            .style(Style::default().fg(Color::DarkGray));
+                    }
+                    // Greyed out while its `enabled_cmd` has not passed
+                    if let Some(label) = state.conditions.disabled_label(m) {
+                        return ListItem::new(format!("{sel}{indent}{text}  ⊘ {label}"))
+                            .style(Style::default().fg(Color::DarkGray));
+                    }
+                    // Greyed out with the remaining time while the item cools down
+                    if let Some(left) = crate::ui::cooldown_left(state, m) {
+                        return ListItem::new(format!("{sel}{indent}{text}  ⏳ {left}s"))
+                            .style(Style::default().fg(Color::DarkGray));
+                    }
+                    // Blocked by a failing startup check (see the banner)
+                    if state.preflight.iter().any(|p| p.blocks(&m.id)) {
+                        return ListItem::new(format!("{sel}{indent}{text}  ⛔ blocked"))
+                            .style(Style::default().fg(Color::DarkGray));
+                    }
+                    // Still enterable, but a file it reads is missing (see `builtin: doctor`)
+                    if let Some(label) = crate::ui::broken_ref_label(state, m) {
+                        let warn = crate::theme::Status::Warn;
+                        return ListItem::new(Line::from(vec![
+                            Span::raw(format!("{sel}{indent}{text}  ")),
+                            Span::styled(
+                                format!("{} {label}", state.theme.status_glyph(warn)),
+                                state.theme.status_style(warn),
+                            ),
+                        ]));
+                    }
                     ListItem::new(format!("{sel}{indent}{text}"))
                 }
-                crate::ui::FlatNode::Child { key, val, depth } => {
+                crate::ui::FlatNode::Child { key, depth, .. } => {
+                    let Some(val) = node.val(state) else {
+                        return ListItem::new("");
+                    };
                     let indent = "  ".repeat(*depth);
-                    let title = crate::ui::title_from_value(val);
+                    let title = noted(key, &crate::ui::title_from_value(val), *depth, None);
                     if crate::ui::is_lazy_value(val) {
                         let hint =
                             crate::ui::initial_text_value(val).unwrap_or("Press Enter to load");
@@ -174,7 +258,8 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                             "▸"
                         };
                         let text = if state.loading.contains(key) {
-                            let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.tick as usize % 6];
+                            let spinner =
+                                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.frame() as usize % 6];
                             format!("{chevron} {title} ({spinner} loading) — {hint}")
                         } else if state.children.contains_key(key) {
                             format!("{chevron} {title} (loaded) — {hint}")
@@ -190,7 +275,8 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                         };
                         let on_enter = crate::ui::expand_on_enter_value(val);
                         let text = if state.loading.contains(key) {
-                            let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.tick as usize % 6];
+                            let spinner =
+                                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.frame() as usize % 6];
                             format!("{chevron} {title} ({spinner} loading)")
                         } else if state.children.contains_key(key) {
                             format!("{chevron} {title} (auto-loaded)")
@@ -201,21 +287,13 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                         };
                         ListItem::new(format!("{sel}{indent}{text}"))
                     } else {
-                        // Meta elements styling: pagination controls and page info
-                        let is_pagination = val
-                            .get("__is_pagination")
-                            .and_then(|v| v.as_bool())
-                            .unwrap_or(false);
+                        // Info rows (a `db:` table header): muted, no bullet prefix
                         let is_info = val
                             .get("__is_info")
                             .and_then(|v| v.as_bool())
                             .unwrap_or(false);
 
-                        if is_pagination {
-                            // Pagination controls: neutral/muted color, no bullet prefix
-                            ListItem::new(format!("{sel}{indent}{title}"))
-                                .style(Style::default().fg(crate::theme::MUTED))
-                        } else if is_info {
+                        if is_info {
                             // Muted color, no bullet prefix
                             ListItem::new(format!("{sel}{indent}{title}"))
                                 .style(Style::default().fg(crate::theme::MUTED))
@@ -265,7 +343,8 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                                     label
                                 };
                                 if let Some(lbl) = running_label {
-                                    let blink_on = (state.tick / 2) % 2 == 0;
+                                    let blink_on = state.reduced_motion
+                                        || (state.frame() / 2).is_multiple_of(2);
                                     let star = if blink_on { "*" } else { " " };
                                     let line = Line::from(vec![
                                         Span::raw(format!("{sel}{indent}• {title}  ")),
@@ -280,6 +359,15 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
                                     return ListItem::new(line);
                                 }
                             }
+                            let fields = crate::ui::row_fields(state, key);
+                            if !fields.is_empty() {
+                                let theme = crate::theme::active();
+                                let mut spans = vec![Span::raw(format!("{sel}{indent}• {title}"))];
+                                spans.extend(crate::widgets::cells::field_spans(
+                                    val, &fields, &theme,
+                                ));
+                                return ListItem::new(Line::from(spans));
+                            }
                             ListItem::new(format!("{sel}{indent}• {title}"))
                         }
                     }
@@ -294,8 +382,37 @@ pub fn draw_menu(f: &mut Frame, area: Rect, state: &AppState) {
         !matches!(state.view, crate::ui::View::Panel)
             || matches!(state.panel_focus, crate::ui::PanelPane::A),
     );
+    // Footer: the page of the paginated list around the selection
+    let pager = crate::ui::paged_list(state)
+        .and_then(|key| state.pages.get(&key))
+        .map(|p| p.label());
+    let block = match pager {
+        Some(label) => block.title_bottom(Line::from(format!(" {label} ")).right_aligned()),
+        None => block,
+    };
     let list = List::new(items).block(block);
     f.render_widget(list, area);
+    // Quick-jump labels replace the selection marker of each visible row
+    if let Some(hm) = &state.hint_mode {
+        for (row, idx) in (start..end).enumerate() {
+            if let Some(label) = hm.label_for(crate::nav::hints::HintTarget::Menu(idx)) {
+                draw_hint_label(f, area, row, label);
+            }
+        }
+    }
+}
+
+// Draw a two-letter jump label over the marker column of a bordered list row
+pub(crate) fn draw_hint_label(f: &mut Frame, area: Rect, row: usize, label: &str) {
+    let y = area.y + 1 + row as u16;
+    if y + 1 >= area.y + area.height || area.width < 4 {
+        return;
+    }
+    let style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    f.buffer_mut().set_string(area.x + 1, y, label, style);
 }
 
 pub struct MenuWidget {
@@ -304,6 +421,8 @@ pub struct MenuWidget {
     pub selected: usize,
     pub offset: usize,
     last_viewport_h: u16,
+    // Quick-jump labels by item index (set while hint mode is active)
+    pub hints: Vec<(usize, String)>,
 }
 
 impl MenuWidget {
@@ -314,8 +433,16 @@ impl MenuWidget {
             selected: 0,
             offset: 0,
             last_viewport_h: 0,
+            hints: Vec::new(),
         }
     }
+    // Item indices currently on screen (as of the last render)
+    pub fn visible_range(&self) -> std::ops::Range<usize> {
+        let total = self.config.menu.len();
+        let ih = self.last_viewport_h as usize;
+        let start = self.offset.min(total.saturating_sub(ih));
+        start..(start + ih).min(total)
+    }
     fn keep_selected_visible(&mut self) {
         let ih = self.last_viewport_h as usize;
         if ih == 0 {
@@ -343,6 +470,7 @@ impl crate::widgets::Widget for MenuWidget {
         let max_start = total.saturating_sub(ih);
         let start = self.offset.min(max_start);
         let end = (start + ih).min(total);
+        let inner_w = area.width.saturating_sub(2) as usize;
         let items: Vec<ListItem> = self
             .config
             .menu
@@ -352,7 +480,8 @@ impl crate::widgets::Widget for MenuWidget {
             .take(end - start)
             .map(|(i, m)| {
                 let sel_mark = if self.selected == i { "> " } else { "  " };
-                let mut text = format!("{}{}", sel_mark, m.title);
+                let title = truncate(&m.title, inner_w.saturating_sub(2));
+                let mut text = format!("{sel_mark}{title}");
                 if let Some(w) = &m.widget {
                     match w.as_str() {
                         "panel" => text.push_str(" [panel]"),
@@ -366,9 +495,15 @@ impl crate::widgets::Widget for MenuWidget {
                 ListItem::new(text)
             })
             .collect();
-        let block = panel_block(&self.title, focused);
+        let title = fit_title(&self.title, area);
+        let block = panel_block(&title, focused);
         let list = List::new(items).block(block);
         f.render_widget(list, area);
+        for (i, label) in &self.hints {
+            if (start..end).contains(i) {
+                draw_hint_label(f, area, i - start, label);
+            }
+        }
     }
     fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
         let total = self.config.menu.len();
@@ -1,28 +1,199 @@
+pub mod action_bar;
+pub mod auth;
 pub mod banner;
+pub mod board;
+pub mod cells;
+pub mod check_rounds;
 pub mod chrome;
+pub mod column_stats;
+pub mod command_prompt;
+pub mod empty_state;
+pub mod error_boundary;
+pub mod filter_bar;
 pub mod form;
+pub mod form_batch;
 pub mod form_widget;
 pub mod header;
+pub mod highlight;
 pub mod horizontal_menu;
+pub mod http_checks;
+pub mod json_tree;
 pub mod json_viewer;
+pub mod large_json;
+pub mod line_edit;
+pub mod log_viewer;
 pub mod markdown;
 pub mod menu;
+pub mod notes;
+pub mod notice;
+pub mod offline;
+pub mod palette;
 pub mod panel;
+pub mod ports;
+pub mod preflight;
+pub mod processes;
+pub mod promql;
+pub mod renderers;
 pub mod result_viewer;
+pub mod results;
+pub mod row_edit;
+pub mod runbook;
+pub mod scheduled;
+pub mod snapshots;
+pub mod soft_wrap;
 pub mod status_bar;
+pub mod stream_viewer;
+pub mod syntax;
+pub mod text_width;
+pub mod tree;
+pub mod unsaved;
 pub mod watchdog;
+pub mod watches;
+pub mod wizard;
+pub mod workspaces;
 
 use crate::app::Effect;
 use crossterm::event::KeyCode;
 use ratatui::prelude::*;
 use std::any::Any;
 
+// What an action bar entry does when picked
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionKind {
+    // Same as pressing the key in the widget
+    Key(KeyCode),
+    // Widget::refresh
+    Refresh,
+    // Copy the widget's content (Ctrl+C); structured data asks for a format first
+    Copy,
+    // Copy the underlying JSON value in the given format
+    CopyAs(crate::services::copy_formats::CopyFormat),
+    // Open the widget's content in $EDITOR
+    OpenInEditor,
+    // Save the rendered widget as a PNG and copy it (Ctrl+P)
+    ExportImage,
+}
+
+// Entry of the action bar (space): label, the shortcut shown next to it, and what it does
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidgetAction {
+    pub label: &'static str,
+    pub keys: &'static str,
+    pub kind: ActionKind,
+}
+
+impl WidgetAction {
+    pub fn key(label: &'static str, keys: &'static str, code: KeyCode) -> Self {
+        Self {
+            label,
+            keys,
+            kind: ActionKind::Key(code),
+        }
+    }
+    pub fn refresh() -> Self {
+        Self {
+            label: "Refresh",
+            keys: "r",
+            kind: ActionKind::Refresh,
+        }
+    }
+    // Charts and dashboards can also be exported as a picture
+    pub fn export_image() -> Self {
+        Self {
+            label: "Export image",
+            keys: "Ctrl+P",
+            kind: ActionKind::ExportImage,
+        }
+    }
+    // Copy and open-in-editor apply to every widget with readable content
+    pub fn content() -> [Self; 2] {
+        [
+            Self {
+                label: "Copy",
+                keys: "Ctrl+C",
+                kind: ActionKind::Copy,
+            },
+            Self {
+                label: "Open in editor",
+                keys: "",
+                kind: ActionKind::OpenInEditor,
+            },
+        ]
+    }
+}
+
+// Footer hints of a widget: its own keys, most useful first ("↑/↓ scroll", "w wrap"). A
+// modal state (typing into a field, a popup) takes every key, so the panel's own keys are
+// not listed after them
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct KeyHints {
+    pub keys: Vec<String>,
+    pub modal: bool,
+}
+
+impl KeyHints {
+    // From "↑/↓ move • Enter edit"
+    pub fn new(keys: &str) -> Self {
+        Self {
+            keys: keys.split(" • ").map(String::from).collect(),
+            modal: false,
+        }
+    }
+    pub fn modal(keys: &str) -> Self {
+        Self {
+            modal: true,
+            ..Self::new(keys)
+        }
+    }
+    // The action bar entries that have a shortcut: "w toggle wrap"
+    pub fn from_actions(actions: &[WidgetAction]) -> Self {
+        let keys = actions
+            .iter()
+            .filter(|a| !a.keys.is_empty())
+            .map(|a| {
+                let mut label = a.label.chars();
+                let first = label.next().map(|c| c.to_lowercase().to_string());
+                format!("{} {}{}", a.keys, first.unwrap_or_default(), label.as_str())
+            })
+            .collect();
+        Self { keys, modal: false }
+    }
+    pub fn push(&mut self, key: &str) {
+        self.keys.push(key.to_string());
+    }
+    pub fn text(&self) -> String {
+        self.keys.join(" • ")
+    }
+}
+
 pub trait Widget {
     fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64);
     fn on_key(&mut self, key: KeyCode) -> Vec<Effect> {
         let _ = key;
         Vec::new()
     }
+    // Reload the widget's own content ('r'): sync reloads happen here, command-backed
+    // widgets return the effect that re-runs their source. Empty = nothing to refresh
+    fn refresh(&mut self) -> Vec<Effect> {
+        Vec::new()
+    }
+    // Actions listed by the action bar (space) for this widget
+    fn actions(&self) -> Vec<WidgetAction> {
+        Vec::new()
+    }
+    // Keys shown in the footer while the widget has focus (default: its actions)
+    fn hints(&self) -> KeyHints {
+        KeyHints::from_actions(&self.actions())
+    }
+    // Input that navigating away would lose (pane title badge and the navigation guard),
+    // the effects that save it, and dropping it instead
+    fn unsaved(&self) -> bool {
+        false
+    }
+    fn save(&mut self) -> Vec<Effect> {
+        Vec::new()
+    }
+    fn discard(&mut self) {}
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
@@ -0,0 +1,53 @@
+use crate::services::notes::Notes;
+use crate::ui::NotePrompt;
+use ratatui::prelude::*;
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+
+// Marker after the title of menu rows that carry a note
+pub const MARKER: &str = " ✎";
+
+// Popup of the selected row's note ('n'): the saved text with when it was written, or the
+// input while editing
+pub fn draw_prompt(f: &mut Frame, screen: Rect, prompt: &NotePrompt, notes: &Notes) {
+    let theme = crate::theme::active();
+    let width = screen.width.saturating_sub(4).min(64);
+    let height = 9.min(screen.height);
+    let rect = Rect {
+        x: screen.x + (screen.width.saturating_sub(width)) / 2,
+        y: screen.y + screen.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let saved = notes.get(&prompt.key);
+    let mut lines = Vec::new();
+    if prompt.editing {
+        lines.push(Line::from(vec![
+            Span::styled("Note: ", theme.text_active_bold()),
+            Span::raw(format!("{}▏", prompt.input)),
+        ]));
+    } else {
+        lines.push(Line::from(prompt.input.clone()));
+    }
+    lines.push(Line::from(""));
+    if let Some(e) = &prompt.error {
+        lines.push(Line::from(Span::styled(e.clone(), theme.text_error())));
+    } else if let Some(note) = saved {
+        let at = crate::services::clock::from_unix(note.updated);
+        lines.push(Line::from(Span::styled(
+            format!("edited {}", crate::services::clock::format_local(at)),
+            theme.text_muted(),
+        )));
+    }
+    let hint = if prompt.editing {
+        "Enter save (empty removes) • Esc cancel"
+    } else {
+        "e edit • d delete • Esc close"
+    };
+    lines.push(Line::from(Span::styled(hint, theme.text_muted())));
+    let title = format!("Note: {}", prompt.title);
+    let p = Paragraph::new(lines)
+        .block(crate::widgets::chrome::panel_block(&title, true))
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
@@ -0,0 +1,177 @@
+use crate::model::BannerSpec;
+use crate::theme::Status;
+use crate::ui::AppState;
+use ratatui::prelude::*;
+use serde_json::Value as JsonValue;
+
+// Notices across the top of the screen: the config's `banner:` and banners pushed by commands
+// (a `_chi: { banner: {...} }` key in their JSON output). A notice with `until:` shows a live
+// countdown and disappears once that time has passed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notice {
+    // Pushing a banner with the same id replaces it (config banner: `config`)
+    id: String,
+    text: String,
+    // Unix time the notice expires
+    until: Option<i64>,
+    level: String,
+}
+
+impl Notice {
+    pub fn from_spec(spec: &BannerSpec, default_id: &str) -> Result<Self, String> {
+        let until = match spec.until.as_deref() {
+            None => None,
+            Some(s) => Some(
+                crate::services::clock::parse_timestamp(&JsonValue::String(s.to_string()))
+                    .ok_or_else(|| format!("banner: invalid `until` time {s:?}"))?,
+            ),
+        };
+        let level = match spec.level.as_deref().unwrap_or("warn") {
+            "warning" => "warn".to_string(),
+            l @ ("info" | "warn" | "error") => l.to_string(),
+            other => return Err(format!("banner: unknown level {other:?} (info|warn|error)")),
+        };
+        Ok(Self {
+            id: spec.id.clone().unwrap_or_else(|| default_id.to_string()),
+            text: spec.text.clone(),
+            until,
+            level,
+        })
+    }
+
+    fn expired(&self, now: i64) -> bool {
+        self.until.is_some_and(|u| u <= now)
+    }
+}
+
+// Banner spec in a command's JSON output, if it pushes one
+pub fn from_envelope(v: &JsonValue) -> Option<Result<BannerSpec, String>> {
+    let spec = v.get("_chi")?.get("banner")?;
+    Some(serde_json::from_value(spec.clone()).map_err(|e| format!("banner: {e}")))
+}
+
+// Add (or replace) a notice; an empty text only removes the notice with that id
+pub fn push(notices: &mut Vec<Notice>, notice: Notice, now: i64) {
+    notices.retain(|n| n.id != notice.id && !n.expired(now));
+    if !notice.text.trim().is_empty() {
+        notices.push(notice);
+    }
+}
+
+// The config's `banner:` (on startup and after switching workspaces)
+pub fn load_config(state: &mut AppState) {
+    state.notices.retain(|n| n.id != "config");
+    let Some(spec) = state.config.banner.clone() else {
+        return;
+    };
+    let now = crate::services::clock::unix_secs(std::time::SystemTime::now());
+    match Notice::from_spec(&spec, "config") {
+        Ok(n) => push(&mut state.notices, n, now),
+        Err(e) => state.dbg(e),
+    }
+}
+
+// "3d 4h", "1h 05m", "4m 09s", "12s"
+fn countdown(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3_599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        3_600..=86_399 => format!("{}h {:02}m", secs / 3_600, secs / 60 % 60),
+        _ => format!("{}d {}h", secs / 86_400, secs / 3_600 % 24),
+    }
+}
+
+pub fn banner_lines(state: &AppState) -> Vec<Line<'static>> {
+    let theme = &state.theme;
+    let now = crate::services::clock::unix_secs(std::time::SystemTime::now());
+    state
+        .notices
+        .iter()
+        .filter(|n| !n.expired(now))
+        .map(|n| {
+            let status = match n.level.as_str() {
+                "info" => Status::Info,
+                "error" => Status::Fail,
+                _ => Status::Warn,
+            };
+            let (glyph, bg) = (theme.status_glyph(status), theme.status_color(status));
+            let badge = Style::default()
+                .fg(theme.bg)
+                .bg(bg)
+                .add_modifier(Modifier::BOLD);
+            let mut spans = vec![
+                Span::styled(format!(" {glyph} "), badge),
+                Span::styled(format!(" {}", n.text), Style::default().fg(bg)),
+            ];
+            if let Some(until) = n.until {
+                spans.push(Span::styled(
+                    format!(
+                        " — ends in {} ({})",
+                        countdown(until - now),
+                        crate::services::clock::format_local(crate::services::clock::from_unix(
+                            until
+                        ))
+                    ),
+                    theme.text_muted(),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn notices_count_down_expire_and_are_replaced_by_id() {
+        let spec = BannerSpec {
+            text: "Deploy freeze".into(),
+            until: Some("2024-06-01T12:00Z".into()),
+            ..Default::default()
+        };
+        let n = Notice::from_spec(&spec, "config").unwrap();
+        let until = 1_717_243_200;
+        assert_eq!((n.until, n.level.as_str()), (Some(until), "warn"));
+        assert!(!n.expired(until - 1));
+        assert!(n.expired(until));
+        assert_eq!(countdown(3_725), "1h 02m");
+        assert_eq!(countdown(249), "4m 09s");
+        assert_eq!(countdown(90_000), "1d 1h");
+
+        let bad = BannerSpec {
+            level: Some("loud".into()),
+            ..spec.clone()
+        };
+        assert!(Notice::from_spec(&bad, "config").is_err());
+
+        // Pushed banners replace the one with the same id; empty text clears it
+        let pushed = |v: JsonValue| {
+            Notice::from_spec(&from_envelope(&v).unwrap().unwrap(), "pushed").unwrap()
+        };
+        let mut notices = vec![n];
+        push(
+            &mut notices,
+            pushed(json!({"_chi": {"banner": {"text": "Maintenance", "level": "info"}}})),
+            until - 60,
+        );
+        push(
+            &mut notices,
+            pushed(json!({"ok": true, "_chi": {"banner": {"text": "Maintenance done"}}})),
+            until - 30,
+        );
+        assert_eq!(notices.len(), 2);
+        assert_eq!(notices[1].text, "Maintenance done");
+        push(
+            &mut notices,
+            pushed(json!({"_chi": {"banner": {"text": ""}}})),
+            until + 1,
+        );
+        // The expired config banner is dropped too
+        assert!(notices.is_empty());
+        assert!(from_envelope(&json!({"banner": {"text": "x"}})).is_none());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::services::result_cache;
+use crate::ui::{AppState, View};
+use ratatui::prelude::*;
+
+// Banner line for offline mode and for a shown result that came from the cache
+pub fn banner_lines(state: &AppState) -> Vec<Line<'static>> {
+    let theme = &state.theme;
+    let badge = Style::default()
+        .fg(theme.bg)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let shown = match state.view {
+        View::Json => state.stream_source.as_deref(),
+        View::Panel => match &state.panel {
+            Some(crate::ui::PanelState {
+                b_content: crate::ui::PaneContent::Widget(w),
+                ..
+            }) => w
+                .as_any()
+                .downcast_ref::<crate::widgets::result_viewer::ResultViewerWidget>()
+                .and_then(|rv| rv.source()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let now = crate::services::clock::unix_secs(std::time::SystemTime::now());
+    let cached = shown.and_then(result_cache::stale_since).map(|saved_at| {
+        format!(
+            " showing cached result from {} ({})",
+            crate::services::clock::format_local(crate::services::clock::from_unix(saved_at)),
+            crate::widgets::cells::relative_time(saved_at, now)
+        )
+    });
+    let mut spans = Vec::new();
+    if result_cache::offline() {
+        spans.push(Span::styled(" OFFLINE ", badge));
+        spans.push(Span::styled(
+            cached.unwrap_or_else(|| " serving cached results • O to go online".to_string()),
+            theme.text_muted(),
+        ));
+    } else if let Some(cached) = cached {
+        spans.push(Span::styled(" ⚠ STALE ", badge));
+        spans.push(Span::styled(
+            format!("{cached} — the command failed"),
+            theme.text_muted(),
+        ));
+    } else {
+        return vec![];
+    }
+    vec![Line::from(spans)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_stale_pane_b_result_shows_the_banner() {
+        let mut st = AppState::default();
+        st.panel = Some(crate::ui::PanelState::default());
+        st.view = View::Panel;
+        let cmd = "app pods --stale-banner-test";
+        result_cache::mark_stale(cmd, 0);
+        // The source is handed to the viewer when the result lands, before any render
+        st.pane_b_source = Some(cmd.into());
+        let _ = crate::app::update(
+            &mut st,
+            crate::app::AppMsg::LoadedPanel {
+                pane: crate::ui::PanelPane::B,
+                outcome: Ok(crate::ui::LoadOutcome::Fallback(serde_json::json!([1]))),
+            },
+        );
+        let text: String = banner_lines(&st)
+            .iter()
+            .flat_map(|l| l.spans.iter().map(|s| s.content.to_string()))
+            .collect();
+        // (STALE, or OFFLINE while another test has offline mode on)
+        assert!(text.contains("showing cached result from"), "{text}");
+    }
+}
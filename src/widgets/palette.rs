@@ -0,0 +1,307 @@
+use crate::nav::keys::{child_key, menu_key};
+use crate::ui::{is_header, title_from_value, AppState};
+use ratatui::prelude::*;
+use ratatui::widgets::{Clear, List, ListItem, Paragraph};
+use serde_json::Value as JsonValue;
+
+// Command palette (Ctrl+K or '/' on the menu): fuzzy search over the menu items, the children
+// loaded so far (expanded or not) and the screens of the tab bar, hidden ones included. Type
+// to narrow (letters in order, any case; tighter matches first), ↑/↓ to pick, Enter to go.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteTarget {
+    // Index into `config.menu`
+    Menu(usize),
+    // A loaded child row; `ancestors` are the keys to expand so it shows in the menu
+    Child {
+        key: String,
+        val: JsonValue,
+        ancestors: Vec<String>,
+    },
+    // Index into `horizontal_menu`
+    Tab(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct PaletteEntry {
+    pub label: String,
+    // Dimmed after the label: the F-key, or "hidden"
+    pub hint: String,
+    pub target: PaletteTarget,
+}
+
+#[derive(Debug, Default)]
+pub struct Palette {
+    pub input: String,
+    // Index into the matching entries
+    pub selected: usize,
+    entries: Vec<PaletteEntry>,
+}
+
+// `query` letters appear in `text` in order (case-insensitive); None when they don't,
+// otherwise lower is a tighter match (letters skipped between and before the matches)
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let mut chars = text.chars().flat_map(char::to_lowercase).enumerate();
+    let mut score = 0;
+    let mut next = 0;
+    for q in query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let (at, _) = chars.by_ref().find(|(_, c)| *c == q)?;
+        // Skipping into the text costs less than a gap inside a match
+        score += if next == 0 {
+            at.min(1)
+        } else {
+            (at - next) * 2
+        };
+        next = at + 1;
+    }
+    Some(score)
+}
+
+// Children of `parent` and everything loaded under them, depth first
+fn child_entries(
+    state: &AppState,
+    parent: &str,
+    path: &str,
+    ancestors: &mut Vec<String>,
+    out: &mut Vec<PaletteEntry>,
+) {
+    let Some(children) = state.children.get(parent) else {
+        return;
+    };
+    ancestors.push(parent.to_string());
+    for (i, val) in children.iter().enumerate() {
+        if val.get("__is_info").is_some() {
+            continue;
+        }
+        let key = child_key(parent, val, i);
+        let label = format!("{path} › {}", title_from_value(val));
+        out.push(PaletteEntry {
+            label: label.clone(),
+            hint: "item".into(),
+            target: PaletteTarget::Child {
+                key: key.clone(),
+                val: val.clone(),
+                ancestors: ancestors.clone(),
+            },
+        });
+        child_entries(state, &key, &label, ancestors, out);
+    }
+    ancestors.pop();
+}
+
+impl Palette {
+    pub fn new(state: &AppState) -> Self {
+        let mut entries = Vec::new();
+        for (i, mi) in state.config.menu.iter().enumerate() {
+            if is_header(mi) || !state.conditions.shown(mi) {
+                continue;
+            }
+            entries.push(PaletteEntry {
+                label: mi.title.clone(),
+                hint: "menu".into(),
+                target: PaletteTarget::Menu(i),
+            });
+            child_entries(
+                state,
+                &menu_key(mi),
+                &mi.title,
+                &mut Vec::new(),
+                &mut entries,
+            );
+        }
+        let tabs = &state.config.horizontal_menu;
+        let shown = crate::widgets::horizontal_menu::visible_tabs(state);
+        entries.extend(shown.iter().enumerate().map(|(pos, &i)| PaletteEntry {
+            label: format!("Screen: {}", tabs[i].title),
+            hint: format!("F{}", pos + 1),
+            target: PaletteTarget::Tab(i),
+        }));
+        entries.extend(
+            tabs.iter()
+                .enumerate()
+                .filter(|(_, t)| t.hidden)
+                .map(|(i, t)| PaletteEntry {
+                    label: format!("Screen: {}", t.title),
+                    hint: "hidden".into(),
+                    target: PaletteTarget::Tab(i),
+                }),
+        );
+        Palette {
+            input: String::new(),
+            selected: 0,
+            entries,
+        }
+    }
+
+    // Entries matching the input, tightest match first (ties keep menu order)
+    pub fn matches(&self) -> Vec<&PaletteEntry> {
+        let mut scored: Vec<(usize, &PaletteEntry)> = self
+            .entries
+            .iter()
+            .filter_map(|e| fuzzy_score(&self.input, &e.label).map(|s| (s, e)))
+            .collect();
+        scored.sort_by_key(|(s, _)| *s);
+        scored.into_iter().map(|(_, e)| e).collect()
+    }
+
+    pub fn chosen(&self) -> Option<&PaletteEntry> {
+        self.matches().get(self.selected).copied()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.selected = 0;
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.selected = 0;
+    }
+
+    pub fn step(&mut self, down: bool) {
+        let n = self.matches().len();
+        if n == 0 {
+            return;
+        }
+        self.selected = if down {
+            (self.selected + 1) % n
+        } else {
+            (self.selected + n - 1) % n
+        };
+    }
+}
+
+pub fn draw_palette(f: &mut Frame, screen: Rect, palette: &Palette) {
+    let theme = crate::theme::active();
+    let width = screen.width.saturating_sub(4).min(64);
+    let height = 12.min(screen.height);
+    let rect = Rect {
+        x: screen.x + (screen.width.saturating_sub(width)) / 2,
+        y: screen.y + screen.height.saturating_sub(height) / 3,
+        width,
+        height,
+    };
+    f.render_widget(Clear, rect);
+    let block = crate::widgets::chrome::panel_block("Command palette", true);
+    let inner = block.inner(rect);
+    f.render_widget(block, rect);
+    if inner.height < 2 {
+        return;
+    }
+    let input = Line::from(vec![
+        Span::styled("> ", theme.text_active_bold()),
+        Span::raw(format!("{}▏", palette.input)),
+    ]);
+    f.render_widget(Paragraph::new(input), Rect { height: 1, ..inner });
+    let matches = palette.matches();
+    let items: Vec<ListItem> = if matches.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No matches",
+            theme.text_muted(),
+        ))]
+    } else {
+        matches
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let marker = if i == palette.selected { "> " } else { "  " };
+                let line = Line::from(vec![
+                    Span::raw(format!("{marker}{}  ", e.label)),
+                    Span::styled(e.hint.clone(), theme.text_muted()),
+                ]);
+                let item = ListItem::new(line);
+                if i == palette.selected {
+                    item.style(theme.list_cursor_style())
+                } else {
+                    item
+                }
+            })
+            .collect()
+    };
+    let list_area = Rect {
+        y: inner.y + 1,
+        height: inner.height - 1,
+        ..inner
+    };
+    // Keep the picked entry in view
+    let skip = palette
+        .selected
+        .saturating_sub(list_area.height.saturating_sub(1) as usize);
+    let items: Vec<ListItem> = items.into_iter().skip(skip).collect();
+    f.render_widget(List::new(items), list_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fuzzy_match(query: &str, text: &str) -> bool {
+        fuzzy_score(query, text).is_some()
+    }
+
+    #[test]
+    fn fuzzy_matches_letters_in_order() {
+        assert!(fuzzy_match("", "Screen: Deploys"));
+        assert!(fuzzy_match("dpl", "Screen: Deploys"));
+        assert!(fuzzy_match("SCR dep", "Screen: Deploys"));
+        assert!(!fuzzy_match("spd", "Screen: Deploys x"));
+        assert!(!fuzzy_match("zz", "Screen: Deploys"));
+        // Contiguous beats scattered, a match at the start beats one further in
+        let pods = fuzzy_score("pod", "Pods").unwrap();
+        assert!(pods < fuzzy_score("pod", "Pending downloads").unwrap());
+        assert!(pods < fuzzy_score("pod", "All pods").unwrap());
+    }
+
+    #[test]
+    fn palette_lists_menu_items_loaded_children_and_screens() {
+        use crate::model::{HorizontalMenuItem, MenuItem};
+        use serde_json::json;
+        let mut st = AppState::default();
+        st.config.menu = vec![MenuItem {
+            id: "pods".into(),
+            title: "Pods".into(),
+            ..Default::default()
+        }];
+        st.config.horizontal_menu = vec![HorizontalMenuItem {
+            id: "admin".into(),
+            title: "Admin".into(),
+            config: None,
+            hidden: true,
+        }];
+        st.children.insert(
+            "menu:pods".into(),
+            vec![
+                json!({"id": "api-1", "title": "api-1"}),
+                json!({"__is_info": true}),
+            ],
+        );
+        st.children
+            .insert("menu:pods/api-1".into(), vec![json!({"name": "sidecar"})]);
+        let mut p = Palette::new(&st);
+        let labels: Vec<&str> = p.matches().iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "Pods",
+                "Pods › api-1",
+                "Pods › api-1 › sidecar",
+                "Screen: Admin"
+            ]
+        );
+        for c in "side".chars() {
+            p.push(c);
+        }
+        assert_eq!(
+            p.chosen().map(|e| &e.target),
+            Some(&PaletteTarget::Child {
+                key: "menu:pods/api-1/#0".into(),
+                val: json!({"name": "sidecar"}),
+                ancestors: vec!["menu:pods".into(), "menu:pods/api-1".into()],
+            })
+        );
+    }
+}
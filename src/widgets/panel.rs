@@ -11,6 +11,44 @@ pub struct PanelWidget {
     title_b: String,
     a_w: Option<Box<dyn crate::widgets::Widget>>,
     b_w: Option<Box<dyn crate::widgets::Widget>>,
+    // Scroll state of plain-text subpanes (errors, non-JSON output)
+    a_view: TextView,
+    b_view: TextView,
+    // 'L': scroll keys move both subpanes to the same line (compare/diff views)
+    sync_scroll: bool,
+}
+
+// Kept across set_subpane_text so a refresh doesn't jump back to the top
+#[derive(Default, Clone, Copy)]
+struct TextView {
+    offset: u16,
+    wrap: bool,
+    viewport_h: u16,
+    total: u16,
+}
+
+impl TextView {
+    fn max_offset(&self) -> u16 {
+        self.total.saturating_sub(self.viewport_h)
+    }
+
+    fn on_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up => self.offset = self.offset.saturating_sub(1),
+            KeyCode::Down => self.offset = self.offset.saturating_add(1).min(self.max_offset()),
+            KeyCode::PageUp => self.offset = self.offset.saturating_sub(self.viewport_h),
+            KeyCode::PageDown => {
+                self.offset = self
+                    .offset
+                    .saturating_add(self.viewport_h)
+                    .min(self.max_offset())
+            }
+            KeyCode::Home => self.offset = 0,
+            KeyCode::End => self.offset = self.max_offset(),
+            KeyCode::Char('w') | KeyCode::Char('W') => self.wrap = !self.wrap,
+            _ => {}
+        }
+    }
 }
 
 impl PanelWidget {
@@ -57,6 +95,9 @@ impl PanelWidget {
             title_b: title_b_str,
             a_w,
             b_w,
+            a_view: TextView::default(),
+            b_view: TextView::default(),
+            sync_scroll: false,
         }
     }
     pub fn nested_focus(&self) -> crate::ui::PanelPane {
@@ -65,6 +106,48 @@ impl PanelWidget {
     pub fn set_nested_focus(&mut self, f: crate::ui::PanelPane) {
         self.nested_focus = f;
     }
+    // Scroll offset of a subpane: its result viewer, or its text view. Other widgets
+    // (forms, watchdogs) keep their own scrolling and are not synchronized.
+    fn offset(&self, sub: crate::ui::PanelPane) -> Option<u16> {
+        let (pane, w, view) = match sub {
+            crate::ui::PanelPane::A => (&self.a, &self.a_w, &self.a_view),
+            crate::ui::PanelPane::B => (&self.b, &self.b_w, &self.b_view),
+        };
+        match w {
+            Some(w) if pane.last_error.is_none() => w
+                .as_any()
+                .downcast_ref::<crate::widgets::result_viewer::ResultViewerWidget>()
+                .map(|rv| rv.scroll_offset()),
+            _ => Some(view.offset),
+        }
+    }
+    fn set_offset(&mut self, sub: crate::ui::PanelPane, y: u16) {
+        let (pane, w, view) = match sub {
+            crate::ui::PanelPane::A => (&self.a, &mut self.a_w, &mut self.a_view),
+            crate::ui::PanelPane::B => (&self.b, &mut self.b_w, &mut self.b_view),
+        };
+        match w {
+            Some(w) if pane.last_error.is_none() => {
+                if let Some(rv) = w
+                    .as_any_mut()
+                    .downcast_mut::<crate::widgets::result_viewer::ResultViewerWidget>()
+                {
+                    rv.set_scroll_offset(y);
+                }
+            }
+            _ => view.offset = y.min(view.max_offset()),
+        }
+    }
+    // Bring the unfocused subpane to the focused one's line
+    fn follow_focused(&mut self) {
+        let other = match self.nested_focus {
+            crate::ui::PanelPane::A => crate::ui::PanelPane::B,
+            crate::ui::PanelPane::B => crate::ui::PanelPane::A,
+        };
+        if let (Some(y), Some(_)) = (self.offset(self.nested_focus), self.offset(other)) {
+            self.set_offset(other, y);
+        }
+    }
     fn constraints(&self) -> [Constraint; 2] {
         match self.ratio {
             crate::ui::PanelRatio::Half => [Constraint::Percentage(50), Constraint::Percentage(50)],
@@ -93,26 +176,12 @@ impl PanelWidget {
             crate::ui::PanelPane::A => {
                 self.a.last_error = None;
                 self.a.last_json_pretty = Some(text.clone());
-                if let Ok(v) = serde_json::from_str::<serde_json::Value>(&text) {
-                    self.a_w = Some(Box::new(
-                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                            self.title_a.clone(),
-                            v,
-                        ),
-                    ));
-                }
+                self.a_w = refreshed_viewer(self.a_w.take(), &self.title_a, &text);
             }
             crate::ui::PanelPane::B => {
                 self.b.last_error = None;
                 self.b.last_json_pretty = Some(text.clone());
-                if let Ok(v) = serde_json::from_str::<serde_json::Value>(&text) {
-                    self.b_w = Some(Box::new(
-                        crate::widgets::result_viewer::ResultViewerWidget::new(
-                            self.title_b.clone(),
-                            v,
-                        ),
-                    ));
-                }
+                self.b_w = refreshed_viewer(self.b_w.take(), &self.title_b, &text);
             }
         }
     }
@@ -132,6 +201,68 @@ impl PanelWidget {
     }
 }
 
+// Refresh an existing viewer in place (keeps its scroll/wrap); non-JSON text drops
+// the viewer so the subpane falls back to its text view
+fn refreshed_viewer(
+    prev: Option<Box<dyn crate::widgets::Widget>>,
+    title: &str,
+    text: &str,
+) -> Option<Box<dyn crate::widgets::Widget>> {
+    let v = serde_json::from_str::<serde_json::Value>(text).ok()?;
+    if let Some(mut w) = prev {
+        if let Some(rv) = w
+            .as_any_mut()
+            .downcast_mut::<crate::widgets::result_viewer::ResultViewerWidget>()
+        {
+            rv.set_value(v);
+            return Some(w);
+        }
+    }
+    Some(Box::new(
+        crate::widgets::result_viewer::ResultViewerWidget::new(title.to_string(), v),
+    ))
+}
+
+// Text subpane with its own offset and wrap; lines are counted to clamp the offset
+fn render_text_pane(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    focused: bool,
+    pane: &crate::ui::PaneData,
+    view: &mut TextView,
+) {
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(err) = &pane.last_error {
+        lines.push(crate::theme::active().error_line(err));
+        lines.push(Line::from(""));
+    }
+    if let Some(txt) = &pane.last_json_pretty {
+        for l in txt.lines() {
+            lines.push(Line::from(l.to_string()));
+        }
+    }
+    view.viewport_h = area.height.saturating_sub(2);
+    let inner_w = area.width.saturating_sub(2).max(1) as usize;
+    view.total = if view.wrap {
+        // Character-wrapped estimate of the rendered height
+        lines
+            .iter()
+            .map(|l| l.width().div_ceil(inner_w).max(1))
+            .sum::<usize>() as u16
+    } else {
+        lines.len() as u16
+    };
+    view.offset = view.offset.min(view.max_offset());
+    let title = crate::widgets::chrome::fit_title(title, area);
+    let block = crate::widgets::chrome::panel_block(&title, focused);
+    let mut p = ratatui::widgets::Paragraph::new(lines).block(block);
+    if view.wrap {
+        p = p.wrap(ratatui::widgets::Wrap { trim: false });
+    }
+    f.render_widget(p.scroll((view.offset, 0)), area);
+}
+
 impl crate::widgets::Widget for PanelWidget {
     fn render(&mut self, f: &mut Frame, area: Rect, _focused: bool, _tick: u64) {
         let chunks = if matches!(self.layout, crate::ui::PanelLayout::Horizontal) {
@@ -146,48 +277,40 @@ impl crate::widgets::Widget for PanelWidget {
                 .split(area)
         };
         // Pane A
-        if self.a.last_error.is_some() || self.a_w.is_none() {
-            let mut lines_a: Vec<Line> = Vec::new();
-            if let Some(err) = &self.a.last_error {
-                lines_a.push(Line::from(err.clone()).style(Style::default().fg(Color::Red)));
-                lines_a.push(Line::from(""));
-            }
-            if let Some(txt) = &self.a.last_json_pretty {
-                for l in txt.lines() {
-                    lines_a.push(Line::from(l.to_string()));
-                }
-            }
-            let block_a = crate::widgets::chrome::panel_block(
+        let focused_a = matches!(self.nested_focus, crate::ui::PanelPane::A);
+        match &mut self.a_w {
+            Some(w) if self.a.last_error.is_none() => w.render(f, chunks[0], focused_a, 0),
+            _ => render_text_pane(
+                f,
+                chunks[0],
                 &self.title_a,
-                matches!(self.nested_focus, crate::ui::PanelPane::A),
-            );
-            let pa = ratatui::widgets::Paragraph::new(lines_a).block(block_a);
-            f.render_widget(pa, chunks[0]);
-        } else if let Some(w) = &mut self.a_w {
-            let focused = matches!(self.nested_focus, crate::ui::PanelPane::A);
-            w.render(f, chunks[0], focused, 0);
+                focused_a,
+                &self.a,
+                &mut self.a_view,
+            ),
         }
         // Pane B
-        if self.b.last_error.is_some() || self.b_w.is_none() {
-            let mut lines_b: Vec<Line> = Vec::new();
-            if let Some(err) = &self.b.last_error {
-                lines_b.push(Line::from(err.clone()).style(Style::default().fg(Color::Red)));
-                lines_b.push(Line::from(""));
-            }
-            if let Some(txt) = &self.b.last_json_pretty {
-                for l in txt.lines() {
-                    lines_b.push(Line::from(l.to_string()));
-                }
-            }
-            let block_b = crate::widgets::chrome::panel_block(
+        let focused_b = matches!(self.nested_focus, crate::ui::PanelPane::B);
+        match &mut self.b_w {
+            Some(w) if self.b.last_error.is_none() => w.render(f, chunks[1], focused_b, 0),
+            _ => render_text_pane(
+                f,
+                chunks[1],
                 &self.title_b,
-                matches!(self.nested_focus, crate::ui::PanelPane::B),
+                focused_b,
+                &self.b,
+                &mut self.b_view,
+            ),
+        }
+        if self.sync_scroll && area.width > 12 {
+            let badge = " ⇅ sync ";
+            let w = crate::widgets::text_width::display_width(badge) as u16;
+            let r = Rect::new(area.x + area.width - w - 1, area.y, w, 1);
+            let theme = crate::theme::active();
+            f.render_widget(
+                ratatui::widgets::Paragraph::new(Span::styled(badge, theme.text_muted())),
+                r,
             );
-            let pb = ratatui::widgets::Paragraph::new(lines_b).block(block_b);
-            f.render_widget(pb, chunks[1]);
-        } else if let Some(w) = &mut self.b_w {
-            let focused = matches!(self.nested_focus, crate::ui::PanelPane::B);
-            w.render(f, chunks[1], focused, 0);
         }
     }
     fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
@@ -200,26 +323,80 @@ impl crate::widgets::Widget for PanelWidget {
                     crate::ui::PanelPane::A
                 };
             }
+            KeyCode::Char('L') => {
+                self.sync_scroll = !self.sync_scroll;
+                if self.sync_scroll {
+                    self.follow_focused();
+                }
+                effects.push(crate::app::Effect::ShowToast {
+                    text: format!(
+                        "Synchronized scrolling {}",
+                        if self.sync_scroll { "on" } else { "off" }
+                    ),
+                    level: crate::ui::ToastLevel::Info,
+                    seconds: 2,
+                });
+            }
             other => {
-                // Forward common keys to the focused pretty viewer (if any)
-                match self.nested_focus {
-                    crate::ui::PanelPane::A => {
-                        if let Some(w) = &mut self.a_w {
-                            let effs = w.on_key(other);
-                            effects.extend(effs);
-                        }
-                    }
-                    crate::ui::PanelPane::B => {
-                        if let Some(w) = &mut self.b_w {
-                            let effs = w.on_key(other);
-                            effects.extend(effs);
-                        }
-                    }
+                // Forward to the focused subpane: its pretty viewer, or its own text scroll
+                let (pane, w, view) = match self.nested_focus {
+                    crate::ui::PanelPane::A => (&self.a, &mut self.a_w, &mut self.a_view),
+                    crate::ui::PanelPane::B => (&self.b, &mut self.b_w, &mut self.b_view),
+                };
+                match w {
+                    Some(w) if pane.last_error.is_none() => effects.extend(w.on_key(other)),
+                    _ => view.on_key(other),
+                }
+                let scroll = matches!(
+                    other,
+                    KeyCode::Up
+                        | KeyCode::Down
+                        | KeyCode::PageUp
+                        | KeyCode::PageDown
+                        | KeyCode::Home
+                        | KeyCode::End
+                );
+                if self.sync_scroll && scroll {
+                    self.follow_focused();
                 }
             }
         }
         effects
     }
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        let w = match self.nested_focus {
+            crate::ui::PanelPane::A => &self.a_w,
+            crate::ui::PanelPane::B => &self.b_w,
+        };
+        let mut actions = vec![
+            crate::widgets::WidgetAction::key("Switch subpane", "Tab", KeyCode::Tab),
+            crate::widgets::WidgetAction::key(
+                if self.sync_scroll {
+                    "Unsync scrolling"
+                } else {
+                    "Sync scrolling"
+                },
+                "L",
+                KeyCode::Char('L'),
+            ),
+        ];
+        match w {
+            Some(w) => actions.extend(w.actions()),
+            None => actions.push(crate::widgets::WidgetAction::key(
+                "Toggle wrap",
+                "w",
+                KeyCode::Char('w'),
+            )),
+        }
+        actions
+    }
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        let w = match self.nested_focus {
+            crate::ui::PanelPane::A => &mut self.a_w,
+            crate::ui::PanelPane::B => &mut self.b_w,
+        };
+        w.as_mut().map(|w| w.refresh()).unwrap_or_default()
+    }
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -229,6 +406,13 @@ impl crate::widgets::Widget for PanelWidget {
 }
 
 impl PanelWidget {
+    pub fn subpane_widget(&self, sub: crate::ui::PanelPane) -> Option<&dyn crate::widgets::Widget> {
+        match sub {
+            crate::ui::PanelPane::A => self.a_w.as_deref(),
+            crate::ui::PanelPane::B => self.b_w.as_deref(),
+        }
+    }
+
     pub fn set_subpane_widget(
         &mut self,
         sub: crate::ui::PanelPane,
@@ -248,3 +432,79 @@ impl PanelWidget {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::PanelPane;
+    use crate::widgets::Widget;
+
+    fn draw(w: &mut PanelWidget) {
+        let backend = ratatui::backend::TestBackend::new(60, 12);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| w.render(f, f.area(), true, 0)).unwrap();
+    }
+
+    #[test]
+    fn subpanes_scroll_independently_and_keep_offsets_on_refresh() {
+        let mut w = PanelWidget::from_panel_state(crate::ui::PanelState::default());
+        let log: String = (0..40).map(|i| format!("line {i}\n")).collect();
+        w.set_subpane_text(PanelPane::A, log.clone());
+        w.set_subpane_text(PanelPane::B, log.clone());
+        draw(&mut w);
+        for _ in 0..3 {
+            w.on_key(KeyCode::Down);
+        }
+        w.on_key(KeyCode::Tab);
+        w.on_key(KeyCode::End);
+        assert_eq!((w.a_view.offset, w.b_view.offset), (3, 30));
+        // Refreshed content keeps both offsets
+        w.set_subpane_text(PanelPane::A, format!("{log}line 40\n"));
+        draw(&mut w);
+        assert_eq!((w.a_view.offset, w.b_view.offset), (3, 30));
+        w.on_key(KeyCode::Home);
+        w.on_key(KeyCode::Char('w'));
+        assert!(w.b_view.wrap && !w.a_view.wrap);
+        assert_eq!(w.b_view.offset, 0);
+    }
+
+    #[test]
+    fn sync_scroll_moves_both_subpanes_until_toggled_off() {
+        let mut w = PanelWidget::from_panel_state(crate::ui::PanelState::default());
+        let log: String = (0..40).map(|i| format!("line {i}\n")).collect();
+        w.set_subpane_text(PanelPane::A, log.clone());
+        w.set_subpane_widget(
+            PanelPane::B,
+            Box::new(crate::widgets::result_viewer::ResultViewerWidget::new(
+                "B",
+                serde_json::json!((0..40).collect::<Vec<_>>()),
+            )),
+        );
+        draw(&mut w);
+        w.on_key(KeyCode::Down);
+        assert_eq!(
+            (w.offset(PanelPane::A), w.offset(PanelPane::B)),
+            (Some(1), Some(0))
+        );
+        // Turning sync on aligns the other subpane to the focused one
+        w.on_key(KeyCode::Char('L'));
+        assert!(w.sync_scroll);
+        assert_eq!(w.offset(PanelPane::B), Some(1));
+        for _ in 0..3 {
+            w.on_key(KeyCode::Down);
+        }
+        w.on_key(KeyCode::Tab);
+        w.on_key(KeyCode::Up);
+        assert_eq!(
+            (w.offset(PanelPane::A), w.offset(PanelPane::B)),
+            (Some(3), Some(3))
+        );
+        // Off again: subpanes scroll independently
+        w.on_key(KeyCode::Char('L'));
+        w.on_key(KeyCode::Home);
+        assert_eq!(
+            (w.offset(PanelPane::A), w.offset(PanelPane::B)),
+            (Some(3), Some(0))
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use crate::theme::Status;
+use crate::widgets::check_rounds::CheckRounds;
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::text_width;
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+// `widget: ports`: reachability of `targets:` (`host:port`, or `label=host:port`), checked
+// with a TCP connect every `refresh_secs`. Each row shows an up/down badge and the connect
+// latency, or why the connect failed.
+
+struct Target {
+    label: String,
+    addr: String,
+    // Last check: connect latency or the error
+    last: Option<Result<Duration, String>>,
+}
+
+// TCP connect to `addr` within `timeout` (per resolved address)
+fn check(addr: &str, timeout: Duration) -> Result<Duration, String> {
+    let addrs: Vec<_> = addr.to_socket_addrs().map_err(|e| e.to_string())?.collect();
+    let mut last_err = format!("{addr}: no address");
+    for sa in addrs {
+        let start = Instant::now();
+        match TcpStream::connect_timeout(&sa, timeout) {
+            Ok(_) => return Ok(start.elapsed()),
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                last_err = format!("timeout after {} ms", timeout.as_millis())
+            }
+            Err(e) => last_err = e.to_string(),
+        }
+    }
+    Err(last_err)
+}
+
+pub struct PortsWidget {
+    title: String,
+    targets: Vec<Target>,
+    timeout: Duration,
+    selected: usize,
+    rounds: CheckRounds<Result<Duration, String>>,
+}
+
+impl PortsWidget {
+    pub fn new(
+        title: impl Into<String>,
+        targets: &[String],
+        refresh_secs: Option<u64>,
+        timeout_ms: Option<u64>,
+    ) -> Self {
+        let targets = targets
+            .iter()
+            .map(|t| {
+                let (label, addr) = t.split_once('=').unwrap_or((t, t));
+                Target {
+                    label: label.trim().to_string(),
+                    addr: addr.trim().to_string(),
+                    last: None,
+                }
+            })
+            .collect();
+        Self {
+            title: title.into(),
+            targets,
+            timeout: Duration::from_millis(timeout_ms.unwrap_or(1000).max(1)),
+            selected: 0,
+            rounds: CheckRounds::new(Duration::from_secs(refresh_secs.unwrap_or(5).max(1))),
+        }
+    }
+
+    // Check every target concurrently
+    fn check_all(&mut self) {
+        let timeout = self.timeout;
+        self.rounds.start(self.targets.iter().map(|t| {
+            let addr = t.addr.clone();
+            move || check(&addr, timeout)
+        }));
+    }
+
+    // Apply finished checks, and start the next round when due
+    fn poll(&mut self) {
+        for (i, outcome) in self.rounds.finished() {
+            if let Some(t) = self.targets.get_mut(i) {
+                t.last = Some(outcome);
+            }
+        }
+        if self.rounds.due() {
+            self.check_all();
+        }
+    }
+
+    fn up_count(&self) -> usize {
+        self.targets
+            .iter()
+            .filter(|t| matches!(t.last, Some(Ok(_))))
+            .count()
+    }
+}
+
+impl crate::widgets::Widget for PortsWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
+        self.poll();
+        let theme = crate::theme::active();
+        let title = format!(
+            "{} — {}/{} up",
+            self.title,
+            self.up_count(),
+            self.targets.len()
+        );
+        let title = fit_title(&title, area);
+        let block = panel_block(&title, focused);
+        let height = block.inner(area).height as usize;
+        let label_w = text_width::max_width(self.targets.iter().map(|t| t.label.as_str()));
+        let addr_w = text_width::max_width(self.targets.iter().map(|t| t.addr.as_str()));
+        let mut lines: Vec<Line> = Vec::new();
+        if self.targets.is_empty() {
+            lines.push(Line::from(Span::styled("No targets", theme.text_muted())));
+        }
+        let skip = (self.selected + 1).saturating_sub(height);
+        for (i, t) in self.targets.iter().enumerate().skip(skip) {
+            let (badge, style, detail) = match &t.last {
+                None => ("○ ....".to_string(), theme.text_muted(), String::new()),
+                Some(Ok(d)) => (
+                    format!("{} UP  ", theme.status_glyph(Status::Ok)),
+                    theme.status_style(Status::Ok),
+                    format!("{} ms", d.as_millis()),
+                ),
+                Some(Err(e)) => (
+                    format!("{} DOWN", theme.status_glyph(Status::Fail)),
+                    theme.status_style(Status::Fail),
+                    e.clone(),
+                ),
+            };
+            let spinner = if self.rounds.checking(i) {
+                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][tick as usize % 6]
+            } else {
+                " "
+            };
+            let mut line = Line::from(vec![
+                Span::styled(badge, style),
+                Span::raw(format!(" {spinner} ")),
+                Span::raw(format!("{}  ", text_width::pad(&t.label, label_w))),
+                Span::styled(
+                    format!("{}  ", text_width::pad(&t.addr, addr_w)),
+                    theme.text_muted(),
+                ),
+                Span::styled(detail, style),
+            ]);
+            if i == self.selected {
+                line = line.style(theme.list_cursor_style());
+            }
+            lines.push(line);
+        }
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        self.poll();
+        let last = self.targets.len().saturating_sub(1);
+        match key {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![WidgetAction::refresh()];
+        actions.push(WidgetAction::export_image());
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        self.check_all();
+        Vec::new()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn reports_open_and_closed_ports() {
+        let open = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open_addr = open.local_addr().unwrap().to_string();
+        // A port that was just free is (almost certainly) still closed
+        let closed_addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+
+        let mut w = PortsWidget::new(
+            "Services",
+            &[
+                format!("api={open_addr}"),
+                closed_addr.clone(),
+                "no-port-here".into(),
+            ],
+            Some(60),
+            Some(500),
+        );
+        assert_eq!(w.targets[0].label, "api");
+        assert_eq!(w.targets[1].label, closed_addr);
+        for _ in 0..200 {
+            w.poll();
+            if w.targets.iter().all(|t| t.last.is_some()) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(matches!(w.targets[0].last, Some(Ok(_))));
+        assert!(matches!(w.targets[1].last, Some(Err(_))));
+        assert!(matches!(w.targets[2].last, Some(Err(_))));
+        assert_eq!(w.up_count(), 1);
+        // The next round waits for `refresh_secs`
+        assert!(w.rounds.idle() && !w.rounds.due());
+    }
+}
@@ -0,0 +1,33 @@
+use crate::ui::AppState;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+// Warning banner for failed startup checks: one line per failure plus its remediation
+pub fn banner_lines(state: &AppState) -> Vec<Line<'static>> {
+    let theme = &state.theme;
+    let alert = Style::default()
+        .fg(theme.bg)
+        .bg(theme.error)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = Vec::new();
+    for status in &state.preflight {
+        let Some(err) = &status.failure else {
+            continue;
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" ⚠ {} ", status.check.title), alert),
+            Span::styled(format!(" {err}"), theme.text_error()),
+        ]));
+        if let Some(fix) = &status.check.remediation {
+            lines.push(Line::from(Span::styled(
+                format!("   → {fix}"),
+                theme.text_muted(),
+            )));
+        }
+    }
+    lines
+}
+
+pub fn draw_banner(f: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area);
+}
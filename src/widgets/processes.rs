@@ -0,0 +1,449 @@
+use crate::widgets::chrome::{fit_title, panel_block};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+// `widget: processes`: a top-like list of the system's processes (or those whose command
+// line matches `pattern:`), re-read from `ps` every `refresh_secs`. `s` cycles the sort
+// column; `k`/`K` send TERM/KILL to the selected process after a y/n confirmation.
+
+#[derive(Debug, Clone, PartialEq)]
+struct Proc {
+    pid: u32,
+    cpu: f32,
+    mem: f32,
+    // Resident memory in KiB
+    rss: u64,
+    name: String,
+    args: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortBy {
+    Cpu,
+    Mem,
+    Pid,
+    Name,
+}
+
+impl SortBy {
+    fn next(self) -> Self {
+        match self {
+            SortBy::Cpu => SortBy::Mem,
+            SortBy::Mem => SortBy::Pid,
+            SortBy::Pid => SortBy::Name,
+            SortBy::Name => SortBy::Cpu,
+        }
+    }
+    fn label(self) -> &'static str {
+        match self {
+            SortBy::Cpu => "CPU",
+            SortBy::Mem => "MEM",
+            SortBy::Pid => "PID",
+            SortBy::Name => "NAME",
+        }
+    }
+}
+
+pub struct ProcessesWidget {
+    title: String,
+    pattern: Option<regex::Regex>,
+    // Invalid `pattern:` (shown instead of the list)
+    pattern_error: Option<String>,
+    interval: Duration,
+    procs: Vec<Proc>,
+    sort: SortBy,
+    selected: usize,
+    scroll_y: usize,
+    // Signal waiting for y/n: (signal name, pid, process name)
+    confirm: Option<(&'static str, u32, String)>,
+    error: Option<String>,
+    loading: bool,
+    loaded: bool,
+    last_load: Option<Instant>,
+    tx: Sender<Result<Vec<Proc>, String>>,
+    rx: Receiver<Result<Vec<Proc>, String>>,
+}
+
+// `ps -eo pid=,pcpu=,pmem=,rss=,args=` output, without the `ps` process itself
+fn parse_ps(out: &str, own_pid: u32) -> Vec<Proc> {
+    out.lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid: u32 = parts.next()?.parse().ok()?;
+            let cpu: f32 = parts.next()?.parse().ok()?;
+            let mem: f32 = parts.next()?.parse().ok()?;
+            let rss: u64 = parts.next()?.parse().ok()?;
+            let args = parts.collect::<Vec<_>>().join(" ");
+            let exe = args.split_whitespace().next().unwrap_or_default();
+            let name = exe.rsplit('/').next().unwrap_or(exe).to_string();
+            Some(Proc {
+                pid,
+                cpu,
+                mem,
+                rss,
+                name,
+                args,
+            })
+        })
+        .filter(|p| p.pid != own_pid)
+        .collect()
+}
+
+fn list_processes() -> Result<Vec<Proc>, String> {
+    let child = Command::new("ps")
+        .args(["-eo", "pid=,pcpu=,pmem=,rss=,args="])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("ps: {e}"))?;
+    let own_pid = child.id();
+    let out = child.wait_with_output().map_err(|e| format!("ps: {e}"))?;
+    if !out.status.success() {
+        return Err(format!(
+            "ps failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        ));
+    }
+    Ok(parse_ps(&String::from_utf8_lossy(&out.stdout), own_pid))
+}
+
+pub(crate) fn human_kib(kib: u64) -> String {
+    if kib >= 1024 * 1024 {
+        format!("{:.1}G", kib as f64 / (1024.0 * 1024.0))
+    } else if kib >= 1024 {
+        format!("{:.0}M", kib as f64 / 1024.0)
+    } else {
+        format!("{kib}K")
+    }
+}
+
+impl ProcessesWidget {
+    pub fn new(title: impl Into<String>, pattern: Option<&str>, refresh_secs: Option<u64>) -> Self {
+        let (tx, rx) = channel();
+        let (pattern, pattern_error) = match pattern.filter(|p| !p.is_empty()) {
+            None => (None, None),
+            Some(p) => match regex::RegexBuilder::new(p).case_insensitive(true).build() {
+                Ok(re) => (Some(re), None),
+                Err(e) => (None, Some(format!("Invalid pattern: {e}"))),
+            },
+        };
+        Self {
+            title: title.into(),
+            pattern,
+            pattern_error,
+            interval: Duration::from_secs(refresh_secs.unwrap_or(2).max(1)),
+            procs: Vec::new(),
+            sort: SortBy::Cpu,
+            selected: 0,
+            scroll_y: 0,
+            confirm: None,
+            error: None,
+            loading: false,
+            loaded: false,
+            last_load: None,
+            tx,
+            rx,
+        }
+    }
+
+    fn load(&mut self) {
+        if self.loading || self.pattern_error.is_some() {
+            return;
+        }
+        self.loading = true;
+        self.last_load = Some(Instant::now());
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(list_processes());
+        });
+    }
+
+    // Apply a finished `ps` run, and start the next one when due
+    fn poll(&mut self) {
+        while let Ok(outcome) = self.rx.try_recv() {
+            self.loading = false;
+            match outcome {
+                Ok(procs) => {
+                    self.error = None;
+                    self.set_procs(procs);
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+        if self.last_load.is_none_or(|t| t.elapsed() >= self.interval) {
+            self.load();
+        }
+    }
+
+    // Replace the list, keeping the selected process selected
+    fn set_procs(&mut self, procs: Vec<Proc>) {
+        let pid = self.selected_proc().map(|p| p.pid);
+        self.procs = procs
+            .into_iter()
+            .filter(|p| self.pattern.as_ref().is_none_or(|re| re.is_match(&p.args)))
+            .collect();
+        self.loaded = true;
+        self.sort_procs();
+        self.select_pid(pid);
+    }
+
+    fn sort_procs(&mut self) {
+        match self.sort {
+            SortBy::Cpu => self.procs.sort_by(|a, b| b.cpu.total_cmp(&a.cpu)),
+            SortBy::Mem => self.procs.sort_by_key(|p| std::cmp::Reverse(p.rss)),
+            SortBy::Pid => self.procs.sort_by_key(|p| p.pid),
+            SortBy::Name => self
+                .procs
+                .sort_by_cached_key(|p| (p.name.to_lowercase(), p.pid)),
+        }
+    }
+
+    fn select_pid(&mut self, pid: Option<u32>) {
+        if let Some(i) = pid.and_then(|pid| self.procs.iter().position(|p| p.pid == pid)) {
+            self.selected = i;
+        }
+        self.selected = self.selected.min(self.procs.len().saturating_sub(1));
+    }
+
+    fn selected_proc(&self) -> Option<&Proc> {
+        self.procs.get(self.selected)
+    }
+
+    // Send the confirmed signal; the list is re-read right after
+    fn send_signal(&mut self, signal: &str, pid: u32, name: &str) -> Vec<crate::app::Effect> {
+        let outcome = Command::new("kill")
+            .args([format!("-{signal}"), pid.to_string()])
+            .output();
+        let (text, level) = match outcome {
+            Ok(out) if out.status.success() => (
+                format!("Sent {signal} to {pid} ({name})"),
+                crate::ui::ToastLevel::Success,
+            ),
+            Ok(out) => (
+                format!(
+                    "kill {pid} failed: {}",
+                    String::from_utf8_lossy(&out.stderr).trim()
+                ),
+                crate::ui::ToastLevel::Error,
+            ),
+            Err(e) => (format!("kill: {e}"), crate::ui::ToastLevel::Error),
+        };
+        self.last_load = None;
+        vec![crate::app::Effect::ShowToast {
+            text,
+            level,
+            seconds: 3,
+        }]
+    }
+}
+
+impl crate::widgets::Widget for ProcessesWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, _tick: u64) {
+        self.poll();
+        let theme = crate::theme::active();
+        let title = format!(
+            "{} ({}) — sorted by {}",
+            self.title,
+            self.procs.len(),
+            self.sort.label()
+        );
+        let title = fit_title(&title, area);
+        let block = panel_block(&title, focused);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let [head, list, foot] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+        f.render_widget(
+            Paragraph::new(format!(
+                "{:>7} {:>5} {:>5} {:>6}  COMMAND",
+                "PID", "CPU%", "MEM%", "RSS"
+            ))
+            .style(theme.title_style()),
+            head,
+        );
+        let height = list.height as usize;
+        if self.selected < self.scroll_y {
+            self.scroll_y = self.selected;
+        } else if height > 0 && self.selected >= self.scroll_y + height {
+            self.scroll_y = self.selected + 1 - height;
+        }
+        let mut lines: Vec<Line> = Vec::new();
+        if let Some(e) = self.pattern_error.as_ref().or(self.error.as_ref()) {
+            lines.push(Line::from(Span::styled(e.clone(), theme.text_error())));
+        } else if !self.loaded {
+            lines.push(Line::from(Span::styled("Loading…", theme.text_muted())));
+        } else if self.procs.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No matching processes",
+                theme.text_muted(),
+            )));
+        }
+        for (i, p) in self
+            .procs
+            .iter()
+            .enumerate()
+            .skip(self.scroll_y)
+            .take(height)
+        {
+            let mut line = Line::from(format!(
+                "{:>7} {:>5.1} {:>5.1} {:>6}  {}",
+                p.pid,
+                p.cpu,
+                p.mem,
+                human_kib(p.rss),
+                p.args
+            ));
+            if i == self.selected {
+                line = line.style(theme.list_cursor_style());
+            }
+            lines.push(line);
+        }
+        f.render_widget(Paragraph::new(lines), list);
+        let hint = match &self.confirm {
+            Some((signal, pid, name)) => Line::from(Span::styled(
+                format!("Send {signal} to {pid} ({name})? y/n"),
+                theme.text_error(),
+            )),
+            None => Line::from(Span::styled(
+                "s sort • k TERM • K KILL • r refresh",
+                theme.text_muted(),
+            )),
+        };
+        f.render_widget(Paragraph::new(hint), foot);
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        self.poll();
+        if let Some((signal, pid, name)) = self.confirm.take() {
+            if key == KeyCode::Char('y') {
+                return self.send_signal(signal, pid, &name);
+            }
+            return Vec::new();
+        }
+        let last = self.procs.len().saturating_sub(1);
+        match key {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::PageUp => self.selected = self.selected.saturating_sub(10),
+            KeyCode::PageDown => self.selected = (self.selected + 10).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            KeyCode::Char('s') => {
+                let pid = self.selected_proc().map(|p| p.pid);
+                self.sort = self.sort.next();
+                self.sort_procs();
+                self.select_pid(pid);
+            }
+            KeyCode::Char(c @ ('k' | 'K')) => {
+                if let Some(p) = self.selected_proc() {
+                    let signal = if c == 'k' { "TERM" } else { "KILL" };
+                    self.confirm = Some((signal, p.pid, p.name.clone()));
+                }
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![WidgetAction::key(
+            "Cycle sort column",
+            "s",
+            KeyCode::Char('s'),
+        )];
+        if self.selected_proc().is_some() {
+            actions.push(WidgetAction::key(
+                "Terminate process",
+                "k",
+                KeyCode::Char('k'),
+            ));
+            actions.push(WidgetAction::key("Kill process", "K", KeyCode::Char('K')));
+        }
+        actions.push(WidgetAction::refresh());
+        actions.push(WidgetAction::export_image());
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        self.last_load = None;
+        self.poll();
+        Vec::new()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Widget;
+
+    #[test]
+    fn sorts_filters_and_signals_processes() {
+        let ps = "    1  0.0  0.1  1024 /sbin/init splash\n\
+                  \x20 200 12.5  3.0 30720 /usr/bin/nginx -g daemon off;\n\
+                  \x20 300  1.0  9.5 2097152 postgres: writer\n\
+                  \x20 400  0.0  0.0   512 ps -eo pid=\n";
+        let procs = parse_ps(ps, 400);
+        assert_eq!(procs.len(), 3);
+        assert_eq!(procs[1].name, "nginx");
+        assert_eq!(procs[2].args, "postgres: writer");
+
+        let mut w = ProcessesWidget::new("Processes", None, None);
+        w.set_procs(procs.clone());
+        let pids = |w: &ProcessesWidget| w.procs.iter().map(|p| p.pid).collect::<Vec<_>>();
+        assert_eq!(pids(&w), [200, 300, 1]);
+        // Sorting keeps the selected process selected
+        w.on_key(KeyCode::Down);
+        w.on_key(KeyCode::Char('s'));
+        assert_eq!(w.sort, SortBy::Mem);
+        assert_eq!(pids(&w), [300, 200, 1]);
+        assert_eq!(w.selected_proc().unwrap().pid, 300);
+        assert_eq!(human_kib(2097152), "2.0G");
+
+        let mut only = ProcessesWidget::new("Processes", Some("NGINX|init"), None);
+        only.set_procs(procs);
+        assert_eq!(pids(&only), [200, 1]);
+        assert!(ProcessesWidget::new("Processes", Some("("), None)
+            .pattern_error
+            .is_some());
+
+        // Signals wait for a `y`; anything else cancels
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+        w.set_procs(vec![Proc {
+            pid,
+            cpu: 0.0,
+            mem: 0.0,
+            rss: 0,
+            name: "sleep".into(),
+            args: "sleep 30".into(),
+        }]);
+        w.on_key(KeyCode::Char('K'));
+        assert!(w.on_key(KeyCode::Char('n')).is_empty());
+        assert!(w.confirm.is_none());
+        w.on_key(KeyCode::Char('k'));
+        let effects = w.on_key(KeyCode::Char('y'));
+        assert!(matches!(
+            &effects[..],
+            [crate::app::Effect::ShowToast { text, level: crate::ui::ToastLevel::Success, .. }]
+                if *text == format!("Sent TERM to {pid} (sleep)")
+        ));
+        assert!(!child.wait().unwrap().success());
+    }
+}
@@ -0,0 +1,618 @@
+use crate::model::PromQuerySpec;
+use crate::theme::Status;
+use crate::widgets::check_rounds::CheckRounds;
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::{cells, text_width};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Axis, Chart, Dataset, GraphType, Paragraph};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::Duration;
+
+// `widget: promql`: `queries:` sent to a Prometheus server's HTTP API (with `curl`) every
+// `refresh_secs`. Each result series is a row with its latest value as a badge, colored by
+// the query's `warn`/`crit` thresholds; range queries add a sparkline, and the lower pane
+// charts the selected series. `{slot.<name>}` in a query is filled from the slots and the
+// queries re-run when one of their slots changes.
+
+// Series shown per query
+const MAX_SERIES: usize = 20;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Series {
+    // `{job="api", instance="a:9100"}` without `__name__`; empty for a single unlabeled series
+    labels: String,
+    value: Option<f64>,
+    // (unix time, value) of a range query
+    points: Vec<(f64, f64)>,
+}
+
+struct Query {
+    spec: PromQuerySpec,
+    result: Option<QueryResult>,
+}
+
+type QueryResult = Result<Vec<Series>, String>;
+
+// `90s`, `15m`, `1h`, `7d`, `2w` (plain numbers are seconds)
+fn duration_secs(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let n: u64 = s[..split].parse().ok()?;
+    let unit = match &s[split..] {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        "w" => 7 * 86400,
+        _ => return None,
+    };
+    Some(n * unit)
+}
+
+// `{slot.<name>}` placeholders replaced by the slot values as-is (unset slots are empty)
+fn fill(template: &str, slots: &HashMap<String, String>) -> String {
+    let mut out = template.to_string();
+    let mut rest = template;
+    while let Some(start) = rest.find("{slot.") {
+        rest = &rest[start + "{slot.".len()..];
+        let Some(end) = rest.find('}') else { break };
+        let name = &rest[..end];
+        let value = slots.get(name).map(String::as_str).unwrap_or("");
+        out = out.replace(&format!("{{slot.{name}}}"), value);
+        rest = &rest[end..];
+    }
+    crate::services::interpolate::env(&out)
+}
+
+fn labels(metric: Option<&JsonValue>) -> String {
+    let pairs: Vec<String> = metric
+        .and_then(|m| m.as_object())
+        .into_iter()
+        .flatten()
+        .filter(|(k, _)| k.as_str() != "__name__")
+        .map(|(k, v)| format!("{k}=\"{}\"", v.as_str().unwrap_or_default()))
+        .collect();
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(", "))
+    }
+}
+
+// `[<unix time>, "<value>"]`
+fn sample(v: &JsonValue) -> Option<(f64, f64)> {
+    let ts = v.get(0)?.as_f64()?;
+    let value = v.get(1)?.as_str()?.parse().ok()?;
+    Some((ts, value))
+}
+
+// Series of a `/api/v1/query` or `/api/v1/query_range` response
+fn parse_response(v: &JsonValue) -> Result<Vec<Series>, String> {
+    if v.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let err = v
+            .get("error")
+            .and_then(|e| e.as_str())
+            .unwrap_or("unexpected response");
+        return Err(err.to_string());
+    }
+    let data = &v["data"];
+    let result = &data["result"];
+    match data["resultType"].as_str() {
+        Some("scalar") => Ok(vec![Series {
+            labels: String::new(),
+            value: sample(result).map(|(_, v)| v),
+            points: Vec::new(),
+        }]),
+        Some("vector") => Ok(result
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|s| Series {
+                labels: labels(s.get("metric")),
+                value: s.get("value").and_then(sample).map(|(_, v)| v),
+                points: Vec::new(),
+            })
+            .collect()),
+        Some("matrix") => Ok(result
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|s| {
+                let points: Vec<(f64, f64)> = s["values"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(sample)
+                    .collect();
+                Series {
+                    labels: labels(s.get("metric")),
+                    value: points.last().map(|p| p.1),
+                    points,
+                }
+            })
+            .collect()),
+        other => Err(format!("unsupported result type {other:?}")),
+    }
+}
+
+fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// Run one query (a range query ending now when it has a `range`)
+fn run_query(base: &str, spec: &PromQuerySpec, query: &str, timeout: Duration) -> QueryResult {
+    let base = base.trim_end_matches('/');
+    let mut cmd = Command::new("curl");
+    cmd.args(["-sS", "-G", "--max-time"])
+        .arg(format!("{:.1}", timeout.as_secs_f64()))
+        .arg("--data-urlencode")
+        .arg(format!("query={query}"));
+    match spec.range.as_deref().and_then(duration_secs) {
+        Some(range) => {
+            let step = spec
+                .step
+                .as_deref()
+                .and_then(duration_secs)
+                .unwrap_or((range / 60).max(1));
+            let end = now_unix();
+            cmd.arg(format!("{base}/api/v1/query_range"));
+            for (k, v) in [
+                ("start", end - range as i64),
+                ("end", end),
+                ("step", step as i64),
+            ] {
+                cmd.arg("--data-urlencode").arg(format!("{k}={v}"));
+            }
+        }
+        None => {
+            cmd.arg(format!("{base}/api/v1/query"));
+        }
+    }
+    let out = cmd.output().map_err(|e| format!("curl: {e}"))?;
+    let body = String::from_utf8_lossy(&out.stdout);
+    match serde_json::from_str::<JsonValue>(&body) {
+        Ok(v) => parse_response(&v),
+        Err(_) if !out.status.success() => Err(String::from_utf8_lossy(&out.stderr)
+            .trim()
+            .trim_start_matches("curl: ")
+            .to_string()),
+        Err(_) => Err(text_width::truncate(body.trim(), 200)),
+    }
+}
+
+// 1234567 -> 1.23M, 0.5 -> 0.5, 42 -> 42
+fn human(v: f64) -> String {
+    if !v.is_finite() {
+        return v.to_string();
+    }
+    let (scaled, suffix) = match v.abs() {
+        a if a >= 1e12 => (v / 1e12, "T"),
+        a if a >= 1e9 => (v / 1e9, "G"),
+        a if a >= 1e6 => (v / 1e6, "M"),
+        a if a >= 1e4 => (v / 1e3, "k"),
+        _ => (v, ""),
+    };
+    let digits = if scaled.abs() >= 100.0 {
+        0
+    } else if scaled.abs() >= 1.0 || scaled == 0.0 {
+        2
+    } else {
+        3
+    };
+    let text = format!("{scaled:.digits$}");
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    format!("{text}{suffix}")
+}
+
+// Badge status of a value: from `warn`/`crit` up, or down when `crit` is below `warn`
+fn level(spec: &PromQuerySpec, v: f64) -> Option<Status> {
+    if spec.warn.is_none() && spec.crit.is_none() {
+        return None;
+    }
+    let falling = matches!((spec.warn, spec.crit), (Some(w), Some(c)) if c < w);
+    let past = |t: f64| if falling { v <= t } else { v >= t };
+    Some(if spec.crit.is_some_and(past) {
+        Status::Fail
+    } else if spec.warn.is_some_and(past) {
+        Status::Warn
+    } else {
+        Status::Ok
+    })
+}
+
+pub struct PromqlWidget {
+    title: String,
+    base: String,
+    queries: Vec<Query>,
+    slots: HashMap<String, String>,
+    timeout: Duration,
+    // Row (query series) under the cursor
+    selected: usize,
+    // A new round drops the replies of older slot values
+    rounds: CheckRounds<QueryResult>,
+}
+
+impl PromqlWidget {
+    pub fn new(
+        title: impl Into<String>,
+        base: Option<&str>,
+        specs: &[PromQuerySpec],
+        slots: HashMap<String, String>,
+        refresh_secs: Option<u64>,
+        timeout_ms: Option<u64>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            base: base.unwrap_or("http://localhost:9090").to_string(),
+            queries: specs
+                .iter()
+                .map(|spec| Query {
+                    spec: spec.clone(),
+                    result: None,
+                })
+                .collect(),
+            slots,
+            timeout: Duration::from_millis(timeout_ms.unwrap_or(5000).max(100)),
+            selected: 0,
+            rounds: CheckRounds::new(Duration::from_secs(refresh_secs.unwrap_or(30).max(1))),
+        }
+    }
+
+    // A published slot changed: queries reading it run again with the new value
+    pub fn set_slot(&mut self, name: &str, value: &str) {
+        if self.slots.get(name).map(String::as_str) == Some(value) {
+            return;
+        }
+        self.slots.insert(name.to_string(), value.to_string());
+        let placeholder = format!("{{slot.{name}}}");
+        let uses = |t: &str| t.contains(&placeholder);
+        if uses(&self.base) || self.queries.iter().any(|q| uses(&q.spec.query)) {
+            self.query_all();
+        }
+    }
+
+    fn query_all(&mut self) {
+        let base = fill(&self.base, &self.slots);
+        let timeout = self.timeout;
+        let jobs: Vec<_> = self
+            .queries
+            .iter()
+            .map(|q| {
+                let (spec, base) = (q.spec.clone(), base.clone());
+                let query = fill(&q.spec.query, &self.slots);
+                move || run_query(&base, &spec, &query, timeout)
+            })
+            .collect();
+        self.rounds.start(jobs);
+    }
+
+    fn poll(&mut self) {
+        for (i, result) in self.rounds.finished() {
+            if let Some(q) = self.queries.get_mut(i) {
+                q.result = Some(result.map(|mut s| {
+                    s.truncate(MAX_SERIES);
+                    s
+                }));
+            }
+        }
+        if self.rounds.due() {
+            self.query_all();
+        }
+    }
+
+    // (query index, series) per row: one row per series, or one for a query without any
+    fn rows(&self) -> Vec<(usize, Option<&Series>)> {
+        let mut rows = Vec::new();
+        for (i, q) in self.queries.iter().enumerate() {
+            match &q.result {
+                Some(Ok(series)) if !series.is_empty() => {
+                    rows.extend(series.iter().map(|s| (i, Some(s))));
+                }
+                _ => rows.push((i, None)),
+            }
+        }
+        rows
+    }
+
+    fn row_lines(&self, tick: u64, height: usize) -> Vec<Line<'static>> {
+        let theme = crate::theme::active();
+        let rows = self.rows();
+        let label = |(i, s): &(usize, Option<&Series>)| {
+            let name = self.queries[*i].spec.label();
+            match s {
+                Some(s) if !s.labels.is_empty() => format!("{name} {}", s.labels),
+                _ => name.to_string(),
+            }
+        };
+        let labels: Vec<String> = rows.iter().map(label).collect();
+        let name_w = text_width::max_width(labels.iter().map(String::as_str)).min(48);
+        let skip = (self.selected + 1).saturating_sub(height);
+        let mut lines = Vec::new();
+        for (row, ((i, series), name)) in rows.iter().zip(&labels).enumerate().skip(skip) {
+            let q = &self.queries[*i];
+            let spinner = if self.rounds.checking(*i) {
+                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][tick as usize % 6]
+            } else {
+                " "
+            };
+            let mut spans = Vec::new();
+            match (&q.result, series.and_then(|s| s.value)) {
+                (_, Some(v)) => {
+                    let status = level(&q.spec, v);
+                    let (glyph, style) = match status {
+                        Some(st) => (theme.status_glyph(st).to_string(), theme.status_style(st)),
+                        None => ("●".to_string(), theme.text_active_bold()),
+                    };
+                    let unit = q.spec.unit.as_deref().unwrap_or("");
+                    spans.push(Span::styled(glyph, style));
+                    spans.push(Span::raw(format!(
+                        " {spinner} {}  ",
+                        text_width::pad(name, name_w)
+                    )));
+                    spans.push(Span::styled(
+                        format!("{:>8} {unit}", human(v)),
+                        style.add_modifier(Modifier::BOLD),
+                    ));
+                    if let Some(s) = series.filter(|s| s.points.len() > 1) {
+                        let values: Vec<f64> =
+                            s.points.iter().rev().take(30).rev().map(|p| p.1).collect();
+                        spans.push(Span::styled(
+                            format!("  {}", cells::sparkline(&values)),
+                            style,
+                        ));
+                    }
+                }
+                (Some(Err(e)), _) => {
+                    spans.push(Span::styled(
+                        theme.status_glyph(Status::Fail).to_string(),
+                        theme.status_style(Status::Fail),
+                    ));
+                    spans.push(Span::raw(format!(
+                        " {spinner} {}  ",
+                        text_width::pad(name, name_w)
+                    )));
+                    spans.push(Span::styled(e.clone(), theme.text_error()));
+                }
+                (result, None) => {
+                    spans.push(Span::styled("○", theme.text_muted()));
+                    spans.push(Span::raw(format!(
+                        " {spinner} {}  ",
+                        text_width::pad(name, name_w)
+                    )));
+                    let note = if result.is_some() { "no data" } else { "...." };
+                    spans.push(Span::styled(note, theme.text_muted()));
+                }
+            }
+            let mut line = Line::from(spans);
+            if row == self.selected {
+                line = line.style(theme.list_cursor_style());
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    fn draw_chart(&self, f: &mut Frame, area: Rect) {
+        let theme = crate::theme::active();
+        let rows = self.rows();
+        let Some(&(i, series)) = rows.get(self.selected) else {
+            return;
+        };
+        let spec = &self.queries[i].spec;
+        let title = match series {
+            Some(s) if !s.labels.is_empty() => format!("{} {}", spec.label(), s.labels),
+            _ => spec.label().to_string(),
+        };
+        let title = fit_title(&title, area);
+        let block = panel_block(&title, false);
+        let points = series.map(|s| s.points.as_slice()).unwrap_or_default();
+        if points.len() < 2 {
+            let note = if spec.range.is_some() {
+                "No data in range"
+            } else {
+                "Instant query — set `range:` to chart it"
+            };
+            f.render_widget(
+                Paragraph::new(Span::styled(note, theme.text_muted())).block(block),
+                area,
+            );
+            return;
+        }
+        let (x0, x1) = (points[0].0, points[points.len() - 1].0);
+        let lo = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+        let hi = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+        let (lo, hi) = if hi > lo {
+            (lo, hi)
+        } else {
+            (lo - 1.0, hi + 1.0)
+        };
+        let style = series
+            .and_then(|s| s.value)
+            .and_then(|v| level(spec, v))
+            .map(|st| theme.status_style(st))
+            .unwrap_or_else(|| theme.text_active_bold());
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(style)
+            .data(points);
+        let range = spec.range.as_deref().unwrap_or_default();
+        let chart = Chart::new(vec![dataset])
+            .block(block)
+            .x_axis(
+                Axis::default()
+                    .bounds([x0, x1])
+                    .style(theme.text_muted())
+                    .labels([format!("-{range}"), "now".to_string()]),
+            )
+            .y_axis(
+                Axis::default()
+                    .bounds([lo, hi])
+                    .style(theme.text_muted())
+                    .labels([human(lo), human(hi)]),
+            );
+        f.render_widget(chart, area);
+    }
+}
+
+impl crate::widgets::Widget for PromqlWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
+        self.poll();
+        let theme = crate::theme::active();
+        let [top, bottom] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(area);
+        let failing = self
+            .queries
+            .iter()
+            .filter(|q| matches!(q.result, Some(Err(_))))
+            .count();
+        let mut title = format!("{} — {} queries", self.title, self.queries.len());
+        if failing > 0 {
+            title.push_str(&format!(", {failing} failing"));
+        }
+        let title = fit_title(&title, top);
+        let block = panel_block(&title, focused);
+        let mut lines = self.row_lines(tick, block.inner(top).height as usize);
+        if self.queries.is_empty() {
+            lines.push(Line::from(Span::styled("No queries", theme.text_muted())));
+        }
+        f.render_widget(Paragraph::new(lines).block(block), top);
+        self.draw_chart(f, bottom);
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        self.poll();
+        let last = self.rows().len().saturating_sub(1);
+        match key {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![WidgetAction::refresh()];
+        actions.push(WidgetAction::export_image());
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        self.query_all();
+        Vec::new()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_results_and_grades_values() {
+        let vector = parse_response(&json!({
+            "status": "success",
+            "data": {"resultType": "vector", "result": [
+                {"metric": {"__name__": "up", "job": "api"}, "value": [1700000000.0, "1"]},
+                {"metric": {}, "value": [1700000000.0, "NaN"]}
+            ]}
+        }))
+        .unwrap();
+        assert_eq!(vector[0].labels, "{job=\"api\"}");
+        assert_eq!(vector[0].value, Some(1.0));
+        assert!(vector[1].labels.is_empty() && vector[1].value.unwrap().is_nan());
+
+        let matrix = parse_response(&json!({
+            "status": "success",
+            "data": {"resultType": "matrix", "result": [
+                {"metric": {"pod": "a"}, "values": [[1.0, "3"], [2.0, "5.5"]]}
+            ]}
+        }))
+        .unwrap();
+        assert_eq!(matrix[0].points, [(1.0, 3.0), (2.0, 5.5)]);
+        assert_eq!(matrix[0].value, Some(5.5));
+        assert_eq!(
+            parse_response(&json!({"status": "error", "error": "parse error at char 5"})),
+            Err("parse error at char 5".to_string())
+        );
+
+        let spec = PromQuerySpec {
+            query: "x".into(),
+            warn: Some(80.0),
+            crit: Some(95.0),
+            ..Default::default()
+        };
+        assert_eq!(level(&spec, 10.0), Some(Status::Ok));
+        assert_eq!(level(&spec, 80.0), Some(Status::Warn));
+        assert_eq!(level(&spec, 99.0), Some(Status::Fail));
+        // crit below warn: lower is worse (free disk, availability)
+        let falling = PromQuerySpec {
+            warn: Some(20.0),
+            crit: Some(5.0),
+            ..spec.clone()
+        };
+        assert_eq!(level(&falling, 50.0), Some(Status::Ok));
+        assert_eq!(level(&falling, 3.0), Some(Status::Fail));
+        assert_eq!(level(&PromQuerySpec::default(), 1.0), None);
+
+        assert_eq!(human(1_234_567.0), "1.23M");
+        assert_eq!(human(0.5), "0.5");
+        assert_eq!(human(42.0), "42");
+        assert_eq!(human(999.4), "999");
+        assert_eq!(human(12_500.0), "12.5k");
+        assert_eq!(duration_secs("15m"), Some(900));
+        assert_eq!(duration_secs("2h"), Some(7200));
+        assert_eq!(duration_secs("soon"), None);
+
+        let slots = HashMap::from([("ns".to_string(), "prod".to_string())]);
+        assert_eq!(
+            fill("up{namespace=\"{slot.ns}\", pod=\"{slot.pod}\"}", &slots),
+            "up{namespace=\"prod\", pod=\"\"}"
+        );
+    }
+
+    #[test]
+    fn slot_changes_rerun_only_queries_that_read_them() {
+        let spec = PromQuerySpec {
+            query: "up{namespace=\"{slot.ns}\"}".into(),
+            ..Default::default()
+        };
+        let mut w = PromqlWidget::new(
+            "Metrics",
+            Some("http://127.0.0.1:1"),
+            &[spec],
+            HashMap::new(),
+            None,
+            Some(100),
+        );
+        w.query_all();
+        assert_eq!(w.rounds.round(), 1);
+        w.set_slot("pod", "api-1");
+        assert_eq!(w.rounds.round(), 1);
+        w.set_slot("ns", "prod");
+        assert_eq!(w.rounds.round(), 2);
+        w.set_slot("ns", "prod");
+        assert_eq!(w.rounds.round(), 2);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::chi_core::envelope::Envelope;
+use crate::widgets::markdown::MarkdownWidget;
+use crate::widgets::result_viewer::ResultViewerWidget;
+use crate::widgets::Widget;
+use ratatui::prelude::*;
+use serde_json::Value as JsonValue;
+
+// Renderers for command results keyed by the envelope's `content_type`, so a backing CLI
+// picks the presentation without YAML changes:
+//   markdown  `data` (or `data.text`) as a markdown page
+//   diff      a unified diff, added / removed / hunk lines colored
+//   ansi      terminal output with SGR colors kept
+//   table     `data.items` (or a `data` array) as aligned columns
+// Other or missing content types stay in the result viewer.
+
+pub fn for_result(title: impl Into<String>, v: JsonValue) -> Box<dyn Widget> {
+    let title = title.into();
+    renderer(&title, &v).unwrap_or_else(|| Box::new(ResultViewerWidget::new(title, v)))
+}
+
+// Rows a list command returned, rendered by its envelope's `content_type` when it had one
+pub fn for_rows(
+    title: impl Into<String>,
+    items: Vec<JsonValue>,
+    content_type: Option<&str>,
+) -> Box<dyn Widget> {
+    let title = title.into();
+    let rendered = content_type.and_then(|ct| {
+        renderer(
+            &title,
+            &serde_json::json!({"ok": true, "content_type": ct, "data": {"items": &items}}),
+        )
+    });
+    rendered.unwrap_or_else(|| Box::new(ResultViewerWidget::new(title, JsonValue::Array(items))))
+}
+
+// The widget `content_type` picks for `v`; None leaves it to the result viewer
+pub fn renderer(title: &str, v: &JsonValue) -> Option<Box<dyn Widget>> {
+    let env = Envelope::parse(v).unwrap_or_default();
+    let text = env
+        .data
+        .as_str()
+        .or_else(|| env.data.get("text").and_then(JsonValue::as_str));
+    match (env.content_type.as_deref(), text) {
+        (Some("markdown"), Some(text)) => Some(Box::new(MarkdownWidget::from_text(title, text))),
+        (Some("diff"), Some(text)) => Some(Box::new(MarkdownWidget::from_lines(
+            title,
+            diff_lines(text),
+            text,
+        ))),
+        (Some("ansi"), Some(text)) => Some(Box::new(MarkdownWidget::from_lines(
+            title,
+            ansi_lines(text),
+            &strip_ansi(text),
+        ))),
+        (Some("table"), _) => {
+            let rows = env.items().cloned().map(JsonValue::Array);
+            let (lines, raw) = table_lines(rows.as_ref().unwrap_or(&env.data))?;
+            Some(Box::new(MarkdownWidget::from_lines(title, lines, &raw)))
+        }
+        _ => None,
+    }
+}
+
+fn diff_lines(text: &str) -> Vec<Line<'static>> {
+    let theme = crate::theme::active();
+    text.lines()
+        .map(|l| {
+            let style = if l.starts_with("+++") || l.starts_with("---") {
+                theme.text_active_bold()
+            } else if l.starts_with('+') {
+                theme.text_success()
+            } else if l.starts_with('-') {
+                theme.text_error()
+            } else if l.starts_with("@@") {
+                Style::default().fg(theme.accent)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(l.to_string(), style))
+        })
+        .collect()
+}
+
+fn table_lines(value: &JsonValue) -> Option<(Vec<Line<'static>>, String)> {
+    let (header, rows) = crate::services::copy_formats::table(value)?;
+    let width = |s: &str| crate::widgets::text_width::display_width(s);
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            rows.iter()
+                .map(|r| width(&r[i]))
+                .chain([width(h)])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: &[String]| -> String {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c}{}", " ".repeat(w - width(c))))
+            .collect();
+        padded.join("  ").trim_end().to_string()
+    };
+    let theme = crate::theme::active();
+    let mut lines = vec![Line::from(Span::styled(
+        line(&header),
+        theme.text_active_bold(),
+    ))];
+    lines.extend(rows.iter().map(|r| Line::from(line(r))));
+    let raw = std::iter::once(&header)
+        .chain(&rows)
+        .map(|r| line(r))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some((lines, raw))
+}
+
+// Escape sequences removed (copying the pane gives plain text)
+fn strip_ansi(text: &str) -> String {
+    ansi_lines(text)
+        .iter()
+        .map(|l| {
+            l.spans
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn ansi_lines(text: &str) -> Vec<Line<'static>> {
+    let mut style = Style::default();
+    text.lines()
+        .map(|l| {
+            let mut spans = Vec::new();
+            let mut buf = String::new();
+            let mut chars = l.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c != '\x1b' {
+                    buf.push(c);
+                    continue;
+                }
+                if chars.peek() != Some(&'[') {
+                    continue;
+                }
+                chars.next();
+                // CSI: parameters up to the final byte; only SGR ('m') changes the style
+                let mut params = String::new();
+                let mut fin = None;
+                for p in chars.by_ref() {
+                    if ('@'..='~').contains(&p) {
+                        fin = Some(p);
+                        break;
+                    }
+                    params.push(p);
+                }
+                if fin == Some('m') {
+                    if !buf.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut buf), style));
+                    }
+                    style = sgr(style, &params);
+                }
+            }
+            if !buf.is_empty() {
+                spans.push(Span::styled(buf, style));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn sgr(mut style: Style, params: &str) -> Style {
+    let codes: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+    let basic = |n: u16| Color::Indexed(n as u8);
+    let mut it = codes.into_iter();
+    while let Some(code) = it.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(basic(code - 30)),
+            90..=97 => style.fg(basic(code - 90 + 8)),
+            40..=47 => style.bg(basic(code - 40)),
+            100..=107 => style.bg(basic(code - 100 + 8)),
+            39 => style.fg(Color::Reset),
+            49 => style.bg(Color::Reset),
+            38 | 48 => {
+                let color = match it.next() {
+                    Some(5) => it.next().map(|n| Color::Indexed(n as u8)),
+                    Some(2) => match (it.next(), it.next(), it.next()) {
+                        (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r as u8, g as u8, b as u8)),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match (code, color) {
+                    (38, Some(c)) => style.fg(c),
+                    (48, Some(c)) => style.bg(c),
+                    _ => style,
+                }
+            }
+            _ => style,
+        };
+    }
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn kind(v: JsonValue) -> &'static str {
+        let w = for_result("Out", v);
+        if w.as_any().is::<MarkdownWidget>() {
+            "text"
+        } else if w.as_any().is::<ResultViewerWidget>() {
+            "result_viewer"
+        } else {
+            "other"
+        }
+    }
+
+    #[test]
+    fn content_type_picks_the_renderer() {
+        let md = json!({"ok": true, "content_type": "markdown", "data": "# Hi"});
+        assert_eq!(kind(md), "text");
+        let diff = json!({"ok": true, "content_type": "diff", "data": {"text": "+a\n-b"}});
+        assert_eq!(kind(diff), "text");
+        let table = json!({"ok": true, "content_type": "table", "data": {"items": [{"a": 1}]}});
+        assert_eq!(kind(table), "text");
+        // No hint, an unknown one, or a payload the renderer cannot show
+        assert_eq!(kind(json!({"ok": true, "data": "x"})), "result_viewer");
+        assert_eq!(
+            kind(json!({"content_type": "svg", "data": "x"})),
+            "result_viewer"
+        );
+        assert_eq!(
+            kind(json!({"content_type": "ansi", "data": [1]})),
+            "result_viewer"
+        );
+
+        let rows = vec![json!({"name": "api"})];
+        assert!(for_rows("Pods", rows.clone(), Some("table"))
+            .as_any()
+            .is::<MarkdownWidget>());
+        assert!(for_rows("Pods", rows, None)
+            .as_any()
+            .is::<ResultViewerWidget>());
+    }
+
+    #[test]
+    fn ansi_colors_become_styles() {
+        let lines = ansi_lines("\x1b[1;31mfail\x1b[0m ok \x1b[38;5;42mx\x1b[K");
+        let spans = &lines[0].spans;
+        assert_eq!(spans[0].content, "fail");
+        assert_eq!(spans[0].style.fg, Some(Color::Indexed(1)));
+        assert!(spans[0].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(
+            (spans[1].content.as_ref(), spans[1].style),
+            (" ok ", Style::default())
+        );
+        assert_eq!(spans[2].style.fg, Some(Color::Indexed(42)));
+        assert_eq!(strip_ansi("\x1b[32mgreen\x1b[0m\nplain"), "green\nplain");
+    }
+
+    #[test]
+    fn tables_align_columns() {
+        let (_, raw) = table_lines(&json!([{"name": "api", "up": true}, {"name": "db"}])).unwrap();
+        assert_eq!(raw, "name  up\napi   true\ndb");
+    }
+}
@@ -3,29 +3,523 @@ use crossterm::event::KeyCode;
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
+// Top-level arrays longer than this are split into client-side pages
+const DEFAULT_PAGE_SIZE: usize = 500;
+
+fn page_size_from_env() -> usize {
+    std::env::var("CHI_TUI_RESULT_PAGE_SIZE")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+}
+
+// Search across all items of a (possibly paged) result array
+struct ResultSearch {
+    query: String,
+    // Matching item indices over the whole array
+    matches: Vec<usize>,
+    current: usize,
+}
+
 pub struct ResultViewerWidget {
     pub title: String,
+    // Raw text of the whole value, or of the current page when paged
     json_pretty: String,
     json_value: serde_json::Value,
     mode_raw: bool,
     wrap: bool,
     scroll_y: u16,
     last_viewport_h: u16,
+    // Client-side paging (0 = off)
+    page_size: usize,
+    page: usize,
+    // Query being typed after '/'
+    search_input: Option<String>,
+    search: Option<ResultSearch>,
+    // Item to bring into view on the next render
+    scroll_to_item: Option<usize>,
+    // Pane B command that produced the value; 'r' re-runs it
+    source: Option<String>,
+    // Filter bar (`filters:`) and the value before client-side filtering
+    filters: Option<crate::widgets::filter_bar::FilterBar>,
+    unfiltered: Option<serde_json::Value>,
+    // Inline cell editing (`editable_fields:`); the cursor row is kept in view after moves
+    row_editor: Option<crate::widgets::row_edit::RowEditor>,
+    follow_row: bool,
+    // Values bigger than this many bytes (0 = off) show a summary until 'X' expands them
+    large_limit: usize,
+    // Size of the summarized value; None once expanded or when small enough
+    summary: Option<usize>,
+    expanded: bool,
+    // Values replaced by in-place refreshes (`[`/`]` step through them, `D` diffs); the
+    // current value is set aside in `live` while a snapshot is shown
+    snapshots: crate::widgets::snapshots::Snapshots,
+    live: Option<serde_json::Value>,
+    // A refresh arrived while a snapshot was shown
+    newer: bool,
+    diff: bool,
+    // Tree mode ('t'): the value as an expandable tree with a cursor; 'y' copies its value
+    tree_mode: bool,
+    tree: crate::widgets::json_tree::JsonTree,
+    // Visual rows of the raw JSON and its line selection ('V')
+    soft: crate::widgets::soft_wrap::SoftWrap,
+    // Column statistics popup ('%'); the inner None when no column was focused
+    column_stats: Option<Option<crate::widgets::column_stats::ColumnStats>>,
+    // First line of each listed item in the last frame, and quick-jump labels by item index
+    // (set while hint mode is active)
+    item_lines: Vec<(usize, u16)>,
+    pub hints: Vec<(usize, String)>,
 }
 
 impl ResultViewerWidget {
     pub fn new(title: impl Into<String>, value: serde_json::Value) -> Self {
-        let title = title.into();
-        let json_pretty =
-            serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string());
-        Self {
-            title,
-            json_pretty,
+        Self::with_page_size(title, value, page_size_from_env())
+    }
+
+    pub fn with_page_size(
+        title: impl Into<String>,
+        value: serde_json::Value,
+        page_size: usize,
+    ) -> Self {
+        let mut w = Self {
+            title: title.into(),
+            json_pretty: String::new(),
             json_value: value,
             mode_raw: false,
             wrap: false,
             scroll_y: 0,
             last_viewport_h: 0,
+            page_size,
+            page: 0,
+            search_input: None,
+            search: None,
+            scroll_to_item: None,
+            source: None,
+            filters: None,
+            unfiltered: None,
+            row_editor: None,
+            follow_row: false,
+            large_limit: crate::widgets::large_json::large_bytes_from_env(),
+            summary: None,
+            expanded: false,
+            snapshots: crate::widgets::snapshots::Snapshots::new(
+                crate::widgets::snapshots::keep_from_env(),
+            ),
+            live: None,
+            newer: false,
+            diff: false,
+            tree_mode: false,
+            tree: crate::widgets::json_tree::JsonTree::new(),
+            soft: crate::widgets::soft_wrap::SoftWrap::default(),
+            column_stats: None,
+            item_lines: Vec::new(),
+            hints: Vec::new(),
+        };
+        w.refresh_raw();
+        w
+    }
+
+    // Swap in refreshed content while keeping scroll, wrap, mode and page
+    pub fn set_value(&mut self, value: serde_json::Value) {
+        self.json_value = self.filtered(value);
+        self.search = None;
+        self.scroll_to_item = None;
+        self.page = self.page.min(self.page_count().saturating_sub(1));
+        let rows = self.items().map(|a| a.len()).unwrap_or(0);
+        if let Some(ed) = &mut self.row_editor {
+            ed.clamp(rows);
+        }
+        self.refresh_raw();
+    }
+
+    // `value` as shown through the filter bar (the unfiltered value is kept for the bar)
+    fn filtered(&mut self, value: serde_json::Value) -> serde_json::Value {
+        match &self.filters {
+            Some(bar) => {
+                let shown = bar.apply(&value);
+                self.unfiltered = Some(value);
+                shown
+            }
+            None => value,
+        }
+    }
+
+    // A re-run of the source command returned: the value it replaces is kept as a snapshot
+    // when it changed. While a snapshot is shown it stays on screen and the title notes
+    // that a newer result is waiting behind `]`.
+    pub fn refreshed(&mut self, value: serde_json::Value) {
+        if self.live.is_some() {
+            let value = self.filtered(value);
+            let Some(live) = self.live.as_mut() else {
+                return;
+            };
+            if *live != value {
+                let old = std::mem::replace(live, value);
+                self.snapshots
+                    .push_behind(old, std::time::SystemTime::now());
+                self.newer = true;
+            }
+            return;
+        }
+        let old = self.json_value.clone();
+        self.set_value(value);
+        if old != self.json_value {
+            self.snapshots.push(old, std::time::SystemTime::now());
+        }
+    }
+
+    // Show a snapshot (None: the current value again); the view starts at its top
+    fn show_snapshot(&mut self, value: Option<serde_json::Value>) {
+        match value {
+            Some(v) => {
+                let current = std::mem::replace(&mut self.json_value, v);
+                self.live.get_or_insert(current);
+            }
+            None => match self.live.take() {
+                Some(current) => {
+                    self.json_value = current;
+                    self.newer = false;
+                }
+                None => return,
+            },
+        }
+        self.search = None;
+        self.scroll_y = 0;
+        self.page = 0;
+        self.refresh_raw();
+    }
+
+    // Lines of the diff between the base snapshot and the current value
+    fn diff_lines(&self) -> Vec<Line<'static>> {
+        let current = self.live.as_ref().unwrap_or(&self.json_value);
+        match self.snapshots.base() {
+            Some((_, base)) => crate::widgets::snapshots::diff_lines(base, current),
+            None => Vec::new(),
+        }
+    }
+
+    // First visible line; set values beyond the end are clamped on render
+    pub fn scroll_offset(&self) -> u16 {
+        self.scroll_y
+    }
+
+    pub fn set_scroll_offset(&mut self, y: u16) {
+        self.scroll_y = y;
+    }
+
+    pub fn value(&self) -> &serde_json::Value {
+        &self.json_value
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    pub fn set_source(&mut self, cmdline: Option<String>) {
+        self.source = cmdline;
+    }
+
+    pub fn has_filters(&self) -> bool {
+        self.filters.is_some()
+    }
+
+    pub fn set_filters(&mut self, bar: crate::widgets::filter_bar::FilterBar) {
+        self.filters = Some(bar);
+        let value = std::mem::take(&mut self.json_value);
+        self.set_value(value);
+    }
+
+    pub fn set_row_editor(&mut self, editor: crate::widgets::row_edit::RowEditor) {
+        self.row_editor = Some(editor);
+    }
+
+    // True while a cell value is being typed
+    pub fn editing(&self) -> bool {
+        self.row_editor.as_ref().is_some_and(|ed| ed.editing())
+    }
+
+    // The update command of an inline edit finished: patch the row (also in the
+    // unfiltered value behind a filter bar). Returns the edited field
+    pub fn row_updated(
+        &mut self,
+        outcome: Result<serde_json::Value, String>,
+    ) -> Result<String, String> {
+        let Some(ed) = &mut self.row_editor else {
+            return Err("the edited result is no longer shown".into());
+        };
+        let index = ed.pending_row();
+        let old = index.and_then(|i| self.json_value.get(i)).cloned();
+        let field = match self.json_value.as_array_mut() {
+            Some(rows) => ed.finish(rows, outcome)?,
+            None => ed.finish(&mut [], outcome)?,
+        };
+        if let (Some(old), Some(new)) = (old, index.and_then(|i| self.json_value.get(i))) {
+            if let Some(row) = self
+                .unfiltered
+                .as_mut()
+                .and_then(|v| v.as_array_mut())
+                .and_then(|all| all.iter_mut().find(|r| **r == old))
+            {
+                *row = new.clone();
+            }
+        }
+        self.refresh_raw();
+        Ok(field)
+    }
+
+    // A filter changed: re-run the command with the new values, or re-filter locally
+    fn filter_changed(&mut self, rerun: bool) -> Vec<crate::app::Effect> {
+        let Some(bar) = &self.filters else {
+            return Vec::new();
+        };
+        self.scroll_y = 0;
+        self.page = 0;
+        if rerun {
+            let cmdline = bar.command();
+            // The reply matches the new source, so it updates this viewer in place; earlier
+            // results of the old command are not its history
+            self.source = Some(cmdline.clone());
+            self.snapshots = crate::widgets::snapshots::Snapshots::new(
+                crate::widgets::snapshots::keep_from_env(),
+            );
+            return vec![crate::app::Effect::LoadPanelCmd {
+                pane: crate::ui::PanelPane::B,
+                cmdline,
+            }];
+        }
+        if let Some(value) = self.unfiltered.take() {
+            self.set_value(value);
+        }
+        Vec::new()
+    }
+
+    // True while a search query is being typed (the viewer wants every key)
+    // Tree mode takes the arrow keys, Enter and 'y' (not while a diff is shown)
+    pub fn tree_active(&self) -> bool {
+        self.tree_mode && !self.diff
+    }
+
+    // 'y' in tree mode: the value under the cursor to the clipboard
+    fn copy_at_cursor(&self) -> Vec<crate::app::Effect> {
+        let Some(v) = self.tree.selected(&self.json_value) else {
+            return Vec::new();
+        };
+        let text = crate::widgets::json_tree::copy_text(v);
+        let pointer = match self.tree.pointer(&self.json_value) {
+            p if p.is_empty() => "the whole value".to_string(),
+            p => p,
+        };
+        crate::widgets::soft_wrap::copy_effects(text, &pointer)
+    }
+
+    // The raw JSON text is on screen (not a summary, diff or tree)
+    fn raw_text_shown(&self) -> bool {
+        self.mode_raw && !self.diff && self.summary.is_none() && !self.tree_active()
+    }
+
+    // A line selection is open in the raw JSON ('V'): the viewer wants every key
+    pub fn selecting(&self) -> bool {
+        self.raw_text_shown() && self.soft.selecting()
+    }
+
+    // The selected raw lines as written (no wrap breaks)
+    pub fn selected_text(&self) -> Option<String> {
+        if !self.selecting() {
+            return None;
+        }
+        self.soft
+            .selected_text(&self.json_pretty.lines().collect::<Vec<_>>())
+    }
+
+    pub fn search_active(&self) -> bool {
+        self.search_input.is_some()
+    }
+
+    // The column statistics popup is open (Esc or '%' closes it)
+    pub fn stats_open(&self) -> bool {
+        self.column_stats.is_some()
+    }
+
+    // Statistics of the focused column: the field under the tree cursor, else the field
+    // picked for inline editing
+    fn focused_column_stats(&self) -> Option<crate::widgets::column_stats::ColumnStats> {
+        use crate::widgets::column_stats::{column_at, compute};
+        if self.tree_active() {
+            let pointer = self.tree.pointer(&self.json_value);
+            let (name, rows, path) = column_at(&self.json_value, &pointer)?;
+            return Some(compute(name, rows, &path));
+        }
+        let (ed, rows) = (self.row_editor.as_ref()?, self.items()?);
+        let field = ed.field().to_string();
+        Some(compute(field.clone(), rows, &[field]))
+    }
+
+    // Listed items whose first line was in view in the last frame
+    pub fn visible_items(&self) -> Vec<usize> {
+        let end = self.scroll_y.saturating_add(self.last_viewport_h);
+        self.item_lines
+            .iter()
+            .filter(|(_, line)| (self.scroll_y..end).contains(line))
+            .map(|(i, _)| *i)
+            .collect()
+    }
+
+    // Quick-jump to an item: the editor's cursor row when cells are editable, else the item
+    // opened in tree mode
+    pub fn open_item(&mut self, i: usize) {
+        if let Some(ed) = &mut self.row_editor {
+            ed.row = i;
+            self.follow_row = true;
+            return;
+        }
+        self.tree.open(&self.json_value, &format!("/{i}"));
+        self.tree_mode = true;
+        self.diff = false;
+    }
+
+    fn items(&self) -> Option<&Vec<serde_json::Value>> {
+        self.json_value.as_array()
+    }
+
+    fn page_count(&self) -> usize {
+        match self.items() {
+            Some(arr) if self.page_size > 0 && arr.len() > self.page_size => {
+                arr.len().div_ceil(self.page_size)
+            }
+            _ => 1,
+        }
+    }
+
+    fn paged(&self) -> bool {
+        self.page_count() > 1
+    }
+
+    // Item index range shown on the current page
+    fn page_range(&self) -> std::ops::Range<usize> {
+        let len = self.items().map(|a| a.len()).unwrap_or(0);
+        if !self.paged() {
+            return 0..len;
+        }
+        let start = self.page * self.page_size;
+        start..(start + self.page_size).min(len)
+    }
+
+    // Raw view only serializes the visible page so huge results stay cheap; a huge
+    // unpaged value is only measured (its summary is built on render)
+    fn refresh_raw(&mut self) {
+        use crate::widgets::large_json::size_within;
+        self.summary = None;
+        if !self.expanded
+            && self.large_limit > 0
+            && !self.paged()
+            && size_within(&self.json_value, self.large_limit).is_none()
+        {
+            self.summary = size_within(&self.json_value, usize::MAX);
+        }
+        self.json_pretty = if self.summary.is_some() {
+            String::new()
+        } else if self.paged() {
+            let slice = &self.items().map(|a| &a[self.page_range()]).unwrap_or(&[]);
+            serde_json::to_string_pretty(slice).unwrap_or_default()
+        } else {
+            serde_json::to_string_pretty(&self.json_value)
+                .unwrap_or_else(|_| self.json_value.to_string())
+        };
+    }
+
+    fn set_page(&mut self, page: usize) {
+        let page = page.min(self.page_count().saturating_sub(1));
+        if page != self.page {
+            self.page = page;
+            self.scroll_y = 0;
+            self.refresh_raw();
+        }
+    }
+
+    fn run_search(&mut self, query: String) {
+        if query.is_empty() {
+            self.search = None;
+            return;
+        }
+        let needle = query.to_lowercase();
+        let matches: Vec<usize> = self
+            .items()
+            .map(|arr| {
+                arr.iter()
+                    .enumerate()
+                    .filter(|(_, v)| v.to_string().to_lowercase().contains(&needle))
+                    .map(|(i, _)| i)
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Start from the first match at or after the current page
+        let from = self.page_range().start;
+        let current = matches.iter().position(|&i| i >= from).unwrap_or(0);
+        self.search = Some(ResultSearch {
+            query,
+            matches,
+            current,
+        });
+        self.jump_to_match();
+    }
+
+    fn next_match(&mut self) {
+        if let Some(s) = &mut self.search {
+            if !s.matches.is_empty() {
+                s.current = (s.current + 1) % s.matches.len();
+            }
+        }
+        self.jump_to_match();
+    }
+
+    fn jump_to_match(&mut self) {
+        let Some(item) = self
+            .search
+            .as_ref()
+            .and_then(|s| s.matches.get(s.current).copied())
+        else {
+            return;
+        };
+        if self.page_size > 0 && self.paged() {
+            self.set_page(item / self.page_size);
+        }
+        self.scroll_to_item = Some(item);
+    }
+
+    fn footer_lines(&self, lines: &mut Vec<Line>) {
+        let total = self.items().map(|a| a.len()).unwrap_or(0);
+        if self.paged() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "─────  Page {}/{} ({} items)  ─────",
+                    self.page + 1,
+                    self.page_count(),
+                    total
+                ),
+                Style::default().fg(crate::theme::MUTED),
+            )));
+        }
+        if let Some(q) = &self.search_input {
+            lines.push(Line::from(vec![
+                Span::styled("/", Style::default().fg(crate::theme::active().secondary)),
+                Span::raw(format!("{q}▏")),
+            ]));
+        } else if let Some(s) = &self.search {
+            let status = if s.matches.is_empty() {
+                format!("No matches for '{}'", s.query)
+            } else {
+                format!(
+                    "Match {}/{} for '{}' • n next",
+                    s.current + 1,
+                    s.matches.len(),
+                    s.query
+                )
+            };
+            lines.push(Line::from(Span::styled(
+                status,
+                Style::default().fg(crate::theme::MUTED),
+            )));
         }
     }
 
@@ -56,7 +550,7 @@ impl ResultViewerWidget {
                 parts.extend(arrow_span());
                 parts.push(Span::styled(
                     b.to_string(),
-                    Style::default().fg(Color::Magenta),
+                    crate::theme::active().json_bool(),
                 ));
                 lines.push(Line::from(parts));
             }
@@ -65,7 +559,7 @@ impl ResultViewerWidget {
                 parts.extend(arrow_span());
                 parts.push(Span::styled(
                     n.to_string(),
-                    Style::default().fg(Color::Yellow),
+                    crate::theme::active().json_number(),
                 ));
                 lines.push(Line::from(parts));
             }
@@ -73,7 +567,10 @@ impl ResultViewerWidget {
                 if !s.is_empty() {
                     let mut parts = vec![Span::raw(indent_sp)];
                     parts.extend(arrow_span());
-                    parts.push(Span::styled(s.clone(), Style::default().fg(Color::Green)));
+                    parts.push(Span::styled(
+                        s.clone(),
+                        crate::theme::active().json_string(),
+                    ));
                     lines.push(Line::from(parts));
                 }
             }
@@ -83,49 +580,7 @@ impl ResultViewerWidget {
                     return;
                 }
                 for item in arr {
-                    if is_empty_value(item) {
-                        continue;
-                    }
-                    // Bullet for each item
-                    let mut hdr = vec![Span::raw(indent_sp.clone())];
-                    hdr.extend(arrow_span());
-                    hdr.push(Span::raw("• "));
-                    let mut sublines: Vec<Line> = Vec::new();
-                    match item {
-                        serde_json::Value::Object(obj) if obj.contains_key("title") => {
-                            let title = obj.get("title").and_then(|s| s.as_str()).unwrap_or("");
-                            hdr.push(Span::styled(
-                                title.to_string(),
-                                Style::default().fg(Color::Cyan),
-                            ));
-                            lines.push(Line::from(hdr));
-                            // Render rest of fields indented (skip empties)
-                            let mut other = obj.clone();
-                            other.remove("title");
-                            for (k, v) in other.iter() {
-                                if is_empty_value(v) || is_technical_field(k, v) {
-                                    continue;
-                                }
-                                let mut l = vec![
-                                    Span::raw(" ".repeat(indent + 2)),
-                                    // show arrow for nested entries
-                                    Span::styled("-> ", Style::default().fg(crate::theme::MUTED)),
-                                    Span::styled(
-                                        format!("{k}: "),
-                                        Style::default().fg(Color::Cyan),
-                                    ),
-                                ];
-                                l.push(value_preview_span(v));
-                                lines.push(Line::from(l));
-                            }
-                        }
-                        _ => {
-                            // Render simple or nested value with additional indent
-                            lines.push(Line::from(hdr));
-                            self.render_value_pretty(item, indent + 2, &mut sublines);
-                            lines.extend(sublines);
-                        }
-                    }
+                    self.render_array_item(item, indent, lines);
                 }
             }
             serde_json::Value::Object(map) => {
@@ -148,7 +603,7 @@ impl ResultViewerWidget {
                     l.extend(arrow_span());
                     l.push(Span::styled(
                         format!("{k}: "),
-                        Style::default().fg(Color::Cyan),
+                        crate::theme::active().json_key(),
                     ));
                     match v {
                         serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
@@ -164,18 +619,74 @@ impl ResultViewerWidget {
             }
         }
     }
+    // One bullet entry of an array (shared by nested arrays and paged top-level results)
+    fn render_array_item(&self, item: &serde_json::Value, indent: usize, lines: &mut Vec<Line>) {
+        if is_empty_value(item) {
+            return;
+        }
+        let indent_sp = " ".repeat(indent);
+        let arrow_span = || {
+            if indent > 0 {
+                vec![Span::styled(
+                    "-> ",
+                    Style::default().fg(crate::theme::MUTED),
+                )]
+            } else {
+                Vec::new()
+            }
+        };
+        // Bullet for each item
+        let mut hdr = vec![Span::raw(indent_sp.clone())];
+        hdr.extend(arrow_span());
+        hdr.push(Span::raw("• "));
+        let mut sublines: Vec<Line> = Vec::new();
+        match item {
+            serde_json::Value::Object(obj) if obj.contains_key("title") => {
+                let title = obj.get("title").and_then(|s| s.as_str()).unwrap_or("");
+                hdr.push(Span::styled(
+                    title.to_string(),
+                    crate::theme::active().json_key(),
+                ));
+                lines.push(Line::from(hdr));
+                // Render rest of fields indented (skip empties)
+                let mut other = obj.clone();
+                other.remove("title");
+                for (k, v) in other.iter() {
+                    if is_empty_value(v) || is_technical_field(k, v) {
+                        continue;
+                    }
+                    let mut l = vec![
+                        Span::raw(" ".repeat(indent + 2)),
+                        // show arrow for nested entries
+                        Span::styled("-> ", Style::default().fg(crate::theme::MUTED)),
+                        Span::styled(format!("{k}: "), crate::theme::active().json_key()),
+                    ];
+                    l.push(value_preview_span(v));
+                    lines.push(Line::from(l));
+                }
+            }
+            _ => {
+                // Render simple or nested value with additional indent
+                lines.push(Line::from(hdr));
+                self.render_value_pretty(item, indent + 2, &mut sublines);
+                lines.extend(sublines);
+            }
+        }
+    }
 }
 
 fn value_preview_span(v: &serde_json::Value) -> Span<'static> {
     match v {
         serde_json::Value::Null => Span::styled("null", Style::default().fg(crate::theme::MUTED)),
         serde_json::Value::Bool(b) => {
-            Span::styled(b.to_string(), Style::default().fg(Color::Magenta))
+            Span::styled(b.to_string(), crate::theme::active().json_bool())
         }
         serde_json::Value::Number(n) => {
-            Span::styled(n.to_string(), Style::default().fg(Color::Yellow))
+            Span::styled(n.to_string(), crate::theme::active().json_number())
+        }
+        serde_json::Value::String(s) => {
+            Span::styled(s.clone(), crate::theme::active().json_string())
         }
-        serde_json::Value::String(s) => Span::styled(s.clone(), Style::default().fg(Color::Green)),
         serde_json::Value::Array(arr) => Span::styled(
             format!("[{} items]", arr.len()),
             Style::default().fg(crate::theme::MUTED),
@@ -211,41 +722,321 @@ fn is_technical_field(key: &str, v: &serde_json::Value) -> bool {
 
 impl crate::widgets::Widget for ResultViewerWidget {
     fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, _tick: u64) {
+        let mut area = area;
+        let mut title = self.title.clone();
+        if let Some(bar) = &self.filters {
+            if area.height > 3 {
+                f.render_widget(Paragraph::new(bar.line()), Rect { height: 1, ..area });
+                area.y += 1;
+                area.height -= 1;
+            }
+            let active = bar.summary();
+            if !active.is_empty() {
+                title = format!("{title} [{active}]");
+            }
+        }
+        if crate::widgets::Widget::unsaved(self) {
+            title.push_str(" •");
+        }
+        let now = std::time::SystemTime::now();
+        let history = if self.diff {
+            self.snapshots.diff_label(now)
+        } else {
+            self.snapshots.label(now)
+        };
+        if let Some(label) = history {
+            title = format!("{title} [{label}]");
+        }
+        if self.newer {
+            title.push_str(" [newer result: } to view]");
+        }
+        if self.tree_active() {
+            let pointer = self.tree.pointer(&self.json_value);
+            let pointer = if pointer.is_empty() { "/" } else { &pointer };
+            let title = crate::widgets::chrome::fit_title(&format!("{title} — {pointer}"), area);
+            let block = panel_block(&title, focused);
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            let theme = crate::theme::active();
+            let mut lines = vec![Line::from(Span::styled(
+                "←/→ collapse/expand  •  Enter toggle  •  y copy value  •  t back",
+                theme.text_muted(),
+            ))];
+            let rows = inner.height.saturating_sub(1) as usize;
+            lines.extend(self.tree.lines(&self.json_value, rows, &theme));
+            f.render_widget(Paragraph::new(lines), inner);
+            if let Some(stats) = &self.column_stats {
+                crate::widgets::column_stats::draw(f, area, stats.as_ref());
+            }
+            return;
+        }
+        let editor_rows = match (&self.row_editor, self.items()) {
+            (Some(ed), Some(rows)) if !self.mode_raw => Some((ed.row, ed.line(rows))),
+            _ => None,
+        };
+        if let Some((_, line)) = &editor_rows {
+            if area.height > 4 {
+                let bottom = area.y + area.height - 1;
+                f.render_widget(
+                    Paragraph::new(line.clone()),
+                    Rect {
+                        y: bottom,
+                        height: 1,
+                        ..area
+                    },
+                );
+                area.height -= 1;
+            }
+        }
+        self.item_lines.clear();
+        // Lines of the editor's cursor row
+        let mut cursor_lines: Option<std::ops::Range<usize>> = None;
         // Build lines according to mode
         let mut lines: Vec<Line> = Vec::new();
-        if self.mode_raw {
-            for l in self.json_pretty.lines() {
-                lines.push(Line::from(l.to_string()));
+        if self.diff {
+            lines.push(Line::from(Span::styled(
+                "D back to the result  •  {/} compare another snapshot",
+                Style::default().fg(crate::theme::MUTED),
+            )));
+            lines.extend(self.diff_lines());
+            self.scroll_to_item = None;
+        } else if let Some(total) = self.summary {
+            lines = crate::widgets::large_json::summary_lines(&self.json_value, total);
+        } else if self.mode_raw {
+            // Wrapped here rather than by the Paragraph so rows map back to the text's lines
+            let theme = crate::theme::active();
+            let text: Vec<&str> = self.json_pretty.lines().collect();
+            let width = if self.wrap {
+                area.width.saturating_sub(2)
+            } else {
+                0
+            };
+            self.soft.layout(&text, width as usize);
+            let styled: Vec<Line<'static>> = text
+                .iter()
+                .map(|l| crate::widgets::syntax::json_line(l, &theme))
+                .collect();
+            lines.extend(self.soft.wrap(&styled, theme.list_cursor_style()));
+            if let Some(r) = self.soft.selected_lines() {
+                title = format!(
+                    "{title} — lines {}-{} (y copy, Esc cancel)",
+                    r.start() + 1,
+                    r.end() + 1
+                );
             }
+            self.scroll_to_item = None;
         } else {
             // Optional first hint line
+            let hint = if !self.snapshots.is_empty() && self.paged() {
+                "Press j to toggle raw JSON  •  [/] page  •  {/} earlier results  •  D diff  •  Backspace to go back"
+            } else if !self.snapshots.is_empty() {
+                "Press j to toggle raw JSON  •  {/} earlier results  •  D diff  •  Backspace to go back"
+            } else if self.paged() {
+                "Press j to toggle raw JSON  •  [/] page  •  / search  •  Backspace to go back"
+            } else if self.items().is_some() {
+                "Press j to toggle raw JSON  •  / search  •  Backspace to go back"
+            } else {
+                "Press j to toggle raw JSON  •  Backspace to go back"
+            };
             lines.push(Line::from(vec![Span::styled(
-                "Press j to toggle raw JSON  •  Backspace to go back",
+                hint,
                 Style::default().fg(crate::theme::MUTED),
             )]));
-            self.render_value_pretty(&self.json_value, 0, &mut lines);
+            if let Some(arr) = self.items() {
+                // Only the current page is rendered; remember where the searched item starts
+                let mut target_line = None;
+                let mut item_lines = Vec::new();
+                for i in self.page_range() {
+                    if self.scroll_to_item == Some(i) {
+                        target_line = Some(lines.len() as u16);
+                    }
+                    let start = lines.len();
+                    self.render_array_item(&arr[i], 0, &mut lines);
+                    if start < lines.len() {
+                        item_lines.push((i, start as u16));
+                    }
+                    // A jump label replaces the item's bullet
+                    if let Some((_, label)) = self.hints.iter().find(|(h, _)| *h == i) {
+                        if let Some(bullet) =
+                            lines[start..].first_mut().and_then(|l| l.spans.get_mut(1))
+                        {
+                            *bullet = Span::styled(
+                                label.clone(),
+                                Style::default()
+                                    .fg(Color::Black)
+                                    .bg(Color::Yellow)
+                                    .add_modifier(Modifier::BOLD),
+                            );
+                        }
+                    }
+                    if editor_rows.as_ref().is_some_and(|(row, _)| *row == i) {
+                        cursor_lines = Some(start..lines.len());
+                    }
+                }
+                if let Some(y) = target_line {
+                    self.scroll_y = y;
+                    self.scroll_to_item = None;
+                }
+                self.item_lines = item_lines;
+            } else {
+                self.render_value_pretty(&self.json_value, 0, &mut lines);
+            }
         }
+        self.footer_lines(&mut lines);
         // Viewport calcs
         self.last_viewport_h = area.height.saturating_sub(2);
         let total = lines.len() as u16;
         let max_scroll = total.saturating_sub(self.last_viewport_h);
+        if let Some(rows) = cursor_lines {
+            let style = crate::theme::active().list_cursor_style();
+            for l in &mut lines[rows.clone()] {
+                l.style = style;
+            }
+            if std::mem::take(&mut self.follow_row) {
+                let (start, end) = (rows.start as u16, rows.end as u16);
+                if start < self.scroll_y {
+                    self.scroll_y = start;
+                } else if end > self.scroll_y + self.last_viewport_h {
+                    self.scroll_y = end.saturating_sub(self.last_viewport_h).min(start);
+                }
+            }
+        }
+        if self.selecting() {
+            self.scroll_y = self.soft.follow(self.scroll_y, self.last_viewport_h);
+        }
         if self.scroll_y > max_scroll {
             self.scroll_y = max_scroll;
         }
-        let block = panel_block(&self.title, focused);
+        let title = crate::widgets::chrome::fit_title(&title, area);
+        let block = panel_block(&title, focused);
         let p = Paragraph::new(lines)
             .block(block)
-            .wrap(ratatui::widgets::Wrap { trim: !self.wrap })
             .scroll((self.scroll_y, 0));
+        let p = if self.raw_text_shown() {
+            p
+        } else {
+            p.wrap(ratatui::widgets::Wrap { trim: !self.wrap })
+        };
         f.render_widget(p, area);
+        if let Some(stats) = &self.column_stats {
+            crate::widgets::column_stats::draw(f, area, stats.as_ref());
+        }
     }
     fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        if self.column_stats.is_some() {
+            if matches!(key, KeyCode::Esc | KeyCode::Char('%')) {
+                self.column_stats = None;
+            }
+            return Vec::new();
+        }
+        // Typing a search query
+        if let Some(q) = &mut self.search_input {
+            match key {
+                KeyCode::Char(c) => q.push(c),
+                KeyCode::Backspace => {
+                    q.pop();
+                }
+                KeyCode::Enter => {
+                    let query = self.search_input.take().unwrap_or_default();
+                    self.run_search(query);
+                }
+                KeyCode::Esc => self.search_input = None,
+                _ => {}
+            }
+            return Vec::new();
+        }
+        // Stepping through earlier results of in-place refreshes ([/] keep turning pages)
+        if !self.snapshots.is_empty() {
+            match key {
+                KeyCode::Char('{') => {
+                    if let Some(v) = self.snapshots.back().cloned() {
+                        self.show_snapshot(Some(v));
+                    }
+                    return Vec::new();
+                }
+                KeyCode::Char('}') => {
+                    match self.snapshots.forward() {
+                        Some(Some(v)) => {
+                            let v = v.clone();
+                            self.show_snapshot(Some(v));
+                        }
+                        Some(None) => self.show_snapshot(None),
+                        None => {}
+                    }
+                    return Vec::new();
+                }
+                KeyCode::Char('D') => {
+                    self.diff = !self.diff;
+                    self.scroll_y = 0;
+                    return Vec::new();
+                }
+                _ => {}
+            }
+        }
+        if self.tree_active() {
+            if key == KeyCode::Char('y') {
+                return self.copy_at_cursor();
+            }
+            if self.tree.on_key(key, &self.json_value) {
+                return Vec::new();
+            }
+        }
+        // Raw JSON: line selection and gj/gk (before the filter bar, which also takes 'V')
+        if self.raw_text_shown() {
+            use crate::widgets::soft_wrap::WrapKey;
+            let text: Vec<&str> = self.json_pretty.lines().collect();
+            let (top, page) = (self.scroll_y as usize, self.last_viewport_h as usize);
+            match self.soft.on_key(key, &text, top, page) {
+                WrapKey::Ignored => {}
+                WrapKey::Handled => return Vec::new(),
+                WrapKey::Scroll(d) => {
+                    self.scroll_y = self.scroll_y.saturating_add_signed(d as i16);
+                    return Vec::new();
+                }
+                WrapKey::Copy(text) => return crate::widgets::soft_wrap::copy_lines(text),
+            }
+        }
+        // Snapshots are read-only
+        if let (Some(ed), Some(rows), false, None) = (
+            self.row_editor.as_mut(),
+            self.json_value.as_array(),
+            self.mode_raw,
+            &self.live,
+        ) {
+            use crate::widgets::row_edit::RowEditKey;
+            match ed.on_key(key, rows) {
+                RowEditKey::Ignored => {}
+                RowEditKey::Handled => {
+                    let row = ed.row;
+                    if self.paged() {
+                        self.set_page(row / self.page_size);
+                    }
+                    self.follow_row = true;
+                    return Vec::new();
+                }
+                RowEditKey::Submit(cmdline) => {
+                    return vec![crate::app::Effect::UpdateRow { cmdline }];
+                }
+            }
+        }
+        if let Some(rerun) = self
+            .filters
+            .as_mut()
+            .filter(|_| self.live.is_none())
+            .and_then(|bar| bar.on_key(key))
+        {
+            return self.filter_changed(rerun);
+        }
         match key {
             KeyCode::Up => {
-                if self.scroll_y > 0 {
-                    self.scroll_y -= 1;
-                }
+                self.scroll_y = self.scroll_y.saturating_sub(1);
+            }
+            KeyCode::Char('[') | KeyCode::Left => self.set_page(self.page.saturating_sub(1)),
+            KeyCode::Char(']') | KeyCode::Right => self.set_page(self.page + 1),
+            KeyCode::Char('/') if self.items().is_some() => {
+                self.search_input = Some(String::new());
             }
+            KeyCode::Char('n') => self.next_match(),
             KeyCode::Down => self.scroll_y = self.scroll_y.saturating_add(1),
             KeyCode::PageUp => {
                 let step = self.last_viewport_h;
@@ -256,15 +1047,16 @@ impl crate::widgets::Widget for ResultViewerWidget {
                 self.scroll_y = self.scroll_y.saturating_add(step);
             }
             KeyCode::Home => self.scroll_y = 0,
-            KeyCode::End => {
-                let max_scroll =
-                    self.json_pretty
-                        .lines()
-                        .count()
-                        .saturating_sub(self.last_viewport_h as usize) as u16;
-                self.scroll_y = max_scroll;
-            }
+            // Clamped to the last page on render (raw, paged and search views differ in length)
+            KeyCode::End => self.scroll_y = u16::MAX,
             KeyCode::Char('w') | KeyCode::Char('W') => self.wrap = !self.wrap,
+            KeyCode::Char('t') => self.tree_mode = !self.tree_mode,
+            KeyCode::Char('%') => self.column_stats = Some(self.focused_column_stats()),
+            KeyCode::Char('X') if self.summary.is_some() => {
+                self.expanded = true;
+                self.scroll_y = 0;
+                self.refresh_raw();
+            }
             KeyCode::Char('j') | KeyCode::Char('J') => {
                 // Toggle raw/pretty
                 self.mode_raw = !self.mode_raw;
@@ -275,6 +1067,138 @@ impl crate::widgets::Widget for ResultViewerWidget {
         }
         Vec::new()
     }
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![
+            WidgetAction::key("Toggle raw JSON", "j", KeyCode::Char('j')),
+            WidgetAction::key("Toggle wrap", "w", KeyCode::Char('w')),
+            WidgetAction::key("Toggle tree view", "t", KeyCode::Char('t')),
+        ];
+        if self.tree_active() {
+            actions.push(WidgetAction::key("Copy value", "y", KeyCode::Char('y')));
+        }
+        if self.tree_active() || self.row_editor.is_some() {
+            actions.push(WidgetAction::key("Column stats", "%", KeyCode::Char('%')));
+        }
+        if self.raw_text_shown() {
+            actions.push(WidgetAction::key("Select lines", "V", KeyCode::Char('V')));
+        }
+        if self.summary.is_some() {
+            actions.push(WidgetAction::key(
+                "Expand large result",
+                "X",
+                KeyCode::Char('X'),
+            ));
+        }
+        if self.items().is_some() {
+            actions.push(WidgetAction::key("Search items", "/", KeyCode::Char('/')));
+        }
+        if let Some(bar) = &self.filters {
+            actions.push(WidgetAction::key(
+                "Next filter value",
+                "v",
+                KeyCode::Char('v'),
+            ));
+            if bar.multiple() {
+                actions.push(WidgetAction::key("Next filter", ">", KeyCode::Char('>')));
+            }
+        }
+        if self.row_editor.is_some() {
+            actions.push(WidgetAction::key("Edit cell", "e", KeyCode::Char('e')));
+        }
+        if !self.snapshots.is_empty() {
+            actions.push(WidgetAction::key("Earlier result", "{", KeyCode::Char('{')));
+            if self.snapshots.viewing() {
+                actions.push(WidgetAction::key("Later result", "}", KeyCode::Char('}')));
+            }
+            actions.push(WidgetAction::key(
+                "Diff with current",
+                "D",
+                KeyCode::Char('D'),
+            ));
+        }
+        if self.paged() {
+            actions.push(WidgetAction::key("Previous page", "[", KeyCode::Char('[')));
+            actions.push(WidgetAction::key("Next page", "]", KeyCode::Char(']')));
+        }
+        if self.source.is_some() {
+            actions.push(WidgetAction::refresh());
+        }
+        actions.extend(WidgetAction::content());
+        actions
+    }
+    fn hints(&self) -> crate::widgets::KeyHints {
+        use crate::widgets::KeyHints;
+        if self.stats_open() {
+            return KeyHints::modal("Esc/% close");
+        }
+        if self.search_active() {
+            return KeyHints::modal("type a query • Enter search • Esc cancel");
+        }
+        if self.editing() {
+            return KeyHints::modal("type a value • Enter save • Esc cancel");
+        }
+        if self.selecting() {
+            return KeyHints::modal("↑/↓ extend • y copy • Esc cancel");
+        }
+        if self.tree_active() {
+            return KeyHints::new(
+                "↑/↓ move • ←/→ collapse/expand • Enter toggle • y copy • % column stats • t back",
+            );
+        }
+        if self.diff {
+            return KeyHints::new("↑/↓ scroll • {/} compare another • D back");
+        }
+        let mut hints = KeyHints::new("↑/↓ scroll • j raw • w wrap • t tree");
+        if self.row_editor.is_some() {
+            hints.push("←/→ field • e edit • % column stats");
+        }
+        if self.items().is_some() {
+            hints.push("/ search");
+        }
+        if self.raw_text_shown() {
+            hints.push("V select lines");
+        }
+        if self.paged() {
+            hints.push("[/] page");
+        }
+        if !self.snapshots.is_empty() {
+            hints.push("{/} earlier results • D diff");
+        }
+        if self.filters.is_some() {
+            hints.push("v filter value");
+        }
+        if self.source.is_some() {
+            hints.push("r refresh");
+        }
+        hints
+    }
+    fn unsaved(&self) -> bool {
+        match (&self.row_editor, self.items()) {
+            (Some(ed), Some(rows)) => ed.unsaved(rows),
+            _ => false,
+        }
+    }
+    fn save(&mut self) -> Vec<crate::app::Effect> {
+        if !self.editing() {
+            return Vec::new();
+        }
+        self.on_key(KeyCode::Enter)
+    }
+    fn discard(&mut self) {
+        if let Some(ed) = &mut self.row_editor {
+            ed.cancel();
+        }
+    }
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        match &self.source {
+            Some(cmdline) => vec![crate::app::Effect::LoadPanelCmd {
+                pane: crate::ui::PanelPane::B,
+                cmdline: cmdline.clone(),
+            }],
+            None => Vec::new(),
+        }
+    }
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -282,3 +1206,190 @@ impl crate::widgets::Widget for ResultViewerWidget {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ResultViewerWidget;
+    use crate::widgets::Widget;
+    use crossterm::event::KeyCode;
+
+    fn render_text(w: &mut ResultViewerWidget) -> String {
+        let backend = ratatui::backend::TestBackend::new(60, 30);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| w.render(f, f.area(), true, 0)).unwrap();
+        let buf = terminal.backend().buffer().clone();
+        buf.content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn huge_arrays_are_paged_and_searched_across_pages() {
+        let items: Vec<_> = (0..35)
+            .map(|i| serde_json::json!({"title": format!("item-{i}")}))
+            .collect();
+        let mut w = ResultViewerWidget::with_page_size("Results", items.into(), 10);
+        assert_eq!(w.page_count(), 4);
+        let text = render_text(&mut w);
+        assert!(text.contains("item-9"));
+        assert!(!text.contains("item-10"));
+        assert!(text.contains("Page 1/4 (35 items)"));
+
+        w.on_key(KeyCode::Char(']'));
+        assert_eq!(w.page_range(), 10..20);
+        // Raw view only holds the current page
+        assert!(w.json_pretty.contains("item-10") && !w.json_pretty.contains("item-9\""));
+
+        // Search finds items on other pages and jumps there
+        w.on_key(KeyCode::Char('/'));
+        assert!(w.search_active());
+        for c in "item-3".chars() {
+            w.on_key(KeyCode::Char(c));
+        }
+        w.on_key(KeyCode::Enter);
+        assert!(!w.search_active());
+        // Matches: 3, 30..=34; first at/after page 2 is item-30 on page 4
+        assert_eq!(w.page, 3);
+        let text = render_text(&mut w);
+        assert!(text.contains("Match 2/6"));
+        w.on_key(KeyCode::Char('n'));
+        w.on_key(KeyCode::Char('n'));
+        w.on_key(KeyCode::Char('n'));
+        w.on_key(KeyCode::Char('n'));
+        w.on_key(KeyCode::Char('n'));
+        // Wrapped around to item-3 on the first page
+        assert_eq!(w.page, 0);
+    }
+
+    #[test]
+    fn huge_values_open_as_a_summary_until_expanded() {
+        let big = serde_json::json!({"blob": "x".repeat(500), "count": 3});
+        let mut w = ResultViewerWidget::new("Results", serde_json::Value::Null);
+        w.large_limit = 200;
+        w.set_value(big.clone());
+        assert!(w.json_pretty.is_empty());
+        let text = render_text(&mut w);
+        assert!(text.contains("Large result (521 B)"));
+        assert!(text.contains("blob: string (500 chars)"));
+        assert!(text.contains("count: number"));
+        assert!(w.actions().iter().any(|a| a.label == "Expand large result"));
+
+        w.on_key(KeyCode::Char('X'));
+        assert!(w.summary.is_none());
+        assert!(w.json_pretty.contains("\"count\": 3"));
+        // A refresh keeps the expanded view
+        w.set_value(big);
+        assert!(!render_text(&mut w).contains("Large result"));
+    }
+
+    #[test]
+    fn refreshes_keep_snapshots_to_step_through_and_diff() {
+        let mut w = ResultViewerWidget::new("Deploy", serde_json::json!({"replicas": 3}));
+        // Without earlier results `{` does nothing
+        w.on_key(KeyCode::Char('{'));
+        assert!(w.live.is_none());
+        w.refreshed(serde_json::json!({"replicas": 3}));
+        assert!(w.snapshots.is_empty());
+        w.refreshed(serde_json::json!({"replicas": 5}));
+        w.refreshed(serde_json::json!({"replicas": 8}));
+
+        w.on_key(KeyCode::Char('{'));
+        w.on_key(KeyCode::Char('{'));
+        assert_eq!(w.value(), &serde_json::json!({"replicas": 3}));
+        let text = render_text(&mut w);
+        assert!(text.contains("[snapshot 1/2"), "{text}");
+
+        w.on_key(KeyCode::Char('D'));
+        let text = render_text(&mut w);
+        assert!(text.contains("~ .replicas  3 → 8"), "{text}");
+        w.on_key(KeyCode::Char('D'));
+
+        w.on_key(KeyCode::Char('}'));
+        assert_eq!(w.value(), &serde_json::json!({"replicas": 5}));
+        w.on_key(KeyCode::Char('}'));
+        assert_eq!(w.value(), &serde_json::json!({"replicas": 8}));
+        assert!(w.live.is_none());
+
+        // A refresh while a snapshot is shown keeps it on screen and notes the newer result
+        w.on_key(KeyCode::Char('{'));
+        w.refreshed(serde_json::json!({"replicas": 9}));
+        assert_eq!(w.value(), &serde_json::json!({"replicas": 5}));
+        let text = render_text(&mut w);
+        assert!(text.contains("[snapshot 2/3"), "{text}");
+        assert!(text.contains("newer result"), "{text}");
+        w.on_key(KeyCode::Char('}'));
+        assert_eq!(w.value(), &serde_json::json!({"replicas": 8}));
+        w.on_key(KeyCode::Char('}'));
+        assert_eq!(w.value(), &serde_json::json!({"replicas": 9}));
+        assert!(!render_text(&mut w).contains("newer result"));
+
+        // A paged result keeps `[`/`]` for its pages once it has earlier results
+        let items: Vec<_> = (0..25)
+            .map(|i| serde_json::json!({"title": format!("item-{i}")}))
+            .collect();
+        let mut w = ResultViewerWidget::with_page_size("Results", items.clone().into(), 10);
+        w.refreshed(items[..20].to_vec().into());
+        assert!(!w.snapshots.is_empty());
+        w.on_key(KeyCode::Char(']'));
+        assert_eq!(w.page_range(), 10..20);
+        assert!(w.live.is_none());
+        let hints = crate::widgets::Widget::hints(&w).text();
+        assert!(hints.contains("[/] page • {/} earlier results"), "{hints}");
+    }
+
+    #[test]
+    fn tree_mode_walks_the_value_with_its_pointer_in_the_title() {
+        let v = serde_json::json!({"data": {"items": [{"name": "web"}, {"name": "db"}]}});
+        let mut w = ResultViewerWidget::new("Pods", v);
+        w.on_key(KeyCode::Char('t'));
+        assert!(w.tree_active());
+        assert!(render_text(&mut w).contains("Pods — /"));
+        for key in [KeyCode::Down, KeyCode::Right, KeyCode::Down, KeyCode::Enter] {
+            w.on_key(key);
+        }
+        w.on_key(KeyCode::End);
+        let text = render_text(&mut w);
+        assert!(text.contains("Pods — /data/items/1"), "{text}");
+        assert!(text.contains("▸ [1]: {1 keys}"), "{text}");
+        // Refreshes keep what is expanded
+        w.refreshed(serde_json::json!({"data": {"items": [{"name": "web"}]}}));
+        assert!(render_text(&mut w).contains("Pods — /data/items/0"));
+        w.on_key(KeyCode::Char('t'));
+        assert!(!w.tree_active());
+    }
+
+    #[test]
+    fn column_stats_summarize_the_field_under_the_tree_cursor() {
+        let v = serde_json::json!({"data": {"items": [
+            {"name": "web", "cpu": 2},
+            {"name": "db", "cpu": 5},
+            {"name": "web", "cpu": 2}
+        ]}});
+        let mut w = ResultViewerWidget::new("Pods", v);
+        // Without a focused column the popup says how to pick one
+        w.on_key(KeyCode::Char('%'));
+        assert!(w.stats_open());
+        assert!(render_text(&mut w).contains("Put the tree cursor"));
+        w.on_key(KeyCode::Esc);
+        assert!(!w.stats_open());
+
+        w.on_key(KeyCode::Char('t'));
+        for key in [KeyCode::Down, KeyCode::Right, KeyCode::Down, KeyCode::Enter] {
+            w.on_key(key);
+        }
+        w.on_key(KeyCode::End);
+        w.on_key(KeyCode::Right);
+        w.on_key(KeyCode::Down);
+        assert!(render_text(&mut w).contains("Pods — /data/items/2/cpu"));
+        w.on_key(KeyCode::Char('%'));
+        let text = render_text(&mut w);
+        assert!(text.contains("Column cpu"), "{text}");
+        assert!(text.contains("mean  3"), "{text}");
+        // Keys stay in the popup until it closes
+        w.on_key(KeyCode::Down);
+        w.on_key(KeyCode::Char('%'));
+        w.on_key(KeyCode::Down);
+        w.on_key(KeyCode::Char('%'));
+        let text = render_text(&mut w);
+        assert!(text.contains("Column name"), "{text}");
+        assert!(text.contains("2 distinct"), "{text}");
+    }
+}
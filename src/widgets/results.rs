@@ -0,0 +1,260 @@
+use crate::ui::AppState;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use serde_json::Value as JsonValue;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+// Results screen ('H' on the menu, or a `builtin: results` item): the completed stream
+// results that the global JSON view showed, newest first, so running another command doesn't
+// lose them. Enter re-opens one, space marks one and `D` diffs it against the selected one,
+// `e` writes the selected one to a JSON file. Only the last `CHI_TUI_RESULT_ARCHIVE`
+// (default 20, 0 disables) are kept.
+
+const DEFAULT_KEEP: usize = 20;
+
+pub fn keep_from_env() -> usize {
+    std::env::var("CHI_TUI_RESULT_ARCHIVE")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_KEEP)
+}
+
+pub struct ArchivedResult {
+    // Stable across pushes, unlike the position
+    pub id: u64,
+    pub title: String,
+    pub cmdline: Option<String>,
+    pub at: SystemTime,
+    pub value: JsonValue,
+}
+
+pub struct ResultArchive {
+    keep: usize,
+    next_id: u64,
+    // Newest first
+    entries: VecDeque<ArchivedResult>,
+}
+
+impl Default for ResultArchive {
+    fn default() -> Self {
+        Self::new(keep_from_env())
+    }
+}
+
+impl ResultArchive {
+    pub fn new(keep: usize) -> Self {
+        Self {
+            keep,
+            next_id: 0,
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub fn push(&mut self, title: String, cmdline: Option<String>, value: JsonValue) {
+        if self.keep == 0 {
+            return;
+        }
+        self.next_id += 1;
+        self.entries.push_front(ArchivedResult {
+            id: self.next_id,
+            title,
+            cmdline,
+            at: SystemTime::now(),
+            value,
+        });
+        self.entries.truncate(self.keep);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ArchivedResult> {
+        self.entries.get(index)
+    }
+
+    pub fn by_id(&self, id: u64) -> Option<&ArchivedResult> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+}
+
+// Selection and modes of the Results screen
+#[derive(Default)]
+pub struct ResultsScreen {
+    pub selected: usize,
+    // Result picked with space as the base of a diff
+    pub marked: Option<u64>,
+    // Diff shown instead of the list: (older, newer) ids
+    pub diff: Option<(u64, u64)>,
+    pub scroll: u16,
+}
+
+impl ResultsScreen {
+    // Ids to diff: the marked result against the selected one (or, without a mark, the
+    // selected one against the result before it), older first
+    pub fn diff_pair(&self, archive: &ResultArchive) -> Option<(u64, u64)> {
+        let selected = archive.get(self.selected)?.id;
+        let other = match self.marked {
+            Some(id) if id != selected => id,
+            _ => archive.get(self.selected + 1)?.id,
+        };
+        // Ids grow with time
+        Some((other.min(selected), other.max(selected)))
+    }
+}
+
+// File in the working directory for `e`: chi-result-<title>-<unix secs>.json
+pub fn export(entry: &ArchivedResult) -> Result<PathBuf, String> {
+    let slug: String = entry
+        .title
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('-');
+    let secs = crate::services::clock::unix_secs(entry.at);
+    let path = std::env::current_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(format!("chi-result-{slug}-{secs}.json"));
+    let text = serde_json::to_string_pretty(&entry.value).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+fn when(at: SystemTime, now: SystemTime) -> String {
+    use crate::services::clock::{format_local, unix_secs};
+    let ago = crate::widgets::cells::relative_time(unix_secs(at), unix_secs(now));
+    format!("{} ({ago})", format_local(at))
+}
+
+fn size_label(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Array(a) => format!("{} items", a.len()),
+        JsonValue::Object(o) => match o.get("data") {
+            Some(JsonValue::Array(a)) => format!("{} items", a.len()),
+            _ => format!("{} keys", o.len()),
+        },
+        _ => "value".to_string(),
+    }
+}
+
+pub fn draw_results(f: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let now = SystemTime::now();
+    let archive = &state.results;
+    let screen = &state.results_screen;
+    if let Some((old, new)) = screen.diff {
+        let (Some(a), Some(b)) = (archive.by_id(old), archive.by_id(new)) else {
+            return;
+        };
+        let title = format!(
+            "Diff: {} {} → {} {}",
+            a.title,
+            when(a.at, now),
+            b.title,
+            when(b.at, now)
+        );
+        let block = crate::widgets::chrome::panel_block(&title, true);
+        let lines = crate::widgets::snapshots::diff_lines(&a.value, &b.value);
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .scroll((screen.scroll, 0)),
+            area,
+        );
+        return;
+    }
+    let mut lines: Vec<Line> = Vec::new();
+    if archive.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  no results yet — results of commands shown in the JSON view are kept here",
+            theme.text_muted(),
+        )));
+    }
+    for (i, e) in archive.entries.iter().enumerate() {
+        let sel = i == screen.selected;
+        let mark = if screen.marked == Some(e.id) {
+            "◆"
+        } else {
+            " "
+        };
+        let mut spans = vec![
+            Span::raw(format!(
+                "{} {mark} {}  {}  ",
+                if sel { "▶" } else { " " },
+                when(e.at, now),
+                e.title
+            )),
+            Span::styled(size_label(&e.value), theme.text_muted()),
+        ];
+        if let Some(cmd) = &e.cmdline {
+            spans.push(Span::styled(format!("  $ {cmd}"), theme.text_muted()));
+        }
+        let line = Line::from(spans);
+        lines.push(if sel {
+            line.style(theme.list_cursor_style())
+        } else {
+            line
+        });
+    }
+    let title = format!("Results ({})", archive.len());
+    let block = crate::widgets::chrome::panel_block(&title, true);
+    let height = area.height.saturating_sub(2) as usize;
+    let skip = screen.selected.saturating_sub(height.saturating_sub(1));
+    let lines: Vec<Line> = lines.into_iter().skip(skip).collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn keeps_the_newest_results_and_pairs_them_for_diffs() {
+        let mut a = ResultArchive::new(3);
+        for i in 0..4 {
+            a.push(format!("run {i}"), Some("app get".into()), json!({"n": i}));
+        }
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.get(0).map(|e| e.title.as_str()), Some("run 3"));
+        assert_eq!(a.get(2).map(|e| e.title.as_str()), Some("run 1"));
+        assert!(a.by_id(1).is_none());
+
+        let mut s = ResultsScreen::default();
+        // Without a mark: the selected result against the one before it
+        assert_eq!(s.diff_pair(&a), Some((3, 4)));
+        s.selected = 2;
+        assert_eq!(s.diff_pair(&a), None);
+        // A mark compares with the selected one, older first
+        s.marked = Some(4);
+        assert_eq!(s.diff_pair(&a), Some((2, 4)));
+
+        let mut off = ResultArchive::new(0);
+        off.push("x".into(), None, json!(1));
+        assert!(off.is_empty());
+    }
+
+    #[test]
+    fn exports_the_value_as_pretty_json() {
+        let mut a = ResultArchive::new(2);
+        a.push("Deploy / api".into(), None, json!({"ok": true}));
+        let path = export(a.get(0).unwrap()).unwrap();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("chi-result-deploy---api-") && name.ends_with(".json"));
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(text, "{\n  \"ok\": true\n}");
+    }
+}
@@ -0,0 +1,277 @@
+use crate::chi_core::argv::quote;
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use serde_json::Value as JsonValue;
+
+// Inline editing of result rows (`editable_fields:` + `update_cmd:` on the item). A row
+// cursor and a field are picked with the arrows, 'e' opens an input for the cell and
+// Enter runs the update command; its reply (or the typed value) then patches the row.
+pub struct RowEditor {
+    fields: Vec<String>,
+    update_cmd: String,
+    // Pane B command whose result the editor belongs to
+    source: String,
+    // Cursor over the items of the result array
+    pub row: usize,
+    field: usize,
+    input: Option<String>,
+    // Edit waiting for the update command: (row, field, value)
+    pending: Option<(usize, String, String)>,
+}
+
+// What a key did to the editor
+#[derive(Debug, PartialEq)]
+pub enum RowEditKey {
+    Ignored,
+    Handled,
+    // Run this update command
+    Submit(String),
+}
+
+// Cell text as typed by the user (strings without quotes)
+fn cell_text(v: Option<&JsonValue>) -> String {
+    match v {
+        Some(JsonValue::String(s)) => s.clone(),
+        Some(JsonValue::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+// `{key}` placeholders replaced by the row's scalar values, shell-quoted (also used by
+// item `verbs:` on loaded children)
+pub fn fill_placeholders(template: &str, row: &JsonValue) -> String {
+    let mut cmd = template.to_string();
+    if let Some(obj) = row.as_object() {
+        for (k, v) in obj {
+            if !v.is_object() && !v.is_array() {
+                cmd = cmd.replace(&format!("{{{k}}}"), &quote(&cell_text(Some(v))));
+            }
+        }
+    }
+    cmd
+}
+
+impl RowEditor {
+    pub fn new(fields: Vec<String>, update_cmd: String, source: String) -> Self {
+        Self {
+            fields,
+            update_cmd,
+            source,
+            row: 0,
+            field: 0,
+            input: None,
+            pending: None,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn field(&self) -> &str {
+        self.fields
+            .get(self.field)
+            .map(|s| s.as_str())
+            .unwrap_or("")
+    }
+
+    pub fn pending_row(&self) -> Option<usize> {
+        self.pending.as_ref().map(|(row, _, _)| *row)
+    }
+
+    // True while a value is being typed (the viewer wants every key)
+    pub fn editing(&self) -> bool {
+        self.input.is_some()
+    }
+
+    // A typed value that differs from the cell
+    pub fn unsaved(&self, rows: &[JsonValue]) -> bool {
+        self.input.as_ref().is_some_and(|input| {
+            *input != cell_text(rows.get(self.row).and_then(|r| r.get(self.field())))
+        })
+    }
+
+    pub fn cancel(&mut self) {
+        self.input = None;
+    }
+
+    pub fn clamp(&mut self, rows: usize) {
+        self.row = self.row.min(rows.saturating_sub(1));
+    }
+
+    // The update command for `field` = `value` on `row`: `{field}`, `{value}` and the
+    // row's scalar keys are substituted, all shell-quoted
+    pub fn command(&self, row: &JsonValue, field: &str, value: &str) -> String {
+        let cmd = self
+            .update_cmd
+            .replace("{field}", &quote(field))
+            .replace("{value}", &quote(value));
+        fill_placeholders(&cmd, row)
+    }
+
+    pub fn on_key(&mut self, key: KeyCode, rows: &[JsonValue]) -> RowEditKey {
+        if self.fields.is_empty() || rows.is_empty() {
+            return RowEditKey::Ignored;
+        }
+        if let Some(input) = &mut self.input {
+            match key {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Enter => {
+                    let value = self.input.take().unwrap_or_default();
+                    let Some(row) = rows.get(self.row) else {
+                        return RowEditKey::Handled;
+                    };
+                    let field = self.field().to_string();
+                    let cmd = self.command(row, &field, &value);
+                    self.pending = Some((self.row, field, value));
+                    return RowEditKey::Submit(cmd);
+                }
+                _ => {}
+            }
+            return RowEditKey::Handled;
+        }
+        match key {
+            KeyCode::Up => self.row = self.row.saturating_sub(1),
+            KeyCode::Down => self.row = (self.row + 1).min(rows.len() - 1),
+            KeyCode::Home => self.row = 0,
+            KeyCode::End => self.row = rows.len() - 1,
+            KeyCode::Left => self.field = (self.field + self.fields.len() - 1) % self.fields.len(),
+            KeyCode::Right => self.field = (self.field + 1) % self.fields.len(),
+            KeyCode::Char('e') => {
+                let current = rows.get(self.row).and_then(|r| r.get(self.field()));
+                self.input = Some(cell_text(current));
+            }
+            _ => return RowEditKey::Ignored,
+        }
+        RowEditKey::Handled
+    }
+
+    // The update command finished: patch the edited row. An object reply is merged into
+    // the row; anything else keeps the typed value (as a number/bool when the cell was one).
+    // Returns the edited field, or the error.
+    pub fn finish(
+        &mut self,
+        rows: &mut [JsonValue],
+        outcome: Result<JsonValue, String>,
+    ) -> Result<String, String> {
+        let Some((row, field, value)) = self.pending.take() else {
+            return Err("no edit pending".into());
+        };
+        let reply = outcome?;
+        let Some(JsonValue::Object(obj)) = rows.get_mut(row) else {
+            return Ok(field);
+        };
+        match reply {
+            JsonValue::Object(updated) => obj.extend(updated),
+            _ => {
+                let typed = match obj.get(&field) {
+                    Some(JsonValue::String(_)) | None => None,
+                    Some(_) => value.parse::<JsonValue>().ok(),
+                };
+                obj.insert(field.clone(), typed.unwrap_or(JsonValue::String(value)));
+            }
+        }
+        Ok(field)
+    }
+
+    pub fn line(&self, rows: &[JsonValue]) -> Line<'static> {
+        let theme = crate::theme::active();
+        let field = self.field().to_string();
+        match &self.input {
+            Some(input) => Line::from(vec![
+                Span::styled(" ✎ ", theme.text_muted()),
+                Span::styled(format!("{field}: "), theme.list_cursor_style()),
+                Span::raw(format!(" {input}▌")),
+                Span::styled("   Enter save • Esc cancel", theme.text_muted()),
+            ]),
+            None => {
+                let current = cell_text(rows.get(self.row).and_then(|r| r.get(&field)));
+                let mut spans = vec![Span::styled(
+                    format!(" ✎ row {}/{} ", self.row + 1, rows.len()),
+                    theme.text_muted(),
+                )];
+                for (i, name) in self.fields.iter().enumerate() {
+                    let style = if i == self.field {
+                        theme.list_cursor_style()
+                    } else {
+                        theme.text_muted()
+                    };
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled(format!(" {name} "), style));
+                }
+                spans.push(Span::raw(format!("  {current}")));
+                spans.push(Span::styled("   e edit • ←/→ field", theme.text_muted()));
+                Line::from(spans)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn edits_a_cell_and_patches_the_row() {
+        let mut ed = RowEditor::new(
+            vec!["status".into(), "replicas".into()],
+            "app set {id} --{field} {value}".into(),
+            "app list".into(),
+        );
+        let mut rows = vec![
+            json!({"id": "a", "status": "up", "replicas": 1}),
+            json!({"id": "b c", "status": "down", "replicas": 2}),
+        ];
+        assert_eq!(ed.on_key(KeyCode::Down, &rows), RowEditKey::Handled);
+        assert_eq!(ed.on_key(KeyCode::Char('e'), &rows), RowEditKey::Handled);
+        assert!(ed.editing());
+        for _ in 0..4 {
+            ed.on_key(KeyCode::Backspace, &rows);
+        }
+        for c in "it's up".chars() {
+            ed.on_key(KeyCode::Char(c), &rows);
+        }
+        assert_eq!(
+            ed.on_key(KeyCode::Enter, &rows),
+            RowEditKey::Submit(r#"app set 'b c' --status "it's up""#.into())
+        );
+        assert_eq!(
+            ed.finish(&mut rows, Ok(JsonValue::Null)),
+            Ok("status".into())
+        );
+        assert_eq!(rows[1]["status"], "it's up");
+
+        // Numbers stay numbers; an object reply is merged
+        ed.on_key(KeyCode::Right, &rows);
+        ed.on_key(KeyCode::Char('e'), &rows);
+        ed.on_key(KeyCode::Char('0'), &rows);
+        assert!(matches!(
+            ed.on_key(KeyCode::Enter, &rows),
+            RowEditKey::Submit(_)
+        ));
+        ed.finish(&mut rows, Ok(JsonValue::Null)).unwrap();
+        assert_eq!(rows[1]["replicas"], 20);
+        ed.on_key(KeyCode::Char('e'), &rows);
+        ed.on_key(KeyCode::Enter, &rows);
+        ed.finish(&mut rows, Ok(json!({"replicas": 3, "status": "scaling"})))
+            .unwrap();
+        assert_eq!(
+            rows[1],
+            json!({"id": "b c", "status": "scaling", "replicas": 3})
+        );
+
+        // A failed update leaves the row alone
+        ed.on_key(KeyCode::Char('e'), &rows);
+        ed.on_key(KeyCode::Enter, &rows);
+        assert_eq!(
+            ed.finish(&mut rows, Err("denied".into())),
+            Err("denied".into())
+        );
+        assert_eq!(rows[1]["replicas"], 3);
+    }
+}
@@ -0,0 +1,443 @@
+use crate::theme::Status;
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::markdown::{highlight_code, markdown_lines};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+// `widget: runbook`: a markdown page whose ```run code blocks are steps. ↑/↓ select a
+// step and Enter runs it with `sh -c`; its output streams into the results area below the
+// page and a marker shows each step's status, turning the runbook into a checklist.
+
+// Output lines kept per step
+const MAX_OUTPUT_LINES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+enum StepStatus {
+    Pending,
+    Running,
+    Done,
+    // Exit code (None when the shell could not be started or was killed)
+    Failed(Option<i32>),
+}
+
+struct Step {
+    code: String,
+    // Page line showing the step's marker
+    line: usize,
+    status: StepStatus,
+    output: Vec<String>,
+}
+
+enum StepEvent {
+    Output(usize, String),
+    Exit(usize, Option<i32>),
+}
+
+pub struct RunbookWidget {
+    title: String,
+    // Page without the step markers (added on render)
+    lines: Vec<Line<'static>>,
+    steps: Vec<Step>,
+    selected: usize,
+    scroll_y: u16,
+    last_viewport_h: u16,
+    // Bring the selected step into view on the next render
+    follow: bool,
+    tx: Sender<StepEvent>,
+    rx: Receiver<StepEvent>,
+    // File the page was read from; 'r' re-reads it
+    source_path: Option<std::path::PathBuf>,
+}
+
+// Page lines and steps: ```run (or ```run <lang>) blocks become steps, the rest is markdown
+fn parse(text: &str) -> (Vec<Line<'static>>, Vec<Step>) {
+    let mut lines = Vec::new();
+    let mut steps = Vec::new();
+    let mut prose = String::new();
+    // Open ```run block: (language, code lines)
+    let mut step: Option<(String, Vec<&str>)> = None;
+    let mut in_other_code = false;
+    for raw in text.lines() {
+        let trimmed = raw.trim_end_matches('\r');
+        let fence = trimmed.starts_with("```");
+        if let Some((lang, code)) = &mut step {
+            if fence {
+                lines.push(Line::from(""));
+                let code = code.join("\n");
+                steps.push(Step {
+                    line: lines.len() - 1,
+                    status: StepStatus::Pending,
+                    output: Vec::new(),
+                    code: code.clone(),
+                });
+                lines.extend(highlight_code(&code, Some(lang)));
+                step = None;
+            } else {
+                code.push(trimmed);
+            }
+            continue;
+        }
+        if fence && !in_other_code {
+            let mut info = trimmed.trim_start_matches('`').split_whitespace();
+            if info.next() == Some("run") {
+                lines.extend(markdown_lines(&prose));
+                prose.clear();
+                step = Some((info.next().unwrap_or("bash").to_string(), Vec::new()));
+                continue;
+            }
+        }
+        if fence {
+            in_other_code = !in_other_code;
+        }
+        prose.push_str(trimmed);
+        prose.push('\n');
+    }
+    lines.extend(markdown_lines(&prose));
+    (lines, steps)
+}
+
+impl RunbookWidget {
+    pub fn from_text(title: impl Into<String>, text: &str) -> Self {
+        let (lines, steps) = parse(text);
+        let (tx, rx) = channel();
+        Self {
+            title: title.into(),
+            lines,
+            steps,
+            selected: 0,
+            scroll_y: 0,
+            last_viewport_h: 0,
+            follow: true,
+            tx,
+            rx,
+            source_path: None,
+        }
+    }
+
+    pub fn from_path(title: impl Into<String>, path: &std::path::Path) -> Self {
+        let content = std::fs::read_to_string(path)
+            .unwrap_or_else(|_| format!("# Error\nFailed to read file: {}", path.display()));
+        let mut w = Self::from_text(title, &content);
+        w.source_path = Some(path.to_path_buf());
+        w
+    }
+
+    fn running(&self) -> bool {
+        self.steps.iter().any(|s| s.status == StepStatus::Running)
+    }
+
+    fn run_step(&mut self, i: usize) {
+        let Some(step) = self.steps.get_mut(i) else {
+            return;
+        };
+        if step.status == StepStatus::Running {
+            return;
+        }
+        step.status = StepStatus::Running;
+        step.output.clear();
+        let script = crate::services::interpolate::env(&step.code);
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let mut child = match Command::new("sh")
+                .arg("-c")
+                .arg(&script)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+            {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(StepEvent::Output(i, format!("sh: {e}")));
+                    let _ = tx.send(StepEvent::Exit(i, None));
+                    return;
+                }
+            };
+            let stderr = child.stderr.take().map(|err| {
+                let tx = tx.clone();
+                std::thread::spawn(move || {
+                    for line in BufReader::new(err).lines().map_while(Result::ok) {
+                        let _ = tx.send(StepEvent::Output(i, line));
+                    }
+                })
+            });
+            if let Some(out) = child.stdout.take() {
+                for line in BufReader::new(out).lines().map_while(Result::ok) {
+                    let _ = tx.send(StepEvent::Output(i, line));
+                }
+            }
+            if let Some(h) = stderr {
+                let _ = h.join();
+            }
+            let code = child.wait().ok().and_then(|s| s.code());
+            let _ = tx.send(StepEvent::Exit(i, code));
+        });
+    }
+
+    // Apply output and exits of running steps; a step that succeeds moves the cursor on
+    pub fn poll(&mut self) {
+        while let Ok(ev) = self.rx.try_recv() {
+            match ev {
+                StepEvent::Output(i, line) => {
+                    if let Some(step) = self.steps.get_mut(i) {
+                        step.output.push(line);
+                        if step.output.len() > MAX_OUTPUT_LINES {
+                            step.output.remove(0);
+                        }
+                    }
+                }
+                StepEvent::Exit(i, code) => {
+                    let Some(step) = self.steps.get_mut(i) else {
+                        continue;
+                    };
+                    step.status = match code {
+                        Some(0) => StepStatus::Done,
+                        other => StepStatus::Failed(other),
+                    };
+                    if code == Some(0) && i == self.selected && i + 1 < self.steps.len() {
+                        self.selected = i + 1;
+                        self.follow = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn select(&mut self, i: usize) {
+        if !self.steps.is_empty() {
+            self.selected = i.min(self.steps.len() - 1);
+            self.follow = true;
+        }
+    }
+
+    fn marker_line(&self, i: usize, tick: u64) -> Line<'static> {
+        let theme = crate::theme::active();
+        let step = &self.steps[i];
+        let (marker, style) = match &step.status {
+            StepStatus::Pending => ("○".to_string(), theme.text_muted()),
+            StepStatus::Running => (
+                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][tick as usize % 6].to_string(),
+                Style::default().fg(theme.selected),
+            ),
+            StepStatus::Done => (
+                theme.status_glyph(Status::Ok).to_string(),
+                theme.status_style(Status::Ok),
+            ),
+            StepStatus::Failed(Some(code)) => (
+                format!("{} exit {code}", theme.status_glyph(Status::Fail)),
+                theme.status_style(Status::Fail),
+            ),
+            StepStatus::Failed(None) => (
+                theme.status_glyph(Status::Fail).to_string(),
+                theme.status_style(Status::Fail),
+            ),
+        };
+        let mut spans = vec![
+            Span::styled(format!("{marker} "), style),
+            Span::styled(
+                format!("Step {}/{}", i + 1, self.steps.len()),
+                theme.md_heading(),
+            ),
+        ];
+        if i == self.selected {
+            spans.push(Span::styled("  ◀ Enter to run", theme.text_muted()));
+            return Line::from(spans).style(theme.list_cursor_style());
+        }
+        Line::from(spans)
+    }
+}
+
+impl crate::widgets::Widget for RunbookWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
+        self.poll();
+        let output = self
+            .steps
+            .get(self.selected)
+            .filter(|s| s.status != StepStatus::Pending);
+        let (page_area, output_area) = match output {
+            Some(_) if area.height >= 12 => {
+                let [page, out] =
+                    Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)])
+                        .areas(area);
+                (page, Some(out))
+            }
+            _ => (area, None),
+        };
+
+        let mut lines = self.lines.clone();
+        for i in 0..self.steps.len() {
+            lines[self.steps[i].line] = self.marker_line(i, tick);
+        }
+        self.last_viewport_h = page_area.height.saturating_sub(2);
+        if std::mem::take(&mut self.follow) {
+            if let Some(step) = self.steps.get(self.selected) {
+                let line = step.line as u16;
+                if line < self.scroll_y || line >= self.scroll_y + self.last_viewport_h {
+                    self.scroll_y = line.saturating_sub(1);
+                }
+            }
+        }
+        let max_scroll = (lines.len() as u16).saturating_sub(self.last_viewport_h);
+        self.scroll_y = self.scroll_y.min(max_scroll);
+        let title = format!(
+            "{} — {}/{} steps done",
+            self.title,
+            self.steps
+                .iter()
+                .filter(|s| s.status == StepStatus::Done)
+                .count(),
+            self.steps.len()
+        );
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(panel_block(&fit_title(&title, page_area), focused))
+                .scroll((self.scroll_y, 0)),
+            page_area,
+        );
+
+        if let (Some(out_area), Some(step)) = (output_area, output) {
+            let state = match &step.status {
+                StepStatus::Running => "running".to_string(),
+                StepStatus::Done => "done".to_string(),
+                StepStatus::Failed(Some(code)) => format!("failed, exit {code}"),
+                _ => "failed".to_string(),
+            };
+            let title = format!("Output — step {} ({state})", self.selected + 1);
+            // Follow the tail of the output
+            let visible = out_area.height.saturating_sub(2) as usize;
+            let start = step.output.len().saturating_sub(visible);
+            let text: Vec<Line> = step.output[start..]
+                .iter()
+                .map(|l| Line::from(l.clone()))
+                .collect();
+            f.render_widget(
+                Paragraph::new(text).block(panel_block(&fit_title(&title, out_area), false)),
+                out_area,
+            );
+        }
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        self.poll();
+        match key {
+            KeyCode::Up => self.select(self.selected.saturating_sub(1)),
+            KeyCode::Down => self.select(self.selected + 1),
+            KeyCode::Enter => self.run_step(self.selected),
+            KeyCode::PageUp => {
+                let step = self.last_viewport_h;
+                self.scroll_y = self.scroll_y.saturating_sub(step);
+            }
+            KeyCode::PageDown => {
+                let step = self.last_viewport_h;
+                self.scroll_y = self.scroll_y.saturating_add(step);
+            }
+            KeyCode::Home => self.scroll_y = 0,
+            // Clamped on render
+            KeyCode::End => self.scroll_y = u16::MAX,
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = Vec::new();
+        if !self.steps.is_empty() {
+            actions.push(WidgetAction::key("Run step", "Enter", KeyCode::Enter));
+        }
+        if self.source_path.is_some() {
+            actions.push(WidgetAction::refresh());
+        }
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        let Some(path) = self.source_path.clone() else {
+            return Vec::new();
+        };
+        if self.running() {
+            return vec![crate::app::Effect::ShowToast {
+                text: "A step is still running".into(),
+                level: crate::ui::ToastLevel::Info,
+                seconds: 2,
+            }];
+        }
+        // Rebuild from the file; step statuses start over
+        let fresh = Self::from_path(self.title.clone(), &path);
+        self.lines = fresh.lines;
+        self.steps = fresh.steps;
+        self.select(self.selected);
+        vec![crate::app::Effect::ShowToast {
+            text: format!("Reloaded {}", path.display()),
+            level: crate::ui::ToastLevel::Info,
+            seconds: 2,
+        }]
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Widget;
+
+    fn wait_idle(w: &mut RunbookWidget) {
+        for _ in 0..200 {
+            w.poll();
+            if !w.running() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        panic!("step did not finish");
+    }
+
+    #[test]
+    fn run_blocks_are_steps_with_status_and_output() {
+        let text = "# Restart\n\nCheck first:\n\n```run\necho checking\necho oops >&2\n```\n\n```bash\nls\n```\n\n```run sh\nexit 3\n```\n";
+        let mut w = RunbookWidget::from_text("Runbook", text);
+        assert_eq!(w.steps.len(), 2);
+        assert_eq!(w.steps[0].code, "echo checking\necho oops >&2");
+        assert_eq!(w.steps[1].code, "exit 3");
+
+        w.on_key(KeyCode::Enter);
+        assert_eq!(w.steps[0].status, StepStatus::Running);
+        wait_idle(&mut w);
+        assert_eq!(w.steps[0].status, StepStatus::Done);
+        assert_eq!(w.steps[0].output.len(), 2);
+        assert!(w.steps[0].output.contains(&"oops".to_string()));
+        // Success moved on to the next step
+        assert_eq!(w.selected, 1);
+
+        w.on_key(KeyCode::Enter);
+        wait_idle(&mut w);
+        assert_eq!(w.steps[1].status, StepStatus::Failed(Some(3)));
+        assert_eq!(w.selected, 1);
+
+        let backend = ratatui::backend::TestBackend::new(60, 30);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| w.render(f, f.area(), true, 0)).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(text.contains("1/2 steps done"));
+        assert!(text.contains("✓ Step 1/2"));
+        assert!(text.contains("✗ exit 3 Step 2/2"));
+        assert!(text.contains("Output — step 2 (failed, exit 3)"));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::services::clock::format_local;
+use crate::services::scheduler::{format_eta, Schedule};
+use crate::theme::Status;
+use crate::ui::{AppState, SchedulePrompt};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use std::time::SystemTime;
+
+// Scheduled screen ('T'): pending jobs on top, recent runs below
+pub fn draw_scheduled(f: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let now = SystemTime::now();
+    let sched = &state.scheduler;
+    let mut lines: Vec<Line> = vec![Line::from(Span::styled(
+        format!("Pending ({})", sched.jobs.len()),
+        theme.title_style(),
+    ))];
+    if sched.jobs.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  nothing scheduled — press t on a menu item",
+            theme.text_muted(),
+        )));
+    }
+    for (i, job) in sched.jobs.iter().enumerate() {
+        let sel = i == state.scheduled_selected;
+        let kind = match job.schedule {
+            Schedule::Once(_) => "once",
+            Schedule::Cron(_) => "cron",
+        };
+        let row = format!(
+            "{} {}  {:<9} {}  [{kind}: {}]",
+            if sel { "▶" } else { " " },
+            format_local(job.next_run),
+            format_eta(job.next_run, now),
+            job.title,
+            job.spec
+        );
+        let style = if sel {
+            theme.list_cursor_style()
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(row, style)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Recent runs", theme.title_style())));
+    if sched.history.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  no runs yet",
+            theme.text_muted(),
+        )));
+    }
+    for run in &sched.history {
+        let status = if run.ok { Status::Ok } else { Status::Fail };
+        let (mark, style) = (theme.status_glyph(status), theme.status_style(status));
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {mark} "), style),
+            Span::raw(format!("{}  {}  ", format_local(run.at), run.title)),
+            Span::styled(run.summary.clone(), theme.text_muted()),
+        ]));
+    }
+    let block = crate::widgets::chrome::panel_block("Scheduled", true);
+    let p = Paragraph::new(lines).block(block);
+    f.render_widget(p, area);
+}
+
+// Modal input for the schedule of the selected item ('t')
+pub fn draw_prompt(f: &mut Frame, screen: Rect, prompt: &SchedulePrompt) {
+    let theme = crate::theme::active();
+    let width = screen.width.saturating_sub(4).min(64);
+    let rect = Rect {
+        x: screen.x + (screen.width.saturating_sub(width)) / 2,
+        y: screen.y + screen.height.saturating_sub(7) / 2,
+        width,
+        height: 7.min(screen.height),
+    };
+    let mut lines = vec![
+        Line::from(Span::raw(format!("$ {}", prompt.cmdline))),
+        Line::from(vec![
+            Span::styled("When: ", theme.text_active_bold()),
+            Span::raw(format!("{}▏", prompt.input)),
+        ]),
+    ];
+    match &prompt.error {
+        Some(e) => lines.push(Line::from(Span::styled(e.clone(), theme.text_error()))),
+        None => lines.push(Line::from(Span::styled(
+            "10m • in 2h • at 14:30 • */15 * * * *",
+            theme.text_muted(),
+        ))),
+    }
+    lines.push(Line::from(Span::styled(
+        "Enter schedule • Esc cancel",
+        theme.text_muted(),
+    )));
+    let title = format!("Schedule: {}", prompt.title);
+    let p = Paragraph::new(lines)
+        .block(crate::widgets::chrome::panel_block(&title, true))
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
@@ -0,0 +1,288 @@
+use ratatui::prelude::*;
+use serde_json::Value as JsonValue;
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+// Earlier results of a viewer refreshed in place: each value it showed before a refresh
+// changed it, with the time it arrived, so `[`/`]` can step back through them and `D` can
+// diff one against the current result. Only the last `CHI_TUI_RESULT_SNAPSHOTS` (default 20,
+// 0 disables) are kept.
+
+const DEFAULT_KEEP: usize = 20;
+
+pub fn keep_from_env() -> usize {
+    std::env::var("CHI_TUI_RESULT_SNAPSHOTS")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_KEEP)
+}
+
+pub struct Snapshots {
+    keep: usize,
+    // Oldest first: (when the value arrived, value)
+    past: VecDeque<(SystemTime, JsonValue)>,
+    // When the current value arrived
+    current_at: SystemTime,
+    // Snapshot shown instead of the current value (index into `past`)
+    viewing: Option<usize>,
+}
+
+impl Snapshots {
+    pub fn new(keep: usize) -> Self {
+        Self {
+            keep,
+            past: VecDeque::new(),
+            current_at: SystemTime::now(),
+            viewing: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.past.is_empty()
+    }
+
+    pub fn viewing(&self) -> bool {
+        self.viewing.is_some()
+    }
+
+    // A refresh replaced `old` (shown since the last one) with a different value at `now`
+    pub fn push(&mut self, old: JsonValue, now: SystemTime) {
+        self.viewing = None;
+        if self.keep == 0 {
+            return;
+        }
+        self.past.push_back((self.current_at, old));
+        while self.past.len() > self.keep {
+            self.past.pop_front();
+        }
+        self.current_at = now;
+    }
+
+    // Like push while a snapshot is viewed: it stays the one viewed, and snapshots beyond
+    // `keep` are dropped but never the viewed one (older ones first, then the newest)
+    pub fn push_behind(&mut self, old: JsonValue, now: SystemTime) {
+        let Some(mut i) = self.viewing else {
+            return self.push(old, now);
+        };
+        self.past.push_back((self.current_at, old));
+        while self.past.len() > self.keep.max(1) {
+            if i > 0 {
+                self.past.pop_front();
+                i -= 1;
+            } else {
+                self.past.pop_back();
+            }
+        }
+        self.viewing = Some(i);
+        self.current_at = now;
+    }
+
+    // One snapshot older; None at the oldest (or without snapshots)
+    pub fn back(&mut self) -> Option<&JsonValue> {
+        let i = match self.viewing {
+            Some(0) => return None,
+            Some(i) => i - 1,
+            None => self.past.len().checked_sub(1)?,
+        };
+        self.viewing = Some(i);
+        self.past.get(i).map(|(_, v)| v)
+    }
+
+    // One snapshot newer: Some(None) when that is the current value again
+    pub fn forward(&mut self) -> Option<Option<&JsonValue>> {
+        let i = self.viewing?;
+        if i + 1 >= self.past.len() {
+            self.viewing = None;
+            return Some(None);
+        }
+        self.viewing = Some(i + 1);
+        Some(self.past.get(i + 1).map(|(_, v)| v))
+    }
+
+    // Value a diff starts from: the snapshot being viewed, or the latest one
+    pub fn base(&self) -> Option<&(SystemTime, JsonValue)> {
+        match self.viewing {
+            Some(i) => self.past.get(i),
+            None => self.past.back(),
+        }
+    }
+
+    // "snapshot 3/5 • 14:02 (2m ago)" while viewing one
+    pub fn label(&self, now: SystemTime) -> Option<String> {
+        let i = self.viewing?;
+        let (at, _) = self.past.get(i)?;
+        Some(format!(
+            "snapshot {}/{} • {}",
+            i + 1,
+            self.past.len(),
+            when(*at, now)
+        ))
+    }
+
+    // "diff 14:02 (2m ago) → current" for the title of the diff view
+    pub fn diff_label(&self, now: SystemTime) -> Option<String> {
+        let (at, _) = self.base()?;
+        let to = match self.viewing {
+            Some(_) => format!("current {}", when(self.current_at, now)),
+            None => "current".to_string(),
+        };
+        Some(format!("diff {} → {to}", when(*at, now)))
+    }
+}
+
+fn when(at: SystemTime, now: SystemTime) -> String {
+    use crate::services::clock::{format_local, unix_secs};
+    let local = format_local(at);
+    let time = local.rsplit(' ').next().unwrap_or(&local).to_string();
+    let ago = crate::widgets::cells::relative_time(unix_secs(at), unix_secs(now));
+    format!("{time} ({ago})")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    Added(String, JsonValue),
+    Removed(String, JsonValue),
+    Changed(String, JsonValue, JsonValue),
+}
+
+// Leaf-level differences between two values, by path (`.items[2].status`); arrays are
+// compared by position
+pub fn diff(old: &JsonValue, new: &JsonValue) -> Vec<Change> {
+    fn walk(path: &mut String, old: &JsonValue, new: &JsonValue, out: &mut Vec<Change>) {
+        let len = path.len();
+        match (old, new) {
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+                keys.sort();
+                keys.dedup();
+                for k in keys {
+                    path.push('.');
+                    path.push_str(k);
+                    match (a.get(k), b.get(k)) {
+                        (Some(x), Some(y)) => walk(path, x, y, out),
+                        (Some(x), None) => out.push(Change::Removed(path.clone(), x.clone())),
+                        (None, Some(y)) => out.push(Change::Added(path.clone(), y.clone())),
+                        (None, None) => {}
+                    }
+                    path.truncate(len);
+                }
+            }
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                for i in 0..a.len().max(b.len()) {
+                    path.push_str(&format!("[{i}]"));
+                    match (a.get(i), b.get(i)) {
+                        (Some(x), Some(y)) => walk(path, x, y, out),
+                        (Some(x), None) => out.push(Change::Removed(path.clone(), x.clone())),
+                        (None, Some(y)) => out.push(Change::Added(path.clone(), y.clone())),
+                        (None, None) => {}
+                    }
+                    path.truncate(len);
+                }
+            }
+            (a, b) if a != b => {
+                let at = if path.is_empty() { "." } else { path };
+                out.push(Change::Changed(at.to_string(), a.clone(), b.clone()));
+            }
+            _ => {}
+        }
+    }
+    let mut out = Vec::new();
+    walk(&mut String::new(), old, new, &mut out);
+    out
+}
+
+// Diff as lines for the viewer: `~ path old → new`, `+ path value`, `- path value`
+pub fn diff_lines(old: &JsonValue, new: &JsonValue) -> Vec<Line<'static>> {
+    let theme = crate::theme::active();
+    let changes = diff(old, new);
+    if changes.is_empty() {
+        return vec![Line::from(Span::styled(
+            "No differences",
+            theme.text_muted(),
+        ))];
+    }
+    changes
+        .into_iter()
+        .map(|c| match c {
+            Change::Changed(path, a, b) => Line::from(vec![
+                Span::styled(format!("~ {path}  "), theme.text_active_bold()),
+                Span::styled(a.to_string(), theme.text_error()),
+                Span::raw(" → "),
+                Span::styled(b.to_string(), theme.text_success()),
+            ]),
+            Change::Added(path, v) => {
+                Line::from(Span::styled(format!("+ {path}  {v}"), theme.text_success()))
+            }
+            Change::Removed(path, v) => {
+                Line::from(Span::styled(format!("- {path}  {v}"), theme.text_error()))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn steps_through_kept_snapshots() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut s = Snapshots::new(2);
+        assert!(s.back().is_none());
+        for i in 0..3 {
+            s.push(json!(i), t0 + Duration::from_secs(60 * (i + 1)));
+        }
+        // Only the last two are kept (values 1 and 2; 3 is current)
+        assert_eq!(s.back(), Some(&json!(2)));
+        assert_eq!(s.back(), Some(&json!(1)));
+        assert_eq!(s.back(), None);
+        let now = t0 + Duration::from_secs(240);
+        assert!(s.label(now).unwrap().starts_with("snapshot 1/2 • "));
+        assert!(s.label(now).unwrap().ends_with("(3m ago)"));
+        assert_eq!(s.forward(), Some(Some(&json!(2))));
+        assert_eq!(s.forward(), Some(None));
+        assert!(!s.viewing());
+        assert_eq!(s.forward(), None);
+        assert_eq!(s.base().map(|(_, v)| v), Some(&json!(2)));
+    }
+
+    #[test]
+    fn refreshes_while_pinned_on_the_oldest_keep_the_cap() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut s = Snapshots::new(3);
+        for i in 0..3 {
+            s.push(json!(i), t0 + Duration::from_secs(60 * (i + 1)));
+        }
+        while s.back().is_some() {}
+        assert_eq!(s.base().map(|(_, v)| v), Some(&json!(0)));
+        for i in 3..50 {
+            s.push_behind(json!(i), t0 + Duration::from_secs(60 * (i + 1)));
+        }
+        assert_eq!(s.past.len(), 3);
+        // The viewed snapshot stays put
+        assert_eq!(s.base().map(|(_, v)| v), Some(&json!(0)));
+        assert_eq!(s.forward(), Some(Some(&json!(1))));
+    }
+
+    #[test]
+    fn diffs_by_path() {
+        let old = json!({"data": {"replicas": 3, "pods": ["a", "b"], "gone": true}});
+        let new = json!({"data": {"replicas": 5, "pods": ["a"], "new": "x"}});
+        assert_eq!(
+            diff(&old, &new),
+            [
+                Change::Removed(".data.gone".into(), json!(true)),
+                Change::Added(".data.new".into(), json!("x")),
+                Change::Removed(".data.pods[1]".into(), json!("b")),
+                Change::Changed(".data.replicas".into(), json!(3), json!(5)),
+            ]
+        );
+        assert_eq!(
+            diff(&json!(1), &json!(2)),
+            [Change::Changed(".".into(), json!(1), json!(2))]
+        );
+        assert!(diff(&old, &old).is_empty());
+    }
+}
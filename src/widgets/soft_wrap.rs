@@ -0,0 +1,302 @@
+use crate::app::Effect;
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use unicode_width::UnicodeWidthChar;
+
+// Soft wrapping for the raw-text viewers (the JSON text viewer and the result viewer's raw
+// JSON): with wrap on, long lines are cut into visual rows at the pane width, while a line
+// selection ('V') and its copy work on the logical lines, so copied text never gains a break
+// the source did not have. gj/gk move by one visual row, Up/Down (j/k) in a selection by line.
+
+// One visual row: a byte range of logical line `line`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VisualRow {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+// Visual rows of `lines` wrapped at `width` cells (0: one row per line)
+pub fn visual_rows(lines: &[&str], width: usize) -> Vec<VisualRow> {
+    let mut rows = Vec::new();
+    for (line, text) in lines.iter().enumerate() {
+        let mut start = 0;
+        let mut used = 0;
+        for (i, c) in text.char_indices() {
+            let w = c.width().unwrap_or(0);
+            if width > 0 && used + w > width && i > start {
+                rows.push(VisualRow {
+                    line,
+                    start,
+                    end: i,
+                });
+                start = i;
+                used = 0;
+            }
+            used += w;
+        }
+        rows.push(VisualRow {
+            line,
+            start,
+            end: text.len(),
+        });
+    }
+    rows
+}
+
+// The bytes `start..end` of a styled line whose spans spell out the logical line
+fn slice_line(line: &Line<'static>, start: usize, end: usize) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut at = 0;
+    for span in &line.spans {
+        let (from, to) = (at, at + span.content.len());
+        at = to;
+        let (a, b) = (start.max(from), end.min(to));
+        if a < b {
+            spans.push(Span::styled(
+                span.content[a - from..b - from].to_string(),
+                span.style,
+            ));
+        }
+    }
+    Line::from(spans).style(line.style)
+}
+
+// What a key did to the wrapped text
+pub enum WrapKey {
+    // Not a wrap or selection key
+    Ignored,
+    Handled,
+    // gj / gk outside a selection: scroll by this many visual rows
+    Scroll(i32),
+    // 'y' / Enter in a selection: the selected lines, as written
+    Copy(String),
+}
+
+// Visual rows of the last layout plus the line selection over them
+#[derive(Default)]
+pub struct SoftWrap {
+    rows: Vec<VisualRow>,
+    // Cursor row and the logical line the selection started on
+    selection: Option<(usize, usize)>,
+    // 'g' typed, waiting for j/k
+    pending_g: bool,
+}
+
+impl SoftWrap {
+    // Re-cut the text for a pane `width` cells wide (0: wrap off)
+    pub fn layout(&mut self, lines: &[&str], width: usize) {
+        self.rows = visual_rows(lines, width);
+        if let Some((cursor, _)) = &mut self.selection {
+            *cursor = (*cursor).min(self.rows.len().saturating_sub(1));
+        }
+    }
+
+    pub fn rows(&self) -> &[VisualRow] {
+        &self.rows
+    }
+
+    pub fn selecting(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    // Visual row under the selection cursor
+    pub fn cursor(&self) -> Option<usize> {
+        self.selection.map(|(cursor, _)| cursor)
+    }
+
+    // Logical lines selected, first to last
+    pub fn selected_lines(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        let (cursor, anchor) = self.selection?;
+        let here = self.rows.get(cursor).map_or(anchor, |r| r.line);
+        Some(anchor.min(here)..=anchor.max(here))
+    }
+
+    // The selected logical lines joined by newlines
+    pub fn selected_text(&self, lines: &[&str]) -> Option<String> {
+        let range = self.selected_lines()?;
+        let end = (*range.end()).min(lines.len().saturating_sub(1));
+        Some(
+            lines
+                .get(*range.start()..=end)
+                .unwrap_or_default()
+                .join("\n"),
+        )
+    }
+
+    // Styled logical lines cut into the visual rows, selected lines highlighted
+    pub fn wrap(&self, lines: &[Line<'static>], selected: Style) -> Vec<Line<'static>> {
+        let range = self.selected_lines();
+        self.rows
+            .iter()
+            .map(|r| {
+                let line = match lines.get(r.line) {
+                    Some(l) => slice_line(l, r.start, r.end),
+                    None => Line::default(),
+                };
+                if range.as_ref().is_some_and(|s| s.contains(&r.line)) {
+                    line.style(selected)
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    // First visual row of the logical line before / after the cursor's
+    fn step_line(&self, cursor: usize, down: bool) -> usize {
+        let Some(line) = self.rows.get(cursor).map(|r| r.line) else {
+            return cursor;
+        };
+        let target = if down {
+            self.rows.iter().position(|r| r.line > line)
+        } else {
+            line.checked_sub(1)
+                .and_then(|prev| self.rows.iter().position(|r| r.line == prev))
+        };
+        target.unwrap_or(cursor)
+    }
+
+    // `top` is the first visual row in view (where 'V' starts), `page` the viewport height
+    pub fn on_key(&mut self, key: KeyCode, lines: &[&str], top: usize, page: usize) -> WrapKey {
+        let g = std::mem::take(&mut self.pending_g);
+        let Some((cursor, anchor)) = self.selection else {
+            return match key {
+                KeyCode::Char('j') if g => WrapKey::Scroll(1),
+                KeyCode::Char('k') if g => WrapKey::Scroll(-1),
+                KeyCode::Char('g') => {
+                    self.pending_g = true;
+                    WrapKey::Handled
+                }
+                KeyCode::Char('V') if !self.rows.is_empty() => {
+                    let cursor = top.min(self.rows.len() - 1);
+                    self.selection = Some((cursor, self.rows[cursor].line));
+                    WrapKey::Handled
+                }
+                _ => WrapKey::Ignored,
+            };
+        };
+        let last = self.rows.len().saturating_sub(1);
+        let page = page.max(1);
+        let cursor = match key {
+            KeyCode::Char('j') if g => (cursor + 1).min(last),
+            KeyCode::Char('k') if g => cursor.saturating_sub(1),
+            KeyCode::Char('g') => {
+                self.pending_g = true;
+                cursor
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.step_line(cursor, true),
+            KeyCode::Up | KeyCode::Char('k') => self.step_line(cursor, false),
+            KeyCode::PageDown => (cursor + page).min(last),
+            KeyCode::PageUp => cursor.saturating_sub(page),
+            KeyCode::Home => 0,
+            KeyCode::End | KeyCode::Char('G') => last,
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                let text = self.selected_text(lines).unwrap_or_default();
+                self.selection = None;
+                return WrapKey::Copy(text);
+            }
+            KeyCode::Esc | KeyCode::Char('V') => {
+                self.selection = None;
+                return WrapKey::Handled;
+            }
+            _ => cursor,
+        };
+        self.selection = Some((cursor, anchor));
+        WrapKey::Handled
+    }
+
+    // Scroll offset that keeps the selection cursor in a viewport of `height` rows
+    pub fn follow(&self, scroll: u16, height: u16) -> u16 {
+        let Some(cursor) = self.cursor().map(|c| c as u16) else {
+            return scroll;
+        };
+        if cursor < scroll {
+            cursor
+        } else if height > 0 && cursor >= scroll + height {
+            cursor + 1 - height
+        } else {
+            scroll
+        }
+    }
+}
+
+// Copy the selected lines with a toast saying how many
+pub fn copy_lines(text: String) -> Vec<Effect> {
+    let n = text.lines().count().max(1);
+    let what = if n == 1 {
+        "1 line".to_string()
+    } else {
+        format!("{n} lines")
+    };
+    copy_effects(text, &what)
+}
+
+// `text` to the clipboard with a toast naming `what` was copied
+pub fn copy_effects(text: String, what: &str) -> Vec<Effect> {
+    use crate::ui::ToastLevel;
+    let (text, level) = match arboard::Clipboard::new().and_then(|mut c| c.set_text(text)) {
+        Ok(()) => (format!("Copied {what}"), ToastLevel::Success),
+        Err(e) => (format!("Copy failed: {e}"), ToastLevel::Error),
+    };
+    vec![Effect::ShowToast {
+        text,
+        level,
+        seconds: 2,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selections_copy_logical_lines_and_gj_gk_move_by_visual_row() {
+        let lines = ["short", "0123456789abcdef", "日本語テキスト", "end"];
+        let mut wrap = SoftWrap::default();
+        wrap.layout(&lines, 6);
+        // The long line takes three rows, the CJK line (14 cells) three more
+        assert_eq!(wrap.rows().len(), 8);
+        assert_eq!(
+            wrap.rows()[2],
+            VisualRow {
+                line: 1,
+                start: 6,
+                end: 12
+            }
+        );
+        assert_eq!(wrap.rows()[4].end - wrap.rows()[4].start, "日本語".len());
+        let styled: Vec<Line<'static>> = lines.iter().map(|l| Line::from(l.to_string())).collect();
+        assert_eq!(wrap.wrap(&styled, Style::default())[3].to_string(), "cdef");
+
+        // Outside a selection gj/gk scroll
+        wrap.on_key(KeyCode::Char('g'), &lines, 0, 3);
+        assert!(matches!(
+            wrap.on_key(KeyCode::Char('j'), &lines, 0, 3),
+            WrapKey::Scroll(1)
+        ));
+        assert!(matches!(
+            wrap.on_key(KeyCode::Char('j'), &lines, 0, 3),
+            WrapKey::Ignored
+        ));
+
+        wrap.on_key(KeyCode::Char('V'), &lines, 1, 3);
+        wrap.on_key(KeyCode::Down, &lines, 1, 3);
+        assert_eq!(wrap.cursor(), Some(4)); // first row of the CJK line
+        wrap.on_key(KeyCode::Char('g'), &lines, 1, 3);
+        wrap.on_key(KeyCode::Char('k'), &lines, 1, 3);
+        assert_eq!(wrap.cursor(), Some(3)); // last row of the long line
+        assert_eq!(wrap.selected_lines(), Some(1..=1));
+        assert_eq!(wrap.follow(0, 3), 1);
+        for key in ['g', 'j', 'g', 'j'] {
+            wrap.on_key(KeyCode::Char(key), &lines, 1, 3);
+        }
+        assert_eq!(wrap.selected_lines(), Some(1..=2));
+        // Whole source lines, without the wrap breaks
+        match wrap.on_key(KeyCode::Char('y'), &lines, 1, 3) {
+            WrapKey::Copy(text) => assert_eq!(text, "0123456789abcdef\n日本語テキスト"),
+            _ => panic!("y should copy"),
+        }
+        assert!(!wrap.selecting());
+    }
+}
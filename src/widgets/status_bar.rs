@@ -3,26 +3,31 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::*;
 
 use crate::ui::AppState;
+use crate::widgets::text_width::{display_width, fit_hints, truncate_spans};
 
 pub fn draw_status(f: &mut Frame, area: Rect, state: &AppState) {
     let mut spans: Vec<Span> = Vec::new();
     if let Some(msg) = &state.status_text {
-        let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.tick as usize % 6];
+        let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.frame() as usize % 6];
         spans.push(Span::raw(format!(" {spinner} {msg}")));
         if let Some(p) = state.status_percent {
             spans.push(Span::raw(format!(" — {p:>5.1}%")));
         }
+        if state.stream_handle.is_some() {
+            spans.push(Span::raw(" (Esc/Ctrl+X cancel)"));
+        }
     }
     if let Some(t) = &state.toast {
         if !spans.is_empty() {
             spans.push(Span::raw("  |  "));
         }
-        let color = crate::theme::toast_color(t.level);
+        let color = state.theme.toast_color(t.level);
         let tag = match t.level {
-            crate::ui::ToastLevel::Success => "[OK]",
-            crate::ui::ToastLevel::Error => "[ERROR]",
-            crate::ui::ToastLevel::Info => "[INFO]",
+            crate::ui::ToastLevel::Success => "OK",
+            crate::ui::ToastLevel::Error => "ERROR",
+            crate::ui::ToastLevel::Info => "INFO",
         };
+        let tag = format!("[{} {tag}]", state.theme.status_glyph(t.level.status()));
         spans.push(Span::styled(
             format!("{tag} "),
             Style::default().fg(color).add_modifier(Modifier::BOLD),
@@ -56,10 +61,7 @@ pub fn draw_status(f: &mut Frame, area: Rect, state: &AppState) {
         // Editing indicator (form editing in Pane B)
         if let Some(ps) = &state.panel {
             if let crate::ui::PaneContent::Widget(w) = &ps.b_content {
-                if let Some(fw) = w
-                    .as_any()
-                    .downcast_ref::<crate::widgets::form_widget::FormWidget>()
-                {
+                if let Some(fw) = crate::widgets::wizard::current_form(w.as_any()) {
                     if fw.form.editing {
                         spans.push(Span::raw("  |  editing"));
                     }
@@ -67,6 +69,7 @@ pub fn draw_status(f: &mut Frame, area: Rect, state: &AppState) {
             }
         }
     }
+    let spans = truncate_spans(spans, area.width as usize);
     let p = Paragraph::new(Line::from(spans)).style(Style::default().fg(Color::Magenta));
     f.render_widget(p, area);
 }
@@ -74,20 +77,24 @@ pub fn draw_status(f: &mut Frame, area: Rect, state: &AppState) {
 pub fn draw_footer_combined(f: &mut Frame, area: Rect, state: &AppState, help_text: &str) {
     let mut spans: Vec<Span> = Vec::new();
     if let Some(msg) = &state.status_text {
-        let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.tick as usize % 6];
+        let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][state.frame() as usize % 6];
         spans.push(Span::raw(format!(" {spinner} {msg}")));
         if let Some(p) = state.status_percent {
             spans.push(Span::raw(format!(" — {p:>5.1}%")));
         }
+        if state.stream_handle.is_some() {
+            spans.push(Span::raw(" (Esc/Ctrl+X cancel)"));
+        }
         spans.push(Span::raw("  |  "));
     }
     if let Some(t) = &state.toast {
-        let color = crate::theme::toast_color(t.level);
+        let color = state.theme.toast_color(t.level);
         let tag = match t.level {
-            crate::ui::ToastLevel::Success => "[OK]",
-            crate::ui::ToastLevel::Error => "[ERROR]",
-            crate::ui::ToastLevel::Info => "[INFO]",
+            crate::ui::ToastLevel::Success => "OK",
+            crate::ui::ToastLevel::Error => "ERROR",
+            crate::ui::ToastLevel::Info => "INFO",
         };
+        let tag = format!("[{} {tag}]", state.theme.status_glyph(t.level.status()));
         spans.push(Span::styled(
             format!("{tag} "),
             Style::default().fg(color).add_modifier(Modifier::BOLD),
@@ -121,10 +128,7 @@ pub fn draw_footer_combined(f: &mut Frame, area: Rect, state: &AppState, help_te
         // Editing indicator
         if let Some(ps) = &state.panel {
             if let crate::ui::PaneContent::Widget(w) = &ps.b_content {
-                if let Some(fw) = w
-                    .as_any()
-                    .downcast_ref::<crate::widgets::form_widget::FormWidget>()
-                {
+                if let Some(fw) = crate::widgets::wizard::current_form(w.as_any()) {
                     if fw.form.editing {
                         spans.push(Span::raw("  |  editing"));
                     }
@@ -133,10 +137,12 @@ pub fn draw_footer_combined(f: &mut Frame, area: Rect, state: &AppState, help_te
         }
         spans.push(Span::raw("  |  "));
     }
+    // Hints take what the status leaves, dropped whole from the end
+    let used: usize = spans.iter().map(|s| display_width(&s.content)).sum();
     spans.push(Span::styled(
-        help_text.to_string(),
+        fit_hints(help_text, (area.width as usize).saturating_sub(used)),
         Style::default().fg(Color::DarkGray),
     ));
-    let p = Paragraph::new(Line::from(spans));
+    let p = Paragraph::new(Line::from(truncate_spans(spans, area.width as usize)));
     f.render_widget(p, area);
 }
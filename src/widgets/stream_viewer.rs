@@ -0,0 +1,271 @@
+use crate::app::Effect;
+use crate::theme::Status;
+use crate::widgets::cells::gauge_spans;
+use crate::widgets::chrome::{fit_title, panel_block};
+use crate::widgets::{Widget, WidgetAction};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::any::Any;
+use std::time::{Duration, Instant};
+
+// A streamed command (`stream: true`, or a command run while a panel is open) shown live in
+// Pane B: one row per progress envelope with the time it arrived, a percent gauge and the
+// elapsed time. The finished result replaces the viewer; a failure stays on it with the log.
+
+// Progress rows kept per run
+const MAX_ROWS: usize = 1000;
+
+pub struct StreamViewerWidget {
+    title: String,
+    cmdline: String,
+    // (elapsed when it arrived, text) per progress envelope, oldest first
+    rows: Vec<(Duration, String)>,
+    percent: Option<f64>,
+    started: Instant,
+    // Elapsed time once the stream ended
+    finished: Option<Duration>,
+    error: Option<String>,
+    cancelled: bool,
+    follow: bool,
+    top: usize,
+    viewport: usize,
+}
+
+// "42s", "3m 05s", "1h 02m"
+fn fmt_elapsed(d: Duration) -> String {
+    let s = d.as_secs();
+    match s {
+        0..=59 => format!("{s}s"),
+        60..=3599 => format!("{}m {:02}s", s / 60, s % 60),
+        _ => format!("{}h {:02}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+impl StreamViewerWidget {
+    pub fn new(title: &str, cmdline: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            cmdline: cmdline.to_string(),
+            rows: Vec::new(),
+            percent: None,
+            started: Instant::now(),
+            finished: None,
+            error: None,
+            cancelled: false,
+            follow: true,
+            top: 0,
+            viewport: 0,
+        }
+    }
+
+    pub fn running(&self) -> bool {
+        self.finished.is_none()
+    }
+
+    pub fn progress(&mut self, text: Option<String>, percent: Option<f64>) {
+        if percent.is_some() {
+            self.percent = percent;
+        }
+        let Some(text) = text else {
+            return;
+        };
+        // Repeated messages only move the gauge
+        if self.rows.last().is_some_and(|(_, t)| *t == text) {
+            return;
+        }
+        self.rows.push((self.started.elapsed(), text));
+        if self.rows.len() > MAX_ROWS {
+            self.rows.drain(..self.rows.len() - MAX_ROWS);
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        self.finished = Some(self.started.elapsed());
+        self.cancelled = true;
+    }
+
+    pub fn fail(&mut self, err: String) {
+        self.finished = Some(self.started.elapsed());
+        self.error = Some(err);
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.finished.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        self.follow = false;
+        self.top = self.top.saturating_add_signed(delta);
+    }
+}
+
+impl Widget for StreamViewerWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
+        let theme = crate::theme::active();
+        let state = match (&self.error, self.running()) {
+            _ if self.cancelled => "cancelled".to_string(),
+            (Some(_), _) => "failed".to_string(),
+            (None, true) => {
+                let spinner = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][tick as usize % 6];
+                format!("{spinner} running")
+            }
+            (None, false) => "done".to_string(),
+        };
+        let title = format!("{} — {state} {}", self.title, fmt_elapsed(self.elapsed()));
+        let title = fit_title(&title, area);
+        let block = panel_block(&title, focused);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let [gauge, body, foot] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        // Percent gauge (the command line until the stream reports a percent)
+        let mut spans = Vec::new();
+        match self.percent {
+            Some(p) => {
+                let p = p.clamp(0.0, 100.0);
+                let label = format!(" {p:>5.1}%");
+                let width = (gauge.width as usize).saturating_sub(label.len());
+                let status = if self.error.is_some() {
+                    Status::Fail
+                } else {
+                    Status::Ok
+                };
+                spans.extend(gauge_spans(
+                    &[((p * 10.0) as usize, status)],
+                    1000,
+                    width,
+                    &theme,
+                ));
+                spans.push(Span::styled(label, theme.text_active_bold()));
+            }
+            None => spans.push(Span::styled(
+                format!("$ {}", self.cmdline),
+                theme.text_muted(),
+            )),
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), gauge);
+
+        self.viewport = body.height as usize;
+        let mut lines: Vec<Line> = self
+            .rows
+            .iter()
+            .map(|(at, text)| {
+                Line::from(vec![
+                    Span::styled(format!("{:>7} ", fmt_elapsed(*at)), theme.text_muted()),
+                    Span::raw(text.clone()),
+                ])
+            })
+            .collect();
+        if let Some(e) = &self.error {
+            lines.push(theme.error_line(e));
+        }
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Waiting for progress…",
+                theme.text_muted(),
+            )));
+        }
+        let max_top = lines.len().saturating_sub(self.viewport);
+        self.top = if self.follow {
+            max_top
+        } else {
+            self.top.min(max_top)
+        };
+        f.render_widget(Paragraph::new(lines).scroll((self.top as u16, 0)), body);
+        let hint = if self.running() {
+            "↑/↓ PgUp/PgDn Home/End scroll • Esc/Ctrl+X cancel"
+        } else {
+            "↑/↓ PgUp/PgDn Home/End scroll • r run again"
+        };
+        f.render_widget(Paragraph::new(Span::styled(hint, theme.text_muted())), foot);
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<Effect> {
+        let page = self.viewport.max(1) as isize;
+        match key {
+            KeyCode::Up => self.scroll_by(-1),
+            KeyCode::Down => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::Home => {
+                self.follow = false;
+                self.top = 0;
+            }
+            KeyCode::End => self.follow = true,
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    // A finished (or failed) stream runs again; a running one is left alone
+    fn refresh(&mut self) -> Vec<Effect> {
+        if self.running() {
+            return Vec::new();
+        }
+        vec![Effect::RunStream {
+            cmdline: self.cmdline.clone(),
+            title: self.title.clone(),
+        }]
+    }
+
+    fn actions(&self) -> Vec<WidgetAction> {
+        let mut out = vec![WidgetAction::key("Follow progress", "End", KeyCode::End)];
+        if !self.running() {
+            out.push(WidgetAction::refresh());
+        }
+        out
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_text(w: &mut dyn Widget) -> String {
+        let backend = ratatui::backend::TestBackend::new(60, 8);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| w.render(f, f.area(), true, 0)).unwrap();
+        let buf = terminal.backend().buffer().clone();
+        buf.content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn progress_rows_and_gauge_follow_the_stream() {
+        let mut w = StreamViewerWidget::new("Deploy", "app deploy --env prod");
+        assert!(render_text(&mut w).contains("Waiting for progress"));
+        w.progress(Some("build — compiling".into()), Some(25.0));
+        w.progress(Some("build — compiling".into()), Some(40.0));
+        w.progress(None, Some(50.0));
+        w.progress(Some("push — uploading".into()), None);
+        assert_eq!(w.rows.len(), 2);
+        let text = render_text(&mut w);
+        assert!(text.contains("Deploy — ⠋ running 0s"), "{text}");
+        assert!(text.contains("50.0%"), "{text}");
+        assert!(text.contains("push — uploading"), "{text}");
+        // No re-run while it is still going
+        assert!(w.refresh().is_empty());
+
+        w.fail("exit code 2".into());
+        let text = render_text(&mut w);
+        assert!(text.contains("Deploy — failed"), "{text}");
+        assert!(text.contains("exit code 2"), "{text}");
+        assert!(matches!(
+            w.refresh().as_slice(),
+            [Effect::RunStream { cmdline, .. }] if cmdline == "app deploy --env prod"
+        ));
+        assert_eq!(fmt_elapsed(Duration::from_secs(185)), "3m 05s");
+    }
+}
@@ -0,0 +1,197 @@
+use crate::theme::Theme;
+use ratatui::prelude::*;
+
+// Content syntax coloring with colors taken from the Theme, so a custom theme
+// restyles JSON and markdown the same way it restyles the chrome.
+
+// One line of pretty-printed JSON: keys, strings, numbers, booleans and null
+pub fn json_line(line: &str, theme: &Theme) -> Line<'static> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            spans.push(Span::raw(std::mem::take(plain)));
+        }
+    };
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            let token: String = chars[start..i].iter().collect();
+            // A string followed by ':' is an object key
+            let is_key = chars[i..]
+                .iter()
+                .find(|c| !c.is_whitespace())
+                .is_some_and(|c| *c == ':');
+            let style = if is_key {
+                theme.json_key()
+            } else {
+                theme.json_string()
+            };
+            flush(&mut plain, &mut spans);
+            spans.push(Span::styled(token, style));
+        } else if c == '-' || c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit() || matches!(chars[i], '-' | '+' | '.' | 'e' | 'E'))
+            {
+                i += 1;
+            }
+            flush(&mut plain, &mut spans);
+            spans.push(Span::styled(
+                chars[start..i].iter().collect::<String>(),
+                theme.json_number(),
+            ));
+        } else if let Some((word, style)) = [
+            ("true", theme.json_bool()),
+            ("false", theme.json_bool()),
+            ("null", theme.text_muted()),
+        ]
+        .into_iter()
+        .find(|(w, _)| line_has_word(&chars, i, w))
+        {
+            flush(&mut plain, &mut spans);
+            spans.push(Span::styled(word.to_string(), style));
+            i += word.len();
+        } else {
+            plain.push(c);
+            i += 1;
+        }
+    }
+    flush(&mut plain, &mut spans);
+    Line::from(spans)
+}
+
+fn line_has_word(chars: &[char], at: usize, word: &str) -> bool {
+    let w: Vec<char> = word.chars().collect();
+    chars.len() >= at + w.len()
+        && chars[at..at + w.len()] == w[..]
+        && chars.get(at + w.len()).is_none_or(|c| !c.is_alphanumeric())
+}
+
+// Inline markdown: `code`, **strong**, *emphasis* / _emphasis_, [text](url)
+pub fn markdown_inline(text: &str, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    let mut prev: Option<char> = None;
+    while let Some(c) = rest.chars().next() {
+        // Intraword `_` (snake_case) is not emphasis
+        let word_inside = prev.is_some_and(|p| p.is_alphanumeric());
+        let styled = match c {
+            '`' => delimited(rest, "`", "`").map(|(inner, n)| (inner, n, theme.md_code())),
+            '*' if rest.starts_with("**") => {
+                delimited(rest, "**", "**").map(|(inner, n)| (inner, n, theme.md_strong()))
+            }
+            '*' | '_' if !word_inside => {
+                let d = &rest[..1];
+                delimited(rest, d, d).map(|(inner, n)| (inner, n, theme.md_emphasis()))
+            }
+            '[' => link(rest).map(|(inner, n)| (inner, n, theme.md_link())),
+            _ => None,
+        };
+        match styled {
+            Some((inner, consumed, style)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::raw(std::mem::take(&mut plain)));
+                }
+                spans.push(Span::styled(inner.to_string(), style));
+                prev = rest[..consumed].chars().last();
+                rest = &rest[consumed..];
+            }
+            None => {
+                plain.push(c);
+                prev = Some(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+    spans
+}
+
+// `open inner close` at the start of `s`; returns (inner, bytes consumed)
+fn delimited<'a>(s: &'a str, open: &str, close: &str) -> Option<(&'a str, usize)> {
+    let body = s.strip_prefix(open)?;
+    let end = body.find(close)?;
+    let inner = &body[..end];
+    if inner.is_empty() || inner.starts_with(' ') {
+        return None;
+    }
+    Some((inner, open.len() + end + close.len()))
+}
+
+// `[text](url)` is shown as the link text only
+fn link(s: &str) -> Option<(&str, usize)> {
+    let (text, n) = delimited(s, "[", "]")?;
+    let (_, m) = delimited(&s[n..], "(", ")")?;
+    Some((text, n + m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_and_markdown_tokens_use_theme_colors() {
+        let theme = Theme::synthwave_light();
+        let line = json_line(
+            r#"  "name": "a:b", "n": -1.5e3, "ok": true, "x": null"#,
+            &theme,
+        );
+        let styled: Vec<(String, Option<Color>)> = line
+            .spans
+            .iter()
+            .filter(|s| s.style.fg.is_some())
+            .map(|s| (s.content.to_string(), s.style.fg))
+            .collect();
+        assert_eq!(
+            styled,
+            vec![
+                ("\"name\"".to_string(), Some(theme.syn_key)),
+                ("\"a:b\"".to_string(), Some(theme.syn_string)),
+                ("\"n\"".to_string(), Some(theme.syn_key)),
+                ("-1.5e3".to_string(), Some(theme.syn_number)),
+                ("\"ok\"".to_string(), Some(theme.syn_key)),
+                ("true".to_string(), Some(theme.syn_bool)),
+                ("\"x\"".to_string(), Some(theme.syn_key)),
+                ("null".to_string(), Some(theme.muted)),
+            ]
+        );
+
+        let spans = markdown_inline(
+            "Run `app hi` or **see** [docs](http://x) _now_ 2*3 my_var_x",
+            &theme,
+        );
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Run ",
+                "app hi",
+                " or ",
+                "see",
+                " ",
+                "docs",
+                " ",
+                "now",
+                " 2*3 my_var_x"
+            ]
+        );
+        assert_eq!(spans[1].style.fg, Some(theme.syn_code));
+        assert_eq!(spans[5].style.fg, Some(theme.syn_link));
+        assert!(spans[7].style.add_modifier.contains(Modifier::ITALIC));
+    }
+}
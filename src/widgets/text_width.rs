@@ -0,0 +1,150 @@
+use ratatui::text::Span;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+// Column-aware text helpers: CJK and most emoji take two terminal cells, combining marks none,
+// so `chars().count()` misaligns columns and cuts titles in the wrong place.
+
+// Terminal cells `s` takes
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+// `s` cut to at most `width` cells, ending in "…" when something was cut
+pub fn truncate(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        // Keep one cell for the ellipsis
+        if used + w > width - 1 {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push('…');
+    out
+}
+
+// `s` padded with spaces (or truncated) to exactly `width` cells
+pub fn pad(s: &str, width: usize) -> String {
+    let s = truncate(s, width);
+    let fill = width.saturating_sub(display_width(&s));
+    format!("{s}{}", " ".repeat(fill))
+}
+
+// `s` right-aligned in `width` cells
+pub fn pad_left(s: &str, width: usize) -> String {
+    let s = truncate(s, width);
+    let fill = width.saturating_sub(display_width(&s));
+    format!("{}{s}", " ".repeat(fill))
+}
+
+// A line of styled spans cut to `width` cells, ending in "…" when something was cut
+pub fn truncate_spans(spans: Vec<Span<'_>>, width: usize) -> Vec<Span<'_>> {
+    let total: usize = spans.iter().map(|s| display_width(&s.content)).sum();
+    if total <= width {
+        return spans;
+    }
+    let mut out = Vec::new();
+    let mut left = width;
+    for span in spans {
+        let w = display_width(&span.content);
+        if w < left {
+            left -= w;
+            out.push(span);
+            continue;
+        }
+        // The span the line ends in keeps its style up to the ellipsis (there is always more
+        // after it, so it gets one even when it would just fit)
+        let cut = truncate(&format!("{}…", span.content), left);
+        out.push(Span::styled(cut, span.style));
+        break;
+    }
+    out
+}
+
+// Key hints ("↑/↓ select • Enter open • q quit") cut to `width` between hints rather than
+// inside one; a dropped tail is marked with "…"
+pub fn fit_hints(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for hint in text.split(" • ") {
+        let next = if out.is_empty() {
+            hint.to_string()
+        } else {
+            format!("{out} • {hint}")
+        };
+        // Room for " …" after it
+        if display_width(&next) + 2 > width {
+            break;
+        }
+        out = next;
+    }
+    if out.is_empty() {
+        return truncate(text, width);
+    }
+    format!("{out} …")
+}
+
+// Widest entry (for column widths)
+pub fn max_width<'a>(items: impl IntoIterator<Item = &'a str>) -> usize {
+    items.into_iter().map(display_width).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths_truncation_and_padding_count_cells() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("🚀 go"), 5);
+
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("hello", 4), "hel…");
+        // A wide char that would straddle the limit is dropped whole
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate("日本語", 0), "");
+        assert_eq!(display_width(&truncate("日本語テキスト", 7)), 7);
+
+        assert_eq!(pad("日本", 6), "日本  ");
+        assert_eq!(pad("日本語", 5), "日本…");
+        assert_eq!(pad_left("42", 4), "  42");
+        assert_eq!(max_width(["ab", "日本語", "🚀"]), 6);
+
+        let spans = vec![
+            Span::raw("[OK] "),
+            Span::raw("保存しました"),
+            Span::raw("  |  focus"),
+        ];
+        let cut: String = truncate_spans(spans, 10)
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect();
+        assert_eq!(cut, "[OK] 保存…");
+        assert_eq!(display_width(&cut), 10);
+        let spans = vec![Span::raw("12345"), Span::raw("678")];
+        let cut: Vec<_> = truncate_spans(spans, 5)
+            .into_iter()
+            .map(|s| s.content)
+            .collect();
+        assert_eq!(cut, ["1234…"]);
+
+        let hints = "↑/↓ select • Enter open • q quit";
+        assert_eq!(fit_hints(hints, 40), hints);
+        assert_eq!(fit_hints(hints, 26), "↑/↓ select • Enter open …");
+        assert_eq!(fit_hints(hints, 12), "↑/↓ select …");
+        assert_eq!(fit_hints(hints, 8), "↑/↓ sel…");
+    }
+}
@@ -0,0 +1,431 @@
+use crate::widgets::chrome::{fit_title, panel_block};
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use serde_json::Value as JsonValue;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+// `widget: tree`: arbitrary nested JSON shown as an expandable tree. Children come from the
+// `children_key` array of a node (default `children`) or, when a node has none, from
+// `children_cmd` run on expand with the node's keys substituted. With `publish:` the
+// selected node's field goes into a slot, so other panes can follow the selection.
+pub struct TreeWidget {
+    title: String,
+    children_key: String,
+    children_cmd: Option<String>,
+    // Command printing the root nodes; None for inline data
+    root_cmd: Option<String>,
+    publish: Option<crate::model::PublishSpec>,
+    roots: Option<Vec<Node>>,
+    root_error: Option<String>,
+    selected: usize,
+    scroll_y: usize,
+    // Bumped on reload so late replies for the old nodes are dropped
+    generation: u64,
+    tx: Sender<TreeReply>,
+    rx: Receiver<TreeReply>,
+}
+
+struct Node {
+    value: JsonValue,
+    // None until loaded (only nodes that can load children)
+    children: Option<Vec<Node>>,
+    expanded: bool,
+    loading: bool,
+    error: Option<String>,
+}
+
+// Reply of a root or children command: (generation, node path, outcome)
+type TreeReply = (u64, Vec<usize>, Result<JsonValue, String>);
+
+// A visible row: the node's path and depth
+struct Row {
+    path: Vec<usize>,
+    depth: usize,
+}
+
+fn label(v: &JsonValue) -> String {
+    for key in ["title", "name", "id"] {
+        match v.get(key) {
+            Some(JsonValue::String(s)) => return s.clone(),
+            Some(JsonValue::Number(n)) => return n.to_string(),
+            _ => {}
+        }
+    }
+    match v {
+        JsonValue::String(s) => s.clone(),
+        other => crate::ui::title_from_value(other),
+    }
+}
+
+impl TreeWidget {
+    pub fn new(
+        title: impl Into<String>,
+        children_key: Option<String>,
+        children_cmd: Option<String>,
+        publish: Option<crate::model::PublishSpec>,
+    ) -> Self {
+        let (tx, rx) = channel();
+        Self {
+            title: title.into(),
+            children_key: children_key.unwrap_or_else(|| "children".to_string()),
+            children_cmd,
+            root_cmd: None,
+            publish,
+            roots: None,
+            root_error: None,
+            selected: 0,
+            scroll_y: 0,
+            generation: 0,
+            tx,
+            rx,
+        }
+    }
+
+    // Roots printed by `cmdline` (loaded in the background)
+    pub fn with_command(mut self, cmdline: String) -> Self {
+        self.root_cmd = Some(cmdline);
+        self.reload();
+        self
+    }
+
+    // Inline roots: an array of nodes, or a single node
+    pub fn with_value(mut self, value: &JsonValue) -> Self {
+        self.set_roots(value);
+        self
+    }
+
+    fn node(&self, value: JsonValue) -> Node {
+        let children = match value.get(&self.children_key) {
+            Some(JsonValue::Array(arr)) => Some(arr.iter().map(|c| self.node(c.clone())).collect()),
+            _ if self.children_cmd.is_some() => None,
+            _ => Some(Vec::new()),
+        };
+        Node {
+            value,
+            children,
+            expanded: false,
+            loading: false,
+            error: None,
+        }
+    }
+
+    fn nodes_of(&self, value: &JsonValue) -> Vec<Node> {
+        match value {
+            JsonValue::Array(arr) => arr.iter().map(|v| self.node(v.clone())).collect(),
+            // `{ "<children_key>": [...] }` envelopes and single nodes
+            JsonValue::Object(o) => match o.get(&self.children_key) {
+                Some(JsonValue::Array(arr)) if o.len() == 1 => {
+                    arr.iter().map(|v| self.node(v.clone())).collect()
+                }
+                _ => vec![self.node(value.clone())],
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn set_roots(&mut self, value: &JsonValue) {
+        self.roots = Some(self.nodes_of(value));
+        self.root_error = None;
+        self.clamp();
+    }
+
+    fn reload(&mut self) {
+        if let Some(cmd) = self.root_cmd.clone() {
+            self.generation += 1;
+            self.roots = None;
+            self.root_error = None;
+            self.spawn(Vec::new(), cmd);
+        }
+    }
+
+    fn spawn(&self, path: Vec<usize>, cmdline: String) {
+        let tx = self.tx.clone();
+        let generation = self.generation;
+        std::thread::spawn(move || {
+            let outcome = crate::services::cli_runner::run_cmdline_to_json(&cmdline)
+                .map_err(|e| format!("{e:#}"));
+            let _ = tx.send((generation, path, outcome));
+        });
+    }
+
+    fn node_at(&self, path: &[usize]) -> Option<&Node> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.as_ref()?.get(*first)?;
+        for i in rest {
+            node = node.children.as_ref()?.get(*i)?;
+        }
+        Some(node)
+    }
+
+    fn node_at_mut(&mut self, path: &[usize]) -> Option<&mut Node> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.as_mut()?.get_mut(*first)?;
+        for i in rest {
+            node = node.children.as_mut()?.get_mut(*i)?;
+        }
+        Some(node)
+    }
+
+    // Apply finished root/children loads
+    pub fn poll(&mut self) {
+        while let Ok((generation, path, outcome)) = self.rx.try_recv() {
+            if generation != self.generation {
+                continue;
+            }
+            if path.is_empty() {
+                match outcome {
+                    Ok(v) => self.set_roots(&v),
+                    Err(e) => self.root_error = Some(e),
+                }
+                continue;
+            }
+            let children = outcome.as_ref().ok().map(|v| self.nodes_of(v));
+            if let Some(node) = self.node_at_mut(&path) {
+                node.loading = false;
+                match (outcome, children) {
+                    (Ok(_), Some(children)) => node.children = Some(children),
+                    (Err(e), _) => {
+                        node.error = Some(e.lines().next().unwrap_or_default().to_string())
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        fn walk(nodes: &[Node], path: &mut Vec<usize>, depth: usize, out: &mut Vec<Row>) {
+            for (i, n) in nodes.iter().enumerate() {
+                path.push(i);
+                out.push(Row {
+                    path: path.clone(),
+                    depth,
+                });
+                if let (true, Some(children)) = (n.expanded, &n.children) {
+                    walk(children, path, depth + 1, out);
+                }
+                path.pop();
+            }
+        }
+        let mut out = Vec::new();
+        if let Some(roots) = &self.roots {
+            walk(roots, &mut Vec::new(), 0, &mut out);
+        }
+        out
+    }
+
+    fn clamp(&mut self) {
+        self.selected = self.selected.min(self.rows().len().saturating_sub(1));
+    }
+
+    fn selected_path(&self) -> Option<Vec<usize>> {
+        self.rows().into_iter().nth(self.selected).map(|r| r.path)
+    }
+
+    // Expand the selected node, loading its children first when needed
+    fn expand(&mut self) {
+        let Some(path) = self.selected_path() else {
+            return;
+        };
+        let cmd = self.children_cmd.clone();
+        let Some(node) = self.node_at_mut(&path) else {
+            return;
+        };
+        node.expanded = true;
+        if node.children.is_none() && !node.loading {
+            if let Some(template) = cmd {
+                node.loading = true;
+                node.error = None;
+                let cmdline = crate::widgets::row_edit::fill_placeholders(&template, &node.value);
+                self.spawn(path, cmdline);
+            }
+        }
+    }
+
+    // Collapse the selected node, or move to its parent
+    fn collapse(&mut self) {
+        let Some(path) = self.selected_path() else {
+            return;
+        };
+        if let Some(node) = self.node_at_mut(&path).filter(|n| n.expanded) {
+            node.expanded = false;
+            return;
+        }
+        let parent = &path[..path.len() - 1];
+        if let Some(i) = self.rows().iter().position(|r| r.path == parent) {
+            self.selected = i;
+        }
+    }
+
+    // (slot, value) of `publish:` for the selected node
+    pub fn published(&self) -> Option<(String, String)> {
+        let spec = self.publish.as_ref()?;
+        let node = self.node_at(&self.selected_path()?)?;
+        let value = match node.value.get(spec.field.as_deref().unwrap_or("id"))? {
+            JsonValue::String(s) => s.clone(),
+            JsonValue::Null => return None,
+            other => other.to_string(),
+        };
+        Some((spec.slot.clone(), value))
+    }
+}
+
+impl crate::widgets::Widget for TreeWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
+        self.poll();
+        let theme = crate::theme::active();
+        let title = fit_title(&self.title, area);
+        let block = panel_block(&title, focused);
+        let height = block.inner(area).height as usize;
+        let rows = self.rows();
+        let mut lines: Vec<Line> = Vec::new();
+        if let Some(e) = &self.root_error {
+            lines.push(Line::from(Span::styled(e.clone(), theme.text_error())));
+        } else if self.roots.is_none() {
+            lines.push(Line::from(Span::styled("Loading…", theme.text_muted())));
+        } else if rows.is_empty() {
+            lines.push(Line::from(Span::styled("No items", theme.text_muted())));
+        }
+        // Keep the selection in view
+        if self.selected < self.scroll_y {
+            self.scroll_y = self.selected;
+        } else if height > 0 && self.selected >= self.scroll_y + height {
+            self.scroll_y = self.selected + 1 - height;
+        }
+        for (i, row) in rows.iter().enumerate().skip(self.scroll_y).take(height) {
+            let Some(node) = self.node_at(&row.path) else {
+                continue;
+            };
+            let marker = if node.loading {
+                ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"][tick as usize % 6]
+            } else {
+                match (&node.children, node.expanded) {
+                    (Some(c), _) if c.is_empty() => "•",
+                    (_, true) => "▾",
+                    _ => "▸",
+                }
+            };
+            let mut spans = vec![
+                Span::raw("  ".repeat(row.depth)),
+                Span::styled(format!("{marker} "), theme.text_muted()),
+                Span::raw(label(&node.value)),
+            ];
+            if let Some(c) = node.children.as_ref().filter(|c| !c.is_empty()) {
+                spans.push(Span::styled(format!(" ({})", c.len()), theme.text_muted()));
+            }
+            if let Some(e) = &node.error {
+                spans.push(Span::styled(format!("  {e}"), theme.text_error()));
+            }
+            let mut line = Line::from(spans);
+            if i == self.selected {
+                line = line.style(theme.list_cursor_style());
+            }
+            lines.push(line);
+        }
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn on_key(&mut self, key: KeyCode) -> Vec<crate::app::Effect> {
+        self.poll();
+        let last = self.rows().len().saturating_sub(1);
+        match key {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            KeyCode::Right | KeyCode::Enter => self.expand(),
+            KeyCode::Left => self.collapse(),
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![
+            WidgetAction::key("Expand node", "→", KeyCode::Right),
+            WidgetAction::key("Collapse node", "←", KeyCode::Left),
+        ];
+        if self.root_cmd.is_some() {
+            actions.push(WidgetAction::refresh());
+        }
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
+    fn refresh(&mut self) -> Vec<crate::app::Effect> {
+        self.reload();
+        Vec::new()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Widget;
+    use serde_json::json;
+
+    fn wait_loaded(w: &mut TreeWidget, path: &[usize]) {
+        for _ in 0..200 {
+            w.poll();
+            if w.node_at(path).is_some_and(|n| !n.loading) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        panic!("children did not load");
+    }
+
+    #[test]
+    fn expands_inline_and_lazy_children_and_publishes_the_selection() {
+        let data = json!([
+            {"id": "eu", "nodes": [{"id": "eu-1", "nodes": []}, {"id": "eu-2"}]},
+            {"id": "us"},
+        ]);
+        let publish = crate::model::PublishSpec {
+            slot: "node".into(),
+            field: None,
+        };
+        let mut w = TreeWidget::new(
+            "Regions",
+            Some("nodes".into()),
+            Some("echo '[{\"id\": \"{id}-a\"}]'".into()),
+            Some(publish),
+        )
+        .with_value(&data);
+        assert_eq!(w.rows().len(), 2);
+        w.on_key(KeyCode::Right);
+        assert_eq!(w.rows().len(), 4);
+        w.on_key(KeyCode::Down);
+        assert_eq!(w.published(), Some(("node".into(), "eu-1".into())));
+        // An empty inline array is a leaf: nothing to load
+        w.on_key(KeyCode::Right);
+        assert!(!w.node_at(&[0, 0]).unwrap().loading);
+
+        // eu-2 has no children array: they come from children_cmd with its keys filled
+        w.on_key(KeyCode::Down);
+        w.on_key(KeyCode::Enter);
+        assert!(w.node_at(&[0, 1]).unwrap().loading);
+        wait_loaded(&mut w, &[0, 1]);
+        assert_eq!(w.rows().len(), 5);
+        w.on_key(KeyCode::Down);
+        assert_eq!(w.published(), Some(("node".into(), "eu-2-a".into())));
+
+        // Left goes to the parent, then collapses it
+        w.on_key(KeyCode::Left);
+        assert_eq!(w.published(), Some(("node".into(), "eu-2".into())));
+        w.on_key(KeyCode::Left);
+        w.on_key(KeyCode::Left);
+        w.on_key(KeyCode::Left);
+        assert_eq!(w.published(), Some(("node".into(), "eu".into())));
+        assert_eq!(w.rows().len(), 2);
+    }
+}
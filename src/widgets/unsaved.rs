@@ -0,0 +1,63 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+// Centered modal box with a title and a few lines of text
+fn draw_dialog(f: &mut Frame, screen: Rect, title: &str, lines: Vec<Line>) {
+    let width = screen.width.saturating_sub(4).min(60);
+    let height = (lines.len() as u16 + 2).min(screen.height);
+    let rect = Rect {
+        x: screen.x + screen.width.saturating_sub(width) / 2,
+        y: screen.y + screen.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let p = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(crate::widgets::chrome::panel_block(title, true));
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
+
+// Navigation guard dialog: leaving Pane B would drop unsaved input
+pub fn draw_guard(f: &mut Frame, screen: Rect, leaving: &str) {
+    let theme = crate::theme::active();
+    let lines = vec![
+        Line::from(format!("Pane B has unsaved changes. {leaving}?")),
+        Line::from(""),
+        Line::from(Span::styled(
+            "s save • d discard • Esc stay",
+            theme.text_muted(),
+        )),
+    ];
+    draw_dialog(f, screen, "Unsaved changes", lines);
+}
+
+// Confirmation of a verb marked `confirm: true` (e.g. "api-7f9 — Delete")
+pub fn draw_confirm(f: &mut Frame, screen: Rect, action: &str, command: &str) {
+    let theme = crate::theme::active();
+    let lines = vec![
+        Line::from(format!("{action}?")),
+        Line::from(Span::styled(command.to_string(), theme.text_muted())),
+        Line::from(""),
+        Line::from(Span::styled("y run • n / Esc cancel", theme.text_muted())),
+    ];
+    draw_dialog(f, screen, "Confirm", lines);
+}
+
+// Quit confirmation: what quitting now would stop or lose (empty with `confirm_quit: always`)
+pub fn draw_quit(f: &mut Frame, screen: Rect, items: &[String]) {
+    let theme = crate::theme::active();
+    let mut lines = Vec::new();
+    if items.is_empty() {
+        lines.push(Line::from("Quit?"));
+    } else {
+        lines.push(Line::from("Quitting now will stop:"));
+        lines.extend(items.iter().map(|i| Line::from(format!("  • {i}"))));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "y / q quit • n / Esc stay",
+        theme.text_muted(),
+    )));
+    draw_dialog(f, screen, "Quit", lines);
+}
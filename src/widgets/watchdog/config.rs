@@ -23,4 +23,7 @@ pub struct WatchdogConfig {
     pub external_check_cmd: Option<String>,
     // Optional command to terminate the external process
     pub external_kill_cmd: Option<String>,
+    // `unit:` / `container:` targets, each followed in a section after the plain commands
+    #[serde(default)]
+    pub targets: Vec<super::targets::Target>,
 }
@@ -0,0 +1,144 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// Restarts older than this no longer count towards the summary
+pub const RESTART_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CmdState {
+    Pending,
+    Running,
+    Done,
+    Failed,
+    Stopped,
+}
+
+// Lifecycle of one watchdog command, updated by the spawner thread
+#[derive(Debug)]
+pub struct CmdHealth {
+    pub state: CmdState,
+    pub restarts: Vec<Instant>,
+    // Process of the current run, sampled for the metrics column
+    pub pid: Option<u32>,
+}
+
+pub type CmdHealthRef = Arc<Mutex<CmdHealth>>;
+
+impl CmdHealth {
+    pub fn new_ref() -> CmdHealthRef {
+        Arc::new(Mutex::new(CmdHealth {
+            state: CmdState::Pending,
+            restarts: Vec::new(),
+            pid: None,
+        }))
+    }
+}
+
+pub fn set_state(h: &CmdHealthRef, state: CmdState) {
+    if let Ok(mut g) = h.lock() {
+        g.state = state;
+    }
+}
+
+pub fn set_pid(h: &CmdHealthRef, pid: Option<u32>) {
+    if let Ok(mut g) = h.lock() {
+        g.pid = pid;
+    }
+}
+
+pub fn record_restart(h: &CmdHealthRef) {
+    if let Ok(mut g) = h.lock() {
+        let now = Instant::now();
+        g.restarts
+            .retain(|t| now.duration_since(*t) < RESTART_WINDOW);
+        g.restarts.push(now);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HealthSummary {
+    pub running: usize,
+    pub done: usize,
+    pub failed: usize,
+    pub stopped: usize,
+    pub recent_restarts: usize,
+}
+
+impl HealthSummary {
+    pub fn collect<'a>(healths: impl IntoIterator<Item = &'a CmdHealthRef>) -> Self {
+        let now = Instant::now();
+        let mut s = Self::default();
+        for h in healths {
+            let Ok(g) = h.lock() else { continue };
+            match g.state {
+                CmdState::Running => s.running += 1,
+                CmdState::Done => s.done += 1,
+                CmdState::Failed => s.failed += 1,
+                CmdState::Stopped => s.stopped += 1,
+                CmdState::Pending => {}
+            }
+            s.recent_restarts += g
+                .restarts
+                .iter()
+                .filter(|t| now.duration_since(**t) < RESTART_WINDOW)
+                .count();
+        }
+        s
+    }
+
+    // e.g. "4 running, 1 failed, 2 restarts in last 5m"
+    pub fn line(&self) -> String {
+        let mut parts = vec![format!("{} running", self.running)];
+        for (n, label) in [
+            (self.failed, "failed"),
+            (self.done, "done"),
+            (self.stopped, "stopped"),
+        ] {
+            if n > 0 {
+                parts.push(format!("{n} {label}"));
+            }
+        }
+        let restarts = match self.recent_restarts {
+            1 => "1 restart".to_string(),
+            n => format!("{n} restarts"),
+        };
+        parts.push(format!(
+            "{restarts} in last {}m",
+            RESTART_WINDOW.as_secs() / 60
+        ));
+        parts.join(", ")
+    }
+
+    // Short menu badge: "3/4 ok" style, flagged when something failed
+    pub fn badge(&self, total: usize) -> String {
+        if self.failed > 0 {
+            format!("{}/{} running, {} failed", self.running, total, self.failed)
+        } else {
+            format!("{}/{} running", self.running, total)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_counts_states_and_recent_restarts() {
+        let hs: Vec<CmdHealthRef> = (0..4).map(|_| CmdHealth::new_ref()).collect();
+        set_state(&hs[0], CmdState::Running);
+        set_state(&hs[1], CmdState::Running);
+        set_state(&hs[2], CmdState::Failed);
+        record_restart(&hs[2]);
+        record_restart(&hs[2]);
+        // A restart outside the window is ignored
+        hs[3].lock().unwrap().restarts.push(
+            Instant::now()
+                .checked_sub(RESTART_WINDOW + Duration::from_secs(1))
+                .unwrap(),
+        );
+        let s = HealthSummary::collect(&hs);
+        assert_eq!(s.line(), "2 running, 1 failed, 2 restarts in last 5m");
+        assert_eq!(s.badge(hs.len()), "2/4 running, 1 failed");
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+// CPU and resident memory of the processes a watchdog runs (or finds through
+// `external_check_cmd`), sampled with `ps` in the background while the widget is drawn
+
+const SAMPLE_EVERY: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProcMetrics {
+    // Percent of one core, as `ps` reports it
+    pub cpu: f32,
+    // Resident memory in KiB
+    pub rss: u64,
+}
+
+impl ProcMetrics {
+    // Processes of one section (an external process may be several) add up
+    pub fn sum<'a>(all: impl IntoIterator<Item = &'a ProcMetrics>) -> Option<Self> {
+        all.into_iter().fold(None, |acc, m| {
+            let acc = acc.unwrap_or_default();
+            Some(Self {
+                cpu: acc.cpu + m.cpu,
+                rss: acc.rss + m.rss,
+            })
+        })
+    }
+}
+
+// `ps -o pid=,pcpu=,rss=` output
+fn parse_ps(out: &str) -> HashMap<u32, ProcMetrics> {
+    out.lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?.parse().ok()?;
+            let cpu = parts.next()?.parse().ok()?;
+            let rss = parts.next()?.parse().ok()?;
+            Some((pid, ProcMetrics { cpu, rss }))
+        })
+        .collect()
+}
+
+// Gone processes are left out (ps then exits 1 but still lists the others)
+fn sample(pids: &[u32]) -> HashMap<u32, ProcMetrics> {
+    let list = pids
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let Ok(out) = Command::new("ps")
+        .args(["-o", "pid=,pcpu=,rss=", "-p", &list])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+    else {
+        return HashMap::new();
+    };
+    parse_ps(&String::from_utf8_lossy(&out.stdout))
+}
+
+pub struct MetricsSampler {
+    latest: HashMap<u32, ProcMetrics>,
+    last: Option<Instant>,
+    sampling: bool,
+    tx: Sender<HashMap<u32, ProcMetrics>>,
+    rx: Receiver<HashMap<u32, ProcMetrics>>,
+}
+
+impl Default for MetricsSampler {
+    fn default() -> Self {
+        let (tx, rx) = channel();
+        Self {
+            latest: HashMap::new(),
+            last: None,
+            sampling: false,
+            tx,
+            rx,
+        }
+    }
+}
+
+impl MetricsSampler {
+    // Take a finished sample, and start the next one for `pids` when due
+    pub fn poll(&mut self, pids: Vec<u32>) {
+        while let Ok(latest) = self.rx.try_recv() {
+            self.sampling = false;
+            self.latest = latest;
+        }
+        if pids.is_empty() {
+            self.latest.clear();
+            return;
+        }
+        if self.sampling || self.last.is_some_and(|t| t.elapsed() < SAMPLE_EVERY) {
+            return;
+        }
+        self.sampling = true;
+        self.last = Some(Instant::now());
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(sample(&pids));
+        });
+    }
+
+    pub fn get(&self, pid: u32) -> Option<&ProcMetrics> {
+        self.latest.get(&pid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_running_processes() {
+        let parsed = parse_ps("  412  3.5  20480\n 9001 0.0 512\nbogus\n");
+        assert_eq!(
+            parsed[&412],
+            ProcMetrics {
+                cpu: 3.5,
+                rss: 20480
+            }
+        );
+        let total = ProcMetrics::sum(parsed.values()).unwrap();
+        assert_eq!((total.cpu, total.rss), (3.5, 20992));
+        assert!(ProcMetrics::sum([]).is_none());
+
+        // Our own process is sampled; a pid that is gone is left out
+        let own = std::process::id();
+        let mut sampler = MetricsSampler::default();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while sampler.get(own).is_none() && Instant::now() < deadline {
+            sampler.poll(vec![own, 3_999_999]);
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(sampler.get(own).is_some_and(|m| m.rss > 0));
+        assert!(sampler.get(3_999_999).is_none());
+    }
+}
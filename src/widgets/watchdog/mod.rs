@@ -1,9 +1,12 @@
 pub mod config;
 pub mod detectors;
+pub mod health;
 pub mod killers;
+pub mod metrics;
 pub mod output;
 pub mod session;
 pub mod spawners;
+pub mod targets;
 pub mod util;
 pub mod widget;
 pub use output::stats::StatsAggregator;
@@ -1,45 +1,246 @@
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 use crate::widgets::watchdog::config::MAX_LINES_PER_CMD;
 
-// Simple output sink backed by a shared VecDeque buffer with max size.
-pub struct RingBufferSink {
-    buf: Arc<Mutex<VecDeque<String>>>,
+pub mod stats;
+
+// Lines per segment. Writers only append to the last segment and trimming drops whole
+// segments from the front, so no line is moved or cloned once written.
+const SEGMENT_LINES: usize = 256;
+
+pub type SharedOutput = Arc<OutputBuffer>;
+
+// Output of one watchdog command, shared by its reader threads and the UI. Lines are numbered
+// from the start of the session (`seq`, kept across trimming and clears) and the buffer keeps
+// the last `cap` of them. Readers take the lock only to copy what they need: the rows on
+// screen, or the lines the stats footer hasn't counted yet. `version` changes on every write
+// and can be checked without the lock.
+pub struct OutputBuffer {
+    lines: Mutex<Lines>,
+    version: AtomicU64,
+    cap: usize,
 }
 
-pub mod stats;
+pub struct Lines {
+    segments: VecDeque<Vec<String>>,
+    // Lines already trimmed from the front segment
+    head: usize,
+    len: usize,
+    // Sequence number of the oldest kept line
+    first_seq: u64,
+    clears: u64,
+}
+
+impl Default for OutputBuffer {
+    fn default() -> Self {
+        Self::with_cap(MAX_LINES_PER_CMD)
+    }
+}
 
-impl RingBufferSink {
-    pub fn new(buf: Arc<Mutex<VecDeque<String>>>) -> Self {
-        Self { buf }
+impl OutputBuffer {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    pub fn push_line(&self, s: String) {
-        if let Ok(mut q) = self.buf.lock() {
-            q.push_back(s);
-            if q.len() > MAX_LINES_PER_CMD {
-                let excess = q.len() - MAX_LINES_PER_CMD;
-                for _ in 0..excess {
-                    let _ = q.pop_front();
-                }
+    pub fn with_cap(cap: usize) -> Self {
+        Self {
+            lines: Mutex::new(Lines {
+                segments: VecDeque::new(),
+                head: 0,
+                len: 0,
+                first_seq: 0,
+                clears: 0,
+            }),
+            version: AtomicU64::new(0),
+            cap,
+        }
+    }
+
+    pub fn push(&self, line: String) {
+        let Ok(mut l) = self.lines.lock() else {
+            return;
+        };
+        match l.segments.back_mut() {
+            Some(seg) if seg.len() < SEGMENT_LINES => seg.push(line),
+            _ => {
+                let mut seg = Vec::with_capacity(SEGMENT_LINES);
+                seg.push(line);
+                l.segments.push_back(seg);
+            }
+        }
+        l.len += 1;
+        while l.len > self.cap {
+            l.head += 1;
+            l.len -= 1;
+            l.first_seq += 1;
+            if l.segments.front().is_some_and(|s| l.head >= s.len()) {
+                l.segments.pop_front();
+                l.head = 0;
             }
         }
+        self.version.fetch_add(1, Ordering::Release);
     }
 
-    #[allow(dead_code)]
+    // Drop every line; numbering carries on from where it was
     pub fn clear(&self) {
-        if let Ok(mut q) = self.buf.lock() {
-            q.clear();
+        let Ok(mut l) = self.lines.lock() else {
+            return;
+        };
+        l.first_seq += l.len as u64;
+        l.segments.clear();
+        l.head = 0;
+        l.len = 0;
+        l.clears += 1;
+        self.version.fetch_add(1, Ordering::Release);
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    pub fn len(&self) -> usize {
+        self.read(|l| l.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Run `f` with the lines locked; keep it short, the writers wait meanwhile
+    pub fn read<R: Default>(&self, f: impl FnOnce(&Lines) -> R) -> R {
+        match self.lines.lock() {
+            Ok(l) => f(&l),
+            Err(_) => R::default(),
         }
     }
 
-    #[allow(dead_code)]
+    // Copies of `count` lines from position `start` (0 = oldest kept line)
+    pub fn window(&self, start: usize, count: usize) -> Vec<String> {
+        self.read(|l| l.range(start, count).map(String::from).collect())
+    }
+
+    pub fn to_vec(&self) -> Vec<String> {
+        self.window(0, usize::MAX)
+    }
+}
+
+impl Lines {
     pub fn len(&self) -> usize {
-        if let Ok(q) = self.buf.lock() {
-            q.len()
-        } else {
-            0
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn first_seq(&self) -> u64 {
+        self.first_seq
+    }
+
+    // Sequence number the next line will get
+    pub fn end_seq(&self) -> u64 {
+        self.first_seq + self.len as u64
+    }
+
+    // How many times the buffer was cleared
+    pub fn clears(&self) -> u64 {
+        self.clears
+    }
+
+    // Up to `count` lines from position `start`, found by segment instead of line by line
+    pub fn range(&self, start: usize, count: usize) -> impl Iterator<Item = &str> {
+        let count = count.min(self.len.saturating_sub(start));
+        let mut skip = self.head + start.min(self.len);
+        let mut first = 0;
+        while first < self.segments.len() && skip >= self.segments[first].len() {
+            skip -= self.segments[first].len();
+            first += 1;
         }
+        self.segments
+            .range(first..)
+            .enumerate()
+            .flat_map(move |(i, seg)| seg[if i == 0 { skip } else { 0 }..].iter())
+            .take(count)
+            .map(String::as_str)
+    }
+
+    // Line numbered `seq`, while it is kept
+    pub fn get(&self, seq: u64) -> Option<&str> {
+        let pos = seq.checked_sub(self.first_seq)?;
+        self.range(usize::try_from(pos).ok()?, 1).next()
+    }
+
+    // Kept lines numbered `seq` or later
+    pub fn since(&self, seq: u64) -> impl Iterator<Item = &str> {
+        let start = seq.saturating_sub(self.first_seq).min(self.len as u64) as usize;
+        self.range(start, self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(cap: usize, n: usize) -> OutputBuffer {
+        let buf = OutputBuffer::with_cap(cap);
+        for i in 0..n {
+            buf.push(format!("line {i}"));
+        }
+        buf
+    }
+
+    #[test]
+    fn keeps_the_last_cap_lines_across_segments() {
+        let buf = filled(600, 1000);
+        assert_eq!(buf.len(), 600);
+        buf.read(|l| {
+            assert_eq!((l.first_seq(), l.end_seq()), (400, 1000));
+            // Trimmed segments are released; at most one partly trimmed one is left
+            assert!(l.segments.len() <= 600 / SEGMENT_LINES + 2);
+        });
+        let all = buf.to_vec();
+        assert_eq!(all.first().map(String::as_str), Some("line 400"));
+        assert_eq!(all.last().map(String::as_str), Some("line 999"));
+        assert_eq!(all.len(), 600);
+    }
+
+    #[test]
+    fn window_copies_only_the_requested_lines() {
+        let buf = filled(600, 1000);
+        // Straddles a segment boundary
+        assert_eq!(
+            buf.window(110, 3),
+            ["line 510", "line 511", "line 512"].map(String::from)
+        );
+        assert_eq!(
+            buf.window(598, 10),
+            ["line 998", "line 999"].map(String::from)
+        );
+        assert!(buf.window(600, 10).is_empty());
+        assert!(buf.window(5000, 10).is_empty());
+    }
+
+    #[test]
+    fn sequence_numbers_survive_trimming_and_clears() {
+        let buf = filled(600, 1000);
+        let v = buf.version();
+        let since: Vec<String> = buf.read(|l| l.since(997).map(String::from).collect());
+        assert_eq!(
+            since,
+            ["line 997", "line 998", "line 999"].map(String::from)
+        );
+        // Lines trimmed before they were read are skipped
+        assert_eq!(buf.read(|l| l.since(0).count()), 600);
+
+        buf.clear();
+        assert!(buf.version() > v);
+        assert!(buf.is_empty());
+        buf.push("after".into());
+        buf.read(|l| {
+            assert_eq!((l.first_seq(), l.end_seq(), l.clears()), (1000, 1001, 1));
+            assert_eq!(l.since(1000).collect::<Vec<_>>(), ["after"]);
+        });
     }
 }
@@ -1,8 +1,6 @@
-use regex::Regex;
-use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
-
 use crate::widgets::watchdog::config::WatchdogStatSpec;
+use crate::widgets::watchdog::output::SharedOutput;
+use regex::Regex;
 
 #[derive(Clone)]
 pub struct Pattern {
@@ -13,7 +11,15 @@ pub struct Pattern {
 pub struct StatsAggregator {
     patterns: Vec<Pattern>,
     counts: Vec<usize>,
-    last_lens: Vec<usize>,
+    // Per stream, how far it has been counted
+    seen: Vec<Seen>,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Seen {
+    version: u64,
+    seq: u64,
+    clears: u64,
 }
 
 impl StatsAggregator {
@@ -28,11 +34,11 @@ impl StatsAggregator {
             })
             .collect();
         let counts = vec![0usize; patterns.len()];
-        let last_lens = vec![0usize; streams];
+        let seen = vec![Seen::default(); streams];
         Self {
             patterns,
             counts,
-            last_lens,
+            seen,
         }
     }
 
@@ -48,61 +54,101 @@ impl StatsAggregator {
         &self.counts
     }
 
-    // Update counts incrementally if buffers only grew; otherwise recompute all.
-    pub fn update_from_buffers(&mut self, buffers: &[Arc<Mutex<VecDeque<String>>>]) {
-        // If any buffer shrank, do full recompute
-        let mut need_full = false;
-        for (i, buf) in buffers.iter().enumerate() {
-            if let Ok(q) = buf.lock() {
-                let cur = q.len();
-                if cur < self.last_lens.get(i).copied().unwrap_or(0) {
-                    need_full = true;
-                    break;
-                }
-            }
-        }
-        if need_full {
+    // Count the lines written since the last update, read by sequence number so a buffer at
+    // its cap still counts; a cleared buffer is counted again from scratch.
+    pub fn update_from_buffers(&mut self, buffers: &[SharedOutput]) {
+        let cleared = buffers.iter().enumerate().any(|(i, buf)| {
+            let seen = self.seen.get(i).copied().unwrap_or_default();
+            buf.version() != seen.version && buf.read(|l| l.clears()) != seen.clears
+        });
+        if cleared {
             self.recompute(buffers);
             return;
         }
-        // Incremental update
         for (i, buf) in buffers.iter().enumerate() {
-            if let Ok(q) = buf.lock() {
-                let cur = q.len();
-                let last = self.last_lens.get(i).copied().unwrap_or(0);
-                if cur > last {
-                    for s in q.iter().skip(last).take(cur - last) {
-                        for (pi, pat) in self.patterns.iter().enumerate() {
-                            self.counts[pi] =
-                                self.counts[pi].saturating_add(pat.re.find_iter(s).count());
-                        }
-                    }
-                    if let Some(slot) = self.last_lens.get_mut(i) {
-                        *slot = cur;
-                    }
-                }
-            } else if let Some(slot) = self.last_lens.get_mut(i) {
-                *slot = 0;
+            let Some(seen) = self.seen.get(i).copied() else {
+                continue;
+            };
+            // Nothing written: skip without locking
+            let version = buf.version();
+            if version == seen.version {
+                continue;
             }
+            let patterns = &self.patterns;
+            let counts = &mut self.counts;
+            let end = buf.read(|l| {
+                for s in l.since(seen.seq) {
+                    count_into(patterns, counts, s);
+                }
+                l.end_seq()
+            });
+            self.seen[i] = Seen {
+                version,
+                seq: end,
+                clears: seen.clears,
+            };
         }
     }
 
-    fn recompute(&mut self, buffers: &[Arc<Mutex<VecDeque<String>>>]) {
+    fn recompute(&mut self, buffers: &[SharedOutput]) {
         self.counts.fill(0);
         for (i, buf) in buffers.iter().enumerate() {
-            if let Ok(q) = buf.lock() {
-                for s in q.iter() {
-                    for (pi, pat) in self.patterns.iter().enumerate() {
-                        self.counts[pi] =
-                            self.counts[pi].saturating_add(pat.re.find_iter(s).count());
-                    }
+            let version = buf.version();
+            let patterns = &self.patterns;
+            let counts = &mut self.counts;
+            let (seq, clears) = buf.read(|l| {
+                for s in l.since(0) {
+                    count_into(patterns, counts, s);
                 }
-                if let Some(slot) = self.last_lens.get_mut(i) {
-                    *slot = q.len();
-                }
-            } else if let Some(slot) = self.last_lens.get_mut(i) {
-                *slot = 0;
+                (l.end_seq(), l.clears())
+            });
+            if let Some(slot) = self.seen.get_mut(i) {
+                *slot = Seen {
+                    version,
+                    seq,
+                    clears,
+                };
             }
         }
     }
 }
+
+fn count_into(patterns: &[Pattern], counts: &mut [usize], line: &str) {
+    for (pi, pat) in patterns.iter().enumerate() {
+        counts[pi] = counts[pi].saturating_add(pat.re.find_iter(line).count());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::watchdog::output::OutputBuffer;
+    use std::sync::Arc;
+
+    #[test]
+    fn counts_keep_up_with_a_full_buffer_and_reset_on_clear() {
+        let specs = [WatchdogStatSpec {
+            label: "errors".into(),
+            regexp: "ERROR".into(),
+        }];
+        let buf = Arc::new(OutputBuffer::with_cap(10));
+        let mut aggr = StatsAggregator::new(&specs, 1);
+        let buffers = [Arc::clone(&buf)];
+        for i in 0..25 {
+            buf.push(format!("{} {i}", if i % 5 == 0 { "ERROR" } else { "ok" }));
+        }
+        aggr.update_from_buffers(&buffers);
+        // Only what is still kept was seen: lines 15..25
+        assert_eq!(aggr.counts(), [2]);
+        // At the cap, new lines are still counted
+        buf.push("ERROR again".into());
+        aggr.update_from_buffers(&buffers);
+        aggr.update_from_buffers(&buffers);
+        assert_eq!(aggr.counts(), [3]);
+
+        buf.clear();
+        buf.push("ERROR after restart".into());
+        aggr.update_from_buffers(&buffers);
+        assert_eq!(aggr.counts(), [1]);
+    }
+}
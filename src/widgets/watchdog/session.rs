@@ -1,16 +1,19 @@
 use super::config::WatchdogConfig;
 use super::detectors::{CommandDetector, Detector};
+use super::health::{set_state, CmdHealth, CmdHealthRef, CmdState, HealthSummary};
 use super::killers::{CommandKiller, Killer};
+use super::output::{OutputBuffer, SharedOutput};
 use super::spawners::{LocalSpawner, Spawner};
-use super::util::{push_line, run_cmd_quiet};
-use std::collections::VecDeque;
+use super::targets::Target;
+use super::util::{push_line, run_cmd_output, run_cmd_pids, run_cmd_quiet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
 pub struct CmdLog {
     pub cmd: String,
-    pub output: Arc<Mutex<VecDeque<String>>>,
+    pub output: SharedOutput,
+    pub health: CmdHealthRef,
 }
 
 struct Worker {
@@ -26,11 +29,17 @@ pub struct WatchdogSession {
     pub started: bool,
     // per command worker state
     workers: Vec<Worker>,
+    // `unit:` / `container:` target behind each command (None for plain commands)
+    targets: Vec<Option<Target>>,
+    // Set by restart_all: the next start restarts the targets before following them
+    restart_targets: bool,
     // Orchestrator thread for sequential mode
     seq_handle: Option<std::thread::JoinHandle<()>>,
     // External mode flags
     pub external: bool,
     pub external_running: bool,
+    // Pids printed by `external_check_cmd` (e.g. `pgrep -f server`), sampled for metrics
+    pub external_pids: Vec<u32>,
     external_stop: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
     external_handle: Option<std::thread::JoinHandle<()>>,
     detector: Option<Box<dyn Detector + Send + Sync>>,
@@ -40,6 +49,44 @@ pub struct WatchdogSession {
 
 pub type WatchdogSessionRef = Arc<Mutex<WatchdogSession>>;
 
+// Control of single commands through the shared session (used by the widget's action bar)
+pub trait SessionControls {
+    fn start(&self, idx: usize) -> Result<(), String>;
+    fn stop(&self, idx: usize) -> Result<(), String>;
+    fn restart(&self, idx: usize) -> Result<(), String>;
+    fn kill(&self) -> Result<(), String>;
+}
+
+impl SessionControls for WatchdogSessionRef {
+    fn start(&self, idx: usize) -> Result<(), String> {
+        let mut s = self
+            .lock()
+            .map_err(|_| "Watchdog session unavailable".to_string())?;
+        s.start_cmd(idx)
+    }
+
+    fn stop(&self, idx: usize) -> Result<(), String> {
+        let mut s = self
+            .lock()
+            .map_err(|_| "Watchdog session unavailable".to_string())?;
+        s.stop_cmd(idx)
+    }
+
+    fn restart(&self, idx: usize) -> Result<(), String> {
+        let mut s = self
+            .lock()
+            .map_err(|_| "Watchdog session unavailable".to_string())?;
+        s.restart_cmd(idx)
+    }
+
+    fn kill(&self) -> Result<(), String> {
+        let mut s = self
+            .lock()
+            .map_err(|_| "Watchdog session unavailable".to_string())?;
+        s.run_kill_cmd()
+    }
+}
+
 impl WatchdogSession {
     pub fn create(commands: Vec<String>, cfg: WatchdogConfig) -> WatchdogSessionRef {
         let mut cmds: Vec<CmdLog> = Vec::new();
@@ -47,10 +94,20 @@ impl WatchdogSession {
             let cmd = raw.clone();
             let log = CmdLog {
                 cmd,
-                output: Arc::new(Mutex::new(VecDeque::new())),
+                output: Arc::new(OutputBuffer::new()),
+                health: CmdHealth::new_ref(),
             };
             cmds.push(log);
         }
+        let mut targets: Vec<Option<Target>> = vec![None; cmds.len()];
+        for t in &cfg.targets {
+            cmds.push(CmdLog {
+                cmd: t.logs_cmd(),
+                output: Arc::new(OutputBuffer::new()),
+                health: CmdHealth::new_ref(),
+            });
+            targets.push(Some(t.clone()));
+        }
         let workers: Vec<Worker> = (0..cmds.len())
             .map(|_| Worker {
                 stop: Arc::new(AtomicBool::new(false)),
@@ -62,9 +119,12 @@ impl WatchdogSession {
             cfg,
             started: false,
             workers,
+            targets,
+            restart_targets: false,
             seq_handle: None,
             external: false,
             external_running: false,
+            external_pids: Vec::new(),
             external_stop: None,
             external_handle: None,
             detector: None,
@@ -102,9 +162,11 @@ impl WatchdogSession {
                         if stop.load(std::sync::atomic::Ordering::SeqCst) {
                             break;
                         }
-                        let running = matches!(run_cmd_quiet(&check_cmd), Some(0));
+                        let (code, pids) = run_cmd_pids(&check_cmd).unwrap_or((-1, Vec::new()));
+                        let running = code == 0;
                         if let Ok(mut g) = sess_clone.lock() {
                             g.external_running = running;
+                            g.external_pids = if running { pids } else { Vec::new() };
                             if last.map(|v| v != running).unwrap_or(true) {
                                 // status changed; append note to panes
                                 for c in &g.cmds {
@@ -130,13 +192,6 @@ impl WatchdogSession {
         session
     }
 
-    pub fn start(&mut self) {
-        if self.started {
-            return;
-        }
-        self.start_locked();
-    }
-
     fn start_locked(&mut self) {
         self.started = true;
         // reset stop flags
@@ -150,6 +205,51 @@ impl WatchdogSession {
         }
     }
 
+    // Start every command that is not running: the whole session when stopped,
+    // otherwise (parallel mode) just the finished/failed/stopped commands
+    pub fn start_all(&mut self) -> usize {
+        if self.external {
+            return 0;
+        }
+        if !self.started {
+            self.start_locked();
+            return self.cmds.len();
+        }
+        if self.cfg.sequential {
+            return 0;
+        }
+        let mut started = 0;
+        for idx in 0..self.cmds.len() {
+            let running = self.cmds[idx]
+                .health
+                .lock()
+                .map(|g| g.state == CmdState::Running)
+                .unwrap_or(true);
+            let finished = self.workers[idx]
+                .handle
+                .as_ref()
+                .is_none_or(|h| h.is_finished());
+            if running || !finished {
+                continue;
+            }
+            if let Some(h) = self.workers[idx].handle.take() {
+                let _ = h.join();
+            }
+            self.workers[idx].stop.store(false, Ordering::SeqCst);
+            push_line(
+                &self.cmds[idx].output,
+                format!("[start] {}", self.cmds[idx].cmd),
+            );
+            self.spawn_one(idx, false);
+            started += 1;
+        }
+        started
+    }
+
+    pub fn summary(&self) -> HealthSummary {
+        HealthSummary::collect(self.cmds.iter().map(|c| &c.health))
+    }
+
     pub fn stop_all(&mut self) {
         // Request stop and kill running child processes
         for w in &mut self.workers {
@@ -176,9 +276,7 @@ impl WatchdogSession {
 
     pub fn clear_outputs(&mut self) {
         for c in &mut self.cmds {
-            if let Ok(mut q) = c.output.lock() {
-                q.clear();
-            }
+            c.output.clear();
         }
     }
 
@@ -203,59 +301,162 @@ impl WatchdogSession {
             for c in &self.cmds {
                 push_line(&c.output, format!("[start] {}", c.cmd));
             }
+            self.restart_targets = true;
             self.start_locked();
         }
     }
 
-    pub fn kill_external(&mut self) -> bool {
-        if !self.external {
-            return false;
+    // Whether the command at `idx` has a live worker (running or waiting to retry)
+    fn worker_alive(&self, idx: usize) -> bool {
+        self.workers[idx]
+            .handle
+            .as_ref()
+            .is_some_and(|h| !h.is_finished())
+    }
+
+    fn check_controllable(&self, idx: usize) -> Result<(), String> {
+        if idx >= self.cmds.len() {
+            return Err("No such command".to_string());
+        }
+        if self.external {
+            return Err("External mode: commands are not spawned".to_string());
+        }
+        if self.cfg.sequential {
+            return Err("Sequential run: use G / X / r for the whole sequence".to_string());
+        }
+        Ok(())
+    }
+
+    // Start one command (parallel mode) unless it is already running
+    pub fn start_cmd(&mut self, idx: usize) -> Result<(), String> {
+        self.check_controllable(idx)?;
+        if self.worker_alive(idx) {
+            return Err(format!("Already running: {}", self.cmds[idx].cmd));
+        }
+        if let Some(h) = self.workers[idx].handle.take() {
+            let _ = h.join();
+        }
+        self.workers[idx].stop.store(false, Ordering::SeqCst);
+        push_line(
+            &self.cmds[idx].output,
+            format!("[start] {}", self.cmds[idx].cmd),
+        );
+        self.spawn_one(idx, false);
+        self.started = true;
+        Ok(())
+    }
+
+    // Stop one command (parallel mode); the others keep running
+    pub fn stop_cmd(&mut self, idx: usize) -> Result<(), String> {
+        self.check_controllable(idx)?;
+        if !self.worker_alive(idx) {
+            return Err(format!("Not running: {}", self.cmds[idx].cmd));
+        }
+        push_line(&self.cmds[idx].output, "[stop requested]".to_string());
+        self.workers[idx].stop.store(true, Ordering::SeqCst);
+        if let Some(h) = self.workers[idx].handle.take() {
+            let _ = h.join();
+        }
+        // The menu's running indicator goes off with the last command
+        if !(0..self.cmds.len()).any(|i| self.worker_alive(i)) {
+            self.started = false;
         }
+        Ok(())
+    }
+
+    // Stop one command if it runs and start it again; a `unit:`/`container:` target is
+    // restarted before its logs are followed again
+    pub fn restart_cmd(&mut self, idx: usize) -> Result<(), String> {
+        self.check_controllable(idx)?;
+        if self.worker_alive(idx) {
+            self.stop_cmd(idx)?;
+        }
+        if let Some(h) = self.workers[idx].handle.take() {
+            let _ = h.join();
+        }
+        self.workers[idx].stop.store(false, Ordering::SeqCst);
+        push_line(
+            &self.cmds[idx].output,
+            format!("[restart] {}", self.cmds[idx].cmd),
+        );
+        self.spawn_one(idx, true);
+        self.started = true;
+        Ok(())
+    }
+
+    // Run the configured `external_kill_cmd`
+    pub fn run_kill_cmd(&mut self) -> Result<(), String> {
         if let Some(k) = &self.killer {
             k.kill();
-            true
         } else if let Some(cmd) = &self.cfg.external_kill_cmd {
             let _ = run_cmd_quiet(cmd);
-            true
         } else {
-            false
+            return Err("No external_kill_cmd configured".to_string());
+        }
+        for c in &self.cmds {
+            push_line(&c.output, "[kill command invoked]".to_string());
         }
+        Ok(())
     }
 
     fn spawn_parallel(&mut self) {
         // spawn one thread per command, each with retries
-        for (idx, cmd) in self.cmds.iter().enumerate() {
-            let lines_arc = Arc::clone(&cmd.output);
-            let cfg = self.cfg.clone();
-            let stop = self.workers[idx].stop.clone();
-            let raw = cmd.cmd.clone();
-            let spawner = self.spawner.clone();
-            self.workers[idx].handle = Some(thread::spawn(move || {
-                let _ = spawner.run_with_retries(&lines_arc, &raw, &cfg, None, &stop);
-            }));
+        let restart = std::mem::take(&mut self.restart_targets);
+        for idx in 0..self.cmds.len() {
+            self.spawn_one(idx, restart);
         }
     }
 
+    fn spawn_one(&mut self, idx: usize, restart: bool) {
+        let cmd = &self.cmds[idx];
+        let target = self.targets[idx].clone();
+        let lines_arc = Arc::clone(&cmd.output);
+        let health = Arc::clone(&cmd.health);
+        let cfg = self.cfg.clone();
+        let stop = self.workers[idx].stop.clone();
+        let raw = cmd.cmd.clone();
+        let spawner = self.spawner.clone();
+        self.workers[idx].handle = Some(thread::spawn(move || {
+            if let Some(t) = &target {
+                target_preamble(&lines_arc, t, restart);
+            }
+            let _ = spawner.run_with_retries(&lines_arc, &health, &raw, &cfg, None, &stop);
+        }));
+    }
+
     fn spawn_sequential(&mut self) {
-        let buffers: Vec<Arc<Mutex<VecDeque<String>>>> =
-            self.cmds.iter().map(|c| Arc::clone(&c.output)).collect();
+        let buffers: Vec<SharedOutput> = self.cmds.iter().map(|c| Arc::clone(&c.output)).collect();
+        let healths: Vec<CmdHealthRef> = self.cmds.iter().map(|c| Arc::clone(&c.health)).collect();
         let raw_cmds: Vec<String> = self.cmds.iter().map(|c| c.cmd.clone()).collect();
         let cfg = self.cfg.clone();
         // Take stop flags per worker
         let stops: Vec<Arc<AtomicBool>> = self.workers.iter().map(|w| w.stop.clone()).collect();
         let spawner = self.spawner.clone();
+        let targets = self.targets.clone();
+        let restart = std::mem::take(&mut self.restart_targets);
         self.seq_handle = Some(thread::spawn(move || {
             for (idx, raw) in raw_cmds.into_iter().enumerate() {
                 let lines_arc = Arc::clone(&buffers[idx]);
                 let stop = &stops[idx];
-                let ok = spawner.run_with_retries(&lines_arc, &raw, &cfg, Some(idx), stop);
+                if let Some(t) = &targets[idx] {
+                    target_preamble(&lines_arc, t, restart);
+                }
+                let ok = spawner.run_with_retries(
+                    &lines_arc,
+                    &healths[idx],
+                    &raw,
+                    &cfg,
+                    Some(idx),
+                    stop,
+                );
                 if stop.load(Ordering::SeqCst) {
                     // stop requested: abort remaining
                     break;
                 }
                 if !ok && cfg.stop_on_failure {
-                    for buf in buffers.iter().skip(idx + 1) {
+                    for (buf, health) in buffers.iter().zip(&healths).skip(idx + 1) {
                         push_line(buf, "[aborted by stop_on_failure]".to_string());
+                        set_state(health, CmdState::Stopped);
                     }
                     break;
                 }
@@ -263,3 +464,108 @@ impl WatchdogSession {
         }));
     }
 }
+
+// Shown before a target's logs: its status, after restarting it when the session restarts
+fn target_preamble(lines: &OutputBuffer, target: &Target, restart: bool) {
+    if restart {
+        let cmd = target.restart_cmd();
+        push_line(lines, format!("[restart] {cmd}"));
+        match run_cmd_output(&cmd) {
+            Some((0, _)) => {}
+            Some((code, out)) => push_line(lines, format!("[restart failed: exit {code}] {out}")),
+            None => push_line(lines, format!("[restart failed] could not run {cmd}")),
+        }
+    }
+    let status = match run_cmd_output(&target.status_cmd()) {
+        Some((_, out)) if !out.is_empty() => out,
+        _ => "unknown".to_string(),
+    };
+    push_line(lines, format!("[status] {status}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg(sequential: bool) -> WatchdogConfig {
+        WatchdogConfig {
+            sequential,
+            auto_restart: false,
+            max_retries: 0,
+            restart_delay_ms: 0,
+            allowed_exit_codes: vec![0],
+            stop_on_failure: false,
+            on_panic_exit_cmd: None,
+            stats: vec![],
+            external_check_cmd: None,
+            external_kill_cmd: None,
+            targets: vec![],
+        }
+    }
+
+    #[test]
+    fn single_commands_start_stop_and_restart_independently() {
+        let cmds = vec!["sleep 30".to_string(), "sleep 30".to_string()];
+        let session = WatchdogSession::create(cmds.clone(), cfg(false));
+        assert!(session.start(0).is_err());
+        session.stop(0).unwrap();
+        assert!(session.stop(0).is_err());
+        {
+            let s = session.lock().unwrap();
+            assert!(s.started);
+            assert!(!s.worker_alive(0) && s.worker_alive(1));
+        }
+        session.start(0).unwrap();
+        session.restart(1).unwrap();
+        assert!(session.lock().unwrap().cmds[1]
+            .output
+            .to_vec()
+            .contains(&"[restart] sleep 30".to_string()));
+        assert_eq!(
+            session.kill().unwrap_err(),
+            "No external_kill_cmd configured"
+        );
+        session.stop(0).unwrap();
+        session.stop(1).unwrap();
+        // Nothing left running: the menu indicator goes off
+        assert!(!session.lock().unwrap().started);
+
+        let seq = WatchdogSession::create(cmds, cfg(true));
+        assert!(seq.stop(0).unwrap_err().starts_with("Sequential run"));
+        seq.lock().unwrap().stop_all();
+    }
+
+    #[test]
+    fn running_commands_and_external_processes_expose_their_pids() {
+        let pid_of = |session: &WatchdogSessionRef| {
+            let health = Arc::clone(&session.lock().unwrap().cmds[0].health);
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+            loop {
+                let pid = health.lock().unwrap().pid;
+                if pid.is_some() || std::time::Instant::now() > deadline {
+                    return pid;
+                }
+                thread::sleep(std::time::Duration::from_millis(20));
+            }
+        };
+        let session = WatchdogSession::create(vec!["sleep 30".to_string()], cfg(false));
+        assert!(pid_of(&session).is_some());
+        session.stop(0).unwrap();
+        assert_eq!(
+            session.lock().unwrap().cmds[0].health.lock().unwrap().pid,
+            None
+        );
+
+        let mut external = cfg(false);
+        external.external_check_cmd = Some("echo '412 9001'".to_string());
+        let session = WatchdogSession::create(vec!["server".to_string()], external);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while session.lock().unwrap().external_pids.is_empty()
+            && std::time::Instant::now() < deadline
+        {
+            thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert_eq!(session.lock().unwrap().external_pids, [412, 9001]);
+        session.lock().unwrap().stop_all();
+    }
+}
@@ -1,12 +1,15 @@
-use std::collections::VecDeque;
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
 use crate::widgets::watchdog::config::WatchdogConfig;
-use crate::widgets::watchdog::util::{expand_vars, push_line};
+use crate::widgets::watchdog::health::{
+    record_restart, set_pid, set_state, CmdHealthRef, CmdState,
+};
+use crate::widgets::watchdog::output::SharedOutput;
+use crate::widgets::watchdog::util::push_line;
 
 use super::Spawner;
 
@@ -22,7 +25,8 @@ impl LocalSpawner {
 impl Spawner for LocalSpawner {
     fn run_with_retries(
         &self,
-        lines_arc: &Arc<Mutex<VecDeque<String>>>,
+        lines_arc: &SharedOutput,
+        health: &CmdHealthRef,
         cmdline: &str,
         cfg: &WatchdogConfig,
         _idx: Option<usize>,
@@ -33,9 +37,11 @@ impl Spawner for LocalSpawner {
             if stop.load(Ordering::SeqCst) {
                 // Aborted before start
                 push_line(lines_arc, "[stopped]".to_string());
+                set_state(health, CmdState::Stopped);
                 return false;
             }
-            let status_code_opt = run_once(lines_arc, cmdline, stop);
+            set_state(health, CmdState::Running);
+            let status_code_opt = run_once(lines_arc, cmdline, stop, Some(health));
             let mut success = false;
             if let Some(code) = status_code_opt {
                 success =
@@ -43,11 +49,13 @@ impl Spawner for LocalSpawner {
             }
             if success {
                 push_line(lines_arc, "[done]".to_string());
+                set_state(health, CmdState::Done);
                 return true;
             }
             // failure path
             if stop.load(Ordering::SeqCst) {
                 push_line(lines_arc, "[stopped]".to_string());
+                set_state(health, CmdState::Stopped);
                 return false;
             }
             if cfg.auto_restart && attempt < cfg.max_retries {
@@ -65,19 +73,22 @@ impl Spawner for LocalSpawner {
                 while waited < sleep_ms {
                     if stop.load(Ordering::SeqCst) {
                         push_line(lines_arc, "[stopped]".to_string());
+                        set_state(health, CmdState::Stopped);
                         return false;
                     }
                     let step = 50;
                     thread::sleep(Duration::from_millis(step));
                     waited += step;
                 }
+                record_restart(health);
                 attempt = next;
                 continue;
             } else {
                 push_line(lines_arc, "[panic: retries exhausted]".to_string());
+                set_state(health, CmdState::Failed);
                 if let Some(hook) = &cfg.on_panic_exit_cmd {
                     push_line(lines_arc, format!("[panic hook] running: {hook}"));
-                    let _ = run_once(lines_arc, hook, stop);
+                    let _ = run_once(lines_arc, hook, stop, None);
                 }
                 return false;
             }
@@ -85,12 +96,14 @@ impl Spawner for LocalSpawner {
     }
 }
 
+// Run `cmdline` once; `health` gets the child's pid while it runs
 fn run_once(
-    lines_arc: &Arc<Mutex<VecDeque<String>>>,
+    lines_arc: &SharedOutput,
     cmdline: &str,
     stop: &Arc<AtomicBool>,
+    health: Option<&CmdHealthRef>,
 ) -> Option<i32> {
-    let expanded = expand_vars(cmdline);
+    let expanded = crate::services::interpolate::env(cmdline);
     let parts = shlex::split(&expanded).unwrap_or_default();
     if parts.is_empty() {
         push_line(lines_arc, "[error] empty command".to_string());
@@ -111,6 +124,9 @@ fn run_once(
             return None;
         }
     };
+    if let Some(h) = health {
+        set_pid(h, Some(child.id()));
+    }
     // Concurrently read stdout and stderr
     let mut handles: Vec<std::thread::JoinHandle<()>> = Vec::new();
     if let Some(stdout) = child.stdout.take() {
@@ -142,6 +158,9 @@ fn run_once(
         }
         match child.try_wait() {
             Ok(Some(status)) => {
+                if let Some(h) = health {
+                    set_pid(h, None);
+                }
                 // Ensure readers are finished
                 for h in handles {
                     let _ = h.join();
@@ -1,15 +1,17 @@
-use std::collections::VecDeque;
 use std::sync::atomic::AtomicBool;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use crate::widgets::watchdog::config::WatchdogConfig;
+use crate::widgets::watchdog::health::CmdHealthRef;
+use crate::widgets::watchdog::output::SharedOutput;
 
 pub mod local;
 
 pub trait Spawner: Send + Sync {
     fn run_with_retries(
         &self,
-        lines_arc: &Arc<Mutex<VecDeque<String>>>,
+        lines_arc: &SharedOutput,
+        health: &CmdHealthRef,
         cmdline: &str,
         cfg: &WatchdogConfig,
         idx: Option<usize>,
@@ -0,0 +1,113 @@
+use crate::chi_core::argv::quote;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+// `unit: nginx.service` / `container: api` on a watchdog: one section per target that follows
+// its logs (journalctl / docker logs), starts with its status, and whose restart (`r`, `R`)
+// restarts the service or container itself before following again.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TargetKind {
+    Unit,
+    Container,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Target {
+    pub kind: TargetKind,
+    pub name: String,
+}
+
+impl Target {
+    // Followed in the target's section
+    pub fn logs_cmd(&self) -> String {
+        let name = quote(&self.name);
+        match self.kind {
+            TargetKind::Unit => format!("journalctl -u {name} -f -n 50 --no-pager -o short-iso"),
+            TargetKind::Container => format!("docker logs -f --tail 50 {name}"),
+        }
+    }
+
+    // Prints a one-word state (active/failed, running/exited)
+    pub fn status_cmd(&self) -> String {
+        let name = quote(&self.name);
+        match self.kind {
+            TargetKind::Unit => format!("systemctl is-active {name}"),
+            TargetKind::Container => format!("docker inspect -f '{{{{.State.Status}}}}' {name}"),
+        }
+    }
+
+    pub fn restart_cmd(&self) -> String {
+        let name = quote(&self.name);
+        match self.kind {
+            TargetKind::Unit => format!("systemctl restart {name}"),
+            TargetKind::Container => format!("docker restart {name}"),
+        }
+    }
+}
+
+// Names from a `unit:` / `container:` value: one name or a list
+fn names(v: Option<&JsonValue>) -> Vec<String> {
+    let list = match v {
+        Some(JsonValue::String(s)) => vec![s.as_str()],
+        Some(JsonValue::Array(arr)) => arr.iter().filter_map(|x| x.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    list.into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// Targets of a menu item (`unit:` before `container:`)
+pub fn from_fields(unit: Option<&JsonValue>, container: Option<&JsonValue>) -> Vec<Target> {
+    let units = names(unit).into_iter().map(|name| Target {
+        kind: TargetKind::Unit,
+        name,
+    });
+    let containers = names(container).into_iter().map(|name| Target {
+        kind: TargetKind::Container,
+        name,
+    });
+    units.chain(containers).collect()
+}
+
+// Targets of a JSON watchdog spec
+pub fn from_value(v: &JsonValue) -> Vec<Target> {
+    from_fields(v.get("unit"), v.get("container"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn units_and_containers_expand_to_lifecycle_commands() {
+        let targets = from_value(&json!({
+            "unit": "nginx.service",
+            "container": ["api", " ", "my db"],
+        }));
+        assert_eq!(targets.len(), 3);
+        let (unit, api, db) = (&targets[0], &targets[1], &targets[2]);
+        assert_eq!(
+            unit.logs_cmd(),
+            "journalctl -u nginx.service -f -n 50 --no-pager -o short-iso"
+        );
+        assert_eq!(unit.status_cmd(), "systemctl is-active nginx.service");
+        assert_eq!(unit.restart_cmd(), "systemctl restart nginx.service");
+        assert_eq!(api.logs_cmd(), "docker logs -f --tail 50 api");
+        assert_eq!(
+            api.status_cmd(),
+            "docker inspect -f '{{.State.Status}}' api"
+        );
+        assert_eq!(
+            shlex::split(&api.status_cmd()).unwrap()[3],
+            "{{.State.Status}}"
+        );
+        // Names are quoted for the command line
+        assert_eq!(db.restart_cmd(), "docker restart 'my db'");
+        assert!(from_value(&json!({"commands": ["x"]})).is_empty());
+    }
+}
@@ -1,36 +1,14 @@
-use crate::widgets::watchdog::output::RingBufferSink;
-use regex::Regex;
-use std::collections::HashMap;
-use std::collections::VecDeque;
+use crate::widgets::watchdog::output::OutputBuffer;
 use std::process::{Command, Stdio};
-use std::sync::{Arc, Mutex};
 
-pub fn push_line(buf: &Arc<Mutex<VecDeque<String>>>, s: String) {
-    let sink = RingBufferSink::new(Arc::clone(buf));
-    sink.push_line(s);
-}
-
-pub fn expand_vars(s: &str) -> String {
-    // matches ${VAR}
-    let re = Regex::new(r"\$\{([A-Z0-9_]+)\}").unwrap();
-    let env_map: HashMap<String, String> = std::env::vars().collect();
-    re.replace_all(s, |caps: &regex::Captures| {
-        let key = &caps[1];
-        if key == "APP_BIN" {
-            if let Ok(v) = std::env::var("CHI_APP_BIN") {
-                return v;
-            }
-            return "example-app".to_string();
-        }
-        env_map.get(key).cloned().unwrap_or_default()
-    })
-    .to_string()
+pub fn push_line(buf: &OutputBuffer, s: String) {
+    buf.push(s);
 }
 
 // Execute a command line quietly (no captured stdout/stderr), returning exit code.
 // Returns None on spawn error or if the process had no exit code.
 pub fn run_cmd_quiet(cmdline: &str) -> Option<i32> {
-    let expanded = expand_vars(cmdline);
+    let expanded = crate::services::interpolate::env(cmdline);
     let parts = shlex::split(&expanded).unwrap_or_default();
     if parts.is_empty() {
         return None;
@@ -48,3 +26,63 @@ pub fn run_cmd_quiet(cmdline: &str) -> Option<i32> {
         Err(_) => None,
     }
 }
+
+// Execute a check command and return its exit code with the pids it printed: the leading
+// number of each stdout line (`pgrep` output, `pidof` words). Returns None on spawn error.
+pub fn run_cmd_pids(cmdline: &str) -> Option<(i32, Vec<u32>)> {
+    let expanded = crate::services::interpolate::env(cmdline);
+    let parts = shlex::split(&expanded).unwrap_or_default();
+    let (program, args) = parts.split_first()?;
+    let out = Command::new(program)
+        .args(args)
+        .env("CHI_TUI_JSON", "1")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    Some((out.status.code().unwrap_or(-1), parse_pids(&out.stdout)))
+}
+
+fn parse_pids(stdout: &[u8]) -> Vec<u32> {
+    let text = String::from_utf8_lossy(stdout);
+    let mut pids: Vec<u32> = text
+        .lines()
+        .flat_map(|l| {
+            let words: Vec<&str> = l.split_whitespace().collect();
+            // A line of only numbers lists pids; otherwise only its first word can be one
+            if words.iter().all(|w| w.parse::<u32>().is_ok()) {
+                words
+            } else {
+                words.into_iter().take(1).collect()
+            }
+        })
+        .filter_map(|w| w.parse().ok())
+        .collect();
+    pids.dedup();
+    pids
+}
+
+// Execute a command line and return its exit code (-1 when killed) with the first line it
+// printed (stdout, else stderr). Returns None on spawn error.
+pub fn run_cmd_output(cmdline: &str) -> Option<(i32, String)> {
+    let expanded = crate::services::interpolate::env(cmdline);
+    let parts = shlex::split(&expanded).unwrap_or_default();
+    let (program, args) = parts.split_first()?;
+    let out = Command::new(program)
+        .args(args)
+        .env("CHI_TUI_JSON", "1")
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    let first = |b: &[u8]| {
+        String::from_utf8_lossy(b)
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .map(String::from)
+    };
+    let line = first(&out.stdout)
+        .or_else(|| first(&out.stderr))
+        .unwrap_or_default();
+    Some((out.status.code().unwrap_or(-1), line))
+}
@@ -1,7 +1,10 @@
 use super::config::WatchdogConfig;
-use super::session::{CmdLog, WatchdogSessionRef};
+use super::health::CmdState;
+use super::metrics::{MetricsSampler, ProcMetrics};
+use super::session::{CmdLog, SessionControls, WatchdogSessionRef};
 use super::util::push_line;
 use super::StatsAggregator;
+use crate::theme::Status;
 use crate::widgets::chrome::panel_block;
 use crossterm::event::KeyCode;
 use ratatui::prelude::*;
@@ -16,7 +19,6 @@ pub struct WatchdogWidget {
     pub cmds: Vec<CmdLog>,
     scroll_offsets: Vec<u16>,
     last_viewport_h: u16,
-    #[allow(dead_code)]
     cfg: WatchdogConfig,
     stats: Option<StatsAggregator>,
     // Session reference (source of truth for process lifecycle)
@@ -25,8 +27,15 @@ pub struct WatchdogWidget {
     auto_follow: bool,
     // Focused subpane index (when this widget is focused in Pane B)
     focused_idx: usize,
+    // Quick-jump labels by subpane index (set while hint mode is active)
+    pub hints: Vec<(usize, String)>,
+    // CPU/RSS of the processes behind the sections
+    metrics: MetricsSampler,
 }
 
+// Width of the metrics column next to a section's output
+const METRICS_W: u16 = 14;
+
 impl WatchdogWidget {
     // Create a fresh session and attach to it.
     pub fn new(title: impl Into<String>, commands: Vec<String>, cfg: WatchdogConfig) -> Self {
@@ -39,6 +48,7 @@ impl WatchdogWidget {
                 .map(|c| CmdLog {
                     cmd: c.cmd.clone(),
                     output: Arc::clone(&c.output),
+                    health: Arc::clone(&c.health),
                 })
                 .collect()
         };
@@ -58,6 +68,8 @@ impl WatchdogWidget {
             session,
             auto_follow: true,
             focused_idx: 0,
+            hints: Vec::new(),
+            metrics: MetricsSampler::default(),
         }
     }
 
@@ -72,6 +84,7 @@ impl WatchdogWidget {
                     .map(|c| CmdLog {
                         cmd: c.cmd.clone(),
                         output: Arc::clone(&c.output),
+                        health: Arc::clone(&c.health),
                     })
                     .collect::<Vec<_>>(),
                 s.cfg.clone(),
@@ -93,6 +106,8 @@ impl WatchdogWidget {
             session: Arc::clone(session),
             auto_follow: true,
             focused_idx: 0,
+            hints: Vec::new(),
+            metrics: MetricsSampler::default(),
         };
         // Add visible notice
         for c in &widget.cmds {
@@ -109,6 +124,14 @@ impl WatchdogWidget {
 
 impl crate::widgets::Widget for WatchdogWidget {
     fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, _tick: u64) {
+        // Aggregate status row on top
+        let mut area = area;
+        if area.height > 3 {
+            let summary_area = Rect { height: 1, ..area };
+            f.render_widget(Paragraph::new(self.summary_line()), summary_area);
+            area.y += 1;
+            area.height -= 1;
+        }
         // Reserve footer area for stats if configured
         let stats_h: u16 = self.stats.as_ref().map(|s| s.len() as u16).unwrap_or(0);
         let mut logs_area = area;
@@ -132,35 +155,96 @@ impl crate::widgets::Widget for WatchdogWidget {
             .split(logs_area);
 
         self.last_viewport_h = logs_area.height.saturating_sub(2);
+        let (external, external_pids) = self
+            .session
+            .lock()
+            .map(|s| (s.external, s.external_pids.clone()))
+            .unwrap_or_default();
+        let has_kill = self.cfg.external_kill_cmd.is_some();
+        // Processes behind each section: its command's current run, or everything the external
+        // check found
+        let section_pids: Vec<Vec<u32>> = self
+            .cmds
+            .iter()
+            .map(|c| match external {
+                true => external_pids.clone(),
+                false => c
+                    .health
+                    .lock()
+                    .ok()
+                    .and_then(|h| h.pid)
+                    .into_iter()
+                    .collect(),
+            })
+            .collect();
+        let mut all_pids: Vec<u32> = section_pids.iter().flatten().copied().collect();
+        all_pids.sort_unstable();
+        all_pids.dedup();
+        self.metrics.poll(all_pids);
 
         for (i, (cmd, chunk)) in self.cmds.iter().zip(chunks.iter()).enumerate() {
+            let pids = &section_pids[i];
+            let sampled = ProcMetrics::sum(pids.iter().filter_map(|p| self.metrics.get(*p)));
+            let (chunk, metrics_chunk) = match sampled {
+                Some(m) if chunk.width >= METRICS_W * 3 => {
+                    let [logs, col] =
+                        Layout::horizontal([Constraint::Min(0), Constraint::Length(METRICS_W)])
+                            .areas(*chunk);
+                    (logs, Some((col, m)))
+                }
+                _ => (*chunk, None),
+            };
+            let chunk = &chunk;
             // clamp scroll per section based on total length
-            let (_total_len, mut visible_lines): (usize, Vec<Line>) =
-                if let Ok(q) = cmd.output.lock() {
-                    let total = q.len();
-                    // Viewport height (minus borders)
-                    let viewport = chunk.height.saturating_sub(2);
-                    let max_scroll = (total as u16).saturating_sub(viewport);
-                    if let Some(off) = self.scroll_offsets.get_mut(i) {
-                        if self.auto_follow || *off > max_scroll {
-                            *off = max_scroll;
-                        }
-                    }
-                    let start = self.scroll_offsets[i] as usize;
-                    let end = start.saturating_add(viewport as usize).min(total);
-                    let mut lines: Vec<Line> = Vec::with_capacity(end.saturating_sub(start));
-                    for s in q.iter().skip(start).take(end.saturating_sub(start)) {
-                        lines.push(Line::from(s.clone()));
-                    }
-                    (total, lines)
-                } else {
-                    (0usize, Vec::new())
+            // Only the rows on screen are copied out of the buffer
+            let offsets = &mut self.scroll_offsets;
+            let auto_follow = self.auto_follow;
+            let mut visible_lines: Vec<Line> = cmd.output.read(|q| {
+                let total = q.len();
+                // Viewport height (minus borders)
+                let viewport = chunk.height.saturating_sub(2);
+                let max_scroll = total
+                    .saturating_sub(viewport as usize)
+                    .min(u16::MAX as usize) as u16;
+                let Some(off) = offsets.get_mut(i) else {
+                    return Vec::new();
                 };
+                if auto_follow || *off > max_scroll {
+                    *off = max_scroll;
+                }
+                q.range(*off as usize, viewport as usize)
+                    .map(|s| Line::from(s.to_string()))
+                    .collect()
+            });
 
             // Render the visible slice
-            let block = panel_block(&cmd.cmd, focused && self.focused_idx == i);
+            let title = match self.hints.iter().find(|(idx, _)| *idx == i) {
+                Some((_, label)) => format!("[{label}] {}", cmd.cmd),
+                None => cmd.cmd.clone(),
+            };
+            let title = crate::widgets::chrome::fit_title(&title, *chunk);
+            let mut block = panel_block(&title, focused && self.focused_idx == i);
+            // Action bar: the command's state, plus its keys on the focused section
+            if !external {
+                let state = cmd
+                    .health
+                    .lock()
+                    .map(|h| h.state)
+                    .unwrap_or(CmdState::Pending);
+                let mut bar = format!(" {} ", state_label(state));
+                if focused && self.focused_idx == i {
+                    bar.push_str("• s start • x stop • R restart ");
+                    if has_kill {
+                        bar.push_str("• k kill ");
+                    }
+                }
+                block = block.title_bottom(Line::from(bar).style(state_style(state)));
+            }
             let p = Paragraph::new(std::mem::take(&mut visible_lines)).block(block);
             f.render_widget(p, *chunk);
+            if let Some((col, m)) = metrics_chunk {
+                f.render_widget(metrics_column(&m, pids), col);
+            }
         }
 
         // Footer: stats + controls
@@ -254,50 +338,28 @@ impl crate::widgets::Widget for WatchdogWidget {
                 }
                 // Fallthrough: no session
             }
-            KeyCode::Char('s') => {
-                // Toggle start/stop or kill in external mode
-                if let Ok(mut s) = self.session.lock() {
-                    if s.external {
-                        let ok = s.kill_external();
-                        if ok {
-                            for c in &self.cmds {
-                                push_line(&c.output, "[external] kill invoked".to_string());
-                            }
-                            return vec![crate::app::Effect::ShowToast {
-                                text: "External kill invoked".to_string(),
-                                level: crate::ui::ToastLevel::Info,
-                                seconds: 2,
-                            }];
-                        } else {
-                            return vec![crate::app::Effect::ShowToast {
-                                text: "External mode: no kill command configured".to_string(),
-                                level: crate::ui::ToastLevel::Error,
-                                seconds: 3,
-                            }];
-                        }
-                    } else if s.started {
-                        // Note in each pane that stop was requested
-                        for c in &self.cmds {
-                            push_line(&c.output, "[stop requested]".to_string());
-                        }
-                        s.stop_all();
-                        return vec![crate::app::Effect::ShowToast {
-                            text: "Watchdog stop requested".to_string(),
-                            level: crate::ui::ToastLevel::Info,
-                            seconds: 2,
-                        }];
-                    } else {
-                        s.start();
-                        return vec![crate::app::Effect::ShowToast {
-                            text: "Watchdog started".to_string(),
-                            level: crate::ui::ToastLevel::Success,
-                            seconds: 2,
-                        }];
-                    }
-                }
-                // Fallthrough: no session
+            // Action bar of the focused command
+            KeyCode::Char('s') | KeyCode::Char('x') | KeyCode::Char('R') => {
+                let idx = self.focused_pane();
+                let cmd = self
+                    .cmds
+                    .get(idx)
+                    .map(|c| c.cmd.clone())
+                    .unwrap_or_default();
+                let (res, done) = match key {
+                    KeyCode::Char('s') => (self.session.start(idx), "Started"),
+                    KeyCode::Char('x') => (self.session.stop(idx), "Stopped"),
+                    _ => (self.session.restart(idx), "Restarting"),
+                };
+                return Self::control_toast(res, format!("{done}: {cmd}"));
+            }
+            KeyCode::Char('k') => {
+                let res = self.session.kill();
+                return Self::control_toast(res, "Kill command invoked".to_string());
             }
-            KeyCode::Char('f') | KeyCode::End => {
+            KeyCode::Char('X') => return self.stop_all(),
+            KeyCode::Char('G') => return self.start_all(),
+            KeyCode::End => {
                 // Resume auto-follow and jump to bottom on next render
                 self.auto_follow = true;
                 return vec![crate::app::Effect::ShowToast {
@@ -349,6 +411,42 @@ impl crate::widgets::Widget for WatchdogWidget {
         Vec::new()
     }
 
+    fn hints(&self) -> crate::widgets::KeyHints {
+        let mut hints = crate::widgets::KeyHints::new(
+            "Tab next pane • Shift+Tab prev • ↑/↓/PgUp/PgDn/Home/End scroll (all panes) • End follow • f jump • s/x/R start/stop/restart section",
+        );
+        if self.cfg.external_kill_cmd.is_some() {
+            hints.push("k kill cmd");
+        }
+        hints.push("G/X/r start/stop/restart all");
+        hints
+    }
+
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        use crate::widgets::WidgetAction;
+        let mut actions = vec![
+            WidgetAction::key("Start command", "s", KeyCode::Char('s')),
+            WidgetAction::key("Stop command", "x", KeyCode::Char('x')),
+            WidgetAction::key("Restart command", "R", KeyCode::Char('R')),
+        ];
+        if self.cfg.external_kill_cmd.is_some() {
+            actions.push(WidgetAction::key(
+                "Run kill command",
+                "k",
+                KeyCode::Char('k'),
+            ));
+        }
+        actions.extend([
+            WidgetAction::key("Start all", "G", KeyCode::Char('G')),
+            WidgetAction::key("Stop all", "X", KeyCode::Char('X')),
+            WidgetAction::key("Restart all (clear logs)", "r", KeyCode::Char('r')),
+            WidgetAction::key("Follow output", "End", KeyCode::End),
+        ]);
+        actions.push(WidgetAction::export_image());
+        actions.extend(WidgetAction::content());
+        actions
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -357,6 +455,143 @@ impl crate::widgets::Widget for WatchdogWidget {
     }
 }
 
+impl WatchdogWidget {
+    // "● 4 running, 1 failed, 2 restarts in last 5m"
+    fn summary_line(&self) -> Line<'static> {
+        let Ok(s) = self.session.lock() else {
+            return Line::default();
+        };
+        let theme = crate::theme::active();
+        if s.external {
+            let (text, glyph, col) = if s.external_running {
+                (
+                    "external process running",
+                    theme.status_glyph(Status::Ok),
+                    theme.success,
+                )
+            } else {
+                ("external process not running", "●", Color::DarkGray)
+            };
+            return Line::from(vec![
+                Span::styled(format!("{glyph} "), Style::default().fg(col)),
+                Span::styled(text, Style::default().fg(col)),
+            ]);
+        }
+        let summary = s.summary();
+        let (glyph, col) = if summary.failed > 0 {
+            (theme.status_glyph(Status::Fail), theme.error)
+        } else if summary.running > 0 {
+            (theme.status_glyph(Status::Ok), theme.success)
+        } else {
+            ("●", Color::DarkGray)
+        };
+        Line::from(vec![
+            Span::styled(format!("{glyph} "), Style::default().fg(col)),
+            Span::styled(
+                summary.line(),
+                Style::default().fg(col).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "   G start all • X stop all • r restart all",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])
+    }
+
+    // Toast for a control: what was done, or why it was refused
+    fn control_toast(res: Result<(), String>, done: String) -> Vec<crate::app::Effect> {
+        let (text, level) = match res {
+            Ok(()) => (done, crate::ui::ToastLevel::Info),
+            Err(e) => (e, crate::ui::ToastLevel::Error),
+        };
+        vec![crate::app::Effect::ShowToast {
+            text,
+            level,
+            seconds: 2,
+        }]
+    }
+
+    fn stop_all(&mut self) -> Vec<crate::app::Effect> {
+        if let Ok(mut s) = self.session.lock() {
+            if !s.external && s.started {
+                for c in &self.cmds {
+                    push_line(&c.output, "[stop requested]".to_string());
+                }
+                s.stop_all();
+                return vec![crate::app::Effect::ShowToast {
+                    text: "Stopped all commands".to_string(),
+                    level: crate::ui::ToastLevel::Info,
+                    seconds: 2,
+                }];
+            }
+        }
+        Vec::new()
+    }
+
+    fn start_all(&mut self) -> Vec<crate::app::Effect> {
+        let Ok(mut s) = self.session.lock() else {
+            return Vec::new();
+        };
+        let (text, level) = match s.start_all() {
+            0 => ("Nothing to start", crate::ui::ToastLevel::Info),
+            _ => ("Started all commands", crate::ui::ToastLevel::Success),
+        };
+        vec![crate::app::Effect::ShowToast {
+            text: text.to_string(),
+            level,
+            seconds: 2,
+        }]
+    }
+}
+
+// "CPU 3.5%", "RSS 20M" and the pid (or the number of external processes)
+fn metrics_column(m: &ProcMetrics, pids: &[u32]) -> Paragraph<'static> {
+    let theme = crate::theme::active();
+    let row = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{label} "), theme.text_muted()),
+            Span::raw(value),
+        ])
+    };
+    let cpu_style = if m.cpu >= 80.0 {
+        theme.status_style(Status::Warn)
+    } else {
+        Style::default()
+    };
+    let proc_row = match pids {
+        [pid] => row("PID", pid.to_string()),
+        _ => row("×", format!("{} procs", pids.len())),
+    };
+    Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("CPU ", theme.text_muted()),
+            Span::styled(format!("{:.1}%", m.cpu), cpu_style),
+        ]),
+        row("RSS", crate::widgets::processes::human_kib(m.rss)),
+        proc_row,
+    ])
+    .block(panel_block("metrics", false))
+}
+
+fn state_label(state: CmdState) -> &'static str {
+    match state {
+        CmdState::Pending => "pending",
+        CmdState::Running => "running",
+        CmdState::Done => "done",
+        CmdState::Failed => "failed",
+        CmdState::Stopped => "stopped",
+    }
+}
+
+fn state_style(state: CmdState) -> Style {
+    let theme = crate::theme::active();
+    match state {
+        CmdState::Running => Style::default().fg(theme.success),
+        CmdState::Failed => Style::default().fg(theme.error),
+        _ => Style::default().fg(Color::DarkGray),
+    }
+}
+
 // Inherent methods for external focus control (used by UI Tab cycling)
 impl WatchdogWidget {
     pub fn pane_count(&self) -> usize {
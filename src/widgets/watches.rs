@@ -0,0 +1,86 @@
+use crate::chi_core::watches::display;
+use crate::ui::{AppState, WatchPrompt};
+use ratatui::prelude::*;
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use std::time::Instant;
+
+// Watch bar: one banner line with every watched value. Values that just changed are
+// highlighted with their direction (▲/▼ for numbers), met predicates are marked as alerts.
+pub fn banner_lines(state: &AppState) -> Vec<Line<'static>> {
+    if state.watches.is_empty() {
+        return vec![];
+    }
+    let theme = &state.theme;
+    let badge = Style::default()
+        .fg(theme.bg)
+        .bg(theme.accent)
+        .add_modifier(Modifier::BOLD);
+    let now = Instant::now();
+    let mut spans = vec![Span::styled(" WATCH ", badge)];
+    for w in state.watches.iter() {
+        let value = match &w.value {
+            Some(v) if !v.is_null() => display(v),
+            _ => "—".to_string(),
+        };
+        let trend = match (w.previous.as_ref(), w.value.as_ref()) {
+            (Some(a), Some(b)) if w.changed(now) => match (a.as_f64(), b.as_f64()) {
+                (Some(a), Some(b)) if b > a => " ▲",
+                (Some(a), Some(b)) if b < a => " ▼",
+                _ => " •",
+            },
+            _ => "",
+        };
+        let style = if w.firing {
+            theme.text_error().add_modifier(Modifier::BOLD)
+        } else if w.changed(now) {
+            theme.text_active_bold()
+        } else {
+            Style::default()
+        };
+        let mark = if w.firing { "! " } else { "" };
+        spans.push(Span::styled(
+            format!("  {mark}{} ", w.expr),
+            theme.text_muted(),
+        ));
+        spans.push(Span::styled(format!("{value}{trend}"), style));
+    }
+    vec![Line::from(spans)]
+}
+
+// Modal input for a watch expression on the focused result ('=')
+pub fn draw_prompt(f: &mut Frame, screen: Rect, prompt: &WatchPrompt) {
+    let theme = crate::theme::active();
+    let width = screen.width.saturating_sub(4).min(72);
+    let rect = Rect {
+        x: screen.x + (screen.width.saturating_sub(width)) / 2,
+        y: screen.y + screen.height.saturating_sub(7) / 2,
+        width,
+        height: 7.min(screen.height),
+    };
+    let mut lines = vec![
+        Line::from(Span::raw(format!("$ {}", prompt.source))),
+        Line::from(vec![
+            Span::styled("Watch: ", theme.text_active_bold()),
+            Span::raw(format!("{}▏", prompt.input)),
+        ]),
+    ];
+    match &prompt.error {
+        Some(e) => lines.push(Line::from(Span::styled(e.clone(), theme.text_error()))),
+        None => lines.push(Line::from(Span::styled(
+            ".data.replicas • .items[0].status != \"Healthy\" • .count > 5",
+            theme.text_muted(),
+        ))),
+    }
+    lines.push(Line::from(Span::styled(
+        "Enter watch (again: unwatch, empty: clear) • Esc cancel",
+        theme.text_muted(),
+    )));
+    let p = Paragraph::new(lines)
+        .block(crate::widgets::chrome::panel_block(
+            "Watch expression",
+            true,
+        ))
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
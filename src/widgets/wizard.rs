@@ -0,0 +1,267 @@
+use crate::app::Effect;
+use crate::widgets::form::FormState;
+use crate::widgets::form_widget::FormWidget;
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use std::any::Any;
+
+// `type: wizard`: a form split into steps, each a form spec of its own. Next checks the step
+// and moves on, Back returns with every value kept, and only the last step submits: one
+// command line with the fields of all steps, in step order.
+pub struct WizardWidget {
+    steps: Vec<FormWidget>,
+    current: usize,
+    submit_cmd: Option<String>,
+    // `mutex:` of the item that opened the wizard
+    pub mutex: Option<String>,
+}
+
+impl WizardWidget {
+    pub fn new(title: &str, steps: Vec<FormState>, submit_cmd: Option<String>) -> Self {
+        let count = steps.len();
+        let steps = steps
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut form)| {
+                form.title = format!("{title} — {}", form.title);
+                form.step = Some((i, count));
+                FormWidget::new(form)
+            })
+            .collect();
+        Self {
+            steps,
+            current: 0,
+            submit_cmd,
+            mutex: None,
+        }
+    }
+
+    pub fn step(&self) -> &FormWidget {
+        &self.steps[self.current]
+    }
+
+    pub fn step_mut(&mut self) -> &mut FormWidget {
+        &mut self.steps[self.current]
+    }
+
+    // Every step's fields as one form with the wizard's submit command
+    pub fn merged(&self) -> FormState {
+        FormState {
+            title: self.step().form.title.clone(),
+            fields: self
+                .steps
+                .iter()
+                .flat_map(|s| s.form.fields.iter().cloned())
+                .collect(),
+            submit_cmd: self.submit_cmd.clone(),
+            ..Default::default()
+        }
+    }
+
+    // Next: check this step, then go on (options of the next step start loading) or submit
+    fn next(&mut self) -> Vec<Effect> {
+        if !crate::widgets::form::validate_form(&mut self.step_mut().form) {
+            return Vec::new();
+        }
+        if self.current + 1 == self.steps.len() {
+            return self.submit();
+        }
+        self.current += 1;
+        self.step_mut().prefetch_options()
+    }
+
+    fn back(&mut self) {
+        if self.current > 0 {
+            self.step_mut().form.message = None;
+            self.current -= 1;
+        }
+    }
+
+    // The combined payload; a step that no longer validates is shown instead
+    fn submit(&mut self) -> Vec<Effect> {
+        for i in 0..self.steps.len() {
+            if !crate::widgets::form::validate_form(&mut self.steps[i].form) {
+                self.current = i;
+                return Vec::new();
+            }
+        }
+        let Some(cmdline) = crate::widgets::form::build_cmdline(&self.merged()) else {
+            self.step_mut().form.message = Some("No submit command configured".into());
+            return Vec::new();
+        };
+        vec![Effect::SubmitForm {
+            pane: crate::ui::PanelPane::B,
+            cmdline,
+            mutex: self.mutex.clone(),
+        }]
+    }
+
+    // Submitted: no step has unsaved values any more
+    pub fn saved(&mut self) {
+        for s in &mut self.steps {
+            crate::widgets::form::capture_initial(&mut s.form);
+            s.form.dirty = false;
+        }
+    }
+}
+
+// The form a Pane B widget edits: a form itself, or the current step of a wizard
+pub fn current_form(any: &dyn Any) -> Option<&FormWidget> {
+    match any.downcast_ref::<WizardWidget>() {
+        Some(wz) => Some(wz.step()),
+        None => any.downcast_ref(),
+    }
+}
+
+pub fn current_form_mut(any: &mut dyn Any) -> Option<&mut FormWidget> {
+    if any.is::<WizardWidget>() {
+        return any.downcast_mut::<WizardWidget>().map(|wz| wz.step_mut());
+    }
+    any.downcast_mut()
+}
+
+impl crate::widgets::Widget for WizardWidget {
+    fn render(&mut self, f: &mut Frame, area: Rect, focused: bool, tick: u64) {
+        self.step_mut().render(f, area, focused, tick);
+    }
+    fn on_key(&mut self, key: KeyCode) -> Vec<Effect> {
+        let fw = self.step();
+        let idle = !fw.form.editing
+            && !fw.preview_open()
+            && !fw.batch_open()
+            && fw.form.confirm.is_none()
+            && !fw.form.disabled;
+        // Enter on the buttons: [ Next ] / [ Submit ] and Back
+        if idle && key == KeyCode::Enter {
+            let buttons = fw.form.fields.len();
+            if fw.form.selected == buttons {
+                return self.next();
+            }
+            if fw.form.selected == buttons + 1 {
+                self.back();
+                return Vec::new();
+            }
+        }
+        self.step_mut().on_key(key)
+    }
+    fn actions(&self) -> Vec<crate::widgets::WidgetAction> {
+        self.step().actions()
+    }
+    fn hints(&self) -> crate::widgets::KeyHints {
+        self.step().hints()
+    }
+    fn unsaved(&self) -> bool {
+        self.steps.iter().any(|s| s.form.dirty && !s.form.disabled)
+    }
+    fn save(&mut self) -> Vec<Effect> {
+        self.submit()
+    }
+    fn discard(&mut self) {
+        for s in &mut self.steps {
+            s.discard();
+        }
+        self.current = 0;
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::form::{FieldKind, FieldValue, FormField};
+    use crate::widgets::Widget;
+
+    fn text_field(name: &str, required: bool) -> FormField {
+        FormField {
+            name: name.into(),
+            label: name.into(),
+            required,
+            kind: FieldKind::Text,
+            value: FieldValue::Text(String::new()),
+            error: None,
+            text_min_len: None,
+            text_max_len: None,
+            text_pattern: None,
+            textarea_max_lines: None,
+            dyn_options_cmd: None,
+            dyn_unwrap: None,
+            dyn_loaded: false,
+            dyn_loaded_at: None,
+            group: None,
+            order: None,
+        }
+    }
+
+    fn step(title: &str, fields: Vec<FormField>) -> FormState {
+        FormState {
+            title: title.into(),
+            fields,
+            ..Default::default()
+        }
+    }
+
+    fn type_text(w: &mut WizardWidget, text: &str) {
+        w.on_key(KeyCode::Enter);
+        for c in text.chars() {
+            w.on_key(KeyCode::Char(c));
+        }
+        w.on_key(KeyCode::Enter);
+    }
+
+    #[test]
+    fn steps_keep_their_values_and_only_the_last_submits_them_all() {
+        let mut w = WizardWidget::new(
+            "New project",
+            vec![
+                step("Basics", vec![text_field("name", true)]),
+                step("Owner", vec![text_field("owner_email", false)]),
+            ],
+            Some("app projects create".into()),
+        );
+        assert_eq!(w.step().form.title, "New project — Basics");
+        assert_eq!(w.step().form.step, Some((0, 2)));
+
+        // Next on an empty required field stays on the step
+        w.on_key(KeyCode::Down);
+        assert!(w.on_key(KeyCode::Enter).is_empty());
+        assert_eq!(w.current, 0);
+        w.on_key(KeyCode::Up);
+        type_text(&mut w, "demo");
+        w.on_key(KeyCode::Down);
+        assert!(w.on_key(KeyCode::Enter).is_empty());
+        assert_eq!(w.current, 1);
+        assert!(w.unsaved());
+
+        // Back and forth keeps the first step's value
+        w.on_key(KeyCode::Down);
+        w.on_key(KeyCode::Right);
+        w.on_key(KeyCode::Enter);
+        assert_eq!(w.current, 0);
+        assert_eq!(
+            w.step().form.fields[0].value,
+            FieldValue::Text("demo".into())
+        );
+        w.on_key(KeyCode::Left);
+        w.on_key(KeyCode::Enter);
+        assert_eq!(w.current, 1);
+
+        // The cursor is still on Back: Up twice reaches the field
+        w.on_key(KeyCode::Up);
+        w.on_key(KeyCode::Up);
+        type_text(&mut w, "a@b.c");
+        w.on_key(KeyCode::Down);
+        let effects = w.on_key(KeyCode::Enter);
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::SubmitForm { cmdline, .. }]
+                if cmdline == "app projects create --name demo --owner-email a@b.c"
+        ));
+        w.saved();
+        assert!(!w.unsaved());
+    }
+}
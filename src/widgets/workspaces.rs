@@ -0,0 +1,41 @@
+use crate::ui::AppState;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+// Workspace switcher ('W'): config roots from the entry config's `workspaces:`
+pub fn draw_workspaces(f: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, ws) in state.workspaces.iter().enumerate() {
+        let active = i == state.workspace_index;
+        // Watchdog sessions keep running while their workspace is in the background
+        let sessions = if active {
+            state.watchdog_sessions.len()
+        } else {
+            state.parked_sessions.get(&ws.dir).map_or(0, |s| s.len())
+        };
+        let mut spans = vec![
+            Span::raw(if i == state.workspace_selected {
+                "▶ "
+            } else {
+                "  "
+            }),
+            Span::raw(if active { "● " } else { "○ " }),
+            Span::raw(ws.title.clone()),
+            Span::styled(format!("  {}", ws.dir.display()), theme.text_muted()),
+        ];
+        if sessions > 0 {
+            spans.push(Span::styled(
+                format!("  ({sessions} watchdog session(s))"),
+                theme.text_success(),
+            ));
+        }
+        let mut line = Line::from(spans);
+        if i == state.workspace_selected {
+            line = line.style(theme.list_cursor_style());
+        }
+        lines.push(line);
+    }
+    let block = crate::widgets::chrome::panel_block("Workspaces", true);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}